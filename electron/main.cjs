@@ -121,6 +121,107 @@ function initStore() {
   console.log("[Store] Initialized successfully");
 }
 
+// ============================================
+// Path Authorization
+// ============================================
+
+const { createPathAccess } = require("./pathAccess.cjs");
+
+// Folders picked in a dialog (imports, relinking, backup folders), remembered
+// so songs imported from them stay readable after a restart
+const AUTHORIZED_FOLDERS_KEY = "authorizedFolders";
+
+// Set on upgrade until the renderer has reported the folders of its songs
+const SONG_FOLDER_MIGRATION_KEY = "songFolderMigrationPending";
+
+// Only the main process writes these, so the renderer can't widen its own access
+const PROTECTED_STORE_KEYS = new Set([
+  "musicFolders",
+  AUTHORIZED_FOLDERS_KEY,
+  SONG_FOLDER_MIGRATION_KEY,
+]);
+
+// File IPC only reaches library folders, picked folders, and files the user
+// opened or chose a save location for (see pathAccess.cjs)
+const pathAccess = createPathAccess({
+  getFolders: () =>
+    store
+      ? [
+          ...store.get("musicFolders", []),
+          ...store.get(AUTHORIZED_FOLDERS_KEY, []),
+        ]
+      : [],
+});
+
+// Remember folders the user picked, skipping those already covered
+function authorizeFolders(folderPaths) {
+  if (!store) return;
+  const folders = store.get(AUTHORIZED_FOLDERS_KEY, []);
+  const known = folders.length;
+  for (const folderPath of folderPaths) {
+    if (!pathAccess.isAllowed(folderPath) && !folders.includes(folderPath)) {
+      folders.push(folderPath);
+    }
+  }
+  if (folders.length > known) store.set(AUTHORIZED_FOLDERS_KEY, folders);
+}
+
+function authorizeFolder(folderPath) {
+  authorizeFolders([folderPath]);
+}
+
+// Older versions only kept the last imported folder, in the renderer's
+// `musicFolderPath` setting; carry it over once so its songs keep playing.
+// Songs from earlier imports are only known to the renderer, which reports
+// their folders once through library:migrateSongFolders
+function migrateAuthorizedFolders() {
+  if (store.get(AUTHORIZED_FOLDERS_KEY) !== undefined) return;
+  const storedFolder = store.get("musicFolderPath");
+  store.set(
+    AUTHORIZED_FOLDERS_KEY,
+    typeof storedFolder === "string" ? [storedFolder] : [],
+  );
+  store.set(SONG_FOLDER_MIGRATION_KEY, true);
+}
+
+// `{ error, code }` for a rejected path (codes in pathAccess.cjs)
+function pathErrorResult(error) {
+  return { error: error.message, code: error.code };
+}
+
+// Split a batch into the items the renderer may touch and `{ path, error, code }`
+// results for the rest, which then fail like any other file in the batch.
+// Allowed entries are `{ item, path, resolved }`: do I/O on `resolved` only,
+// so a symlink swapped in after the check isn't followed
+function partitionAllowed(items, getPath = (item) => item) {
+  const allowed = [];
+  const rejected = [];
+  for (const item of items) {
+    const requested = getPath(item);
+    try {
+      const resolved = pathAccess.resolve(requested);
+      allowed.push({ item, path: requested, resolved });
+    } catch (error) {
+      rejected.push({ path: requested, ...pathErrorResult(error) });
+    }
+  }
+  return { allowed, rejected };
+}
+
+// Report results for canonical paths under the paths the renderer sent
+function withRequestedPaths(results, allowed) {
+  const requestedByResolved = new Map();
+  for (const { path: requested, resolved } of allowed) {
+    const paths = requestedByResolved.get(resolved) ?? [];
+    paths.push(requested);
+    requestedByResolved.set(resolved, paths);
+  }
+  return results.map((result) => {
+    const requested = requestedByResolved.get(result.path)?.shift();
+    return requested === undefined ? result : { ...result, path: requested };
+  });
+}
+
 let mainWindow;
 let tray = null;
 let currentPlaybackState = {
@@ -129,11 +230,23 @@ let currentPlaybackState = {
 };
 
 // ============================================
-// Audio Transcoding (FFmpeg)
+// Audio Transcoding (vinyl-media / FFmpeg)
 // ============================================
 
 // Cache directory for transcoded audio files
 const TRANSCODE_CACHE_DIR = path.join(app.getPath("userData"), "transcode-cache");
+const TRANSCODE_CACHE_INDEX = path.join(TRANSCODE_CACHE_DIR, "index.json");
+
+// Transcodes are cached as FLAC: lossless, about half the size of WAV, and
+// readable while they are still being written
+const TRANSCODE_MIME_TYPE = "audio/flac";
+
+// Size limit for the cache, least recently played files are evicted first
+const TRANSCODE_CACHE_MAX_SIZE_KEY = "transcodeCacheMaxSize";
+const DEFAULT_TRANSCODE_CACHE_MAX_SIZE = 2 * 1024 * 1024 * 1024;
+
+// Last-played times are only persisted this often, playback reads in chunks
+const TRANSCODE_TOUCH_INTERVAL = 60 * 1000;
 
 // Formats that need transcoding on Linux (Chromium doesn't support these natively)
 const FORMATS_NEEDING_TRANSCODE = [".m4a", ".aac", ".wma", ".ape"];
@@ -149,21 +262,121 @@ function needsTranscoding(filePath) {
   return [".wma", ".ape"].includes(ext);
 }
 
-// Get cache path for a file
-function getTranscodeCachePath(filePath) {
-  // Create a hash of the file path for the cache filename
+// Bumped when cached files should be redone (2: no longer cut to 16 bits)
+const TRANSCODE_CACHE_VERSION = 2;
+
+// Cache file name for a source, which changes whenever the file is modified
+function getTranscodeCacheKey(filePath, stats) {
   const crypto = require("crypto");
-  const hash = crypto.createHash("md5").update(filePath).digest("hex");
-  const ext = ".wav"; // Transcode to WAV for best compatibility
-  return path.join(TRANSCODE_CACHE_DIR, `${hash}${ext}`);
+  const hash = crypto
+    .createHash("md5")
+    .update(
+      `${filePath}\0${stats.size}\0${stats.mtimeMs}\0${TRANSCODE_CACHE_VERSION}`
+    )
+    .digest("hex");
+  return `${hash}.flac`;
 }
 
-// Ensure cache directory exists
-function ensureTranscodeCacheDir() {
-  if (!fs.existsSync(TRANSCODE_CACHE_DIR)) {
+// Cache index: file name -> { source, size, lastAccessed }
+let transcodeCacheEntries = null;
+// Running transcodes: file name -> job
+const transcodeJobs = new Map();
+
+function saveTranscodeCacheIndex() {
+  try {
     fs.mkdirSync(TRANSCODE_CACHE_DIR, { recursive: true });
-    console.log("[Transcode] Created cache directory:", TRANSCODE_CACHE_DIR);
+    fs.writeFileSync(TRANSCODE_CACHE_INDEX, JSON.stringify(transcodeCacheEntries));
+  } catch (e) {
+    console.error("[Transcode] Error saving cache index:", e);
+  }
+}
+
+// Load the index, dropping entries whose files are gone and files it doesn't know about
+function loadTranscodeCache() {
+  if (transcodeCacheEntries !== null) return;
+
+  try {
+    transcodeCacheEntries = JSON.parse(fs.readFileSync(TRANSCODE_CACHE_INDEX, "utf-8"));
+  } catch {
+    transcodeCacheEntries = {};
+  }
+
+  for (const key of Object.keys(transcodeCacheEntries)) {
+    if (!fs.existsSync(path.join(TRANSCODE_CACHE_DIR, key))) {
+      delete transcodeCacheEntries[key];
+    }
+  }
+
+  // Unfinished transcodes and the WAV files older versions left behind
+  if (fs.existsSync(TRANSCODE_CACHE_DIR)) {
+    for (const name of fs.readdirSync(TRANSCODE_CACHE_DIR)) {
+      if (name !== "index.json" && !transcodeCacheEntries[name]) {
+        fs.rmSync(path.join(TRANSCODE_CACHE_DIR, name), { force: true });
+      }
+    }
+  }
+
+  evictTranscodeCache();
+}
+
+function getTranscodeCacheMaxSize() {
+  return store
+    ? store.get(TRANSCODE_CACHE_MAX_SIZE_KEY, DEFAULT_TRANSCODE_CACHE_MAX_SIZE)
+    : DEFAULT_TRANSCODE_CACHE_MAX_SIZE;
+}
+
+// Remove least recently played entries until the cache fits its size limit
+function evictTranscodeCache(keep) {
+  const maxSize = getTranscodeCacheMaxSize();
+  const entries = Object.entries(transcodeCacheEntries);
+  let total = entries.reduce((sum, [, entry]) => sum + entry.size, 0);
+  let evicted = 0;
+
+  entries.sort(([, a], [, b]) => a.lastAccessed - b.lastAccessed);
+  for (const [key, entry] of entries) {
+    if (total <= maxSize) break;
+    if (key === keep) continue;
+
+    fs.rmSync(path.join(TRANSCODE_CACHE_DIR, key), { force: true });
+    delete transcodeCacheEntries[key];
+    total -= entry.size;
+    evicted++;
+  }
+
+  if (evicted > 0) {
+    console.log(`[Transcode] Evicted ${evicted} cached file(s)`);
+    saveTranscodeCacheIndex();
+  }
+}
+
+function getTranscodeCacheInfo() {
+  loadTranscodeCache();
+  const entries = Object.values(transcodeCacheEntries)
+    .map((entry) => ({
+      sourcePath: entry.source,
+      size: entry.size,
+      lastAccessed: entry.lastAccessed,
+    }))
+    .sort((a, b) => b.lastAccessed - a.lastAccessed);
+
+  return {
+    directory: TRANSCODE_CACHE_DIR,
+    totalSize: entries.reduce((sum, entry) => sum + entry.size, 0),
+    maxSize: getTranscodeCacheMaxSize(),
+    activeTranscodes: transcodeJobs.size,
+    entries,
+  };
+}
+
+// Delete every finished transcode; running ones are left to complete
+function clearTranscodeCache() {
+  loadTranscodeCache();
+  for (const key of Object.keys(transcodeCacheEntries)) {
+    fs.rmSync(path.join(TRANSCODE_CACHE_DIR, key), { force: true });
   }
+  transcodeCacheEntries = {};
+  saveTranscodeCacheIndex();
+  console.log("[Transcode] Cache cleared");
 }
 
 // Check if FFmpeg is available
@@ -188,7 +401,7 @@ async function checkFFmpeg() {
   });
 }
 
-// Transcode audio file to WAV using FFmpeg
+// Transcode audio file to FLAC using FFmpeg
 function transcodeAudio(inputPath, outputPath) {
   return new Promise((resolve, reject) => {
     console.log("[Transcode] Starting:", inputPath);
@@ -197,9 +410,9 @@ function transcodeAudio(inputPath, outputPath) {
       "-i", inputPath,           // Input file
       "-y",                      // Overwrite output
       "-vn",                     // No video
-      "-acodec", "pcm_s16le",    // PCM 16-bit (WAV)
-      "-ar", "44100",            // Sample rate
-      "-ac", "2",                // Stereo
+      "-acodec", "flac",         // Lossless, written frame by frame
+      "-sample_fmt", "s32",      // Written as 24-bit, hi-res sources keep their depth
+      "-f", "flac",              // Output is named .part while it's written
       outputPath
     ]);
     
@@ -226,54 +439,527 @@ function transcodeAudio(inputPath, outputPath) {
   });
 }
 
-// Get audio file data, transcoding if necessary
-async function getAudioFileData(filePath) {
-  // Check if transcoding is needed
-  if (needsTranscoding(filePath)) {
-    const hasFFmpeg = await checkFFmpeg();
-    
-    if (!hasFFmpeg) {
-      // FFmpeg not available, try to read raw file anyway
-      console.warn("[Transcode] FFmpeg not available, trying raw file");
-      return { data: fs.readFileSync(filePath).buffer, transcoded: false };
-    }
-    
-    ensureTranscodeCacheDir();
-    const cachePath = getTranscodeCachePath(filePath);
-    
-    // Check if we have a cached transcode
-    if (fs.existsSync(cachePath)) {
-      // Verify cache is newer than source
-      const srcStat = fs.statSync(filePath);
-      const cacheStat = fs.statSync(cachePath);
-      
-      if (cacheStat.mtime >= srcStat.mtime) {
-        console.log("[Transcode] Using cached:", cachePath);
-        return { 
-          data: fs.readFileSync(cachePath).buffer, 
-          transcoded: true,
-          mimeType: "audio/wav"
-        };
+// Formats the vinyl-media helper decodes without FFmpeg
+// (there is no pure-Rust WMA decoder, that still needs FFmpeg)
+const NATIVE_DECODE_FORMATS = [".m4a", ".aac", ".mp4", ".ape"];
+
+// Decode audio file to FLAC using the bundled vinyl-media helper
+function decodeAudioNative(helperPath, inputPath, outputPath) {
+  return new Promise((resolve, reject) => {
+    console.log("[Transcode] Decoding natively:", inputPath);
+
+    const helper = spawn(helperPath, ["decode", inputPath, outputPath]);
+
+    let stderr = "";
+    helper.stderr.on("data", (data) => {
+      stderr += data.toString();
+    });
+
+    helper.on("error", (err) => {
+      reject(new Error(`Decoder not available: ${err.message}`));
+    });
+
+    helper.on("close", (code) => {
+      if (code === 0) {
+        console.log("[Transcode] Complete:", outputPath);
+        resolve(outputPath);
+      } else {
+        reject(new Error(stderr.trim() || `Decoder failed with code ${code}`));
       }
-    }
-    
-    // Transcode the file
+    });
+  });
+}
+
+// Run a transcode in the background, into a .part file that can be read while it grows
+function startTranscode(filePath, key) {
+  const cachePath = path.join(TRANSCODE_CACHE_DIR, key);
+  const job = {
+    partialPath: `${cachePath}.part`,
+    status: "running",
+    path: null,
+    error: null,
+  };
+
+  job.promise = (async () => {
+    const ext = path.extname(filePath).toLowerCase();
+    const helperPath = getMediaHelperPath();
+    let decoded = false;
+
     try {
-      await transcodeAudio(filePath, cachePath);
-      return { 
-        data: fs.readFileSync(cachePath).buffer, 
-        transcoded: true,
-        mimeType: "audio/wav"
+      if (helperPath && NATIVE_DECODE_FORMATS.includes(ext)) {
+        try {
+          await decodeAudioNative(helperPath, filePath, job.partialPath);
+          decoded = true;
+        } catch (err) {
+          if (!(await checkFFmpeg())) throw err;
+          console.warn("[Transcode] Native decode failed, trying FFmpeg:", err.message);
+        }
+      }
+      if (!decoded) {
+        await transcodeAudio(filePath, job.partialPath);
+      }
+
+      fs.renameSync(job.partialPath, cachePath);
+      job.status = "done";
+      job.path = cachePath;
+
+      // Older transcodes of the same file are stale now
+      for (const [staleKey, entry] of Object.entries(transcodeCacheEntries)) {
+        if (entry.source === filePath) {
+          fs.rmSync(path.join(TRANSCODE_CACHE_DIR, staleKey), { force: true });
+          delete transcodeCacheEntries[staleKey];
+        }
+      }
+      transcodeCacheEntries[key] = {
+        source: filePath,
+        size: fs.statSync(cachePath).size,
+        lastAccessed: Date.now(),
       };
+      saveTranscodeCacheIndex();
+      evictTranscodeCache(key);
     } catch (err) {
       console.error("[Transcode] Error:", err.message);
-      // Fall back to raw file
-      return { data: fs.readFileSync(filePath).buffer, transcoded: false, error: err.message };
+      fs.rmSync(job.partialPath, { force: true });
+      job.status = "failed";
+      job.error = err.message;
+    } finally {
+      transcodeJobs.delete(key);
     }
-  }
-  
+  })();
+
+  transcodeJobs.set(key, job);
+  return job;
+}
+
+// Resolve where to read a file from, starting a transcode if there's no cached copy.
+// Returns { path, transcoded, mimeType? }, { job } while transcoding, or { error }.
+async function openPlayable(filePath) {
   // No transcoding needed
-  return { data: fs.readFileSync(filePath).buffer, transcoded: false };
+  if (!needsTranscoding(filePath)) {
+    return { path: filePath, transcoded: false };
+  }
+
+  loadTranscodeCache();
+  const key = getTranscodeCacheKey(filePath, fs.statSync(filePath));
+
+  const running = transcodeJobs.get(key);
+  if (running) {
+    return { job: running };
+  }
+
+  const cachePath = path.join(TRANSCODE_CACHE_DIR, key);
+  const entry = transcodeCacheEntries[key];
+  if (entry && fs.existsSync(cachePath)) {
+    const now = Date.now();
+    if (now - entry.lastAccessed > TRANSCODE_TOUCH_INTERVAL) {
+      entry.lastAccessed = now;
+      saveTranscodeCacheIndex();
+    }
+    return { path: cachePath, transcoded: true, mimeType: TRANSCODE_MIME_TYPE };
+  }
+
+  const ext = path.extname(filePath).toLowerCase();
+  const canDecodeNatively = getMediaHelperPath() && NATIVE_DECODE_FORMATS.includes(ext);
+  if (!canDecodeNatively && !(await checkFFmpeg())) {
+    // Raw bytes can't be played either, so report why instead
+    return {
+      transcoded: false,
+      error: `Playing ${ext} files requires FFmpeg, which was not found on this system`,
+    };
+  }
+
+  // Another request may have started it while FFmpeg was being checked
+  const started = transcodeJobs.get(key);
+  if (started) {
+    return { job: started };
+  }
+
+  fs.mkdirSync(TRANSCODE_CACHE_DIR, { recursive: true });
+  return { job: startTranscode(filePath, key) };
+}
+
+// Resolve the file that should actually be played, waiting for any transcode to finish
+async function preparePlayablePath(filePath) {
+  const playable = await openPlayable(filePath);
+  if (!playable.job) {
+    if (playable.transcoded) {
+      console.log("[Transcode] Using cached:", playable.path);
+    }
+    return playable;
+  }
+
+  const { job } = playable;
+  await job.promise;
+  if (job.status !== "done") {
+    return { transcoded: false, error: job.error };
+  }
+  return { path: job.path, transcoded: true, mimeType: TRANSCODE_MIME_TYPE };
+}
+
+// Get audio file data, transcoding if necessary
+async function getAudioFileData(filePath) {
+  const prepared = await preparePlayablePath(filePath);
+  if (prepared.error) {
+    return prepared;
+  }
+  return {
+    data: fs.readFileSync(prepared.path).buffer,
+    transcoded: prepared.transcoded,
+    mimeType: prepared.mimeType,
+  };
+}
+
+// ============================================
+// Media Streaming Protocol (vinyl-media://)
+// ============================================
+
+const MEDIA_SCHEME = "vinyl-media";
+// Open-ended range requests are answered in chunks of this size
+const MEDIA_CHUNK_SIZE = 1024 * 1024;
+
+const MEDIA_MIME_TYPES = {
+  ".mp3": "audio/mpeg",
+  ".wav": "audio/wav",
+  ".ogg": "audio/ogg",
+  ".oga": "audio/ogg",
+  ".opus": "audio/ogg",
+  ".flac": "audio/flac",
+  ".m4a": "audio/mp4",
+  ".mp4": "audio/mp4",
+  ".aac": "audio/aac",
+  ".aiff": "audio/aiff",
+  ".webm": "audio/webm",
+};
+
+// Must run before the app is ready so <audio> can stream from the scheme
+electron.protocol.registerSchemesAsPrivileged([
+  {
+    scheme: MEDIA_SCHEME,
+    privileges: {
+      standard: true,
+      secure: true,
+      supportFetchAPI: true,
+      stream: true,
+      corsEnabled: true,
+    },
+  },
+]);
+
+// Parse a single "bytes=start-end" range against a file size.
+// Returns null for a full response and { unsatisfiable: true } when out of bounds.
+function parseByteRange(header, size) {
+  const match = /^bytes=(\d*)-(\d*)$/.exec((header || "").trim());
+  if (!match || (match[1] === "" && match[2] === "")) return null;
+
+  let start;
+  let end;
+  if (match[1] === "") {
+    // Suffix range: the last N bytes
+    const suffix = Number(match[2]);
+    if (suffix === 0) return { unsatisfiable: true };
+    start = Math.max(0, size - suffix);
+    end = size - 1;
+  } else {
+    start = Number(match[1]);
+    end = match[2] === ""
+      ? Math.min(start + MEDIA_CHUNK_SIZE, size) - 1
+      : Math.min(Number(match[2]), size - 1);
+  }
+
+  if (start >= size || end < start) return { unsatisfiable: true };
+  return { start, end };
+}
+
+// Start and optional end of a range against a file that is still growing.
+// No header means from the start; suffix ranges can't be answered yet (null).
+function parseOpenRange(header) {
+  if (!header) return { start: 0, end: null };
+  const match = /^bytes=(\d+)-(\d*)$/.exec(header.trim());
+  if (!match) return null;
+
+  const start = Number(match[1]);
+  const end = match[2] === "" ? null : Number(match[2]);
+  return end !== null && end < start ? null : { start, end };
+}
+
+function serveMediaFile(filePath, contentType, request) {
+  const { Readable } = require("stream");
+  const size = fs.statSync(filePath).size;
+  const range = parseByteRange(request.headers.get("Range"), size);
+
+  if (range && range.unsatisfiable) {
+    return new Response(null, {
+      status: 416,
+      headers: { "Content-Range": `bytes */${size}` },
+    });
+  }
+
+  const start = range ? range.start : 0;
+  const end = range ? range.end : size - 1;
+  const body = size === 0
+    ? null
+    : Readable.toWeb(fs.createReadStream(filePath, { start, end }));
+  const headers = {
+    "Content-Type": contentType,
+    "Accept-Ranges": "bytes",
+    "Content-Length": String(size === 0 ? 0 : end - start + 1),
+  };
+  if (range) {
+    headers["Content-Range"] = `bytes ${start}-${end}/${size}`;
+  }
+
+  return new Response(body, { status: range ? 206 : 200, headers });
+}
+
+// Serve a transcode while it's being written: each response holds the bytes
+// on disk so far, with an unknown total length until the job finishes
+async function serveTranscodingFile(job, request) {
+  const range = parseOpenRange(request.headers.get("Range"));
+  if (!range) {
+    return new Response("Suffix ranges need the final length", { status: 416 });
+  }
+
+  for (;;) {
+    if (job.status === "done") {
+      return serveMediaFile(job.path, TRANSCODE_MIME_TYPE, request);
+    }
+    if (job.status === "failed") {
+      return new Response(job.error, { status: 500 });
+    }
+
+    let available = 0;
+    try {
+      available = fs.statSync(job.partialPath).size;
+    } catch {
+      // Not created yet, or just renamed into place
+    }
+
+    if (available > range.start) {
+      const last = Math.min(
+        available - 1,
+        range.start + MEDIA_CHUNK_SIZE - 1,
+        range.end ?? Infinity,
+      );
+      const buffer = Buffer.alloc(last - range.start + 1);
+      try {
+        const handle = await fs.promises.open(job.partialPath, "r");
+        try {
+          await handle.read(buffer, 0, buffer.length, range.start);
+        } finally {
+          await handle.close();
+        }
+      } catch (err) {
+        // Finished between the size check and the read
+        if (job.status === "done") continue;
+        throw err;
+      }
+
+      return new Response(buffer, {
+        status: 206,
+        headers: {
+          "Content-Type": TRANSCODE_MIME_TYPE,
+          "Accept-Ranges": "bytes",
+          "Content-Length": String(buffer.length),
+          "Content-Range": `bytes ${range.start}-${last}/*`,
+        },
+      });
+    }
+
+    await new Promise((resolve) => setTimeout(resolve, 100));
+  }
+}
+
+// The scheme is its own origin: without these, Web Audio gets silence from
+// <audio> elements streaming it
+const MEDIA_CORS_HEADERS = {
+  "Access-Control-Allow-Origin": "*",
+  "Access-Control-Expose-Headers":
+    "Accept-Ranges, Content-Length, Content-Range",
+};
+
+function withCorsHeaders(response) {
+  for (const [name, value] of Object.entries(MEDIA_CORS_HEADERS)) {
+    response.headers.set(name, value);
+  }
+  return response;
+}
+
+async function handleMediaRequest(request) {
+  // Preflight for range requests made with fetch
+  if (request.method === "OPTIONS") {
+    return new Response(null, {
+      status: 204,
+      headers: {
+        "Access-Control-Allow-Methods": "GET, HEAD, OPTIONS",
+        "Access-Control-Allow-Headers": "Range",
+      },
+    });
+  }
+
+  let filePath;
+  try {
+    // Serve the canonical path, a symlink swapped in after the check is ignored
+    filePath = pathAccess.resolve(
+      decodeURIComponent(new URL(request.url).pathname.slice(1)),
+    );
+  } catch {
+    return new Response("Not in your music folders", { status: 403 });
+  }
+  if (!isAudioFile(filePath)) {
+    return new Response("Not an audio file", { status: 403 });
+  }
+  if (!fs.existsSync(filePath)) {
+    return new Response("File not found", { status: 404 });
+  }
+
+  const playable = await openPlayable(filePath);
+  if (playable.error) {
+    return new Response(playable.error, { status: 404 });
+  }
+  if (playable.job) {
+    return serveTranscodingFile(playable.job, request);
+  }
+
+  const contentType =
+    playable.mimeType ||
+    MEDIA_MIME_TYPES[path.extname(filePath).toLowerCase()] ||
+    "application/octet-stream";
+  return serveMediaFile(playable.path, contentType, request);
+}
+
+// ============================================
+// Native Metadata Reader (vinyl-media sidecar)
+// ============================================
+
+// Built from src-tauri/crates/vinyl-media (`bun run media:build`)
+const MEDIA_HELPER_NAME =
+  process.platform === "win32" ? "vinyl-media.exe" : "vinyl-media";
+
+function getMediaHelperPath() {
+  const candidates = isPackaged
+    ? [getResourcePath(path.join("bin", MEDIA_HELPER_NAME))]
+    : [
+        path.join(__dirname, "../src-tauri/target/release", MEDIA_HELPER_NAME),
+        path.join(__dirname, "../src-tauri/target/debug", MEDIA_HELPER_NAME),
+      ];
+  return candidates.find((candidate) => fs.existsSync(candidate)) || null;
+}
+
+// One line of helper output, or null (logged) when it isn't valid JSON
+function parseHelperLine(command, line) {
+  try {
+    return JSON.parse(line);
+  } catch {
+    console.error(
+      `[MediaHelper] ${command} wrote a bad line:`,
+      line.slice(0, 200),
+    );
+    return null;
+  }
+}
+
+// Run the helper with `input` on stdin and parse its JSON-lines output
+// Resolves to null when the helper isn't available or fails
+// `onMessage` receives each JSON line as it arrives, for commands that stream progress
+// Lines that don't parse are skipped; a bad final line fails the whole run,
+// since it is usually a cut-off result
+function runMediaHelper(args, input, onMessage) {
+  const helperPath = getMediaHelperPath();
+  if (!helperPath) {
+    return Promise.resolve(null);
+  }
+
+  return new Promise((resolve) => {
+    const helper = spawn(helperPath, args);
+    const results = [];
+    let pending = "";
+    let stderr = "";
+
+    helper.stdout.setEncoding("utf-8");
+    helper.stdout.on("data", (data) => {
+      const lines = (pending + data).split("\n");
+      pending = lines.pop();
+      for (const line of lines) {
+        if (!line.trim()) continue;
+        const message = parseHelperLine(args[0], line);
+        if (message === null) continue;
+        results.push(message);
+        onMessage?.(message);
+      }
+    });
+    helper.stderr.on("data", (data) => {
+      stderr += data.toString();
+    });
+
+    helper.on("error", (err) => {
+      console.error("[MediaHelper] Spawn error:", err.message);
+      resolve(null);
+    });
+
+    helper.on("close", (code) => {
+      if (code !== 0) {
+        console.error(
+          `[MediaHelper] ${args[0]} failed with code:`,
+          code,
+          stderr.slice(-500),
+        );
+        resolve(null);
+        return;
+      }
+
+      if (pending.trim()) {
+        const message = parseHelperLine(args[0], pending);
+        if (message === null) {
+          resolve(null);
+          return;
+        }
+        results.push(message);
+      }
+      resolve(results);
+    });
+
+    helper.stdin.end(input);
+  });
+}
+
+// Read tags for many files in one helper process
+// Resolves to null when the helper isn't available (renderer falls back to music-metadata)
+function readMetadataNative(filePaths, includeCoverArt = true) {
+  const args = ["tags"];
+  if (!includeCoverArt) args.push("--no-cover-art");
+  return runMediaHelper(args, filePaths.join("\n"));
+}
+
+// Measure loudness (ReplayGain) and the audible range of each file
+// `albums` is an array of path arrays, so album gain covers each album's tracks
+function analyzeLoudnessNative(albums) {
+  return runMediaHelper(["loudness"], JSON.stringify(albums));
+}
+
+// Originals of files the tag editor rewrote
+const TAG_BACKUP_DIR = path.join(app.getPath("userData"), "tag-backups");
+
+// Write edited tags back to files, keeping a backup of each original
+// `writes` is an array of { path, edit }; resolves to null when the helper isn't available
+function writeTagsNative(writes) {
+  return runMediaHelper(["write-tags", TAG_BACKUP_DIR], JSON.stringify(writes));
+}
+
+// Size, mtime and partial hash of every file seen by the last scan
+const SCAN_INDEX_PATH = path.join(app.getPath("userData"), "scan-index.json");
+
+// Rescan folders against the saved index, off the main thread
+// Resolves to { added, changed, moved, removed, unchanged, totalCount, folderStats },
+// or null when the helper isn't available
+async function scanFoldersNative(folders, onProgress) {
+  const messages = await runMediaHelper(
+    ["scan", SCAN_INDEX_PATH],
+    JSON.stringify(folders),
+    (message) => {
+      if (message.progress) onProgress(message.progress);
+    },
+  );
+  return messages?.find((message) => message.report)?.report ?? null;
 }
 
 // Supported audio extensions
@@ -296,6 +982,15 @@ function isAudioFile(filePath) {
   return AUDIO_EXTENSIONS.includes(ext);
 }
 
+// Scan the canonical `resolved` folder, reporting file paths under
+// `requested`, the form the library keeps them in
+function scanResolvedFolder(resolved, requested) {
+  return scanMusicFolder(resolved).map((file) => ({
+    ...file,
+    path: path.join(requested, path.relative(resolved, file.path)),
+  }));
+}
+
 // Recursively scan directory for audio files
 function scanMusicFolder(folderPath, rootPath = folderPath) {
   const results = [];
@@ -541,7 +1236,7 @@ function createWindow() {
           "script-src 'self' 'unsafe-inline' 'unsafe-eval' http://localhost:*; " +
           "style-src 'self' 'unsafe-inline' http://localhost:*; " +
           "img-src 'self' data: blob: http://localhost:*; " +
-          "media-src 'self' blob: file:; " +
+          "media-src 'self' blob: file: vinyl-media:; " +
           "connect-src 'self' http://localhost:* ws://localhost:*; " +
           "font-src 'self' data:"
         : // Production CSP - stricter
@@ -549,7 +1244,7 @@ function createWindow() {
           "script-src 'self'; " +
           "style-src 'self' 'unsafe-inline'; " +
           "img-src 'self' data: blob:; " +
-          "media-src 'self' blob: file:; " +
+          "media-src 'self' blob: file: vinyl-media:; " +
           "connect-src 'self'; " +
           "font-src 'self' data:";
 
@@ -618,34 +1313,120 @@ function createWindow() {
 // ============================================
 
 // Open folder picker dialog
-ipcMain.handle("dialog:openFolder", async () => {
+ipcMain.handle("dialog:openFolder", async (event, options = {}) => {
   const result = await dialog.showOpenDialog(mainWindow, {
     properties: ["openDirectory"],
-    title: "Select your music folder",
+    title:
+      typeof options.title === "string"
+        ? options.title
+        : "Select your music folder",
+    defaultPath:
+      typeof options.defaultPath === "string" ? options.defaultPath : undefined,
   });
 
   if (result.canceled) {
     return null;
   }
 
+  authorizeFolder(result.filePaths[0]);
   return result.filePaths[0];
 });
 
+// Pick a playlist file to import, returning its path and raw bytes
+ipcMain.handle("dialog:openPlaylist", async () => {
+  const result = await dialog.showOpenDialog(mainWindow, {
+    properties: ["openFile"],
+    title: "Import playlist",
+    filters: [{ name: "Playlists", extensions: ["m3u", "m3u8", "pls", "xspf"] }],
+  });
+
+  if (result.canceled || result.filePaths.length === 0) {
+    return null;
+  }
+
+  const filePath = result.filePaths[0];
+  const data = await fs.promises.readFile(filePath);
+  return { path: filePath, data: new Uint8Array(data) };
+});
+
+// Pick where to save an exported playlist
+ipcMain.handle("dialog:savePlaylist", async (event, defaultName) => {
+  const extension = path.extname(defaultName).slice(1) || "m3u8";
+  const result = await dialog.showSaveDialog(mainWindow, {
+    title: "Export playlist",
+    defaultPath: defaultName,
+    filters: [{ name: "Playlist", extensions: [extension] }],
+  });
+
+  if (result.canceled || !result.filePath) {
+    return null;
+  }
+
+  pathAccess.allowFile(result.filePath);
+  return result.filePath;
+});
+
+// Pick a library backup to restore
+ipcMain.handle("dialog:openBackup", async () => {
+  const result = await dialog.showOpenDialog(mainWindow, {
+    properties: ["openFile"],
+    title: "Restore backup",
+    filters: [{ name: "Vinyl backup", extensions: ["json"] }],
+  });
+
+  if (result.canceled || result.filePaths.length === 0) {
+    return null;
+  }
+
+  const filePath = result.filePaths[0];
+  const data = await fs.promises.readFile(filePath);
+  return { path: filePath, data: new Uint8Array(data) };
+});
+
+// Pick where to save a library backup
+ipcMain.handle("dialog:saveBackup", async (event, defaultName) => {
+  const result = await dialog.showSaveDialog(mainWindow, {
+    title: "Back up library",
+    defaultPath: defaultName,
+    filters: [{ name: "Vinyl backup", extensions: ["json"] }],
+  });
+
+  if (result.canceled || !result.filePath) {
+    return null;
+  }
+
+  pathAccess.allowFile(result.filePath);
+  return result.filePath;
+});
+
 // Scan a folder for music files
 ipcMain.handle("fs:scanMusicFolder", async (event, folderPath) => {
-  if (!fs.existsSync(folderPath)) {
+  let resolved;
+  try {
+    resolved = pathAccess.resolve(folderPath);
+  } catch (error) {
+    return pathErrorResult(error);
+  }
+  if (!fs.existsSync(resolved)) {
     return { error: "Folder does not exist" };
   }
 
-  const files = scanMusicFolder(folderPath);
+  const files = scanResolvedFolder(resolved, folderPath);
   return { files };
 });
 
 // Read a file and return as buffer (for audio data)
 // Automatically transcodes unsupported formats using FFmpeg
 ipcMain.handle("fs:readFile", async (event, filePath) => {
+  let resolved;
   try {
-    const result = await getAudioFileData(filePath);
+    resolved = pathAccess.resolve(filePath);
+  } catch (error) {
+    return pathErrorResult(error);
+  }
+
+  try {
+    const result = await getAudioFileData(resolved);
     return { 
       data: result.data,
       transcoded: result.transcoded,
@@ -659,13 +1440,50 @@ ipcMain.handle("fs:readFile", async (event, filePath) => {
 
 // Check if a file exists
 ipcMain.handle("fs:fileExists", async (event, filePath) => {
-  return fs.existsSync(filePath);
+  try {
+    return fs.existsSync(pathAccess.resolve(filePath));
+  } catch {
+    return false;
+  }
 });
 
+// Write a text file (exported playlists), only where a save dialog was used
+ipcMain.handle("fs:writeTextFile", async (event, filePath, content) => {
+  try {
+    const resolved = pathAccess.resolveFile(filePath);
+    await fs.promises.writeFile(resolved, content, "utf8");
+    return true;
+  } catch (error) {
+    console.error("[FS] Failed to write file:", error.message);
+    return false;
+  }
+});
+
+// Write a scheduled backup into a folder the user picked. Only backup file
+// names are accepted, so nothing else in the folder can be overwritten
+const BACKUP_FILE_NAME = /^vinyl-backup-[\d-]+\.json$/;
+
+ipcMain.handle(
+  "fs:writeBackupFile",
+  async (event, folderPath, fileName, content) => {
+    try {
+      if (typeof fileName !== "string" || !BACKUP_FILE_NAME.test(fileName)) {
+        throw new Error(`Not a backup file name: ${fileName}`);
+      }
+      const folder = pathAccess.resolve(folderPath);
+      await fs.promises.writeFile(path.join(folder, fileName), content, "utf8");
+      return true;
+    } catch (error) {
+      console.error("[FS] Failed to write backup:", error.message);
+      return false;
+    }
+  },
+);
+
 // Get file stats
 ipcMain.handle("fs:getStats", async (event, filePath) => {
   try {
-    const stats = fs.statSync(filePath);
+    const stats = fs.statSync(pathAccess.resolve(filePath));
     return {
       size: stats.size,
       mtime: stats.mtime.toISOString(),
@@ -677,6 +1495,42 @@ ipcMain.handle("fs:getStats", async (event, filePath) => {
   }
 });
 
+// Stats for many files in one call; null entries are missing or unreadable
+ipcMain.handle("fs:getStatsBatch", async (event, filePaths) => {
+  return Promise.all(
+    filePaths.map(async (filePath) => {
+      try {
+        const stats = await fs.promises.stat(pathAccess.resolve(filePath));
+        return {
+          size: stats.size,
+          mtime: stats.mtime.toISOString(),
+          isFile: stats.isFile(),
+          isDirectory: stats.isDirectory(),
+        };
+      } catch {
+        return null;
+      }
+    }),
+  );
+});
+
+// Transcode cache
+ipcMain.handle("transcodeCache:getInfo", async () => {
+  return getTranscodeCacheInfo();
+});
+
+ipcMain.handle("transcodeCache:clear", async () => {
+  clearTranscodeCache();
+  return getTranscodeCacheInfo();
+});
+
+ipcMain.handle("transcodeCache:setMaxSize", async (event, maxSize) => {
+  store.set(TRANSCODE_CACHE_MAX_SIZE_KEY, maxSize);
+  loadTranscodeCache();
+  evictTranscodeCache();
+  return getTranscodeCacheInfo();
+});
+
 // Store operations
 ipcMain.handle("store:get", async (event, key) => {
   if (!store) return undefined;
@@ -684,13 +1538,13 @@ ipcMain.handle("store:get", async (event, key) => {
 });
 
 ipcMain.handle("store:set", async (event, key, value) => {
-  if (!store) return false;
+  if (!store || PROTECTED_STORE_KEYS.has(key)) return false;
   store.set(key, value);
   return true;
 });
 
 ipcMain.handle("store:delete", async (event, key) => {
-  if (!store) return false;
+  if (!store || PROTECTED_STORE_KEYS.has(key)) return false;
   store.delete(key);
   return true;
 });
@@ -733,8 +1587,16 @@ ipcMain.handle("library:getFolders", async () => {
 ipcMain.handle("library:addFolder", async (event, folderPath) => {
   if (!store) return { error: "Store not initialized" };
 
+  // Only folders picked in the folder dialog can join the library
+  let resolved;
+  try {
+    resolved = pathAccess.resolve(folderPath);
+  } catch (error) {
+    return pathErrorResult(error);
+  }
+
   // Verify folder exists
-  if (!fs.existsSync(folderPath)) {
+  if (!fs.existsSync(resolved)) {
     return { error: "Folder does not exist" };
   }
 
@@ -764,17 +1626,29 @@ ipcMain.handle("library:removeFolder", async (event, folderPath) => {
 
   folders.splice(index, 1);
   store.set("musicFolders", folders);
+  // The folder was picked when it was added, forget that too
+  const authorized = store.get(AUTHORIZED_FOLDERS_KEY, []);
+  store.set(
+    AUTHORIZED_FOLDERS_KEY,
+    authorized.filter((folder) => folder !== folderPath),
+  );
 
   return { success: true, folders };
 });
 
 // Scan a folder and count files (for progress reporting)
 ipcMain.handle("library:scanFolderWithProgress", async (event, folderPath) => {
-  if (!fs.existsSync(folderPath)) {
+  let resolved;
+  try {
+    resolved = pathAccess.resolve(folderPath);
+  } catch (error) {
+    return { ...pathErrorResult(error), files: [] };
+  }
+  if (!fs.existsSync(resolved)) {
     return { error: "Folder does not exist", files: [] };
   }
 
-  const files = scanMusicFolder(folderPath);
+  const files = scanResolvedFolder(resolved, folderPath);
   return {
     files,
     totalCount: files.length,
@@ -787,6 +1661,25 @@ ipcMain.handle("library:scanAllFolders", async () => {
   if (!store) return { error: "Store not initialized", files: [] };
 
   const folders = store.get("musicFolders", []);
+  const report = await scanFoldersNative(folders, (progress) => {
+    if (mainWindow && !mainWindow.isDestroyed()) {
+      mainWindow.webContents.send("library:scanProgress", progress);
+    }
+  });
+
+  if (report) {
+    return {
+      files: report.added,
+      changed: report.changed,
+      moved: report.moved,
+      removed: report.removed,
+      unchanged: report.unchanged,
+      totalCount: report.totalCount,
+      folderStats: report.folderStats,
+    };
+  }
+
+  // No helper: full walk, every file is reported and the renderer dedupes
   const allFiles = [];
   const folderStats = [];
 
@@ -815,13 +1708,110 @@ ipcMain.handle("library:scanAllFolders", async () => {
   };
 });
 
+// Authorize the folders of songs imported before file access was limited to
+// picked folders. Only the renderer knows them, so this is accepted once, on
+// the first start after the upgrade
+ipcMain.handle("library:migrateSongFolders", async (event, folders) => {
+  if (!store || !store.get(SONG_FOLDER_MIGRATION_KEY)) return false;
+  store.delete(SONG_FOLDER_MIGRATION_KEY);
+  if (!Array.isArray(folders)) return false;
+
+  authorizeFolders(
+    folders.filter((folder) => {
+      if (typeof folder !== "string" || !path.isAbsolute(folder)) return false;
+      try {
+        return fs.statSync(folder).isDirectory();
+      } catch {
+        return false;
+      }
+    }),
+  );
+  return true;
+});
+
+// Forget the scan index so the next scan reports every file as new
+ipcMain.handle("library:resetScanIndex", async () => {
+  await fs.promises.rm(SCAN_INDEX_PATH, { force: true });
+  return true;
+});
+
+// Drop deleted songs from the scan index, so the next scan imports them again
+ipcMain.handle("library:forgetScannedFiles", async (event, filePaths) => {
+  if (!Array.isArray(filePaths)) return false;
+  const paths = filePaths.filter((filePath) => typeof filePath === "string");
+  const messages = await runMediaHelper(
+    ["forget", SCAN_INDEX_PATH],
+    JSON.stringify(paths),
+  );
+  return messages !== null;
+});
+
+// Read tags and durations for a batch of files natively
+ipcMain.handle("library:readMetadata", async (event, filePaths, options = {}) => {
+  try {
+    const { allowed, rejected } = partitionAllowed(filePaths);
+    if (allowed.length === 0) return rejected;
+    const results = await readMetadataNative(
+      allowed.map((entry) => entry.resolved),
+      options.includeCoverArt !== false,
+    );
+    return results && [...withRequestedPaths(results, allowed), ...rejected];
+  } catch (error) {
+    console.error("[Metadata] Error:", error);
+    return null;
+  }
+});
+
+// Measure track and album loudness, plus where each track starts and stops
+ipcMain.handle("library:analyzeLoudness", async (event, albums) => {
+  try {
+    const allowed = [];
+    const rejected = [];
+    const allowedAlbums = albums
+      .map((album) => {
+        const partition = partitionAllowed(album);
+        allowed.push(...partition.allowed);
+        rejected.push(...partition.rejected);
+        return partition.allowed.map((entry) => entry.resolved);
+      })
+      .filter((album) => album.length > 0);
+    if (allowedAlbums.length === 0) return rejected;
+    const results = await analyzeLoudnessNative(allowedAlbums);
+    return results && [...withRequestedPaths(results, allowed), ...rejected];
+  } catch (error) {
+    console.error("[Loudness] Error:", error);
+    return null;
+  }
+});
+
+// Write tags edited in the tag editor back to the files
+ipcMain.handle("library:writeTags", async (event, writes) => {
+  try {
+    const { allowed, rejected } = partitionAllowed(
+      writes,
+      (write) => write.path,
+    );
+    if (allowed.length === 0) return rejected;
+    const results = await writeTagsNative(
+      allowed.map(({ item, resolved }) => ({ ...item, path: resolved })),
+    );
+    return results && [...withRequestedPaths(results, allowed), ...rejected];
+  } catch (error) {
+    console.error("[Tags] Error:", error);
+    return null;
+  }
+});
+
 // Show item in folder (file manager)
 ipcMain.handle("shell:showItemInFolder", async (event, filePath) => {
-  if (fs.existsSync(filePath)) {
-    shell.showItemInFolder(filePath);
+  try {
+    const resolved = pathAccess.resolve(filePath);
+    if (!fs.existsSync(resolved)) return false;
+    shell.showItemInFolder(resolved);
     return true;
+  } catch {
+    return false;
   }
-  return false;
 });
 
 // ============================================
@@ -866,74 +1856,200 @@ ipcMain.handle("tray:hide", async () => {
 let watcher = null;
 let chokidar = null;
 
-// Debounce map to prevent duplicate events
+// Debounce map to prevent duplicate events, keyed by "type:path"
 const pendingEvents = new Map();
 const DEBOUNCE_DELAY = 500; // ms
+// How long an add or unlink waits for its other half before it is sent alone
+const MOVE_WINDOW = 1500; // ms
 
-// Initialize chokidar (lazy load since it's ESM)
-async function initChokidar() {
-  if (!chokidar) {
-    try {
-      chokidar = await import("chokidar");
-      console.log("[Watcher] chokidar initialized successfully");
-    } catch (error) {
-      console.error("[Watcher] Failed to initialize chokidar:", error);
-      return null;
+// Size and inode of every watched file, so an unlink followed by an add of the
+// same file can be reported as a move and the song keeps its id
+const knownFiles = new Map();
+
+function getFileIdentity(filePath, stats) {
+  try {
+    const fileStats = stats || fs.statSync(filePath);
+    if (!fileStats.isFile()) return null;
+    return {
+      size: fileStats.size,
+      inode: fileStats.ino ? `${fileStats.dev}:${fileStats.ino}` : null,
+      name: path.basename(filePath),
+    };
+  } catch {
+    return null;
+  }
+}
+
+// Same inode for renames on one volume; same name for moves across volumes
+function isSameFile(a, b) {
+  return (
+    a.size === b.size && ((a.inode !== null && a.inode === b.inode) || a.name === b.name)
+  );
+}
+
+// Audio files under a folder, listed without blocking the main process
+// (a synchronous walk of a large network share freezes every window)
+async function listAudioFiles(folderPath) {
+  let items;
+  try {
+    items = await fs.promises.readdir(folderPath, { withFileTypes: true });
+  } catch (error) {
+    console.error("Error scanning folder:", folderPath, error);
+    return [];
+  }
+
+  const files = [];
+  const subfolders = [];
+  for (const item of items) {
+    const fullPath = path.join(folderPath, item.name);
+    if (item.isDirectory()) {
+      subfolders.push(fullPath);
+    } else if (item.isFile() && isAudioFile(fullPath)) {
+      files.push(fullPath);
     }
   }
-  return chokidar;
+  const nested = await Promise.all(subfolders.map(listAudioFiles));
+  return files.concat(...nested);
 }
 
-// Send event to renderer with debouncing
-function sendWatcherEvent(eventType, filePath) {
-  if (!mainWindow || mainWindow.isDestroyed()) return;
+async function indexKnownFiles(folders) {
+  knownFiles.clear();
+  for (const folder of folders) {
+    for (const filePath of await listAudioFiles(folder)) {
+      try {
+        const stats = await fs.promises.stat(filePath);
+        knownFiles.set(filePath, getFileIdentity(filePath, stats));
+      } catch {
+        // Removed while indexing
+      }
+    }
+  }
+}
+
+// Find a pending event of the given type whose file matches
+function takePendingMatch(type, matches) {
+  for (const [key, entry] of pendingEvents) {
+    if (entry.type === type && matches(entry.filePath)) {
+      clearTimeout(entry.timeoutId);
+      pendingEvents.delete(key);
+      return entry.filePath;
+    }
+  }
+  return null;
+}
 
-  // Create a unique key for this file+event combination
+// Queue an event; adds and unlinks wait long enough to be paired into a move
+function queueWatcherEvent(eventType, filePath) {
   const key = `${eventType}:${filePath}`;
 
   // Clear any pending event for this key
   if (pendingEvents.has(key)) {
-    clearTimeout(pendingEvents.get(key));
+    clearTimeout(pendingEvents.get(key).timeoutId);
   }
 
-  // Set a new debounced event
+  const delay = eventType === "change" ? DEBOUNCE_DELAY : MOVE_WINDOW;
   const timeoutId = setTimeout(() => {
     pendingEvents.delete(key);
+    flushWatcherEvent(eventType, filePath);
+  }, delay);
 
-    if (!mainWindow || mainWindow.isDestroyed()) return;
+  pendingEvents.set(key, { type: eventType, filePath, timeoutId });
+}
 
-    const fileName = path.basename(filePath);
-    const folderPath = path.dirname(filePath);
-    const ext = path.extname(filePath).slice(1).toLowerCase();
+function flushWatcherEvent(eventType, filePath) {
+  if (eventType === "remove") {
+    const identity = knownFiles.get(filePath);
+    const destination =
+      identity &&
+      takePendingMatch("add", (candidate) => {
+        const other = getFileIdentity(candidate);
+        return other !== null && isSameFile(identity, other);
+      });
+    knownFiles.delete(filePath);
+    if (destination) {
+      knownFiles.set(destination, getFileIdentity(destination));
+      sendWatcherEvent("move", destination, filePath);
+    } else {
+      sendWatcherEvent("remove", filePath);
+    }
+    return;
+  }
 
-    // Find the root folder this file belongs to
-    const folders = store ? store.get("musicFolders", []) : [];
-    let rootFolder = null;
-    let relativePath = null;
+  // Gone again before it settled
+  const identity = getFileIdentity(filePath);
+  if (!identity) return;
 
-    for (const folder of folders) {
-      if (filePath.startsWith(folder)) {
-        rootFolder = folder;
-        relativePath = path.relative(folder, folderPath);
-        break;
-      }
+  if (eventType === "add") {
+    const source = takePendingMatch("remove", (candidate) => {
+      const known = knownFiles.get(candidate);
+      return known !== undefined && isSameFile(known, identity);
+    });
+    if (source) {
+      knownFiles.delete(source);
+      knownFiles.set(filePath, identity);
+      sendWatcherEvent("move", filePath, source);
+      return;
+    }
+    // Saved over an existing file (editors often write a temp file and rename)
+    if (knownFiles.has(filePath)) {
+      eventType = "change";
     }
+  }
 
-    mainWindow.webContents.send("library:fileChange", {
-      type: eventType,
-      file: {
-        path: filePath,
-        name: fileName,
-        extension: ext,
-        folder: relativePath || null,
-      },
-      rootFolder,
-    });
+  knownFiles.set(filePath, identity);
+  sendWatcherEvent(eventType, filePath);
+}
+
+// Send a settled event to the renderer
+function sendWatcherEvent(eventType, filePath, previousPath = null) {
+  if (!mainWindow || mainWindow.isDestroyed()) return;
 
-    console.log(`[Watcher] ${eventType}: ${filePath}`);
-  }, DEBOUNCE_DELAY);
+  const fileName = path.basename(filePath);
+  const folderPath = path.dirname(filePath);
+  const ext = path.extname(filePath).slice(1).toLowerCase();
 
-  pendingEvents.set(key, timeoutId);
+  // Find the root folder this file belongs to
+  const folders = store ? store.get("musicFolders", []) : [];
+  let rootFolder = null;
+  let relativePath = null;
+
+  for (const folder of folders) {
+    if (filePath.startsWith(folder)) {
+      rootFolder = folder;
+      relativePath = path.relative(folder, folderPath);
+      break;
+    }
+  }
+
+  mainWindow.webContents.send("library:fileChange", {
+    type: eventType,
+    file: {
+      path: filePath,
+      name: fileName,
+      extension: ext,
+      folder: relativePath || null,
+    },
+    ...(previousPath && { previousPath }),
+    rootFolder,
+  });
+
+  console.log(
+    `[Watcher] ${eventType}: ${previousPath ? `${previousPath} -> ` : ""}${filePath}`,
+  );
+}
+
+// Initialize chokidar (lazy load since it's ESM)
+async function initChokidar() {
+  if (!chokidar) {
+    try {
+      chokidar = await import("chokidar");
+      console.log("[Watcher] chokidar initialized successfully");
+    } catch (error) {
+      console.error("[Watcher] Failed to initialize chokidar:", error);
+      return null;
+    }
+  }
+  return chokidar;
 }
 
 // Start watching all music folders
@@ -976,15 +2092,22 @@ ipcMain.handle("library:startWatching", async () => {
     depth: 10, // Max depth to recurse
   });
 
+  await indexKnownFiles(folders);
+
   watcher
     .on("add", (filePath) => {
       if (isAudioFile(filePath)) {
-        sendWatcherEvent("add", filePath);
+        queueWatcherEvent("add", filePath);
+      }
+    })
+    .on("change", (filePath) => {
+      if (isAudioFile(filePath)) {
+        queueWatcherEvent("change", filePath);
       }
     })
     .on("unlink", (filePath) => {
       if (isAudioFile(filePath)) {
-        sendWatcherEvent("remove", filePath);
+        queueWatcherEvent("remove", filePath);
       }
     })
     .on("error", (error) => {
@@ -1006,10 +2129,11 @@ ipcMain.handle("library:stopWatching", async () => {
   }
 
   // Clear any pending events
-  for (const timeoutId of pendingEvents.values()) {
+  for (const { timeoutId } of pendingEvents.values()) {
     clearTimeout(timeoutId);
   }
   pendingEvents.clear();
+  knownFiles.clear();
 
   return { success: true };
 });
@@ -1042,6 +2166,7 @@ app.on("open-file", (event, filePath) => {
   // Check if it's an audio file
   if (isAudioFile(filePath)) {
     // Always queue the file first
+    pathAccess.allowFile(filePath);
     pendingFilesToOpen.push(filePath);
     console.log("[OpenFile] Queued file:", filePath);
     
@@ -1077,7 +2202,10 @@ function handleCommandLineArgs(argv) {
     // Check if it's a file path and an audio file
     if (fs.existsSync(arg) && isAudioFile(arg)) {
       console.log("[OpenFile] File from command line:", arg);
-      pendingFilesToOpen.push(arg);
+      // Absolute, so the renderer can read it through file IPC
+      const filePath = path.resolve(arg);
+      pathAccess.allowFile(filePath);
+      pendingFilesToOpen.push(filePath);
     }
   }
 }
@@ -1132,13 +2260,15 @@ if (!gotTheLock) {
       if (arg.includes("electron") || arg.includes("app.asar")) continue;
       
       // Check if it's a file path that exists and is an audio file
-      if (fs.existsSync(arg) && isAudioFile(arg)) {
-        console.log("[OpenFile] File from second instance:", arg);
-        
+      const filePath = path.resolve(workingDirectory, arg);
+      if (fs.existsSync(filePath) && isAudioFile(filePath)) {
+        console.log("[OpenFile] File from second instance:", filePath);
+        pathAccess.allowFile(filePath);
+
         if (windowReady && mainWindow && !mainWindow.isDestroyed()) {
-          mainWindow.webContents.send("file:open", arg);
+          mainWindow.webContents.send("file:open", filePath);
         } else {
-          pendingFilesToOpen.push(arg);
+          pendingFilesToOpen.push(filePath);
         }
       }
     }
@@ -1164,6 +2294,16 @@ app.whenReady().then(() => {
   try {
     // Initialize store first
     initStore();
+    migrateAuthorizedFolders();
+
+    electron.protocol.handle(MEDIA_SCHEME, (request) =>
+      handleMediaRequest(request)
+        .catch((error) => {
+          console.error("[Media] Stream error:", error.message);
+          return new Response(error.message, { status: 500 });
+        })
+        .then(withCorsHeaders)
+    );
 
     createWindow();
     
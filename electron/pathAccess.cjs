@@ -0,0 +1,172 @@
+const path = require("path");
+const fs = require("fs");
+
+// Which paths the renderer may touch through file IPC. Everything the
+// renderer sends is canonicalized (symlinks and `..` resolved against the
+// real file system) and must fall under a library folder, a folder picked in
+// a dialog, or a file the user opened or chose a save location for.
+// Kept free of Electron imports so it can be tested on its own.
+
+// Error codes sent back to the renderer alongside the message
+const PATH_ACCESS_ERRORS = {
+  // Not an absolute path, or not a string at all
+  INVALID_PATH: "INVALID_PATH",
+  // Canonical path is outside everything the user has allowed
+  PATH_NOT_ALLOWED: "PATH_NOT_ALLOWED",
+};
+
+class PathAccessError extends Error {
+  constructor(code, message) {
+    super(message);
+    this.name = "PathAccessError";
+    this.code = code;
+  }
+}
+
+function isMissingError(error) {
+  return error.code === "ENOENT" || error.code === "ENOTDIR";
+}
+
+function isSymlink(filePath) {
+  try {
+    return fs.lstatSync(filePath).isSymbolicLink();
+  } catch {
+    return false;
+  }
+}
+
+function realpathOr(filePath, fallback) {
+  try {
+    return fs.realpathSync.native(filePath);
+  } catch (error) {
+    // Missing, keep it as given. A dangling symlink could be pointed anywhere
+    // later, so that's refused (as in src-tauri/src/path_access.rs)
+    if (isMissingError(error) && !isSymlink(filePath)) return fallback;
+    throw error;
+  }
+}
+
+// Resolve `input` one segment at a time so `..` applies to where a symlink
+// actually points, not to the link's own folder. Parts that don't exist yet
+// (a file about to be written) are kept as given
+function canonicalizePath(input) {
+  if (typeof input !== "string" || input.includes("\0")) {
+    throw new PathAccessError(
+      PATH_ACCESS_ERRORS.INVALID_PATH,
+      "Expected a file path",
+    );
+  }
+  if (!path.isAbsolute(input)) {
+    throw new PathAccessError(
+      PATH_ACCESS_ERRORS.INVALID_PATH,
+      `Not an absolute path: ${input}`,
+    );
+  }
+
+  const { root } = path.parse(input);
+  const segments = input
+    .slice(root.length)
+    .split(/[\\/]+/)
+    .filter((segment) => segment && segment !== ".");
+
+  try {
+    let current = realpathOr(path.resolve(root), path.resolve(root));
+    for (const segment of segments) {
+      if (segment === "..") {
+        current = path.dirname(current);
+      } else {
+        const next = path.join(current, segment);
+        current = realpathOr(next, next);
+      }
+    }
+    return current;
+  } catch (error) {
+    throw new PathAccessError(
+      PATH_ACCESS_ERRORS.INVALID_PATH,
+      `Can't resolve ${input}: ${error.message}`,
+    );
+  }
+}
+
+// Whether `target` is `root` or somewhere inside it, both canonical
+function isPathWithin(root, target) {
+  const relative = path.relative(root, target);
+  return (
+    relative === "" ||
+    (relative !== ".." &&
+      !relative.startsWith(`..${path.sep}`) &&
+      !path.isAbsolute(relative))
+  );
+}
+
+// `getFolders` returns the allowed folders (library folders and picked ones)
+// and is read on every check, so folders added later apply right away.
+// Files are allowed one at a time for the rest of the session
+function createPathAccess({ getFolders }) {
+  const allowedFiles = new Set();
+  // Canonical form of each folder as configured, folders rarely change
+  const canonicalFolders = new Map();
+
+  const getCanonicalFolders = () =>
+    getFolders().flatMap((folder) => {
+      if (!canonicalFolders.has(folder)) {
+        try {
+          canonicalFolders.set(folder, canonicalizePath(folder));
+        } catch {
+          canonicalFolders.set(folder, null);
+        }
+      }
+      const canonical = canonicalFolders.get(folder);
+      return canonical ? [canonical] : [];
+    });
+
+  // Canonical path of `input`, or a PathAccessError when it isn't allowed
+  const resolve = (input) => {
+    const canonical = canonicalizePath(input);
+    if (
+      allowedFiles.has(canonical) ||
+      getCanonicalFolders().some((folder) => isPathWithin(folder, canonical))
+    ) {
+      return canonical;
+    }
+    throw new PathAccessError(
+      PATH_ACCESS_ERRORS.PATH_NOT_ALLOWED,
+      `Not in your music folders: ${input}`,
+    );
+  };
+
+  // Canonical path of a file the user opened or picked as a save location.
+  // Library folders don't count, so the renderer can't overwrite songs
+  const resolveFile = (input) => {
+    const canonical = canonicalizePath(input);
+    if (allowedFiles.has(canonical)) return canonical;
+    throw new PathAccessError(
+      PATH_ACCESS_ERRORS.PATH_NOT_ALLOWED,
+      `Not a file you picked: ${input}`,
+    );
+  };
+
+  const isAllowed = (input) => {
+    try {
+      resolve(input);
+      return true;
+    } catch {
+      return false;
+    }
+  };
+
+  // A file the user opened or picked as a save location
+  const allowFile = (input) => {
+    allowedFiles.add(canonicalizePath(input));
+  };
+
+  return { resolve, resolveFile, isAllowed, allowFile };
+}
+
+module.exports = {
+  PATH_ACCESS_ERRORS,
+  PathAccessError,
+  canonicalizePath,
+  isPathWithin,
+  createPathAccess,
+};
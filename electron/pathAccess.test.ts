@@ -0,0 +1,139 @@
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import fs from 'fs';
+import os from 'os';
+import path from 'path';
+import { createRequire } from 'module';
+
+const requireCjs = createRequire(import.meta.url);
+const { createPathAccess, canonicalizePath } = requireCjs('./pathAccess.cjs');
+
+function expectError(fn: () => unknown, code: string) {
+  try {
+    fn();
+  } catch (error) {
+    expect((error as { code?: string }).code).toBe(code);
+    return;
+  }
+  throw new Error(`Expected a ${code} error`);
+}
+
+describe('pathAccess', () => {
+  let base: string;
+  let music: string;
+  let outside: string;
+  let folders: string[];
+  let access: ReturnType<typeof createPathAccess>;
+
+  beforeEach(() => {
+    // Real path, so expectations match on macOS where tmp is a symlink
+    base = fs.realpathSync(fs.mkdtempSync(path.join(os.tmpdir(), 'vinyl-path-access-')));
+    music = path.join(base, 'Music');
+    outside = path.join(base, 'Private');
+    fs.mkdirSync(path.join(music, 'Album'), { recursive: true });
+    fs.mkdirSync(outside);
+    fs.writeFileSync(path.join(music, 'Album', 'song.mp3'), '');
+    fs.writeFileSync(path.join(outside, 'secret.txt'), '');
+
+    folders = [music];
+    access = createPathAccess({ getFolders: () => folders });
+  });
+
+  afterEach(() => {
+    fs.rmSync(base, { recursive: true, force: true });
+  });
+
+  it('allows files inside a library folder', () => {
+    const song = path.join(music, 'Album', 'song.mp3');
+    expect(access.resolve(song)).toBe(song);
+    expect(access.isAllowed(music)).toBe(true);
+  });
+
+  it('allows files that do not exist yet inside a library folder', () => {
+    const playlist = path.join(music, 'New Folder', 'mix.m3u8');
+    expect(access.resolve(playlist)).toBe(playlist);
+  });
+
+  it('rejects paths that climb out with ..', () => {
+    expectError(() => access.resolve(`${music}/Album/../../Private/secret.txt`), 'PATH_NOT_ALLOWED');
+    expectError(() => access.resolve(`${music}/Missing/../../Private/secret.txt`), 'PATH_NOT_ALLOWED');
+  });
+
+  it('resolves .. that stays inside the folder', () => {
+    const song = path.join(music, 'Album', 'song.mp3');
+    expect(access.resolve(`${music}/Album/../Album/./song.mp3`)).toBe(song);
+  });
+
+  it('rejects sibling folders that share a name prefix', () => {
+    fs.mkdirSync(`${music}-backup`);
+    expectError(() => access.resolve(path.join(`${music}-backup`, 'song.mp3')), 'PATH_NOT_ALLOWED');
+  });
+
+  it.skipIf(process.platform === 'win32')('rejects symlinks that point outside the folder', () => {
+    fs.symlinkSync(outside, path.join(music, 'link'));
+    fs.symlinkSync(path.join(outside, 'secret.txt'), path.join(music, 'secret.mp3'));
+
+    expectError(() => access.resolve(path.join(music, 'link', 'secret.txt')), 'PATH_NOT_ALLOWED');
+    expectError(() => access.resolve(path.join(music, 'secret.mp3')), 'PATH_NOT_ALLOWED');
+  });
+
+  it.skipIf(process.platform === 'win32')('applies .. after a symlink to where it points', () => {
+    // Music/link -> Private/Nested, so Music/link/.. is Private, not Music
+    fs.mkdirSync(path.join(outside, 'Nested'));
+    fs.symlinkSync(path.join(outside, 'Nested'), path.join(music, 'link'));
+
+    expectError(() => access.resolve(`${music}/link/../secret.txt`), 'PATH_NOT_ALLOWED');
+  });
+
+  it.skipIf(process.platform === 'win32')('follows a library folder that is itself a symlink', () => {
+    const linked = path.join(base, 'Linked Music');
+    fs.symlinkSync(music, linked);
+    folders = [linked];
+
+    expect(access.resolve(path.join(linked, 'Album', 'song.mp3'))).toBe(path.join(music, 'Album', 'song.mp3'));
+    expect(access.isAllowed(path.join(music, 'Album', 'song.mp3'))).toBe(true);
+  });
+
+  it.skipIf(process.platform === 'win32')('rejects dangling symlinks that could be pointed outside later', () => {
+    fs.symlinkSync(path.join(outside, 'missing'), path.join(music, 'later'));
+    fs.symlinkSync(path.join(outside, 'missing.mp3'), path.join(music, 'later.mp3'));
+
+    expectError(() => access.resolve(path.join(music, 'later', 'song.mp3')), 'INVALID_PATH');
+    expectError(() => access.resolve(path.join(music, 'later.mp3')), 'INVALID_PATH');
+    expectError(() => access.resolve(`${music}/later/../Album/song.mp3`), 'INVALID_PATH');
+  });
+
+  it('rejects relative and malformed paths', () => {
+    expectError(() => access.resolve('Music/song.mp3'), 'INVALID_PATH');
+    expectError(() => access.resolve(`${music}/song.mp3\0.txt`), 'INVALID_PATH');
+    expectError(() => access.resolve(42), 'INVALID_PATH');
+    expectError(() => canonicalizePath(undefined), 'INVALID_PATH');
+  });
+
+  it('allows single files without their folder', () => {
+    const secret = path.join(outside, 'secret.txt');
+    access.allowFile(secret);
+
+    expect(access.resolve(secret)).toBe(secret);
+    expect(access.resolve(`${outside}/../Private/secret.txt`)).toBe(secret);
+    expect(access.isAllowed(path.join(outside, 'other.txt'))).toBe(false);
+    expect(access.isAllowed(outside)).toBe(false);
+  });
+
+  it('only writes to files picked in a dialog', () => {
+    const song = path.join(music, 'Album', 'song.mp3');
+    const playlist = path.join(outside, 'mix.m3u8');
+    access.allowFile(playlist);
+
+    expect(access.resolveFile(`${outside}/../Private/mix.m3u8`)).toBe(playlist);
+    expectError(() => access.resolveFile(song), 'PATH_NOT_ALLOWED');
+    expectError(() => access.resolveFile(path.join(music, 'mix.m3u8')), 'PATH_NOT_ALLOWED');
+  });
+
+  it('picks up folders added later', () => {
+    const secret = path.join(outside, 'secret.txt');
+    expect(access.isAllowed(secret)).toBe(false);
+
+    folders = [music, outside];
+    expect(access.isAllowed(secret)).toBe(true);
+  });
+});
@@ -7,7 +7,14 @@ contextBridge.exposeInMainWorld("electron", {
   isElectron: true,
 
   // Dialog APIs
-  openFolderPicker: () => ipcRenderer.invoke("dialog:openFolder"),
+  openFolderPicker: (options) =>
+    ipcRenderer.invoke("dialog:openFolder", options),
+  openPlaylistFile: () => ipcRenderer.invoke("dialog:openPlaylist"),
+  pickPlaylistSavePath: (defaultName) =>
+    ipcRenderer.invoke("dialog:savePlaylist", defaultName),
+  openBackupFile: () => ipcRenderer.invoke("dialog:openBackup"),
+  pickBackupSavePath: (defaultName) =>
+    ipcRenderer.invoke("dialog:saveBackup", defaultName),
 
   // File system APIs
   scanMusicFolder: (folderPath) =>
@@ -27,10 +34,22 @@ contextBridge.exposeInMainWorld("electron", {
     };
   },
 
+  // Streaming URL for the <audio> element (supports Range requests)
+  getMediaUrl: (filePath) => `vinyl-media://local/${encodeURIComponent(filePath)}`,
+
   fileExists: (filePath) => ipcRenderer.invoke("fs:fileExists", filePath),
 
   getFileStats: (filePath) => ipcRenderer.invoke("fs:getStats", filePath),
 
+  getFilesStats: (filePaths) =>
+    ipcRenderer.invoke("fs:getStatsBatch", filePaths),
+
+  writeTextFile: (filePath, content) =>
+    ipcRenderer.invoke("fs:writeTextFile", filePath, content),
+
+  writeBackupFile: (folderPath, fileName, content) =>
+    ipcRenderer.invoke("fs:writeBackupFile", folderPath, fileName, content),
+
   // Shell APIs
   showItemInFolder: (filePath) =>
     ipcRenderer.invoke("shell:showItemInFolder", filePath),
@@ -59,6 +78,16 @@ contextBridge.exposeInMainWorld("electron", {
     scanFolder: (folderPath) =>
       ipcRenderer.invoke("library:scanFolderWithProgress", folderPath),
     scanAllFolders: () => ipcRenderer.invoke("library:scanAllFolders"),
+    resetScanIndex: () => ipcRenderer.invoke("library:resetScanIndex"),
+    forgetScannedFiles: (filePaths) =>
+      ipcRenderer.invoke("library:forgetScannedFiles", filePaths),
+    migrateSongFolders: (folders) =>
+      ipcRenderer.invoke("library:migrateSongFolders", folders),
+    readMetadata: (filePaths, options) =>
+      ipcRenderer.invoke("library:readMetadata", filePaths, options),
+    analyzeLoudness: (albums) =>
+      ipcRenderer.invoke("library:analyzeLoudness", albums),
+    writeTags: (writes) => ipcRenderer.invoke("library:writeTags", writes),
     // Watcher APIs
     startWatching: () => ipcRenderer.invoke("library:startWatching"),
     stopWatching: () => ipcRenderer.invoke("library:stopWatching"),
@@ -70,6 +99,20 @@ contextBridge.exposeInMainWorld("electron", {
       // Return cleanup function
       return () => ipcRenderer.removeListener("library:fileChange", handler);
     },
+    // Progress of scanAllFolders: { phase: "walking" | "hashing", current, total }
+    onScanProgress: (callback) => {
+      const handler = (_event, data) => callback(data);
+      ipcRenderer.on("library:scanProgress", handler);
+      return () => ipcRenderer.removeListener("library:scanProgress", handler);
+    },
+  },
+
+  // Transcode cache APIs
+  transcodeCache: {
+    getInfo: () => ipcRenderer.invoke("transcodeCache:getInfo"),
+    clear: () => ipcRenderer.invoke("transcodeCache:clear"),
+    setMaxSize: (maxSize) =>
+      ipcRenderer.invoke("transcodeCache:setMaxSize", maxSize),
   },
 
   // System Tray APIs
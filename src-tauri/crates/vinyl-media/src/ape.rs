@@ -0,0 +1,131 @@
+//! Monkey's Audio (APE) decoding, which Symphonia doesn't have.
+//!
+//! `ape-decoder` hands back each frame as little-endian PCM bytes; this turns
+//! them into the same full-scale `i32` buffers Symphonia produces, so the FLAC
+//! encoder and loudness analysis don't care where the samples came from.
+
+use crate::{Error, Result};
+use ape_decoder::ApeDecoder;
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use symphonia::core::audio::{AudioBuffer, AudioBufferRef, Channels, Signal, SignalSpec};
+
+/// Most channels a frame can be laid out into (Symphonia's channel mask is 32 bits)
+const MAX_CHANNELS: u16 = 32;
+
+pub(crate) struct ApeReader {
+    decoder: ApeDecoder<BufReader<File>>,
+    next_frame: u32,
+    bits_per_sample: u16,
+    buffer: AudioBuffer<i32>,
+}
+
+impl ApeReader {
+    pub(crate) fn open(input: &Path) -> Result<Self> {
+        let decoder = ApeDecoder::new(BufReader::new(File::open(input)?))?;
+        let info = decoder.info();
+        // Float samples are stored transformed and the crate doesn't undo that
+        if info.is_floating_point {
+            return Err(Error::Unsupported("floating-point APE".into()));
+        }
+        if !matches!(info.bits_per_sample, 8 | 16 | 24 | 32) {
+            return Err(Error::Unsupported(format!(
+                "{}-bit APE",
+                info.bits_per_sample
+            )));
+        }
+        if info.channels == 0 || info.channels > MAX_CHANNELS {
+            return Err(Error::Unsupported(format!(
+                "APE with {} channels",
+                info.channels
+            )));
+        }
+
+        let mask = u32::MAX >> (32 - u32::from(info.channels));
+        let channels = Channels::from_bits_truncate(mask);
+        let spec = SignalSpec::new(info.sample_rate, channels);
+        let buffer = AudioBuffer::new(u64::from(info.blocks_per_frame.max(1)), spec);
+        let bits_per_sample = info.bits_per_sample;
+
+        Ok(Self {
+            decoder,
+            next_frame: 0,
+            bits_per_sample,
+            buffer,
+        })
+    }
+
+    pub(crate) fn n_frames(&self) -> u64 {
+        self.decoder.info().total_samples
+    }
+
+    pub(crate) fn bits_per_sample(&self) -> u32 {
+        u32::from(self.bits_per_sample)
+    }
+
+    /// The next decoded frame, or `None` after the last one
+    pub(crate) fn next_buffer(&mut self) -> Result<Option<AudioBufferRef<'_>>> {
+        if self.next_frame >= self.decoder.total_frames() {
+            return Ok(None);
+        }
+        let pcm = self.decoder.decode_frame(self.next_frame)?;
+        self.next_frame += 1;
+
+        let channels = self.buffer.spec().channels.count();
+        let bytes_per_sample = usize::from(self.bits_per_sample / 8);
+        let frames = pcm.len() / (bytes_per_sample * channels);
+
+        self.buffer.clear();
+        self.buffer.render_reserved(Some(frames));
+        {
+            let mut planes = self.buffer.planes_mut();
+            for (index, sample) in pcm.chunks_exact(bytes_per_sample).enumerate() {
+                planes.planes()[index % channels][index / channels] = to_full_scale(sample);
+            }
+        }
+        Ok(Some(AudioBufferRef::S32(Cow::Borrowed(&self.buffer))))
+    }
+}
+
+/// One little-endian PCM sample scaled to the full `i32` range.
+///
+/// 8-bit samples are unsigned (biased by 128), wider ones are signed.
+fn to_full_scale(sample: &[u8]) -> i32 {
+    match *sample {
+        [byte] => (i32::from(byte) - 128) << 24,
+        [lo, hi] => i32::from(i16::from_le_bytes([lo, hi])) << 16,
+        [lo, mid, hi] => i32::from_le_bytes([0, lo, mid, hi]),
+        [b0, b1, b2, b3] => i32::from_le_bytes([b0, b1, b2, b3]),
+        _ => unreachable!("APE samples are 1 to 4 bytes"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode::tests::temp_dir;
+    use std::fs;
+
+    #[test]
+    fn scales_every_bit_depth_to_full_scale() {
+        assert_eq!(to_full_scale(&[0]), i32::MIN);
+        assert_eq!(to_full_scale(&[128]), 0);
+        assert_eq!(to_full_scale(&[0xff]), 127 << 24);
+        assert_eq!(to_full_scale(&(-2i16).to_le_bytes()), -2 << 16);
+        assert_eq!(to_full_scale(&[0xff, 0xff, 0x7f]), 0x7fff_ff00);
+        assert_eq!(to_full_scale(&[0x00, 0x00, 0x80]), i32::MIN);
+        assert_eq!(to_full_scale(&(-5i32).to_le_bytes()), -5);
+    }
+
+    #[test]
+    fn rejects_files_that_are_not_ape() {
+        let dir = temp_dir("ape");
+        let input = dir.join("fake.ape");
+        fs::write(&input, b"definitely not monkey's audio").unwrap();
+
+        assert!(ApeReader::open(&input).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,290 @@
+//! Decoding to FLAC for formats the webview can't play.
+//!
+//! Replaces the FFmpeg transcode step for AAC and ALAC (in MP4 or ADTS) via
+//! Symphonia, and for Monkey's Audio via [`crate::ape`]. There is no pure-Rust
+//! WMA decoder, so [`can_decode`] is false for WMA and callers still need
+//! FFmpeg for it.
+
+use crate::ape::ApeReader;
+use crate::flac::{FlacEncoder, MAX_BITS_PER_SAMPLE};
+use crate::{Error, Result};
+use std::fs::File;
+use std::io::{Seek, Write};
+use std::path::Path;
+use symphonia::core::audio::{AudioBufferRef, SampleBuffer};
+use symphonia::core::codecs::{Decoder, DecoderOptions, CODEC_TYPE_NULL};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::{FormatOptions, FormatReader};
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+/// Extensions decoded natively (everything else the webview plays itself)
+const DECODABLE_EXTENSIONS: &[&str] = &["m4a", "aac", "mp4", "ape"];
+
+pub fn can_decode(path: &Path) -> bool {
+    path.extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .is_some_and(|ext| DECODABLE_EXTENSIONS.contains(&ext.as_str()))
+}
+
+/// Decoded audio from the first track of a file, one buffer at a time
+pub(crate) struct AudioReader {
+    source: Source,
+    /// Length in frames, when the container declares it
+    pub n_frames: Option<u64>,
+    /// Bit depth of the source, when it is integer PCM underneath (not for lossy codecs)
+    pub bits_per_sample: Option<u32>,
+}
+
+enum Source {
+    Symphonia(SymphoniaReader),
+    Ape(Box<ApeReader>),
+}
+
+struct SymphoniaReader {
+    format: Box<dyn FormatReader>,
+    decoder: Box<dyn Decoder>,
+    track_id: u32,
+}
+
+impl AudioReader {
+    pub(crate) fn open(input: &Path) -> Result<Self> {
+        let is_ape = input
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("ape"));
+        if is_ape {
+            let reader = ApeReader::open(input)?;
+            return Ok(Self {
+                n_frames: Some(reader.n_frames()),
+                bits_per_sample: Some(reader.bits_per_sample()),
+                source: Source::Ape(Box::new(reader)),
+            });
+        }
+
+        let source = MediaSourceStream::new(Box::new(File::open(input)?), Default::default());
+        let mut hint = Hint::new();
+        if let Some(ext) = input.extension().and_then(|ext| ext.to_str()) {
+            hint.with_extension(ext);
+        }
+
+        // Gapless trims encoder delay/padding so decoded length matches the source
+        let format_options = FormatOptions {
+            enable_gapless: true,
+            ..Default::default()
+        };
+        let probed = symphonia::default::get_probe().format(
+            &hint,
+            source,
+            &format_options,
+            &MetadataOptions::default(),
+        )?;
+        let format = probed.format;
+
+        let track = format
+            .tracks()
+            .iter()
+            .find(|track| track.codec_params.codec != CODEC_TYPE_NULL)
+            .ok_or_else(|| Error::Unsupported("no audio track".into()))?;
+        let track_id = track.id;
+        let n_frames = track.codec_params.n_frames;
+        let bits_per_sample = track.codec_params.bits_per_sample;
+        let decoder = symphonia::default::get_codecs()
+            .make(&track.codec_params, &DecoderOptions::default())?;
+
+        Ok(Self {
+            source: Source::Symphonia(SymphoniaReader {
+                format,
+                decoder,
+                track_id,
+            }),
+            n_frames,
+            bits_per_sample,
+        })
+    }
+
+    /// The next decoded buffer, or `None` at the end of the stream
+    pub(crate) fn next_buffer(&mut self) -> Result<Option<AudioBufferRef<'_>>> {
+        match &mut self.source {
+            Source::Symphonia(reader) => reader.next_buffer(),
+            Source::Ape(reader) => reader.next_buffer(),
+        }
+    }
+}
+
+impl SymphoniaReader {
+    fn next_buffer(&mut self) -> Result<Option<AudioBufferRef<'_>>> {
+        loop {
+            let packet = match self.format.next_packet() {
+                Ok(packet) => packet,
+                Err(SymphoniaError::IoError(error))
+                    if error.kind() == std::io::ErrorKind::UnexpectedEof =>
+                {
+                    return Ok(None)
+                }
+                Err(SymphoniaError::ResetRequired) => return Ok(None),
+                Err(error) => return Err(error.into()),
+            };
+            if packet.track_id() != self.track_id {
+                continue;
+            }
+
+            match self.decoder.decode(&packet) {
+                Ok(_) => break,
+                // A corrupt packet shouldn't fail the whole file
+                Err(SymphoniaError::DecodeError(_)) => continue,
+                Err(error) => return Err(error.into()),
+            }
+        }
+        Ok(Some(self.decoder.last_decoded()))
+    }
+}
+
+/// Decode `input` into FLAC written to `out`, keeping the source rate and channels.
+///
+/// Lossless sources keep their bit depth (up to [`MAX_BITS_PER_SAMPLE`]);
+/// lossy ones are written at 16 bits.
+///
+/// Frames are flushed as they are encoded so the output can be played while
+/// it is still being written. Nothing is written if the input can't be
+/// probed; callers own cleaning up `out` after a later failure.
+pub fn decode_to_flac<W: Write + Seek>(input: &Path, out: W) -> Result<W> {
+    let mut reader = AudioReader::open(input)?;
+    let expected_frames = reader.n_frames.unwrap_or(0);
+    let bits_per_sample = reader
+        .bits_per_sample
+        .unwrap_or(16)
+        .min(MAX_BITS_PER_SAMPLE);
+    // Samples come back scaled to the full i32 range
+    let shift = 32 - bits_per_sample;
+
+    let mut out = Some(out);
+    let mut encoder: Option<FlacEncoder<W>> = None;
+    let mut samples: Option<SampleBuffer<i32>> = None;
+    let mut aligned = Vec::new();
+
+    while let Some(decoded) = reader.next_buffer()? {
+        let spec = *decoded.spec();
+        // The decoded spec is authoritative, containers don't always declare it
+        let encoder = match &mut encoder {
+            Some(encoder) => encoder,
+            None => encoder.insert(FlacEncoder::new(
+                out.take().expect("output is only taken once"),
+                spec.rate,
+                spec.channels.count() as u16,
+                bits_per_sample,
+                expected_frames,
+            )?),
+        };
+
+        let buffer = match &mut samples {
+            Some(buffer) if buffer.capacity() >= decoded.capacity() => buffer,
+            _ => samples.insert(SampleBuffer::new(decoded.capacity() as u64, spec)),
+        };
+        buffer.copy_interleaved_ref(decoded);
+        aligned.clear();
+        aligned.extend(buffer.samples().iter().map(|&sample| sample >> shift));
+        encoder.write(&aligned)?;
+    }
+
+    encoder
+        .ok_or_else(|| Error::Unsupported("no decodable audio".into()))?
+        .finish()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::flac::tests::decode_flac;
+    use std::fs;
+    use std::io::Cursor;
+    use std::path::PathBuf;
+
+    /// Canonical 44-byte header for integer PCM
+    fn wav_header(sample_rate: u32, channels: u16, bits: u16, data_len: u32) -> Vec<u8> {
+        let block_align = channels * bits / 8;
+        let mut header = Vec::with_capacity(44);
+        header.extend_from_slice(b"RIFF");
+        header.extend_from_slice(&(36 + data_len).to_le_bytes());
+        header.extend_from_slice(b"WAVEfmt ");
+        header.extend_from_slice(&16u32.to_le_bytes());
+        header.extend_from_slice(&1u16.to_le_bytes());
+        header.extend_from_slice(&channels.to_le_bytes());
+        header.extend_from_slice(&sample_rate.to_le_bytes());
+        header.extend_from_slice(&(sample_rate * u32::from(block_align)).to_le_bytes());
+        header.extend_from_slice(&block_align.to_le_bytes());
+        header.extend_from_slice(&bits.to_le_bytes());
+        header.extend_from_slice(b"data");
+        header.extend_from_slice(&data_len.to_le_bytes());
+        header
+    }
+
+    /// 16-bit PCM WAV holding a ramp, so decoded samples can be compared
+    pub(crate) fn write_wav(path: &Path, sample_rate: u32, channels: u16, frames: u32) {
+        let samples = frames * u32::from(channels);
+        let mut bytes = wav_header(sample_rate, channels, 16, samples * 2);
+        for index in 0..samples {
+            bytes.extend_from_slice(&((index % 2000) as i16 - 1000).to_le_bytes());
+        }
+        fs::write(path, bytes).unwrap();
+    }
+
+    pub(crate) fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("vinyl-media-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn only_claims_formats_it_decodes() {
+        assert!(can_decode(Path::new("song.M4A")));
+        assert!(can_decode(Path::new("song.aac")));
+        assert!(can_decode(Path::new("song.ape")));
+        assert!(!can_decode(Path::new("song.wma")));
+    }
+
+    #[test]
+    fn round_trips_pcm_through_the_decoder() {
+        let dir = temp_dir("decode");
+        let input = dir.join("input.wav");
+        write_wav(&input, 22_050, 2, 5_000);
+
+        let flac = decode_to_flac(&input, Cursor::new(Vec::new())).unwrap();
+
+        let pcm = fs::read(&input).unwrap()[44..]
+            .chunks(2)
+            .map(|bytes| i32::from(i16::from_le_bytes([bytes[0], bytes[1]])))
+            .collect();
+        assert_eq!(decode_flac(flac.into_inner()), (22_050, 2, 16, pcm));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn keeps_the_bit_depth_of_lossless_sources() {
+        let dir = temp_dir("decode-24");
+        let input = dir.join("input.wav");
+        let pcm: Vec<i32> = (0..6_000).map(|index| index * 1_297 - 3_000_000).collect();
+        let mut bytes = wav_header(48_000, 2, 24, pcm.len() as u32 * 3);
+        for sample in &pcm {
+            bytes.extend_from_slice(&sample.to_le_bytes()[..3]);
+        }
+        fs::write(&input, bytes).unwrap();
+
+        let flac = decode_to_flac(&input, Cursor::new(Vec::new())).unwrap();
+
+        assert_eq!(decode_flac(flac.into_inner()), (48_000, 2, 24, pcm));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn failed_decode_writes_nothing() {
+        let dir = temp_dir("decode-fail");
+        let input = dir.join("broken.m4a");
+        fs::write(&input, b"definitely not an mp4").unwrap();
+
+        let mut out = Cursor::new(Vec::new());
+        assert!(decode_to_flac(&input, &mut out).is_err());
+        assert!(out.get_ref().is_empty());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,24 @@
+use std::io;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("{0}")]
+    Io(#[from] io::Error),
+
+    #[error("Unreadable tags: {0}")]
+    Tags(#[from] lofty::error::FileParseError),
+
+    #[error("Couldn't write tags: {0}")]
+    TagWrite(#[from] lofty::error::FileEncodingError),
+
+    #[error("Decoding failed: {0}")]
+    Decode(#[from] symphonia::core::errors::Error),
+
+    #[error("Decoding failed: {0}")]
+    ApeDecode(#[from] ape_decoder::ApeError),
+
+    #[error("Unsupported audio: {0}")]
+    Unsupported(String),
+}
@@ -0,0 +1,793 @@
+//! Minimal streaming FLAC encoder for the transcode cache.
+//!
+//! Decoded audio is cached as FLAC instead of WAV, at the source's bit depth
+//! up to [`MAX_BITS_PER_SAMPLE`]: it is roughly half the size, and every
+//! frame is flushed as soon as it is encoded so the player can start reading
+//! the file while it is still being written.
+//!
+//! Only what a cache needs is implemented: fixed-size blocks, fixed
+//! predictors (orders 0-4), partitioned Rice coding and stereo decorrelation.
+
+use crate::{Error, Result};
+use std::io::{Seek, SeekFrom, Write};
+
+/// Samples per channel in every frame but the last
+const BLOCK_SIZE: usize = 4096;
+
+/// Deepest samples written; 32-bit sources are cut to this, which players
+/// widely decode (and is what FFmpeg writes for them too)
+pub const MAX_BITS_PER_SAMPLE: u32 = 24;
+
+const MIN_BITS_PER_SAMPLE: u32 = 4;
+
+const MAX_CHANNELS: u16 = 8;
+
+/// Highest fixed predictor order defined by the format
+const MAX_FIXED_ORDER: usize = 4;
+
+const MAX_PARTITION_ORDER: u32 = 6;
+
+/// 4-bit Rice parameters top out at 14, 15 is the escape code
+const MAX_RICE_PARAM: u32 = 14;
+
+/// 5-bit parameters (coding method 1) top out at 30, for samples above 16 bits
+const MAX_RICE2_PARAM: u32 = 30;
+
+/// `fLaC` marker plus the STREAMINFO block header
+const STREAMINFO_OFFSET: u64 = 8;
+
+const STREAMINFO_LEN: usize = 34;
+
+/// Encodes interleaved samples of a fixed bit depth into a FLAC stream.
+///
+/// The STREAMINFO block is written up front with the expected length, so a
+/// reader tailing the file knows the duration, and patched with the real
+/// totals by [`FlacEncoder::finish`].
+pub struct FlacEncoder<W: Write + Seek> {
+    out: W,
+    sample_rate: u32,
+    channels: usize,
+    bits_per_sample: u32,
+    pending: Vec<i32>,
+    frame_number: u64,
+    total_frames: u64,
+    frame_sizes: Option<(u32, u32)>,
+}
+
+impl<W: Write + Seek> FlacEncoder<W> {
+    pub fn new(
+        mut out: W,
+        sample_rate: u32,
+        channels: u16,
+        bits_per_sample: u32,
+        expected_frames: u64,
+    ) -> Result<Self> {
+        if channels == 0 || channels > MAX_CHANNELS {
+            return Err(Error::Unsupported(format!("{channels} channels")));
+        }
+        if !(MIN_BITS_PER_SAMPLE..=MAX_BITS_PER_SAMPLE).contains(&bits_per_sample) {
+            return Err(Error::Unsupported(format!("{bits_per_sample}-bit samples")));
+        }
+        if sample_rate == 0 || sample_rate >= 1 << 20 {
+            return Err(Error::Unsupported(format!("{sample_rate} Hz sample rate")));
+        }
+
+        out.write_all(b"fLaC")?;
+        // Last metadata block, type 0 (STREAMINFO)
+        out.write_all(&[0x80, 0, 0, STREAMINFO_LEN as u8])?;
+        out.write_all(&stream_info(
+            sample_rate,
+            channels,
+            bits_per_sample,
+            expected_frames,
+            None,
+        ))?;
+        out.flush()?;
+
+        Ok(Self {
+            out,
+            sample_rate,
+            channels: usize::from(channels),
+            bits_per_sample,
+            pending: Vec::with_capacity(BLOCK_SIZE * usize::from(channels)),
+            frame_number: 0,
+            total_frames: 0,
+            frame_sizes: None,
+        })
+    }
+
+    /// Queue interleaved samples, encoding and flushing every full block.
+    ///
+    /// Samples are right-aligned: they must fit in the encoder's bit depth.
+    pub fn write(&mut self, samples: &[i32]) -> Result<()> {
+        let block_len = BLOCK_SIZE * self.channels;
+        let mut samples = samples;
+
+        while !samples.is_empty() {
+            let take = (block_len - self.pending.len()).min(samples.len());
+            self.pending.extend_from_slice(&samples[..take]);
+            samples = &samples[take..];
+
+            if self.pending.len() == block_len {
+                self.write_frame()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Encode the final partial block and patch STREAMINFO with the real totals
+    pub fn finish(mut self) -> Result<W> {
+        if !self.pending.is_empty() {
+            self.write_frame()?;
+        }
+
+        let end = self.out.stream_position()?;
+        self.out.seek(SeekFrom::Start(STREAMINFO_OFFSET))?;
+        self.out.write_all(&stream_info(
+            self.sample_rate,
+            self.channels as u16,
+            self.bits_per_sample,
+            self.total_frames,
+            self.frame_sizes,
+        ))?;
+        self.out.seek(SeekFrom::Start(end))?;
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    fn write_frame(&mut self) -> Result<()> {
+        let block_size = self.pending.len() / self.channels;
+        let channels: Vec<Vec<i32>> = (0..self.channels)
+            .map(|channel| {
+                self.pending
+                    .iter()
+                    .skip(channel)
+                    .step_by(self.channels)
+                    .copied()
+                    .collect()
+            })
+            .collect();
+
+        let frame = encode_frame(
+            &channels,
+            block_size,
+            self.frame_number,
+            self.sample_rate,
+            self.bits_per_sample,
+        );
+        let len = frame.len() as u32;
+        self.frame_sizes = Some(match self.frame_sizes {
+            Some((min, max)) => (min.min(len), max.max(len)),
+            None => (len, len),
+        });
+
+        self.out.write_all(&frame)?;
+        // Readers tail the file, so every frame has to reach the disk
+        self.out.flush()?;
+
+        self.frame_number += 1;
+        self.total_frames += block_size as u64;
+        self.pending.clear();
+        Ok(())
+    }
+}
+
+fn stream_info(
+    sample_rate: u32,
+    channels: u16,
+    bits_per_sample: u32,
+    total_frames: u64,
+    frame_sizes: Option<(u32, u32)>,
+) -> [u8; STREAMINFO_LEN] {
+    let (min_frame, max_frame) = frame_sizes.unwrap_or((0, 0));
+    let mut bits = BitWriter::default();
+    bits.write(BLOCK_SIZE as u64, 16);
+    bits.write(BLOCK_SIZE as u64, 16);
+    bits.write(u64::from(min_frame), 24);
+    bits.write(u64::from(max_frame), 24);
+    bits.write(u64::from(sample_rate), 20);
+    bits.write(u64::from(channels - 1), 3);
+    bits.write(u64::from(bits_per_sample - 1), 5);
+    bits.write(total_frames.min((1 << 36) - 1), 36);
+    // An all-zero MD5 means "not computed"
+    bits.write(0, 64);
+    bits.write(0, 64);
+
+    let bytes = bits.into_bytes();
+    let mut info = [0; STREAMINFO_LEN];
+    info.copy_from_slice(&bytes);
+    info
+}
+
+/// How the channels of a stereo frame are stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChannelMode {
+    Independent,
+    LeftSide,
+    SideRight,
+    MidSide,
+}
+
+fn encode_frame(
+    channels: &[Vec<i32>],
+    block_size: usize,
+    frame_number: u64,
+    rate: u32,
+    bits_per_sample: u32,
+) -> Vec<u8> {
+    let (mode, subframes) = if channels.len() == 2 {
+        decorrelate(&channels[0], &channels[1], bits_per_sample)
+    } else {
+        let subframes = channels
+            .iter()
+            .map(|samples| (samples.clone(), bits_per_sample))
+            .collect();
+        (ChannelMode::Independent, subframes)
+    };
+
+    let mut bits = BitWriter::default();
+    write_frame_header(
+        &mut bits,
+        block_size,
+        frame_number,
+        rate,
+        channels.len(),
+        bits_per_sample,
+        mode,
+    );
+    for (samples, bits_per_sample) in &subframes {
+        write_subframe(&mut bits, samples, *bits_per_sample);
+    }
+    bits.align();
+
+    let mut frame = bits.into_bytes();
+    let crc = crc16(&frame);
+    frame.extend_from_slice(&crc.to_be_bytes());
+    frame
+}
+
+/// Pick the stereo layout whose channels look cheapest to code
+fn decorrelate(
+    left: &[i32],
+    right: &[i32],
+    bits_per_sample: u32,
+) -> (ChannelMode, Vec<(Vec<i32>, u32)>) {
+    let side: Vec<i32> = left.iter().zip(right).map(|(l, r)| l - r).collect();
+    let mid: Vec<i32> = left.iter().zip(right).map(|(l, r)| (l + r) >> 1).collect();
+
+    let [left_cost, right_cost, side_cost, mid_cost] =
+        [left, right, &side[..], &mid[..]].map(estimate_cost);
+    let mode = [
+        (ChannelMode::Independent, left_cost + right_cost),
+        (ChannelMode::LeftSide, left_cost + side_cost),
+        (ChannelMode::SideRight, side_cost + right_cost),
+        (ChannelMode::MidSide, mid_cost + side_cost),
+    ]
+    .into_iter()
+    .min_by_key(|&(_, cost)| cost)
+    .map(|(mode, _)| mode)
+    .unwrap_or(ChannelMode::Independent);
+
+    // The side channel needs one extra bit
+    let side_bits = bits_per_sample + 1;
+    let subframes = match mode {
+        ChannelMode::Independent => vec![
+            (left.to_vec(), bits_per_sample),
+            (right.to_vec(), bits_per_sample),
+        ],
+        ChannelMode::LeftSide => vec![(left.to_vec(), bits_per_sample), (side, side_bits)],
+        ChannelMode::SideRight => vec![(side, side_bits), (right.to_vec(), bits_per_sample)],
+        ChannelMode::MidSide => vec![(mid, bits_per_sample), (side, side_bits)],
+    };
+    (mode, subframes)
+}
+
+/// Rough size of a channel: the magnitude of its second-order residual
+fn estimate_cost(samples: &[i32]) -> u64 {
+    samples
+        .windows(3)
+        .map(|window| u64::from((window[2] - 2 * window[1] + window[0]).unsigned_abs()))
+        .sum()
+}
+
+fn write_frame_header(
+    bits: &mut BitWriter,
+    block_size: usize,
+    frame_number: u64,
+    rate: u32,
+    channel_count: usize,
+    bits_per_sample: u32,
+    mode: ChannelMode,
+) {
+    let start = bits.len();
+
+    // Sync code and fixed-blocksize strategy
+    bits.write(0b1111_1111_1111_1000, 16);
+
+    let block_size_code = if block_size == BLOCK_SIZE {
+        0b1100
+    } else if block_size <= 256 {
+        0b0110
+    } else {
+        0b0111
+    };
+    bits.write(block_size_code, 4);
+    bits.write(u64::from(sample_rate_code(rate)), 4);
+
+    let channel_code = match mode {
+        ChannelMode::Independent => channel_count as u64 - 1,
+        ChannelMode::LeftSide => 0b1000,
+        ChannelMode::SideRight => 0b1001,
+        ChannelMode::MidSide => 0b1010,
+    };
+    bits.write(channel_code, 4);
+    bits.write(u64::from(sample_size_code(bits_per_sample)), 3);
+    // Reserved bit
+    bits.write(0, 1);
+
+    for byte in utf8_number(frame_number) {
+        bits.write(u64::from(byte), 8);
+    }
+    match block_size_code {
+        0b0110 => bits.write(block_size as u64 - 1, 8),
+        0b0111 => bits.write(block_size as u64 - 1, 16),
+        _ => {}
+    }
+
+    let crc = crc8(&bits.bytes()[start / 8..]);
+    bits.write(u64::from(crc), 8);
+}
+
+/// Sample rates with a dedicated header code; anything else is read from STREAMINFO
+fn sample_rate_code(rate: u32) -> u8 {
+    match rate {
+        88_200 => 0b0001,
+        176_400 => 0b0010,
+        192_000 => 0b0011,
+        8_000 => 0b0100,
+        16_000 => 0b0101,
+        22_050 => 0b0110,
+        24_000 => 0b0111,
+        32_000 => 0b1000,
+        44_100 => 0b1001,
+        48_000 => 0b1010,
+        96_000 => 0b1011,
+        _ => 0b0000,
+    }
+}
+
+/// Sample sizes with a dedicated header code; anything else is read from STREAMINFO
+fn sample_size_code(bits_per_sample: u32) -> u8 {
+    match bits_per_sample {
+        8 => 0b001,
+        12 => 0b010,
+        16 => 0b100,
+        20 => 0b101,
+        24 => 0b110,
+        _ => 0b000,
+    }
+}
+
+/// Frame numbers use the UTF-8 byte layout, extended to 36 bits
+fn utf8_number(value: u64) -> Vec<u8> {
+    if value < 0x80 {
+        return vec![value as u8];
+    }
+
+    let mut continuation = Vec::new();
+    let mut rest = value;
+    // Each leading byte of an n-byte sequence has 7 - n payload bits
+    while rest >= 1 << (6 - continuation.len()) {
+        continuation.push(0x80 | (rest & 0x3f) as u8);
+        rest >>= 6;
+    }
+
+    let len = continuation.len() + 1;
+    let lead = (0xff_u8 << (8 - len)) | rest as u8;
+    std::iter::once(lead)
+        .chain(continuation.into_iter().rev())
+        .collect()
+}
+
+fn write_subframe(bits: &mut BitWriter, samples: &[i32], bits_per_sample: u32) {
+    if samples.iter().all(|&sample| sample == samples[0]) {
+        // CONSTANT
+        bits.write(0, 8);
+        bits.write_signed(samples[0], bits_per_sample);
+        return;
+    }
+
+    let verbatim_bits = samples.len() as u64 * u64::from(bits_per_sample);
+    let order = best_fixed_order(samples);
+    let residual = fixed_residual(samples, order);
+    let rice = plan_rice(&residual, samples.len(), order, bits_per_sample);
+
+    let fixed_bits = order as u64 * u64::from(bits_per_sample) + rice.bits;
+    if fixed_bits >= verbatim_bits {
+        // VERBATIM
+        bits.write(0b0000_0010, 8);
+        for &sample in samples {
+            bits.write_signed(sample, bits_per_sample);
+        }
+        return;
+    }
+
+    // FIXED, with the predictor order in the low bits of the type
+    bits.write(0, 1);
+    bits.write(0b001000 | order as u64, 6);
+    bits.write(0, 1);
+    for &sample in &samples[..order] {
+        bits.write_signed(sample, bits_per_sample);
+    }
+    write_residual(bits, &residual, &rice);
+}
+
+fn best_fixed_order(samples: &[i32]) -> usize {
+    (0..=MAX_FIXED_ORDER.min(samples.len() - 1))
+        .min_by_key(|&order| {
+            fixed_residual(samples, order)
+                .iter()
+                .map(|residual| u64::from(residual.unsigned_abs()))
+                .sum::<u64>()
+        })
+        .unwrap_or(0)
+}
+
+/// Prediction error of the fixed polynomial predictor, skipping the warm-up samples
+fn fixed_residual(samples: &[i32], order: usize) -> Vec<i32> {
+    let s = samples;
+    (order..s.len())
+        .map(|i| match order {
+            0 => s[i],
+            1 => s[i] - s[i - 1],
+            2 => s[i] - 2 * s[i - 1] + s[i - 2],
+            3 => s[i] - 3 * s[i - 1] + 3 * s[i - 2] - s[i - 3],
+            _ => s[i] - 4 * s[i - 1] + 6 * s[i - 2] - 4 * s[i - 3] + s[i - 4],
+        })
+        .collect()
+}
+
+struct RicePlan {
+    /// Width of each Rice parameter: 4 bits (coding method 0) or 5 (method 1)
+    param_bits: u32,
+    partition_order: u32,
+    /// Residuals per partition, the first one is shorter by the predictor order
+    partition_len: usize,
+    params: Vec<u32>,
+    bits: u64,
+}
+
+/// Choose the partition order and per-partition Rice parameters with the fewest bits
+fn plan_rice(
+    residual: &[i32],
+    block_size: usize,
+    predictor_order: usize,
+    bits_per_sample: u32,
+) -> RicePlan {
+    // Deeper samples need parameters past 14 to code quiet passages compactly
+    let (param_bits, max_param) = if bits_per_sample > 16 {
+        (5, MAX_RICE2_PARAM)
+    } else {
+        (4, MAX_RICE_PARAM)
+    };
+    let folded: Vec<u64> = residual.iter().map(|&value| zigzag(value)).collect();
+    let mut best: Option<RicePlan> = None;
+
+    for partition_order in 0..=MAX_PARTITION_ORDER {
+        let partitions = 1 << partition_order;
+        if block_size % partitions != 0 || block_size / partitions <= predictor_order {
+            break;
+        }
+
+        let partition_len = block_size / partitions;
+        let mut start = 0;
+        let mut params = Vec::with_capacity(partitions);
+        // Coding method and partition order fields
+        let mut bits = 2 + 4;
+
+        for partition in 0..partitions {
+            // The first partition doesn't hold the warm-up samples
+            let len = if partition == 0 {
+                partition_len - predictor_order
+            } else {
+                partition_len
+            };
+            let (param, partition_bits) = best_rice_param(&folded[start..start + len], max_param);
+            params.push(param);
+            bits += u64::from(param_bits) + partition_bits;
+            start += len;
+        }
+
+        if best.as_ref().map_or(true, |plan| bits < plan.bits) {
+            best = Some(RicePlan {
+                param_bits,
+                partition_order,
+                partition_len,
+                params,
+                bits,
+            });
+        }
+    }
+
+    best.expect("a block always fits partition order 0")
+}
+
+fn best_rice_param(folded: &[u64], max_param: u32) -> (u32, u64) {
+    let rice_bits = |param: u32| {
+        folded
+            .iter()
+            .map(|&value| (value >> param) + 1 + u64::from(param))
+            .sum::<u64>()
+    };
+
+    // Start from the parameter suggested by the mean and check its neighbours
+    let sum: u64 = folded.iter().sum();
+    let mean = sum / (folded.len().max(1) as u64);
+    let guess = (u64::BITS - mean.leading_zeros()).min(max_param);
+
+    (guess.saturating_sub(1)..=(guess + 1).min(max_param))
+        .map(|param| (param, rice_bits(param)))
+        .min_by_key(|&(_, bits)| bits)
+        .unwrap_or((0, 0))
+}
+
+fn write_residual(bits: &mut BitWriter, residual: &[i32], plan: &RicePlan) {
+    // Coding method 0 has 4-bit Rice parameters, method 1 5-bit ones
+    bits.write(u64::from(plan.param_bits - 4), 2);
+    bits.write(u64::from(plan.partition_order), 4);
+
+    // Whatever the partitions don't cover was sent as warm-up samples
+    let warmup = plan.partition_len * plan.params.len() - residual.len();
+    let mut start = 0;
+    for (partition, &param) in plan.params.iter().enumerate() {
+        let len = if partition == 0 {
+            plan.partition_len - warmup
+        } else {
+            plan.partition_len
+        };
+        bits.write(u64::from(param), plan.param_bits);
+        for &value in &residual[start..start + len] {
+            let folded = zigzag(value);
+            bits.write_unary(folded >> param);
+            bits.write(folded & ((1 << param) - 1), param);
+        }
+        start += len;
+    }
+}
+
+fn zigzag(value: i32) -> u64 {
+    u64::from(((value << 1) ^ (value >> 31)) as u32)
+}
+
+/// MSB-first bit packer
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    accumulator: u64,
+    pending_bits: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u64, bits: u32) {
+        // Split wide writes so the accumulator never overflows
+        if bits > 32 {
+            self.write(value >> 32, bits - 32);
+            self.write(value & 0xffff_ffff, 32);
+            return;
+        }
+        if bits == 0 {
+            return;
+        }
+
+        let mask = (1u64 << bits) - 1;
+        self.accumulator = (self.accumulator << bits) | (value & mask);
+        self.pending_bits += bits;
+        while self.pending_bits >= 8 {
+            self.pending_bits -= 8;
+            self.bytes
+                .push((self.accumulator >> self.pending_bits) as u8);
+        }
+        self.accumulator &= (1u64 << self.pending_bits) - 1;
+    }
+
+    fn write_signed(&mut self, value: i32, bits: u32) {
+        self.write(u64::from(value as u32), bits);
+    }
+
+    fn write_unary(&mut self, zeros: u64) {
+        let mut zeros = zeros;
+        while zeros >= 32 {
+            self.write(0, 32);
+            zeros -= 32;
+        }
+        self.write(1, zeros as u32 + 1);
+    }
+
+    fn align(&mut self) {
+        if self.pending_bits > 0 {
+            self.write(0, 8 - self.pending_bits);
+        }
+    }
+
+    /// Bits written so far
+    fn len(&self) -> usize {
+        self.bytes.len() * 8 + self.pending_bits as usize
+    }
+
+    /// Completed bytes; only meaningful when byte-aligned
+    fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    fn into_bytes(mut self) -> Vec<u8> {
+        self.align();
+        self.bytes
+    }
+}
+
+fn crc8(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |mut crc, &byte| {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+fn crc16(bytes: &[u8]) -> u16 {
+    bytes.iter().fold(0u16, |mut crc, &byte| {
+        crc ^= u16::from(byte) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x8005
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use symphonia::core::audio::SampleBuffer;
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::probe::Hint;
+
+    /// Decode FLAC bytes back to interleaved samples at their own bit depth with Symphonia
+    pub(crate) fn decode_flac(bytes: Vec<u8>) -> (u32, usize, u32, Vec<i32>) {
+        let source = MediaSourceStream::new(Box::new(Cursor::new(bytes)), Default::default());
+        let mut hint = Hint::new();
+        hint.with_extension("flac");
+        let mut format = symphonia::default::get_probe()
+            .format(&hint, source, &Default::default(), &Default::default())
+            .unwrap()
+            .format;
+        let params = format.default_track().unwrap().codec_params.clone();
+        let mut decoder = symphonia::default::get_codecs()
+            .make(&params, &Default::default())
+            .unwrap();
+
+        let bits = params.bits_per_sample.unwrap();
+        let mut samples = Vec::new();
+        while let Ok(packet) = format.next_packet() {
+            let decoded = decoder.decode(&packet).unwrap();
+            let mut buffer = SampleBuffer::<i32>::new(decoded.capacity() as u64, *decoded.spec());
+            buffer.copy_interleaved_ref(decoded);
+            samples.extend(buffer.samples().iter().map(|&sample| sample >> (32 - bits)));
+        }
+        let channels = params.channels.unwrap().count();
+        (params.sample_rate.unwrap(), channels, bits, samples)
+    }
+
+    fn encode(samples: &[i32], sample_rate: u32, channels: u16, bits: u32) -> Vec<u8> {
+        let mut encoder =
+            FlacEncoder::new(Cursor::new(Vec::new()), sample_rate, channels, bits, 0).unwrap();
+        // Odd write sizes exercise the block buffering
+        for chunk in samples.chunks(1000 * usize::from(channels)) {
+            encoder.write(chunk).unwrap();
+        }
+        encoder.finish().unwrap().into_inner()
+    }
+
+    /// A sine with some noise, different per channel so stereo modes get picked
+    fn signal(frames: usize, channels: usize) -> Vec<i32> {
+        let mut seed = 1u32;
+        (0..frames * channels)
+            .map(|index| {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                let frame = (index / channels) as f64;
+                let channel = (index % channels) as f64;
+                let tone = (frame * 0.03 * (channel + 1.0)).sin() * 12_000.0;
+                (tone + f64::from(seed >> 24) - 128.0) as i32
+            })
+            .collect()
+    }
+
+    #[test]
+    fn round_trips_stereo_losslessly() {
+        let samples = signal(BLOCK_SIZE * 3 + 123, 2);
+        let bytes = encode(&samples, 44_100, 2, 16);
+
+        assert!(
+            bytes.len() < samples.len() * 2,
+            "FLAC should be smaller than PCM"
+        );
+        assert_eq!(decode_flac(bytes), (44_100, 2, 16, samples));
+    }
+
+    #[test]
+    fn round_trips_24_bit_losslessly() {
+        // Fine detail below the top 16 bits must survive
+        let samples: Vec<i32> = signal(BLOCK_SIZE * 2 + 55, 2)
+            .iter()
+            .enumerate()
+            .map(|(index, &sample)| sample * 256 + (index % 251) as i32 - 125)
+            .collect();
+        let bytes = encode(&samples, 96_000, 2, 24);
+
+        assert!(
+            bytes.len() < samples.len() * 3,
+            "FLAC should be smaller than PCM"
+        );
+        assert_eq!(decode_flac(bytes), (96_000, 2, 24, samples));
+    }
+
+    #[test]
+    fn rejects_bit_depths_it_cannot_write() {
+        assert!(FlacEncoder::new(Cursor::new(Vec::new()), 44_100, 2, 32, 0).is_err());
+        assert!(FlacEncoder::new(Cursor::new(Vec::new()), 44_100, 2, 0, 0).is_err());
+    }
+
+    #[test]
+    fn round_trips_mono_and_odd_rates() {
+        let samples = signal(BLOCK_SIZE + 7, 1);
+        assert_eq!(
+            decode_flac(encode(&samples, 37_800, 1, 16)),
+            (37_800, 1, 16, samples)
+        );
+    }
+
+    #[test]
+    fn round_trips_silence_and_full_scale_noise() {
+        let mut samples = vec![0i32; BLOCK_SIZE * 2];
+        let mut seed = 7u32;
+        samples.extend((0..BLOCK_SIZE * 2).map(|_| {
+            seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            i32::from((seed >> 16) as i16)
+        }));
+        assert_eq!(
+            decode_flac(encode(&samples, 48_000, 2, 16)),
+            (48_000, 2, 16, samples)
+        );
+    }
+
+    #[test]
+    fn patches_stream_info_with_the_real_length() {
+        let samples = signal(10_000, 2);
+        let bytes = encode(&samples, 44_100, 2, 16);
+
+        // Total samples are the low 36 bits of bytes 13..18 of STREAMINFO
+        let info = &bytes[STREAMINFO_OFFSET as usize..];
+        let total = info[13..18]
+            .iter()
+            .fold(0u64, |total, &byte| (total << 8) | u64::from(byte))
+            & ((1 << 36) - 1);
+        assert_eq!(total, 10_000);
+    }
+
+    #[test]
+    fn encodes_frame_numbers_like_utf8() {
+        assert_eq!(utf8_number(0x7f), vec![0x7f]);
+        assert_eq!(utf8_number(0x80), "\u{80}".as_bytes());
+        assert_eq!(utf8_number(0x20ac), "\u{20ac}".as_bytes());
+        assert_eq!(utf8_number(0x1_f600), "\u{1f600}".as_bytes());
+    }
+}
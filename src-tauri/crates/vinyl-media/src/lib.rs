@@ -0,0 +1,24 @@
+//! Native audio metadata, tag writing, decoding, loudness analysis and
+//! incremental library scanning for Vinyl's desktop shells.
+//!
+//! The Tauri backend links this crate directly; Electron spawns the
+//! `vinyl-media` binary and talks to it over stdin/stdout (see `main.rs`).
+
+mod ape;
+pub mod decode;
+mod error;
+pub mod flac;
+pub mod library_index;
+pub mod loudness;
+mod parallel;
+pub mod tag_writer;
+pub mod tags;
+
+pub use decode::{can_decode, decode_to_flac};
+pub use error::{Error, Result};
+pub use library_index::{
+    scan_folders, ScanIndex, ScanPhase, ScanProgress, ScanReport, ScannedFile, AUDIO_EXTENSIONS,
+};
+pub use loudness::{analyze_albums, AudibleRange, LoudnessResult, ReplayGain};
+pub use tag_writer::{write_tags, write_tags_batch, TagEdit, TagWrite, TagWriteResult};
+pub use tags::{read_metadata, read_metadata_batch, MetadataResult, ReadOptions, TrackMetadata};
@@ -0,0 +1,542 @@
+//! Incremental library scanning.
+//!
+//! A persistent index records the size, modification time and a partial
+//! content hash of every audio file under the library folders. Rescans only
+//! `stat` each file; new and modified files are hashed, and a removed path
+//! whose hash reappears elsewhere is reported as a move. Folders are walked
+//! level by level with many threads, since on network shares each directory
+//! listing is a round trip.
+
+use crate::parallel::parallel_map_with;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+/// Supported audio extensions (kept in sync with `electron/main.cjs`)
+pub const AUDIO_EXTENSIONS: &[&str] = &[
+    "mp3", "wav", "ogg", "flac", "aac", "m4a", "wma", "aiff", "ape", "opus", "webm",
+];
+
+/// Bump when the entry format or hash changes; older indexes are discarded
+const INDEX_VERSION: u32 = 1;
+
+/// Bytes hashed from each end of a file
+const HASH_CHUNK: u64 = 64 * 1024;
+
+/// Directory listings in flight at once; mostly waiting on disk or network
+const WALK_THREADS: usize = 32;
+
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexEntry {
+    pub size: u64,
+    /// Milliseconds since the Unix epoch
+    pub modified: u64,
+    /// FNV-1a of the size and the first and last 64 KiB
+    pub hash: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ScanIndex {
+    version: u32,
+    files: BTreeMap<String, IndexEntry>,
+}
+
+impl ScanIndex {
+    /// Load a saved index; a missing, unreadable or outdated one starts empty
+    pub fn load(path: &Path) -> Self {
+        fs::read(path)
+            .ok()
+            .and_then(|data| serde_json::from_slice::<Self>(&data).ok())
+            .filter(|index| index.version == INDEX_VERSION)
+            .unwrap_or_default()
+    }
+
+    /// Write through a temporary file so a crash never leaves half an index
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let temp = path.with_extension("tmp");
+        let file = File::create(&temp)?;
+        serde_json::to_writer(
+            BufWriter::new(file),
+            &Self {
+                version: INDEX_VERSION,
+                files: self.files.clone(),
+            },
+        )?;
+        fs::rename(temp, path)
+    }
+
+    /// Drop files the library no longer holds, so the next scan reports them
+    /// as added instead of unchanged. Returns how many were indexed.
+    pub fn forget<S: AsRef<str>>(&mut self, paths: &[S]) -> usize {
+        paths
+            .iter()
+            .filter(|path| self.files.remove(path.as_ref()).is_some())
+            .count()
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+/// A file as the renderer's `MusicFileInfo` describes it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ScannedFile {
+    pub path: String,
+    pub name: String,
+    pub extension: String,
+    /// Relative folder path from the scanned root (for playlist creation)
+    pub folder: Option<String>,
+}
+
+impl ScannedFile {
+    fn new(path: &Path, root: &Path) -> Self {
+        Self {
+            path: path.to_string_lossy().into_owned(),
+            name: path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            extension: extension_of(path).unwrap_or_default(),
+            folder: path
+                .parent()
+                .and_then(|parent| parent.strip_prefix(root).ok())
+                .map(|relative| relative.to_string_lossy().into_owned())
+                .filter(|relative| !relative.is_empty()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MovedFile {
+    pub from: String,
+    pub file: ScannedFile,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FolderStats {
+    pub path: String,
+    pub count: usize,
+    pub exists: bool,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanReport {
+    /// Files not in the index before
+    pub added: Vec<ScannedFile>,
+    /// Indexed files whose size or modification time changed
+    pub changed: Vec<ScannedFile>,
+    pub moved: Vec<MovedFile>,
+    /// Indexed files that are gone from folders that still exist
+    pub removed: Vec<String>,
+    pub unchanged: usize,
+    pub total_count: usize,
+    pub folder_stats: Vec<FolderStats>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScanPhase {
+    /// Listing folders; `total` is not known yet
+    Walking,
+    /// Hashing new and changed files
+    Hashing,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ScanProgress {
+    pub phase: ScanPhase,
+    pub current: usize,
+    pub total: usize,
+}
+
+/// Rate-limits progress callbacks from many threads
+struct Throttle<'a, F> {
+    report: &'a F,
+    last: Mutex<Option<Instant>>,
+}
+
+impl<'a, F: Fn(ScanProgress) + Sync> Throttle<'a, F> {
+    fn new(report: &'a F) -> Self {
+        Self {
+            report,
+            last: Mutex::new(None),
+        }
+    }
+
+    fn tick(&self, progress: ScanProgress) {
+        let mut last = self.last.lock().unwrap();
+        let due = last.map_or(true, |at| at.elapsed() >= PROGRESS_INTERVAL);
+        if due || progress.current == progress.total {
+            *last = Some(Instant::now());
+            (self.report)(progress);
+        }
+    }
+}
+
+struct FoundFile {
+    path: PathBuf,
+    size: u64,
+    modified: u64,
+    root: usize,
+}
+
+/// Rescan `roots` against `index`, updating it in place
+pub fn scan_folders<F>(roots: &[PathBuf], index: &mut ScanIndex, progress: F) -> ScanReport
+where
+    F: Fn(ScanProgress) + Sync,
+{
+    let throttle = Throttle::new(&progress);
+    let mut report = ScanReport::default();
+
+    let existing: Vec<usize> = (0..roots.len()).filter(|&i| roots[i].is_dir()).collect();
+    let found = walk(roots, &existing, &throttle);
+
+    for (i, root) in roots.iter().enumerate() {
+        report.folder_stats.push(FolderStats {
+            path: root.to_string_lossy().into_owned(),
+            count: found.iter().filter(|file| file.root == i).count(),
+            exists: existing.contains(&i),
+        });
+    }
+    report.total_count = found.len();
+
+    // Only new or modified files are read
+    let (unchanged, stale): (Vec<&FoundFile>, Vec<&FoundFile>) = found.iter().partition(|file| {
+        index
+            .files
+            .get(file.path.to_string_lossy().as_ref())
+            .is_some_and(|entry| entry.size == file.size && entry.modified == file.modified)
+    });
+    report.unchanged = unchanged.len();
+
+    let hashed = AtomicUsize::new(0);
+    let hashes = parallel_map_with(&stale, WALK_THREADS, |file| {
+        let hash = partial_hash(&file.path, file.size).ok();
+        let current = hashed.fetch_add(1, Ordering::Relaxed) + 1;
+        throttle.tick(ScanProgress {
+            phase: ScanPhase::Hashing,
+            current,
+            total: stale.len(),
+        });
+        hash
+    });
+
+    // Indexed files under a scanned root that weren't found; roots that are
+    // missing (an unmounted drive) are left alone
+    let found_paths: std::collections::HashSet<String> = found
+        .iter()
+        .map(|file| file.path.to_string_lossy().into_owned())
+        .collect();
+    let missing: Vec<String> = index
+        .files
+        .keys()
+        .filter(|path| {
+            existing
+                .iter()
+                .any(|&i| Path::new(path.as_str()).starts_with(&roots[i]))
+                && !found_paths.contains(*path)
+        })
+        .cloned()
+        .collect();
+    let mut missing_by_hash: HashMap<(u64, String), Vec<String>> = HashMap::new();
+    for path in &missing {
+        let entry = &index.files[path];
+        missing_by_hash
+            .entry((entry.size, entry.hash.clone()))
+            .or_default()
+            .push(path.clone());
+    }
+
+    for (file, hash) in stale.into_iter().zip(hashes) {
+        let Some(hash) = hash else {
+            // Unreadable right now; leave it for the next scan
+            continue;
+        };
+        let path = file.path.to_string_lossy().into_owned();
+        let scanned = ScannedFile::new(&file.path, &roots[file.root]);
+
+        if index.files.contains_key(&path) {
+            report.changed.push(scanned);
+        } else if let Some(from) = missing_by_hash
+            .get_mut(&(file.size, hash.clone()))
+            .and_then(Vec::pop)
+        {
+            index.files.remove(&from);
+            report.moved.push(MovedFile {
+                from,
+                file: scanned,
+            });
+        } else {
+            report.added.push(scanned);
+        }
+
+        index.files.insert(
+            path,
+            IndexEntry {
+                size: file.size,
+                modified: file.modified,
+                hash,
+            },
+        );
+    }
+
+    for path in missing_by_hash.into_values().flatten() {
+        index.files.remove(&path);
+        report.removed.push(path);
+    }
+    report.removed.sort();
+
+    report
+}
+
+/// List audio files under the existing roots, one directory level at a time
+fn walk<F: Fn(ScanProgress) + Sync>(
+    roots: &[PathBuf],
+    existing: &[usize],
+    throttle: &Throttle<'_, F>,
+) -> Vec<FoundFile> {
+    let mut level: Vec<(PathBuf, usize)> =
+        existing.iter().map(|&i| (roots[i].clone(), i)).collect();
+    let mut found = Vec::new();
+    let count = AtomicUsize::new(0);
+
+    while !level.is_empty() {
+        let listings = parallel_map_with(&level, WALK_THREADS, |(dir, root)| {
+            let listing = list_dir(dir, *root);
+            let current = count.fetch_add(listing.0.len(), Ordering::Relaxed) + listing.0.len();
+            throttle.tick(ScanProgress {
+                phase: ScanPhase::Walking,
+                current,
+                total: 0,
+            });
+            listing
+        });
+
+        level = Vec::new();
+        for (files, dirs) in listings {
+            found.extend(files);
+            level.extend(dirs);
+        }
+    }
+
+    found.sort_by(|a, b| a.path.cmp(&b.path));
+    found
+}
+
+fn list_dir(dir: &Path, root: usize) -> (Vec<FoundFile>, Vec<(PathBuf, usize)>) {
+    let mut files = Vec::new();
+    let mut dirs = Vec::new();
+
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(error) => {
+            eprintln!("Error scanning folder: {} {error}", dir.display());
+            return (files, dirs);
+        }
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        // Skip dotfiles like the watcher does (`.Trashes`, AppleDouble `._` files)
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+
+        if file_type.is_dir() {
+            dirs.push((path, root));
+        } else if file_type.is_file() && is_audio_file(&path) {
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            let modified = metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |since| since.as_millis() as u64);
+            files.push(FoundFile {
+                path,
+                size: metadata.len(),
+                modified,
+                root,
+            });
+        }
+    }
+
+    (files, dirs)
+}
+
+fn extension_of(path: &Path) -> Option<String> {
+    path.extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+}
+
+pub fn is_audio_file(path: &Path) -> bool {
+    extension_of(path).is_some_and(|ext| AUDIO_EXTENSIONS.contains(&ext.as_str()))
+}
+
+/// FNV-1a (64-bit) of the size plus the first and last 64 KiB. Enough to tell
+/// files apart without reading whole albums over the network
+fn partial_hash(path: &Path, size: u64) -> io::Result<String> {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut hash = OFFSET;
+    let mut feed = |bytes: &[u8]| {
+        for &byte in bytes {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(PRIME);
+        }
+    };
+    feed(&size.to_le_bytes());
+
+    let mut file = File::open(path)?;
+    let mut buffer = Vec::with_capacity(HASH_CHUNK as usize);
+    (&mut file).take(HASH_CHUNK).read_to_end(&mut buffer)?;
+    feed(&buffer);
+
+    if size > HASH_CHUNK * 2 {
+        buffer.clear();
+        file.seek(SeekFrom::End(-(HASH_CHUNK as i64)))?;
+        file.take(HASH_CHUNK).read_to_end(&mut buffer)?;
+        feed(&buffer);
+    }
+
+    Ok(format!("{hash:016x}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode::tests::temp_dir;
+
+    fn scan(roots: &[PathBuf], index: &mut ScanIndex) -> ScanReport {
+        scan_folders(roots, index, |_| {})
+    }
+
+    #[test]
+    fn rescans_report_only_differences() {
+        let dir = temp_dir("index-rescan");
+        let root = dir.join("music");
+        fs::create_dir_all(root.join("Album")).unwrap();
+        fs::create_dir_all(root.join(".hidden")).unwrap();
+        fs::write(root.join("Album/01.mp3"), b"one").unwrap();
+        fs::write(root.join("Album/02.mp3"), b"two").unwrap();
+        fs::write(root.join("Album/cover.jpg"), b"jpg").unwrap();
+        fs::write(root.join(".hidden/03.mp3"), b"three").unwrap();
+        let roots = vec![root.clone()];
+
+        let mut index = ScanIndex::default();
+        let first = scan(&roots, &mut index);
+        assert_eq!(first.added.len(), 2);
+        assert_eq!(first.added[0].folder.as_deref(), Some("Album"));
+        assert_eq!(index.len(), 2);
+
+        let second = scan(&roots, &mut index);
+        assert!(second.added.is_empty() && second.changed.is_empty());
+        assert_eq!(second.unchanged, 2);
+
+        fs::write(root.join("Album/01.mp3"), b"one, retagged").unwrap();
+        fs::rename(root.join("Album/02.mp3"), root.join("02 Renamed.mp3")).unwrap();
+        fs::write(root.join("new.flac"), b"new").unwrap();
+        let third = scan(&roots, &mut index);
+        assert_eq!(third.changed.len(), 1);
+        assert_eq!(third.changed[0].name, "01.mp3");
+        assert_eq!(third.moved.len(), 1);
+        assert!(third.moved[0].from.ends_with("02.mp3"));
+        assert_eq!(third.moved[0].file.name, "02 Renamed.mp3");
+        assert_eq!(third.moved[0].file.folder, None);
+        assert_eq!(third.added.len(), 1);
+        assert_eq!(third.added[0].name, "new.flac");
+
+        fs::remove_file(root.join("new.flac")).unwrap();
+        let fourth = scan(&roots, &mut index);
+        assert_eq!(fourth.removed.len(), 1);
+        assert!(fourth.removed[0].ends_with("new.flac"));
+        assert_eq!(index.len(), 2);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn forgotten_files_are_added_again() {
+        let dir = temp_dir("index-forget");
+        let root = dir.join("music");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("kept.mp3"), b"kept").unwrap();
+        fs::write(root.join("deleted.mp3"), b"deleted").unwrap();
+        let roots = vec![root.clone()];
+
+        let mut index = ScanIndex::default();
+        scan(&roots, &mut index);
+        let deleted = root.join("deleted.mp3").to_string_lossy().into_owned();
+        assert_eq!(index.forget(&[deleted, "/not/indexed.mp3".into()]), 1);
+
+        let report = scan(&roots, &mut index);
+        assert_eq!(report.unchanged, 1);
+        assert_eq!(report.added.len(), 1);
+        assert_eq!(report.added[0].name, "deleted.mp3");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn keeps_entries_of_missing_roots_and_round_trips() {
+        let dir = temp_dir("index-missing");
+        let root = dir.join("music");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("song.ogg"), b"ogg").unwrap();
+        let index_path = dir.join("index.json");
+
+        let roots = vec![root.clone()];
+        let mut index = ScanIndex::default();
+        scan(&roots, &mut index);
+        index.save(&index_path).unwrap();
+
+        // Unmounted drive: nothing is reported removed
+        let unmounted = dir.join("unmounted");
+        fs::rename(&root, &unmounted).unwrap();
+        let mut index = ScanIndex::load(&index_path);
+        let report = scan(&roots, &mut index);
+        assert!(report.removed.is_empty());
+        assert!(!report.folder_stats[0].exists);
+        assert_eq!(index.len(), 1);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn hashes_both_ends_of_large_files() {
+        let dir = temp_dir("index-hash");
+        let a = dir.join("a.mp3");
+        let b = dir.join("b.mp3");
+        let mut data = vec![0u8; (HASH_CHUNK * 3) as usize];
+        fs::write(&a, &data).unwrap();
+        *data.last_mut().unwrap() = 1;
+        fs::write(&b, &data).unwrap();
+
+        let size = data.len() as u64;
+        assert_ne!(
+            partial_hash(&a, size).unwrap(),
+            partial_hash(&b, size).unwrap()
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,474 @@
+//! EBU R128 / ITU-R BS.1770 loudness measurement.
+//!
+//! Fills in ReplayGain 2.0 values for files that have neither ReplayGain nor
+//! R128 tags: K-weighted, gated integrated loudness plus sample peak. Album
+//! loudness gates the blocks of every track together, as if the album were
+//! one long file, so quiet interludes don't pull the album gain up.
+//!
+//! The same pass finds each track's leading and trailing silence, which the
+//! player's crossfade overlaps.
+
+use crate::decode::AudioReader;
+use crate::parallel::parallel_map;
+use crate::Result;
+use serde::Serialize;
+use std::f64::consts::PI;
+use std::path::{Path, PathBuf};
+use symphonia::core::audio::SampleBuffer;
+
+/// ReplayGain 2.0 reference level in LUFS
+pub const REFERENCE_LOUDNESS: f64 = -18.0;
+
+/// R128 gain tags are relative to -23 LUFS, 5 dB below ReplayGain's reference
+const R128_REFERENCE_OFFSET: f64 = REFERENCE_LOUDNESS - -23.0;
+
+const ABSOLUTE_GATE: f64 = -70.0;
+const RELATIVE_GATE: f64 = -10.0;
+
+/// Blocks are 400 ms with 75% overlap, built from 100 ms steps
+const STEPS_PER_BLOCK: usize = 4;
+const STEPS_PER_SECOND: u32 = 10;
+
+/// Anything quieter than -50 dBFS counts as silence, the same threshold as the
+/// renderer's fallback for web songs (`findAudibleRange` in `src/lib/crossfade.ts`)
+const SILENCE_THRESHOLD: f64 = 0.003_162_277_660_168_38;
+
+/// Gains in dB relative to [`REFERENCE_LOUDNESS`]; peaks are linear (1.0 = full scale)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayGain {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub track_gain: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub track_peak: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub album_gain: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub album_peak: Option<f64>,
+}
+
+impl ReplayGain {
+    pub fn is_empty(&self) -> bool {
+        self.track_gain.is_none() && self.album_gain.is_none()
+    }
+}
+
+/// Convert an R128_*_GAIN tag (Q7.8 fixed point) to a ReplayGain gain
+pub fn r128_to_replay_gain(value: i16) -> f64 {
+    f64::from(value) / 256.0 + R128_REFERENCE_OFFSET
+}
+
+/// Seconds between a track's first and last audible sample
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct AudibleRange {
+    pub start: f64,
+    pub end: f64,
+}
+
+/// Gating blocks, sample peak and audible range of one decoded track
+#[derive(Debug, Clone, Default)]
+pub struct Measurement {
+    /// Mean square power of each 400 ms block, channel weighted
+    blocks: Vec<f64>,
+    peak: f64,
+    /// `None` for a track that is silent throughout
+    audible_range: Option<AudibleRange>,
+}
+
+impl Measurement {
+    /// Integrated loudness in LUFS, `None` for silence
+    pub fn loudness(&self) -> Option<f64> {
+        integrated_loudness(&self.blocks)
+    }
+
+    pub fn peak(&self) -> f64 {
+        self.peak
+    }
+
+    pub fn audible_range(&self) -> Option<AudibleRange> {
+        self.audible_range
+    }
+}
+
+/// Loudness for one file of an analysis request
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoudnessResult {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replay_gain: Option<ReplayGain>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audible_range: Option<AudibleRange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Second-order IIR section (transposed direct form II)
+#[derive(Debug, Clone, Copy)]
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    z: [f64; 2],
+}
+
+impl Biquad {
+    fn process(&mut self, x: f64) -> f64 {
+        let y = self.b[0] * x + self.z[0];
+        self.z[0] = self.b[1] * x - self.a[0] * y + self.z[1];
+        self.z[1] = self.b[2] * x - self.a[1] * y;
+        y
+    }
+}
+
+/// BS.1770 K-weighting: a high shelf for head effects, then a high pass.
+/// Coefficients are derived for any rate rather than the tabled 48 kHz ones.
+fn k_weighting(sample_rate: u32) -> [Biquad; 2] {
+    let rate = f64::from(sample_rate);
+
+    let shelf = {
+        let (f0, gain, q) = (1681.974450955533, 3.999843853973347, 0.7071752369554196);
+        let k = (PI * f0 / rate).tan();
+        let vh = 10f64.powf(gain / 20.0);
+        let vb = vh.powf(0.4996667741545416);
+        let a0 = 1.0 + k / q + k * k;
+        Biquad {
+            b: [
+                (vh + vb * k / q + k * k) / a0,
+                2.0 * (k * k - vh) / a0,
+                (vh - vb * k / q + k * k) / a0,
+            ],
+            a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+            z: [0.0; 2],
+        }
+    };
+
+    let high_pass = {
+        let (f0, q) = (38.13547087602444, 0.5003270373238773);
+        let k = (PI * f0 / rate).tan();
+        let a0 = 1.0 + k / q + k * k;
+        Biquad {
+            b: [1.0, -2.0, 1.0],
+            a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+            z: [0.0; 2],
+        }
+    };
+
+    [shelf, high_pass]
+}
+
+/// BS.1770 channel weights: surrounds count +1.5 dB, LFE is ignored
+fn channel_weights(channels: usize) -> Vec<f64> {
+    match channels {
+        // L R C LFE Ls Rs
+        6 => vec![1.0, 1.0, 1.0, 0.0, 1.41, 1.41],
+        // L R C Ls Rs
+        5 => vec![1.0, 1.0, 1.0, 1.41, 1.41],
+        _ => vec![1.0; channels],
+    }
+}
+
+/// Streaming meter fed interleaved frames
+struct Meter {
+    filters: Vec<[Biquad; 2]>,
+    weights: Vec<f64>,
+    step_len: usize,
+    step_frames: usize,
+    step_energy: f64,
+    /// Weighted mean square of each completed 100 ms step
+    steps: Vec<f64>,
+    peak: f64,
+    sample_rate: u32,
+    frames: u64,
+    /// First and last frame with a sample above [`SILENCE_THRESHOLD`]
+    audible_frames: Option<(u64, u64)>,
+}
+
+impl Meter {
+    fn new(sample_rate: u32, channels: usize) -> Self {
+        Self {
+            filters: vec![k_weighting(sample_rate); channels],
+            weights: channel_weights(channels),
+            step_len: (sample_rate / STEPS_PER_SECOND).max(1) as usize,
+            step_frames: 0,
+            step_energy: 0.0,
+            steps: Vec::new(),
+            peak: 0.0,
+            sample_rate,
+            frames: 0,
+            audible_frames: None,
+        }
+    }
+
+    fn push(&mut self, interleaved: &[f32]) {
+        let channels = self.filters.len();
+        for frame in interleaved.chunks_exact(channels) {
+            let mut audible = false;
+            for (channel, &sample) in frame.iter().enumerate() {
+                let sample = f64::from(sample);
+                self.peak = self.peak.max(sample.abs());
+                audible |= sample.abs() > SILENCE_THRESHOLD;
+
+                let [shelf, high_pass] = &mut self.filters[channel];
+                let weighted = high_pass.process(shelf.process(sample));
+                self.step_energy += self.weights[channel] * weighted * weighted;
+            }
+
+            if audible {
+                let first = self.audible_frames.map_or(self.frames, |(first, _)| first);
+                self.audible_frames = Some((first, self.frames));
+            }
+            self.frames += 1;
+
+            self.step_frames += 1;
+            if self.step_frames == self.step_len {
+                self.steps.push(self.step_energy / self.step_len as f64);
+                self.step_frames = 0;
+                self.step_energy = 0.0;
+            }
+        }
+    }
+
+    fn finish(self) -> Measurement {
+        let blocks = self
+            .steps
+            .windows(STEPS_PER_BLOCK)
+            .map(|steps| steps.iter().sum::<f64>() / STEPS_PER_BLOCK as f64)
+            .collect();
+        let rate = f64::from(self.sample_rate);
+        Measurement {
+            blocks,
+            peak: self.peak,
+            audible_range: self.audible_frames.map(|(first, last)| AudibleRange {
+                start: first as f64 / rate,
+                end: (last + 1) as f64 / rate,
+            }),
+        }
+    }
+}
+
+fn block_loudness(power: f64) -> f64 {
+    -0.691 + 10.0 * power.log10()
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// Gated integrated loudness of a set of blocks (BS.1770-4 §2.8)
+fn integrated_loudness(blocks: &[f64]) -> Option<f64> {
+    let above_absolute: Vec<f64> = blocks
+        .iter()
+        .copied()
+        .filter(|&power| power > 0.0 && block_loudness(power) > ABSOLUTE_GATE)
+        .collect();
+    if above_absolute.is_empty() {
+        return None;
+    }
+
+    let relative_gate = block_loudness(mean(&above_absolute)) + RELATIVE_GATE;
+    let gated: Vec<f64> = above_absolute
+        .into_iter()
+        .filter(|&power| block_loudness(power) > relative_gate)
+        .collect();
+    (!gated.is_empty()).then(|| block_loudness(mean(&gated)))
+}
+
+/// Decode a file and measure its loudness
+pub fn measure_file(path: &Path) -> Result<Measurement> {
+    let mut reader = AudioReader::open(path)?;
+    let mut meter: Option<Meter> = None;
+    let mut samples: Option<SampleBuffer<f32>> = None;
+
+    while let Some(decoded) = reader.next_buffer()? {
+        let spec = *decoded.spec();
+        let meter = meter.get_or_insert_with(|| Meter::new(spec.rate, spec.channels.count()));
+
+        let buffer = match &mut samples {
+            Some(buffer) if buffer.capacity() >= decoded.capacity() => buffer,
+            _ => samples.insert(SampleBuffer::new(decoded.capacity() as u64, spec)),
+        };
+        buffer.copy_interleaved_ref(decoded);
+        meter.push(buffer.samples());
+    }
+
+    Ok(meter.map(Meter::finish).unwrap_or_default())
+}
+
+/// Measure each album's tracks and fill in track and album gain.
+///
+/// Each inner list is one album; loose tracks can be passed as one-track
+/// albums. Results come back flattened in input order.
+pub fn analyze_albums(albums: &[Vec<PathBuf>]) -> Vec<LoudnessResult> {
+    let paths: Vec<&PathBuf> = albums.iter().flatten().collect();
+    let mut measurements = parallel_map(&paths, |path| measure_file(path)).into_iter();
+
+    let mut results = Vec::with_capacity(paths.len());
+    for album in albums {
+        let measured: Vec<_> = album.iter().zip(measurements.by_ref()).collect();
+
+        let album_blocks: Vec<f64> = measured
+            .iter()
+            .filter_map(|(_, measurement)| measurement.as_ref().ok())
+            .flat_map(|measurement| measurement.blocks.iter().copied())
+            .collect();
+        let album_gain =
+            integrated_loudness(&album_blocks).map(|loudness| REFERENCE_LOUDNESS - loudness);
+        let album_peak = measured
+            .iter()
+            .filter_map(|(_, measurement)| measurement.as_ref().ok())
+            .map(Measurement::peak)
+            .reduce(f64::max);
+
+        for (path, measurement) in measured {
+            let path = path.to_string_lossy().into_owned();
+            results.push(match measurement {
+                Ok(measurement) => LoudnessResult {
+                    path,
+                    replay_gain: Some(ReplayGain {
+                        track_gain: measurement
+                            .loudness()
+                            .map(|loudness| REFERENCE_LOUDNESS - loudness),
+                        track_peak: Some(measurement.peak()),
+                        album_gain,
+                        album_peak,
+                    }),
+                    audible_range: measurement.audible_range(),
+                    error: None,
+                },
+                Err(error) => LoudnessResult {
+                    path,
+                    replay_gain: None,
+                    audible_range: None,
+                    error: Some(error.to_string()),
+                },
+            });
+        }
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode::tests::{temp_dir, write_wav};
+    use std::fs;
+
+    /// Interleaved sine at `amplitude` on every channel
+    fn sine(rate: u32, channels: usize, seconds: f64, amplitude: f64) -> Vec<f32> {
+        let frames = (f64::from(rate) * seconds) as usize;
+        (0..frames)
+            .flat_map(|frame| {
+                let value = amplitude * (2.0 * PI * 1000.0 * frame as f64 / f64::from(rate)).sin();
+                std::iter::repeat(value as f32).take(channels)
+            })
+            .collect()
+    }
+
+    fn measure(rate: u32, channels: usize, samples: &[f32]) -> Measurement {
+        let mut meter = Meter::new(rate, channels);
+        meter.push(samples);
+        meter.finish()
+    }
+
+    fn decibels(db: f64) -> f64 {
+        10f64.powf(db / 20.0)
+    }
+
+    #[test]
+    fn measures_the_ebu_reference_tone() {
+        // EBU Tech 3341 case 1: stereo 1 kHz at -23 dBFS reads -23 LUFS
+        for rate in [44_100, 48_000, 96_000] {
+            let measurement = measure(rate, 2, &sine(rate, 2, 20.0, decibels(-23.0)));
+            let loudness = measurement.loudness().unwrap();
+            assert!((loudness + 23.0).abs() < 0.1, "{rate} Hz: {loudness}");
+            assert!((measurement.peak() - decibels(-23.0)).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn gates_out_silence() {
+        let rate = 48_000;
+        let mut samples = sine(rate, 2, 10.0, decibels(-20.0));
+        samples.extend(vec![0.0; rate as usize * 2 * 20]);
+
+        let loudness = measure(rate, 2, &samples).loudness().unwrap();
+        let tone_only = measure(rate, 2, &sine(rate, 2, 10.0, decibels(-20.0)))
+            .loudness()
+            .unwrap();
+        assert!(
+            (loudness - tone_only).abs() < 0.1,
+            "{loudness} vs {tone_only}"
+        );
+
+        assert_eq!(measure(rate, 2, &vec![0.0; 96_000]).loudness(), None);
+    }
+
+    #[test]
+    fn album_loudness_gates_tracks_together() {
+        let rate = 48_000;
+        let loud = measure(rate, 1, &sine(rate, 1, 10.0, decibels(-10.0)));
+        let quiet = measure(rate, 1, &sine(rate, 1, 10.0, decibels(-30.0)));
+
+        let blocks: Vec<f64> = loud.blocks.iter().chain(&quiet.blocks).copied().collect();
+        let album = integrated_loudness(&blocks).unwrap();
+        // The quiet track falls under the relative gate
+        assert!((album - loud.loudness().unwrap()).abs() < 0.1, "{album}");
+    }
+
+    #[test]
+    fn finds_leading_and_trailing_silence() {
+        let rate = 10;
+        // Quieter than -50 dBFS counts as silence
+        let samples = [0.0, 0.001, 0.0, 0.5, 0.0, -0.2, 0.0, 0.001, 0.0, 0.0];
+        assert_eq!(
+            measure(rate, 1, &samples).audible_range(),
+            Some(AudibleRange {
+                start: 0.3,
+                end: 0.6
+            })
+        );
+
+        // Any channel being audible counts
+        let stereo = [0.0, 0.0, 0.0, 0.3, 0.4, 0.0, 0.0, 0.0];
+        let range = measure(2, 2, &stereo).audible_range().unwrap();
+        assert_eq!((range.start, range.end), (0.5, 1.5));
+
+        assert_eq!(
+            measure(rate, 1, &[0.0, 0.001, -0.001]).audible_range(),
+            None
+        );
+    }
+
+    #[test]
+    fn converts_r128_gain() {
+        assert_eq!(r128_to_replay_gain(0), 5.0);
+        assert_eq!(r128_to_replay_gain(-256 * 3), 2.0);
+    }
+
+    #[test]
+    fn analyzes_files_per_album() {
+        let dir = temp_dir("loudness");
+        let first = dir.join("first.wav");
+        let second = dir.join("second.wav");
+        let broken = dir.join("broken.mp3");
+        write_wav(&first, 44_100, 2, 44_100 * 2);
+        write_wav(&second, 44_100, 2, 44_100);
+        fs::write(&broken, b"not audio").unwrap();
+
+        let results = analyze_albums(&[vec![first.clone(), second], vec![broken]]);
+        assert_eq!(results.len(), 3);
+
+        let gain = results[0].replay_gain.unwrap();
+        let track_gain = gain.track_gain.unwrap();
+        assert!(gain.album_gain.is_some());
+        assert_eq!(gain.album_gain, results[1].replay_gain.unwrap().album_gain);
+        assert_eq!(gain.track_peak, Some(1000.0 / 32768.0));
+        // The ramp peaks at 1000/32768 (about -30 dBFS), so it needs boosting
+        assert!(track_gain > 0.0, "{track_gain}");
+
+        assert!(results[2].error.is_some());
+        assert_eq!(results[0].path, first.to_string_lossy());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
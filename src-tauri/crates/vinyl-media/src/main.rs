@@ -0,0 +1,159 @@
+//! `vinyl-media` sidecar for the Electron shell.
+//!
+//! - `vinyl-media tags [--no-cover-art]`, with one file path per line on
+//!   stdin. Writes one JSON `MetadataResult` per line to stdout, in input
+//!   order, flushing after every batch so the caller can report progress.
+//! - `vinyl-media decode <input> <output.flac>` decodes AAC/ALAC/APE to FLAC
+//!   at the source's bit depth, flushing every frame so the output can be
+//!   played while it grows.
+//! - `vinyl-media loudness`, with a JSON array of albums (arrays of paths) on
+//!   stdin. Writes one JSON `LoudnessResult` per line to stdout, in input order.
+//! - `vinyl-media write-tags <backup-dir>`, with a JSON array of `TagWrite`s
+//!   on stdin. Writes one JSON `TagWriteResult` per line to stdout, in input
+//!   order, keeping a copy of each original in the backup folder.
+//! - `vinyl-media scan <index.json>`, with a JSON array of folders on stdin.
+//!   Rescans them against the saved index, writing `{"progress": ...}` lines
+//!   while it works and a final `{"report": ...}` line, then saves the index.
+//! - `vinyl-media forget <index.json>`, with a JSON array of file paths on
+//!   stdin. Drops them from the saved index and writes how many it held.
+
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use vinyl_media::{ReadOptions, ScanIndex, ScanProgress, ScanReport, TagWrite};
+
+/// Files read in parallel before results are flushed
+const BATCH_SIZE: usize = 64;
+
+fn write_batch(
+    batch: &mut Vec<PathBuf>,
+    options: ReadOptions,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    for result in vinyl_media::read_metadata_batch(batch, options) {
+        serde_json::to_writer(&mut *out, &result)?;
+        out.write_all(b"\n")?;
+    }
+    batch.clear();
+    out.flush()
+}
+
+fn run_tags(options: ReadOptions) -> io::Result<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    let mut batch = Vec::with_capacity(BATCH_SIZE);
+
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        if line.is_empty() {
+            continue;
+        }
+        batch.push(PathBuf::from(line));
+        if batch.len() == BATCH_SIZE {
+            write_batch(&mut batch, options, &mut out)?;
+        }
+    }
+    write_batch(&mut batch, options, &mut out)
+}
+
+fn run_loudness() -> io::Result<()> {
+    let albums: Vec<Vec<PathBuf>> = serde_json::from_reader(io::stdin().lock())?;
+    let mut out = BufWriter::new(io::stdout().lock());
+    for result in vinyl_media::analyze_albums(&albums) {
+        serde_json::to_writer(&mut out, &result)?;
+        out.write_all(b"\n")?;
+    }
+    out.flush()
+}
+
+fn run_write_tags(backup_dir: &Path) -> io::Result<()> {
+    let writes: Vec<TagWrite> = serde_json::from_reader(io::stdin().lock())?;
+    let mut out = BufWriter::new(io::stdout().lock());
+    for result in vinyl_media::write_tags_batch(&writes, backup_dir) {
+        serde_json::to_writer(&mut out, &result)?;
+        out.write_all(b"\n")?;
+    }
+    out.flush()
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+enum ScanMessage<'a> {
+    Progress(ScanProgress),
+    Report(&'a ScanReport),
+}
+
+fn write_message(out: &mut impl Write, message: &ScanMessage) -> io::Result<()> {
+    serde_json::to_writer(&mut *out, message)?;
+    out.write_all(b"\n")?;
+    out.flush()
+}
+
+fn run_scan(index_path: &Path) -> io::Result<()> {
+    let roots: Vec<PathBuf> = serde_json::from_reader(io::stdin().lock())?;
+    let mut index = ScanIndex::load(index_path);
+
+    let report = vinyl_media::scan_folders(&roots, &mut index, |progress| {
+        // A closed pipe surfaces when the report is written
+        let _ = write_message(&mut io::stdout().lock(), &ScanMessage::Progress(progress));
+    });
+    index.save(index_path)?;
+    write_message(&mut io::stdout().lock(), &ScanMessage::Report(&report))
+}
+
+fn run_forget(index_path: &Path) -> io::Result<()> {
+    let paths: Vec<String> = serde_json::from_reader(io::stdin().lock())?;
+    let mut index = ScanIndex::load(index_path);
+    let forgotten = index.forget(&paths);
+    if forgotten > 0 {
+        index.save(index_path)?;
+    }
+    let mut out = io::stdout().lock();
+    serde_json::to_writer(&mut out, &forgotten)?;
+    out.write_all(b"\n")?;
+    out.flush()
+}
+
+fn run_decode(input: &Path, output: &Path) -> io::Result<()> {
+    let result = File::create(output).and_then(|file| {
+        vinyl_media::decode_to_flac(input, BufWriter::new(file))
+            .map(drop)
+            .map_err(io::Error::other)
+    });
+    if result.is_err() {
+        let _ = fs::remove_file(output);
+    }
+    result
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let result = match args.first().map(String::as_str) {
+        Some("tags") => run_tags(ReadOptions {
+            cover_art: !args.iter().any(|arg| arg == "--no-cover-art"),
+        }),
+        Some("decode") if args.len() == 3 => run_decode(Path::new(&args[1]), Path::new(&args[2])),
+        Some("loudness") => run_loudness(),
+        Some("write-tags") if args.len() == 2 => run_write_tags(Path::new(&args[1])),
+        Some("scan") if args.len() == 2 => run_scan(Path::new(&args[1])),
+        Some("forget") if args.len() == 2 => run_forget(Path::new(&args[1])),
+        _ => {
+            eprintln!("Usage: vinyl-media tags [--no-cover-art] < paths");
+            eprintln!("       vinyl-media decode <input> <output.flac>");
+            eprintln!("       vinyl-media loudness < albums.json");
+            eprintln!("       vinyl-media write-tags <backup-dir> < writes.json");
+            eprintln!("       vinyl-media scan <index.json> < folders.json");
+            eprintln!("       vinyl-media forget <index.json> < paths.json");
+            return ExitCode::from(2);
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("vinyl-media: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,422 @@
+//! Writing edited tags back to audio files.
+//!
+//! Each file's primary tag is updated in place of its format: ID3v2.4 for
+//! MP3/WAV/AIFF, Vorbis comments for FLAC/Ogg/Opus, MP4 atoms for M4A and APE
+//! tags for APE/WavPack/Musepack. Other tags the file already carries (e.g. an
+//! ID3v1 trailer) get the same changes so readers agree.
+//!
+//! Writes never touch the original until the new file is complete: the file
+//! is copied next to itself, tagged and re-read, a backup of the original is
+//! kept, and only then is the copy renamed over the original.
+
+use crate::tags::{read_metadata, ReadOptions};
+use crate::{Error, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use lofty::config::WriteOptions;
+use lofty::file::{AudioFile, TaggedFileExt};
+use lofty::picture::{MimeType, Picture, PictureType};
+use lofty::probe::Probe;
+use lofty::tag::items::Timestamp;
+use lofty::tag::{Accessor, ItemKey, Tag};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Disk space kept for backups of edited files; the newest is always kept
+const BACKUP_BUDGET_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Fields to change, deserialized from the renderer's `TagEdit`. A missing
+/// field is left as it is; an empty string or 0 removes it from the file
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagEdit {
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album_artist: Option<String>,
+    pub album: Option<String>,
+    pub genre: Option<String>,
+    pub track_number: Option<u32>,
+    pub disc_number: Option<u32>,
+    pub year: Option<u16>,
+    /// Front cover as a data URL
+    pub cover_art: Option<String>,
+}
+
+/// One file to retag
+#[derive(Debug, Clone, Deserialize)]
+pub struct TagWrite {
+    pub path: PathBuf,
+    pub edit: TagEdit,
+}
+
+/// Outcome for one file of a batch write
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagWriteResult {
+    pub path: String,
+    /// Copy of the file as it was before the write
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backup_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// `Some(None)` removes the cover
+fn parse_cover(cover_art: Option<&str>) -> Result<Option<Option<Picture>>> {
+    let Some(url) = cover_art else {
+        return Ok(None);
+    };
+    if url.is_empty() {
+        return Ok(Some(None));
+    }
+
+    let invalid = || Error::Unsupported("cover art must be a base64 data URL".into());
+    let (header, payload) = url
+        .strip_prefix("data:")
+        .and_then(|rest| rest.split_once(','))
+        .ok_or_else(invalid)?;
+    let mime_type = header.strip_suffix(";base64").ok_or_else(invalid)?;
+    let data = BASE64.decode(payload).map_err(|_| invalid())?;
+
+    let picture = Picture::unchecked(data)
+        .pic_type(PictureType::CoverFront)
+        .mime_type(MimeType::from_str(mime_type))
+        .build();
+    Ok(Some(Some(picture)))
+}
+
+fn set_text(tag: &mut Tag, value: Option<&str>, set: fn(&mut Tag, String), remove: fn(&mut Tag)) {
+    match value.map(str::trim) {
+        Some("") => remove(tag),
+        Some(value) => set(tag, value.to_string()),
+        None => {}
+    }
+}
+
+fn set_number(tag: &mut Tag, value: Option<u32>, set: fn(&mut Tag, u32), remove: fn(&mut Tag)) {
+    match value {
+        Some(0) => remove(tag),
+        Some(value) => set(tag, value),
+        None => {}
+    }
+}
+
+fn apply_edit(tag: &mut Tag, edit: &TagEdit, cover: Option<&Option<Picture>>) {
+    set_text(
+        tag,
+        edit.title.as_deref(),
+        Tag::set_title,
+        Tag::remove_title,
+    );
+    set_text(
+        tag,
+        edit.artist.as_deref(),
+        Tag::set_artist,
+        Tag::remove_artist,
+    );
+    set_text(
+        tag,
+        edit.album.as_deref(),
+        Tag::set_album,
+        Tag::remove_album,
+    );
+    set_text(
+        tag,
+        edit.genre.as_deref(),
+        Tag::set_genre,
+        Tag::remove_genre,
+    );
+    set_number(tag, edit.track_number, Tag::set_track, Tag::remove_track);
+    set_number(tag, edit.disc_number, Tag::set_disk, Tag::remove_disk);
+
+    match edit.album_artist.as_deref().map(str::trim) {
+        Some("") => tag.remove_key(ItemKey::AlbumArtist),
+        Some(value) => {
+            tag.insert_text(ItemKey::AlbumArtist, value.to_string());
+        }
+        None => {}
+    }
+
+    match edit.year {
+        Some(0) => tag.remove_date(),
+        Some(year) => tag.set_date(Timestamp {
+            year,
+            ..Timestamp::default()
+        }),
+        None => {}
+    }
+
+    match cover {
+        // Readers fall back to any picture, so removing clears them all
+        Some(None) => {
+            while !tag.pictures().is_empty() {
+                tag.remove_picture(0);
+            }
+        }
+        Some(Some(picture)) => {
+            tag.remove_picture_type(PictureType::CoverFront);
+            tag.push_picture(picture.clone());
+        }
+        None => {}
+    }
+}
+
+/// Tag `path` in place. Runs on a copy so a failed write leaves the file as
+/// it was
+fn retag(path: &Path, edit: &TagEdit, cover: Option<&Option<Picture>>) -> Result<()> {
+    let mut tagged = Probe::open(path)?.guess_file_type()?.read()?;
+
+    // Files without the format's main tag get one, seeded from whatever tag
+    // they do have (e.g. ID3v1) so existing fields carry over
+    if tagged.primary_tag().is_none() {
+        let primary_type = tagged.primary_tag_type();
+        let mut tag = tagged
+            .first_tag()
+            .cloned()
+            .unwrap_or_else(|| Tag::new(primary_type));
+        tag.re_map(primary_type);
+        tagged.insert_tag(tag);
+    }
+
+    let tag_types: Vec<_> = tagged.tags().iter().map(Tag::tag_type).collect();
+    for tag_type in tag_types {
+        if let Some(tag) = tagged.tag_mut(tag_type) {
+            apply_edit(tag, edit, cover);
+        }
+    }
+    tagged.save_to_path(path, WriteOptions::default())?;
+
+    // Make sure what was written still parses before it replaces anything
+    read_metadata(path, ReadOptions { cover_art: false })?;
+    Ok(())
+}
+
+fn unix_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis())
+}
+
+/// Delete the oldest backups once they outgrow the budget
+fn prune_backups(backup_dir: &Path) -> Result<()> {
+    let mut backups: Vec<(SystemTime, u64, PathBuf)> = fs::read_dir(backup_dir)?
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let metadata = entry.metadata().ok().filter(fs::Metadata::is_file)?;
+            Some((metadata.modified().ok()?, metadata.len(), entry.path()))
+        })
+        .collect();
+    backups.sort_by_key(|(modified, ..)| Reverse(*modified));
+
+    let mut total = 0;
+    for (index, (_, size, path)) in backups.into_iter().enumerate() {
+        total += size;
+        if index > 0 && total > BACKUP_BUDGET_BYTES {
+            fs::remove_file(path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Write `edit` to the file at `path`, keeping a copy of the original in
+/// `backup_dir`. Returns the backup's path
+pub fn write_tags(path: &Path, edit: &TagEdit, backup_dir: &Path) -> Result<PathBuf> {
+    let cover = parse_cover(edit.cover_art.as_deref())?;
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| Error::Unsupported(format!("not a file: {}", path.display())))?
+        .to_string_lossy()
+        .into_owned();
+
+    // Same folder, so the final rename can't cross file systems
+    let temp_path = path.with_file_name(format!(".{file_name}.vinyl-tmp"));
+    fs::copy(path, &temp_path)?;
+    if let Err(error) = retag(&temp_path, edit, cover.as_ref()) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(error);
+    }
+
+    fs::create_dir_all(backup_dir)?;
+    // Batches can write same-named files from different folders at once
+    let stamp = unix_millis();
+    let backup_path = (0..)
+        .map(|attempt| backup_dir.join(format!("{stamp}-{attempt}-{file_name}")))
+        .find(|candidate| !candidate.exists())
+        .expect("unbounded range");
+    if let Err(error) = fs::copy(path, &backup_path) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(error.into());
+    }
+    if let Err(error) = fs::rename(&temp_path, path) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(error.into());
+    }
+
+    if let Err(error) = prune_backups(backup_dir) {
+        eprintln!("[Tags] Failed to prune backups: {error}");
+    }
+    Ok(backup_path)
+}
+
+/// Write many files one after another, keeping the input order
+pub fn write_tags_batch(writes: &[TagWrite], backup_dir: &Path) -> Vec<TagWriteResult> {
+    writes
+        .iter()
+        .map(|write| {
+            let path = write.path.to_string_lossy().into_owned();
+            match write_tags(&write.path, &write.edit, backup_dir) {
+                Ok(backup_path) => TagWriteResult {
+                    path,
+                    backup_path: Some(backup_path.to_string_lossy().into_owned()),
+                    error: None,
+                },
+                Err(error) => TagWriteResult {
+                    path,
+                    backup_path: None,
+                    error: Some(error.to_string()),
+                },
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode::tests::{temp_dir, write_wav};
+    use crate::flac::FlacEncoder;
+    use crate::tags::read_metadata;
+    use std::fs::File;
+
+    // 1x1 transparent PNG
+    const PNG_DATA_URL: &str = "data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNk+M9QDwADhgGAWjR9awAAAABJRU5ErkJggg==";
+
+    fn write_flac(path: &Path) {
+        let mut encoder =
+            FlacEncoder::new(File::create(path).unwrap(), 8_000, 1, 16, 8_000).unwrap();
+        encoder.write(&vec![0; 8_000]).unwrap();
+        encoder.finish().unwrap();
+    }
+
+    fn full_edit() -> TagEdit {
+        TagEdit {
+            title: Some("New Title".into()),
+            artist: Some("New Artist".into()),
+            album_artist: Some("Various Artists".into()),
+            album: Some("New Album".into()),
+            genre: Some("Jazz".into()),
+            track_number: Some(3),
+            disc_number: Some(2),
+            year: Some(1999),
+            cover_art: Some(PNG_DATA_URL.into()),
+        }
+    }
+
+    fn assert_edited(path: &Path) {
+        let metadata = read_metadata(path, ReadOptions::default()).unwrap();
+        assert_eq!(metadata.title, "New Title");
+        assert_eq!(metadata.artist, "New Artist");
+        assert_eq!(metadata.album_artist.as_deref(), Some("Various Artists"));
+        assert_eq!(metadata.album, "New Album");
+        assert_eq!(metadata.genre.as_deref(), Some("Jazz"));
+        assert_eq!(metadata.track_number, Some(3));
+        assert_eq!(metadata.disc_number, Some(2));
+        assert_eq!(metadata.year, Some(1999));
+        assert_eq!(metadata.cover_art.as_deref(), Some(PNG_DATA_URL));
+    }
+
+    #[test]
+    fn writes_id3v2_to_wav() {
+        let dir = temp_dir("write-wav");
+        let backups = dir.join("backups");
+        let path = dir.join("track.wav");
+        write_wav(&path, 8_000, 1, 8_000);
+        let original = fs::read(&path).unwrap();
+
+        let backup = write_tags(&path, &full_edit(), &backups).unwrap();
+        assert_edited(&path);
+        assert_eq!(fs::read(backup).unwrap(), original);
+        assert!(!dir.join(".track.wav.vinyl-tmp").exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn writes_vorbis_comments_to_flac() {
+        let dir = temp_dir("write-flac");
+        let path = dir.join("track.flac");
+        write_flac(&path);
+
+        write_tags(&path, &full_edit(), &dir.join("backups")).unwrap();
+        assert_edited(&path);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn leaves_missing_fields_and_clears_empty_ones() {
+        let dir = temp_dir("write-partial");
+        let backups = dir.join("backups");
+        let path = dir.join("track.flac");
+        write_flac(&path);
+        write_tags(&path, &full_edit(), &backups).unwrap();
+
+        let edit = TagEdit {
+            album: Some("Renamed".into()),
+            genre: Some(String::new()),
+            track_number: Some(0),
+            cover_art: Some(String::new()),
+            ..TagEdit::default()
+        };
+        write_tags(&path, &edit, &backups).unwrap();
+
+        let metadata = read_metadata(&path, ReadOptions::default()).unwrap();
+        assert_eq!(metadata.title, "New Title");
+        assert_eq!(metadata.album, "Renamed");
+        assert_eq!(metadata.genre, None);
+        assert_eq!(metadata.track_number, None);
+        assert_eq!(metadata.disc_number, Some(2));
+        assert_eq!(metadata.cover_art, None);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn failed_writes_leave_the_file_alone() {
+        let dir = temp_dir("write-fail");
+        let backups = dir.join("backups");
+        let bad = dir.join("bad.mp3");
+        fs::write(&bad, b"not audio").unwrap();
+        let good = dir.join("good.wav");
+        write_wav(&good, 8_000, 1, 8_000);
+        let original = fs::read(&good).unwrap();
+
+        let invalid_cover = TagEdit {
+            cover_art: Some("https://example.com/cover.jpg".into()),
+            ..full_edit()
+        };
+        let results = write_tags_batch(
+            &[
+                TagWrite {
+                    path: bad.clone(),
+                    edit: full_edit(),
+                },
+                TagWrite {
+                    path: good.clone(),
+                    edit: invalid_cover,
+                },
+            ],
+            &backups,
+        );
+        assert!(results.iter().all(|result| result.error.is_some()));
+        assert_eq!(fs::read(&bad).unwrap(), b"not audio");
+        assert_eq!(fs::read(&good).unwrap(), original);
+        assert!(!dir.join(".bad.mp3.vinyl-tmp").exists());
+        assert!(!backups.exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,439 @@
+//! Tag and stream property reading.
+//!
+//! Mirrors `extractMetadata` in `src/lib/audioMetadata.ts`: the result has the
+//! same shape as the `Partial<Song>` the renderer builds, including the
+//! filename fallbacks for untagged files.
+
+use crate::loudness::{r128_to_replay_gain, ReplayGain};
+use crate::parallel::parallel_map;
+use crate::Result;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use lofty::config::ParseOptions;
+use lofty::file::FileType;
+use lofty::picture::{Picture, PictureType};
+use lofty::prelude::*;
+use lofty::probe::Probe;
+use lofty::tag::Tag;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const UNKNOWN_ARTIST: &str = "Unknown Artist";
+const UNKNOWN_ALBUM: &str = "Unknown Album";
+
+/// Metadata for one file, serialized as a `Partial<Song>`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackMetadata {
+    pub title: String,
+    pub artist: String,
+    pub album: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub album_artist: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub track_number: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disc_number: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub genre: Option<String>,
+    /// Release year, from the recording date or year tag
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub year: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub composer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bpm: Option<u32>,
+    /// Duration in seconds, from the stream headers
+    pub duration: f64,
+    /// Audio bitrate in kbps
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bitrate: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample_rate: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bit_depth: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channels: Option<u8>,
+    /// Short codec name, same vocabulary as `normalizeCodec` in the renderer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub codec: Option<&'static str>,
+    /// Embedded cover art, or a cover/folder/front image next to the file, as
+    /// a data URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cover_art: Option<String>,
+    pub source_type: &'static str,
+    pub file_name: String,
+    pub file_size: u64,
+    pub file_path: String,
+    /// ReplayGain values from ReplayGain or R128 tags
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replay_gain: Option<ReplayGain>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ReadOptions {
+    /// Embedded pictures are the bulk of most tags, skip them when not needed
+    pub cover_art: bool,
+}
+
+impl Default for ReadOptions {
+    fn default() -> Self {
+        Self { cover_art: true }
+    }
+}
+
+/// Outcome for one path of a batch read
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetadataResult {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<TrackMetadata>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Read tags and stream properties from an audio file
+pub fn read_metadata(path: &Path, options: ReadOptions) -> Result<TrackMetadata> {
+    let file_size = path.metadata()?.len();
+    let tagged = Probe::open(path)?
+        .options(ParseOptions::new().read_cover_art(options.cover_art))
+        .guess_file_type()?
+        .read()?;
+
+    // Primary tag first (e.g. ID3v2 over ID3v1), falling back field by field
+    let tags: Vec<&Tag> = tagged
+        .primary_tag()
+        .into_iter()
+        .chain(tagged.tags().iter())
+        .collect();
+    let first = |get: fn(&Tag) -> Option<String>| tags.iter().find_map(|tag| get(tag));
+
+    let title = first(|tag| non_empty(tag.title()));
+    let artist = first(|tag| non_empty(tag.artist()))
+        .or_else(|| first(|tag| non_empty(tag.get_string(ItemKey::AlbumArtist))));
+    let album = first(|tag| non_empty(tag.album()));
+    let album_artist = first(|tag| non_empty(tag.get_string(ItemKey::AlbumArtist)));
+    let track_number = tags.iter().find_map(|tag| tag.track()).filter(|&n| n > 0);
+    let disc_number = tags.iter().find_map(|tag| tag.disk()).filter(|&n| n > 0);
+    let genre = first(|tag| non_empty(tag.genre()));
+    let year = tags
+        .iter()
+        .find_map(|tag| tag.date())
+        .map(|date| date.year)
+        .filter(|&year| year > 0);
+    let composer = first(|tag| non_empty(tag.get_string(ItemKey::Composer)));
+    let bpm = tags
+        .iter()
+        .find_map(|tag| {
+            tag.get_string(ItemKey::IntegerBpm)
+                .or_else(|| tag.get_string(ItemKey::Bpm))
+                .and_then(parse_leading_number)
+        })
+        .map(|bpm| bpm.round() as u32)
+        .filter(|&bpm| bpm > 0);
+    let properties = tagged.properties();
+
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let (title, artist) = resolve_title_and_artist(title, artist, &file_name);
+
+    let cover_art = if options.cover_art {
+        tags.iter()
+            .find_map(|tag| front_cover(tag.pictures()))
+            .map(picture_to_data_url)
+            .or_else(|| folder_cover(path))
+    } else {
+        None
+    };
+
+    Ok(TrackMetadata {
+        title,
+        artist,
+        album: album.unwrap_or_else(|| UNKNOWN_ALBUM.to_string()),
+        album_artist,
+        track_number,
+        disc_number,
+        genre,
+        year,
+        composer,
+        bpm,
+        duration: properties.duration().as_secs_f64(),
+        bitrate: properties.audio_bitrate().filter(|&rate| rate > 0),
+        sample_rate: properties.sample_rate().filter(|&rate| rate > 0),
+        bit_depth: properties.bit_depth().filter(|&depth| depth > 0),
+        channels: properties.channels().filter(|&channels| channels > 0),
+        codec: codec_name(tagged.file_type(), properties.bit_depth().is_some()),
+        cover_art,
+        source_type: "local",
+        file_name,
+        file_size,
+        file_path: path.to_string_lossy().into_owned(),
+        replay_gain: read_replay_gain(&tags),
+    })
+}
+
+/// Read many files in parallel, keeping the input order
+pub fn read_metadata_batch(paths: &[PathBuf], options: ReadOptions) -> Vec<MetadataResult> {
+    parallel_map(paths, |path| match read_metadata(path, options) {
+        Ok(metadata) => MetadataResult {
+            path: path.to_string_lossy().into_owned(),
+            metadata: Some(metadata),
+            error: None,
+        },
+        Err(error) => MetadataResult {
+            path: path.to_string_lossy().into_owned(),
+            metadata: None,
+            error: Some(error.to_string()),
+        },
+    })
+}
+
+/// ReplayGain tags, falling back to Opus R128 tags
+fn read_replay_gain(tags: &[&Tag]) -> Option<ReplayGain> {
+    let value = |key: ItemKey| {
+        tags.iter()
+            .find_map(|tag| tag.get_string(key).and_then(parse_leading_number))
+    };
+    let r128 = |key: ItemKey| {
+        tags.iter()
+            .find_map(|tag| tag.get_string(key))
+            .and_then(|value| value.trim().parse::<i16>().ok())
+            .map(r128_to_replay_gain)
+    };
+
+    let replay_gain = ReplayGain {
+        track_gain: value(ItemKey::ReplayGainTrackGain).or_else(|| r128(ItemKey::R128TrackGain)),
+        track_peak: value(ItemKey::ReplayGainTrackPeak),
+        album_gain: value(ItemKey::ReplayGainAlbumGain).or_else(|| r128(ItemKey::R128AlbumGain)),
+        album_peak: value(ItemKey::ReplayGainAlbumPeak),
+    };
+    (!replay_gain.is_empty()).then_some(replay_gain)
+}
+
+/// MP4 holds either AAC or ALAC; only the lossless one reports a bit depth
+fn codec_name(file_type: FileType, has_bit_depth: bool) -> Option<&'static str> {
+    Some(match file_type {
+        FileType::Aac => "AAC",
+        FileType::Aiff | FileType::Wav => "PCM",
+        FileType::Ape => "APE",
+        FileType::Flac => "FLAC",
+        FileType::Mpeg => "MP3",
+        FileType::Mp4 if has_bit_depth => "ALAC",
+        FileType::Mp4 => "AAC",
+        FileType::Mpc => "Musepack",
+        FileType::Opus => "Opus",
+        FileType::Vorbis => "Vorbis",
+        FileType::Speex => "Speex",
+        FileType::WavPack => "WavPack",
+        _ => return None,
+    })
+}
+
+/// Parse values like "-6.48 dB" or "0.988547"
+fn parse_leading_number(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let end = value
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, '+' | '-' | '.')))
+        .unwrap_or(value.len());
+    value[..end]
+        .parse()
+        .ok()
+        .filter(|number: &f64| number.is_finite())
+}
+
+fn non_empty<S: AsRef<str>>(value: Option<S>) -> Option<String> {
+    value
+        .map(|value| value.as_ref().trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Fill in a missing title (and artist) from an "Artist - Title" file name
+fn resolve_title_and_artist(
+    title: Option<String>,
+    artist: Option<String>,
+    file_name: &str,
+) -> (String, String) {
+    if let Some(title) = title {
+        return (title, artist.unwrap_or_else(|| UNKNOWN_ARTIST.to_string()));
+    }
+
+    let stem = match file_name.rfind('.') {
+        Some(dot) if dot > 0 => &file_name[..dot],
+        _ => file_name,
+    };
+
+    match stem.split_once(" - ") {
+        Some((name_artist, name_title)) => (
+            name_title.trim().to_string(),
+            artist.unwrap_or_else(|| name_artist.trim().to_string()),
+        ),
+        None => (
+            stem.to_string(),
+            artist.unwrap_or_else(|| UNKNOWN_ARTIST.to_string()),
+        ),
+    }
+}
+
+fn front_cover(pictures: &[Picture]) -> Option<&Picture> {
+    pictures
+        .iter()
+        .find(|picture| picture.pic_type() == PictureType::CoverFront)
+        .or_else(|| pictures.first())
+}
+
+fn picture_to_data_url(picture: &Picture) -> String {
+    let mime_type = picture
+        .mime_type()
+        .map_or("image/jpeg", |mime_type| mime_type.as_str());
+    format!("data:{mime_type};base64,{}", BASE64.encode(picture.data()))
+}
+
+/// Image file stems used as a folder's cover, best first
+const FOLDER_COVER_NAMES: [&str; 3] = ["cover", "folder", "front"];
+
+fn folder_cover_rank(file_name: &str) -> Option<(usize, &'static str)> {
+    let (stem, extension) = file_name.rsplit_once('.')?;
+    let mime_type = match extension.to_ascii_lowercase().as_str() {
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        _ => return None,
+    };
+    let stem = stem.to_ascii_lowercase();
+    let rank = FOLDER_COVER_NAMES.iter().position(|name| *name == stem)?;
+    Some((rank, mime_type))
+}
+
+/// Best cover image in the file's folder, for tracks without embedded art
+fn folder_cover(path: &Path) -> Option<String> {
+    let (cover_path, mime_type) = fs::read_dir(path.parent()?)
+        .ok()?
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let (rank, mime_type) = folder_cover_rank(entry.file_name().to_str()?)?;
+            Some((rank, entry.path(), mime_type))
+        })
+        .min_by_key(|(rank, ..)| *rank)
+        .map(|(_, path, mime_type)| (path, mime_type))?;
+    let data = fs::read(cover_path).ok()?;
+    Some(format!("data:{mime_type};base64,{}", BASE64.encode(data)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode::tests::{temp_dir, write_wav};
+    use std::fs;
+
+    #[test]
+    fn parses_artist_and_title_from_file_name() {
+        assert_eq!(
+            resolve_title_and_artist(None, None, "Daft Punk - One More Time.mp3"),
+            ("One More Time".to_string(), "Daft Punk".to_string())
+        );
+        assert_eq!(
+            resolve_title_and_artist(None, Some("Tagged".into()), "A - B - C.flac"),
+            ("B - C".to_string(), "Tagged".to_string())
+        );
+        assert_eq!(
+            resolve_title_and_artist(None, None, "untitled.ogg"),
+            ("untitled".to_string(), UNKNOWN_ARTIST.to_string())
+        );
+        assert_eq!(
+            resolve_title_and_artist(Some("Song".into()), None, "Other - Name.mp3"),
+            ("Song".to_string(), UNKNOWN_ARTIST.to_string())
+        );
+    }
+
+    #[test]
+    fn parses_replay_gain_values() {
+        assert_eq!(parse_leading_number("-6.48 dB"), Some(-6.48));
+        assert_eq!(parse_leading_number("+2.10dB"), Some(2.1));
+        assert_eq!(parse_leading_number(" 0.988547"), Some(0.988547));
+        assert_eq!(parse_leading_number("dB"), None);
+    }
+
+    #[test]
+    fn reads_exact_duration_from_stream_headers() {
+        let dir = temp_dir("duration");
+        let path = dir.join("Artist - Silence.wav");
+        write_wav(&path, 44_100, 2, 44_100 * 3 / 2);
+
+        let metadata = read_metadata(&path, ReadOptions::default()).unwrap();
+        assert!(
+            (metadata.duration - 1.5).abs() < 0.001,
+            "{}",
+            metadata.duration
+        );
+        assert_eq!(metadata.title, "Silence");
+        assert_eq!(metadata.artist, "Artist");
+        assert_eq!(metadata.album, UNKNOWN_ALBUM);
+        assert_eq!(metadata.album_artist, None);
+        assert_eq!(metadata.track_number, None);
+        assert_eq!(metadata.genre, None);
+        assert_eq!(metadata.year, None);
+        assert_eq!(metadata.composer, None);
+        assert_eq!(metadata.sample_rate, Some(44_100));
+        assert_eq!(metadata.bit_depth, Some(16));
+        assert_eq!(metadata.channels, Some(2));
+        assert_eq!(metadata.codec, Some("PCM"));
+        assert_eq!(metadata.replay_gain, None);
+        assert_eq!(metadata.file_size, fs::metadata(&path).unwrap().len());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn ranks_folder_cover_names() {
+        assert_eq!(folder_cover_rank("cover.jpg"), Some((0, "image/jpeg")));
+        assert_eq!(folder_cover_rank("Folder.PNG"), Some((1, "image/png")));
+        assert_eq!(folder_cover_rank("front.jpeg"), Some((2, "image/jpeg")));
+        assert_eq!(folder_cover_rank("back.jpg"), None);
+        assert_eq!(folder_cover_rank("cover.gif"), None);
+        assert_eq!(folder_cover_rank("cover"), None);
+    }
+
+    #[test]
+    fn falls_back_to_folder_cover_image() {
+        let dir = temp_dir("folder-cover");
+        let path = dir.join("track.wav");
+        write_wav(&path, 8_000, 1, 8_000);
+        fs::write(dir.join("front.jpg"), b"front").unwrap();
+        fs::write(dir.join("Cover.png"), b"cover").unwrap();
+
+        let metadata = read_metadata(&path, ReadOptions::default()).unwrap();
+        assert_eq!(
+            metadata.cover_art.as_deref(),
+            Some("data:image/png;base64,Y292ZXI=")
+        );
+
+        let metadata = read_metadata(&path, ReadOptions { cover_art: false }).unwrap();
+        assert_eq!(metadata.cover_art, None);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn batch_keeps_order_and_reports_errors() {
+        let dir = temp_dir("batch");
+        let good = dir.join("good.wav");
+        let bad = dir.join("bad.mp3");
+        write_wav(&good, 8_000, 1, 8_000);
+        fs::write(&bad, b"not audio").unwrap();
+        let missing = dir.join("missing.flac");
+
+        let results = read_metadata_batch(
+            &[bad.clone(), good.clone(), missing],
+            ReadOptions::default(),
+        );
+        assert_eq!(results.len(), 3);
+        assert!(results[0].error.is_some());
+        assert_eq!(results[1].metadata.as_ref().unwrap().title, "good");
+        assert!(results[2].error.is_some());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,169 @@
+// Exposes the Tauri commands to the renderer as `window.electron`, with the
+// same shape as electron/preload.cjs so src/lib/platform.ts works unchanged.
+(function () {
+  if (window.electron) return;
+
+  const invoke = (command, args) => window.__TAURI__.core.invoke(command, args);
+
+  // Custom protocols are served from http://<scheme>.localhost on Windows
+  const MEDIA_BASE_URL =
+    "__VINYL_PLATFORM__" === "win32"
+      ? "http://vinyl-media.localhost"
+      : "vinyl-media://localhost";
+
+  // Subscribe to a backend event, returning a synchronous cleanup function
+  const listen = (eventName, callback) => {
+    let unlisten = null;
+    let cancelled = false;
+
+    window.__TAURI__.event
+      .listen(eventName, (event) => callback(event.payload))
+      .then((fn) => {
+        if (cancelled) fn();
+        else unlisten = fn;
+      });
+
+    return () => {
+      cancelled = true;
+      if (unlisten) unlisten();
+    };
+  };
+
+  window.electron = {
+    // Platform info
+    platform: "__VINYL_PLATFORM__",
+    isElectron: true,
+
+    // Dialog APIs
+    openFolderPicker: (options = {}) =>
+      invoke("dialog_open_folder", {
+        title: options.title,
+        defaultPath: options.defaultPath,
+      }),
+    openPlaylistFile: async () => {
+      const result = await invoke("dialog_open_playlist");
+      return (
+        result && { path: result.path, data: new Uint8Array(result.data) }
+      );
+    },
+    pickPlaylistSavePath: (defaultName) =>
+      invoke("dialog_save_playlist", { defaultName }),
+    openBackupFile: async () => {
+      const result = await invoke("dialog_open_backup");
+      return (
+        result && { path: result.path, data: new Uint8Array(result.data) }
+      );
+    },
+    pickBackupSavePath: (defaultName) =>
+      invoke("dialog_save_backup", { defaultName }),
+
+    // File system APIs
+    scanMusicFolder: (folderPath) =>
+      invoke("fs_scan_music_folder", { folderPath }),
+
+    readFile: async (filePath) => {
+      const result = await invoke("fs_prepare_file", { filePath });
+      if (!result.path) {
+        throw new Error(result.error);
+      }
+      // Bytes come back as a raw ArrayBuffer rather than JSON
+      const data = await invoke("fs_read_bytes", { path: result.path });
+      return {
+        data,
+        transcoded: result.transcoded,
+        mimeType: result.mimeType,
+        error: result.error, // May have warning even with data
+      };
+    },
+
+    // Streaming URL for the <audio> element (supports Range requests)
+    getMediaUrl: (filePath) =>
+      `${MEDIA_BASE_URL}/${encodeURIComponent(filePath)}`,
+
+    fileExists: (filePath) => invoke("fs_file_exists", { filePath }),
+
+    getFileStats: (filePath) => invoke("fs_get_stats", { filePath }),
+
+    getFilesStats: (filePaths) => invoke("fs_get_stats_batch", { filePaths }),
+
+    writeTextFile: (filePath, content) =>
+      invoke("fs_write_text_file", { filePath, content }),
+
+    writeBackupFile: (folderPath, fileName, content) =>
+      invoke("fs_write_backup_file", { folderPath, fileName, content }),
+
+    // Shell APIs
+    showItemInFolder: (filePath) =>
+      invoke("shell_show_item_in_folder", { filePath }),
+
+    // Store APIs for persisting settings
+    store: {
+      get: (key) => invoke("store_get", { key }),
+      set: (key, value) => invoke("store_set", { key, value }),
+      delete: (key) => invoke("store_delete", { key }),
+    },
+
+    // Setup / First Launch APIs
+    setup: {
+      isFirstLaunch: () => invoke("setup_is_first_launch"),
+      completeSetup: () => invoke("setup_complete_setup"),
+      resetSetup: () => invoke("setup_reset_setup"),
+    },
+
+    // Music Library APIs
+    library: {
+      getFolders: () => invoke("library_get_folders"),
+      addFolder: (folderPath) => invoke("library_add_folder", { folderPath }),
+      removeFolder: (folderPath) =>
+        invoke("library_remove_folder", { folderPath }),
+      scanFolder: (folderPath) =>
+        invoke("library_scan_folder_with_progress", { folderPath }),
+      scanAllFolders: () => invoke("library_scan_all_folders"),
+      resetScanIndex: () => invoke("library_reset_scan_index"),
+      forgetScannedFiles: (filePaths) =>
+        invoke("library_forget_scanned_files", { filePaths }),
+      migrateSongFolders: (folders) =>
+        invoke("library_migrate_song_folders", { folders }),
+      readMetadata: (filePaths, options = {}) =>
+        invoke("library_read_metadata", {
+          filePaths,
+          includeCoverArt: options.includeCoverArt,
+        }),
+      analyzeLoudness: (albums) =>
+        invoke("library_analyze_loudness", { albums }),
+      writeTags: (writes) => invoke("library_write_tags", { writes }),
+      // Watcher APIs
+      startWatching: () => invoke("library_start_watching"),
+      stopWatching: () => invoke("library_stop_watching"),
+      getWatcherStatus: () => invoke("library_get_watcher_status"),
+      // Event listener for file changes
+      onFileChange: (callback) => listen("library:fileChange", callback),
+      onScanProgress: (callback) => listen("library:scanProgress", callback),
+    },
+
+    // Transcode Cache APIs
+    transcodeCache: {
+      getInfo: () => invoke("transcode_cache_get_info"),
+      clear: () => invoke("transcode_cache_clear"),
+      setMaxSize: (maxSize) =>
+        invoke("transcode_cache_set_max_size", { maxSize }),
+    },
+
+    // System Tray APIs
+    tray: {
+      updatePlaybackState: (state) =>
+        invoke("tray_update_playback_state", { state }),
+      show: () => invoke("tray_show"),
+      hide: () => invoke("tray_hide"),
+      onPlayPause: (callback) => listen("tray:playPause", () => callback()),
+      onNext: (callback) => listen("tray:next", () => callback()),
+      onPrevious: (callback) => listen("tray:previous", () => callback()),
+    },
+
+    // File Open APIs (for "Open With" from Finder/Explorer)
+    fileOpen: {
+      getPendingFiles: () => invoke("file_get_pending_files"),
+      onFileOpen: (callback) => listen("file:open", callback),
+    },
+  };
+})();
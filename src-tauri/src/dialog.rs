@@ -0,0 +1,117 @@
+//! Native dialogs.
+
+use crate::path_access::{self, PathAccess};
+use serde::Serialize;
+use std::path::Path;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::DialogExt;
+
+const PLAYLIST_EXTENSIONS: &[&str] = &["m3u", "m3u8", "pls", "xspf"];
+
+/// Open folder picker dialog, resolving to `null` when cancelled
+#[tauri::command]
+pub async fn dialog_open_folder(
+    app: AppHandle,
+    title: Option<String>,
+    default_path: Option<String>,
+) -> Option<String> {
+    let mut dialog = app
+        .dialog()
+        .file()
+        .set_title(title.as_deref().unwrap_or("Select your music folder"));
+    if let Some(directory) = default_path.filter(|path| Path::new(path).is_dir()) {
+        dialog = dialog.set_directory(directory);
+    }
+    let folder = dialog.blocking_pick_folder()?;
+
+    let folder = folder.into_path().ok()?.to_string_lossy().into_owned();
+    path_access::authorize_folder(&app, &folder);
+    Some(folder)
+}
+
+/// A file picked for import; the renderer decodes the bytes
+#[derive(Serialize)]
+pub struct OpenedFile {
+    path: String,
+    data: Vec<u8>,
+}
+
+/// Pick a playlist file to import and read it, resolving to `null` when cancelled
+#[tauri::command]
+pub async fn dialog_open_playlist(app: AppHandle) -> Result<Option<OpenedFile>, String> {
+    let Some(file) = app
+        .dialog()
+        .file()
+        .set_title("Import playlist")
+        .add_filter("Playlists", PLAYLIST_EXTENSIONS)
+        .blocking_pick_file()
+    else {
+        return Ok(None);
+    };
+
+    let path = file.into_path().map_err(|error| error.to_string())?;
+    let data = std::fs::read(&path).map_err(|error| error.to_string())?;
+    Ok(Some(OpenedFile {
+        path: path.to_string_lossy().into_owned(),
+        data,
+    }))
+}
+
+/// Pick where to save an exported playlist, resolving to `null` when cancelled
+#[tauri::command]
+pub async fn dialog_save_playlist(app: AppHandle, default_name: String) -> Option<String> {
+    let extension = Path::new(&default_name)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or("m3u8")
+        .to_owned();
+
+    let file = app
+        .dialog()
+        .file()
+        .set_title("Export playlist")
+        .set_file_name(&default_name)
+        .add_filter("Playlist", &[extension.as_str()])
+        .blocking_save_file()?;
+
+    let file = file.into_path().ok()?.to_string_lossy().into_owned();
+    app.state::<PathAccess>().allow_file(&file);
+    Some(file)
+}
+
+/// Pick a library backup to restore, resolving to `null` when cancelled
+#[tauri::command]
+pub async fn dialog_open_backup(app: AppHandle) -> Result<Option<OpenedFile>, String> {
+    let Some(file) = app
+        .dialog()
+        .file()
+        .set_title("Restore backup")
+        .add_filter("Vinyl backup", &["json"])
+        .blocking_pick_file()
+    else {
+        return Ok(None);
+    };
+
+    let path = file.into_path().map_err(|error| error.to_string())?;
+    let data = std::fs::read(&path).map_err(|error| error.to_string())?;
+    Ok(Some(OpenedFile {
+        path: path.to_string_lossy().into_owned(),
+        data,
+    }))
+}
+
+/// Pick where to save a library backup, resolving to `null` when cancelled
+#[tauri::command]
+pub async fn dialog_save_backup(app: AppHandle, default_name: String) -> Option<String> {
+    let file = app
+        .dialog()
+        .file()
+        .set_title("Back up library")
+        .set_file_name(&default_name)
+        .add_filter("Vinyl backup", &["json"])
+        .blocking_save_file()?;
+
+    let file = file.into_path().ok()?.to_string_lossy().into_owned();
+    app.state::<PathAccess>().allow_file(&file);
+    Some(file)
+}
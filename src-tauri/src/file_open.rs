@@ -0,0 +1,65 @@
+//! File open handling ("Open With" from Finder/Explorer).
+
+use crate::path_access::PathAccess;
+use crate::scan;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// Files opened before the renderer was ready to receive them
+#[derive(Default)]
+pub struct PendingFiles {
+    files: Mutex<Vec<String>>,
+    window_ready: AtomicBool,
+}
+
+impl PendingFiles {
+    pub fn set_window_ready(&self, ready: bool) {
+        self.window_ready.store(ready, Ordering::SeqCst);
+    }
+}
+
+/// Audio files among command line arguments, skipping flags. Relative paths
+/// are resolved against `cwd`, so the renderer can read them through file commands
+pub fn audio_files_from_args<I: IntoIterator<Item = String>>(args: I, cwd: &Path) -> Vec<String> {
+    args.into_iter()
+        .filter(|arg| !arg.starts_with('-'))
+        .map(|arg| cwd.join(arg))
+        .filter(|path| path.is_file() && scan::is_audio_file(path))
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect()
+}
+
+/// Queue files for the renderer, or send them right away once it is listening
+pub fn open_files(app: &AppHandle, files: Vec<String>) {
+    if files.is_empty() {
+        return;
+    }
+
+    // Opened explicitly, so the renderer may read them even outside the library
+    let access = app.state::<PathAccess>();
+    for file in &files {
+        access.allow_file(file);
+    }
+
+    let pending = app.state::<PendingFiles>();
+    if pending.window_ready.load(Ordering::SeqCst) {
+        for file in &files {
+            println!("[OpenFile] Sending file to renderer: {file}");
+            if let Err(error) = app.emit("file:open", file) {
+                eprintln!("[OpenFile] Failed to send file: {error}");
+            }
+        }
+        crate::show_main_window(app);
+    } else {
+        println!("[OpenFile] Queued files: {files:?}");
+        pending.files.lock().unwrap().extend(files);
+    }
+}
+
+/// Get files that were opened before the renderer was ready
+#[tauri::command]
+pub fn file_get_pending_files(pending: State<'_, PendingFiles>) -> Vec<String> {
+    std::mem::take(&mut *pending.files.lock().unwrap())
+}
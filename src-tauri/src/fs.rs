@@ -0,0 +1,297 @@
+//! File system commands used by the renderer to read the music library.
+
+use crate::path_access::{self, AllowedPaths, PathAccessErrorCode};
+use crate::scan::{self, MusicFileInfo};
+use crate::transcode::{self, TranscodeCache};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tauri::{AppHandle, Manager};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    files: Option<Vec<MusicFileInfo>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<PathAccessErrorCode>,
+}
+
+/// Scan a folder for music files
+#[tauri::command]
+pub async fn fs_scan_music_folder(app: AppHandle, folder_path: String) -> ScanResult {
+    // Walk the canonical folder, a symlink swapped in after the check isn't followed
+    let folder = match path_access::resolve(&app, &folder_path) {
+        Ok(folder) => folder,
+        Err(error) => {
+            return ScanResult {
+                files: None,
+                error: Some(error.message),
+                code: Some(error.code),
+            }
+        }
+    };
+    if !folder.exists() {
+        return ScanResult {
+            files: None,
+            error: Some("Folder does not exist".into()),
+            code: None,
+        };
+    }
+
+    let files = tauri::async_runtime::spawn_blocking(move || {
+        scan::scan_resolved_folder(&folder, Path::new(&folder_path))
+    })
+    .await
+    .unwrap_or_default();
+
+    ScanResult {
+        files: Some(files),
+        error: None,
+        code: None,
+    }
+}
+
+/// The playable location of a file, see `bridge.js` for how it is read
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreparedFile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    transcoded: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mime_type: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<PathAccessErrorCode>,
+}
+
+/// Resolve where to read `source` from, starting a transcode into the app cache if needed
+pub(crate) fn open_playable(app: &AppHandle, source: &Path) -> Result<transcode::Playable, String> {
+    if !source.is_file() {
+        return Err(format!("File not found: {}", source.display()));
+    }
+    app.state::<TranscodeCache>().open(source)
+}
+
+/// Resolve the playable file for `source`, waiting for any transcode to finish
+fn prepare_playable(app: &AppHandle, source: &Path) -> Result<transcode::PreparedFile, String> {
+    if !source.is_file() {
+        return Err(format!("File not found: {}", source.display()));
+    }
+    app.state::<TranscodeCache>().prepare(source)
+}
+
+/// Transcode the file if necessary and report where its audio data lives.
+///
+/// Reading is split from preparing so the bytes can be returned as a raw
+/// IPC response instead of a JSON number array.
+#[tauri::command]
+pub async fn fs_prepare_file(app: AppHandle, file_path: String) -> PreparedFile {
+    let source = match path_access::resolve(&app, &file_path) {
+        Ok(source) => source,
+        Err(error) => {
+            return PreparedFile {
+                path: None,
+                transcoded: false,
+                mime_type: None,
+                error: Some(error.message),
+                code: Some(error.code),
+            }
+        }
+    };
+
+    let prepared = tauri::async_runtime::spawn_blocking(move || prepare_playable(&app, &source))
+        .await
+        .map_err(|error| error.to_string())
+        .and_then(|prepared| prepared);
+
+    match prepared {
+        Ok(prepared) => PreparedFile {
+            path: Some(prepared.path.to_string_lossy().into_owned()),
+            transcoded: prepared.transcoded,
+            mime_type: prepared.mime_type,
+            error: None,
+            code: None,
+        },
+        Err(error) => PreparedFile {
+            path: None,
+            transcoded: false,
+            mime_type: None,
+            error: Some(error),
+            code: None,
+        },
+    }
+}
+
+/// Canonical path of a library file the renderer may read or a cached transcode
+fn prepared_path(app: &AppHandle, path: &str) -> Option<PathBuf> {
+    if let Ok(path) = path_access::resolve(app, path) {
+        return Some(path);
+    }
+    let cache_dir = fs::canonicalize(app.state::<TranscodeCache>().dir()).ok()?;
+    path_access::canonicalize(path)
+        .ok()
+        .filter(|path| path.starts_with(cache_dir))
+}
+
+/// Read a file and return its bytes (an `ArrayBuffer` on the JS side).
+/// `path` is where `fs_prepare_file` said the audio data lives
+#[tauri::command]
+pub async fn fs_read_bytes(app: AppHandle, path: String) -> Result<tauri::ipc::Response, String> {
+    let Some(path) = prepared_path(&app, &path) else {
+        return Err(format!("Not in your music folders: {path}"));
+    };
+
+    tauri::async_runtime::spawn_blocking(move || fs::read(path))
+        .await
+        .map_err(|error| error.to_string())?
+        .map(tauri::ipc::Response::new)
+        .map_err(|error| error.to_string())
+}
+
+/// Check if a file exists
+#[tauri::command]
+pub fn fs_file_exists(app: AppHandle, file_path: String) -> bool {
+    path_access::resolve(&app, &file_path).is_ok_and(|path| path.exists())
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileStats {
+    size: u64,
+    mtime: String,
+    is_file: bool,
+    is_directory: bool,
+}
+
+/// Get file stats
+#[tauri::command]
+pub fn fs_get_stats(app: AppHandle, file_path: String) -> Option<FileStats> {
+    file_stats(&path_access::resolve(&app, &file_path).ok()?)
+}
+
+/// Stats for many files in one call; `None` entries are missing or unreadable
+#[tauri::command]
+pub async fn fs_get_stats_batch(app: AppHandle, file_paths: Vec<String>) -> Vec<Option<FileStats>> {
+    let allowed = AllowedPaths::load(&app);
+    tauri::async_runtime::spawn_blocking(move || {
+        file_paths
+            .iter()
+            .map(|path| file_stats(&allowed.resolve(path).ok()?))
+            .collect()
+    })
+    .await
+    .unwrap_or_default()
+}
+
+/// Write a text file (exported playlists), only where a save dialog was used
+#[tauri::command]
+pub fn fs_write_text_file(app: AppHandle, file_path: String, content: String) -> bool {
+    let path = match path_access::resolve_file(&app, &file_path) {
+        Ok(path) => path,
+        Err(error) => {
+            eprintln!("[FS] Refused to write {file_path}: {error}");
+            return false;
+        }
+    };
+
+    match fs::write(path, content) {
+        Ok(()) => true,
+        Err(error) => {
+            eprintln!("[FS] Failed to write {file_path}: {error}");
+            false
+        }
+    }
+}
+
+/// Write a scheduled backup into a folder the user picked. Only backup file
+/// names are accepted, so nothing else in the folder can be overwritten
+#[tauri::command]
+pub fn fs_write_backup_file(
+    app: AppHandle,
+    folder_path: String,
+    file_name: String,
+    content: String,
+) -> bool {
+    if !is_backup_file_name(&file_name) {
+        eprintln!("[FS] Refused to write backup {file_name}: not a backup file name");
+        return false;
+    }
+    let folder = match path_access::resolve(&app, &folder_path) {
+        Ok(folder) => folder,
+        Err(error) => {
+            eprintln!("[FS] Refused to write backup to {folder_path}: {error}");
+            return false;
+        }
+    };
+
+    match fs::write(folder.join(&file_name), content) {
+        Ok(()) => true,
+        Err(error) => {
+            eprintln!("[FS] Failed to write backup {file_name}: {error}");
+            false
+        }
+    }
+}
+
+/// `vinyl-backup-<date>.json`, a plain name with no separators
+fn is_backup_file_name(name: &str) -> bool {
+    name.strip_prefix("vinyl-backup-")
+        .and_then(|rest| rest.strip_suffix(".json"))
+        .is_some_and(|date| {
+            !date.is_empty() && date.chars().all(|c| c.is_ascii_digit() || c == '-')
+        })
+}
+
+fn file_stats(path: &Path) -> Option<FileStats> {
+    let metadata = fs::metadata(path).ok()?;
+    let mtime = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+
+    Some(FileStats {
+        size: metadata.len(),
+        mtime: humantime::format_rfc3339_millis(mtime).to_string(),
+        is_file: metadata.is_file(),
+        is_directory: metadata.is_dir(),
+    })
+}
+
+/// Show item in folder (file manager)
+#[tauri::command]
+pub fn shell_show_item_in_folder(app: AppHandle, file_path: String) -> bool {
+    let path = match path_access::resolve(&app, &file_path) {
+        Ok(path) if path.exists() => path,
+        _ => return false,
+    };
+
+    match tauri_plugin_opener::reveal_item_in_dir(path) {
+        Ok(()) => true,
+        Err(error) => {
+            eprintln!("[Shell] Failed to reveal {file_path}: {error}");
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_only_backup_file_names() {
+        assert!(is_backup_file_name("vinyl-backup-2026-10-15.json"));
+        for name in [
+            "song.mp3",
+            "vinyl-backup-.json",
+            "vinyl-backup-2026/../../song.json",
+            "vinyl-backup-2026-10-15.json.mp3",
+            "vinyl-backup-..\\song.json",
+        ] {
+            assert!(!is_backup_file_name(name), "{name}");
+        }
+    }
+}
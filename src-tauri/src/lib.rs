@@ -0,0 +1,202 @@
+//! Tauri backend for Vinyl.
+//!
+//! Implements the same command surface as `electron/main.cjs`. `bridge.js` is
+//! injected into the webview and exposes it as `window.electron`, so the
+//! renderer (`src/lib/platform.ts`) runs unchanged on either shell.
+
+mod dialog;
+mod file_open;
+mod fs;
+mod library;
+mod path_access;
+mod protocol;
+mod scan;
+mod store;
+mod transcode;
+mod tray;
+mod watcher;
+
+use file_open::PendingFiles;
+use path_access::PathAccess;
+use std::path::Path;
+use store::Store;
+use tauri::webview::PageLoadEvent;
+use tauri::{AppHandle, Manager, RunEvent, WebviewUrl, WebviewWindowBuilder};
+use transcode::TranscodeCache;
+use tray::TrayState;
+use watcher::WatcherState;
+
+const MAIN_WINDOW: &str = "main";
+
+/// `process.platform` equivalent, so the renderer sees the same values as under Electron
+fn platform() -> &'static str {
+    match std::env::consts::OS {
+        "macos" => "darwin",
+        "windows" => "win32",
+        other => other,
+    }
+}
+
+fn bridge_script() -> String {
+    include_str!("bridge.js").replace("__VINYL_PLATFORM__", platform())
+}
+
+fn create_main_window(app: &AppHandle) -> tauri::Result<()> {
+    println!("[Tauri] Creating window...");
+
+    WebviewWindowBuilder::new(app, MAIN_WINDOW, WebviewUrl::default())
+        .title("Vinyl Music Player")
+        .inner_size(1200.0, 800.0)
+        .min_inner_size(800.0, 600.0)
+        .initialization_script(bridge_script())
+        .on_page_load(|window, payload| {
+            let ready = matches!(payload.event(), PageLoadEvent::Finished);
+            window.state::<PendingFiles>().set_window_ready(ready);
+            if ready {
+                println!("[Tauri] Page loaded successfully");
+            }
+        })
+        .build()?;
+
+    Ok(())
+}
+
+/// Show and focus the main window, recreating it if it was closed
+pub(crate) fn show_main_window(app: &AppHandle) {
+    match app.get_webview_window(MAIN_WINDOW) {
+        Some(window) => {
+            let _ = window.unminimize();
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+        None => {
+            if let Err(error) = create_main_window(app) {
+                eprintln!("[Tauri] Failed to create window: {error}");
+            }
+        }
+    }
+}
+
+#[cfg_attr(not(target_os = "macos"), allow(unused_variables))]
+fn handle_run_event(app: &AppHandle, event: RunEvent) {
+    match event {
+        // Files opened via "Open With" in Finder (macOS)
+        #[cfg(target_os = "macos")]
+        RunEvent::Opened { urls } => {
+            let files = urls
+                .into_iter()
+                .filter_map(|url| url.to_file_path().ok())
+                .map(|path| path.to_string_lossy().into_owned());
+            // File URLs are always absolute
+            file_open::open_files(app, file_open::audio_files_from_args(files, Path::new("")));
+        }
+        #[cfg(target_os = "macos")]
+        RunEvent::Reopen { .. } => show_main_window(app),
+        // Keep running without windows on macOS, like other Mac apps
+        RunEvent::ExitRequested {
+            code: None, api, ..
+        } if cfg!(target_os = "macos") => {
+            api.prevent_exit();
+        }
+        _ => {}
+    }
+}
+
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+pub fn run() {
+    tauri::Builder::default()
+        // Must be registered first so a second instance exits before doing any work
+        .plugin(tauri_plugin_single_instance::init(|app, argv, cwd| {
+            println!("[Tauri] Second instance detected: {argv:?}");
+            file_open::open_files(
+                app,
+                file_open::audio_files_from_args(argv.into_iter().skip(1), Path::new(&cwd)),
+            );
+            show_main_window(app);
+        }))
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_opener::init())
+        .register_asynchronous_uri_scheme_protocol(protocol::SCHEME, protocol::handle)
+        .setup(|app| {
+            let data_dir = app.path().app_data_dir()?;
+            let store = Store::load(data_dir.clone());
+            store.migrate_authorized_folders();
+            let cache_max_size = store
+                .transcode_cache_max_size()
+                .unwrap_or(transcode::DEFAULT_MAX_CACHE_SIZE);
+            app.manage(store);
+            app.manage(TranscodeCache::load(
+                data_dir.join("transcode-cache"),
+                cache_max_size,
+            ));
+            app.manage(WatcherState::default());
+            app.manage(TrayState::default());
+            app.manage(PendingFiles::default());
+            app.manage(PathAccess::default());
+
+            // Handle files passed as command line arguments (Windows/Linux)
+            let handle = app.handle();
+            file_open::open_files(
+                handle,
+                file_open::audio_files_from_args(
+                    std::env::args().skip(1),
+                    &std::env::current_dir().unwrap_or_default(),
+                ),
+            );
+
+            create_main_window(handle)?;
+
+            // Create tray on startup (will be updated when playback starts)
+            if let Err(error) = tray::create_tray(handle) {
+                eprintln!("[Tray] Failed to create tray: {error}");
+            }
+
+            Ok(())
+        })
+        .invoke_handler(tauri::generate_handler![
+            dialog::dialog_open_folder,
+            dialog::dialog_open_playlist,
+            dialog::dialog_save_playlist,
+            dialog::dialog_open_backup,
+            dialog::dialog_save_backup,
+            fs::fs_scan_music_folder,
+            fs::fs_prepare_file,
+            fs::fs_read_bytes,
+            fs::fs_file_exists,
+            fs::fs_get_stats,
+            fs::fs_get_stats_batch,
+            fs::fs_write_text_file,
+            fs::fs_write_backup_file,
+            fs::shell_show_item_in_folder,
+            transcode::transcode_cache_get_info,
+            transcode::transcode_cache_clear,
+            transcode::transcode_cache_set_max_size,
+            store::store_get,
+            store::store_set,
+            store::store_delete,
+            store::setup_is_first_launch,
+            store::setup_complete_setup,
+            store::setup_reset_setup,
+            library::library_get_folders,
+            library::library_add_folder,
+            library::library_remove_folder,
+            library::library_scan_folder_with_progress,
+            library::library_scan_all_folders,
+            library::library_reset_scan_index,
+            library::library_forget_scanned_files,
+            library::library_migrate_song_folders,
+            library::library_read_metadata,
+            library::library_analyze_loudness,
+            library::library_write_tags,
+            watcher::library_start_watching,
+            watcher::library_stop_watching,
+            watcher::library_get_watcher_status,
+            tray::tray_update_playback_state,
+            tray::tray_show,
+            tray::tray_hide,
+            file_open::file_get_pending_files,
+        ])
+        .build(tauri::generate_context!())
+        .expect("error while building Vinyl")
+        .run(handle_run_event);
+}
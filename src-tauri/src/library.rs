@@ -0,0 +1,404 @@
+//! Music library folder management.
+
+use crate::path_access::{self, BatchResult, PathAccessError, PathAccessErrorCode};
+use crate::scan::{self, MusicFileInfo};
+use crate::store::Store;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Manager, State};
+use vinyl_media::library_index::{FolderStats, MovedFile};
+use vinyl_media::{
+    LoudnessResult, MetadataResult, ReadOptions, ScanIndex, ScanReport, ScannedFile, TagWrite,
+    TagWriteResult,
+};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    success: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    folders: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<PathAccessErrorCode>,
+}
+
+impl FolderResult {
+    fn ok(folders: Vec<String>) -> Self {
+        Self {
+            success: Some(true),
+            folders: Some(folders),
+            error: None,
+            code: None,
+        }
+    }
+
+    fn error(message: &str) -> Self {
+        Self {
+            success: None,
+            folders: None,
+            error: Some(message.to_string()),
+            code: None,
+        }
+    }
+
+    fn refused(error: PathAccessError) -> Self {
+        Self {
+            code: Some(error.code),
+            ..Self::error(&error.message)
+        }
+    }
+}
+
+/// Get all watched music folders
+#[tauri::command]
+pub fn library_get_folders(store: State<'_, Store>) -> Vec<String> {
+    store.music_folders()
+}
+
+/// Add a folder to watched list
+#[tauri::command]
+pub fn library_add_folder(
+    app: AppHandle,
+    store: State<'_, Store>,
+    folder_path: String,
+) -> FolderResult {
+    // Only folders picked in the folder dialog can join the library
+    let resolved = match path_access::resolve(&app, &folder_path) {
+        Ok(resolved) => resolved,
+        Err(error) => return FolderResult::refused(error),
+    };
+    if !resolved.exists() {
+        return FolderResult::error("Folder does not exist");
+    }
+
+    let mut folders = store.music_folders();
+    if folders.contains(&folder_path) {
+        return FolderResult::error("Folder already in library");
+    }
+
+    folders.push(folder_path);
+    store.set_music_folders(&folders);
+    FolderResult::ok(folders)
+}
+
+/// Authorize the folders of songs imported before file access was limited to
+/// picked folders. Only the renderer knows them, so this is accepted once,
+/// on the first start after the upgrade
+#[tauri::command]
+pub fn library_migrate_song_folders(
+    app: AppHandle,
+    store: State<'_, Store>,
+    folders: Vec<String>,
+) -> bool {
+    if !store.take_song_folder_migration() {
+        return false;
+    }
+    let folders: Vec<String> = folders
+        .into_iter()
+        .filter(|folder| Path::new(folder).is_absolute() && Path::new(folder).is_dir())
+        .collect();
+    path_access::authorize_folders(&app, &folders);
+    true
+}
+
+/// Remove a folder from watched list
+#[tauri::command]
+pub fn library_remove_folder(store: State<'_, Store>, folder_path: String) -> FolderResult {
+    let mut folders = store.music_folders();
+    let Some(index) = folders.iter().position(|folder| *folder == folder_path) else {
+        return FolderResult::error("Folder not in library");
+    };
+
+    folders.remove(index);
+    store.set_music_folders(&folders);
+    // The folder was picked when it was added, forget that too
+    let mut authorized = store.authorized_folders();
+    authorized.retain(|folder| *folder != folder_path);
+    store.set_authorized_folders(&authorized);
+    FolderResult::ok(folders)
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderScanResult {
+    files: Vec<MusicFileInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total_count: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    folder_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<PathAccessErrorCode>,
+}
+
+/// Scan a folder and count files (for progress reporting)
+#[tauri::command]
+pub async fn library_scan_folder_with_progress(
+    app: AppHandle,
+    folder_path: String,
+) -> FolderScanResult {
+    // Walk the canonical folder, a symlink swapped in after the check isn't followed
+    let resolved = match path_access::resolve(&app, &folder_path) {
+        Ok(resolved) => resolved,
+        Err(error) => {
+            return FolderScanResult {
+                files: Vec::new(),
+                total_count: None,
+                folder_path: None,
+                error: Some(error.message),
+                code: Some(error.code),
+            }
+        }
+    };
+    if !resolved.exists() {
+        return FolderScanResult {
+            files: Vec::new(),
+            total_count: None,
+            folder_path: None,
+            error: Some("Folder does not exist".into()),
+            code: None,
+        };
+    }
+
+    let root = PathBuf::from(&folder_path);
+    let files =
+        tauri::async_runtime::spawn_blocking(move || scan::scan_resolved_folder(&resolved, &root))
+            .await
+            .unwrap_or_default();
+
+    FolderScanResult {
+        total_count: Some(files.len()),
+        files,
+        folder_path: Some(folder_path),
+        error: None,
+        code: None,
+    }
+}
+
+const SCAN_INDEX_FILE: &str = "scan-index.json";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryScanResult {
+    /// Files new to the scan index
+    files: Vec<ScannedFile>,
+    changed: Vec<ScannedFile>,
+    moved: Vec<MovedFile>,
+    removed: Vec<String>,
+    unchanged: usize,
+    total_count: usize,
+    folder_stats: Vec<FolderStats>,
+}
+
+impl From<ScanReport> for LibraryScanResult {
+    fn from(report: ScanReport) -> Self {
+        Self {
+            files: report.added,
+            changed: report.changed,
+            moved: report.moved,
+            removed: report.removed,
+            unchanged: report.unchanged,
+            total_count: report.total_count,
+            folder_stats: report.folder_stats,
+        }
+    }
+}
+
+fn scan_index_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join(SCAN_INDEX_FILE))
+}
+
+/// Rescan all watched folders against the saved index, reporting only differences
+#[tauri::command]
+pub async fn library_scan_all_folders(app: AppHandle) -> LibraryScanResult {
+    let folders: Vec<PathBuf> = app
+        .state::<Store>()
+        .music_folders()
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+    let index_path = scan_index_path(&app);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut index = index_path
+            .as_deref()
+            .map(ScanIndex::load)
+            .unwrap_or_default();
+
+        let report = vinyl_media::scan_folders(&folders, &mut index, |progress| {
+            let _ = app.emit("library:scanProgress", progress);
+        });
+
+        if let Some(path) = index_path {
+            if let Err(error) = index.save(&path) {
+                eprintln!("[Library] Failed to save scan index: {error}");
+            }
+        }
+        LibraryScanResult::from(report)
+    })
+    .await
+    .unwrap_or_else(|error| {
+        eprintln!("[Library] Scan failed: {error}");
+        LibraryScanResult::from(ScanReport::default())
+    })
+}
+
+/// Forget the scan index so the next scan reports every file as new
+#[tauri::command]
+pub fn library_reset_scan_index(app: AppHandle) -> bool {
+    let Some(path) = scan_index_path(&app) else {
+        return false;
+    };
+    match std::fs::remove_file(path) {
+        Ok(()) => true,
+        Err(error) => error.kind() == std::io::ErrorKind::NotFound,
+    }
+}
+
+/// Drop deleted songs from the scan index, so the next scan imports them again
+#[tauri::command]
+pub async fn library_forget_scanned_files(app: AppHandle, file_paths: Vec<String>) -> bool {
+    let Some(index_path) = scan_index_path(&app) else {
+        return false;
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut index = ScanIndex::load(&index_path);
+        if index.forget(&file_paths) == 0 {
+            return true;
+        }
+        match index.save(&index_path) {
+            Ok(()) => true,
+            Err(error) => {
+                eprintln!("[Library] Failed to save scan index: {error}");
+                false
+            }
+        }
+    })
+    .await
+    .unwrap_or(false)
+}
+
+/// Read tags and durations for a batch of files, without sending audio data to the renderer
+#[tauri::command]
+pub async fn library_read_metadata(
+    app: AppHandle,
+    file_paths: Vec<String>,
+    include_cover_art: Option<bool>,
+) -> Vec<BatchResult<MetadataResult>> {
+    let options = ReadOptions {
+        cover_art: include_cover_art.unwrap_or(true),
+    };
+    let (allowed, rejected) = path_access::partition_allowed(&app, file_paths, String::clone);
+    let (requested, paths): (Vec<String>, Vec<PathBuf>) = allowed.into_iter().unzip();
+
+    let mut results = tauri::async_runtime::spawn_blocking(move || {
+        vinyl_media::read_metadata_batch(&paths, options)
+    })
+    .await
+    .unwrap_or_else(|error| {
+        eprintln!("[Library] Metadata read failed: {error}");
+        Vec::new()
+    });
+    for (result, path) in results.iter_mut().zip(requested) {
+        result.path = path;
+    }
+    path_access::with_rejected(results, rejected)
+}
+
+/// Measure loudness and the audible range for files missing either.
+/// Each inner list is one album, so album gain covers all of its tracks.
+#[tauri::command]
+pub async fn library_analyze_loudness(
+    app: AppHandle,
+    albums: Vec<Vec<String>>,
+) -> Vec<BatchResult<LoudnessResult>> {
+    let mut rejected = Vec::new();
+    let mut requested = Vec::new();
+    let albums: Vec<Vec<PathBuf>> = albums
+        .into_iter()
+        .map(|paths| {
+            let (allowed, refused) = path_access::partition_allowed(&app, paths, String::clone);
+            rejected.extend(refused);
+            allowed
+                .into_iter()
+                .map(|(path, resolved)| {
+                    requested.push(path);
+                    resolved
+                })
+                .collect::<Vec<_>>()
+        })
+        .filter(|paths| !paths.is_empty())
+        .collect();
+
+    let mut results =
+        tauri::async_runtime::spawn_blocking(move || vinyl_media::analyze_albums(&albums))
+            .await
+            .unwrap_or_else(|error| {
+                eprintln!("[Library] Loudness analysis failed: {error}");
+                Vec::new()
+            });
+    // One result per file, in order
+    for (result, path) in results.iter_mut().zip(requested) {
+        result.path = path;
+    }
+    path_access::with_rejected(results, rejected)
+}
+
+/// Originals of files the tag editor rewrote, in the app data folder
+const TAG_BACKUP_DIR: &str = "tag-backups";
+
+/// Write edited tags back to files, keeping a backup of each original
+#[tauri::command]
+pub async fn library_write_tags(
+    app: AppHandle,
+    writes: Vec<TagWrite>,
+) -> Vec<BatchResult<TagWriteResult>> {
+    let (allowed, rejected) = path_access::partition_allowed(&app, writes, |write| {
+        write.path.to_string_lossy().into_owned()
+    });
+    // Write to the canonical paths, report under the ones the renderer sent
+    let (requested, writes): (Vec<String>, Vec<TagWrite>) = allowed
+        .into_iter()
+        .map(|(write, path)| {
+            let requested = write.path.to_string_lossy().into_owned();
+            (requested, TagWrite { path, ..write })
+        })
+        .unzip();
+    let Ok(backup_dir) = app
+        .path()
+        .app_data_dir()
+        .map(|dir| dir.join(TAG_BACKUP_DIR))
+    else {
+        return requested
+            .into_iter()
+            .map(|path| TagWriteResult {
+                path,
+                backup_path: None,
+                error: Some("No app data folder for backups".to_string()),
+            })
+            .map(BatchResult::Done)
+            .chain(rejected.into_iter().map(BatchResult::Rejected))
+            .collect();
+    };
+
+    let mut results = tauri::async_runtime::spawn_blocking(move || {
+        vinyl_media::write_tags_batch(&writes, &backup_dir)
+    })
+    .await
+    .unwrap_or_else(|error| {
+        eprintln!("[Library] Tag write failed: {error}");
+        Vec::new()
+    });
+    for (result, path) in results.iter_mut().zip(requested) {
+        result.path = path;
+    }
+    path_access::with_rejected(results, rejected)
+}
@@ -0,0 +1,382 @@
+//! Which paths the renderer may touch through file commands.
+//!
+//! Same rules as `electron/pathAccess.cjs`: every path from the renderer is
+//! canonicalized (symlinks and `..` resolved against the real file system)
+//! and must fall under a library folder, a folder picked in a dialog, or a
+//! file the user opened or chose a save location for.
+
+use crate::store::Store;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+/// Error codes sent to the renderer alongside the message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PathAccessErrorCode {
+    /// Not an absolute path, or one that can't be resolved
+    InvalidPath,
+    /// Canonical path is outside everything the user has allowed
+    PathNotAllowed,
+}
+
+#[derive(Debug)]
+pub struct PathAccessError {
+    pub code: PathAccessErrorCode,
+    pub message: String,
+}
+
+impl PathAccessError {
+    fn invalid(message: String) -> Self {
+        Self {
+            code: PathAccessErrorCode::InvalidPath,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for PathAccessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Resolve `input` one component at a time so `..` applies to where a symlink
+/// actually points, not to the link's own folder. Components that don't exist
+/// yet (a file about to be written) are kept as given.
+pub fn canonicalize(input: &str) -> Result<PathBuf, PathAccessError> {
+    let path = Path::new(input);
+    if input.contains('\0') || !path.is_absolute() {
+        return Err(PathAccessError::invalid(format!(
+            "Not an absolute path: {input}"
+        )));
+    }
+
+    let mut current = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Prefix(_) | Component::RootDir => current.push(component),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                current.pop();
+            }
+            Component::Normal(name) => {
+                current.push(name);
+                match fs::canonicalize(&current) {
+                    Ok(resolved) => current = resolved,
+                    // Missing, keep it as given. A dangling symlink could be
+                    // pointed anywhere later, so that's refused
+                    Err(_) if fs::symlink_metadata(&current).is_err() => {}
+                    Err(error) => {
+                        return Err(PathAccessError::invalid(format!(
+                            "Can't resolve {input}: {error}"
+                        )))
+                    }
+                }
+            }
+        }
+    }
+    Ok(current)
+}
+
+/// Files the user opened or picked a save location for, this session
+#[derive(Default)]
+pub struct PathAccess {
+    files: Mutex<HashSet<PathBuf>>,
+}
+
+impl PathAccess {
+    pub fn allow_file(&self, path: &str) {
+        match canonicalize(path) {
+            Ok(path) => {
+                self.files.lock().unwrap().insert(path);
+            }
+            Err(error) => eprintln!("[PathAccess] {error}"),
+        }
+    }
+}
+
+/// What the renderer may access right now, canonicalized once for checking
+/// many paths
+pub struct AllowedPaths {
+    folders: Vec<PathBuf>,
+    files: HashSet<PathBuf>,
+}
+
+impl AllowedPaths {
+    /// Library folders, folders picked in a dialog and allowed files
+    pub fn load(app: &AppHandle) -> Self {
+        let store = app.state::<Store>();
+        let mut folders = store.music_folders();
+        folders.extend(store.authorized_folders());
+        Self::new(
+            &folders,
+            app.state::<PathAccess>().files.lock().unwrap().clone(),
+        )
+    }
+
+    fn new(folders: &[String], files: HashSet<PathBuf>) -> Self {
+        Self {
+            folders: folders
+                .iter()
+                .filter_map(|folder| canonicalize(folder).ok())
+                .collect(),
+            files,
+        }
+    }
+
+    /// Canonical path of `input`, or why the renderer can't use it
+    pub fn resolve(&self, input: &str) -> Result<PathBuf, PathAccessError> {
+        let path = canonicalize(input)?;
+        if self.files.contains(&path) || self.folders.iter().any(|folder| path.starts_with(folder))
+        {
+            Ok(path)
+        } else {
+            Err(PathAccessError {
+                code: PathAccessErrorCode::PathNotAllowed,
+                message: format!("Not in your music folders: {input}"),
+            })
+        }
+    }
+
+    /// Canonical path of a file the user opened or picked as a save location.
+    /// Library folders don't count, so the renderer can't overwrite songs
+    pub fn resolve_file(&self, input: &str) -> Result<PathBuf, PathAccessError> {
+        let path = canonicalize(input)?;
+        if self.files.contains(&path) {
+            Ok(path)
+        } else {
+            Err(PathAccessError {
+                code: PathAccessErrorCode::PathNotAllowed,
+                message: format!("Not a file you picked: {input}"),
+            })
+        }
+    }
+}
+
+pub fn resolve(app: &AppHandle, input: &str) -> Result<PathBuf, PathAccessError> {
+    AllowedPaths::load(app).resolve(input)
+}
+
+pub fn resolve_file(app: &AppHandle, input: &str) -> Result<PathBuf, PathAccessError> {
+    AllowedPaths::load(app).resolve_file(input)
+}
+
+/// Remember a folder the user picked, unless it's already covered
+pub fn authorize_folder(app: &AppHandle, folder: &str) {
+    authorize_folders(app, &[folder.to_string()]);
+}
+
+/// Remember several folders at once, skipping those already covered
+pub fn authorize_folders(app: &AppHandle, new_folders: &[String]) {
+    let allowed = AllowedPaths::load(app);
+    let store = app.state::<Store>();
+    let mut folders = store.authorized_folders();
+    let known = folders.len();
+
+    for folder in new_folders {
+        if allowed.resolve(folder).is_err() && !folders.contains(folder) {
+            folders.push(folder.clone());
+        }
+    }
+    if folders.len() > known {
+        store.set_authorized_folders(&folders);
+    }
+}
+
+/// A batch entry that was refused, shaped like the per-file error results
+#[derive(Serialize)]
+pub struct RejectedPath {
+    path: String,
+    error: String,
+    code: PathAccessErrorCode,
+}
+
+/// One entry of a batch command: its own result, or why its path was refused
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum BatchResult<T> {
+    Done(T),
+    Rejected(RejectedPath),
+}
+
+/// Results for a batch, followed by the entries that were refused
+pub fn with_rejected<T>(results: Vec<T>, rejected: Vec<RejectedPath>) -> Vec<BatchResult<T>> {
+    results
+        .into_iter()
+        .map(BatchResult::Done)
+        .chain(rejected.into_iter().map(BatchResult::Rejected))
+        .collect()
+}
+
+/// Split a batch into the items the renderer may touch, each with the canonical
+/// path to do its I/O on, and refusals for the rest
+pub fn partition_allowed<T>(
+    app: &AppHandle,
+    items: Vec<T>,
+    path_of: impl Fn(&T) -> String,
+) -> (Vec<(T, PathBuf)>, Vec<RejectedPath>) {
+    let allowed_paths = AllowedPaths::load(app);
+    let mut allowed = Vec::new();
+    let mut rejected = Vec::new();
+
+    for item in items {
+        let path = path_of(&item);
+        match allowed_paths.resolve(&path) {
+            Ok(resolved) => allowed.push((item, resolved)),
+            Err(error) => rejected.push(RejectedPath {
+                path,
+                error: error.message,
+                code: error.code,
+            }),
+        }
+    }
+    (allowed, rejected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir()
+                .join(format!("vinyl-path-access-{name}-{}", std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(dir.join("Music/Album")).unwrap();
+            fs::create_dir_all(dir.join("Private")).unwrap();
+            fs::write(dir.join("Music/Album/song.mp3"), b"").unwrap();
+            fs::write(dir.join("Private/secret.txt"), b"").unwrap();
+            Self(fs::canonicalize(dir).unwrap())
+        }
+
+        fn path(&self, relative: &str) -> String {
+            format!("{}/{relative}", self.0.display())
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn check(dir: &TempDir, input: &str) -> Result<PathBuf, PathAccessErrorCode> {
+        AllowedPaths::new(&[dir.path("Music")], HashSet::new())
+            .resolve(input)
+            .map_err(|error| error.code)
+    }
+
+    #[test]
+    fn allows_files_inside_library_folders() {
+        let dir = TempDir::new("inside");
+        let song = dir.0.join("Music/Album/song.mp3");
+
+        assert_eq!(
+            check(&dir, &dir.path("Music/Album/song.mp3")),
+            Ok(song.clone())
+        );
+        assert_eq!(
+            check(&dir, &dir.path("Music/Album/../Album/./song.mp3")),
+            Ok(song)
+        );
+        // Not written yet, e.g. an exported playlist
+        assert!(check(&dir, &dir.path("Music/New/mix.m3u8")).is_ok());
+    }
+
+    #[test]
+    fn rejects_paths_that_climb_out() {
+        let dir = TempDir::new("traversal");
+        fs::create_dir_all(dir.0.join("Music-backup")).unwrap();
+
+        for input in [
+            "Music/../Private/secret.txt",
+            "Music/Album/../../Private/secret.txt",
+            "Music/Missing/../../Private/secret.txt",
+            "Music-backup/song.mp3",
+        ] {
+            assert_eq!(
+                check(&dir, &dir.path(input)),
+                Err(PathAccessErrorCode::PathNotAllowed),
+                "{input}"
+            );
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_symlinks_that_point_outside() {
+        let dir = TempDir::new("symlinks");
+        fs::create_dir_all(dir.0.join("Private/Nested")).unwrap();
+        std::os::unix::fs::symlink(dir.0.join("Private"), dir.0.join("Music/link")).unwrap();
+        std::os::unix::fs::symlink(dir.0.join("Private/Nested"), dir.0.join("Music/nested"))
+            .unwrap();
+
+        for input in [
+            "Music/link/secret.txt",
+            // Music/nested/.. is Private, not Music
+            "Music/nested/../secret.txt",
+        ] {
+            assert_eq!(
+                check(&dir, &dir.path(input)),
+                Err(PathAccessErrorCode::PathNotAllowed),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_relative_paths() {
+        let dir = TempDir::new("relative");
+        assert_eq!(
+            check(&dir, "Music/song.mp3"),
+            Err(PathAccessErrorCode::InvalidPath)
+        );
+        assert_eq!(
+            check(&dir, &dir.path("Music/song\0.mp3")),
+            Err(PathAccessErrorCode::InvalidPath)
+        );
+    }
+
+    #[test]
+    fn allows_single_files_without_their_folder() {
+        let dir = TempDir::new("files");
+        let secret = dir.0.join("Private/secret.txt");
+        let allowed = AllowedPaths::new(&[dir.path("Music")], HashSet::from([secret.clone()]));
+
+        assert_eq!(
+            allowed.resolve(&dir.path("Private/secret.txt")).ok(),
+            Some(secret)
+        );
+        assert!(allowed.resolve(&dir.path("Private/other.txt")).is_err());
+    }
+
+    #[test]
+    fn only_writes_to_picked_files() {
+        let dir = TempDir::new("writes");
+        let playlist = dir.0.join("Private/mix.m3u8");
+        let allowed = AllowedPaths::new(&[dir.path("Music")], HashSet::from([playlist.clone()]));
+
+        assert_eq!(
+            allowed
+                .resolve_file(&dir.path("Music/../Private/mix.m3u8"))
+                .ok(),
+            Some(playlist)
+        );
+        for input in ["Music/Album/song.mp3", "Music/mix.m3u8"] {
+            assert_eq!(
+                allowed
+                    .resolve_file(&dir.path(input))
+                    .map_err(|error| error.code),
+                Err(PathAccessErrorCode::PathNotAllowed),
+                "{input}"
+            );
+        }
+    }
+}
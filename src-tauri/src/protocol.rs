@@ -0,0 +1,419 @@
+//! `vinyl-media://` protocol: streams library files to the `<audio>` element.
+//!
+//! Supports HTTP Range requests so the webview can seek without the whole
+//! file (or its transcode) being read into memory. Transcodes that are still
+//! running are served as they grow, with an unknown total length.
+//!
+//! The scheme is a different origin from the app page, so every response
+//! allows cross-origin reads; without that, Web Audio gets silence from the
+//! `<audio>` element.
+
+use crate::transcode::{JobRead, Playable, CACHED_MIME_TYPE};
+use crate::{fs, path_access, scan};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use tauri::http::{header, HeaderValue, Method, Request, Response, StatusCode};
+use tauri::{AppHandle, UriSchemeContext, UriSchemeResponder};
+
+pub const SCHEME: &str = "vinyl-media";
+
+/// Upper bound for one response to any range, the webview asks for more as it plays
+const MAX_CHUNK_LEN: u64 = 1024 * 1024;
+
+#[derive(Debug, PartialEq, Eq)]
+enum ByteRange {
+    /// Inclusive byte range
+    Partial {
+        start: u64,
+        end: u64,
+    },
+    Unsatisfiable,
+}
+
+/// Parse a single-range `Range` header against a file of `size` bytes
+///
+/// No header (or one in other units) is answered like `bytes=0-`, and every
+/// range is cut to `MAX_CHUNK_LEN` bytes from its start, so a file is never
+/// read whole.
+fn parse_range(header: Option<&str>, size: u64) -> ByteRange {
+    let spec = header
+        .and_then(|value| value.trim().strip_prefix("bytes="))
+        .unwrap_or("0-");
+    // Multipart ranges aren't used by media elements, serve the first one
+    let spec = spec.split(',').next().unwrap_or_default().trim();
+    let Some((start, end)) = spec.split_once('-') else {
+        return ByteRange::Unsatisfiable;
+    };
+
+    let (start, end) = match (start.parse::<u64>().ok(), end.parse::<u64>().ok()) {
+        // bytes=-500 is the last 500 bytes
+        (None, Some(suffix)) if start.is_empty() => {
+            (size.saturating_sub(suffix), size.saturating_sub(1))
+        }
+        (Some(start), None) if end.is_empty() => (start, size.saturating_sub(1)),
+        (Some(start), Some(end)) => (start, end.min(size.saturating_sub(1))),
+        _ => return ByteRange::Unsatisfiable,
+    };
+    let end = end.min(start.saturating_add(MAX_CHUNK_LEN - 1));
+
+    if size == 0 || start > end || start >= size {
+        ByteRange::Unsatisfiable
+    } else {
+        ByteRange::Partial { start, end }
+    }
+}
+
+/// Start and optional inclusive end of a range against a file that is still growing.
+///
+/// No header means from the start; suffix ranges can't be answered yet.
+fn open_range(header: Option<&str>) -> Option<(u64, Option<u64>)> {
+    let Some(spec) = header.and_then(|value| value.trim().strip_prefix("bytes=")) else {
+        return Some((0, None));
+    };
+    let spec = spec.split(',').next().unwrap_or_default().trim();
+    let (start, end) = spec.split_once('-')?;
+    let start = start.parse::<u64>().ok()?;
+
+    match end.parse::<u64>() {
+        Ok(end) if end >= start => Some((start, Some(end))),
+        Ok(_) => None,
+        Err(_) if end.is_empty() => Some((start, None)),
+        Err(_) => None,
+    }
+}
+
+fn range_header(request: &Request<Vec<u8>>) -> Option<&str> {
+    request
+        .headers()
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok())
+}
+
+fn mime_type_for(path: &Path) -> &'static str {
+    let ext = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    match ext.as_str() {
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "ogg" => "audio/ogg",
+        "flac" => "audio/flac",
+        "aac" => "audio/aac",
+        "m4a" => "audio/mp4",
+        "wma" => "audio/x-ms-wma",
+        "aiff" => "audio/aiff",
+        "ape" => "audio/ape",
+        "opus" => "audio/opus",
+        "webm" => "audio/webm",
+        _ => "application/octet-stream",
+    }
+}
+
+/// `vinyl-media://localhost/<encoded path>` (`http://vinyl-media.localhost/...` on Windows)
+fn requested_path(request: &Request<Vec<u8>>) -> Option<PathBuf> {
+    let encoded = request.uri().path().strip_prefix('/')?;
+    let decoded = percent_decode(encoded)?;
+    Some(PathBuf::from(decoded))
+}
+
+fn percent_decode(input: &str) -> Option<String> {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let hex = input.get(index + 1..index + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+
+    String::from_utf8(decoded).ok()
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response<Vec<u8>> {
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "text/plain")
+        .body(message.as_bytes().to_vec())
+        .unwrap()
+}
+
+fn serve(app: &AppHandle, request: &Request<Vec<u8>>) -> Response<Vec<u8>> {
+    let Some(requested) = requested_path(request) else {
+        return error_response(StatusCode::BAD_REQUEST, "Invalid media path");
+    };
+    // Serve the canonical path so a symlink swapped in after the check isn't followed
+    let Ok(source) = path_access::resolve(app, &requested.to_string_lossy()) else {
+        return error_response(StatusCode::FORBIDDEN, "Not in your music folders");
+    };
+    if !scan::is_audio_file(&source) {
+        return error_response(StatusCode::FORBIDDEN, "Not an audio file");
+    }
+
+    match fs::open_playable(app, &source) {
+        Ok(Playable::Source(path)) => serve_file(&path, mime_type_for(&path), request),
+        Ok(Playable::Cached(path)) => serve_file(&path, CACHED_MIME_TYPE, request),
+        Ok(Playable::Transcoding(job)) => {
+            let Some((start, end)) = open_range(range_header(request)) else {
+                return error_response(
+                    StatusCode::RANGE_NOT_SATISFIABLE,
+                    "Suffix ranges need the final length",
+                );
+            };
+            let max_len = match end {
+                Some(end) => end.checked_sub(start).and_then(|len| len.checked_add(1)),
+                None => Some(MAX_CHUNK_LEN),
+            };
+            let Some(max_len) = max_len.map(|len| len.min(MAX_CHUNK_LEN)) else {
+                return error_response(StatusCode::RANGE_NOT_SATISFIABLE, "Invalid range");
+            };
+
+            match job.read_from(start, max_len) {
+                JobRead::Data(body) => {
+                    let Some(end) = start.checked_add(body.len() as u64 - 1) else {
+                        return error_response(StatusCode::RANGE_NOT_SATISFIABLE, "Invalid range");
+                    };
+                    Response::builder()
+                        .status(StatusCode::PARTIAL_CONTENT)
+                        .header(header::CONTENT_TYPE, CACHED_MIME_TYPE)
+                        .header(header::ACCEPT_RANGES, "bytes")
+                        .header(header::CONTENT_LENGTH, body.len())
+                        // The final length isn't known until the transcode finishes
+                        .header(header::CONTENT_RANGE, format!("bytes {start}-{end}/*"))
+                        .body(body)
+                        .unwrap()
+                }
+                JobRead::Finished(path) => serve_file(&path, CACHED_MIME_TYPE, request),
+                JobRead::Failed(error) => error_response(StatusCode::INTERNAL_SERVER_ERROR, &error),
+            }
+        }
+        Err(error) => error_response(StatusCode::NOT_FOUND, &error),
+    }
+}
+
+fn serve_file(path: &Path, mime_type: &str, request: &Request<Vec<u8>>) -> Response<Vec<u8>> {
+    match read_range(path, request) {
+        Ok(Chunk {
+            start,
+            end,
+            size,
+            body,
+        }) => Response::builder()
+            .status(StatusCode::PARTIAL_CONTENT)
+            .header(header::CONTENT_TYPE, mime_type)
+            .header(header::ACCEPT_RANGES, "bytes")
+            .header(header::CONTENT_LENGTH, body.len())
+            .header(header::CONTENT_RANGE, format!("bytes {start}-{end}/{size}"))
+            .body(body)
+            .unwrap(),
+        Err(RangeError::Unsatisfiable(size)) => Response::builder()
+            .status(StatusCode::RANGE_NOT_SATISFIABLE)
+            .header(header::CONTENT_RANGE, format!("bytes */{size}"))
+            .body(Vec::new())
+            .unwrap(),
+        Err(RangeError::Io(error)) => {
+            error_response(StatusCode::INTERNAL_SERVER_ERROR, &error.to_string())
+        }
+    }
+}
+
+/// Bytes `start..=end` of a file of `size` bytes
+struct Chunk {
+    start: u64,
+    end: u64,
+    size: u64,
+    body: Vec<u8>,
+}
+
+enum RangeError {
+    Unsatisfiable(u64),
+    Io(std::io::Error),
+}
+
+fn read_range(path: &Path, request: &Request<Vec<u8>>) -> Result<Chunk, RangeError> {
+    let mut file = File::open(path).map_err(RangeError::Io)?;
+    let size = file.metadata().map_err(RangeError::Io)?.len();
+
+    let ByteRange::Partial { start, end } = parse_range(range_header(request), size) else {
+        return Err(RangeError::Unsatisfiable(size));
+    };
+    let mut body = vec![0; (end - start + 1) as usize];
+    file.seek(SeekFrom::Start(start)).map_err(RangeError::Io)?;
+    file.read_exact(&mut body).map_err(RangeError::Io)?;
+
+    Ok(Chunk {
+        start,
+        end,
+        size,
+        body,
+    })
+}
+
+/// Answer a CORS preflight, sent for range requests made with `fetch`
+fn preflight() -> Response<Vec<u8>> {
+    Response::builder()
+        .status(StatusCode::NO_CONTENT)
+        .header(header::ACCESS_CONTROL_ALLOW_METHODS, "GET, HEAD, OPTIONS")
+        .header(header::ACCESS_CONTROL_ALLOW_HEADERS, "Range")
+        .body(Vec::new())
+        .unwrap()
+}
+
+/// Let the app page read a response from this scheme's origin
+fn allow_cross_origin(response: &mut Response<Vec<u8>>) {
+    let headers = response.headers_mut();
+    headers.insert(
+        header::ACCESS_CONTROL_ALLOW_ORIGIN,
+        HeaderValue::from_static("*"),
+    );
+    headers.insert(
+        header::ACCESS_CONTROL_EXPOSE_HEADERS,
+        HeaderValue::from_static("Accept-Ranges, Content-Length, Content-Range"),
+    );
+}
+
+/// Protocol handler; requests are served off the main thread since they may wait on a transcode
+pub fn handle(
+    context: UriSchemeContext<'_, tauri::Wry>,
+    request: Request<Vec<u8>>,
+    responder: UriSchemeResponder,
+) {
+    let app = context.app_handle().clone();
+    std::thread::spawn(move || {
+        let mut response = if request.method() == Method::OPTIONS {
+            preflight()
+        } else {
+            serve(&app, &request)
+        };
+        allow_cross_origin(&mut response);
+        responder.respond(response)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_byte_ranges() {
+        assert_eq!(
+            parse_range(None, 100),
+            ByteRange::Partial { start: 0, end: 99 }
+        );
+        assert_eq!(
+            parse_range(Some("bytes=0-9"), 100),
+            ByteRange::Partial { start: 0, end: 9 }
+        );
+        assert_eq!(
+            parse_range(Some("bytes=90-200"), 100),
+            ByteRange::Partial { start: 90, end: 99 }
+        );
+        assert_eq!(
+            parse_range(Some("bytes=-10"), 100),
+            ByteRange::Partial { start: 90, end: 99 }
+        );
+        assert_eq!(
+            parse_range(Some("bytes=100-"), 100),
+            ByteRange::Unsatisfiable
+        );
+        assert_eq!(
+            parse_range(Some("bytes=abc"), 100),
+            ByteRange::Unsatisfiable
+        );
+    }
+
+    #[test]
+    fn caps_open_ended_ranges() {
+        let size = MAX_CHUNK_LEN * 4;
+        assert_eq!(
+            parse_range(Some("bytes=10-"), size),
+            ByteRange::Partial {
+                start: 10,
+                end: 10 + MAX_CHUNK_LEN - 1
+            }
+        );
+        assert_eq!(
+            parse_range(Some("bytes=0-"), 50),
+            ByteRange::Partial { start: 0, end: 49 }
+        );
+        assert_eq!(
+            parse_range(None, size),
+            ByteRange::Partial {
+                start: 0,
+                end: MAX_CHUNK_LEN - 1
+            }
+        );
+    }
+
+    #[test]
+    fn caps_closed_and_suffix_ranges() {
+        let size = MAX_CHUNK_LEN * 4;
+        assert_eq!(
+            parse_range(Some("bytes=0-18446744073709551614"), size),
+            ByteRange::Partial {
+                start: 0,
+                end: MAX_CHUNK_LEN - 1
+            }
+        );
+        assert_eq!(
+            parse_range(Some(&format!("bytes=-{size}")), size),
+            ByteRange::Partial {
+                start: 0,
+                end: MAX_CHUNK_LEN - 1
+            }
+        );
+        assert_eq!(
+            parse_range(Some("bytes=-10"), size),
+            ByteRange::Partial {
+                start: size - 10,
+                end: size - 1
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_ranges_past_u64_max() {
+        assert_eq!(
+            parse_range(Some("bytes=18446744073709551615-"), 100),
+            ByteRange::Unsatisfiable
+        );
+        assert_eq!(
+            parse_range(Some("bytes=18446744073709551615-"), u64::MAX),
+            ByteRange::Unsatisfiable
+        );
+        assert_eq!(
+            open_range(Some("bytes=18446744073709551615-")),
+            Some((u64::MAX, None))
+        );
+    }
+
+    #[test]
+    fn parses_ranges_against_growing_files() {
+        assert_eq!(open_range(None), Some((0, None)));
+        assert_eq!(open_range(Some("bytes=0-")), Some((0, None)));
+        assert_eq!(open_range(Some("bytes=100-199")), Some((100, Some(199))));
+        assert_eq!(open_range(Some("bytes=-500")), None);
+        assert_eq!(open_range(Some("bytes=9-3")), None);
+    }
+
+    #[test]
+    fn decodes_percent_encoded_paths() {
+        assert_eq!(
+            percent_decode("%2Fmusic%2FAC%2FDC%20-%20T.N.T..mp3").as_deref(),
+            Some("/music/AC/DC - T.N.T..mp3")
+        );
+        assert_eq!(
+            percent_decode("C%3A%5CMusic%5Cs%C3%A9ance.flac").as_deref(),
+            Some("C:\\Music\\séance.flac")
+        );
+        assert_eq!(percent_decode("%zz"), None);
+    }
+}
@@ -0,0 +1,152 @@
+//! Music folder scanning, shared by the `fs:` and `library:` commands.
+
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+pub use vinyl_media::library_index::is_audio_file;
+
+/// Music file info returned from scanning (matches `MusicFileInfo` in platform.ts)
+#[derive(Debug, Clone, Serialize)]
+pub struct MusicFileInfo {
+    pub path: String,
+    pub name: String,
+    pub extension: String,
+    /// Relative folder path from the scanned root (for playlist creation)
+    pub folder: Option<String>,
+}
+
+impl MusicFileInfo {
+    /// Describe a file relative to the library root it belongs to (if any)
+    pub fn from_path(path: &Path, root: Option<&Path>) -> Self {
+        let folder = match (root, path.parent()) {
+            (Some(root), Some(parent)) => parent
+                .strip_prefix(root)
+                .ok()
+                .map(|relative| relative.to_string_lossy().into_owned())
+                .filter(|relative| !relative.is_empty()),
+            _ => None,
+        };
+
+        Self {
+            path: path.to_string_lossy().into_owned(),
+            name: path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            extension: extension_of(path).unwrap_or_default(),
+            folder,
+        }
+    }
+}
+
+fn extension_of(path: &Path) -> Option<String> {
+    path.extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+}
+
+/// Dotfiles and dot-folders are skipped, like the chokidar watcher does
+pub fn is_hidden(path: &Path) -> bool {
+    path.components().any(|component| {
+        component
+            .as_os_str()
+            .to_str()
+            .is_some_and(|name| name.starts_with('.') && name != "." && name != "..")
+    })
+}
+
+/// Recursively scan a directory for audio files
+pub fn scan_music_folder(root: &Path) -> Vec<MusicFileInfo> {
+    let mut results = Vec::new();
+    scan_into(root, root, &mut results);
+    results
+}
+
+/// Scan the canonical `resolved` folder, reporting file paths under
+/// `requested`, the form the library keeps them in
+pub fn scan_resolved_folder(resolved: &Path, requested: &Path) -> Vec<MusicFileInfo> {
+    let mut files = scan_music_folder(resolved);
+    for file in &mut files {
+        if let Ok(relative) = Path::new(&file.path).strip_prefix(resolved) {
+            file.path = requested.join(relative).to_string_lossy().into_owned();
+        }
+    }
+    files
+}
+
+fn scan_into(folder: &Path, root: &Path, results: &mut Vec<MusicFileInfo>) {
+    let entries = match fs::read_dir(folder) {
+        Ok(entries) => entries,
+        Err(error) => {
+            eprintln!("Error scanning folder: {} {error}", folder.display());
+            return;
+        }
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+
+        if file_type.is_dir() {
+            scan_into(&path, root, results);
+        } else if file_type.is_file() && is_audio_file(&path) {
+            results.push(MusicFileInfo::from_path(&path, Some(root)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn detects_audio_extensions_case_insensitively() {
+        assert!(is_audio_file(Path::new("/music/song.MP3")));
+        assert!(is_audio_file(Path::new("/music/song.flac")));
+        assert!(!is_audio_file(Path::new("/music/cover.jpg")));
+        assert!(!is_audio_file(Path::new("/music/noextension")));
+    }
+
+    #[test]
+    fn reports_folder_relative_to_root() {
+        let root = PathBuf::from("/music");
+        let nested = MusicFileInfo::from_path(&root.join("Artist/Album/01.mp3"), Some(&root));
+        assert_eq!(nested.name, "01.mp3");
+        assert_eq!(nested.extension, "mp3");
+        assert_eq!(
+            nested.folder.as_deref(),
+            Some(Path::new("Artist/Album").to_string_lossy().as_ref())
+        );
+
+        let top_level = MusicFileInfo::from_path(&root.join("song.ogg"), Some(&root));
+        assert_eq!(top_level.folder, None);
+    }
+
+    #[test]
+    fn reports_resolved_scans_under_the_requested_folder() {
+        let root = std::env::temp_dir().join(format!("vinyl-scan-{}", std::process::id()));
+        fs::create_dir_all(root.join("Album")).unwrap();
+        fs::write(root.join("Album/01.mp3"), b"").unwrap();
+
+        let requested = PathBuf::from("/Users/me/Music");
+        let files = scan_resolved_folder(&root, &requested);
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(
+            files[0].path,
+            requested.join("Album").join("01.mp3").to_string_lossy()
+        );
+        assert_eq!(files[0].folder.as_deref(), Some("Album"));
+    }
+
+    #[test]
+    fn skips_dotfiles() {
+        assert!(is_hidden(Path::new("/music/.sync/song.mp3")));
+        assert!(is_hidden(Path::new("/music/._song.mp3")));
+        assert!(!is_hidden(Path::new("/music/song.mp3")));
+    }
+}
@@ -0,0 +1,199 @@
+//! Simple JSON file store, compatible with the Electron `config.json` store.
+
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::State;
+
+const SETUP_COMPLETED_KEY: &str = "setupCompleted";
+const MUSIC_FOLDERS_KEY: &str = "musicFolders";
+/// Folders picked in a dialog, see `path_access`
+const AUTHORIZED_FOLDERS_KEY: &str = "authorizedFolders";
+/// Last imported folder, kept by the renderer in older versions
+const STORED_FOLDER_PATH_KEY: &str = "musicFolderPath";
+/// Set on upgrade until the renderer has reported the folders of its songs
+const SONG_FOLDER_MIGRATION_KEY: &str = "songFolderMigrationPending";
+/// Only the backend writes these, so the renderer can't widen its own file access
+const PROTECTED_KEYS: &[&str] = &[
+    MUSIC_FOLDERS_KEY,
+    AUTHORIZED_FOLDERS_KEY,
+    SONG_FOLDER_MIGRATION_KEY,
+];
+const TRANSCODE_CACHE_MAX_SIZE_KEY: &str = "transcodeCacheMaxSize";
+
+pub struct Store {
+    path: PathBuf,
+    data: Mutex<Map<String, Value>>,
+}
+
+impl Store {
+    /// Load the store from `config.json` in the app data directory
+    pub fn load(dir: PathBuf) -> Self {
+        let path = dir.join("config.json");
+        println!("[Store] Using file-based store at: {}", path.display());
+
+        let data = fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| match serde_json::from_str(&contents) {
+                Ok(Value::Object(map)) => Some(map),
+                Ok(_) => None,
+                Err(error) => {
+                    eprintln!("[Store] Error loading config: {error}");
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        Self {
+            path,
+            data: Mutex::new(data),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<Value> {
+        self.data.lock().unwrap().get(key).cloned()
+    }
+
+    pub fn set(&self, key: &str, value: Value) {
+        let mut data = self.data.lock().unwrap();
+        data.insert(key.to_string(), value);
+        self.save(&data);
+    }
+
+    pub fn delete(&self, key: &str) {
+        let mut data = self.data.lock().unwrap();
+        data.remove(key);
+        self.save(&data);
+    }
+
+    /// Library folders, in the order they were added
+    pub fn music_folders(&self) -> Vec<String> {
+        self.get(MUSIC_FOLDERS_KEY)
+            .and_then(|value| serde_json::from_value(value).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn set_music_folders(&self, folders: &[String]) {
+        self.set(MUSIC_FOLDERS_KEY, Value::from(folders.to_vec()));
+    }
+
+    /// Folders picked in a dialog (imports, relinking, backup folders)
+    pub fn authorized_folders(&self) -> Vec<String> {
+        self.get(AUTHORIZED_FOLDERS_KEY)
+            .and_then(|value| serde_json::from_value(value).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn set_authorized_folders(&self, folders: &[String]) {
+        self.set(AUTHORIZED_FOLDERS_KEY, Value::from(folders.to_vec()));
+    }
+
+    /// Older versions only kept the last imported folder, in the renderer's
+    /// `musicFolderPath` setting; carry it over once so its songs keep playing.
+    /// Songs from earlier imports are only known to the renderer, which reports
+    /// their folders once through `library_migrate_song_folders`
+    pub fn migrate_authorized_folders(&self) {
+        if self.get(AUTHORIZED_FOLDERS_KEY).is_some() {
+            return;
+        }
+        let folders: Vec<String> = self
+            .get(STORED_FOLDER_PATH_KEY)
+            .and_then(|value| value.as_str().map(str::to_string))
+            .into_iter()
+            .collect();
+        self.set_authorized_folders(&folders);
+        self.set(SONG_FOLDER_MIGRATION_KEY, Value::Bool(true));
+    }
+
+    /// Whether song folders still need carrying over, clearing the flag
+    pub fn take_song_folder_migration(&self) -> bool {
+        let pending = self
+            .get(SONG_FOLDER_MIGRATION_KEY)
+            .and_then(|value| value.as_bool())
+            .unwrap_or(false);
+        if pending {
+            self.delete(SONG_FOLDER_MIGRATION_KEY);
+        }
+        pending
+    }
+
+    /// Size limit for the transcode cache, in bytes
+    pub fn transcode_cache_max_size(&self) -> Option<u64> {
+        self.get(TRANSCODE_CACHE_MAX_SIZE_KEY)
+            .and_then(|value| value.as_u64())
+    }
+
+    pub fn set_transcode_cache_max_size(&self, max_size: u64) {
+        self.set(TRANSCODE_CACHE_MAX_SIZE_KEY, Value::from(max_size));
+    }
+
+    fn save(&self, data: &Map<String, Value>) {
+        if let Some(dir) = self.path.parent() {
+            if let Err(error) = fs::create_dir_all(dir) {
+                eprintln!("[Store] Error creating config directory: {error}");
+                return;
+            }
+        }
+
+        let result = serde_json::to_string_pretty(data)
+            .map_err(|error| error.to_string())
+            .and_then(|json| fs::write(&self.path, json).map_err(|error| error.to_string()));
+
+        if let Err(error) = result {
+            eprintln!("[Store] Error saving config: {error}");
+        }
+    }
+}
+
+// ============================================
+// Store commands
+// ============================================
+
+#[tauri::command]
+pub fn store_get(store: State<'_, Store>, key: String) -> Option<Value> {
+    store.get(&key)
+}
+
+#[tauri::command]
+pub fn store_set(store: State<'_, Store>, key: String, value: Value) -> bool {
+    if PROTECTED_KEYS.contains(&key.as_str()) {
+        return false;
+    }
+    store.set(&key, value);
+    true
+}
+
+#[tauri::command]
+pub fn store_delete(store: State<'_, Store>, key: String) -> bool {
+    if PROTECTED_KEYS.contains(&key.as_str()) {
+        return false;
+    }
+    store.delete(&key);
+    true
+}
+
+// ============================================
+// First Launch / Setup State
+// ============================================
+
+#[tauri::command]
+pub fn setup_is_first_launch(store: State<'_, Store>) -> bool {
+    !store
+        .get(SETUP_COMPLETED_KEY)
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false)
+}
+
+#[tauri::command]
+pub fn setup_complete_setup(store: State<'_, Store>) -> bool {
+    store.set(SETUP_COMPLETED_KEY, Value::Bool(true));
+    true
+}
+
+/// Reset setup state (for testing)
+#[tauri::command]
+pub fn setup_reset_setup(store: State<'_, Store>) -> bool {
+    store.delete(SETUP_COMPLETED_KEY);
+    true
+}
@@ -0,0 +1,635 @@
+//! Audio transcoding for formats the webview can't play natively.
+//!
+//! AAC/ALAC and APE are decoded in-process by `vinyl-media`; FFmpeg is only
+//! needed for WMA, which has no pure-Rust decoder.
+//!
+//! Transcodes are cached as FLAC in a size-capped directory and evicted least
+//! recently played first. They run in the background and can be read while
+//! they are still being written (see [`Job::read_from`]), so playback starts
+//! as soon as the first frames are on disk.
+
+use crate::store::Store;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufWriter, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::State;
+
+/// Formats that need transcoding on Linux (the webview doesn't support these natively)
+const FORMATS_NEEDING_TRANSCODE: &[&str] = &["m4a", "aac", "wma", "ape"];
+
+/// On Windows/macOS, only WMA and APE need transcoding
+const FORMATS_NEEDING_TRANSCODE_ANYWHERE: &[&str] = &["wma", "ape"];
+
+pub const DEFAULT_MAX_CACHE_SIZE: u64 = 2 * 1024 * 1024 * 1024;
+
+pub const CACHED_MIME_TYPE: &str = "audio/flac";
+
+const INDEX_FILE: &str = "index.json";
+
+/// Bumped when cached files should be redone (2: no longer cut to 16 bits).
+/// Old entries stay in the index and are evicted like any other.
+const CACHE_VERSION: u32 = 2;
+
+/// Last-played times are only persisted this often, playback reads in chunks
+const TOUCH_INTERVAL_MS: u64 = 60_000;
+
+/// How long a reader waits for a running transcode before checking again
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+pub fn needs_transcoding(path: &Path) -> bool {
+    let Some(ext) = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+    else {
+        return false;
+    };
+
+    if cfg!(target_os = "linux") {
+        FORMATS_NEEDING_TRANSCODE.contains(&ext.as_str())
+    } else {
+        FORMATS_NEEDING_TRANSCODE_ANYWHERE.contains(&ext.as_str())
+    }
+}
+
+fn ffmpeg_available() -> bool {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+
+    *AVAILABLE.get_or_init(|| {
+        let available = Command::new("ffmpeg")
+            .arg("-version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success());
+
+        if available {
+            println!("[Transcode] FFmpeg available");
+        } else {
+            println!("[Transcode] FFmpeg not found on system");
+        }
+        available
+    })
+}
+
+/// Cache file name for a source, which changes whenever the file is modified
+fn cache_key(source: &Path, size: u64, modified: SystemTime) -> String {
+    let modified = modified
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or_default();
+    let identity = format!(
+        "{}\0{size}\0{modified}\0{CACHE_VERSION}",
+        source.to_string_lossy()
+    );
+
+    // FNV-1a keeps the file name stable across releases, unlike `DefaultHasher`
+    let hash = identity
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("{hash:016x}.flac")
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+fn transcode(input: &Path, output: &Path) -> Result<(), String> {
+    println!("[Transcode] Starting: {}", input.display());
+
+    // FFmpeg flushes FLAC frames as it goes, so the output is readable while it grows.
+    // s32 is written as 24-bit FLAC, so hi-res sources aren't cut to 16 bits
+    let result = Command::new("ffmpeg")
+        .arg("-i")
+        .arg(input)
+        .args([
+            "-y",
+            "-vn",
+            "-acodec",
+            "flac",
+            "-sample_fmt",
+            "s32",
+            "-f",
+            "flac",
+        ])
+        .arg(output)
+        .stdout(Stdio::null())
+        .output()
+        .map_err(|error| format!("FFmpeg not available: {error}"))?;
+
+    if result.status.success() {
+        println!("[Transcode] Complete: {}", output.display());
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    let tail_start = stderr.len().saturating_sub(500);
+    eprintln!("[Transcode] Failed with status: {}", result.status);
+    eprintln!(
+        "[Transcode] stderr: {}",
+        stderr.get(tail_start..).unwrap_or(&stderr)
+    );
+    Err(format!("FFmpeg failed with {}", result.status))
+}
+
+fn decode_natively(source: &Path, output: &Path) -> Result<(), String> {
+    let file = File::create(output).map_err(|error| error.to_string())?;
+    vinyl_media::decode_to_flac(source, BufWriter::new(file))
+        .map(drop)
+        .map_err(|error| error.to_string())
+}
+
+/// Decode into `output` with the native decoder, falling back to FFmpeg
+fn decode_to_cache(source: &Path, output: &Path) -> Result<(), String> {
+    if vinyl_media::can_decode(source) {
+        println!("[Transcode] Decoding natively: {}", source.display());
+        match decode_natively(source, output) {
+            Ok(()) => return Ok(()),
+            Err(error) if ffmpeg_available() => {
+                eprintln!("[Transcode] Native decode failed, trying FFmpeg: {error}");
+            }
+            Err(error) => return Err(error),
+        }
+    }
+
+    transcode(source, output)
+}
+
+fn ensure_decodable(source: &Path) -> Result<(), String> {
+    if vinyl_media::can_decode(source) || ffmpeg_available() {
+        return Ok(());
+    }
+
+    let ext = source
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    Err(format!(
+        "Playing .{ext} files requires FFmpeg, which was not found on this system"
+    ))
+}
+
+#[derive(Debug, Clone)]
+enum JobStatus {
+    Running,
+    Done(PathBuf),
+    Failed(String),
+}
+
+/// A transcode in progress, written to a `.part` file next to its cache entry
+pub struct Job {
+    partial: PathBuf,
+    status: Mutex<JobStatus>,
+    changed: Condvar,
+}
+
+/// Result of reading from a running transcode
+pub enum JobRead {
+    Data(Vec<u8>),
+    /// The transcode has finished, read the cached file instead
+    Finished(PathBuf),
+    Failed(String),
+}
+
+impl Job {
+    fn new(partial: PathBuf) -> Self {
+        Self {
+            partial,
+            status: Mutex::new(JobStatus::Running),
+            changed: Condvar::new(),
+        }
+    }
+
+    /// Read up to `max_len` bytes at `offset`, waiting until they have been written.
+    ///
+    /// The status lock is held while reading so the `.part` file can't be
+    /// renamed into place halfway through.
+    pub fn read_from(&self, offset: u64, max_len: u64) -> JobRead {
+        let mut status = self.status.lock().unwrap();
+        loop {
+            match &*status {
+                JobStatus::Done(path) => return JobRead::Finished(path.clone()),
+                JobStatus::Failed(error) => return JobRead::Failed(error.clone()),
+                JobStatus::Running => {}
+            }
+
+            let available = fs::metadata(&self.partial).map_or(0, |meta| meta.len());
+            if available > offset {
+                let len = (available - offset).min(max_len);
+                let mut data = vec![0; len as usize];
+                let read = File::open(&self.partial).and_then(|mut file| {
+                    file.seek(SeekFrom::Start(offset))?;
+                    file.read_exact(&mut data)
+                });
+                return match read {
+                    Ok(()) => JobRead::Data(data),
+                    Err(error) => JobRead::Failed(error.to_string()),
+                };
+            }
+
+            status = self.changed.wait_timeout(status, POLL_INTERVAL).unwrap().0;
+        }
+    }
+
+    /// Block until the transcode has finished
+    pub fn wait(&self) -> Result<PathBuf, String> {
+        let mut status = self.status.lock().unwrap();
+        loop {
+            match &*status {
+                JobStatus::Done(path) => return Ok(path.clone()),
+                JobStatus::Failed(error) => return Err(error.clone()),
+                JobStatus::Running => status = self.changed.wait(status).unwrap(),
+            }
+        }
+    }
+}
+
+/// Where the audio for a file should be read from
+pub enum Playable {
+    /// The library file itself, no transcoding needed
+    Source(PathBuf),
+    /// A finished transcode in the cache
+    Cached(PathBuf),
+    Transcoding(Arc<Job>),
+}
+
+/// Where the playable data for a file lives, once any transcode has finished
+pub struct PreparedFile {
+    pub path: PathBuf,
+    pub transcoded: bool,
+    pub mime_type: Option<&'static str>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CacheEntry {
+    source: PathBuf,
+    size: u64,
+    /// Milliseconds since the epoch
+    last_accessed: u64,
+}
+
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<String, CacheEntry>,
+    jobs: HashMap<String, Arc<Job>>,
+}
+
+/// Entries to remove, least recently used first, to fit `max_size`
+fn entries_to_evict(
+    entries: &HashMap<String, CacheEntry>,
+    max_size: u64,
+    keep: &str,
+) -> Vec<String> {
+    let mut total: u64 = entries.values().map(|entry| entry.size).sum();
+    let mut candidates: Vec<(&String, &CacheEntry)> =
+        entries.iter().filter(|(key, _)| *key != keep).collect();
+    candidates.sort_by_key(|(_, entry)| entry.last_accessed);
+
+    let mut evicted = Vec::new();
+    for (key, entry) in candidates {
+        if total <= max_size {
+            break;
+        }
+        total -= entry.size;
+        evicted.push(key.clone());
+    }
+    evicted
+}
+
+struct CacheInner {
+    dir: PathBuf,
+    max_size: AtomicU64,
+    state: Mutex<CacheState>,
+}
+
+/// Size-capped LRU cache of transcoded audio
+#[derive(Clone)]
+pub struct TranscodeCache(Arc<CacheInner>);
+
+impl TranscodeCache {
+    /// Load the index, dropping entries whose files are gone and files it doesn't know about
+    pub fn load(dir: PathBuf, max_size: u64) -> Self {
+        let mut entries: HashMap<String, CacheEntry> = fs::read_to_string(dir.join(INDEX_FILE))
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        entries.retain(|key, _| dir.join(key).is_file());
+
+        // Unfinished transcodes and the WAV files older versions left behind
+        if let Ok(read_dir) = fs::read_dir(&dir) {
+            for item in read_dir.flatten() {
+                let name = item.file_name().to_string_lossy().into_owned();
+                if name != INDEX_FILE && !entries.contains_key(&name) {
+                    let _ = fs::remove_file(item.path());
+                }
+            }
+        }
+
+        let cache = Self(Arc::new(CacheInner {
+            dir,
+            max_size: AtomicU64::new(max_size),
+            state: Mutex::new(CacheState {
+                entries,
+                jobs: HashMap::new(),
+            }),
+        }));
+        cache.evict("");
+        cache
+    }
+
+    /// Where transcoded files are kept
+    pub fn dir(&self) -> &Path {
+        &self.0.dir
+    }
+
+    /// Resolve where to read `source` from, starting a transcode if there's no cached copy
+    pub fn open(&self, source: &Path) -> Result<Playable, String> {
+        if !needs_transcoding(source) {
+            return Ok(Playable::Source(source.to_path_buf()));
+        }
+
+        let metadata = fs::metadata(source).map_err(|error| error.to_string())?;
+        let key = cache_key(
+            source,
+            metadata.len(),
+            metadata.modified().unwrap_or(UNIX_EPOCH),
+        );
+
+        let mut state = self.0.state.lock().unwrap();
+        if let Some(job) = state.jobs.get(&key) {
+            return Ok(Playable::Transcoding(job.clone()));
+        }
+
+        let cached = self.0.dir.join(&key);
+        if let Some(entry) = state.entries.get_mut(&key) {
+            if cached.is_file() {
+                let now = now_ms();
+                if now.saturating_sub(entry.last_accessed) > TOUCH_INTERVAL_MS {
+                    entry.last_accessed = now;
+                    self.save_index(&state);
+                }
+                return Ok(Playable::Cached(cached));
+            }
+            state.entries.remove(&key);
+        }
+
+        ensure_decodable(source)?;
+        fs::create_dir_all(&self.0.dir).map_err(|error| error.to_string())?;
+
+        let job = Arc::new(Job::new(self.0.dir.join(format!("{key}.part"))));
+        state.jobs.insert(key.clone(), job.clone());
+        drop(state);
+
+        let cache = self.clone();
+        let source = source.to_path_buf();
+        let running = job.clone();
+        std::thread::spawn(move || cache.run_job(&key, &source, &running));
+
+        Ok(Playable::Transcoding(job))
+    }
+
+    /// Resolve the file for `source`, waiting for any transcode to finish
+    pub fn prepare(&self, source: &Path) -> Result<PreparedFile, String> {
+        let (path, transcoded) = match self.open(source)? {
+            Playable::Source(path) => (path, false),
+            Playable::Cached(path) => {
+                println!("[Transcode] Using cached: {}", path.display());
+                (path, true)
+            }
+            Playable::Transcoding(job) => (job.wait()?, true),
+        };
+
+        Ok(PreparedFile {
+            path,
+            transcoded,
+            mime_type: transcoded.then_some(CACHED_MIME_TYPE),
+        })
+    }
+
+    fn run_job(&self, key: &str, source: &Path, job: &Job) {
+        let cached = self.0.dir.join(key);
+        let result = decode_to_cache(source, &job.partial);
+
+        let status = {
+            // Renamed under the job lock so readers never see the file disappear
+            let mut status = job.status.lock().unwrap();
+            *status = match result
+                .and_then(|()| fs::rename(&job.partial, &cached).map_err(|e| e.to_string()))
+            {
+                Ok(()) => JobStatus::Done(cached.clone()),
+                Err(error) => {
+                    eprintln!("[Transcode] Error: {error}");
+                    let _ = fs::remove_file(&job.partial);
+                    JobStatus::Failed(error)
+                }
+            };
+            status.clone()
+        };
+        job.changed.notify_all();
+
+        let mut state = self.0.state.lock().unwrap();
+        state.jobs.remove(key);
+        if let JobStatus::Done(_) = status {
+            // Older transcodes of the same file are stale now
+            let stale: Vec<String> = state
+                .entries
+                .iter()
+                .filter(|(_, entry)| entry.source == source)
+                .map(|(key, _)| key.clone())
+                .collect();
+            for key in stale {
+                state.entries.remove(&key);
+                let _ = fs::remove_file(self.0.dir.join(key));
+            }
+
+            state.entries.insert(
+                key.to_string(),
+                CacheEntry {
+                    source: source.to_path_buf(),
+                    size: fs::metadata(&cached).map_or(0, |meta| meta.len()),
+                    last_accessed: now_ms(),
+                },
+            );
+            self.save_index(&state);
+        }
+        drop(state);
+
+        self.evict(key);
+    }
+
+    /// Remove least recently played entries until the cache fits its size limit
+    fn evict(&self, keep: &str) {
+        let mut state = self.0.state.lock().unwrap();
+        let evicted = entries_to_evict(
+            &state.entries,
+            self.0.max_size.load(Ordering::Relaxed),
+            keep,
+        );
+        if evicted.is_empty() {
+            return;
+        }
+
+        for key in &evicted {
+            state.entries.remove(key);
+            let _ = fs::remove_file(self.0.dir.join(key));
+        }
+        println!("[Transcode] Evicted {} cached file(s)", evicted.len());
+        self.save_index(&state);
+    }
+
+    fn save_index(&self, state: &CacheState) {
+        let result = serde_json::to_string(&state.entries)
+            .map_err(|error| error.to_string())
+            .and_then(|json| {
+                fs::create_dir_all(&self.0.dir)
+                    .and_then(|()| fs::write(self.0.dir.join(INDEX_FILE), json))
+                    .map_err(|error| error.to_string())
+            });
+
+        if let Err(error) = result {
+            eprintln!("[Transcode] Error saving cache index: {error}");
+        }
+    }
+
+    fn info(&self) -> CacheInfo {
+        let state = self.0.state.lock().unwrap();
+        let mut entries: Vec<CacheEntryInfo> = state
+            .entries
+            .values()
+            .map(|entry| CacheEntryInfo {
+                source_path: entry.source.to_string_lossy().into_owned(),
+                size: entry.size,
+                last_accessed: entry.last_accessed,
+            })
+            .collect();
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.last_accessed));
+
+        CacheInfo {
+            directory: self.0.dir.to_string_lossy().into_owned(),
+            total_size: entries.iter().map(|entry| entry.size).sum(),
+            max_size: self.0.max_size.load(Ordering::Relaxed),
+            active_transcodes: state.jobs.len(),
+            entries,
+        }
+    }
+
+    fn clear(&self) {
+        let mut state = self.0.state.lock().unwrap();
+        for key in state.entries.keys() {
+            let _ = fs::remove_file(self.0.dir.join(key));
+        }
+        state.entries.clear();
+        self.save_index(&state);
+        println!("[Transcode] Cache cleared");
+    }
+
+    fn set_max_size(&self, max_size: u64) {
+        self.0.max_size.store(max_size, Ordering::Relaxed);
+        self.evict("");
+    }
+}
+
+// ============================================
+// Cache commands
+// ============================================
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheEntryInfo {
+    source_path: String,
+    size: u64,
+    last_accessed: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheInfo {
+    directory: String,
+    total_size: u64,
+    max_size: u64,
+    active_transcodes: usize,
+    /// Most recently played first
+    entries: Vec<CacheEntryInfo>,
+}
+
+#[tauri::command]
+pub fn transcode_cache_get_info(cache: State<'_, TranscodeCache>) -> CacheInfo {
+    cache.info()
+}
+
+/// Delete every finished transcode; running ones are left to complete
+#[tauri::command]
+pub fn transcode_cache_clear(cache: State<'_, TranscodeCache>) -> CacheInfo {
+    cache.clear();
+    cache.info()
+}
+
+#[tauri::command]
+pub fn transcode_cache_set_max_size(
+    cache: State<'_, TranscodeCache>,
+    store: State<'_, Store>,
+    max_size: u64,
+) -> CacheInfo {
+    store.set_transcode_cache_max_size(max_size);
+    cache.set_max_size(max_size);
+    cache.info()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_key_changes_when_the_source_does() {
+        let path = Path::new("/music/a.wma");
+        let modified = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let key = cache_key(path, 1000, modified);
+
+        assert_eq!(key, cache_key(path, 1000, modified));
+        assert_ne!(key, cache_key(Path::new("/music/b.wma"), 1000, modified));
+        assert_ne!(key, cache_key(path, 1001, modified));
+        assert_ne!(
+            key,
+            cache_key(path, 1000, modified + Duration::from_secs(1))
+        );
+        assert!(key.ends_with(".flac"));
+    }
+
+    #[test]
+    fn evicts_least_recently_used_first() {
+        let entry = |size, last_accessed| CacheEntry {
+            source: PathBuf::from("/music/song.wma"),
+            size,
+            last_accessed,
+        };
+        let entries = HashMap::from([
+            ("old".to_string(), entry(40, 1)),
+            ("middle".to_string(), entry(40, 2)),
+            ("new".to_string(), entry(40, 3)),
+        ]);
+
+        assert!(entries_to_evict(&entries, 120, "").is_empty());
+        assert_eq!(entries_to_evict(&entries, 100, ""), vec!["old"]);
+        assert_eq!(entries_to_evict(&entries, 40, ""), vec!["old", "middle"]);
+        // The file that was just transcoded is never evicted
+        assert_eq!(entries_to_evict(&entries, 40, "old"), vec!["middle", "new"]);
+    }
+
+    #[test]
+    fn always_transcodes_wma_and_ape() {
+        assert!(needs_transcoding(Path::new("song.WMA")));
+        assert!(needs_transcoding(Path::new("song.ape")));
+        assert!(!needs_transcoding(Path::new("song.mp3")));
+    }
+}
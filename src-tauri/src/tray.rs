@@ -0,0 +1,224 @@
+//! System tray with playback controls.
+
+use serde::Deserialize;
+use std::sync::Mutex;
+use tauri::image::Image;
+use tauri::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Emitter, Manager, State, Wry};
+
+const TRAY_ID: &str = "main";
+const DEFAULT_TOOLTIP: &str = "Vinyl Music Player";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TraySong {
+    pub title: String,
+    pub artist: String,
+}
+
+/// Playback state sent from the renderer (`TrayPlaybackState` in platform.ts)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaybackState {
+    #[serde(default)]
+    pub is_playing: bool,
+    #[serde(default)]
+    pub song: Option<TraySong>,
+}
+
+#[derive(Default)]
+pub struct TrayState(Mutex<PlaybackState>);
+
+fn build_menu(app: &AppHandle, state: &PlaybackState) -> tauri::Result<Menu<Wry>> {
+    let menu = Menu::new(app)?;
+
+    // Show current song info if available
+    if let Some(song) = &state.song {
+        let title = if song.title.is_empty() {
+            "Unknown Title"
+        } else {
+            &song.title
+        };
+        let artist = if song.artist.is_empty() {
+            "Unknown Artist"
+        } else {
+            &song.artist
+        };
+        menu.append(&MenuItem::with_id(
+            app,
+            "song-title",
+            title,
+            false,
+            None::<&str>,
+        )?)?;
+        menu.append(&MenuItem::with_id(
+            app,
+            "song-artist",
+            artist,
+            false,
+            None::<&str>,
+        )?)?;
+        menu.append(&PredefinedMenuItem::separator(app)?)?;
+    }
+
+    let play_pause = if state.is_playing {
+        "⏸ Pause"
+    } else {
+        "▶ Play"
+    };
+    menu.append(&MenuItem::with_id(
+        app,
+        "play-pause",
+        play_pause,
+        true,
+        None::<&str>,
+    )?)?;
+    menu.append(&MenuItem::with_id(
+        app,
+        "previous",
+        "⏮ Previous",
+        true,
+        None::<&str>,
+    )?)?;
+    menu.append(&MenuItem::with_id(
+        app,
+        "next",
+        "⏭ Next",
+        true,
+        None::<&str>,
+    )?)?;
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
+    menu.append(&MenuItem::with_id(
+        app,
+        "show",
+        "Show Vinyl",
+        true,
+        None::<&str>,
+    )?)?;
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
+    menu.append(&MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?)?;
+
+    Ok(menu)
+}
+
+fn tooltip(state: &PlaybackState) -> String {
+    match &state.song {
+        Some(song) => {
+            let status = if state.is_playing { "▶" } else { "⏸" };
+            format!("{status} {} - {}", song.title, song.artist)
+        }
+        None => DEFAULT_TOOLTIP.to_string(),
+    }
+}
+
+fn handle_menu_event(app: &AppHandle, event: MenuEvent) {
+    let emit = |name: &str| {
+        if let Err(error) = app.emit(name, ()) {
+            eprintln!("[Tray] Failed to send {name}: {error}");
+        }
+    };
+
+    match event.id().as_ref() {
+        "play-pause" => emit("tray:playPause"),
+        "previous" => emit("tray:previous"),
+        "next" => emit("tray:next"),
+        "show" => crate::show_main_window(app),
+        "quit" => app.exit(0),
+        _ => {}
+    }
+}
+
+fn handle_tray_event(tray: &TrayIcon, event: TrayIconEvent) {
+    // Click shows/focuses the main window, the menu is on right click
+    match event {
+        TrayIconEvent::Click {
+            button: MouseButton::Left,
+            button_state: MouseButtonState::Up,
+            ..
+        }
+        | TrayIconEvent::DoubleClick { .. } => crate::show_main_window(tray.app_handle()),
+        _ => {}
+    }
+}
+
+/// Create the tray if it doesn't exist yet
+pub fn create_tray(app: &AppHandle) -> tauri::Result<()> {
+    if app.tray_by_id(TRAY_ID).is_some() {
+        return Ok(());
+    }
+
+    let state = app.state::<TrayState>().0.lock().unwrap().clone();
+    let icon = Image::from_bytes(include_bytes!("../../public/icons/32x32.png"))?;
+
+    TrayIconBuilder::with_id(TRAY_ID)
+        .icon(icon)
+        .tooltip(tooltip(&state))
+        .menu(&build_menu(app, &state)?)
+        .show_menu_on_left_click(false)
+        .on_menu_event(handle_menu_event)
+        .on_tray_icon_event(handle_tray_event)
+        .build(app)?;
+
+    println!("[Tray] System tray created");
+    Ok(())
+}
+
+fn update_tray(app: &AppHandle, tray: &TrayIcon) -> tauri::Result<()> {
+    let state = app.state::<TrayState>().0.lock().unwrap().clone();
+    tray.set_menu(Some(build_menu(app, &state)?))?;
+    tray.set_tooltip(Some(tooltip(&state)))?;
+    Ok(())
+}
+
+fn destroy_tray(app: &AppHandle) {
+    if app.remove_tray_by_id(TRAY_ID).is_some() {
+        println!("[Tray] System tray destroyed");
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct TrayResult {
+    success: bool,
+}
+
+/// Update playback state (called from renderer)
+#[tauri::command]
+pub fn tray_update_playback_state(
+    app: AppHandle,
+    tray_state: State<'_, TrayState>,
+    state: PlaybackState,
+) -> TrayResult {
+    let is_playing = state.is_playing;
+    *tray_state.0.lock().unwrap() = state;
+
+    let result = match app.tray_by_id(TRAY_ID) {
+        Some(tray) => update_tray(&app, &tray),
+        // Create tray if it doesn't exist and we're playing
+        None if is_playing => create_tray(&app),
+        None => Ok(()),
+    };
+
+    if let Err(error) = &result {
+        eprintln!("[Tray] Failed to update tray: {error}");
+    }
+    TrayResult {
+        success: result.is_ok(),
+    }
+}
+
+#[tauri::command]
+pub fn tray_show(app: AppHandle) -> TrayResult {
+    let result = create_tray(&app);
+    if let Err(error) = &result {
+        eprintln!("[Tray] Failed to create tray: {error}");
+    }
+    TrayResult {
+        success: result.is_ok(),
+    }
+}
+
+#[tauri::command]
+pub fn tray_hide(app: AppHandle) -> TrayResult {
+    destroy_tray(&app);
+    TrayResult { success: true }
+}
@@ -0,0 +1,592 @@
+//! File watcher (auto-detect new, removed, moved and retagged songs in library folders).
+
+use crate::scan::{self, MusicFileInfo};
+use crate::store::Store;
+use notify::event::{ModifyKind, RenameMode};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+const DEBOUNCE_DELAY: Duration = Duration::from_millis(500);
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// How long an add or remove waits for its other half before it is reported on its own
+const MOVE_WINDOW: Duration = Duration::from_millis(1500);
+
+/// The active watcher; dropping it closes the channel and stops the dispatcher thread
+#[derive(Default)]
+pub struct WatcherState(Mutex<Option<RecommendedWatcher>>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum ChangeKind {
+    Add,
+    Remove,
+    /// Contents (usually tags) changed in place
+    Change,
+    /// Same file at a new path; `previous_path` holds the old one
+    Move,
+}
+
+impl ChangeKind {
+    fn delay(self) -> Duration {
+        match self {
+            ChangeKind::Add | ChangeKind::Remove => MOVE_WINDOW,
+            ChangeKind::Change | ChangeKind::Move => DEBOUNCE_DELAY,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FileChangeEvent {
+    #[serde(rename = "type")]
+    kind: ChangeKind,
+    file: MusicFileInfo,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    previous_path: Option<String>,
+    root_folder: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Change {
+    kind: ChangeKind,
+    path: PathBuf,
+    previous_path: Option<PathBuf>,
+}
+
+/// What a file looked like when last seen, to recognise it at another path
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FileIdentity {
+    size: u64,
+    /// (device, inode) where the platform exposes it
+    inode: Option<(u64, u64)>,
+    name: Option<OsString>,
+}
+
+impl FileIdentity {
+    fn of(path: &Path) -> Option<Self> {
+        let metadata = fs::metadata(path).ok().filter(fs::Metadata::is_file)?;
+        Some(Self {
+            size: metadata.len(),
+            inode: inode_of(&metadata),
+            name: path.file_name().map(OsString::from),
+        })
+    }
+
+    /// Same inode for renames on one volume; same name for moves across volumes
+    fn matches(&self, other: &Self) -> bool {
+        self.size == other.size
+            && ((self.inode.is_some() && self.inode == other.inode) || self.name == other.name)
+    }
+}
+
+#[cfg(unix)]
+fn inode_of(metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn inode_of(_metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+/// Debounces raw events and pairs a remove with an add of the same file into a move,
+/// so reorganising folders doesn't delete and re-import songs
+#[derive(Default)]
+struct ChangeTracker {
+    known: HashMap<PathBuf, FileIdentity>,
+    pending: HashMap<(ChangeKind, PathBuf), Instant>,
+}
+
+impl ChangeTracker {
+    /// Remember the files already in the library folders
+    fn index(&mut self, folders: &[String]) {
+        for folder in folders {
+            for file in scan::scan_music_folder(Path::new(folder)) {
+                let path = PathBuf::from(file.path);
+                if let Some(identity) = FileIdentity::of(&path) {
+                    self.known.insert(path, identity);
+                }
+            }
+        }
+    }
+
+    fn record(&mut self, event: &notify::Event, now: Instant) {
+        // Writes to a new file push its add back; writes to a known file are a change
+        if let EventKind::Modify(ModifyKind::Data(_) | ModifyKind::Any) = event.kind {
+            for path in &event.paths {
+                if let Some(seen) = self.pending.get_mut(&(ChangeKind::Add, path.clone())) {
+                    *seen = now;
+                } else if self.known.contains_key(path) {
+                    self.pending.insert((ChangeKind::Change, path.clone()), now);
+                }
+            }
+        }
+
+        for (kind, path) in classify(event) {
+            match kind {
+                // A folder moved in arrives as one event for the directory
+                ChangeKind::Add if path.is_dir() => {
+                    for file in scan::scan_music_folder(&path) {
+                        self.pending
+                            .insert((ChangeKind::Add, PathBuf::from(file.path)), now);
+                    }
+                }
+                // Likewise a folder moved out: report every file we knew under it
+                ChangeKind::Remove => {
+                    let under: Vec<PathBuf> = self
+                        .known
+                        .keys()
+                        .filter(|known| known.starts_with(&path))
+                        .cloned()
+                        .collect();
+                    if under.is_empty() && is_watched_file(&path) {
+                        self.pending.insert((kind, path), now);
+                    }
+                    for known in under {
+                        self.pending.insert((kind, known), now);
+                    }
+                }
+                _ if is_watched_file(&path) => {
+                    self.pending.insert((kind, path), now);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Changes that have been quiet long enough, with removes and adds paired up
+    fn take_ready(&mut self, now: Instant) -> Vec<Change> {
+        let mut ready: Vec<(ChangeKind, PathBuf)> = self
+            .pending
+            .iter()
+            .filter(|((kind, _), seen)| now.duration_since(**seen) >= kind.delay())
+            .map(|(key, _)| key.clone())
+            .collect();
+        ready.sort();
+
+        let mut changes = Vec::new();
+        for key in ready {
+            // Already consumed as the other half of a move
+            if self.pending.remove(&key).is_none() {
+                continue;
+            }
+            let (kind, path) = key;
+            match kind {
+                ChangeKind::Remove => {
+                    let destination = self.known.get(&path).and_then(|identity| {
+                        self.pending
+                            .keys()
+                            .filter(|(kind, _)| *kind == ChangeKind::Add)
+                            .map(|(_, candidate)| candidate)
+                            .find(|candidate| {
+                                FileIdentity::of(candidate)
+                                    .is_some_and(|other| identity.matches(&other))
+                            })
+                            .cloned()
+                    });
+                    match destination {
+                        Some(destination) => {
+                            self.pending.remove(&(ChangeKind::Add, destination.clone()));
+                            changes.push(self.moved(path, destination));
+                        }
+                        None => {
+                            self.known.remove(&path);
+                            changes.push(Change {
+                                kind,
+                                path,
+                                previous_path: None,
+                            });
+                        }
+                    }
+                }
+                ChangeKind::Add => {
+                    // Gone again before it settled
+                    let Some(identity) = FileIdentity::of(&path) else {
+                        continue;
+                    };
+                    let source = self
+                        .pending
+                        .keys()
+                        .filter(|(kind, _)| *kind == ChangeKind::Remove)
+                        .map(|(_, candidate)| candidate)
+                        .find(|candidate| {
+                            self.known
+                                .get(*candidate)
+                                .is_some_and(|known| known.matches(&identity))
+                        })
+                        .cloned();
+                    if let Some(source) = source {
+                        self.pending.remove(&(ChangeKind::Remove, source.clone()));
+                        changes.push(self.moved(source, path));
+                        continue;
+                    }
+                    // Saved over an existing file (editors often write a temp file and rename)
+                    let kind = if self.known.contains_key(&path) {
+                        ChangeKind::Change
+                    } else {
+                        ChangeKind::Add
+                    };
+                    self.known.insert(path.clone(), identity);
+                    changes.push(Change {
+                        kind,
+                        path,
+                        previous_path: None,
+                    });
+                }
+                ChangeKind::Change | ChangeKind::Move => {
+                    let Some(identity) = FileIdentity::of(&path) else {
+                        continue;
+                    };
+                    self.known.insert(path.clone(), identity);
+                    changes.push(Change {
+                        kind: ChangeKind::Change,
+                        path,
+                        previous_path: None,
+                    });
+                }
+            }
+        }
+        changes
+    }
+
+    fn moved(&mut self, from: PathBuf, to: PathBuf) -> Change {
+        self.known.remove(&from);
+        if let Some(identity) = FileIdentity::of(&to) {
+            self.known.insert(to.clone(), identity);
+        }
+        Change {
+            kind: ChangeKind::Move,
+            path: to,
+            previous_path: Some(from),
+        }
+    }
+}
+
+/// Map a notify event onto add/remove; renames become a remove plus an add that
+/// `ChangeTracker` pairs back up
+fn classify(event: &notify::Event) -> Vec<(ChangeKind, PathBuf)> {
+    let all = |kind: ChangeKind| {
+        event
+            .paths
+            .iter()
+            .map(|path| (kind, path.clone()))
+            .collect()
+    };
+
+    match event.kind {
+        EventKind::Create(_) => all(ChangeKind::Add),
+        EventKind::Remove(_) => all(ChangeKind::Remove),
+        EventKind::Modify(ModifyKind::Name(RenameMode::From)) => all(ChangeKind::Remove),
+        EventKind::Modify(ModifyKind::Name(RenameMode::To)) => all(ChangeKind::Add),
+        EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
+            let mut changes = Vec::new();
+            if let [from, to, ..] = event.paths.as_slice() {
+                changes.push((ChangeKind::Remove, from.clone()));
+                changes.push((ChangeKind::Add, to.clone()));
+            }
+            changes
+        }
+        EventKind::Modify(ModifyKind::Name(_)) => event
+            .paths
+            .iter()
+            .map(|path| {
+                let kind = if path.exists() {
+                    ChangeKind::Add
+                } else {
+                    ChangeKind::Remove
+                };
+                (kind, path.clone())
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn is_watched_file(path: &Path) -> bool {
+    scan::is_audio_file(path) && !scan::is_hidden(path)
+}
+
+/// Send a settled change to the renderer
+fn emit_change(app: &AppHandle, change: Change) {
+    let folders = app.state::<Store>().music_folders();
+    let root_folder = folders
+        .into_iter()
+        .find(|folder| change.path.starts_with(folder));
+
+    let event = FileChangeEvent {
+        kind: change.kind,
+        file: MusicFileInfo::from_path(&change.path, root_folder.as_deref().map(Path::new)),
+        previous_path: change
+            .previous_path
+            .as_ref()
+            .map(|path| path.to_string_lossy().into_owned()),
+        root_folder,
+    };
+
+    if let Err(error) = app.emit("library:fileChange", &event) {
+        eprintln!("[Watcher] Failed to send event: {error}");
+    }
+    match &change.previous_path {
+        Some(previous) => println!(
+            "[Watcher] {:?}: {} -> {}",
+            change.kind,
+            previous.display(),
+            change.path.display()
+        ),
+        None => println!("[Watcher] {:?}: {}", change.kind, change.path.display()),
+    }
+}
+
+fn dispatch(
+    app: AppHandle,
+    folders: Vec<String>,
+    events: mpsc::Receiver<notify::Result<notify::Event>>,
+) {
+    let mut tracker = ChangeTracker::default();
+    tracker.index(&folders);
+
+    loop {
+        match events.recv_timeout(POLL_INTERVAL) {
+            Ok(Ok(event)) => tracker.record(&event, Instant::now()),
+            Ok(Err(error)) => eprintln!("[Watcher] Error: {error}"),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+
+        for change in tracker.take_ready(Instant::now()) {
+            emit_change(&app, change);
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchResult {
+    success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    watching: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Start watching all music folders
+#[tauri::command]
+pub fn library_start_watching(
+    app: AppHandle,
+    store: State<'_, Store>,
+    state: State<'_, WatcherState>,
+) -> WatchResult {
+    let folders = store.music_folders();
+    let mut active = state.0.lock().unwrap();
+
+    // Stop existing watcher if any
+    *active = None;
+
+    if folders.is_empty() {
+        return WatchResult {
+            success: true,
+            watching: Some(0),
+            error: None,
+        };
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = match notify::recommended_watcher(sender) {
+        Ok(watcher) => watcher,
+        Err(error) => {
+            eprintln!("[Watcher] Failed to initialize: {error}");
+            return WatchResult {
+                success: false,
+                watching: None,
+                error: Some("File watching not available".into()),
+            };
+        }
+    };
+
+    println!("[Watcher] Starting to watch folders: {folders:?}");
+    for folder in &folders {
+        if let Err(error) = watcher.watch(Path::new(folder), RecursiveMode::Recursive) {
+            eprintln!("[Watcher] Cannot watch {folder}: {error}");
+        }
+    }
+
+    let watched = folders.clone();
+    thread::spawn(move || dispatch(app, watched, receiver));
+    *active = Some(watcher);
+
+    WatchResult {
+        success: true,
+        watching: Some(folders.len()),
+        error: None,
+    }
+}
+
+/// Stop watching
+#[tauri::command]
+pub fn library_stop_watching(state: State<'_, WatcherState>) -> WatchResult {
+    if state.0.lock().unwrap().take().is_some() {
+        println!("[Watcher] Stopped watching");
+    }
+
+    WatchResult {
+        success: true,
+        watching: None,
+        error: None,
+    }
+}
+
+#[derive(Serialize)]
+pub struct WatcherStatus {
+    watching: bool,
+    folders: Vec<String>,
+}
+
+/// Get watcher status
+#[tauri::command]
+pub fn library_get_watcher_status(
+    store: State<'_, Store>,
+    state: State<'_, WatcherState>,
+) -> WatcherStatus {
+    WatcherStatus {
+        watching: state.0.lock().unwrap().is_some(),
+        folders: store.music_folders(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{CreateKind, RemoveKind};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("vinyl-watcher-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn event(kind: EventKind, paths: &[&Path]) -> notify::Event {
+        paths.iter().fold(notify::Event::new(kind), |event, path| {
+            event.add_path(path.to_path_buf())
+        })
+    }
+
+    #[test]
+    fn pairs_remove_and_add_of_the_same_file_into_a_move() {
+        let dir = temp_dir("move");
+        let from = dir.join("A/01 Song.mp3");
+        let to = dir.join("B/01 Song.mp3");
+        fs::create_dir_all(from.parent().unwrap()).unwrap();
+        fs::create_dir_all(to.parent().unwrap()).unwrap();
+        fs::write(&from, b"audio").unwrap();
+
+        let mut tracker = ChangeTracker::default();
+        tracker.index(&[dir.to_string_lossy().into_owned()]);
+
+        fs::rename(&from, &to).unwrap();
+        let start = Instant::now();
+        tracker.record(&event(EventKind::Remove(RemoveKind::File), &[&from]), start);
+        tracker.record(&event(EventKind::Create(CreateKind::File), &[&to]), start);
+
+        assert!(tracker.take_ready(start).is_empty());
+        assert_eq!(
+            tracker.take_ready(start + MOVE_WINDOW),
+            vec![Change {
+                kind: ChangeKind::Move,
+                path: to.clone(),
+                previous_path: Some(from),
+            }]
+        );
+        assert!(tracker.known.contains_key(&to));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn reports_unpaired_files_and_in_place_edits() {
+        let dir = temp_dir("unpaired");
+        let kept = dir.join("kept.flac");
+        let removed = dir.join("removed.flac");
+        let added = dir.join("added.ogg");
+        fs::write(&kept, b"one").unwrap();
+        fs::write(&removed, b"two").unwrap();
+
+        let mut tracker = ChangeTracker::default();
+        tracker.index(&[dir.to_string_lossy().into_owned()]);
+
+        fs::remove_file(&removed).unwrap();
+        fs::write(&added, b"three").unwrap();
+        fs::write(&kept, b"retagged").unwrap();
+        let start = Instant::now();
+        tracker.record(
+            &event(EventKind::Remove(RemoveKind::File), &[&removed]),
+            start,
+        );
+        tracker.record(
+            &event(EventKind::Create(CreateKind::File), &[&added]),
+            start,
+        );
+        tracker.record(
+            &event(
+                EventKind::Modify(ModifyKind::Data(notify::event::DataChange::Content)),
+                &[&kept],
+            ),
+            start,
+        );
+
+        let mut changes = tracker.take_ready(start + MOVE_WINDOW);
+        changes.sort_by_key(|change| change.kind);
+        let kinds: Vec<_> = changes
+            .iter()
+            .map(|change| (change.kind, &change.path))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (ChangeKind::Add, &added),
+                (ChangeKind::Remove, &removed),
+                (ChangeKind::Change, &kept),
+            ]
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn expands_a_removed_folder_to_the_files_under_it() {
+        let mut tracker = ChangeTracker::default();
+        let album = PathBuf::from("/music/Album");
+        let identity = FileIdentity {
+            size: 1,
+            inode: None,
+            name: None,
+        };
+        tracker.known.insert(album.join("1.mp3"), identity.clone());
+        tracker.known.insert(album.join("2.mp3"), identity.clone());
+        tracker
+            .known
+            .insert(PathBuf::from("/music/Other/3.mp3"), identity);
+
+        tracker.record(
+            &event(EventKind::Remove(RemoveKind::Folder), &[&album]),
+            Instant::now(),
+        );
+
+        let mut removed: Vec<_> = tracker
+            .pending
+            .keys()
+            .map(|(_, path)| path.clone())
+            .collect();
+        removed.sort();
+        assert_eq!(removed, vec![album.join("1.mp3"), album.join("2.mp3")]);
+    }
+}
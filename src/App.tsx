@@ -20,17 +20,50 @@ import { QuickPlayOverlay, DropZoneOverlay } from "./components/QuickPlayOverlay
 import { MusicInfoDialog } from "./components/MusicInfoDialog";
 import { CommandMenu } from "./components/CommandMenu";
 import { KeyboardShortcutsDialog } from "./components/KeyboardShortcutsDialog";
+import { LibrarySearchBar } from "./components/LibrarySearchBar";
+import { LibrarySortMenu } from "./components/LibrarySortMenu";
+import { ArtistsView } from "./components/ArtistsView";
+import { ArtistDetailView } from "./components/ArtistDetailView";
+import { AlbumsView } from "./components/AlbumsView";
+import { AlbumDetailView } from "./components/AlbumDetailView";
+import { StatsView } from "./components/StatsView";
+import { TagEditorDialog } from "./components/TagEditorDialog";
 import { useSongs, checkSongsAvailability } from "./hooks/useSongs";
 import { usePlaylists } from "./hooks/usePlaylists";
 import { useAudioPlayer } from "./hooks/useAudioPlayer";
 import { useEqualizer } from "./hooks/useEqualizer";
+import { usePlaybackProfiles } from "./hooks/usePlaybackProfiles";
+import { useAudioOutputDevice } from "./hooks/useAudioOutputDevice";
+import { resolvePlaybackProfile } from "./lib/playbackProfiles";
+import { clampSemitones, formatSemitones } from "./lib/pitchShift";
 import { useSettings } from "./hooks/useSettings";
 import { usePWA } from "./hooks/usePWA";
 import { useSleepTimer } from "./hooks/useSleepTimer";
 import { useAudioVisualizer } from "./hooks/useAudioVisualizer";
 import { useLibrary } from "./hooks/useLibrary";
 import { useKeyboardShortcuts } from "./hooks/useKeyboardShortcuts";
+import { useLibrarySearch } from "./hooks/useLibrarySearch";
+import { useFolderAccess } from "./hooks/useFolderAccess";
+import { useSmartPlaylists } from "./hooks/useSmartPlaylists";
+import { usePlaylistFiles } from "./hooks/usePlaylistFiles";
+import { usePlayHistory, type PlayContext } from "./hooks/usePlayHistory";
+import { useScheduledBackup } from "./hooks/useLibraryBackup";
 import { clearAllData } from "./lib/db";
+import { sortSongs } from "./lib/songSort";
+import { describeSmartRules } from "./lib/smartPlaylists";
+import { applyTagEdit } from "./lib/tagEdits";
+import type {
+  PlaylistFileFormat,
+  PlaylistPathMode,
+} from "./lib/playlistFiles";
+import {
+  groupAlbums,
+  groupArtists,
+  getAlbumArtist,
+  getAlbumId,
+  type Album,
+  type Artist,
+} from "./lib/albums";
 import {
   isDesktop as checkIsDesktop,
   clearStoredData,
@@ -42,12 +75,13 @@ import {
   onTrayPrevious,
   onFileOpen,
   getPendingOpenFiles,
+  openFolderPicker,
 } from "./lib/platform";
 
 import { FirstLaunchWizard } from "./components/FirstLaunchWizard";
 import { ScrollArea } from "./components/ui";
 import { toast } from "sonner";
-import type { Playlist, Song } from "./types";
+import type { Playlist, Song, TagEdit } from "./types";
 import {
   ArrowLeft,
   Library,
@@ -136,6 +170,7 @@ function PlaylistDetailPage({
           </h1>
           <p className="text-vinyl-text-muted">
             {playlistSongs.length} songs
+            {playlist.rules && ` • ${describeSmartRules(playlist.rules)}`}
             {isCurrentPlaylist && isPlaying && (
               <span className="ml-2 text-vinyl-accent">• Now Playing</span>
             )}
@@ -187,6 +222,9 @@ function PlaylistDetailPage({
   );
 }
 
+// Stable empty list for the tag editor while it's closed
+const NO_SONGS: Song[] = [];
+
 function App() {
   const navigate = useNavigate();
   const [hasInitialized, setHasInitialized] = useState(false);
@@ -201,6 +239,11 @@ function App() {
   const [droppedFiles, setDroppedFiles] = useState<File[]>([]);
   const [showQuickPlayOverlay, setShowQuickPlayOverlay] = useState(false);
   const [showMusicInfoDialog, setShowMusicInfoDialog] = useState(false);
+  // Songs open in the tag editor, and the album page it was opened from
+  const [tagEditor, setTagEditor] = useState<{
+    songs: Song[];
+    albumId?: string;
+  } | null>(null);
   const [showCommandMenu, setShowCommandMenu] = useState(false);
   const [showKeyboardShortcuts, setShowKeyboardShortcuts] = useState(false);
   const [autoExpandPlayer, setAutoExpandPlayer] = useState(false);
@@ -249,14 +292,45 @@ function App() {
     pickAndImportFolder,
     autoLoadStoredFolder,
     importFromMusicFileInfos,
+    moveSongFile,
+    refreshSongFile,
+    editSongTags,
+    analyzeLoudness,
+    loudnessProgress,
+    libraryHealth,
+    checkLibraryHealth,
+    relinkMissingSongs,
+    recordPlay,
+    // Web-specific
+    linkSongsToFolders,
   } = useSongs();
 
+  // Web: folders remembered through the File System Access API
+  const folderAccess = useFolderAccess(songs, linkSongsToFolders);
+
+  // Library search, backed by the persistent search index
+  const [librarySearch, setLibrarySearch] = useState("");
+  const { results: searchResults, isSearching } = useLibrarySearch(
+    songs,
+    librarySearch,
+  );
+  const librarySongs = useMemo(
+    () =>
+      sortSongs(
+        searchResults ?? songs,
+        settings.librarySort,
+        settings.librarySortDirection,
+      ),
+    [searchResults, songs, settings.librarySort, settings.librarySortDirection],
+  );
+
   // Music library management (desktop only)
   const library = useLibrary();
   const {
     playlists,
     favoriteSongIds,
     createPlaylist,
+    createSmartPlaylist,
     deletePlaylist,
     updatePlaylist,
     addSongToPlaylist,
@@ -265,6 +339,34 @@ function App() {
     toggleFavorite,
   } = usePlaylists();
 
+  // Smart playlist contents, kept in step with the library
+  const smartPlaylistSongs = useSmartPlaylists(playlists, songs);
+
+  // M3U/M3U8, PLS and XSPF import and export
+  const { importPlaylist, exportPlaylist } = usePlaylistFiles(
+    songs,
+    createPlaylist,
+  );
+
+  // EQ, speed and gain attached to songs, albums, artists, playlists and
+  // output devices
+  const {
+    profiles: playbackProfiles,
+    saveProfile: savePlaybackProfile,
+    deleteProfile: deletePlaybackProfile,
+    deleteProfilesFor: deletePlaybackProfilesFor,
+  } = usePlaybackProfiles();
+  const outputDevice = useAudioOutputDevice();
+  const getPlaybackProfile = useCallback(
+    (song: Song, playlistId: string | null) =>
+      resolvePlaybackProfile(playbackProfiles, {
+        song,
+        playlistId,
+        outputDevice,
+      }),
+    [playbackProfiles, outputDevice],
+  );
+
   const {
     currentSong,
     isPlaying,
@@ -275,12 +377,18 @@ function App() {
     repeat,
     shuffle,
     speed,
+    pitch,
+    isPitchRemembered,
+    canTranspose,
+    playbackProfile,
     currentPlaylistId,
     queueSongs,
     audioElement,
     playSong,
     playFromQueue,
     playPlaylist,
+    playSongs,
+    addToQueue,
     playFile,
     playFiles,
     playFilePath,
@@ -293,21 +401,51 @@ function App() {
     toggleRepeat,
     toggleShuffle,
     setSpeed,
+    setPitch,
+    toggleRememberPitch,
+    forgetSongPitch,
     removeSongFromQueue,
     reorderQueue,
-  } = useAudioPlayer(songs, settings);
+  } = useAudioPlayer(songs, settings, getPlaybackProfile);
 
   // Initialize equalizer with audio element
   const {
-    bands: eqBands,
+    filters: eqFilters,
+    preamp: eqPreamp,
     enabled: eqEnabled,
     currentPreset: eqPreset,
+    userPresets: eqUserPresets,
     isConnected: eqConnected,
-    setBandGain,
+    setFilter: setEqFilter,
+    addFilter: addEqFilter,
+    removeFilter: removeEqFilter,
+    setPreamp: setEqPreamp,
     applyPreset,
+    savePreset: saveEqPreset,
+    deletePreset: deleteEqPreset,
+    importProfile: importEqProfile,
     reset: resetEqualizer,
     toggleEnabled: toggleEqualizer,
-  } = useEqualizer(audioElement);
+  } = useEqualizer(audioElement, playbackProfile.eqPreset);
+
+  // Measure loudness of untagged songs while normalization is on, and the
+  // silence around songs while crossfading (desktop)
+  // Re-runs as imports add songs; each song is only attempted once
+  useEffect(() => {
+    if (
+      !isDesktop ||
+      (settings.replayGainMode === "off" && settings.crossfadeDuration <= 0)
+    ) {
+      return;
+    }
+    analyzeLoudness();
+  }, [
+    isDesktop,
+    settings.replayGainMode,
+    settings.crossfadeDuration,
+    songs.length,
+    analyzeLoudness,
+  ]);
 
   // Sleep timer - stops playback with fade-out effect when timer ends
   const sleepTimer = useSleepTimer({
@@ -547,7 +685,7 @@ function App() {
       const unavailable = checkSongsAvailability(songs);
       setUnavailableSongIds(unavailable);
     }
-  }, [songs, isLoading, connectedCount]);
+  }, [songs, isLoading, connectedCount, libraryHealth, folderAccess.folders]);
 
   const handleImport = async (
     files: FileList,
@@ -633,7 +771,8 @@ function App() {
 
         // Check if a playlist with this name already exists
         const existingPlaylist = playlists.find(
-          (p) => p.name.toLowerCase() === playlistName.toLowerCase(),
+          (p) =>
+            !p.rules && p.name.toLowerCase() === playlistName.toLowerCase(),
         );
 
         if (existingPlaylist) {
@@ -693,7 +832,8 @@ function App() {
 
       // Check if a playlist with this name already exists
       const existingPlaylist = playlists.find(
-        (p) => p.name.toLowerCase() === playlistName.toLowerCase(),
+        (p) =>
+          !p.rules && p.name.toLowerCase() === playlistName.toLowerCase(),
       );
 
       if (existingPlaylist) {
@@ -717,6 +857,10 @@ function App() {
     // Remove from all playlists
     await removeSongFromAllPlaylists(songId);
 
+    // Forget its remembered pitch and profile
+    forgetSongPitch(songId);
+    deletePlaybackProfilesFor("song", songId);
+
     // Delete from library
     await deleteSong(songId);
   };
@@ -755,6 +899,22 @@ function App() {
       }
     });
 
+    // Moves and renames keep the song (and its playlists) under the new path
+    library.setOnFileMoved(async (previousPath, file) => {
+      try {
+        if (!(await moveSongFile(previousPath, file))) {
+          await importFromMusicFileInfos([file]);
+        }
+      } catch (error) {
+        console.error("[App] Failed to handle moved file:", error);
+      }
+    });
+
+    // Files rewritten in place (e.g. retagged) refresh the existing song
+    library.setOnFileChanged((file) => {
+      refreshSongFile(file.path);
+    });
+
     // Handle removed files detected by watcher
     library.setOnFileRemoved((filePath) => {
       console.log("[App] File removed:", filePath);
@@ -768,12 +928,18 @@ function App() {
     return () => {
       library.setOnFileAdded(null);
       library.setOnFileRemoved(null);
+      library.setOnFileMoved(null);
+      library.setOnFileChanged(null);
     };
   }, [
     library.isDesktop,
     library.setOnFileAdded,
     library.setOnFileRemoved,
+    library.setOnFileMoved,
+    library.setOnFileChanged,
     importFromMusicFileInfos,
+    moveSongFile,
+    refreshSongFile,
     songs,
   ]);
 
@@ -841,6 +1007,46 @@ function App() {
     });
   }, [toggleEqualizer, eqEnabled]);
 
+  const handleSaveEqPreset = useCallback(
+    (name: string) => {
+      const saved = saveEqPreset(name);
+      if (saved) {
+        toast.success(`Saved preset "${name.trim()}"`, { duration: 2000 });
+      } else {
+        toast.error("Pick a name that isn't a built-in preset", {
+          duration: 3000,
+        });
+      }
+      return saved;
+    },
+    [saveEqPreset],
+  );
+
+  // AutoEq names its files "<headphone> ParametricEQ.txt"
+  const handleImportEqProfile = useCallback(
+    async (file: File) => {
+      const name = file.name
+        .replace(/\.txt$/i, "")
+        .replace(/\s*ParametricEQ$/i, "");
+      try {
+        const skipped = importEqProfile(await file.text(), name);
+        toast.success(
+          `Imported "${name || "Imported"}"` +
+            (skipped > 0
+              ? `, ${skipped} unsupported filter${skipped === 1 ? "" : "s"} left out`
+              : ""),
+          { duration: 3000 },
+        );
+      } catch (error) {
+        console.error("Failed to import equalizer profile:", error);
+        toast.error("Couldn't read that equalizer profile", {
+          duration: 3000,
+        });
+      }
+    },
+    [importEqProfile],
+  );
+
   // Favorite toggle with toast
   const handleToggleFavoriteWithToast = useCallback(
     (songId: string) => {
@@ -909,17 +1115,105 @@ function App() {
     [handleDeleteSong, songs]
   );
 
+  // Relink songs whose files went missing, searching a folder the user picks
+  const handleRelinkMissing = useCallback(async () => {
+    const folderPath = await openFolderPicker();
+    if (!folderPath) return;
+
+    const { relinked, unmatched } = await relinkMissingSongs(folderPath);
+    if (relinked === 0) {
+      toast.error("No matching files found in that folder", { duration: 3000 });
+    } else {
+      toast.success(
+        `Relinked ${relinked} song${relinked === 1 ? "" : "s"}` +
+          (unmatched > 0 ? `, ${unmatched} still missing` : ""),
+        { duration: 3000 },
+      );
+    }
+  }, [relinkMissingSongs]);
 
 
-  // Speed change with toast
+  // Save tag edits, following the album page if the edit renamed the album
+  const handleSaveTags = useCallback(
+    async (edit: TagEdit) => {
+      if (!tagEditor) return;
+      const { songs: editedSongs, albumId } = tagEditor;
+      const { updated, failed } = await editSongTags(
+        editedSongs.map((song) => song.id),
+        edit,
+      );
+
+      if (failed.length === 1) {
+        toast.error(
+          `Couldn't update "${failed[0].song.title}": ${failed[0].error}`,
+          { duration: 3000 },
+        );
+      } else if (failed.length > 1) {
+        toast.error(`Couldn't update ${failed.length} songs`, {
+          duration: 3000,
+        });
+      }
+      if (updated > 0) {
+        toast.success(
+          updated === 1 ? "Tags updated" : `Tags updated for ${updated} songs`,
+          { duration: 2000 },
+        );
+      }
+
+      if (albumId && updated > 0) {
+        const newAlbumId = getAlbumId(applyTagEdit(editedSongs[0], edit));
+        if (newAlbumId !== albumId) {
+          navigate(`/albums/${newAlbumId}`, { replace: true });
+        }
+      }
+    },
+    [tagEditor, editSongTags, navigate],
+  );
+
+  // Speed change with toast (one toast while dragging the tempo slider)
   const handleSpeedChange = useCallback(
     (newSpeed: number) => {
       setSpeed(newSpeed);
-      toast.success(`Playback speed: ${newSpeed}x`, { duration: 2000 });
+      toast.success(`Playback speed: ${newSpeed}x`, {
+        id: "playback-speed",
+        duration: 2000,
+      });
     },
     [setSpeed]
   );
 
+  // Transpose with toast
+  const handlePitchChange = useCallback(
+    (semitones: number) => {
+      setPitch(semitones);
+      toast.success(`Pitch: ${formatSemitones(clampSemitones(semitones))}`, {
+        id: "playback-pitch",
+        duration: 2000,
+      });
+    },
+    [setPitch]
+  );
+
+  const handleTogglePreservePitch = useCallback(() => {
+    updateSetting("preservePitch", !settings.preservePitch);
+    toast.success(
+      settings.preservePitch
+        ? "Speed changes the pitch"
+        : "Speed keeps the pitch",
+      { duration: 2000 }
+    );
+  }, [updateSetting, settings.preservePitch]);
+
+  const handleToggleRememberPitch = useCallback(() => {
+    toggleRememberPitch();
+    toast.success(
+      isPitchRemembered
+        ? "Speed and pitch no longer remembered for this song"
+        : "Speed and pitch remembered for this song",
+      { duration: 2000 }
+    );
+  }, [toggleRememberPitch, isPitchRemembered]);
+
   // ========== End of action handlers with toasts ==========
 
   // Global drag and drop handlers for quick play
@@ -1005,11 +1299,52 @@ function App() {
   };
 
   const getPlaylistSongs = (playlist: Playlist): Song[] => {
+    if (playlist.rules) {
+      return smartPlaylistSongs.get(playlist.id) ?? [];
+    }
     return playlist.songIds
       .map((id) => songs.find((s) => s.id === id))
       .filter((s): s is Song => s !== undefined);
   };
 
+  const handleImportPlaylist = async (file?: File) => {
+    try {
+      const result = await importPlaylist(file);
+      if (!result) return;
+      toast.success(
+        `Imported "${result.name}" with ${result.matched} song${result.matched === 1 ? "" : "s"}` +
+          (result.unmatched > 0
+            ? `, ${result.unmatched} not found in library`
+            : ""),
+        { duration: 3000 },
+      );
+    } catch (error) {
+      console.error("Failed to import playlist:", error);
+      toast.error("Couldn't read that playlist file", { duration: 3000 });
+    }
+  };
+
+  const handleExportPlaylist = async (
+    playlist: Playlist,
+    format: PlaylistFileFormat,
+    pathMode: PlaylistPathMode,
+  ) => {
+    try {
+      const exported = await exportPlaylist(
+        playlist.name,
+        getPlaylistSongs(playlist),
+        format,
+        pathMode,
+      );
+      if (exported) {
+        toast.success(`Exported "${playlist.name}"`, { duration: 2000 });
+      }
+    } catch (error) {
+      console.error("Failed to export playlist:", error);
+      toast.error("Couldn't save the playlist file", { duration: 3000 });
+    }
+  };
+
   const handlePlayPlaylist = (playlist: Playlist) => {
     const playlistSongs = getPlaylistSongs(playlist);
     if (playlistSongs.length > 0) {
@@ -1037,6 +1372,78 @@ function App() {
     }
   };
 
+  // Albums and artists, grouped by album artist
+  const albums = useMemo(() => groupAlbums(songs), [songs]);
+  const artists = useMemo(() => groupArtists(albums), [albums]);
+  const currentAlbumId = currentSong ? getAlbumId(currentSong) : null;
+  const currentArtist = currentSong ? getAlbumArtist(currentSong) : null;
+
+  // Album or artist the queue was last started from, for play history
+  const playContextRef = useRef<{
+    source: "album" | "artist";
+    sourceId: string;
+    songIds: Set<string>;
+  } | null>(null);
+
+  const handlePlayAlbum = (album: Album, startSong?: Song) => {
+    playContextRef.current = {
+      source: "album",
+      sourceId: album.id,
+      songIds: new Set(album.songs.map((s) => s.id)),
+    };
+    playSongs(album.songs, { startSongId: startSong?.id });
+  };
+
+  const handleQueueAlbum = (album: Album, playNext: boolean) => {
+    addToQueue(album.songs, playNext);
+    toast.success(
+      playNext
+        ? `Playing "${album.title}" next`
+        : `Added "${album.title}" to queue`,
+      { duration: 1500 },
+    );
+  };
+
+  const handlePlayArtist = (artist: Artist, shuffleAll: boolean) => {
+    const artistSongs = artist.albums.flatMap((album) => album.songs);
+    if (shuffleAll) {
+      handleShufflePlay(artistSongs, null);
+    } else {
+      playContextRef.current = {
+        source: "artist",
+        sourceId: artist.name,
+        songIds: new Set(artistSongs.map((s) => s.id)),
+      };
+      playSongs(artistSongs);
+    }
+  };
+
+  const getPlayContext = useCallback((): PlayContext => {
+    if (shuffle) {
+      return { source: "shuffle", sourceId: currentPlaylistId ?? undefined };
+    }
+    if (currentPlaylistId) {
+      return { source: "playlist", sourceId: currentPlaylistId };
+    }
+    const context = playContextRef.current;
+    if (context && currentSong && context.songIds.has(currentSong.id)) {
+      return { source: context.source, sourceId: context.sourceId };
+    }
+    return { source: "library" };
+  }, [shuffle, currentPlaylistId, currentSong]);
+
+  // Log each listen to the play history
+  usePlayHistory({
+    currentSong,
+    isPlaying,
+    currentTime,
+    getPlayContext,
+    recordPlay,
+  });
+
+  // Scheduled library backups (desktop only)
+  useScheduledBackup(settings, updateSetting);
+
   // Memoized page elements to prevent re-mounting on every render
   const libraryPage = useMemo(
     () => (
@@ -1044,13 +1451,17 @@ function App() {
         <div className="flex items-center justify-between mb-6 flex-shrink-0">
           <div>
             <h1 className="text-2xl font-bold text-vinyl-text">Library</h1>
-            <p className="text-vinyl-text-muted">{songs.length} songs</p>
+            <p className="text-vinyl-text-muted">
+              {searchResults
+                ? `${searchResults.length} of ${songs.length} songs`
+                : `${songs.length} songs`}
+            </p>
           </div>
           <div className="flex items-center gap-2">
-            {songs.length > 0 && (
+            {librarySongs.length > 0 && (
               <>
                 <button
-                  onClick={() => handleShufflePlay(songs, null)}
+                  onClick={() => handleShufflePlay(librarySongs, null)}
                   className="p-3 bg-vinyl-surface border border-vinyl-border rounded-full text-vinyl-text-muted hover:text-vinyl-accent hover:border-vinyl-accent transition-colors"
                   data-tooltip-id="global-tooltip"
                   data-tooltip-content="Shuffle Play"
@@ -1058,7 +1469,9 @@ function App() {
                   <Shuffle className="w-5 h-5" />
                 </button>
                 <button
-                  onClick={() => songs.length > 0 && playSong(songs[0], null)}
+                  onClick={() =>
+                    librarySongs.length > 0 && playSong(librarySongs[0], null)
+                  }
                   className="p-3 bg-vinyl-accent rounded-full text-vinyl-bg hover:bg-vinyl-accent-light transition-colors"
                   data-tooltip-id="global-tooltip"
                   data-tooltip-content="Play All"
@@ -1072,6 +1485,11 @@ function App() {
               onConnectFolder={connectFolder}
               onPickAndImportFolder={pickAndImportFolder}
               onCreatePlaylistsFromFolders={handleCreatePlaylistsFromFolders}
+              onPickFolderHandle={
+                folderAccess.isSupported ? folderAccess.addFolder : undefined
+              }
+              pendingFolderCount={folderAccess.pendingCount}
+              onRestoreFolderAccess={folderAccess.restoreAccess}
               isImporting={!!importProgress}
               importProgress={importProgress}
               connectedCount={connectedCount}
@@ -1080,6 +1498,23 @@ function App() {
             />
           </div>
         </div>
+        {songs.length > 0 && (
+          <div className="mb-4 flex-shrink-0 flex items-center gap-2">
+            <LibrarySearchBar
+              value={librarySearch}
+              onChange={setLibrarySearch}
+              isSearching={isSearching}
+            />
+            <LibrarySortMenu
+              sortKey={settings.librarySort}
+              direction={settings.librarySortDirection}
+              onSortKeyChange={(key) => updateSetting("librarySort", key)}
+              onDirectionChange={(direction) =>
+                updateSetting("librarySortDirection", direction)
+              }
+            />
+          </div>
+        )}
         {isLoading ? (
           <div className="flex items-center justify-center py-16">
             <div className="w-8 h-8 border-2 border-vinyl-accent border-t-transparent rounded-full animate-spin" />
@@ -1097,10 +1532,16 @@ function App() {
               You can also <span className="text-vinyl-accent">drag and drop</span> audio files anywhere to play them instantly
             </p>
           </div>
+        ) : librarySongs.length === 0 ? (
+          <div className="text-center py-16">
+            <p className="text-vinyl-text-muted">
+              No songs match "{librarySearch}"
+            </p>
+          </div>
         ) : (
           <div className="flex-1 min-h-0 overflow-hidden">
             <VirtualizedSongList
-              songs={songs}
+              songs={librarySongs}
               currentSongId={currentSong?.id || null}
               isPlaying={isPlaying}
               onPlay={(song) => playSong(song, null)}
@@ -1112,8 +1553,10 @@ function App() {
               onCreatePlaylist={handleCreatePlaylistWithToast}
               unavailableSongIds={unavailableSongIds}
               onDeleteSong={handleDeleteSongWithToast}
+              onRelinkMissing={isDesktop ? handleRelinkMissing : undefined}
               favoriteSongIds={favoriteSongIds}
               onToggleFavorite={handleToggleFavoriteWithToast}
+              onEditTags={(song) => setTagEditor({ songs: [song] })}
               skipDeleteConfirmation={settings.skipDeleteConfirmation}
               onSkipDeleteConfirmationChange={
                 handleSkipDeleteConfirmationChange
@@ -1125,6 +1568,13 @@ function App() {
     ),
     [
       songs,
+      librarySongs,
+      searchResults,
+      librarySearch,
+      isSearching,
+      settings.librarySort,
+      settings.librarySortDirection,
+      updateSetting,
       currentSong?.id,
       isPlaying,
       isLoading,
@@ -1137,6 +1587,7 @@ function App() {
       togglePlayPause,
       stop,
       handleDeleteSongWithToast,
+      handleRelinkMissing,
       handleAddSongToPlaylist,
       handleCreatePlaylistWithToast,
       favoriteSongIds,
@@ -1158,6 +1609,13 @@ function App() {
             onCreatePlaylist={async (name) => {
               await handleCreatePlaylistWithToast(name);
             }}
+            onCreateSmartPlaylist={async (name, rules) => {
+              await createSmartPlaylist(name, rules);
+              toast.success(`Created smart playlist "${name}"`, {
+                duration: 2000,
+              });
+            }}
+            smartPlaylistSongs={smartPlaylistSongs}
             onDeletePlaylist={deletePlaylist}
             onUpdatePlaylist={updatePlaylist}
             onSelectPlaylist={handleSelectPlaylist}
@@ -1167,6 +1625,9 @@ function App() {
               handleShufflePlay(playlistSongs, playlist.id);
             }}
             onStopPlaylist={stop}
+            onImportPlaylist={handleImportPlaylist}
+            onExportPlaylist={handleExportPlaylist}
+            isDesktop={isDesktop}
           />
         </ScrollArea>
       </div>
@@ -1177,6 +1638,8 @@ function App() {
       currentPlaylistId,
       isPlaying,
       createPlaylist,
+      createSmartPlaylist,
+      smartPlaylistSongs,
       deletePlaylist,
       updatePlaylist,
       handleSelectPlaylist,
@@ -1184,9 +1647,71 @@ function App() {
       getPlaylistSongs,
       handleShufflePlay,
       stop,
+      handleImportPlaylist,
+      handleExportPlaylist,
+      isDesktop,
     ],
   );
 
+  const artistsPage = useMemo(
+    () => (
+      <div className="flex-1 flex flex-col p-6 pt-16 pb-24 md:pb-20 h-full overflow-hidden">
+        <div className="mb-6 flex-shrink-0">
+          <h1 className="text-2xl font-bold text-vinyl-text">Artists</h1>
+          <p className="text-vinyl-text-muted">{artists.length} artists</p>
+        </div>
+        <ScrollArea className="flex-1">
+          <ArtistsView artists={artists} currentArtist={currentArtist} />
+        </ScrollArea>
+      </div>
+    ),
+    [artists, currentArtist],
+  );
+
+  const albumsPage = useMemo(
+    () => (
+      <div className="flex-1 flex flex-col p-6 pt-16 pb-24 md:pb-20 h-full overflow-hidden">
+        <div className="mb-6 flex-shrink-0">
+          <h1 className="text-2xl font-bold text-vinyl-text">Albums</h1>
+          <p className="text-vinyl-text-muted">{albums.length} albums</p>
+        </div>
+        <ScrollArea className="flex-1">
+          <AlbumsView
+            albums={albums}
+            currentAlbumId={currentAlbumId}
+            onPlayAlbum={handlePlayAlbum}
+          />
+        </ScrollArea>
+      </div>
+    ),
+    [albums, currentAlbumId, handlePlayAlbum],
+  );
+
+  const statsPage = useMemo(
+    () => (
+      <div className="flex-1 flex flex-col p-6 pt-16 pb-24 md:pb-20 h-full overflow-hidden">
+        <div className="mb-6 flex-shrink-0">
+          <h1 className="text-2xl font-bold text-vinyl-text">Stats</h1>
+          <p className="text-vinyl-text-muted">Your listening history</p>
+        </div>
+        <ScrollArea className="flex-1">
+          <StatsView
+            songs={songs}
+            currentSongId={currentSong?.id ?? null}
+            onPlaySong={(song) => playSong(song, null)}
+            onSaveSmartPlaylist={async (name, rules) => {
+              await createSmartPlaylist(name, rules);
+              toast.success(`Created smart playlist "${name}"`, {
+                duration: 2000,
+              });
+            }}
+          />
+        </ScrollArea>
+      </div>
+    ),
+    [songs, currentSong?.id, playSong, createSmartPlaylist],
+  );
+
   // Reference to stop the generator from outside
   const stopGeneratorRef = useRef<(() => void) | null>(null);
 
@@ -1223,6 +1748,22 @@ function App() {
     [importFromMusicFileInfos],
   );
 
+  // Apply the moves and edits an incremental scan found; returns the files to import
+  const handleLibraryScanAll = useCallback(async () => {
+    const result = await library.scanAllFolders();
+    const files = [...result.files];
+
+    for (const { from, file } of result.moved ?? []) {
+      if (!(await moveSongFile(from, file))) files.push(file);
+    }
+    for (const file of result.changed ?? []) {
+      if (!(await refreshSongFile(file.path))) files.push(file);
+    }
+    // A drive may have come back (or gone) since the last health check
+    checkLibraryHealth();
+    return files;
+  }, [library.scanAllFolders, moveSongFile, refreshSongFile, checkLibraryHealth]);
+
   const settingsPage = useMemo(
     () => (
       <ScrollArea className="flex-1 pt-16 pb-24 md:pb-20">
@@ -1231,14 +1772,21 @@ function App() {
           onUpdateSetting={updateSetting}
           onResetSettings={resetSettings}
           onResetPlayer={handleResetPlayer}
-          eqBands={eqBands}
+          eqFilters={eqFilters}
           eqEnabled={eqEnabled}
           eqPreset={eqPreset}
+          eqUserPresets={eqUserPresets}
           eqConnected={eqConnected}
-          onEqBandChange={setBandGain}
+          onEqFilterChange={setEqFilter}
           onEqPresetChange={applyPreset}
           onEqReset={resetEqualizer}
           onEqToggleEnabled={toggleEqualizer}
+          playbackProfiles={playbackProfiles}
+          currentSong={currentSong ?? null}
+          currentPlaylist={currentPlaylist}
+          outputDevice={outputDevice}
+          onSavePlaybackProfile={savePlaybackProfile}
+          onDeletePlaybackProfile={deletePlaybackProfile}
           // Library props
           libraryFolders={library.folders}
           libraryIsScanning={library.isScanning}
@@ -1251,9 +1799,13 @@ function App() {
           onLibraryAddFolder={library.addFolder}
           onLibraryRemoveFolder={library.removeFolder}
           onLibraryScanFolder={library.scanFolder}
-          onLibraryScanAllFolders={library.scanAllFolders}
+          onLibraryScanAllFolders={handleLibraryScanAll}
           onLibraryImportFiles={handleLibraryImport}
           onLibraryClearError={library.clearError}
+          libraryHealth={libraryHealth}
+          onLibraryCheckHealth={checkLibraryHealth}
+          onLibraryRelinkMissing={handleRelinkMissing}
+          loudnessProgress={loudnessProgress}
         />
       </ScrollArea>
     ),
@@ -1261,14 +1813,21 @@ function App() {
       settings,
       updateSetting,
       resetSettings,
-      eqBands,
+      eqFilters,
       eqEnabled,
       eqPreset,
+      eqUserPresets,
       eqConnected,
-      setBandGain,
+      setEqFilter,
       applyPreset,
       resetEqualizer,
       toggleEqualizer,
+      playbackProfiles,
+      currentSong,
+      currentPlaylist,
+      outputDevice,
+      savePlaybackProfile,
+      deletePlaybackProfile,
       library.folders,
       library.isScanning,
       library.scanProgress,
@@ -1280,9 +1839,13 @@ function App() {
       library.addFolder,
       library.removeFolder,
       library.scanFolder,
-      library.scanAllFolders,
+      handleLibraryScanAll,
       handleLibraryImport,
       library.clearError,
+      libraryHealth,
+      checkLibraryHealth,
+      handleRelinkMissing,
+      loudnessProgress,
     ],
   );
 
@@ -1371,6 +1934,38 @@ function App() {
               />
             }
           />
+          <Route path="/artists" element={artistsPage} />
+          <Route
+            path="/artists/:artistName"
+            element={
+              <ArtistDetailView
+                artists={artists}
+                currentAlbumId={currentAlbumId}
+                onPlayAlbum={handlePlayAlbum}
+                onPlayArtist={handlePlayArtist}
+              />
+            }
+          />
+          <Route path="/albums" element={albumsPage} />
+          <Route
+            path="/albums/:albumId"
+            element={
+              <AlbumDetailView
+                albums={albums}
+                currentSongId={currentSong?.id || null}
+                isPlaying={isPlaying}
+                unavailableSongIds={unavailableSongIds}
+                onPlayAlbum={handlePlayAlbum}
+                onShuffleAlbum={(album) => handleShufflePlay(album.songs, null)}
+                onQueueAlbum={handleQueueAlbum}
+                onTogglePlayPause={togglePlayPause}
+                onEditAlbum={(album) =>
+                  setTagEditor({ songs: album.songs, albumId: album.id })
+                }
+              />
+            }
+          />
+          <Route path="/stats" element={statsPage} />
           <Route path="/settings" element={settingsPage} />
           <Route path="/about" element={aboutPage} />
         </Routes>
@@ -1387,16 +1982,28 @@ function App() {
         repeat={repeat}
         shuffle={shuffle}
         speed={speed}
+        pitch={pitch}
+        preservePitch={settings.preservePitch}
+        isPitchRemembered={isPitchRemembered}
+        canTranspose={canTranspose}
         queueSongs={queueSongs}
         currentPlaylist={currentPlaylist}
         showAlbumArt={settings.showAlbumArt}
         isFavorite={currentSong ? favoriteSongIds.has(currentSong.id) : false}
-        eqBands={eqBands}
+        eqFilters={eqFilters}
+        eqPreamp={eqPreamp}
         eqEnabled={eqEnabled}
         eqPreset={eqPreset}
         eqConnected={eqConnected}
-        onEqBandChange={setBandGain}
+        eqUserPresets={eqUserPresets}
+        onEqFilterChange={setEqFilter}
+        onEqAddFilter={addEqFilter}
+        onEqRemoveFilter={removeEqFilter}
+        onEqPreampChange={setEqPreamp}
         onEqPresetChange={applyPreset}
+        onEqSavePreset={handleSaveEqPreset}
+        onEqDeletePreset={deleteEqPreset}
+        onEqImportProfile={handleImportEqProfile}
         onEqReset={resetEqualizer}
         onEqToggleEnabled={handleToggleEqualizer}
         // Sleep timer props
@@ -1438,6 +2045,9 @@ function App() {
         onToggleRepeat={handleToggleRepeat}
         onToggleShuffle={handleToggleShuffle}
         onSpeedChange={handleSpeedChange}
+        onPitchChange={handlePitchChange}
+        onTogglePreservePitch={handleTogglePreservePitch}
+        onToggleRememberPitch={handleToggleRememberPitch}
         onPlayFromQueue={playFromQueue}
         onStop={stop}
         onDeleteFromQueue={removeSongFromQueue}
@@ -1500,6 +2110,19 @@ function App() {
         isOpen={showMusicInfoDialog}
         onClose={() => setShowMusicInfoDialog(false)}
         song={currentSong || null}
+        onEditTags={() => {
+          if (!currentSong) return;
+          setShowMusicInfoDialog(false);
+          setTagEditor({ songs: [currentSong] });
+        }}
+      />
+
+      {/* Tag Editor - single song or a whole album */}
+      <TagEditorDialog
+        isOpen={!!tagEditor}
+        songs={tagEditor?.songs ?? NO_SONGS}
+        onSave={handleSaveTags}
+        onClose={() => setTagEditor(null)}
       />
 
       {/* Command Menu (Cmd/Ctrl+K) */}
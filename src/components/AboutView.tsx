@@ -1,5 +1,6 @@
 import { useState, useEffect } from "react";
 import {
+  Archive,
   Disc3,
   Heart,
   Database,
@@ -27,6 +28,7 @@ import {
 import { checkStorageQuota } from "../lib/db";
 import { formatFileSize } from "../lib/audioMetadata";
 import { isDesktop } from "../lib/platform";
+import { BackupActions } from "./BackupSettings";
 
 // Detect browser type
 function getBrowserInfo() {
@@ -147,6 +149,19 @@ export function AboutView() {
         </div>
       </div>
 
+      {/* Backup */}
+      <div className="bg-vinyl-surface rounded-xl p-5 border border-vinyl-border">
+        <h3 className="font-medium text-vinyl-text flex items-center gap-2 mb-3">
+          <Archive className="w-5 h-5 text-vinyl-accent" />
+          Your Data
+        </h3>
+        <p className="text-sm text-vinyl-text-muted mb-3">
+          Everything stays on this device. Back it up to a file to move it to
+          another device or keep it safe.
+        </p>
+        <BackupActions />
+      </div>
+
       {/* Features Grid */}
       <div className="space-y-4">
         <h2 className="text-lg font-semibold text-vinyl-text flex items-center gap-2">
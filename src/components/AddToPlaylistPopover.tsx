@@ -33,9 +33,9 @@ export function AddToPlaylistPopover({
   const inputRef = useRef<HTMLInputElement>(null);
   const searchInputRef = useRef<HTMLInputElement>(null);
 
-  // Filter out Favorites and filter by search query
+  // Filter out Favorites and smart playlists, then filter by search query
   const availablePlaylists = playlists
-    .filter((p) => p.name !== "Favorites")
+    .filter((p) => p.name !== "Favorites" && !p.rules)
     .filter((p) =>
       searchQuery
         ? p.name.toLowerCase().includes(searchQuery.toLowerCase())
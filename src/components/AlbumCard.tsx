@@ -0,0 +1,62 @@
+import { memo } from "react";
+import { Link } from "react-router-dom";
+import { Disc3, Play } from "lucide-react";
+import type { Album } from "../lib/albums";
+import { getAlbumPath } from "../lib/albums";
+import { tooltipProps } from "./Tooltip";
+import { ArtworkImage } from "./ArtworkImage";
+
+interface AlbumCardProps {
+  album: Album;
+  isCurrent?: boolean;
+  // Show the album artist under the title (off on artist pages)
+  showArtist?: boolean;
+  onPlay: (album: Album) => void;
+}
+
+export const AlbumCard = memo(function AlbumCard({
+  album,
+  isCurrent = false,
+  showArtist = true,
+  onPlay,
+}: AlbumCardProps) {
+  return (
+    <Link
+      to={getAlbumPath(album)}
+      className="group block p-2 rounded-lg hover:bg-vinyl-surface transition-colors"
+    >
+      <div className="relative aspect-square rounded-lg overflow-hidden bg-vinyl-border mb-2">
+        <ArtworkImage
+          artworkId={album.artworkId}
+          alt=""
+          loading="lazy"
+          className="w-full h-full object-cover"
+          fallback={
+            <div className="w-full h-full flex items-center justify-center">
+              <Disc3 className="w-1/3 h-1/3 text-vinyl-text-muted" />
+            </div>
+          }
+        />
+        <button
+          onClick={(e) => {
+            e.preventDefault();
+            onPlay(album);
+          }}
+          className="absolute bottom-2 right-2 p-3 bg-vinyl-accent rounded-full text-vinyl-bg shadow-lg opacity-0 group-hover:opacity-100 focus:opacity-100 transition-opacity hover:bg-vinyl-accent-light"
+          {...tooltipProps("Play album")}
+        >
+          <Play className="w-4 h-4" fill="currentColor" />
+        </button>
+      </div>
+      <p
+        className={`text-sm font-medium truncate ${isCurrent ? "text-vinyl-accent" : "text-vinyl-text"}`}
+      >
+        {album.title}
+      </p>
+      <p className="text-xs text-vinyl-text-muted truncate">
+        {showArtist ? album.artist : `${album.songs.length} songs`}
+        {album.year ? ` • ${album.year}` : ""}
+      </p>
+    </Link>
+  );
+});
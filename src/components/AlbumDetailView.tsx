@@ -0,0 +1,210 @@
+import { Link, useParams } from "react-router-dom";
+import {
+  ArrowLeft,
+  Disc3,
+  Play,
+  Pause,
+  Shuffle,
+  ListEnd,
+  ListStart,
+  PenLine,
+} from "lucide-react";
+import type { Song } from "../types";
+import type { Album } from "../lib/albums";
+import { getArtistPath } from "../lib/albums";
+import { formatDuration } from "../lib/audioMetadata";
+import { tooltipProps } from "./Tooltip";
+import { ArtworkImage } from "./ArtworkImage";
+
+interface AlbumDetailViewProps {
+  albums: Album[];
+  currentSongId: string | null;
+  isPlaying: boolean;
+  unavailableSongIds: Set<string>;
+  onPlayAlbum: (album: Album, startSong?: Song) => void;
+  onShuffleAlbum: (album: Album) => void;
+  onQueueAlbum: (album: Album, playNext: boolean) => void;
+  onTogglePlayPause: () => void;
+  onEditAlbum?: (album: Album) => void;
+}
+
+export function AlbumDetailView({
+  albums,
+  currentSongId,
+  isPlaying,
+  unavailableSongIds,
+  onPlayAlbum,
+  onShuffleAlbum,
+  onQueueAlbum,
+  onTogglePlayPause,
+  onEditAlbum,
+}: AlbumDetailViewProps) {
+  const { albumId } = useParams<{ albumId: string }>();
+  const album = albums.find((a) => a.id === albumId);
+
+  if (!album) {
+    return (
+      <div className="flex-1 flex flex-col items-center justify-center p-6 pt-16 pb-24 md:pb-20">
+        <p className="text-vinyl-text-muted">Album not found</p>
+        <Link to="/albums" className="mt-4 text-vinyl-accent hover:underline">
+          Back to Albums
+        </Link>
+      </div>
+    );
+  }
+
+  const isCurrentAlbum = album.songs.some((song) => song.id === currentSongId);
+  const discs = new Set(album.songs.map((song) => song.discNumber ?? 1));
+  const showDiscs = discs.size > 1;
+
+  return (
+    <div className="flex-1 flex flex-col p-6 pt-16 pb-24 md:pb-20 h-full overflow-y-auto">
+      <Link
+        to="/albums"
+        className="flex items-center gap-2 text-vinyl-text-muted hover:text-vinyl-text mb-4 transition-colors flex-shrink-0"
+      >
+        <ArrowLeft className="w-5 h-5" />
+        Back to Albums
+      </Link>
+
+      {/* Header */}
+      <div className="flex flex-col sm:flex-row gap-6 mb-6 flex-shrink-0">
+        <div className="w-48 h-48 rounded-lg overflow-hidden bg-vinyl-border flex-shrink-0 shadow-lg">
+          <ArtworkImage
+            artworkId={album.artworkId}
+            variant="full"
+            alt=""
+            className="w-full h-full object-cover"
+            fallback={
+              <div className="w-full h-full flex items-center justify-center">
+                <Disc3 className="w-16 h-16 text-vinyl-text-muted" />
+              </div>
+            }
+          />
+        </div>
+        <div className="flex flex-col justify-end min-w-0">
+          <h1
+            className={`text-2xl font-bold truncate ${isCurrentAlbum ? "text-vinyl-accent" : "text-vinyl-text"}`}
+          >
+            {album.title}
+          </h1>
+          <Link
+            to={getArtistPath(album.artist)}
+            className="text-vinyl-text hover:text-vinyl-accent transition-colors truncate"
+          >
+            {album.artist}
+          </Link>
+          <p className="text-sm text-vinyl-text-muted">
+            {album.year ? `${album.year} • ` : ""}
+            {album.genre ? `${album.genre} • ` : ""}
+            {album.songs.length} {album.songs.length === 1 ? "song" : "songs"}{" "}
+            • {formatDuration(album.duration)}
+            {album.codec ? ` • ${album.codec}` : ""}
+          </p>
+          <div className="flex items-center gap-2 mt-4">
+            <button
+              onClick={() =>
+                isCurrentAlbum ? onTogglePlayPause() : onPlayAlbum(album)
+              }
+              className="p-3 bg-vinyl-accent rounded-full text-vinyl-bg hover:bg-vinyl-accent-light transition-colors"
+              {...tooltipProps(
+                isCurrentAlbum && isPlaying ? "Pause" : "Play Album",
+              )}
+            >
+              {isCurrentAlbum && isPlaying ? (
+                <Pause className="w-5 h-5" fill="currentColor" />
+              ) : (
+                <Play className="w-5 h-5" fill="currentColor" />
+              )}
+            </button>
+            <button
+              onClick={() => onShuffleAlbum(album)}
+              className="p-3 bg-vinyl-surface border border-vinyl-border rounded-full text-vinyl-text-muted hover:text-vinyl-accent hover:border-vinyl-accent transition-colors"
+              {...tooltipProps("Shuffle Play")}
+            >
+              <Shuffle className="w-5 h-5" />
+            </button>
+            <button
+              onClick={() => onQueueAlbum(album, true)}
+              className="p-3 bg-vinyl-surface border border-vinyl-border rounded-full text-vinyl-text-muted hover:text-vinyl-accent hover:border-vinyl-accent transition-colors"
+              {...tooltipProps("Play Next")}
+            >
+              <ListStart className="w-5 h-5" />
+            </button>
+            <button
+              onClick={() => onQueueAlbum(album, false)}
+              className="p-3 bg-vinyl-surface border border-vinyl-border rounded-full text-vinyl-text-muted hover:text-vinyl-accent hover:border-vinyl-accent transition-colors"
+              {...tooltipProps("Add to Queue")}
+            >
+              <ListEnd className="w-5 h-5" />
+            </button>
+            {onEditAlbum && (
+              <button
+                onClick={() => onEditAlbum(album)}
+                className="p-3 bg-vinyl-surface border border-vinyl-border rounded-full text-vinyl-text-muted hover:text-vinyl-accent hover:border-vinyl-accent transition-colors"
+                {...tooltipProps("Edit Tags")}
+              >
+                <PenLine className="w-5 h-5" />
+              </button>
+            )}
+          </div>
+        </div>
+      </div>
+
+      {/* Tracks */}
+      <div className="space-y-1">
+        {album.songs.map((song, index) => {
+          const disc = song.discNumber ?? 1;
+          const startsDisc =
+            showDiscs &&
+            (index === 0 || (album.songs[index - 1].discNumber ?? 1) !== disc);
+          const isCurrentSong = song.id === currentSongId;
+          const isUnavailable = unavailableSongIds.has(song.id);
+
+          return (
+            <div key={song.id}>
+              {startsDisc && (
+                <h3 className="flex items-center gap-2 text-sm font-semibold text-vinyl-text-muted px-3 pt-4 pb-1">
+                  <Disc3 className="w-4 h-4" />
+                  Disc {disc}
+                </h3>
+              )}
+              <button
+                onClick={() =>
+                  isCurrentSong ? onTogglePlayPause() : onPlayAlbum(album, song)
+                }
+                disabled={isUnavailable}
+                className={`w-full flex items-center gap-3 p-3 rounded-lg text-left transition-colors ${
+                  isCurrentSong
+                    ? "bg-vinyl-accent/20 text-vinyl-accent"
+                    : isUnavailable
+                      ? "text-vinyl-text-muted opacity-50 cursor-not-allowed"
+                      : "hover:bg-vinyl-surface text-vinyl-text"
+                }`}
+              >
+                <span className="w-6 text-right text-sm text-vinyl-text-muted tabular-nums flex-shrink-0">
+                  {isCurrentSong && isPlaying ? (
+                    <Pause className="w-4 h-4 inline" />
+                  ) : (
+                    (song.trackNumber ?? "–")
+                  )}
+                </span>
+                <div className="flex-1 min-w-0">
+                  <p className="font-medium truncate">{song.title}</p>
+                  {song.artist !== album.artist && (
+                    <p className="text-xs text-vinyl-text-muted truncate">
+                      {song.artist}
+                    </p>
+                  )}
+                </div>
+                <span className="text-sm text-vinyl-text-muted tabular-nums">
+                  {formatDuration(song.duration)}
+                </span>
+              </button>
+            </div>
+          );
+        })}
+      </div>
+    </div>
+  );
+}
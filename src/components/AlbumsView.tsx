@@ -0,0 +1,57 @@
+import { Disc3 } from "lucide-react";
+import type { Album } from "../lib/albums";
+import { AlbumCard } from "./AlbumCard";
+
+interface AlbumsViewProps {
+  albums: Album[];
+  currentAlbumId: string | null;
+  onPlayAlbum: (album: Album) => void;
+}
+
+export function AlbumsView({
+  albums,
+  currentAlbumId,
+  onPlayAlbum,
+}: AlbumsViewProps) {
+  if (albums.length === 0) {
+    return (
+      <div className="flex flex-col items-center py-16 text-vinyl-text-muted">
+        <Disc3 className="w-12 h-12 mb-2 opacity-50" />
+        <p>No albums yet</p>
+      </div>
+    );
+  }
+
+  // Albums arrive sorted by album artist, so sections are consecutive runs
+  const sections: { artist: string; albums: Album[] }[] = [];
+  for (const album of albums) {
+    const last = sections[sections.length - 1];
+    if (last && last.artist === album.artist) {
+      last.albums.push(album);
+    } else {
+      sections.push({ artist: album.artist, albums: [album] });
+    }
+  }
+
+  return (
+    <div className="space-y-6">
+      {sections.map((section) => (
+        <section key={section.artist}>
+          <h2 className="text-sm font-semibold text-vinyl-text-muted uppercase tracking-wide mb-2 px-2">
+            {section.artist}
+          </h2>
+          <div className="grid grid-cols-2 sm:grid-cols-3 lg:grid-cols-5 xl:grid-cols-6 gap-2">
+            {section.albums.map((album) => (
+              <AlbumCard
+                key={album.id}
+                album={album}
+                isCurrent={album.id === currentAlbumId}
+                onPlay={onPlayAlbum}
+              />
+            ))}
+          </div>
+        </section>
+      ))}
+    </div>
+  );
+}
@@ -0,0 +1,116 @@
+import { Link, useParams } from "react-router-dom";
+import { ArrowLeft, Play, Shuffle, Users } from "lucide-react";
+import type { Album, Artist } from "../lib/albums";
+import { findArtist } from "../lib/albums";
+import { AlbumCard } from "./AlbumCard";
+import { tooltipProps } from "./Tooltip";
+import { ArtworkImage } from "./ArtworkImage";
+
+interface ArtistDetailViewProps {
+  artists: Artist[];
+  currentAlbumId: string | null;
+  onPlayAlbum: (album: Album) => void;
+  onPlayArtist: (artist: Artist, shuffle: boolean) => void;
+}
+
+export function ArtistDetailView({
+  artists,
+  currentAlbumId,
+  onPlayAlbum,
+  onPlayArtist,
+}: ArtistDetailViewProps) {
+  const { artistName = "" } = useParams<{ artistName: string }>();
+  const artist = findArtist(artists, artistName);
+
+  if (!artist) {
+    return (
+      <div className="flex-1 flex flex-col items-center justify-center p-6 pt-16 pb-24 md:pb-20">
+        <p className="text-vinyl-text-muted">Artist not found</p>
+        <Link to="/artists" className="mt-4 text-vinyl-accent hover:underline">
+          Back to Artists
+        </Link>
+      </div>
+    );
+  }
+
+  const renderAlbums = (albums: Album[], showArtist: boolean) => (
+    <div className="grid grid-cols-2 sm:grid-cols-3 lg:grid-cols-5 xl:grid-cols-6 gap-2">
+      {albums.map((album) => (
+        <AlbumCard
+          key={album.id}
+          album={album}
+          isCurrent={album.id === currentAlbumId}
+          showArtist={showArtist}
+          onPlay={onPlayAlbum}
+        />
+      ))}
+    </div>
+  );
+
+  return (
+    <div className="flex-1 flex flex-col p-6 pt-16 pb-24 md:pb-20 h-full overflow-y-auto">
+      <Link
+        to="/artists"
+        className="flex items-center gap-2 text-vinyl-text-muted hover:text-vinyl-text mb-4 transition-colors flex-shrink-0"
+      >
+        <ArrowLeft className="w-5 h-5" />
+        Back to Artists
+      </Link>
+
+      <div className="flex items-center justify-between gap-4 mb-6 flex-shrink-0">
+        <div className="flex items-center gap-4 min-w-0">
+          <div className="w-20 h-20 rounded-full overflow-hidden bg-vinyl-border flex-shrink-0">
+            <ArtworkImage
+              artworkId={artist.artworkId}
+              alt=""
+              className="w-full h-full object-cover"
+              fallback={
+                <div className="w-full h-full flex items-center justify-center">
+                  <Users className="w-8 h-8 text-vinyl-text-muted" />
+                </div>
+              }
+            />
+          </div>
+          <div className="min-w-0">
+            <h1 className="text-2xl font-bold text-vinyl-text truncate">
+              {artist.name}
+            </h1>
+            <p className="text-vinyl-text-muted">
+              {artist.albums.length}{" "}
+              {artist.albums.length === 1 ? "album" : "albums"} •{" "}
+              {artist.songCount} {artist.songCount === 1 ? "song" : "songs"}
+            </p>
+          </div>
+        </div>
+        <div className="flex items-center gap-2">
+          <button
+            onClick={() => onPlayArtist(artist, true)}
+            className="p-3 bg-vinyl-surface border border-vinyl-border rounded-full text-vinyl-text-muted hover:text-vinyl-accent hover:border-vinyl-accent transition-colors"
+            {...tooltipProps("Shuffle Play")}
+          >
+            <Shuffle className="w-5 h-5" />
+          </button>
+          <button
+            onClick={() => onPlayArtist(artist, false)}
+            className="p-3 bg-vinyl-accent rounded-full text-vinyl-bg hover:bg-vinyl-accent-light transition-colors"
+            {...tooltipProps("Play All")}
+          >
+            <Play className="w-5 h-5" fill="currentColor" />
+          </button>
+        </div>
+      </div>
+
+      <h2 className="text-lg font-semibold text-vinyl-text mb-2">Albums</h2>
+      {renderAlbums(artist.albums, false)}
+
+      {artist.appearsOn.length > 0 && (
+        <>
+          <h2 className="text-lg font-semibold text-vinyl-text mt-6 mb-2">
+            Appears On
+          </h2>
+          {renderAlbums(artist.appearsOn, true)}
+        </>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,60 @@
+import { Link } from "react-router-dom";
+import { Users } from "lucide-react";
+import type { Artist } from "../lib/albums";
+import { getArtistPath } from "../lib/albums";
+import { ArtworkImage } from "./ArtworkImage";
+
+interface ArtistsViewProps {
+  artists: Artist[];
+  currentArtist: string | null;
+}
+
+export function ArtistsView({ artists, currentArtist }: ArtistsViewProps) {
+  if (artists.length === 0) {
+    return (
+      <div className="flex flex-col items-center py-16 text-vinyl-text-muted">
+        <Users className="w-12 h-12 mb-2 opacity-50" />
+        <p>No artists yet</p>
+      </div>
+    );
+  }
+
+  return (
+    <div className="grid grid-cols-2 sm:grid-cols-3 lg:grid-cols-5 xl:grid-cols-6 gap-2">
+      {artists.map((artist) => {
+        const isCurrent = artist.name === currentArtist;
+        return (
+          <Link
+            key={artist.name}
+            to={getArtistPath(artist.name)}
+            className="block p-2 rounded-lg hover:bg-vinyl-surface transition-colors text-center"
+          >
+            <div className="aspect-square rounded-full overflow-hidden bg-vinyl-border mb-2">
+              <ArtworkImage
+                artworkId={artist.artworkId}
+                alt=""
+                loading="lazy"
+                className="w-full h-full object-cover"
+                fallback={
+                  <div className="w-full h-full flex items-center justify-center">
+                    <Users className="w-1/3 h-1/3 text-vinyl-text-muted" />
+                  </div>
+                }
+              />
+            </div>
+            <p
+              className={`text-sm font-medium truncate ${isCurrent ? "text-vinyl-accent" : "text-vinyl-text"}`}
+            >
+              {artist.name}
+            </p>
+            <p className="text-xs text-vinyl-text-muted">
+              {artist.albums.length}{" "}
+              {artist.albums.length === 1 ? "album" : "albums"} •{" "}
+              {artist.songCount} {artist.songCount === 1 ? "song" : "songs"}
+            </p>
+          </Link>
+        );
+      })}
+    </div>
+  );
+}
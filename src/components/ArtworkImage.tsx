@@ -0,0 +1,22 @@
+import type { ArtworkVariant } from "../lib/artwork";
+import { useArtworkUrl } from "../hooks/useArtworkUrl";
+
+interface ArtworkImageProps
+  extends Omit<React.ImgHTMLAttributes<HTMLImageElement>, "src"> {
+  artworkId?: string;
+  variant?: ArtworkVariant;
+  // Shown while there is no artwork, or it hasn't loaded yet
+  fallback?: React.ReactNode;
+}
+
+// <img> for a stored artwork id
+export function ArtworkImage({
+  artworkId,
+  variant = "thumbnail",
+  fallback = null,
+  alt = "",
+  ...props
+}: ArtworkImageProps) {
+  const url = useArtworkUrl(artworkId, variant);
+  return url ? <img src={url} alt={alt} {...props} /> : <>{fallback}</>;
+}
@@ -0,0 +1,315 @@
+import { useRef, useState } from "react";
+import { toast } from "sonner";
+import { Archive, Download, FolderOpen, Upload } from "lucide-react";
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from "@/components/ui/dialog";
+import { Button } from "@/components/ui/button";
+import type { AppSettings, AutoBackupInterval } from "../types";
+import type {
+  BackupRestoreMode,
+  BackupRestorePlan,
+  LibraryBackup,
+} from "../lib/backup";
+import { useLibraryBackup } from "../hooks/useLibraryBackup";
+import { isDesktop, openFolderPicker } from "../lib/platform";
+
+const selectClass =
+  "px-3 py-1.5 bg-vinyl-border text-vinyl-text rounded text-sm border-0 cursor-pointer";
+
+const RESTORE_MODES: {
+  mode: BackupRestoreMode;
+  label: string;
+  description: string;
+}[] = [
+  {
+    mode: "merge",
+    label: "Merge",
+    description:
+      "Add the backup's songs, playlists and history to your library. Your settings stay as they are.",
+  },
+  {
+    mode: "replace",
+    label: "Replace",
+    description:
+      "Replace your library, playlists, history and settings with the backup.",
+  },
+];
+
+function plural(count: number, word: string): string {
+  return `${count} ${word}${count === 1 ? "" : "s"}`;
+}
+
+// Back Up Now / Restore buttons, shared by Settings and About
+export function BackupActions() {
+  const { exportBackup, readBackup, restoreBackup } = useLibraryBackup();
+  const fileInputRef = useRef<HTMLInputElement>(null);
+  const [isExporting, setIsExporting] = useState(false);
+  const [backup, setBackup] = useState<LibraryBackup | null>(null);
+  const [mode, setMode] = useState<BackupRestoreMode>("merge");
+  const [isRestoring, setIsRestoring] = useState(false);
+  const [result, setResult] = useState<BackupRestorePlan | null>(null);
+
+  const handleExport = async () => {
+    setIsExporting(true);
+    try {
+      if (await exportBackup()) {
+        toast.success("Library backed up", { duration: 2000 });
+      }
+    } catch (error) {
+      console.error("Failed to back up library:", error);
+      toast.error("Couldn't back up the library", { duration: 3000 });
+    } finally {
+      setIsExporting(false);
+    }
+  };
+
+  const openBackup = async (file?: File) => {
+    try {
+      const opened = await readBackup(file);
+      if (opened) {
+        setMode("merge");
+        setBackup(opened);
+      }
+    } catch (error) {
+      toast.error(
+        error instanceof Error ? error.message : "Couldn't read the backup",
+        { duration: 3000 },
+      );
+    }
+  };
+
+  const handleRestoreClick = () => {
+    if (isDesktop()) {
+      openBackup();
+    } else {
+      fileInputRef.current?.click();
+    }
+  };
+
+  const handleFileChange = (e: React.ChangeEvent<HTMLInputElement>) => {
+    const file = e.target.files?.[0];
+    e.target.value = "";
+    if (file) openBackup(file);
+  };
+
+  const handleRestore = async () => {
+    if (!backup) return;
+    setIsRestoring(true);
+    try {
+      setResult(await restoreBackup(backup, mode));
+    } catch (error) {
+      console.error("Failed to restore backup:", error);
+      toast.error("Couldn't restore the backup", { duration: 3000 });
+    } finally {
+      setIsRestoring(false);
+    }
+  };
+
+  return (
+    <>
+      <div className="flex flex-wrap gap-2">
+        <Button variant="outline" onClick={handleExport} disabled={isExporting}>
+          <Download />
+          {isExporting ? "Backing Up..." : "Back Up Now"}
+        </Button>
+        <Button variant="outline" onClick={handleRestoreClick}>
+          <Upload />
+          Restore...
+        </Button>
+        <input
+          ref={fileInputRef}
+          type="file"
+          accept=".json,application/json"
+          className="hidden"
+          onChange={handleFileChange}
+        />
+      </div>
+
+      {/* Restore confirmation */}
+      <Dialog
+        open={backup !== null && result === null}
+        onOpenChange={(open) => !open && !isRestoring && setBackup(null)}
+      >
+        <DialogContent className="max-w-md">
+          <DialogHeader>
+            <DialogTitle className="flex items-center gap-2">
+              <Upload className="w-5 h-5 text-vinyl-accent" />
+              Restore Backup
+            </DialogTitle>
+            <DialogDescription>
+              {backup &&
+                `Backup from ${new Date(backup.createdAt).toLocaleString()}: ${plural(backup.data.songs.length, "song")}, ${plural(backup.data.playlists.length, "playlist")}, ${plural(backup.data.plays.length, "play")}.`}
+            </DialogDescription>
+          </DialogHeader>
+
+          <div className="space-y-2">
+            {RESTORE_MODES.map((option) => (
+              <button
+                key={option.mode}
+                onClick={() => setMode(option.mode)}
+                className={`w-full text-left p-3 rounded-lg border transition-colors ${
+                  mode === option.mode
+                    ? "border-vinyl-accent bg-vinyl-accent/10"
+                    : "border-vinyl-border hover:bg-vinyl-border/50"
+                }`}
+              >
+                <p className="text-sm font-medium text-vinyl-text">
+                  {option.label}
+                </p>
+                <p className="text-xs text-vinyl-text-muted">
+                  {option.description}
+                </p>
+              </button>
+            ))}
+          </div>
+
+          <DialogFooter>
+            <Button
+              variant="outline"
+              onClick={() => setBackup(null)}
+              disabled={isRestoring}
+            >
+              Cancel
+            </Button>
+            <Button
+              variant={mode === "replace" ? "destructive" : "default"}
+              onClick={handleRestore}
+              disabled={isRestoring}
+            >
+              {isRestoring ? "Restoring..." : "Restore"}
+            </Button>
+          </DialogFooter>
+        </DialogContent>
+      </Dialog>
+
+      {/* Restore report; the app reloads to pick up the restored data */}
+      <Dialog open={result !== null}>
+        <DialogContent className="max-w-md">
+          <DialogHeader>
+            <DialogTitle>Backup Restored</DialogTitle>
+            <DialogDescription>
+              {result &&
+                `Restored ${plural(result.added.songs, "song")}, ${plural(result.added.playlists, "playlist")} and ${plural(result.added.plays, "play")}. Vinyl needs to reload to finish.`}
+            </DialogDescription>
+          </DialogHeader>
+
+          {result && result.conflicts.length > 0 && (
+            <div className="space-y-2">
+              <p className="text-sm font-medium text-vinyl-text">
+                {plural(result.conflicts.length, "conflict")}
+              </p>
+              <ul className="max-h-60 overflow-y-auto space-y-1.5 text-sm">
+                {result.conflicts.map((conflict, index) => (
+                  <li key={index}>
+                    <p className="text-vinyl-text truncate">{conflict.name}</p>
+                    <p className="text-xs text-vinyl-text-muted">
+                      {conflict.resolution}
+                    </p>
+                  </li>
+                ))}
+              </ul>
+            </div>
+          )}
+
+          <DialogFooter>
+            <Button onClick={() => window.location.reload()}>Reload</Button>
+          </DialogFooter>
+        </DialogContent>
+      </Dialog>
+    </>
+  );
+}
+
+interface BackupSettingsProps {
+  settings: AppSettings;
+  onUpdateSetting: <K extends keyof AppSettings>(
+    key: K,
+    value: AppSettings[K],
+  ) => void;
+}
+
+export function BackupSettings({
+  settings,
+  onUpdateSetting,
+}: BackupSettingsProps) {
+  const showSchedule = isDesktop();
+
+  const handlePickFolder = async () => {
+    const folder = await openFolderPicker();
+    if (folder) onUpdateSetting("autoBackupFolder", folder);
+  };
+
+  return (
+    <section className="bg-vinyl-surface border border-vinyl-border rounded-lg overflow-hidden">
+      <div className="px-4 py-3 border-b border-vinyl-border bg-vinyl-border/20">
+        <h2 className="font-semibold text-vinyl-text flex items-center gap-2">
+          <Archive className="w-5 h-5 text-vinyl-accent" />
+          Backup & Restore
+        </h2>
+      </div>
+      <div className="p-4 space-y-4">
+        <div>
+          <p className="text-sm text-vinyl-text-muted mb-3">
+            Save your library, playlists, play history, settings and equalizer
+            to a single file, or restore them from one.
+          </p>
+          <BackupActions />
+        </div>
+
+        {showSchedule && (
+          <>
+            <div className="flex items-center justify-between">
+              <div>
+                <h3 className="text-vinyl-text font-medium">
+                  Automatic Backups
+                </h3>
+                <p className="text-sm text-vinyl-text-muted">
+                  {settings.lastAutoBackupAt
+                    ? `Last backup ${new Date(settings.lastAutoBackupAt).toLocaleString()}`
+                    : "Write a backup to a folder on a schedule"}
+                </p>
+              </div>
+              <select
+                value={settings.autoBackup}
+                onChange={(e) =>
+                  onUpdateSetting(
+                    "autoBackup",
+                    e.target.value as AutoBackupInterval,
+                  )
+                }
+                className={selectClass}
+              >
+                <option value="off">Off</option>
+                <option value="daily">Daily</option>
+                <option value="weekly">Weekly</option>
+              </select>
+            </div>
+
+            {settings.autoBackup !== "off" && (
+              <div className="flex items-center justify-between gap-4">
+                <div className="min-w-0">
+                  <h3 className="text-vinyl-text font-medium">
+                    Backup Folder
+                  </h3>
+                  <p className="text-sm text-vinyl-text-muted truncate">
+                    {settings.autoBackupFolder ?? "No folder chosen"}
+                  </p>
+                </div>
+                <Button variant="outline" size="sm" onClick={handlePickFolder}>
+                  <FolderOpen />
+                  Choose...
+                </Button>
+              </div>
+            )}
+          </>
+        )}
+      </div>
+    </section>
+  );
+}
@@ -15,6 +15,7 @@ import type { Song, Playlist } from "../types";
 import { formatDuration } from "../lib/audioMetadata";
 import { VirtualizedSongList } from "./VirtualizedSongList";
 import { tooltipProps } from "./Tooltip";
+import { ArtworkImage } from "./ArtworkImage";
 
 interface BottomPlayerProps {
   currentSong: Song | null;
@@ -133,15 +134,12 @@ export function BottomPlayer({
         <div className="flex items-center gap-3 flex-1 min-w-0">
           {/* Album art / icon */}
           <div className="w-10 h-10 rounded-lg bg-vinyl-border flex items-center justify-center flex-shrink-0 overflow-hidden">
-            {currentSong?.coverArt ? (
-              <img
-                src={currentSong.coverArt}
-                alt={currentSong.title}
-                className="w-full h-full object-cover"
-              />
-            ) : (
-              <Music className="w-5 h-5 text-vinyl-text-muted" />
-            )}
+            <ArtworkImage
+              artworkId={currentSong?.artworkId}
+              alt={currentSong.title}
+              className="w-full h-full object-cover"
+              fallback={<Music className="w-5 h-5 text-vinyl-text-muted" />}
+            />
           </div>
 
           {/* Title and artist */}
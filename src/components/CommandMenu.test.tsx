@@ -3,6 +3,27 @@ import { render, screen, fireEvent } from '@testing-library/react';
 import { BrowserRouter } from 'react-router-dom';
 import { CommandMenu } from './CommandMenu';
 import type { Song } from '../types';
+import type { SearchClause } from '../lib/searchQuery';
+
+// Stand-in for the IndexedDB search index: prefix match on title/artist/album words
+vi.mock('../lib/db', () => ({
+  searchSongIds: vi.fn(async (clauses: SearchClause[]) =>
+    mockSongs
+      .filter((song) =>
+        clauses.every(
+          (clause) =>
+            clause.kind === 'text' &&
+            clause.tokens.every((token) =>
+              `${song.title} ${song.artist} ${song.album}`
+                .toLowerCase()
+                .split(' ')
+                .some((word) => word.startsWith(token)),
+            ),
+        ),
+      )
+      .map((song) => song.id),
+  ),
+}));
 
 // Mock scrollIntoView which cmdk uses
 beforeAll(() => {
@@ -106,6 +127,8 @@ describe('CommandMenu', () => {
       expect(screen.getByText('Go to Library')).toBeInTheDocument();
       expect(screen.getByText('Go to Playlists')).toBeInTheDocument();
       expect(screen.getByText('Go to Settings')).toBeInTheDocument();
+      expect(screen.getByText('Go to Artists')).toBeInTheDocument();
+      expect(screen.getByText('Go to Albums')).toBeInTheDocument();
     });
 
     it('renders visualizer commands', () => {
@@ -160,31 +183,32 @@ describe('CommandMenu', () => {
   });
 
   describe('song search', () => {
-    it('shows songs when searching by title', () => {
+    it('shows songs when searching by title', async () => {
       renderCommandMenu();
       
       const input = screen.getByPlaceholderText('Search songs, commands...');
       fireEvent.change(input, { target: { value: 'Test Song' } });
       
-      expect(screen.getByText('Test Song 1')).toBeInTheDocument();
+      expect(await screen.findByText('Test Song 1')).toBeInTheDocument();
     });
 
-    it('filters out non-matching songs', () => {
+    it('filters out non-matching songs', async () => {
       renderCommandMenu();
       
       const input = screen.getByPlaceholderText('Search songs, commands...');
       fireEvent.change(input, { target: { value: 'Test Song' } });
       
+      await screen.findByText('Test Song 1');
       expect(screen.queryByText('Another Track')).not.toBeInTheDocument();
     });
 
-    it('shows songs when searching by artist', () => {
+    it('shows songs when searching by artist', async () => {
       renderCommandMenu();
       
       const input = screen.getByPlaceholderText('Search songs, commands...');
       fireEvent.change(input, { target: { value: 'Jazz' } });
       
-      expect(screen.getByText('Jazz Song')).toBeInTheDocument();
+      expect(await screen.findByText('Jazz Song')).toBeInTheDocument();
     });
 
     it.skip('shows no results message when nothing matches - cmdk shows commands', () => {
@@ -196,13 +220,13 @@ describe('CommandMenu', () => {
       expect(screen.getByText('No results found.')).toBeInTheDocument();
     });
 
-    it('shows Playing badge for current song in search', () => {
+    it('shows Playing badge for current song in search', async () => {
       renderCommandMenu({ currentSong: mockSongs[0] });
       
       const input = screen.getByPlaceholderText('Search songs, commands...');
       fireEvent.change(input, { target: { value: 'Test' } });
       
-      expect(screen.getByText('Playing')).toBeInTheDocument();
+      expect(await screen.findByText('Playing')).toBeInTheDocument();
     });
   });
 
@@ -254,14 +278,14 @@ describe('CommandMenu', () => {
       }
     });
 
-    it('calls onPlaySong when a song is selected', () => {
+    it('calls onPlaySong when a song is selected', async () => {
       const onPlaySong = vi.fn();
       renderCommandMenu({ onPlaySong });
       
       const input = screen.getByPlaceholderText('Search songs, commands...');
       fireEvent.change(input, { target: { value: 'Test' } });
       
-      const songItem = screen.getByText('Test Song 1').closest('[cmdk-item]');
+      const songItem = (await screen.findByText('Test Song 1')).closest('[cmdk-item]');
       if (songItem) {
         fireEvent.click(songItem);
         expect(onPlaySong).toHaveBeenCalledWith(mockSongs[0]);
@@ -22,11 +22,14 @@ import {
   Moon,
   BarChart3,
   Disc3,
-
+  Disc,
+  Users,
   Keyboard,
   Zap,
 } from "lucide-react";
 import type { Song } from "../types";
+import { useLibrarySearch } from "../hooks/useLibrarySearch";
+import { ArtworkImage } from "./ArtworkImage";
 
 import "./CommandMenu.css";
 
@@ -91,6 +94,7 @@ export function CommandMenu({
 }: CommandMenuProps) {
   const navigate = useNavigate();
   const [search, setSearch] = useState("");
+  const { results: searchResults } = useLibrarySearch(songs, search);
 
   // Close on Escape
   useEffect(() => {
@@ -123,14 +127,8 @@ export function CommandMenu({
 
   if (!isOpen) return null;
 
-  // Filter songs for search
-  const filteredSongs = search.length > 0
-    ? songs.filter(song =>
-        song.title.toLowerCase().includes(search.toLowerCase()) ||
-        song.artist.toLowerCase().includes(search.toLowerCase()) ||
-        song.album.toLowerCase().includes(search.toLowerCase())
-      ).slice(0, 10) // Limit to 10 results
-    : [];
+  // Songs matching the search (supports field filters like artist:)
+  const filteredSongs = searchResults?.slice(0, 10) ?? []; // Limit to 10 results
 
   return (
     <div className="cmdk-overlay" onClick={onClose}>
@@ -167,11 +165,12 @@ export function CommandMenu({
                     className="cmdk-item"
                   >
                     <div className="cmdk-item-icon">
-                      {song.coverArt ? (
-                        <img src={song.coverArt} alt="" className="cmdk-song-art" />
-                      ) : (
-                        <Music className="w-4 h-4" />
-                      )}
+                      <ArtworkImage
+                        artworkId={song.artworkId}
+                        alt=""
+                        className="cmdk-song-art"
+                        fallback={<Music className="w-4 h-4" />}
+                      />
                     </div>
                     <div className="cmdk-item-content">
                       <span className="cmdk-item-title">{song.title}</span>
@@ -316,6 +315,28 @@ export function CommandMenu({
                 <span>Go to Library</span>
               </Command.Item>
 
+              <Command.Item
+                value="go-artists"
+                onSelect={() => runCommand(() => navigate("/artists"))}
+                className="cmdk-item"
+              >
+                <div className="cmdk-item-icon">
+                  <Users className="w-4 h-4" />
+                </div>
+                <span>Go to Artists</span>
+              </Command.Item>
+
+              <Command.Item
+                value="go-albums"
+                onSelect={() => runCommand(() => navigate("/albums"))}
+                className="cmdk-item"
+              >
+                <div className="cmdk-item-icon">
+                  <Disc className="w-4 h-4" />
+                </div>
+                <span>Go to Albums</span>
+              </Command.Item>
+
               <Command.Item
                 value="go-playlists"
                 onSelect={() => runCommand(() => navigate("/playlists"))}
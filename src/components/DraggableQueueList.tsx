@@ -20,6 +20,7 @@ import { GripVertical, Music, Play, Pause, Square, Trash2 } from "lucide-react";
 import type { Song } from "../types";
 import { formatDuration } from "../lib/audioMetadata";
 import { tooltipProps } from "./Tooltip";
+import { ArtworkImage } from "./ArtworkImage";
 
 interface DraggableQueueListProps {
   songs: Song[];
@@ -90,15 +91,14 @@ const SortableSongItem = memo(function SortableSongItem({
 
       {/* Index / cover art */}
       <div className="w-8 h-8 flex items-center justify-center flex-shrink-0 rounded bg-vinyl-border/50 overflow-hidden">
-        {song.coverArt ? (
-          <img
-            src={song.coverArt}
-            alt=""
-            className="w-full h-full object-cover"
-          />
-        ) : (
-          <span className="text-xs text-vinyl-text-muted">{index + 1}</span>
-        )}
+        <ArtworkImage
+          artworkId={song.artworkId}
+          alt=""
+          className="w-full h-full object-cover"
+          fallback={
+            <span className="text-xs text-vinyl-text-muted">{index + 1}</span>
+          }
+        />
       </div>
 
       {/* Song info */}
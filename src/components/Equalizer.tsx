@@ -1,123 +1,240 @@
 import { useState, useRef, useEffect, useMemo } from "react";
-import { RotateCcw, Power, ChevronDown, AudioWaveform } from "lucide-react";
-import type { EqualizerBand, EqualizerPreset } from "../hooks/useEqualizer";
+import {
+  RotateCcw,
+  Power,
+  ChevronDown,
+  AudioWaveform,
+  Plus,
+  X,
+  FileUp,
+  Save,
+} from "lucide-react";
+import type { EqualizerPreset } from "../hooks/useEqualizer";
 import { EQUALIZER_PRESETS } from "../hooks/useEqualizer";
+import {
+  EQ_FILTER_TYPES,
+  EQ_LIMITS,
+  formatFrequency,
+  logFrequencies,
+  profileResponse,
+  usesGain,
+  usesQ,
+  type EqFilter,
+  type EqFilterType,
+} from "../lib/parametricEq";
 import { tooltipProps } from "./Tooltip";
 
 interface EqualizerProps {
-  bands: EqualizerBand[];
+  filters: EqFilter[];
+  preamp: number;
   enabled: boolean;
   currentPreset: string | null;
+  userPresets: EqualizerPreset[];
   isConnected: boolean;
-  onBandChange: (index: number, gain: number) => void;
+  onFilterChange: (index: number, patch: Partial<EqFilter>) => void;
+  onAddFilter: () => void;
+  onRemoveFilter: (index: number) => void;
+  onPreampChange: (gain: number) => void;
   onPresetChange: (preset: EqualizerPreset) => void;
+  onSavePreset: (name: string) => boolean;
+  onDeletePreset: (name: string) => void;
+  onImportProfile: (file: File) => void;
   onReset: () => void;
   onToggleEnabled: () => void;
 }
 
+const CURVE_POINTS = 128;
+const CURVE_FREQUENCIES = logFrequencies(CURVE_POINTS);
+const GRID_FREQUENCIES = [100, 1000, 10000];
+
+// Horizontal position (0-100) of a frequency on the log axis
+function frequencyToX(frequency: number): number {
+  const { min, max } = EQ_LIMITS.frequency;
+  return (Math.log(frequency / min) / Math.log(max / min)) * 100;
+}
+
 function FrequencyResponseCurve({
-  bands,
+  filters,
+  preamp,
   enabled,
 }: {
-  bands: EqualizerBand[];
+  filters: EqFilter[];
+  preamp: number;
   enabled: boolean;
 }) {
-  const svgPath = useMemo(() => {
-    const width = 100;
-    const height = 100;
-    const padding = 10;
-
-    const points = bands.map((band, index) => {
-      const x = padding + (index / (bands.length - 1)) * (width - 2 * padding);
-      const normalizedGain = enabled ? (band.gain + 12) / 24 : 0.5;
-      const y = height - padding - normalizedGain * (height - 2 * padding);
-      return { x, y };
-    });
+  const { path, range, markers } = useMemo(() => {
+    const profile = enabled ? { preamp, filters } : { preamp: 0, filters: [] };
+    const response = profileResponse(profile, CURVE_FREQUENCIES);
+    const markerResponse = profileResponse(
+      profile,
+      filters.map((filter) => filter.frequency),
+    );
 
-    if (points.length < 2) return "";
+    // Scale in 6 dB steps so small adjustments stay visible
+    const peak = Math.max(
+      ...response.map(Math.abs),
+      ...markerResponse.map(Math.abs),
+    );
+    const range = Math.min(
+      EQ_LIMITS.gain.max,
+      Math.max(12, Math.ceil(peak / 6) * 6),
+    );
+    const toY = (gain: number) =>
+      50 - (Math.max(-range, Math.min(range, gain)) / range) * 45;
 
-    let path = `M ${points[0].x} ${points[0].y}`;
+    const path = response
+      .map(
+        (gain, i) =>
+          `${i === 0 ? "M" : "L"} ${frequencyToX(CURVE_FREQUENCIES[i])} ${toY(gain)}`,
+      )
+      .join(" ");
+    const markers = filters.map((filter, i) => ({
+      id: filter.id,
+      x: frequencyToX(filter.frequency),
+      y: toY(markerResponse[i]),
+    }));
 
-    for (let i = 0; i < points.length - 1; i++) {
-      const p0 = points[Math.max(0, i - 1)];
-      const p1 = points[i];
-      const p2 = points[i + 1];
-      const p3 = points[Math.min(points.length - 1, i + 2)];
+    return { path, range, markers };
+  }, [filters, preamp, enabled]);
 
-      const tension = 0.5;
-      const cp1x = p1.x + ((p2.x - p0.x) / 6) * tension;
-      const cp1y = p1.y + ((p2.y - p0.y) / 6) * tension;
-      const cp2x = p2.x - ((p3.x - p1.x) / 6) * tension;
-      const cp2y = p2.y - ((p3.y - p1.y) / 6) * tension;
-
-      path += ` C ${cp1x} ${cp1y}, ${cp2x} ${cp2y}, ${p2.x} ${p2.y}`;
-    }
-
-    return path;
-  }, [bands, enabled]);
+  const color = enabled ? "var(--vinyl-accent)" : "var(--vinyl-text-muted)";
 
   return (
-    <div className="relative h-20 bg-vinyl-border/20 rounded-lg overflow-hidden">
+    <div className="relative h-24 bg-vinyl-border/20 rounded-lg overflow-hidden">
       <svg
         viewBox="0 0 100 100"
         preserveAspectRatio="none"
         className="w-full h-full"
       >
+        {GRID_FREQUENCIES.map((frequency) => (
+          <line
+            key={frequency}
+            x1={frequencyToX(frequency)}
+            y1="0"
+            x2={frequencyToX(frequency)}
+            y2="100"
+            stroke="var(--vinyl-border)"
+            strokeWidth="0.5"
+            vectorEffect="non-scaling-stroke"
+          />
+        ))}
         <line
-          x1="10"
+          x1="0"
           y1="50"
-          x2="90"
+          x2="100"
           y2="50"
           stroke="var(--vinyl-border)"
-          strokeWidth="0.5"
+          strokeWidth="1"
           strokeDasharray="2 2"
+          vectorEffect="non-scaling-stroke"
         />
         <path
-          d={svgPath}
+          d={path}
           fill="none"
-          stroke={enabled ? "var(--vinyl-accent)" : "var(--vinyl-text-muted)"}
+          stroke={color}
           strokeWidth="2"
           strokeLinecap="round"
           strokeLinejoin="round"
-          className="transition-all duration-200"
+          vectorEffect="non-scaling-stroke"
         />
-        {bands.map((band, index) => {
-          const x = 10 + (index / (bands.length - 1)) * 80;
-          const normalizedGain = enabled ? (band.gain + 12) / 24 : 0.5;
-          const y = 90 - normalizedGain * 80;
-          return (
-            <circle
-              key={band.frequency}
-              cx={x}
-              cy={y}
-              r="2.5"
-              fill={enabled ? "var(--vinyl-accent)" : "var(--vinyl-text-muted)"}
-              className="transition-all duration-200"
-            />
-          );
-        })}
+        {markers.map((marker) => (
+          <line
+            key={marker.id}
+            x1={marker.x}
+            y1={marker.y}
+            x2={marker.x}
+            y2={marker.y}
+            stroke={color}
+            strokeWidth="6"
+            strokeLinecap="round"
+            vectorEffect="non-scaling-stroke"
+          />
+        ))}
       </svg>
-      <div className="absolute bottom-1 left-0 right-0 flex justify-between px-3 text-[9px] text-vinyl-text-muted">
-        <span>32Hz</span>
-        <span>16kHz</span>
+      <div className="absolute top-1 left-1.5 text-[9px] text-vinyl-text-muted">
+        +{range}dB
+      </div>
+      <div className="absolute bottom-1 left-1.5 right-1.5 flex justify-between text-[9px] text-vinyl-text-muted">
+        <span>-{range}dB</span>
+        {GRID_FREQUENCIES.map((frequency) => (
+          <span key={frequency}>{formatFrequency(frequency)}</span>
+        ))}
+        <span>20K</span>
       </div>
     </div>
   );
 }
 
+// Number input that commits on blur or Enter, so typing "500" doesn't pass
+// through an out of range "5" that gets clamped
+function NumberField({
+  label,
+  value,
+  min,
+  max,
+  step,
+  disabled,
+  onChange,
+}: {
+  label: string;
+  value: number;
+  min: number;
+  max: number;
+  step: number;
+  disabled?: boolean;
+  onChange: (value: number) => void;
+}) {
+  const [draft, setDraft] = useState<string | null>(null);
+  const display = String(Number(value.toFixed(2)));
+
+  const commit = () => {
+    const parsed = parseFloat(draft ?? "");
+    if (Number.isFinite(parsed) && parsed !== value) onChange(parsed);
+    setDraft(null);
+  };
+
+  return (
+    <label className="flex flex-col gap-0.5 min-w-0">
+      <span className="text-[10px] text-vinyl-text-muted">{label}</span>
+      <input
+        type="number"
+        value={draft ?? display}
+        min={min}
+        max={max}
+        step={step}
+        disabled={disabled}
+        onChange={(e) => setDraft(e.target.value)}
+        onBlur={commit}
+        onKeyDown={(e) => e.key === "Enter" && e.currentTarget.blur()}
+        className="w-full px-2 py-1 text-xs bg-vinyl-border/50 rounded text-vinyl-text disabled:opacity-40"
+      />
+    </label>
+  );
+}
+
 export function Equalizer({
-  bands,
+  filters,
+  preamp,
   enabled,
   currentPreset,
+  userPresets,
   isConnected,
-  onBandChange,
+  onFilterChange,
+  onAddFilter,
+  onRemoveFilter,
+  onPreampChange,
   onPresetChange,
+  onSavePreset,
+  onDeletePreset,
+  onImportProfile,
   onReset,
   onToggleEnabled,
 }: EqualizerProps) {
   const [showPresets, setShowPresets] = useState(false);
   const [showCurve, setShowCurve] = useState(true);
+  const [presetName, setPresetName] = useState<string | null>(null);
   const presetsRef = useRef<HTMLDivElement>(null);
+  const importInputRef = useRef<HTMLInputElement>(null);
 
   useEffect(() => {
     const handleClickOutside = (e: MouseEvent) => {
@@ -132,6 +249,25 @@ export function Equalizer({
     return () => document.removeEventListener("mousedown", handleClickOutside);
   }, []);
 
+  const handleSavePreset = () => {
+    if (presetName !== null && onSavePreset(presetName)) {
+      setPresetName(null);
+    }
+  };
+
+  const handleImportFile = (e: React.ChangeEvent<HTMLInputElement>) => {
+    const file = e.target.files?.[0];
+    if (file) onImportProfile(file);
+    e.target.value = "";
+  };
+
+  const presetButtonClass = (name: string) =>
+    `flex-1 px-4 py-2.5 text-sm text-left transition-colors ${
+      currentPreset === name
+        ? "bg-vinyl-accent text-vinyl-bg"
+        : "text-vinyl-text hover:bg-vinyl-border"
+    }`;
+
   return (
     <div className="space-y-4">
       {/* Status message */}
@@ -168,6 +304,31 @@ export function Equalizer({
           >
             <RotateCcw className="w-4 h-4" />
           </button>
+
+          {/* Save current settings as a preset */}
+          <button
+            onClick={() => setPresetName(presetName === null ? "" : null)}
+            className="p-2 rounded-full text-vinyl-text-muted hover:text-vinyl-text hover:bg-vinyl-border transition-colors"
+            {...tooltipProps("Save as Preset")}
+          >
+            <Save className="w-4 h-4" />
+          </button>
+
+          {/* AutoEq / EqualizerAPO import */}
+          <button
+            onClick={() => importInputRef.current?.click()}
+            className="p-2 rounded-full text-vinyl-text-muted hover:text-vinyl-text hover:bg-vinyl-border transition-colors"
+            {...tooltipProps("Import AutoEq ParametricEQ.txt")}
+          >
+            <FileUp className="w-4 h-4" />
+          </button>
+          <input
+            ref={importInputRef}
+            type="file"
+            accept=".txt,text/plain"
+            onChange={handleImportFile}
+            className="hidden"
+          />
         </div>
 
         {/* Presets dropdown */}
@@ -176,7 +337,9 @@ export function Equalizer({
             onClick={() => setShowPresets(!showPresets)}
             className="flex items-center gap-2 px-3 py-2 rounded-lg text-sm bg-vinyl-border text-vinyl-text hover:bg-vinyl-border/70 transition-colors"
           >
-            <span>{currentPreset || "Presets"}</span>
+            <span className="max-w-[120px] truncate">
+              {currentPreset || "Presets"}
+            </span>
             <ChevronDown
               className={`w-4 h-4 transition-transform ${
                 showPresets ? "rotate-180" : ""
@@ -185,7 +348,30 @@ export function Equalizer({
           </button>
 
           {showPresets && (
-            <div className="absolute right-0 top-full mt-1 bg-vinyl-surface border border-vinyl-border rounded-lg shadow-xl overflow-hidden z-50 min-w-[140px] max-h-[250px] overflow-y-auto">
+            <div className="absolute right-0 top-full mt-1 bg-vinyl-surface border border-vinyl-border rounded-lg shadow-xl overflow-hidden z-50 min-w-[160px] max-h-[250px] overflow-y-auto">
+              {userPresets.map((preset) => (
+                <div key={preset.name} className="flex items-center">
+                  <button
+                    onClick={() => {
+                      onPresetChange(preset);
+                      setShowPresets(false);
+                    }}
+                    className={presetButtonClass(preset.name)}
+                  >
+                    {preset.name}
+                  </button>
+                  <button
+                    onClick={() => onDeletePreset(preset.name)}
+                    className="p-2.5 text-vinyl-text-muted hover:text-vinyl-text"
+                    {...tooltipProps("Delete Preset")}
+                  >
+                    <X className="w-3.5 h-3.5" />
+                  </button>
+                </div>
+              ))}
+              {userPresets.length > 0 && (
+                <div className="border-t border-vinyl-border" />
+              )}
               {EQUALIZER_PRESETS.map((preset) => (
                 <button
                   key={preset.name}
@@ -193,11 +379,7 @@ export function Equalizer({
                     onPresetChange(preset);
                     setShowPresets(false);
                   }}
-                  className={`block w-full px-4 py-2.5 text-sm text-left transition-colors ${
-                    currentPreset === preset.name
-                      ? "bg-vinyl-accent text-vinyl-bg"
-                      : "text-vinyl-text hover:bg-vinyl-border"
-                  }`}
+                  className={`block w-full ${presetButtonClass(preset.name)}`}
                 >
                   {preset.name}
                 </button>
@@ -207,6 +389,34 @@ export function Equalizer({
         </div>
       </div>
 
+      {/* Preset name input */}
+      {presetName !== null && (
+        <form
+          onSubmit={(e) => {
+            e.preventDefault();
+            handleSavePreset();
+          }}
+          className="flex items-center gap-2"
+        >
+          <input
+            type="text"
+            value={presetName}
+            onChange={(e) => setPresetName(e.target.value)}
+            onKeyDown={(e) => e.key === "Escape" && setPresetName(null)}
+            placeholder="Preset name"
+            autoFocus
+            className="flex-1 px-3 py-2 text-sm bg-vinyl-border/50 rounded-lg text-vinyl-text placeholder:text-vinyl-text-muted"
+          />
+          <button
+            type="submit"
+            disabled={!presetName.trim()}
+            className="px-3 py-2 rounded-lg text-sm bg-vinyl-accent text-vinyl-bg disabled:opacity-50"
+          >
+            Save
+          </button>
+        </form>
+      )}
+
       {/* Frequency response curve toggle */}
       <button
         onClick={() => setShowCurve(!showCurve)}
@@ -221,57 +431,105 @@ export function Equalizer({
       </button>
 
       {/* Frequency response visualization */}
-      {showCurve && <FrequencyResponseCurve bands={bands} enabled={enabled} />}
+      {showCurve && (
+        <FrequencyResponseCurve
+          filters={filters}
+          preamp={preamp}
+          enabled={enabled}
+        />
+      )}
 
-      {/* Frequency bands */}
-      <div className="flex justify-between items-end gap-1">
-        {bands.map((band, index) => (
+      {/* Preamp */}
+      <div className="flex items-center gap-3">
+        <span className="text-xs text-vinyl-text-muted w-14">Preamp</span>
+        <input
+          type="range"
+          min={EQ_LIMITS.preamp.min}
+          max={EQ_LIMITS.preamp.max}
+          step="0.1"
+          value={preamp}
+          onChange={(e) => onPreampChange(parseFloat(e.target.value))}
+          disabled={!enabled}
+          className="flex-1 accent-vinyl-accent"
+        />
+        <span className="text-xs text-vinyl-text-muted w-14 text-right">
+          {preamp > 0 ? "+" : ""}
+          {preamp.toFixed(1)}dB
+        </span>
+      </div>
+
+      {/* Filters */}
+      <div className="space-y-2">
+        {filters.map((filter, index) => (
           <div
-            key={band.frequency}
-            className="flex flex-col items-center gap-1 flex-1"
+            key={filter.id}
+            className="grid grid-cols-[1.3fr_1fr_1fr_1fr_auto] items-end gap-1.5"
           >
-            {/* Gain value */}
-            <span className="text-[10px] text-vinyl-text-muted text-center">
-              {band.gain > 0 ? "+" : ""}
-              {band.gain}
-            </span>
-
-            {/* Vertical slider */}
-            <div className="h-28 flex items-center justify-center">
-              <input
-                type="range"
-                min="-12"
-                max="12"
-                step="1"
-                value={band.gain}
+            <label className="flex flex-col gap-0.5 min-w-0">
+              <span className="text-[10px] text-vinyl-text-muted">
+                Filter {index + 1}
+              </span>
+              <select
+                value={filter.type}
                 onChange={(e) =>
-                  onBandChange(index, parseFloat(e.target.value))
+                  onFilterChange(index, {
+                    type: e.target.value as EqFilterType,
+                  })
                 }
                 disabled={!enabled}
-                className="eq-slider-vertical-small"
-                style={
-                  {
-                    "--eq-value": `${((band.gain + 12) / 24) * 100}%`,
-                  } as React.CSSProperties
-                }
-                {...tooltipProps(`${band.frequency}Hz: ${band.gain}dB`)}
-              />
-            </div>
-
-            {/* Frequency label */}
-            <span className="text-[10px] text-vinyl-text-muted">
-              {band.label}
-            </span>
+                className="w-full px-1.5 py-1 text-xs bg-vinyl-border/50 rounded text-vinyl-text disabled:opacity-40"
+              >
+                {EQ_FILTER_TYPES.map((type) => (
+                  <option key={type.key} value={type.key}>
+                    {type.label}
+                  </option>
+                ))}
+              </select>
+            </label>
+            <NumberField
+              label="Hz"
+              value={filter.frequency}
+              {...EQ_LIMITS.frequency}
+              step={1}
+              disabled={!enabled}
+              onChange={(frequency) => onFilterChange(index, { frequency })}
+            />
+            <NumberField
+              label="dB"
+              value={filter.gain}
+              {...EQ_LIMITS.gain}
+              step={0.1}
+              disabled={!enabled || !usesGain(filter.type)}
+              onChange={(gain) => onFilterChange(index, { gain })}
+            />
+            <NumberField
+              label="Q"
+              value={filter.q}
+              {...EQ_LIMITS.q}
+              step={0.01}
+              disabled={!enabled || !usesQ(filter.type)}
+              onChange={(q) => onFilterChange(index, { q })}
+            />
+            <button
+              onClick={() => onRemoveFilter(index)}
+              disabled={!enabled}
+              className="p-1.5 rounded-full text-vinyl-text-muted hover:text-vinyl-text hover:bg-vinyl-border transition-colors disabled:opacity-40"
+              {...tooltipProps("Remove Filter")}
+            >
+              <X className="w-3.5 h-3.5" />
+            </button>
           </div>
         ))}
       </div>
 
-      {/* dB scale reference */}
-      <div className="flex justify-between text-[10px] text-vinyl-text-muted px-1">
-        <span>-12dB</span>
-        <span>0dB</span>
-        <span>+12dB</span>
-      </div>
+      <button
+        onClick={onAddFilter}
+        disabled={!enabled}
+        className="flex items-center gap-1 px-3 py-1.5 text-sm bg-vinyl-border/50 hover:bg-vinyl-border rounded-lg transition-colors disabled:opacity-50"
+      >
+        <Plus className="w-4 h-4" />
+        Add Filter
+      </button>
     </div>
   );
 }
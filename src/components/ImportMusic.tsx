@@ -11,6 +11,7 @@ import {
   Unlink2,
   Info,
   Lightbulb,
+  KeyRound,
 } from "lucide-react";
 import { isDesktop as checkIsDesktop } from "../lib/platform";
 
@@ -33,6 +34,11 @@ interface ImportMusicProps {
   onCreatePlaylistsFromFolders?: (
     folderSongs: Map<string, string[]>,
   ) => Promise<number>;
+  // Web: pick a folder the browser remembers across reloads
+  onPickFolderHandle?: () => Promise<File[] | null>;
+  // Web: remembered folders waiting for the user to confirm access again
+  pendingFolderCount?: number;
+  onRestoreFolderAccess?: () => Promise<number>;
   isImporting: boolean;
   importProgress: { current: number; total: number; skipped?: number } | null;
   connectedCount?: number;
@@ -40,6 +46,12 @@ interface ImportMusicProps {
   isDesktop?: boolean;
 }
 
+function toFileList(files: File[]): FileList {
+  const transfer = new DataTransfer();
+  files.forEach((f) => transfer.items.add(f));
+  return transfer.files;
+}
+
 interface ImportResult {
   imported: number;
   skipped: number;
@@ -51,6 +63,9 @@ export function ImportMusic({
   onConnectFolder,
   onPickAndImportFolder,
   onCreatePlaylistsFromFolders,
+  onPickFolderHandle,
+  pendingFolderCount = 0,
+  onRestoreFolderAccess,
   isImporting,
   importProgress,
   connectedCount = 0,
@@ -67,6 +82,7 @@ export function ImportMusic({
   } | null>(null);
   const [showReconnectDialog, setShowReconnectDialog] = useState(false);
   const [reconnectDismissed, setReconnectDismissed] = useState(false);
+  const [isRestoringAccess, setIsRestoringAccess] = useState(false);
   const fileInputRef = useRef<HTMLInputElement>(null);
   const folderInputRef = useRef<HTMLInputElement>(null);
   const reconnectInputRef = useRef<HTMLInputElement>(null);
@@ -115,9 +131,8 @@ export function ImportMusic({
   }, [isOpen]);
 
   // Handle reconnecting folder
-  const handleReconnect = (e: React.ChangeEvent<HTMLInputElement>) => {
-    const files = e.target.files;
-    if (files && files.length > 0 && onConnectFolder) {
+  const reconnectFiles = (files: FileList) => {
+    if (files.length > 0 && onConnectFolder) {
       const result = onConnectFolder(files);
       setConnectResult({
         connected: result.connected,
@@ -140,9 +155,34 @@ export function ImportMusic({
         });
       }
     }
+  };
+
+  const handleReconnect = (e: React.ChangeEvent<HTMLInputElement>) => {
+    if (e.target.files) reconnectFiles(e.target.files);
     e.target.value = "";
   };
 
+  // Picked through the File System Access API when available, so the folder
+  // is remembered; otherwise through the hidden webkitdirectory input
+  const pickReconnectFolder = async () => {
+    if (!onPickFolderHandle) {
+      reconnectInputRef.current?.click();
+      return;
+    }
+    const files = await onPickFolderHandle();
+    if (files) reconnectFiles(toFileList(files));
+  };
+
+  const handleRestoreAccess = async () => {
+    if (!onRestoreFolderAccess) return;
+    setIsRestoringAccess(true);
+    try {
+      await onRestoreFolderAccess();
+    } finally {
+      setIsRestoringAccess(false);
+    }
+  };
+
   const handleDismissReconnect = () => {
     setShowReconnectDialog(false);
     setReconnectDismissed(true);
@@ -153,9 +193,8 @@ export function ImportMusic({
     setImportResult(null);
   };
 
-  const handleFileSelect = async (e: React.ChangeEvent<HTMLInputElement>) => {
-    const files = e.target.files;
-    if (files && files.length > 0) {
+  const importSelectedFiles = async (files: FileList) => {
+    if (files.length > 0) {
       // Check if this is a folder import by looking at webkitRelativePath
       let folderName: string | undefined;
       if (files[0]?.webkitRelativePath) {
@@ -179,8 +218,13 @@ export function ImportMusic({
         setIsOpen(false);
       }
     }
+  };
+
+  const handleFileSelect = async (e: React.ChangeEvent<HTMLInputElement>) => {
+    const files = e.target.files;
     // Reset input
     e.target.value = "";
+    if (files) await importSelectedFiles(files);
   };
 
   const handleDrop = async (e: React.DragEvent) => {
@@ -271,15 +315,31 @@ export function ImportMusic({
           <div className="flex items-start gap-3">
             <Info className="w-5 h-5 text-vinyl-accent flex-shrink-0 mt-0.5" />
             <div className="text-sm text-vinyl-text-muted">
-              <p className="mb-2">
-                For your privacy and security, browsers don't allow apps to
-                access your files automatically after you close or refresh the
-                page.
-              </p>
-              <p>
-                To play your music, simply select your music folder again. Your
-                library, playlists, and preferences are all saved.
-              </p>
+              {pendingFolderCount > 0 ? (
+                <>
+                  <p className="mb-2">
+                    Your browser remembers your music folder
+                    {pendingFolderCount !== 1 ? "s" : ""}, but asks you to
+                    confirm access again after the page is closed.
+                  </p>
+                  <p>
+                    Restore access to pick up where you left off. Your library,
+                    playlists, and preferences are all saved.
+                  </p>
+                </>
+              ) : (
+                <>
+                  <p className="mb-2">
+                    For your privacy and security, browsers don't allow apps to
+                    access your files automatically after you close or refresh
+                    the page.
+                  </p>
+                  <p>
+                    To play your music, simply select your music folder again.
+                    Your library, playlists, and preferences are all saved.
+                  </p>
+                </>
+              )}
             </div>
           </div>
         </div>
@@ -301,9 +361,27 @@ export function ImportMusic({
 
         {/* Actions */}
         <div className="flex flex-col gap-2">
+          {pendingFolderCount > 0 && onRestoreFolderAccess && (
+            <button
+              onClick={handleRestoreAccess}
+              disabled={isRestoringAccess}
+              className="flex items-center justify-center gap-2 w-full py-3 bg-vinyl-accent text-vinyl-bg rounded-lg hover:bg-vinyl-accent-light transition-colors font-medium disabled:opacity-50"
+            >
+              {isRestoringAccess ? (
+                <Loader2 className="w-5 h-5 animate-spin" />
+              ) : (
+                <KeyRound className="w-5 h-5" />
+              )}
+              Restore Folder Access
+            </button>
+          )}
           <button
-            onClick={() => reconnectInputRef.current?.click()}
-            className="flex items-center justify-center gap-2 w-full py-3 bg-vinyl-accent text-vinyl-bg rounded-lg hover:bg-vinyl-accent-light transition-colors font-medium"
+            onClick={pickReconnectFolder}
+            className={
+              pendingFolderCount > 0 && onRestoreFolderAccess
+                ? "flex items-center justify-center gap-2 w-full py-3 bg-vinyl-border/50 text-vinyl-text rounded-lg hover:bg-vinyl-border transition-colors font-medium"
+                : "flex items-center justify-center gap-2 w-full py-3 bg-vinyl-accent text-vinyl-bg rounded-lg hover:bg-vinyl-accent-light transition-colors font-medium"
+            }
           >
             <FolderOpen className="w-5 h-5" />
             Select Music Folder
@@ -565,6 +643,10 @@ export function ImportMusic({
                         setIsOpen(false);
                       }
                     }
+                  } else if (onPickFolderHandle) {
+                    // Remembered web folder (File System Access API)
+                    const files = await onPickFolderHandle();
+                    if (files) await importSelectedFiles(toFileList(files));
                   } else {
                     // Use web folder picker
                     folderInputRef.current?.click();
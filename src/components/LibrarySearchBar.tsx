@@ -0,0 +1,50 @@
+import { Search, X, Loader2 } from "lucide-react";
+import { tooltipProps } from "./Tooltip";
+
+interface LibrarySearchBarProps {
+  value: string;
+  onChange: (value: string) => void;
+  isSearching?: boolean;
+}
+
+const SEARCH_HELP =
+  'Filter with artist:, album:, title:, genre:, path:, year:>2000, duration:<3m. Quote phrases ("pink floyd"), prefix with - to exclude';
+
+export function LibrarySearchBar({
+  value,
+  onChange,
+  isSearching = false,
+}: LibrarySearchBarProps) {
+  return (
+    <div className="relative w-full max-w-md" {...tooltipProps(SEARCH_HELP, "bottom")}>
+      {isSearching ? (
+        <Loader2 className="absolute left-3 top-1/2 -translate-y-1/2 w-4 h-4 text-vinyl-text-muted animate-spin" />
+      ) : (
+        <Search className="absolute left-3 top-1/2 -translate-y-1/2 w-4 h-4 text-vinyl-text-muted" />
+      )}
+      <input
+        type="text"
+        value={value}
+        onChange={(e) => onChange(e.target.value)}
+        onKeyDown={(e) => {
+          if (e.key === "Escape" && value) {
+            e.stopPropagation();
+            onChange("");
+          }
+        }}
+        placeholder="Search library... e.g. artist:radiohead year:>2000"
+        className="w-full pl-9 pr-8 py-2 bg-vinyl-surface border border-vinyl-border rounded-lg text-vinyl-text text-sm placeholder:text-vinyl-text-muted focus:outline-none focus:ring-2 focus:ring-vinyl-accent focus:border-transparent"
+        aria-label="Search library"
+      />
+      {value && (
+        <button
+          onClick={() => onChange("")}
+          className="absolute right-2 top-1/2 -translate-y-1/2 p-1 rounded text-vinyl-text-muted hover:text-vinyl-text"
+          aria-label="Clear search"
+        >
+          <X className="w-3 h-3" />
+        </button>
+      )}
+    </div>
+  );
+}
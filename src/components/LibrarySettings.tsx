@@ -12,10 +12,25 @@ import {
   X,
   Eye,
   EyeOff,
+  FolderSearch,
 } from "lucide-react";
 import { tooltipProps } from "./Tooltip";
 import { ConfirmDialog } from "./ConfirmDialog";
-import type { MusicFileInfo } from "../lib/platform";
+import { TranscodeCacheSettings } from "./TranscodeCacheSettings";
+import type { LibraryScanResult, MusicFileInfo } from "../lib/platform";
+
+// "12 new, 3 changed, 1 moved, 2 missing" for incremental scans
+function describeScanChanges(result: LibraryScanResult): string {
+  const parts = [
+    [result.files.length, "new"],
+    [result.changed?.length ?? 0, "changed"],
+    [result.moved?.length ?? 0, "moved"],
+    [result.removed?.length ?? 0, "missing"],
+  ]
+    .filter(([count]) => count)
+    .map(([count, label]) => `${count} ${label}`);
+  return parts.length > 0 ? parts.join(", ") : "no changes";
+}
 
 interface LibrarySettingsProps {
   folders: string[];
@@ -25,16 +40,7 @@ interface LibrarySettingsProps {
     total: number;
     currentFolder: string;
   } | null;
-  lastScanResult: {
-    files: MusicFileInfo[];
-    totalCount: number;
-    folderStats?: {
-      path: string;
-      count: number;
-      exists: boolean;
-    }[];
-    error?: string;
-  } | null;
+  lastScanResult: LibraryScanResult | null;
   error: string | null;
   isDesktop: boolean;
   isWatching: boolean;
@@ -45,6 +51,10 @@ interface LibrarySettingsProps {
   onScanAllFolders: () => Promise<MusicFileInfo[]>;
   onImportFiles: (files: MusicFileInfo[]) => Promise<void>;
   onClearError: () => void;
+  // Result of the last check that every song's file still exists
+  libraryHealth: { checkedAt: number; missing: number } | null;
+  onCheckHealth: () => Promise<number | null>;
+  onRelinkMissing: () => Promise<void>;
 }
 
 export function LibrarySettings({
@@ -62,8 +72,12 @@ export function LibrarySettings({
   onScanAllFolders,
   onImportFiles,
   onClearError,
+  libraryHealth,
+  onCheckHealth,
+  onRelinkMissing,
 }: LibrarySettingsProps) {
   const [removingFolder, setRemovingFolder] = useState<string | null>(null);
+  const [isCheckingHealth, setIsCheckingHealth] = useState(false);
   const [importedCount, setImportedCount] = useState<number | null>(null);
   const [isImporting, setIsImporting] = useState(false);
 
@@ -103,6 +117,12 @@ export function LibrarySettings({
     }
   };
 
+  const handleCheckHealth = async () => {
+    setIsCheckingHealth(true);
+    await onCheckHealth();
+    setIsCheckingHealth(false);
+  };
+
   // Get folder name from path
   const getFolderName = (folderPath: string) => {
     const parts = folderPath.split(/[/\\]/);
@@ -242,9 +262,14 @@ export function LibrarySettings({
             </div>
             <div className="h-2 bg-vinyl-border rounded-full overflow-hidden">
               <div
-                className="h-full bg-vinyl-accent transition-all duration-300"
+                className={`h-full bg-vinyl-accent transition-all duration-300 ${
+                  scanProgress.total > 0 ? "" : "animate-pulse"
+                }`}
                 style={{
-                  width: `${(scanProgress.current / scanProgress.total) * 100}%`,
+                  width:
+                    scanProgress.total > 0
+                      ? `${(scanProgress.current / scanProgress.total) * 100}%`
+                      : "100%",
                 }}
               />
             </div>
@@ -348,8 +373,68 @@ export function LibrarySettings({
           <div className="text-xs text-vinyl-text-muted p-2 bg-vinyl-border/20 rounded">
             Last scan found {lastScanResult.totalCount} total songs across{" "}
             {lastScanResult.folderStats?.length || 0} folders
+            {lastScanResult.unchanged !== undefined && (
+              <>
+                {" "}
+                ({describeScanChanges(lastScanResult)})
+              </>
+            )}
+          </div>
+        )}
+
+        {/* Missing Files */}
+        {libraryHealth && totalSongsInLibrary > 0 && (
+          <div
+            className={`flex items-center justify-between gap-3 p-3 rounded-lg border ${
+              libraryHealth.missing > 0
+                ? "bg-red-500/10 border-red-500/30"
+                : "bg-vinyl-border/20 border-transparent"
+            }`}
+          >
+            <div className="flex items-center gap-2 text-sm min-w-0">
+              {libraryHealth.missing > 0 ? (
+                <AlertCircle className="w-4 h-4 flex-shrink-0 text-red-400" />
+              ) : (
+                <CheckCircle className="w-4 h-4 flex-shrink-0 text-green-400" />
+              )}
+              <span
+                className={
+                  libraryHealth.missing > 0
+                    ? "text-red-400"
+                    : "text-vinyl-text-muted"
+                }
+              >
+                {libraryHealth.missing > 0
+                  ? `${libraryHealth.missing} song file${libraryHealth.missing === 1 ? "" : "s"} not found`
+                  : "All song files found"}
+              </span>
+            </div>
+            <div className="flex items-center gap-2 flex-shrink-0">
+              <button
+                onClick={handleCheckHealth}
+                disabled={isCheckingHealth}
+                className="p-2 rounded-lg text-vinyl-text-muted hover:text-vinyl-text hover:bg-vinyl-border/50 transition-colors disabled:opacity-50"
+                {...tooltipProps("Check again")}
+              >
+                <RefreshCw
+                  className={`w-4 h-4 ${isCheckingHealth ? "animate-spin" : ""}`}
+                />
+              </button>
+              {libraryHealth.missing > 0 && (
+                <button
+                  onClick={onRelinkMissing}
+                  className="flex items-center gap-2 px-3 py-1.5 bg-vinyl-border/50 hover:bg-vinyl-border text-vinyl-text rounded-lg text-sm transition-colors"
+                  {...tooltipProps("Search a folder for the missing files")}
+                >
+                  <FolderSearch className="w-4 h-4" />
+                  Relink...
+                </button>
+              )}
+            </div>
           </div>
         )}
+
+        <TranscodeCacheSettings />
       </div>
 
       {/* Remove Folder Confirmation */}
@@ -0,0 +1,47 @@
+import { ArrowDownNarrowWide, ArrowUpNarrowWide } from "lucide-react";
+import type { SongSortKey, SortDirection } from "../types";
+import { SONG_SORT_OPTIONS } from "../lib/songSort";
+import { tooltipProps } from "./Tooltip";
+
+interface LibrarySortMenuProps {
+  sortKey: SongSortKey;
+  direction: SortDirection;
+  onSortKeyChange: (key: SongSortKey) => void;
+  onDirectionChange: (direction: SortDirection) => void;
+}
+
+export function LibrarySortMenu({
+  sortKey,
+  direction,
+  onSortKeyChange,
+  onDirectionChange,
+}: LibrarySortMenuProps) {
+  return (
+    <div className="flex items-center gap-1">
+      <select
+        value={sortKey}
+        onChange={(e) => onSortKeyChange(e.target.value as SongSortKey)}
+        className="px-3 py-2 bg-vinyl-surface border border-vinyl-border text-vinyl-text rounded-lg text-sm cursor-pointer focus:outline-none focus:ring-2 focus:ring-vinyl-accent"
+        aria-label="Sort library by"
+      >
+        {SONG_SORT_OPTIONS.map((option) => (
+          <option key={option.key} value={option.key}>
+            {option.label}
+          </option>
+        ))}
+      </select>
+      <button
+        onClick={() => onDirectionChange(direction === "asc" ? "desc" : "asc")}
+        className="p-2 rounded-lg border border-vinyl-border bg-vinyl-surface text-vinyl-text-muted hover:text-vinyl-accent transition-colors"
+        aria-label={direction === "asc" ? "Sort ascending" : "Sort descending"}
+        {...tooltipProps(direction === "asc" ? "Ascending" : "Descending")}
+      >
+        {direction === "asc" ? (
+          <ArrowUpNarrowWide className="w-4 h-4" />
+        ) : (
+          <ArrowDownNarrowWide className="w-4 h-4" />
+        )}
+      </button>
+    </div>
+  );
+}
@@ -1,9 +1,19 @@
 import { NavLink } from "react-router-dom";
-import { ListMusic, Music, Settings } from "lucide-react";
+import {
+  BarChart3,
+  Disc,
+  ListMusic,
+  Music,
+  Settings,
+  Users,
+} from "lucide-react";
 
 const navItems = [
   { to: "/library", icon: Music, label: "Library" },
+  { to: "/artists", icon: Users, label: "Artists" },
+  { to: "/albums", icon: Disc, label: "Albums" },
   { to: "/playlists", icon: ListMusic, label: "Playlists" },
+  { to: "/stats", icon: BarChart3, label: "Stats" },
   { to: "/settings", icon: Settings, label: "Settings" },
 ];
 
@@ -16,7 +26,7 @@ export function MobileNav() {
             key={item.to}
             to={item.to}
             className={({ isActive }) =>
-              `flex flex-col items-center gap-1 px-4 py-2 rounded-lg transition-colors ${
+              `flex flex-col items-center gap-1 px-3 py-2 rounded-lg transition-colors ${
                 isActive ? "text-vinyl-accent" : "text-vinyl-text-muted"
               }`
             }
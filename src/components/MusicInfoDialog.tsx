@@ -1,4 +1,20 @@
-import { Music, Clock, Disc, User, Folder, FileAudio, Calendar, Hash } from "lucide-react";
+import {
+  Music,
+  Clock,
+  Disc,
+  User,
+  Users,
+  Folder,
+  FileAudio,
+  Calendar,
+  Hash,
+  ListOrdered,
+  Tag,
+  PenLine,
+  Activity,
+  AudioWaveform,
+  Gauge,
+} from "lucide-react";
 import type { Song } from "../types";
 import {
   Dialog,
@@ -6,11 +22,14 @@ import {
   DialogHeader,
   DialogTitle,
 } from "@/components/ui/dialog";
+import { Button } from "@/components/ui/button";
+import { ArtworkImage } from "./ArtworkImage";
 
 interface MusicInfoDialogProps {
   isOpen: boolean;
   onClose: () => void;
   song: Song | null;
+  onEditTags?: () => void;
 }
 
 function formatDuration(seconds: number): string {
@@ -27,6 +46,30 @@ function formatFileSize(bytes?: number): string {
   return `${(bytes / (1024 * 1024)).toFixed(2)} MB`;
 }
 
+function formatTrack(song: Song): string | null {
+  if (!song.trackNumber) return null;
+  return song.discNumber
+    ? `${song.trackNumber} (Disc ${song.discNumber})`
+    : `${song.trackNumber}`;
+}
+
+function formatChannels(channels: number): string {
+  if (channels === 1) return "Mono";
+  if (channels === 2) return "Stereo";
+  return `${channels} ch`;
+}
+
+// e.g. "FLAC • 96 kHz • 24-bit • Stereo"
+function formatAudioFormat(song: Song): string | null {
+  const parts = [
+    song.codec,
+    song.sampleRate && `${song.sampleRate / 1000} kHz`,
+    song.bitDepth && `${song.bitDepth}-bit`,
+    song.channels && formatChannels(song.channels),
+  ].filter(Boolean);
+  return parts.length > 0 ? parts.join(" • ") : null;
+}
+
 function formatDate(timestamp: number): string {
   return new Date(timestamp).toLocaleDateString(undefined, {
     year: "numeric",
@@ -39,10 +82,11 @@ export function MusicInfoDialog({
   isOpen,
   onClose,
   song,
+  onEditTags,
 }: MusicInfoDialogProps) {
   return (
     <Dialog open={isOpen} onOpenChange={(open) => !open && onClose()}>
-      <DialogContent className="max-w-md p-0 gap-0">
+      <DialogContent className="max-w-md p-0 gap-0 max-h-[90vh] overflow-y-auto">
         {/* Header */}
         <DialogHeader className="px-6 py-4 border-b border-vinyl-border">
           <div className="flex items-center gap-3">
@@ -59,6 +103,17 @@ export function MusicInfoDialog({
                 to toggle
               </p>
             </div>
+            {song && onEditTags && (
+              <Button
+                variant="outline"
+                size="sm"
+                onClick={onEditTags}
+                className="ml-auto mr-6"
+              >
+                <PenLine />
+                Edit Tags
+              </Button>
+            )}
           </div>
         </DialogHeader>
 
@@ -68,17 +123,16 @@ export function MusicInfoDialog({
             {/* Album Art */}
             <div className="flex items-center gap-4 mb-4">
               <div className="w-20 h-20 rounded-lg overflow-hidden flex-shrink-0 bg-vinyl-border">
-                {song.coverArt ? (
-                  <img
-                    src={song.coverArt}
-                    alt={song.album}
-                    className="w-full h-full object-cover"
-                  />
-                ) : (
-                  <div className="w-full h-full flex items-center justify-center">
-                    <Music className="w-8 h-8 text-vinyl-text-muted" />
-                  </div>
-                )}
+                <ArtworkImage
+                  artworkId={song.artworkId}
+                  alt={song.album}
+                  className="w-full h-full object-cover"
+                  fallback={
+                    <div className="w-full h-full flex items-center justify-center">
+                      <Music className="w-8 h-8 text-vinyl-text-muted" />
+                    </div>
+                  }
+                />
               </div>
               <div className="min-w-0 flex-1">
                 <h3 className="font-semibold text-vinyl-text truncate text-lg">
@@ -94,7 +148,31 @@ export function MusicInfoDialog({
             <div className="space-y-2 overflow-hidden">
               <InfoRow icon={<User className="w-4 h-4" />} label="Artist" value={song.artist} />
               <InfoRow icon={<Disc className="w-4 h-4" />} label="Album" value={song.album} />
+              {song.albumArtist && song.albumArtist !== song.artist && (
+                <InfoRow icon={<Users className="w-4 h-4" />} label="Album Artist" value={song.albumArtist} />
+              )}
+              {formatTrack(song) && (
+                <InfoRow icon={<ListOrdered className="w-4 h-4" />} label="Track" value={formatTrack(song)!} />
+              )}
+              {song.genre && (
+                <InfoRow icon={<Tag className="w-4 h-4" />} label="Genre" value={song.genre} />
+              )}
+              {song.year && (
+                <InfoRow icon={<Calendar className="w-4 h-4" />} label="Year" value={String(song.year)} />
+              )}
+              {song.composer && (
+                <InfoRow icon={<PenLine className="w-4 h-4" />} label="Composer" value={song.composer} />
+              )}
+              {song.bpm && (
+                <InfoRow icon={<Activity className="w-4 h-4" />} label="BPM" value={String(song.bpm)} />
+              )}
               <InfoRow icon={<Clock className="w-4 h-4" />} label="Duration" value={formatDuration(song.duration)} />
+              {formatAudioFormat(song) && (
+                <InfoRow icon={<AudioWaveform className="w-4 h-4" />} label="Format" value={formatAudioFormat(song)!} />
+              )}
+              {song.bitrate && (
+                <InfoRow icon={<Gauge className="w-4 h-4" />} label="Bitrate" value={`${song.bitrate} kbps`} />
+              )}
               {song.fileName && (
                 <InfoRow icon={<FileAudio className="w-4 h-4" />} label="File" value={song.fileName} />
               )}
@@ -142,7 +220,7 @@ function InfoRow({
   return (
     <div className="flex items-center gap-3 py-2 px-3 rounded-lg hover:bg-vinyl-border/30 transition-colors min-w-0">
       <span className="text-vinyl-text-muted flex-shrink-0">{icon}</span>
-      <span className="text-vinyl-text-muted text-sm flex-shrink-0 w-16">{label}</span>
+      <span className="text-vinyl-text-muted text-sm flex-shrink-0 w-24">{label}</span>
       <span 
         className={`text-vinyl-text text-sm flex-1 text-right min-w-0 ${truncate ? 'truncate' : 'break-all'}`} 
         title={value}
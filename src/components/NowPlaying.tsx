@@ -1,5 +1,6 @@
 import { Music } from "lucide-react";
 import type { Song } from "../types";
+import { ArtworkImage } from "./ArtworkImage";
 
 interface NowPlayingProps {
   song: Song | undefined;
@@ -20,17 +21,16 @@ export function NowPlaying({ song, showAlbumArt = false }: NowPlayingProps) {
       {/* Album art (optional) */}
       {showAlbumArt && (
         <div className="w-16 h-16 rounded-lg overflow-hidden flex-shrink-0 bg-vinyl-border">
-          {song.coverArt ? (
-            <img
-              src={song.coverArt}
-              alt={song.album}
-              className="w-full h-full object-cover"
-            />
-          ) : (
-            <div className="w-full h-full flex items-center justify-center">
-              <Music className="w-8 h-8 text-vinyl-text-muted" />
-            </div>
-          )}
+          <ArtworkImage
+            artworkId={song.artworkId}
+            alt={song.album}
+            className="w-full h-full object-cover"
+            fallback={
+              <div className="w-full h-full flex items-center justify-center">
+                <Music className="w-8 h-8 text-vinyl-text-muted" />
+              </div>
+            }
+          />
         </div>
       )}
 
@@ -0,0 +1,247 @@
+import { useState } from "react";
+import { toast } from "sonner";
+import { UserCog, Trash2 } from "lucide-react";
+import type {
+  PlaybackProfile,
+  PlaybackProfileTarget,
+  Playlist,
+  Song,
+} from "../types";
+import type { PlaybackProfileInput } from "../hooks/usePlaybackProfiles";
+import {
+  PLAYBACK_PROFILE_TARGETS,
+  PROFILE_GAIN_LIMITS,
+  describeAdjustments,
+  getProfileKey,
+} from "../lib/playbackProfiles";
+import { getAlbumArtist } from "../lib/albums";
+import { SPEED_OPTIONS } from "./PlayerControls";
+import { tooltipProps } from "./Tooltip";
+
+const selectClass =
+  "px-3 py-1.5 bg-vinyl-border text-vinyl-text rounded text-sm border-0 cursor-pointer";
+
+interface PlaybackProfileSettingsProps {
+  profiles: PlaybackProfile[];
+  currentSong: Song | null;
+  currentPlaylist: Playlist | null;
+  outputDevice: string | null;
+  eqPresetNames: string[];
+  onSaveProfile: (profile: PlaybackProfileInput) => void;
+  onDeleteProfile: (id: string) => void;
+}
+
+// What a new profile for the playing track would be attached to, or why not
+function describeTarget(
+  target: PlaybackProfileTarget,
+  song: Song,
+  playlist: Playlist | null,
+  outputDevice: string | null,
+): string | null {
+  switch (target) {
+    case "song":
+      return song.title;
+    case "album":
+      return song.album === "Unknown Album"
+        ? null
+        : `${song.album} — ${getAlbumArtist(song)}`;
+    case "artist":
+      return song.artist === "Unknown Artist" ? null : getAlbumArtist(song);
+    case "playlist":
+      return playlist?.name ?? null;
+    case "output":
+      return outputDevice;
+  }
+}
+
+export function PlaybackProfileSettings({
+  profiles,
+  currentSong,
+  currentPlaylist,
+  outputDevice,
+  eqPresetNames,
+  onSaveProfile,
+  onDeleteProfile,
+}: PlaybackProfileSettingsProps) {
+  const [target, setTarget] = useState<PlaybackProfileTarget>("album");
+  const [eqPreset, setEqPreset] = useState("");
+  const [speed, setSpeed] = useState("");
+  const [gain, setGain] = useState("");
+
+  const label = currentSong
+    ? describeTarget(target, currentSong, currentPlaylist, outputDevice)
+    : null;
+  const key = currentSong
+    ? getProfileKey(target, {
+        song: currentSong,
+        playlistId: currentPlaylist?.id,
+        outputDevice,
+      })
+    : null;
+  const gainValue = gain.trim() === "" ? undefined : parseFloat(gain);
+  const hasSettings =
+    !!eqPreset || !!speed || (gainValue !== undefined && !isNaN(gainValue));
+
+  const handleSave = () => {
+    if (!key || !label) return;
+    onSaveProfile({
+      target,
+      key,
+      label,
+      eqPreset: eqPreset || undefined,
+      speed: speed ? parseFloat(speed) : undefined,
+      gain:
+        gainValue === undefined || isNaN(gainValue)
+          ? undefined
+          : Math.max(
+              PROFILE_GAIN_LIMITS.min,
+              Math.min(PROFILE_GAIN_LIMITS.max, gainValue),
+            ),
+    });
+    setEqPreset("");
+    setSpeed("");
+    setGain("");
+    toast.success(`Profile saved for "${label}"`, { duration: 2000 });
+  };
+
+  const targetLabel = (value: PlaybackProfileTarget) =>
+    PLAYBACK_PROFILE_TARGETS.find((t) => t.key === value)?.label ?? value;
+
+  return (
+    <section className="bg-vinyl-surface border border-vinyl-border rounded-lg overflow-hidden">
+      <div className="px-4 py-3 border-b border-vinyl-border bg-vinyl-border/20">
+        <h2 className="font-semibold text-vinyl-text flex items-center gap-2">
+          <UserCog className="w-5 h-5 text-vinyl-accent" />
+          Playback Profiles
+        </h2>
+      </div>
+      <div className="p-4 space-y-4">
+        <p className="text-sm text-vinyl-text-muted">
+          Give a song, album, artist, playlist or output device its own
+          equalizer preset, speed or volume. They apply when a matching track
+          starts and your own settings come back afterwards; a song's profile
+          wins over its album's, then playlist, artist and output device.
+        </p>
+
+        {/* New profile for what is playing */}
+        {currentSong ? (
+          <div className="space-y-3 p-3 bg-vinyl-border/20 rounded-lg">
+            <div className="flex items-center justify-between gap-4">
+              <div className="min-w-0">
+                <h3 className="text-vinyl-text font-medium">
+                  New Profile for Now Playing
+                </h3>
+                <p className="text-sm text-vinyl-text-muted truncate">
+                  {label ??
+                    (target === "output"
+                      ? "Output device name isn't available"
+                      : `No ${targetLabel(target).toLowerCase()} playing`)}
+                </p>
+              </div>
+              <select
+                value={target}
+                onChange={(e) =>
+                  setTarget(e.target.value as PlaybackProfileTarget)
+                }
+                className={selectClass}
+              >
+                {PLAYBACK_PROFILE_TARGETS.map((t) => (
+                  <option key={t.key} value={t.key}>
+                    {t.label}
+                  </option>
+                ))}
+              </select>
+            </div>
+
+            <div className="grid grid-cols-3 gap-2">
+              <label className="flex flex-col gap-1 text-xs text-vinyl-text-muted">
+                Equalizer
+                <select
+                  value={eqPreset}
+                  onChange={(e) => setEqPreset(e.target.value)}
+                  className={selectClass}
+                >
+                  <option value="">Unchanged</option>
+                  {eqPresetNames.map((name) => (
+                    <option key={name} value={name}>
+                      {name}
+                    </option>
+                  ))}
+                </select>
+              </label>
+              <label className="flex flex-col gap-1 text-xs text-vinyl-text-muted">
+                Speed
+                <select
+                  value={speed}
+                  onChange={(e) => setSpeed(e.target.value)}
+                  className={selectClass}
+                >
+                  <option value="">Unchanged</option>
+                  {SPEED_OPTIONS.map((s) => (
+                    <option key={s} value={s}>
+                      {s}x
+                    </option>
+                  ))}
+                </select>
+              </label>
+              <label className="flex flex-col gap-1 text-xs text-vinyl-text-muted">
+                Gain (dB)
+                <input
+                  type="number"
+                  value={gain}
+                  min={PROFILE_GAIN_LIMITS.min}
+                  max={PROFILE_GAIN_LIMITS.max}
+                  step="0.5"
+                  placeholder="0"
+                  onChange={(e) => setGain(e.target.value)}
+                  className="px-3 py-1.5 bg-vinyl-border text-vinyl-text rounded text-sm border-0"
+                />
+              </label>
+            </div>
+
+            <button
+              onClick={handleSave}
+              disabled={!key || !label || !hasSettings}
+              className="px-3 py-1.5 rounded text-sm bg-vinyl-accent text-vinyl-bg disabled:opacity-50"
+            >
+              Save Profile
+            </button>
+          </div>
+        ) : (
+          <p className="text-sm text-vinyl-text-muted">
+            Play a song to add a profile for it, its album, artist, playlist or
+            output device.
+          </p>
+        )}
+
+        {/* Saved profiles */}
+        {profiles.length > 0 && (
+          <ul className="divide-y divide-vinyl-border">
+            {profiles.map((profile) => (
+              <li
+                key={profile.id}
+                className="flex items-center justify-between gap-4 py-2"
+              >
+                <div className="min-w-0">
+                  <p className="text-sm text-vinyl-text truncate">
+                    {profile.label}
+                  </p>
+                  <p className="text-xs text-vinyl-text-muted">
+                    {targetLabel(profile.target)} · {describeAdjustments(profile)}
+                  </p>
+                </div>
+                <button
+                  onClick={() => onDeleteProfile(profile.id)}
+                  className="p-2 text-vinyl-text-muted hover:text-red-500 rounded-full hover:bg-vinyl-border transition-colors"
+                  {...tooltipProps("Delete Profile")}
+                >
+                  <Trash2 className="w-4 h-4" />
+                </button>
+              </li>
+            ))}
+          </ul>
+        )}
+      </div>
+    </section>
+  );
+}
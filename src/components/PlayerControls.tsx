@@ -11,8 +11,11 @@ import {
   Repeat1,
   Shuffle,
   Gauge,
+  Minus,
+  Plus,
 } from "lucide-react";
 import { formatDuration } from "../lib/audioMetadata";
+import { PITCH_LIMITS, formatSemitones } from "../lib/pitchShift";
 import type { PlayerState } from "../types";
 import { tooltipProps } from "./Tooltip";
 
@@ -24,6 +27,10 @@ interface PlayerControlsProps {
   repeat: PlayerState["repeat"];
   shuffle: boolean;
   speed: number;
+  pitch: number; // Semitones
+  preservePitch: boolean;
+  isPitchRemembered: boolean;
+  canTranspose: boolean;
   onTogglePlay: () => void;
   onNext: () => void;
   onPrevious: () => void;
@@ -32,10 +39,16 @@ interface PlayerControlsProps {
   onToggleRepeat: () => void;
   onToggleShuffle: () => void;
   onSpeedChange: (speed: number) => void;
+  onPitchChange: (semitones: number) => void;
+  onTogglePreservePitch: () => void;
+  onToggleRememberPitch: () => void;
   disabled: boolean;
 }
 
-const SPEED_OPTIONS = [0.5, 0.75, 1, 1.25, 1.5, 2];
+export const SPEED_OPTIONS = [0.5, 0.75, 1, 1.25, 1.5, 2];
+
+// Fine tempo slider for practising along
+const TEMPO_RANGE = { min: 0.5, max: 2, step: 0.05 };
 
 export function PlayerControls({
   isPlaying,
@@ -45,6 +58,10 @@ export function PlayerControls({
   repeat,
   shuffle,
   speed,
+  pitch,
+  preservePitch,
+  isPitchRemembered,
+  canTranspose,
   onTogglePlay,
   onNext,
   onPrevious,
@@ -53,6 +70,9 @@ export function PlayerControls({
   onToggleRepeat,
   onToggleShuffle,
   onSpeedChange,
+  onPitchChange,
+  onTogglePreservePitch,
+  onToggleRememberPitch,
   disabled,
 }: PlayerControlsProps) {
   const [showSpeedMenu, setShowSpeedMenu] = useState(false);
@@ -216,43 +236,124 @@ export function PlayerControls({
           <RepeatIcon className="w-5 h-5" />
         </button>
 
-        {/* Speed control - expandable */}
+        {/* Speed and pitch control - expandable */}
         <div ref={speedRef} className="relative flex items-center">
           <button
             onClick={() => setShowSpeedMenu(!showSpeedMenu)}
             disabled={disabled}
             className={`p-2 rounded-full transition-colors ${
-              speed !== 1
+              speed !== 1 || pitch !== 0
                 ? "text-vinyl-accent"
                 : "text-vinyl-text-muted hover:text-vinyl-text"
             } disabled:opacity-50`}
-            {...tooltipProps(`Speed: ${speed}x`)}
+            {...tooltipProps(
+              pitch !== 0
+                ? `Speed: ${speed}x, Pitch: ${formatSemitones(pitch)}`
+                : `Speed: ${speed}x`,
+            )}
           >
             <Gauge className="w-5 h-5" />
           </button>
 
           {/* Speed menu popup */}
           {showSpeedMenu && (
-            <div className="absolute bottom-full right-0 mb-2 bg-vinyl-surface border border-vinyl-border rounded-lg shadow-xl overflow-hidden animate-fade-in z-50">
+            <div className="absolute bottom-full right-0 mb-2 w-56 bg-vinyl-surface border border-vinyl-border rounded-lg shadow-xl overflow-hidden animate-fade-in z-50">
               <div className="px-3 py-2 text-xs text-vinyl-text-muted border-b border-vinyl-border">
                 Playback Speed
               </div>
-              {SPEED_OPTIONS.map((s) => (
-                <button
-                  key={s}
-                  onClick={() => {
-                    onSpeedChange(s);
-                    setShowSpeedMenu(false);
-                  }}
-                  className={`block w-full px-4 py-2 text-sm text-left transition-colors whitespace-nowrap ${
-                    speed === s
-                      ? "bg-vinyl-accent text-vinyl-bg"
-                      : "text-vinyl-text hover:bg-vinyl-border"
-                  }`}
-                >
-                  {s}x {s === 1 && "(Normal)"}
-                </button>
-              ))}
+              <div className="grid grid-cols-3 gap-1 p-2">
+                {SPEED_OPTIONS.map((s) => (
+                  <button
+                    key={s}
+                    onClick={() => onSpeedChange(s)}
+                    className={`px-2 py-1.5 rounded text-sm transition-colors ${
+                      speed === s
+                        ? "bg-vinyl-accent text-vinyl-bg"
+                        : "text-vinyl-text hover:bg-vinyl-border"
+                    }`}
+                  >
+                    {s}x
+                  </button>
+                ))}
+              </div>
+
+              {/* Fine tempo */}
+              <div className="px-3 py-2 border-t border-vinyl-border">
+                <div className="flex justify-between text-xs text-vinyl-text-muted mb-1">
+                  <span>Tempo</span>
+                  <span>{Math.round(speed * 100)}%</span>
+                </div>
+                <input
+                  type="range"
+                  min={TEMPO_RANGE.min}
+                  max={TEMPO_RANGE.max}
+                  step={TEMPO_RANGE.step}
+                  value={speed}
+                  onChange={(e) => onSpeedChange(parseFloat(e.target.value))}
+                  className="w-full accent-vinyl-accent cursor-pointer"
+                />
+              </div>
+
+              {/* Transpose */}
+              <div className="px-3 py-2 border-t border-vinyl-border">
+                <div className="text-xs text-vinyl-text-muted mb-1">
+                  Transpose
+                </div>
+                {canTranspose ? (
+                  <div className="flex items-center justify-between gap-2">
+                    <button
+                      onClick={() => onPitchChange(pitch - 1)}
+                      disabled={pitch <= PITCH_LIMITS.min}
+                      className="p-1 rounded text-vinyl-text hover:bg-vinyl-border disabled:opacity-50"
+                      {...tooltipProps("Down a Semitone")}
+                    >
+                      <Minus className="w-4 h-4" />
+                    </button>
+                    <button
+                      onClick={() => onPitchChange(0)}
+                      className={`text-sm tabular-nums ${
+                        pitch !== 0 ? "text-vinyl-accent" : "text-vinyl-text"
+                      }`}
+                      {...tooltipProps("Reset Pitch")}
+                    >
+                      {formatSemitones(pitch)}
+                    </button>
+                    <button
+                      onClick={() => onPitchChange(pitch + 1)}
+                      disabled={pitch >= PITCH_LIMITS.max}
+                      className="p-1 rounded text-vinyl-text hover:bg-vinyl-border disabled:opacity-50"
+                      {...tooltipProps("Up a Semitone")}
+                    >
+                      <Plus className="w-4 h-4" />
+                    </button>
+                  </div>
+                ) : (
+                  <p className="text-xs text-vinyl-text-muted">
+                    Not available in this browser
+                  </p>
+                )}
+              </div>
+
+              <div className="px-3 py-2 border-t border-vinyl-border space-y-2">
+                <label className="flex items-center justify-between gap-3 text-sm text-vinyl-text cursor-pointer whitespace-nowrap">
+                  Keep Pitch
+                  <input
+                    type="checkbox"
+                    checked={preservePitch}
+                    onChange={onTogglePreservePitch}
+                    className="accent-vinyl-accent cursor-pointer"
+                  />
+                </label>
+                <label className="flex items-center justify-between gap-3 text-sm text-vinyl-text cursor-pointer whitespace-nowrap">
+                  Remember for Song
+                  <input
+                    type="checkbox"
+                    checked={isPitchRemembered}
+                    onChange={onToggleRememberPitch}
+                    className="accent-vinyl-accent cursor-pointer"
+                  />
+                </label>
+              </div>
             </div>
           )}
         </div>
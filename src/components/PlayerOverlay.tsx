@@ -19,8 +19,11 @@ import {
 } from "lucide-react";
 import { formatDuration } from "../lib/audioMetadata";
 import type { Song, Playlist, PlaybackState } from "../types";
-import type { EqualizerBand, EqualizerPreset } from "../hooks/useEqualizer";
+import type { EqualizerPreset } from "../hooks/useEqualizer";
+import type { EqFilter } from "../lib/parametricEq";
 import type { VisualizerStyle } from "../hooks/useAudioVisualizer";
+import { useArtworkUrl } from "../hooks/useArtworkUrl";
+import { ArtworkImage } from "./ArtworkImage";
 import { VinylPlayer } from "./VinylPlayer";
 import { NowPlaying } from "./NowPlaying";
 import { PlayerControls } from "./PlayerControls";
@@ -42,6 +45,10 @@ interface PlayerOverlayProps {
   repeat: "none" | "one" | "all";
   shuffle: boolean;
   speed: number;
+  pitch: number;
+  preservePitch: boolean;
+  isPitchRemembered: boolean;
+  canTranspose: boolean;
   queueSongs: Song[];
   currentPlaylist: Playlist | null;
   showAlbumArt: boolean;
@@ -50,12 +57,20 @@ interface PlayerOverlayProps {
   autoExpand?: boolean;
   onAutoExpandHandled?: () => void;
   // Equalizer props
-  eqBands: EqualizerBand[];
+  eqFilters: EqFilter[];
+  eqPreamp: number;
   eqEnabled: boolean;
   eqPreset: string | null;
   eqConnected: boolean;
-  onEqBandChange: (index: number, gain: number) => void;
+  eqUserPresets: EqualizerPreset[];
+  onEqFilterChange: (index: number, patch: Partial<EqFilter>) => void;
+  onEqAddFilter: () => void;
+  onEqRemoveFilter: (index: number) => void;
+  onEqPreampChange: (gain: number) => void;
   onEqPresetChange: (preset: EqualizerPreset) => void;
+  onEqSavePreset: (name: string) => boolean;
+  onEqDeletePreset: (name: string) => void;
+  onEqImportProfile: (file: File) => void;
   onEqReset: () => void;
   onEqToggleEnabled: () => void;
   // Sleep timer props
@@ -85,6 +100,9 @@ interface PlayerOverlayProps {
   onToggleRepeat: () => void;
   onToggleShuffle: () => void;
   onSpeedChange: (speed: number) => void;
+  onPitchChange: (semitones: number) => void;
+  onTogglePreservePitch: () => void;
+  onToggleRememberPitch: () => void;
   onPlayFromQueue: (song: Song) => void;
   onStop: () => void;
   onDeleteFromQueue: (songId: string) => void;
@@ -142,15 +160,12 @@ function MiniPlayer({
           >
             {/* Album art */}
             <div className="w-10 h-10 rounded-lg bg-vinyl-border flex items-center justify-center flex-shrink-0 overflow-hidden">
-              {currentSong.coverArt ? (
-                <img
-                  src={currentSong.coverArt}
-                  alt={currentSong.title}
-                  className="w-full h-full object-cover"
-                />
-              ) : (
-                <Music className="w-5 h-5 text-vinyl-text-muted" />
-              )}
+              <ArtworkImage
+                artworkId={currentSong.artworkId}
+                alt={currentSong.title}
+                className="w-full h-full object-cover"
+                fallback={<Music className="w-5 h-5 text-vinyl-text-muted" />}
+              />
             </div>
 
             {/* Song info */}
@@ -251,16 +266,22 @@ function ExpandedPlayer({
   repeat,
   shuffle,
   speed,
+  pitch,
+  preservePitch,
+  isPitchRemembered,
+  canTranspose,
   queueSongs,
   currentPlaylist,
   showAlbumArt,
   showQueue,
   showEqualizer,
   isFavorite,
-  eqBands,
+  eqFilters,
+  eqPreamp,
   eqEnabled,
   eqPreset,
   eqConnected,
+  eqUserPresets,
   // Sleep timer
   sleepTimerActive,
   sleepTimerRemainingTime,
@@ -285,8 +306,14 @@ function ExpandedPlayer({
   onToggleEqualizer,
   onCloseEqualizer,
   onToggleFavorite,
-  onEqBandChange,
+  onEqFilterChange,
+  onEqAddFilter,
+  onEqRemoveFilter,
+  onEqPreampChange,
   onEqPresetChange,
+  onEqSavePreset,
+  onEqDeletePreset,
+  onEqImportProfile,
   onEqReset,
   onEqToggleEnabled,
   onTogglePlayPause,
@@ -297,6 +324,9 @@ function ExpandedPlayer({
   onToggleRepeat,
   onToggleShuffle,
   onSpeedChange,
+  onPitchChange,
+  onTogglePreservePitch,
+  onToggleRememberPitch,
   onPlayFromQueue,
   onStop,
   onDeleteFromQueue,
@@ -312,16 +342,22 @@ function ExpandedPlayer({
   repeat: "none" | "one" | "all";
   shuffle: boolean;
   speed: number;
+  pitch: number;
+  preservePitch: boolean;
+  isPitchRemembered: boolean;
+  canTranspose: boolean;
   queueSongs: Song[];
   currentPlaylist: Playlist | null;
   showAlbumArt: boolean;
   showQueue: boolean;
   showEqualizer: boolean;
   isFavorite: boolean;
-  eqBands: EqualizerBand[];
+  eqFilters: EqFilter[];
+  eqPreamp: number;
   eqEnabled: boolean;
   eqPreset: string | null;
   eqConnected: boolean;
+  eqUserPresets: EqualizerPreset[];
   // Sleep timer
   sleepTimerActive: boolean;
   sleepTimerRemainingTime: string;
@@ -346,8 +382,14 @@ function ExpandedPlayer({
   onToggleEqualizer: () => void;
   onCloseEqualizer: () => void;
   onToggleFavorite?: () => void;
-  onEqBandChange: (index: number, gain: number) => void;
+  onEqFilterChange: (index: number, patch: Partial<EqFilter>) => void;
+  onEqAddFilter: () => void;
+  onEqRemoveFilter: (index: number) => void;
+  onEqPreampChange: (gain: number) => void;
   onEqPresetChange: (preset: EqualizerPreset) => void;
+  onEqSavePreset: (name: string) => boolean;
+  onEqDeletePreset: (name: string) => void;
+  onEqImportProfile: (file: File) => void;
   onEqReset: () => void;
   onEqToggleEnabled: () => void;
   onTogglePlayPause: () => void;
@@ -358,6 +400,9 @@ function ExpandedPlayer({
   onToggleRepeat: () => void;
   onToggleShuffle: () => void;
   onSpeedChange: (speed: number) => void;
+  onPitchChange: (semitones: number) => void;
+  onTogglePreservePitch: () => void;
+  onToggleRememberPitch: () => void;
   onPlayFromQueue: (song: Song) => void;
   onStop: () => void;
   onDeleteFromQueue: (songId: string) => void;
@@ -370,6 +415,9 @@ function ExpandedPlayer({
   const [generatorFreqData, setGeneratorFreqData] = useState<Uint8Array>(new Uint8Array(128));
   const [generatorWaveData, setGeneratorWaveData] = useState<Uint8Array>(new Uint8Array(256));
   const startY = useRef<number | null>(null);
+  // The blurred background only needs the thumbnail
+  const backgroundArtUrl = useArtworkUrl(currentSong.artworkId);
+  const fullArtUrl = useArtworkUrl(currentSong.artworkId, "full");
   
   // Callbacks for generator controls
   const handleGeneratorVisualizerData = (freqData: Uint8Array, waveData: Uint8Array) => {
@@ -434,11 +482,11 @@ function ExpandedPlayer({
       onTouchEnd={handleTouchEnd}
     >
       {/* Background blur effect */}
-      {currentSong.coverArt && (
+      {backgroundArtUrl && (
         <div
           className="absolute inset-0 z-0"
           style={{
-            backgroundImage: `url(${currentSong.coverArt})`,
+            backgroundImage: `url(${backgroundArtUrl})`,
             backgroundSize: "cover",
             backgroundPosition: "center",
             filter: "blur(60px) saturate(1.2)",
@@ -621,9 +669,9 @@ function ExpandedPlayer({
           {/* Album Art Only */}
           {displayMode === "albumArt" && (
             <div className="w-64 h-64 md:w-80 md:h-80 rounded-2xl overflow-hidden shadow-2xl bg-vinyl-surface">
-              {currentSong.coverArt && !showGenerator ? (
+              {fullArtUrl && !showGenerator ? (
                 <img
-                  src={currentSong.coverArt}
+                  src={fullArtUrl}
                   alt={currentSong.title}
                   className={`w-full h-full object-cover transition-transform duration-500 ${
                     (isPlaying || generatorPlaying) ? "scale-105" : "scale-100"
@@ -670,6 +718,10 @@ function ExpandedPlayer({
               repeat={repeat}
               shuffle={shuffle}
               speed={speed}
+              pitch={pitch}
+              preservePitch={preservePitch}
+              isPitchRemembered={isPitchRemembered}
+              canTranspose={canTranspose}
               onTogglePlay={onTogglePlayPause}
               onNext={onNext}
               onPrevious={onPrevious}
@@ -678,6 +730,9 @@ function ExpandedPlayer({
               onToggleRepeat={onToggleRepeat}
               onToggleShuffle={onToggleShuffle}
               onSpeedChange={onSpeedChange}
+              onPitchChange={onPitchChange}
+              onTogglePreservePitch={onTogglePreservePitch}
+              onToggleRememberPitch={onToggleRememberPitch}
               disabled={false}
             />
           )}
@@ -758,12 +813,20 @@ function ExpandedPlayer({
         {/* Equalizer content */}
         <ScrollArea className="p-4 h-[calc(100%-4.5rem)]">
           <Equalizer
-            bands={eqBands}
+            filters={eqFilters}
+            preamp={eqPreamp}
             enabled={eqEnabled}
             currentPreset={eqPreset}
+            userPresets={eqUserPresets}
             isConnected={eqConnected}
-            onBandChange={onEqBandChange}
+            onFilterChange={onEqFilterChange}
+            onAddFilter={onEqAddFilter}
+            onRemoveFilter={onEqRemoveFilter}
+            onPreampChange={onEqPreampChange}
             onPresetChange={onEqPresetChange}
+            onSavePreset={onEqSavePreset}
+            onDeletePreset={onEqDeletePreset}
+            onImportProfile={onEqImportProfile}
             onReset={onEqReset}
             onToggleEnabled={onEqToggleEnabled}
           />
@@ -795,17 +858,17 @@ function ExpandedPlayer({
           {/* Album Art */}
           <div className="flex justify-center mb-6">
             <div className="w-48 h-48 rounded-xl overflow-hidden shadow-lg bg-vinyl-border">
-              {currentSong.coverArt ? (
-                <img
-                  src={currentSong.coverArt}
-                  alt={currentSong.album}
-                  className="w-full h-full object-cover"
-                />
-              ) : (
-                <div className="w-full h-full flex items-center justify-center">
-                  <Music className="w-16 h-16 text-vinyl-text-muted" />
-                </div>
-              )}
+              <ArtworkImage
+                artworkId={currentSong.artworkId}
+                variant="full"
+                alt={currentSong.album}
+                className="w-full h-full object-cover"
+                fallback={
+                  <div className="w-full h-full flex items-center justify-center">
+                    <Music className="w-16 h-16 text-vinyl-text-muted" />
+                  </div>
+                }
+              />
             </div>
           </div>
 
@@ -931,16 +994,28 @@ export function PlayerOverlay({
   repeat,
   shuffle,
   speed,
+  pitch,
+  preservePitch,
+  isPitchRemembered,
+  canTranspose,
   queueSongs,
   currentPlaylist,
   showAlbumArt,
   isFavorite = false,
-  eqBands,
+  eqFilters,
+  eqPreamp,
   eqEnabled,
   eqPreset,
   eqConnected,
-  onEqBandChange,
+  eqUserPresets,
+  onEqFilterChange,
+  onEqAddFilter,
+  onEqRemoveFilter,
+  onEqPreampChange,
   onEqPresetChange,
+  onEqSavePreset,
+  onEqDeletePreset,
+  onEqImportProfile,
   onEqReset,
   onEqToggleEnabled,
   // Sleep timer
@@ -969,6 +1044,9 @@ export function PlayerOverlay({
   onToggleRepeat,
   onToggleShuffle,
   onSpeedChange,
+  onPitchChange,
+  onTogglePreservePitch,
+  onToggleRememberPitch,
   onPlayFromQueue,
   onStop,
   onDeleteFromQueue,
@@ -1070,16 +1148,22 @@ export function PlayerOverlay({
           repeat={repeat}
           shuffle={shuffle}
           speed={speed}
+          pitch={pitch}
+          preservePitch={preservePitch}
+          isPitchRemembered={isPitchRemembered}
+          canTranspose={canTranspose}
           queueSongs={queueSongs}
           currentPlaylist={currentPlaylist}
           showAlbumArt={showAlbumArt}
           showQueue={showQueue}
           showEqualizer={showEqualizer}
           isFavorite={isFavorite}
-          eqBands={eqBands}
+          eqFilters={eqFilters}
+          eqPreamp={eqPreamp}
           eqEnabled={eqEnabled}
           eqPreset={eqPreset}
           eqConnected={eqConnected}
+          eqUserPresets={eqUserPresets}
           sleepTimerActive={sleepTimerActive}
           sleepTimerRemainingTime={sleepTimerRemainingTime}
           sleepTimerProgress={sleepTimerProgress}
@@ -1106,8 +1190,14 @@ export function PlayerOverlay({
           }}
           onCloseEqualizer={() => setShowEqualizer(false)}
           onToggleFavorite={onToggleFavorite}
-          onEqBandChange={onEqBandChange}
+          onEqFilterChange={onEqFilterChange}
+          onEqAddFilter={onEqAddFilter}
+          onEqRemoveFilter={onEqRemoveFilter}
+          onEqPreampChange={onEqPreampChange}
           onEqPresetChange={onEqPresetChange}
+          onEqSavePreset={onEqSavePreset}
+          onEqDeletePreset={onEqDeletePreset}
+          onEqImportProfile={onEqImportProfile}
           onEqReset={onEqReset}
           onEqToggleEnabled={onEqToggleEnabled}
           onTogglePlayPause={onTogglePlayPause}
@@ -1118,6 +1208,9 @@ export function PlayerOverlay({
           onToggleRepeat={onToggleRepeat}
           onToggleShuffle={onToggleShuffle}
           onSpeedChange={onSpeedChange}
+          onPitchChange={onPitchChange}
+          onTogglePreservePitch={onTogglePreservePitch}
+          onToggleRememberPitch={onToggleRememberPitch}
           onPlayFromQueue={onPlayFromQueue}
           onStop={onStop}
           onDeleteFromQueue={onDeleteFromQueue}
@@ -0,0 +1,119 @@
+import { useState } from "react";
+import { FileDown } from "lucide-react";
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from "@/components/ui/dialog";
+import { Button } from "@/components/ui/button";
+import {
+  PLAYLIST_FILE_FORMATS,
+  type PlaylistFileFormat,
+  type PlaylistPathMode,
+} from "../lib/playlistFiles";
+
+interface PlaylistExportDialogProps {
+  isOpen: boolean;
+  playlistName?: string;
+  // Desktop only: web exports always use paths within the music folder
+  showPathMode: boolean;
+  onExport: (
+    format: PlaylistFileFormat,
+    pathMode: PlaylistPathMode,
+  ) => Promise<void>;
+  onClose: () => void;
+}
+
+const selectClass =
+  "px-3 py-1.5 bg-vinyl-border text-vinyl-text rounded text-sm border-0 cursor-pointer";
+
+export function PlaylistExportDialog({
+  isOpen,
+  playlistName,
+  showPathMode,
+  onExport,
+  onClose,
+}: PlaylistExportDialogProps) {
+  // Kept between exports
+  const [format, setFormat] = useState<PlaylistFileFormat>("m3u8");
+  const [pathMode, setPathMode] = useState<PlaylistPathMode>("relative");
+  const [isExporting, setIsExporting] = useState(false);
+
+  const handleExport = async () => {
+    setIsExporting(true);
+    try {
+      await onExport(format, pathMode);
+    } finally {
+      setIsExporting(false);
+    }
+  };
+
+  return (
+    <Dialog open={isOpen} onOpenChange={(open) => !open && onClose()}>
+      <DialogContent className="max-w-md">
+        <DialogHeader>
+          <DialogTitle className="flex items-center gap-2">
+            <FileDown className="w-5 h-5 text-vinyl-accent" />
+            Export Playlist
+          </DialogTitle>
+          <DialogDescription>
+            Save "{playlistName}" as a playlist file for other players and DJ
+            software.
+          </DialogDescription>
+        </DialogHeader>
+
+        <div className="space-y-4">
+          <div className="flex items-center justify-between gap-4">
+            <label className="text-sm text-vinyl-text">Format</label>
+            <select
+              value={format}
+              onChange={(e) => setFormat(e.target.value as PlaylistFileFormat)}
+              className={selectClass}
+            >
+              {PLAYLIST_FILE_FORMATS.map((option) => (
+                <option key={option.key} value={option.key}>
+                  {option.label}
+                </option>
+              ))}
+            </select>
+          </div>
+
+          {showPathMode && (
+            <div className="flex items-center justify-between gap-4">
+              <div>
+                <label className="text-sm text-vinyl-text">Paths</label>
+                <p className="text-xs text-vinyl-text-muted">
+                  {pathMode === "relative"
+                    ? "Relative to where the playlist is saved, so it moves with your music"
+                    : "Full paths, so the playlist works from any folder"}
+                </p>
+              </div>
+              <select
+                value={pathMode}
+                onChange={(e) =>
+                  setPathMode(e.target.value as PlaylistPathMode)
+                }
+                className={selectClass}
+              >
+                <option value="relative">Relative</option>
+                <option value="absolute">Absolute</option>
+              </select>
+            </div>
+          )}
+        </div>
+
+        <DialogFooter>
+          <Button variant="outline" onClick={onClose}>
+            Cancel
+          </Button>
+          <Button onClick={handleExport} disabled={isExporting}>
+            Export
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
@@ -1,4 +1,4 @@
-import { useState } from "react";
+import { useState, useRef } from "react";
 import {
   ListMusic,
   Plus,
@@ -11,9 +11,21 @@ import {
   Square,
   Shuffle,
   Heart,
+  Sparkles,
+  SlidersHorizontal,
+  FileDown,
+  FileUp,
 } from "lucide-react";
-import type { Playlist, Song } from "../types";
+import type { Playlist, SmartPlaylistRules, Song } from "../types";
 import { ConfirmDialog } from "./ConfirmDialog";
+import { SmartPlaylistEditor } from "./SmartPlaylistEditor";
+import { PlaylistExportDialog } from "./PlaylistExportDialog";
+import { describeSmartRules, isSmartPlaylist } from "../lib/smartPlaylists";
+import {
+  PLAYLIST_FILE_EXTENSIONS,
+  type PlaylistFileFormat,
+  type PlaylistPathMode,
+} from "../lib/playlistFiles";
 import { tooltipProps } from "./Tooltip";
 import { FAVORITES_PLAYLIST_NAME } from "../hooks/usePlaylists";
 
@@ -23,12 +35,27 @@ interface PlaylistViewProps {
   currentPlaylistId?: string | null;
   isPlaying?: boolean;
   onCreatePlaylist: (name: string) => Promise<void>;
+  onCreateSmartPlaylist?: (
+    name: string,
+    rules: SmartPlaylistRules,
+  ) => Promise<void>;
+  // Current songs of each smart playlist, by id
+  smartPlaylistSongs?: Map<string, Song[]>;
   onDeletePlaylist: (id: string) => Promise<void>;
   onUpdatePlaylist: (id: string, updates: Partial<Playlist>) => Promise<void>;
   onSelectPlaylist: (playlist: Playlist) => void;
   onPlayPlaylist?: (playlist: Playlist) => void;
   onShufflePlaylist?: (playlist: Playlist) => void;
   onStopPlaylist?: () => void;
+  // Web passes the chosen file; desktop picks it through a native dialog
+  onImportPlaylist?: (file?: File) => Promise<void>;
+  onExportPlaylist?: (
+    playlist: Playlist,
+    format: PlaylistFileFormat,
+    pathMode: PlaylistPathMode,
+  ) => Promise<void>;
+  // Desktop reads and writes playlist files through native dialogs
+  isDesktop?: boolean;
 }
 
 export function PlaylistView({
@@ -37,18 +64,30 @@ export function PlaylistView({
   currentPlaylistId,
   isPlaying = false,
   onCreatePlaylist,
+  onCreateSmartPlaylist,
+  smartPlaylistSongs,
   onDeletePlaylist,
   onUpdatePlaylist,
   onSelectPlaylist,
   onPlayPlaylist,
   onShufflePlaylist,
   onStopPlaylist,
+  onImportPlaylist,
+  onExportPlaylist,
+  isDesktop = false,
 }: PlaylistViewProps) {
   const [isCreating, setIsCreating] = useState(false);
   const [newName, setNewName] = useState("");
   const [editingId, setEditingId] = useState<string | null>(null);
   const [editName, setEditName] = useState("");
   const [deletePlaylistId, setDeletePlaylistId] = useState<string | null>(null);
+  // "new" while creating a smart playlist, else the one being edited
+  const [smartEditor, setSmartEditor] = useState<Playlist | "new" | null>(
+    null,
+  );
+  const editingSmart = smartEditor !== "new" ? smartEditor : null;
+  const [exportPlaylist, setExportPlaylist] = useState<Playlist | null>(null);
+  const importInputRef = useRef<HTMLInputElement>(null);
 
   const playlistToDelete = deletePlaylistId
     ? playlists.find((p) => p.id === deletePlaylistId)
@@ -70,7 +109,32 @@ export function PlaylistView({
     }
   };
 
+  const handleSaveSmart = async (name: string, rules: SmartPlaylistRules) => {
+    if (editingSmart) {
+      await onUpdatePlaylist(editingSmart.id, { name, rules });
+    } else if (onCreateSmartPlaylist) {
+      await onCreateSmartPlaylist(name, rules);
+    }
+  };
+
+  const handleImportClick = () => {
+    if (isDesktop) {
+      onImportPlaylist?.();
+    } else {
+      importInputRef.current?.click();
+    }
+  };
+
+  const handleImportFile = (e: React.ChangeEvent<HTMLInputElement>) => {
+    const file = e.target.files?.[0];
+    e.target.value = "";
+    if (file) onImportPlaylist?.(file);
+  };
+
   const getSongCount = (playlist: Playlist) => {
+    if (isSmartPlaylist(playlist)) {
+      return smartPlaylistSongs?.get(playlist.id)?.length ?? 0;
+    }
     return playlist.songIds.filter((id) => songs.find((s) => s.id === id))
       .length;
   };
@@ -79,13 +143,44 @@ export function PlaylistView({
     <div className="space-y-4">
       <div className="flex items-center justify-between">
         <h2 className="text-lg font-semibold text-vinyl-text">Playlists</h2>
-        <button
-          onClick={() => setIsCreating(true)}
-          className="flex items-center gap-1 px-3 py-1.5 text-sm bg-vinyl-border/50 hover:bg-vinyl-border rounded-lg transition-colors"
-        >
-          <Plus className="w-4 h-4" />
-          New
-        </button>
+        <div className="flex items-center gap-2">
+          {onImportPlaylist && (
+            <>
+              <button
+                onClick={handleImportClick}
+                className="flex items-center gap-1 px-3 py-1.5 text-sm bg-vinyl-border/50 hover:bg-vinyl-border rounded-lg transition-colors"
+                {...tooltipProps("Import M3U, PLS or XSPF playlist")}
+              >
+                <FileUp className="w-4 h-4" />
+                Import
+              </button>
+              <input
+                ref={importInputRef}
+                type="file"
+                accept={PLAYLIST_FILE_EXTENSIONS.join(",")}
+                onChange={handleImportFile}
+                className="hidden"
+              />
+            </>
+          )}
+          {onCreateSmartPlaylist && (
+            <button
+              onClick={() => setSmartEditor("new")}
+              className="flex items-center gap-1 px-3 py-1.5 text-sm bg-vinyl-border/50 hover:bg-vinyl-border rounded-lg transition-colors"
+              {...tooltipProps("New playlist from rules")}
+            >
+              <Sparkles className="w-4 h-4" />
+              Smart
+            </button>
+          )}
+          <button
+            onClick={() => setIsCreating(true)}
+            className="flex items-center gap-1 px-3 py-1.5 text-sm bg-vinyl-border/50 hover:bg-vinyl-border rounded-lg transition-colors"
+          >
+            <Plus className="w-4 h-4" />
+            New
+          </button>
+        </div>
       </div>
 
       {/* Create playlist input */}
@@ -132,6 +227,7 @@ export function PlaylistView({
             const isCurrentlyPlaying = isCurrentPlaylist && isPlaying;
             const isFavoritesPlaylist =
               playlist.name === FAVORITES_PLAYLIST_NAME;
+            const isSmart = isSmartPlaylist(playlist);
 
             return (
               <div
@@ -167,6 +263,8 @@ export function PlaylistView({
                     <Heart
                       className={`w-5 h-5 ${isCurrentPlaylist ? "text-red-500" : "text-red-500"} fill-current`}
                     />
+                  ) : isSmart ? (
+                    <Sparkles className="w-5 h-5 text-vinyl-accent" />
                   ) : (
                     <ListMusic
                       className={`w-5 h-5 ${isCurrentPlaylist ? "text-vinyl-accent" : "text-vinyl-accent"}`}
@@ -213,6 +311,8 @@ export function PlaylistView({
                       </p>
                       <p className="text-sm text-vinyl-text-muted">
                         {getSongCount(playlist)} songs
+                        {playlist.rules &&
+                          ` • ${describeSmartRules(playlist.rules)}`}
                       </p>
                     </div>
 
@@ -275,6 +375,34 @@ export function PlaylistView({
                         </button>
                       )}
 
+                      {/* Edit rules button - smart playlists only */}
+                      {isSmart && (
+                        <button
+                          onClick={(e) => {
+                            e.stopPropagation();
+                            setSmartEditor(playlist);
+                          }}
+                          className="p-2 rounded-full hover:bg-vinyl-border transition-colors text-vinyl-text-muted hover:text-vinyl-text"
+                          {...tooltipProps("Edit rules")}
+                        >
+                          <SlidersHorizontal className="w-4 h-4" />
+                        </button>
+                      )}
+
+                      {/* Export button */}
+                      {onExportPlaylist && (
+                        <button
+                          onClick={(e) => {
+                            e.stopPropagation();
+                            setExportPlaylist(playlist);
+                          }}
+                          className="p-2 rounded-full hover:bg-vinyl-border transition-colors text-vinyl-text-muted hover:text-vinyl-text"
+                          {...tooltipProps("Export")}
+                        >
+                          <FileDown className="w-4 h-4" />
+                        </button>
+                      )}
+
                       {/* Rename button - hidden for Favorites */}
                       {!isFavoritesPlaylist && (
                         <button
@@ -312,6 +440,30 @@ export function PlaylistView({
         </div>
       )}
 
+      {/* Smart playlist rules */}
+      <SmartPlaylistEditor
+        isOpen={!!smartEditor}
+        initialName={editingSmart?.name}
+        initialRules={editingSmart?.rules}
+        songs={songs}
+        onSave={handleSaveSmart}
+        onClose={() => setSmartEditor(null)}
+      />
+
+      {/* Export as a playlist file */}
+      <PlaylistExportDialog
+        isOpen={!!exportPlaylist}
+        playlistName={exportPlaylist?.name}
+        showPathMode={isDesktop}
+        onExport={async (format, pathMode) => {
+          if (exportPlaylist && onExportPlaylist) {
+            await onExportPlaylist(exportPlaylist, format, pathMode);
+          }
+          setExportPlaylist(null);
+        }}
+        onClose={() => setExportPlaylist(null)}
+      />
+
       {/* Delete confirmation dialog */}
       <ConfirmDialog
         isOpen={!!deletePlaylistId}
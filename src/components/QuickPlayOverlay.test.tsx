@@ -19,7 +19,7 @@ vi.mock('../lib/audioMetadata', () => {
       artist: 'Test Artist',
       album: 'Test Album',
       duration: 180,
-      coverArt: undefined,
+      artworkId: undefined,
     }),
     formatDuration: (seconds: number) => {
       const mins = Math.floor(seconds / 60);
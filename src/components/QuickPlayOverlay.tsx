@@ -9,6 +9,7 @@ import {
   ListMusic,
 } from "lucide-react";
 import { isAudioFile, extractMetadata, formatDuration } from "../lib/audioMetadata";
+import { ArtworkImage } from "./ArtworkImage";
 
 interface QuickPlayOverlayProps {
   /** Whether the overlay is visible */
@@ -29,7 +30,7 @@ interface FileInfo {
   artist: string;
   album: string;
   duration: number;
-  coverArt?: string;
+  artworkId?: string;
 }
 
 export function QuickPlayOverlay({
@@ -62,7 +63,8 @@ export function QuickPlayOverlay({
               artist: metadata.artist || "Unknown Artist",
               album: metadata.album || "Unknown Album",
               duration: metadata.duration || 0,
-              coverArt: metadata.coverArt,
+              artworkId: metadata.artworkId,
+              replayGain: metadata.replayGain,
             }]);
           })
           .catch(() => {
@@ -177,15 +179,12 @@ export function QuickPlayOverlay({
               </div>
             ) : (
               <div className="w-16 h-16 rounded-lg bg-vinyl-border flex items-center justify-center flex-shrink-0 overflow-hidden">
-                {firstFile?.coverArt ? (
-                  <img
-                    src={firstFile.coverArt}
-                    alt={firstFile.title}
-                    className="w-full h-full object-cover"
-                  />
-                ) : (
-                  <Music className="w-8 h-8 text-vinyl-text-muted" />
-                )}
+                <ArtworkImage
+                  artworkId={firstFile?.artworkId}
+                  alt={firstFile?.title}
+                  className="w-full h-full object-cover"
+                  fallback={<Music className="w-8 h-8 text-vinyl-text-muted" />}
+                />
               </div>
             )}
             <div className="flex-1 min-w-0">
@@ -16,12 +16,28 @@ import {
   AlertTriangle,
   Activity,
 } from "lucide-react";
-import type { AppSettings, RepeatMode, QueueBehavior } from "../types";
-import type { EqualizerBand, EqualizerPreset } from "../hooks/useEqualizer";
+import type {
+  AppSettings,
+  RepeatMode,
+  QueueBehavior,
+  ReplayGainMode,
+  PlaybackProfile,
+  Playlist,
+  Song,
+} from "../types";
+import type { EqualizerPreset } from "../hooks/useEqualizer";
 import { EQUALIZER_PRESETS } from "../hooks/useEqualizer";
+import {
+  formatFrequency,
+  usesGain,
+  type EqFilter,
+} from "../lib/parametricEq";
 import { tooltipProps } from "./Tooltip";
 import { ConfirmDialog } from "./ConfirmDialog";
 import { LibrarySettings } from "./LibrarySettings";
+import { BackupSettings } from "./BackupSettings";
+import { PlaybackProfileSettings } from "./PlaybackProfileSettings";
+import type { PlaybackProfileInput } from "../hooks/usePlaybackProfiles";
 import type { MusicFileInfo, LibraryScanResult } from "../lib/platform";
 
 interface SettingsViewProps {
@@ -34,14 +50,22 @@ interface SettingsViewProps {
   /** Reset entire player - clears all data */
   onResetPlayer: () => Promise<void>;
   // Equalizer props
-  eqBands: EqualizerBand[];
+  eqFilters: EqFilter[];
   eqEnabled: boolean;
   eqPreset: string | null;
+  eqUserPresets: EqualizerPreset[];
   eqConnected: boolean;
-  onEqBandChange: (index: number, gain: number) => void;
+  onEqFilterChange: (index: number, patch: Partial<EqFilter>) => void;
   onEqPresetChange: (preset: EqualizerPreset) => void;
   onEqReset: () => void;
   onEqToggleEnabled: () => void;
+  // Playback profile props
+  playbackProfiles: PlaybackProfile[];
+  currentSong: Song | null;
+  currentPlaylist: Playlist | null;
+  outputDevice: string | null;
+  onSavePlaybackProfile: (profile: PlaybackProfileInput) => void;
+  onDeletePlaybackProfile: (id: string) => void;
   // Library props
   libraryFolders: string[];
   libraryIsScanning: boolean;
@@ -61,6 +85,11 @@ interface SettingsViewProps {
   onLibraryScanAllFolders: () => Promise<MusicFileInfo[]>;
   onLibraryImportFiles: (files: MusicFileInfo[]) => Promise<void>;
   onLibraryClearError: () => void;
+  libraryHealth: { checkedAt: number; missing: number } | null;
+  onLibraryCheckHealth: () => Promise<number | null>;
+  onLibraryRelinkMissing: () => Promise<void>;
+  // Loudness analysis (desktop only)
+  loudnessProgress: { current: number; total: number } | null;
 }
 
 const APP_ICONS = [
@@ -75,14 +104,21 @@ export function SettingsView({
   onUpdateSetting,
   onResetSettings,
   onResetPlayer,
-  eqBands,
+  eqFilters,
   eqEnabled,
   eqPreset,
+  eqUserPresets,
   eqConnected,
-  onEqBandChange,
+  onEqFilterChange,
   onEqPresetChange,
   onEqReset,
   onEqToggleEnabled,
+  playbackProfiles,
+  currentSong,
+  currentPlaylist,
+  outputDevice,
+  onSavePlaybackProfile,
+  onDeletePlaybackProfile,
   // Library props
   libraryFolders,
   libraryIsScanning,
@@ -98,6 +134,10 @@ export function SettingsView({
   onLibraryScanAllFolders,
   onLibraryImportFiles,
   onLibraryClearError,
+  libraryHealth,
+  onLibraryCheckHealth,
+  onLibraryRelinkMissing,
+  loudnessProgress,
 }: SettingsViewProps) {
   const [editingTitle, setEditingTitle] = useState(false);
   const [tempTitle, setTempTitle] = useState(settings.appTitle);
@@ -141,6 +181,9 @@ export function SettingsView({
         onScanAllFolders={onLibraryScanAllFolders}
         onImportFiles={onLibraryImportFiles}
         onClearError={onLibraryClearError}
+        libraryHealth={libraryHealth}
+        onCheckHealth={onLibraryCheckHealth}
+        onRelinkMissing={onLibraryRelinkMissing}
       />
 
       {/* Appearance Section */}
@@ -336,7 +379,7 @@ export function SettingsView({
             <div>
               <h3 className="text-vinyl-text font-medium">Gapless Playback</h3>
               <p className="text-sm text-vinyl-text-muted">
-                Remove silence between tracks
+                Join tracks seamlessly (not used while crossfade is on)
               </p>
             </div>
             <ToggleSwitch
@@ -345,6 +388,21 @@ export function SettingsView({
             />
           </div>
 
+          {/* Keep Pitch */}
+          <div className="flex items-center justify-between">
+            <div>
+              <h3 className="text-vinyl-text font-medium">Keep Pitch</h3>
+              <p className="text-sm text-vinyl-text-muted">
+                Change speed without changing the key (off plays like a
+                turntable)
+              </p>
+            </div>
+            <ToggleSwitch
+              enabled={settings.preservePitch}
+              onChange={(v) => onUpdateSetting("preservePitch", v)}
+            />
+          </div>
+
           {/* Crossfade */}
           <div className="flex items-center justify-between">
             <div>
@@ -352,7 +410,7 @@ export function SettingsView({
                 Crossfade Duration
               </h3>
               <p className="text-sm text-vinyl-text-muted">
-                Blend between tracks (0 = off)
+                Blend between tracks, except within an album (0 = off)
               </p>
             </div>
             <div className="flex items-center gap-2">
@@ -373,6 +431,86 @@ export function SettingsView({
             </div>
           </div>
 
+          {/* Volume Normalization */}
+          <div className="flex items-center justify-between">
+            <div>
+              <h3 className="text-vinyl-text font-medium">
+                Volume Normalization
+              </h3>
+              <p className="text-sm text-vinyl-text-muted">
+                {loudnessProgress
+                  ? `Analyzing loudness... ${loudnessProgress.current}/${loudnessProgress.total}`
+                  : "Even out loudness using ReplayGain"}
+              </p>
+            </div>
+            <select
+              value={settings.replayGainMode}
+              onChange={(e) =>
+                onUpdateSetting(
+                  "replayGainMode",
+                  e.target.value as ReplayGainMode,
+                )
+              }
+              className="px-3 py-1.5 bg-vinyl-border text-vinyl-text rounded text-sm border-0 cursor-pointer"
+            >
+              <option value="off">Off</option>
+              <option value="track">Track</option>
+              <option value="album">Album</option>
+            </select>
+          </div>
+
+          {settings.replayGainMode !== "off" && (
+            <>
+              {/* Normalization Preamp */}
+              <div className="flex items-center justify-between">
+                <div>
+                  <h3 className="text-vinyl-text font-medium">Preamp</h3>
+                  <p className="text-sm text-vinyl-text-muted">
+                    Extra gain applied on top of ReplayGain
+                  </p>
+                </div>
+                <div className="flex items-center gap-2">
+                  <input
+                    type="range"
+                    min="-12"
+                    max="12"
+                    step="1"
+                    value={settings.replayGainPreamp}
+                    onChange={(e) =>
+                      onUpdateSetting(
+                        "replayGainPreamp",
+                        parseInt(e.target.value),
+                      )
+                    }
+                    className="w-24 accent-vinyl-accent"
+                  />
+                  <span className="text-sm text-vinyl-text w-12 text-right">
+                    {settings.replayGainPreamp > 0 ? "+" : ""}
+                    {settings.replayGainPreamp} dB
+                  </span>
+                </div>
+              </div>
+
+              {/* Prevent Clipping */}
+              <div className="flex items-center justify-between">
+                <div>
+                  <h3 className="text-vinyl-text font-medium">
+                    Prevent Clipping
+                  </h3>
+                  <p className="text-sm text-vinyl-text-muted">
+                    Lower the gain when a track's peak would clip
+                  </p>
+                </div>
+                <ToggleSwitch
+                  enabled={settings.replayGainPreventClipping}
+                  onChange={(v) =>
+                    onUpdateSetting("replayGainPreventClipping", v)
+                  }
+                />
+              </div>
+            </>
+          )}
+
           {/* Default Shuffle */}
           <div className="flex items-center justify-between">
             <div>
@@ -550,18 +688,34 @@ export function SettingsView({
               <select
                 value={eqPreset || ""}
                 onChange={(e) => {
-                  const preset = EQUALIZER_PRESETS.find(
+                  const preset = [...eqUserPresets, ...EQUALIZER_PRESETS].find(
                     (p) => p.name === e.target.value,
                   );
                   if (preset) onEqPresetChange(preset);
                 }}
                 className="px-3 py-1.5 bg-vinyl-border text-vinyl-text rounded text-sm border-0 cursor-pointer"
               >
-                {EQUALIZER_PRESETS.map((preset) => (
-                  <option key={preset.name} value={preset.name}>
-                    {preset.name}
+                {!eqPreset && (
+                  <option value="" disabled>
+                    Custom
                   </option>
-                ))}
+                )}
+                {eqUserPresets.length > 0 && (
+                  <optgroup label="My Presets">
+                    {eqUserPresets.map((preset) => (
+                      <option key={preset.name} value={preset.name}>
+                        {preset.name}
+                      </option>
+                    ))}
+                  </optgroup>
+                )}
+                <optgroup label="Built-in">
+                  {EQUALIZER_PRESETS.map((preset) => (
+                    <option key={preset.name} value={preset.name}>
+                      {preset.name}
+                    </option>
+                  ))}
+                </optgroup>
               </select>
               <button
                 onClick={onEqReset}
@@ -577,35 +731,38 @@ export function SettingsView({
           {eqEnabled && (
             <div className="pt-4 border-t border-vinyl-border">
               <div className="flex justify-between items-end gap-2">
-                {eqBands.map((band, index) => (
+                {eqFilters.map((filter, index) => (
                   <div
-                    key={band.frequency}
+                    key={filter.id}
                     className="flex flex-col items-center gap-1 flex-1"
                   >
                     <span className="text-[10px] text-vinyl-text-muted text-center">
-                      {band.gain > 0 ? "+" : ""}
-                      {band.gain}
+                      {filter.gain > 0 ? "+" : ""}
+                      {Number(filter.gain.toFixed(1))}
                     </span>
                     <div className="h-20 flex items-center justify-center">
                       <input
                         type="range"
                         min="-12"
                         max="12"
-                        step="1"
-                        value={band.gain}
+                        step="0.5"
+                        value={filter.gain}
                         onChange={(e) =>
-                          onEqBandChange(index, parseFloat(e.target.value))
+                          onEqFilterChange(index, {
+                            gain: parseFloat(e.target.value),
+                          })
                         }
+                        disabled={!usesGain(filter.type)}
                         className="eq-slider-vertical-small"
                         style={
                           {
-                            "--eq-value": `${((band.gain + 12) / 24) * 100}%`,
+                            "--eq-value": `${((Math.max(-12, Math.min(12, filter.gain)) + 12) / 24) * 100}%`,
                           } as React.CSSProperties
                         }
                       />
                     </div>
                     <span className="text-[10px] text-vinyl-text-muted">
-                      {band.label}
+                      {formatFrequency(filter.frequency)}
                     </span>
                   </div>
                 ))}
@@ -620,6 +777,19 @@ export function SettingsView({
         </div>
       </section>
 
+      {/* Playback Profiles Section */}
+      <PlaybackProfileSettings
+        profiles={playbackProfiles}
+        currentSong={currentSong}
+        currentPlaylist={currentPlaylist}
+        outputDevice={outputDevice}
+        eqPresetNames={[...eqUserPresets, ...EQUALIZER_PRESETS].map(
+          (preset) => preset.name,
+        )}
+        onSaveProfile={onSavePlaybackProfile}
+        onDeleteProfile={onDeletePlaybackProfile}
+      />
+
       {/* Now Playing Display Section */}
       <section className="bg-vinyl-surface border border-vinyl-border rounded-lg overflow-hidden">
         <div className="px-4 py-3 border-b border-vinyl-border bg-vinyl-border/20">
@@ -738,6 +908,9 @@ export function SettingsView({
         </div>
       </section>
 
+      {/* Backup Section */}
+      <BackupSettings settings={settings} onUpdateSetting={onUpdateSetting} />
+
       {/* Danger Zone Section */}
       <section className="bg-red-500/5 border border-red-500/20 rounded-lg overflow-hidden">
         <div className="px-4 py-3 border-b border-red-500/20 bg-red-500/10">
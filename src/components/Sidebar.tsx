@@ -1,6 +1,8 @@
 import { useState, useEffect } from "react";
 import { NavLink } from "react-router-dom";
 import {
+  BarChart3,
+  Disc,
   Disc3,
   ListMusic,
   Music,
@@ -12,6 +14,7 @@ import {
   Moon,
   Info,
   HelpCircle,
+  Users,
 } from "lucide-react";
 import { tooltipProps } from "./Tooltip";
 import { KeyboardShortcutsDialog } from "./KeyboardShortcutsDialog";
@@ -37,7 +40,10 @@ const ICON_MAP = {
 
 const navItems = [
   { to: "/library", icon: Music, label: "Library" },
+  { to: "/artists", icon: Users, label: "Artists" },
+  { to: "/albums", icon: Disc, label: "Albums" },
   { to: "/playlists", icon: ListMusic, label: "Playlists" },
+  { to: "/stats", icon: BarChart3, label: "Stats" },
   { to: "/settings", icon: Settings, label: "Settings" },
 ];
 
@@ -0,0 +1,445 @@
+import { useState, useEffect, useMemo } from "react";
+import { Plus, Trash2, Shuffle, Sparkles } from "lucide-react";
+import type {
+  SmartPlaylistRules,
+  SmartRule,
+  SmartRuleField,
+  SmartRuleOperator,
+  SmartSortKey,
+  Song,
+} from "../types";
+import {
+  Dialog,
+  DialogContent,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from "@/components/ui/dialog";
+import { Button } from "@/components/ui/button";
+import {
+  SMART_RULE_FIELDS,
+  SMART_RULE_OPERATORS,
+  SMART_SORT_OPTIONS,
+  createDefaultSmartRules,
+  evaluateSmartPlaylist,
+  getFieldKind,
+} from "../lib/smartPlaylists";
+import { tooltipProps } from "./Tooltip";
+
+interface SmartPlaylistEditorProps {
+  isOpen: boolean;
+  // Omitted when creating a new smart playlist
+  initialName?: string;
+  initialRules?: SmartPlaylistRules;
+  songs: Song[];
+  onSave: (name: string, rules: SmartPlaylistRules) => Promise<void>;
+  onClose: () => void;
+}
+
+const selectClass =
+  "px-2 py-1.5 bg-vinyl-border text-vinyl-text rounded text-sm border-0 cursor-pointer";
+const inputClass =
+  "px-2 py-1.5 bg-vinyl-bg border border-vinyl-border rounded text-vinyl-text text-sm min-w-0";
+
+function defaultValue(field: SmartRuleField): SmartRule["value"] {
+  switch (getFieldKind(field)) {
+    case "date":
+      return 30;
+    case "number":
+      return 0;
+    default:
+      return "";
+  }
+}
+
+function formatTotalDuration(seconds: number): string {
+  const hours = Math.floor(seconds / 3600);
+  const minutes = Math.round((seconds % 3600) / 60);
+  return hours > 0 ? `${hours} hr ${minutes} min` : `${minutes} min`;
+}
+
+interface RuleRowProps {
+  rule: SmartRule;
+  onChange: (rule: SmartRule) => void;
+  onRemove: () => void;
+}
+
+function RuleRow({ rule, onChange, onRemove }: RuleRowProps) {
+  const kind = getFieldKind(rule.field);
+  const operators = SMART_RULE_OPERATORS[kind];
+
+  const handleFieldChange = (field: SmartRuleField) => {
+    const nextKind = getFieldKind(field);
+    if (nextKind === kind) {
+      onChange({ ...rule, field });
+      return;
+    }
+    onChange({
+      field,
+      operator: SMART_RULE_OPERATORS[nextKind][0].key,
+      value: defaultValue(field),
+    });
+  };
+
+  const handleOperatorChange = (operator: SmartRuleOperator) => {
+    let value = rule.value;
+    if (operator === "in" || operator === "notIn") {
+      value = Array.isArray(value) ? value : [String(value)].filter(Boolean);
+    } else if (operator === "between") {
+      value = Array.isArray(value)
+        ? value
+        : ([Number(value) || 0, 0] as [number, number]);
+    } else if (Array.isArray(value)) {
+      value = kind === "text" ? value.join(", ") : Number(value[0]) || 0;
+    }
+    onChange({ ...rule, operator, value });
+  };
+
+  const renderValue = () => {
+    if (rule.operator === "in" || rule.operator === "notIn") {
+      const list = Array.isArray(rule.value) ? rule.value : [];
+      return (
+        <input
+          type="text"
+          defaultValue={list.join(", ")}
+          onBlur={(e) =>
+            onChange({
+              ...rule,
+              value: e.target.value
+                .split(",")
+                .map((v) => v.trim())
+                .filter(Boolean),
+            })
+          }
+          placeholder="jazz, soul"
+          className={`${inputClass} flex-1`}
+        />
+      );
+    }
+    if (rule.operator === "between") {
+      const [min, max] = Array.isArray(rule.value)
+        ? (rule.value as (string | number)[]).map(Number)
+        : [0, 0];
+      return (
+        <div className="flex items-center gap-1 flex-1">
+          <input
+            type="number"
+            value={min}
+            onChange={(e) =>
+              onChange({
+                ...rule,
+                value: [Number(e.target.value), max] as [number, number],
+              })
+            }
+            className={`${inputClass} w-20`}
+          />
+          <span className="text-vinyl-text-muted text-sm">and</span>
+          <input
+            type="number"
+            value={max}
+            onChange={(e) =>
+              onChange({
+                ...rule,
+                value: [min, Number(e.target.value)] as [number, number],
+              })
+            }
+            className={`${inputClass} w-20`}
+          />
+        </div>
+      );
+    }
+    return (
+      <input
+        type={kind === "text" ? "text" : "number"}
+        value={String(rule.value)}
+        onChange={(e) =>
+          onChange({
+            ...rule,
+            value:
+              kind === "text" ? e.target.value : Number(e.target.value),
+          })
+        }
+        className={`${inputClass} flex-1`}
+      />
+    );
+  };
+
+  return (
+    <div className="flex flex-wrap items-center gap-2">
+      <select
+        value={rule.field}
+        onChange={(e) => handleFieldChange(e.target.value as SmartRuleField)}
+        className={selectClass}
+      >
+        {SMART_RULE_FIELDS.map((f) => (
+          <option key={f.key} value={f.key}>
+            {f.label}
+          </option>
+        ))}
+      </select>
+      <select
+        value={rule.operator}
+        onChange={(e) =>
+          handleOperatorChange(e.target.value as SmartRuleOperator)
+        }
+        className={selectClass}
+      >
+        {operators.map((o) => (
+          <option key={o.key} value={o.key}>
+            {o.label}
+          </option>
+        ))}
+      </select>
+      {renderValue()}
+      <button
+        onClick={onRemove}
+        className="p-1.5 rounded-full hover:bg-vinyl-border transition-colors text-vinyl-text-muted hover:text-red-400"
+        {...tooltipProps("Remove rule")}
+      >
+        <Trash2 className="w-4 h-4" />
+      </button>
+    </div>
+  );
+}
+
+export function SmartPlaylistEditor({
+  isOpen,
+  initialName,
+  initialRules,
+  songs,
+  onSave,
+  onClose,
+}: SmartPlaylistEditorProps) {
+  const [name, setName] = useState("");
+  const [rules, setRules] = useState<SmartPlaylistRules>(
+    createDefaultSmartRules,
+  );
+  const [isSaving, setIsSaving] = useState(false);
+  // Bumped on removal so rows (and their uncontrolled inputs) remount
+  const [rowsVersion, setRowsVersion] = useState(0);
+
+  // Start from the playlist being edited each time the dialog opens
+  useEffect(() => {
+    if (isOpen) {
+      setName(initialName ?? "");
+      setRules(initialRules ?? createDefaultSmartRules());
+    }
+  }, [isOpen, initialName, initialRules]);
+
+  // Live preview of what the rules match right now
+  const preview = useMemo(
+    () => (isOpen ? evaluateSmartPlaylist(rules, songs) : []),
+    [isOpen, rules, songs],
+  );
+  const previewDuration = preview.reduce((sum, s) => sum + s.duration, 0);
+
+  const updateRule = (index: number, rule: SmartRule) => {
+    setRules((prev) => ({
+      ...prev,
+      rules: prev.rules.map((r, i) => (i === index ? rule : r)),
+    }));
+  };
+
+  const addRule = () => {
+    setRules((prev) => ({
+      ...prev,
+      rules: [...prev.rules, { field: "artist", operator: "is", value: "" }],
+    }));
+  };
+
+  const removeRule = (index: number) => {
+    setRowsVersion((v) => v + 1);
+    setRules((prev) => ({
+      ...prev,
+      rules: prev.rules.filter((_, i) => i !== index),
+    }));
+  };
+
+  const handleSortChange = (sortBy: SmartSortKey) => {
+    setRules((prev) => ({
+      ...prev,
+      sortBy,
+      seed: sortBy === "random" ? (prev.seed ?? Date.now()) : prev.seed,
+    }));
+  };
+
+  const handleSave = async () => {
+    if (!name.trim()) return;
+    setIsSaving(true);
+    try {
+      await onSave(name.trim(), rules);
+      onClose();
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  return (
+    <Dialog open={isOpen} onOpenChange={(open) => !open && onClose()}>
+      <DialogContent className="max-w-2xl">
+        <DialogHeader>
+          <DialogTitle className="flex items-center gap-2">
+            <Sparkles className="w-5 h-5 text-vinyl-accent" />
+            {initialRules ? "Edit Smart Playlist" : "New Smart Playlist"}
+          </DialogTitle>
+        </DialogHeader>
+
+        <div className="space-y-4 max-h-[60vh] overflow-y-auto pr-1">
+          <input
+            type="text"
+            value={name}
+            onChange={(e) => setName(e.target.value)}
+            placeholder="Playlist name"
+            className={`${inputClass} w-full`}
+            autoFocus
+          />
+
+          {/* Rules */}
+          <div className="space-y-2">
+            <div className="flex items-center gap-2 text-sm text-vinyl-text">
+              Match
+              <select
+                value={rules.match}
+                onChange={(e) =>
+                  setRules((prev) => ({
+                    ...prev,
+                    match: e.target.value as SmartPlaylistRules["match"],
+                  }))
+                }
+                className={selectClass}
+              >
+                <option value="all">all</option>
+                <option value="any">any</option>
+              </select>
+              of the following rules:
+            </div>
+            {rules.rules.map((rule, index) => (
+              <RuleRow
+                key={`${rowsVersion}-${index}`}
+                rule={rule}
+                onChange={(r) => updateRule(index, r)}
+                onRemove={() => removeRule(index)}
+              />
+            ))}
+            <Button variant="ghost" size="sm" onClick={addRule}>
+              <Plus />
+              Add Rule
+            </Button>
+          </div>
+
+          {/* Limit */}
+          <div className="flex flex-wrap items-center gap-2 text-sm text-vinyl-text">
+            <label className="flex items-center gap-2 cursor-pointer">
+              <input
+                type="checkbox"
+                checked={!!rules.limit}
+                onChange={(e) =>
+                  setRules((prev) => ({
+                    ...prev,
+                    limit: e.target.checked
+                      ? { value: 25, unit: "songs" }
+                      : undefined,
+                  }))
+                }
+                className="accent-vinyl-accent"
+              />
+              Limit to
+            </label>
+            {rules.limit && (
+              <>
+                <input
+                  type="number"
+                  min={1}
+                  value={rules.limit.value}
+                  onChange={(e) =>
+                    setRules((prev) => ({
+                      ...prev,
+                      limit: prev.limit && {
+                        ...prev.limit,
+                        value: Number(e.target.value),
+                      },
+                    }))
+                  }
+                  className={`${inputClass} w-20`}
+                />
+                <select
+                  value={rules.limit.unit}
+                  onChange={(e) =>
+                    setRules((prev) => ({
+                      ...prev,
+                      limit: prev.limit && {
+                        ...prev.limit,
+                        unit: e.target.value as "songs" | "minutes" | "hours",
+                      },
+                    }))
+                  }
+                  className={selectClass}
+                >
+                  <option value="songs">songs</option>
+                  <option value="minutes">minutes</option>
+                  <option value="hours">hours</option>
+                </select>
+              </>
+            )}
+          </div>
+
+          {/* Sort */}
+          <div className="flex flex-wrap items-center gap-2 text-sm text-vinyl-text">
+            Sort by
+            <select
+              value={rules.sortBy}
+              onChange={(e) => handleSortChange(e.target.value as SmartSortKey)}
+              className={selectClass}
+            >
+              {SMART_SORT_OPTIONS.map((option) => (
+                <option key={option.key} value={option.key}>
+                  {option.label}
+                </option>
+              ))}
+            </select>
+            {rules.sortBy === "random" ? (
+              <Button
+                variant="ghost"
+                size="sm"
+                onClick={() =>
+                  setRules((prev) => ({ ...prev, seed: Date.now() }))
+                }
+              >
+                <Shuffle />
+                Reshuffle
+              </Button>
+            ) : (
+              <select
+                value={rules.sortDirection}
+                onChange={(e) =>
+                  setRules((prev) => ({
+                    ...prev,
+                    sortDirection: e.target
+                      .value as SmartPlaylistRules["sortDirection"],
+                  }))
+                }
+                className={selectClass}
+              >
+                <option value="asc">Ascending</option>
+                <option value="desc">Descending</option>
+              </select>
+            )}
+          </div>
+        </div>
+
+        <DialogFooter className="items-center gap-2">
+          <p className="text-sm text-vinyl-text-muted mr-auto">
+            {preview.length} song{preview.length !== 1 ? "s" : ""} •{" "}
+            {formatTotalDuration(previewDuration)}
+          </p>
+          <Button variant="outline" onClick={onClose}>
+            Cancel
+          </Button>
+          <Button onClick={handleSave} disabled={!name.trim() || isSaving}>
+            Save
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
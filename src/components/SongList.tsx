@@ -3,6 +3,7 @@ import { Music, Trash2, Play, Pause, Square, ListPlus } from "lucide-react";
 import type { Song, Playlist } from "../types";
 import { formatDuration } from "../lib/audioMetadata";
 import { tooltipProps } from "./Tooltip";
+import { ArtworkImage } from "./ArtworkImage";
 
 interface SongListProps {
   songs: Song[];
@@ -65,20 +66,19 @@ export function SongList({
             {/* Album art or track number */}
             <div
               className={`${compact ? "w-8 h-8" : "w-10 h-10"} flex items-center justify-center flex-shrink-0 rounded overflow-hidden relative ${
-                !song.coverArt ? "bg-vinyl-border" : ""
+                !song.artworkId ? "bg-vinyl-border" : ""
               }`}
             >
-              {song.coverArt ? (
-                <img
-                  src={song.coverArt}
-                  alt={song.album}
-                  className="w-full h-full object-cover"
-                />
-              ) : (
-                <Music
-                  className={`${compact ? "w-4 h-4" : "w-5 h-5"} text-vinyl-text-muted`}
-                />
-              )}
+              <ArtworkImage
+                artworkId={song.artworkId}
+                alt={song.album}
+                className="w-full h-full object-cover"
+                fallback={
+                  <Music
+                    className={`${compact ? "w-4 h-4" : "w-5 h-5"} text-vinyl-text-muted`}
+                  />
+                }
+              />
 
               {/* Playing indicator overlay */}
               {isCurrentlyPlaying && (
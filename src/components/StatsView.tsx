@@ -0,0 +1,319 @@
+import { useState, useEffect, useMemo } from "react";
+import {
+  BarChart3,
+  Clock,
+  Disc3,
+  Music,
+  Play,
+  SkipForward,
+  Sparkles,
+  Users,
+} from "lucide-react";
+import type { PlayRecord, SmartPlaylistRules, Song } from "../types";
+import { getPlaysSince } from "../lib/db";
+import {
+  STATS_PERIODS,
+  computeListeningStats,
+  getPeriodStart,
+  type RankedEntry,
+  type StatsPeriod,
+} from "../lib/playStats";
+import {
+  MOST_PLAYED_RULES,
+  RECENTLY_PLAYED_RULES,
+  evaluateSmartPlaylist,
+} from "../lib/smartPlaylists";
+import { tooltipProps } from "./Tooltip";
+import { ArtworkImage } from "./ArtworkImage";
+
+interface StatsViewProps {
+  songs: Song[];
+  currentSongId: string | null;
+  onPlaySong: (song: Song) => void;
+  onSaveSmartPlaylist: (
+    name: string,
+    rules: SmartPlaylistRules,
+  ) => Promise<void>;
+}
+
+function formatListeningTime(seconds: number): string {
+  const hours = Math.floor(seconds / 3600);
+  const minutes = Math.floor((seconds % 3600) / 60);
+  if (hours === 0) return `${minutes} min`;
+  return `${hours} hr ${minutes} min`;
+}
+
+function formatLastPlayed(timestamp: number): string {
+  return new Date(timestamp).toLocaleDateString(undefined, {
+    month: "short",
+    day: "numeric",
+  });
+}
+
+interface RankedListProps {
+  title: string;
+  icon: React.ReactNode;
+  entries: RankedEntry[];
+  onPlay?: (entry: RankedEntry) => void;
+}
+
+function RankedList({ title, icon, entries, onPlay }: RankedListProps) {
+  return (
+    <section className="bg-vinyl-surface rounded-xl p-4">
+      <h2 className="flex items-center gap-2 text-sm font-semibold text-vinyl-text-muted uppercase tracking-wide mb-3">
+        {icon}
+        {title}
+      </h2>
+      {entries.length === 0 ? (
+        <p className="text-sm text-vinyl-text-muted py-4 text-center">
+          Nothing played yet
+        </p>
+      ) : (
+        <ol className="space-y-1">
+          {entries.map((entry, index) => (
+            <li
+              key={entry.key}
+              className={`flex items-center gap-3 p-2 rounded-lg ${
+                onPlay ? "hover:bg-vinyl-border/50 cursor-pointer" : ""
+              }`}
+              onClick={() => onPlay?.(entry)}
+            >
+              <span className="w-5 text-right text-sm text-vinyl-text-muted">
+                {index + 1}
+              </span>
+              <ArtworkImage
+                artworkId={entry.song.artworkId}
+                alt=""
+                className="w-9 h-9 rounded object-cover flex-shrink-0"
+                fallback={
+                  <div className="w-9 h-9 rounded bg-vinyl-border flex items-center justify-center flex-shrink-0">
+                    <Music className="w-4 h-4 text-vinyl-text-muted" />
+                  </div>
+                }
+              />
+              <div className="flex-1 min-w-0">
+                <p className="text-sm text-vinyl-text truncate">
+                  {entry.label}
+                </p>
+                {entry.detail && (
+                  <p className="text-xs text-vinyl-text-muted truncate">
+                    {entry.detail}
+                  </p>
+                )}
+              </div>
+              <span className="text-xs text-vinyl-text-muted flex-shrink-0">
+                {entry.plays} play{entry.plays !== 1 ? "s" : ""}
+              </span>
+            </li>
+          ))}
+        </ol>
+      )}
+    </section>
+  );
+}
+
+interface SmartListProps {
+  title: string;
+  songs: Song[];
+  currentSongId: string | null;
+  describe: (song: Song) => string;
+  onPlaySong: (song: Song) => void;
+  onSave: () => void;
+}
+
+function SmartList({
+  title,
+  songs,
+  currentSongId,
+  describe,
+  onPlaySong,
+  onSave,
+}: SmartListProps) {
+  return (
+    <section className="bg-vinyl-surface rounded-xl p-4">
+      <div className="flex items-center justify-between mb-3">
+        <h2 className="text-sm font-semibold text-vinyl-text-muted uppercase tracking-wide">
+          {title}
+        </h2>
+        <button
+          onClick={onSave}
+          className="p-1.5 rounded-full hover:bg-vinyl-border transition-colors text-vinyl-text-muted hover:text-vinyl-accent"
+          {...tooltipProps("Save as smart playlist")}
+        >
+          <Sparkles className="w-4 h-4" />
+        </button>
+      </div>
+      {songs.length === 0 ? (
+        <p className="text-sm text-vinyl-text-muted py-4 text-center">
+          Nothing played yet
+        </p>
+      ) : (
+        <ul className="space-y-1">
+          {songs.slice(0, 10).map((song) => (
+            <li
+              key={song.id}
+              className="group flex items-center gap-3 p-2 rounded-lg hover:bg-vinyl-border/50 cursor-pointer"
+              onClick={() => onPlaySong(song)}
+            >
+              <Play className="w-4 h-4 text-vinyl-text-muted group-hover:text-vinyl-accent flex-shrink-0" />
+              <div className="flex-1 min-w-0">
+                <p
+                  className={`text-sm truncate ${
+                    song.id === currentSongId
+                      ? "text-vinyl-accent"
+                      : "text-vinyl-text"
+                  }`}
+                >
+                  {song.title}
+                </p>
+                <p className="text-xs text-vinyl-text-muted truncate">
+                  {song.artist}
+                </p>
+              </div>
+              <span className="text-xs text-vinyl-text-muted flex-shrink-0">
+                {describe(song)}
+              </span>
+            </li>
+          ))}
+        </ul>
+      )}
+    </section>
+  );
+}
+
+export function StatsView({
+  songs,
+  currentSongId,
+  onPlaySong,
+  onSaveSmartPlaylist,
+}: StatsViewProps) {
+  const [period, setPeriod] = useState<StatsPeriod>("month");
+  const [plays, setPlays] = useState<PlayRecord[]>([]);
+
+  // Reload when the period changes or a new listen updates a song
+  useEffect(() => {
+    let cancelled = false;
+    getPlaysSince(getPeriodStart(period, Date.now()))
+      .then((result) => {
+        if (!cancelled) setPlays(result);
+      })
+      .catch((error) => console.error("Failed to load play history:", error));
+    return () => {
+      cancelled = true;
+    };
+  }, [period, songs]);
+
+  const stats = useMemo(
+    () => computeListeningStats(plays, new Map(songs.map((s) => [s.id, s]))),
+    [plays, songs],
+  );
+  const mostPlayed = useMemo(
+    () => evaluateSmartPlaylist(MOST_PLAYED_RULES, songs),
+    [songs],
+  );
+  const recentlyPlayed = useMemo(
+    () => evaluateSmartPlaylist(RECENTLY_PLAYED_RULES, songs),
+    [songs],
+  );
+
+  return (
+    <div className="space-y-6">
+      {/* Period */}
+      <div className="flex items-center gap-1 bg-vinyl-surface rounded-lg p-1 w-fit">
+        {STATS_PERIODS.map((option) => (
+          <button
+            key={option.key}
+            onClick={() => setPeriod(option.key)}
+            className={`px-3 py-1.5 text-sm rounded-md transition-colors ${
+              period === option.key
+                ? "bg-vinyl-accent text-vinyl-bg"
+                : "text-vinyl-text-muted hover:text-vinyl-text"
+            }`}
+          >
+            {option.label}
+          </button>
+        ))}
+      </div>
+
+      {/* Totals */}
+      <div className="grid grid-cols-1 sm:grid-cols-3 gap-3">
+        {[
+          {
+            icon: <Clock className="w-5 h-5 text-vinyl-accent" />,
+            label: "Listening Time",
+            value: formatListeningTime(stats.totalListened),
+          },
+          {
+            icon: <BarChart3 className="w-5 h-5 text-vinyl-accent" />,
+            label: "Plays",
+            value: stats.plays.toLocaleString(),
+          },
+          {
+            icon: <SkipForward className="w-5 h-5 text-vinyl-accent" />,
+            label: "Skips",
+            value: stats.skips.toLocaleString(),
+          },
+        ].map((card) => (
+          <div
+            key={card.label}
+            className="flex items-center gap-3 bg-vinyl-surface rounded-xl p-4"
+          >
+            {card.icon}
+            <div>
+              <p className="text-xs text-vinyl-text-muted">{card.label}</p>
+              <p className="text-lg font-semibold text-vinyl-text">
+                {card.value}
+              </p>
+            </div>
+          </div>
+        ))}
+      </div>
+
+      {/* Top lists for the period */}
+      <div className="grid grid-cols-1 lg:grid-cols-3 gap-3">
+        <RankedList
+          title="Top Tracks"
+          icon={<Music className="w-4 h-4" />}
+          entries={stats.topTracks}
+          onPlay={(entry) => onPlaySong(entry.song)}
+        />
+        <RankedList
+          title="Top Artists"
+          icon={<Users className="w-4 h-4" />}
+          entries={stats.topArtists}
+        />
+        <RankedList
+          title="Top Albums"
+          icon={<Disc3 className="w-4 h-4" />}
+          entries={stats.topAlbums}
+        />
+      </div>
+
+      {/* All-time lists, also available as smart playlists */}
+      <div className="grid grid-cols-1 lg:grid-cols-2 gap-3">
+        <SmartList
+          title="Most Played"
+          songs={mostPlayed}
+          currentSongId={currentSongId}
+          describe={(song) =>
+            `${song.playCount} play${song.playCount !== 1 ? "s" : ""}`
+          }
+          onPlaySong={onPlaySong}
+          onSave={() => onSaveSmartPlaylist("Most Played", MOST_PLAYED_RULES)}
+        />
+        <SmartList
+          title="Recently Played"
+          songs={recentlyPlayed}
+          currentSongId={currentSongId}
+          describe={(song) =>
+            song.lastPlayedAt ? formatLastPlayed(song.lastPlayedAt) : ""
+          }
+          onPlaySong={onPlaySong}
+          onSave={() =>
+            onSaveSmartPlaylist("Recently Played", RECENTLY_PLAYED_RULES)
+          }
+        />
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,247 @@
+import { useEffect, useRef, useState } from "react";
+import { ImageOff, ImagePlus, Music, Tags } from "lucide-react";
+import type { Song, TagEdit } from "../types";
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from "@/components/ui/dialog";
+import { Button } from "@/components/ui/button";
+import {
+  TAG_EDIT_FIELDS,
+  buildTagEdit,
+  getTagFormValues,
+  hasTagChanges,
+  isTagNumberField,
+  parseTagNumber,
+  type TagEditField,
+  type TagFormValues,
+} from "../lib/tagEdits";
+import { isDesktop } from "../lib/platform";
+import { ArtworkImage } from "./ArtworkImage";
+
+interface TagEditorDialogProps {
+  isOpen: boolean;
+  // One song, or several for a batch edit
+  songs: Song[];
+  onSave: (edit: TagEdit) => Promise<void>;
+  onClose: () => void;
+}
+
+const inputClass =
+  "w-full px-2 py-1.5 bg-vinyl-bg border border-vinyl-border rounded text-vinyl-text text-sm min-w-0";
+
+function readAsDataUrl(file: File): Promise<string> {
+  return new Promise((resolve, reject) => {
+    const reader = new FileReader();
+    reader.onload = () => resolve(reader.result as string);
+    reader.onerror = () => reject(reader.error);
+    reader.readAsDataURL(file);
+  });
+}
+
+export function TagEditorDialog({
+  isOpen,
+  songs,
+  onSave,
+  onClose,
+}: TagEditorDialogProps) {
+  const fileInputRef = useRef<HTMLInputElement>(null);
+  const [values, setValues] = useState<TagFormValues | null>(null);
+  const [mixed, setMixed] = useState<Set<TagEditField>>(new Set());
+  const [changed, setChanged] = useState<Set<TagEditField>>(new Set());
+  // Data URL of a new cover, or "" when the artwork is being removed
+  const [coverArt, setCoverArt] = useState<string | undefined>();
+  const [isSaving, setIsSaving] = useState(false);
+
+  const isBatch = songs.length > 1;
+  const fields = TAG_EDIT_FIELDS.filter((f) => !isBatch || !f.singleOnly);
+  const artworkIds = new Set(songs.map((song) => song.artworkId));
+  const sharedArtworkId =
+    artworkIds.size === 1 ? songs[0]?.artworkId : undefined;
+
+  // Start from the songs' current tags each time the dialog opens
+  useEffect(() => {
+    if (!isOpen) return;
+    const form = getTagFormValues(songs);
+    setValues(form.values);
+    setMixed(form.mixed);
+    setChanged(new Set());
+    setCoverArt(undefined);
+  }, [isOpen, songs]);
+
+  const updateField = (field: TagEditField, value: string) => {
+    setValues((prev) => prev && { ...prev, [field]: value });
+    setChanged((prev) => new Set(prev).add(field));
+  };
+
+  const handleCoverChange = async (e: React.ChangeEvent<HTMLInputElement>) => {
+    const file = e.target.files?.[0];
+    e.target.value = "";
+    if (file) setCoverArt(await readAsDataUrl(file));
+  };
+
+  const invalidFields = new Set(
+    [...changed].filter(
+      (field) =>
+        values &&
+        isTagNumberField(field) &&
+        parseTagNumber(field, values[field]) === undefined,
+    ),
+  );
+
+  const edit: TagEdit = values
+    ? { ...buildTagEdit(values, changed), coverArt }
+    : {};
+  const canSave =
+    !isSaving && invalidFields.size === 0 && hasTagChanges(edit);
+
+  const handleSave = async () => {
+    if (!canSave) return;
+    setIsSaving(true);
+    try {
+      await onSave(edit);
+      onClose();
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  const renderCover = () => {
+    if (coverArt) {
+      return (
+        <img src={coverArt} alt="" className="w-full h-full object-cover" />
+      );
+    }
+    const placeholder = (
+      <div className="w-full h-full flex items-center justify-center">
+        <Music className="w-8 h-8 text-vinyl-text-muted" />
+      </div>
+    );
+    if (coverArt === "") return placeholder;
+    if (artworkIds.size > 1) {
+      return (
+        <div className="w-full h-full flex items-center justify-center p-2 text-center text-xs text-vinyl-text-muted">
+          Multiple covers
+        </div>
+      );
+    }
+    return (
+      <ArtworkImage
+        artworkId={sharedArtworkId}
+        className="w-full h-full object-cover"
+        fallback={placeholder}
+      />
+    );
+  };
+
+  return (
+    <Dialog
+      open={isOpen}
+      onOpenChange={(open) => !open && !isSaving && onClose()}
+    >
+      <DialogContent className="max-w-lg">
+        <DialogHeader>
+          <DialogTitle className="flex items-center gap-2">
+            <Tags className="w-5 h-5 text-vinyl-accent" />
+            {isBatch ? `Edit Tags (${songs.length} songs)` : "Edit Tags"}
+          </DialogTitle>
+          <DialogDescription>
+            {isDesktop()
+              ? "Changes are written to the files. The originals are backed up first."
+              : "Changes are saved to your library. The browser can't change the files themselves."}
+          </DialogDescription>
+        </DialogHeader>
+
+        {values && (
+          <div className="space-y-4 max-h-[60vh] overflow-y-auto pr-1">
+            {/* Artwork */}
+            <div className="flex items-center gap-4">
+              <div className="w-20 h-20 rounded-lg overflow-hidden flex-shrink-0 bg-vinyl-border">
+                {renderCover()}
+              </div>
+              <div className="flex flex-wrap gap-2">
+                <Button
+                  variant="outline"
+                  size="sm"
+                  onClick={() => fileInputRef.current?.click()}
+                >
+                  <ImagePlus />
+                  Choose Cover...
+                </Button>
+                <Button
+                  variant="ghost"
+                  size="sm"
+                  onClick={() => setCoverArt("")}
+                  disabled={
+                    coverArt === "" ||
+                    (!coverArt && songs.every((song) => !song.artworkId))
+                  }
+                >
+                  <ImageOff />
+                  Remove
+                </Button>
+                <input
+                  ref={fileInputRef}
+                  type="file"
+                  accept="image/jpeg,image/png"
+                  className="hidden"
+                  onChange={handleCoverChange}
+                />
+              </div>
+            </div>
+
+            {/* Fields */}
+            <div className="grid grid-cols-2 gap-3">
+              {fields.map(({ field, label }) => (
+                <label
+                  key={field}
+                  className={`space-y-1 ${
+                    isTagNumberField(field) ? "" : "col-span-2"
+                  }`}
+                >
+                  <span className="text-xs text-vinyl-text-muted">
+                    {label}
+                  </span>
+                  <input
+                    type="text"
+                    inputMode={
+                      isTagNumberField(field) ? "numeric" : undefined
+                    }
+                    value={values[field]}
+                    onChange={(e) => updateField(field, e.target.value)}
+                    placeholder={
+                      mixed.has(field) && !changed.has(field)
+                        ? "Multiple values"
+                        : undefined
+                    }
+                    className={`${inputClass} ${
+                      invalidFields.has(field) ? "border-red-400" : ""
+                    }`}
+                  />
+                </label>
+              ))}
+            </div>
+            {isBatch && (
+              <p className="text-xs text-vinyl-text-muted">
+                Only the fields you change are applied to every song.
+              </p>
+            )}
+          </div>
+        )}
+
+        <DialogFooter>
+          <Button variant="outline" onClick={onClose} disabled={isSaving}>
+            Cancel
+          </Button>
+          <Button onClick={handleSave} disabled={!canSave}>
+            {isSaving ? "Saving..." : "Save"}
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
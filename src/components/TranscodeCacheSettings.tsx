@@ -0,0 +1,207 @@
+import { useCallback, useEffect, useState } from "react";
+import { Database, Trash2, Loader2, ChevronDown, ChevronUp } from "lucide-react";
+import { tooltipProps } from "./Tooltip";
+import { ConfirmDialog } from "./ConfirmDialog";
+import { formatFileSize } from "../lib/audioMetadata";
+import {
+  getTranscodeCacheInfo,
+  clearTranscodeCache,
+  setTranscodeCacheMaxSize,
+  type TranscodeCacheInfo,
+} from "../lib/platform";
+
+const GB = 1024 * 1024 * 1024;
+
+const CACHE_SIZE_OPTIONS = [
+  { label: "512 MB", value: GB / 2 },
+  { label: "1 GB", value: GB },
+  { label: "2 GB", value: 2 * GB },
+  { label: "5 GB", value: 5 * GB },
+  { label: "10 GB", value: 10 * GB },
+];
+
+// Entries shown before "Show all"
+const PREVIEW_COUNT = 5;
+
+// Refresh interval while transcodes are running
+const ACTIVE_REFRESH_MS = 2000;
+
+function getFileName(filePath: string): string {
+  const parts = filePath.split(/[/\\]/);
+  return parts[parts.length - 1] || filePath;
+}
+
+export function TranscodeCacheSettings() {
+  const [info, setInfo] = useState<TranscodeCacheInfo | null>(null);
+  const [isClearing, setIsClearing] = useState(false);
+  const [showClearConfirm, setShowClearConfirm] = useState(false);
+  const [showAll, setShowAll] = useState(false);
+
+  const refresh = useCallback(async () => {
+    setInfo(await getTranscodeCacheInfo());
+  }, []);
+
+  useEffect(() => {
+    refresh();
+  }, [refresh]);
+
+  // Keep sizes current while something is being transcoded
+  useEffect(() => {
+    if (!info?.activeTranscodes) return;
+    const timer = setTimeout(refresh, ACTIVE_REFRESH_MS);
+    return () => clearTimeout(timer);
+  }, [info, refresh]);
+
+  const handleClear = async () => {
+    setShowClearConfirm(false);
+    setIsClearing(true);
+    const updated = await clearTranscodeCache();
+    if (updated) setInfo(updated);
+    setIsClearing(false);
+  };
+
+  const handleMaxSizeChange = async (maxSize: number) => {
+    const updated = await setTranscodeCacheMaxSize(maxSize);
+    if (updated) setInfo(updated);
+  };
+
+  // Older desktop shells don't expose the cache
+  if (!info) return null;
+
+  const usedPercent = Math.min(100, (info.totalSize / info.maxSize) * 100);
+  const sizeOptions = CACHE_SIZE_OPTIONS.some((o) => o.value === info.maxSize)
+    ? CACHE_SIZE_OPTIONS
+    : [...CACHE_SIZE_OPTIONS, { label: formatFileSize(info.maxSize), value: info.maxSize }];
+  const visibleEntries = showAll
+    ? info.entries
+    : info.entries.slice(0, PREVIEW_COUNT);
+
+  return (
+    <div>
+      <div className="flex items-center justify-between mb-3">
+        <h3 className="text-vinyl-text font-medium flex items-center gap-2">
+          <Database className="w-4 h-4 text-vinyl-accent" />
+          Transcode Cache
+        </h3>
+        <button
+          onClick={() => setShowClearConfirm(true)}
+          disabled={isClearing || info.entries.length === 0}
+          className="flex items-center gap-2 px-3 py-1.5 text-sm bg-vinyl-border text-vinyl-text rounded-lg hover:bg-vinyl-border/70 transition-colors disabled:opacity-50"
+          {...tooltipProps("Delete cached transcodes")}
+        >
+          {isClearing ? (
+            <Loader2 className="w-4 h-4 animate-spin" />
+          ) : (
+            <Trash2 className="w-4 h-4" />
+          )}
+          Clear Cache
+        </button>
+      </div>
+
+      <div className="p-3 bg-vinyl-border/30 rounded-lg space-y-3">
+        <p className="text-sm text-vinyl-text-muted">
+          Formats your system can't play directly are converted to FLAC while
+          they play and kept here, so they start instantly next time.
+        </p>
+
+        <div>
+          <div className="flex items-center justify-between text-sm mb-1">
+            <span className="text-vinyl-text">
+              {formatFileSize(info.totalSize)} of {formatFileSize(info.maxSize)}
+            </span>
+            <span className="text-vinyl-text-muted">
+              {info.entries.length} file{info.entries.length !== 1 ? "s" : ""}
+              {info.activeTranscodes > 0 &&
+                ` · ${info.activeTranscodes} converting`}
+            </span>
+          </div>
+          <div className="h-2 bg-vinyl-border rounded-full overflow-hidden">
+            <div
+              className="h-full bg-vinyl-accent transition-all duration-300"
+              style={{ width: `${usedPercent}%` }}
+            />
+          </div>
+        </div>
+
+        <div className="flex items-center justify-between">
+          <div>
+            <p className="text-sm text-vinyl-text">Size limit</p>
+            <p className="text-xs text-vinyl-text-muted">
+              Least recently played files are removed first
+            </p>
+          </div>
+          <select
+            value={info.maxSize}
+            onChange={(e) => handleMaxSizeChange(Number(e.target.value))}
+            className="px-3 py-1.5 bg-vinyl-border text-vinyl-text rounded text-sm border-0 cursor-pointer"
+          >
+            {sizeOptions.map((option) => (
+              <option key={option.value} value={option.value}>
+                {option.label}
+              </option>
+            ))}
+          </select>
+        </div>
+
+        {info.entries.length > 0 && (
+          <div className="space-y-1">
+            {visibleEntries.map((entry) => (
+              <div
+                key={entry.sourcePath}
+                className="flex items-center justify-between gap-3 text-xs"
+              >
+                <span
+                  className="text-vinyl-text truncate"
+                  title={entry.sourcePath}
+                >
+                  {getFileName(entry.sourcePath)}
+                </span>
+                <span className="text-vinyl-text-muted flex-shrink-0">
+                  {formatFileSize(entry.size)} ·{" "}
+                  {new Date(entry.lastAccessed).toLocaleDateString()}
+                </span>
+              </div>
+            ))}
+            {info.entries.length > PREVIEW_COUNT && (
+              <button
+                onClick={() => setShowAll((value) => !value)}
+                className="flex items-center gap-1 text-xs text-vinyl-accent hover:underline"
+              >
+                {showAll ? (
+                  <>
+                    <ChevronUp className="w-3 h-3" />
+                    Show less
+                  </>
+                ) : (
+                  <>
+                    <ChevronDown className="w-3 h-3" />
+                    Show all {info.entries.length}
+                  </>
+                )}
+              </button>
+            )}
+          </div>
+        )}
+
+        <p
+          className="text-xs text-vinyl-text-muted truncate"
+          title={info.directory}
+        >
+          {info.directory}
+        </p>
+      </div>
+
+      <ConfirmDialog
+        isOpen={showClearConfirm}
+        title="Clear Transcode Cache?"
+        message={`Delete ${formatFileSize(info.totalSize)} of cached transcodes?`}
+        warningText="Your music files are not affected. Files will be converted again the next time they play."
+        confirmLabel="Clear Cache"
+        cancelLabel="Cancel"
+        variant="danger"
+        onConfirm={handleClear}
+        onCancel={() => setShowClearConfirm(false)}
+      />
+    </div>
+  );
+}
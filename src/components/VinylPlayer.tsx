@@ -1,5 +1,6 @@
 import { useRef, useEffect } from "react";
 import type { Song, PlaybackState } from "../types";
+import { useArtworkUrl } from "../hooks/useArtworkUrl";
 
 interface VinylPlayerProps {
   currentSong: Song | undefined;
@@ -17,6 +18,8 @@ export function VinylPlayer({
   showAlbumArt = true,
 }: VinylPlayerProps) {
   const vinylRef = useRef<HTMLDivElement>(null);
+  // The label is small enough for the thumbnail
+  const labelArtUrl = useArtworkUrl(currentSong?.artworkId);
 
   // Calculate spin duration based on speed (base is 3s at 1x speed)
   const spinDuration = 3 / speed;
@@ -153,7 +156,7 @@ export function VinylPlayer({
           />
 
           {/* Label - with album art if available and setting enabled */}
-          {currentSong?.coverArt && showAlbumArt ? (
+          {labelArtUrl && showAlbumArt ? (
             <>
               {/* Clip path for circular album art */}
               <defs>
@@ -163,7 +166,7 @@ export function VinylPlayer({
               </defs>
               {/* Album art as label */}
               <image
-                href={currentSong.coverArt}
+                href={labelArtUrl}
                 x="140"
                 y="140"
                 width="120"
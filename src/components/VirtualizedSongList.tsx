@@ -10,12 +10,15 @@ import {
   AlertTriangle,
   Heart,
   ListPlus,
+  FolderSearch,
+  PenLine,
 } from "lucide-react";
 import type { Song, Playlist } from "../types";
 import { formatDuration } from "../lib/audioMetadata";
 import { ConfirmDialog } from "./ConfirmDialog";
 import { AddToPlaylistPopover } from "./AddToPlaylistPopover";
 import { tooltipProps } from "./Tooltip";
+import { ArtworkImage } from "./ArtworkImage";
 
 interface VirtualizedSongListProps {
   songs: Song[];
@@ -31,8 +34,12 @@ interface VirtualizedSongListProps {
   onCreatePlaylist?: (name: string) => Promise<string | void>;
   unavailableSongIds?: Set<string>;
   onDeleteSong?: (songId: string) => void;
+  /** Desktop: look for missing files in another folder */
+  onRelinkMissing?: () => void;
   favoriteSongIds?: Set<string>;
   onToggleFavorite?: (songId: string) => void;
+  /** Open the tag editor for a song */
+  onEditTags?: (song: Song) => void;
   /** Skip delete confirmation dialog */
   skipDeleteConfirmation?: boolean;
   /** Callback when user chooses to skip confirmation */
@@ -55,6 +62,8 @@ interface SongRowProps {
   onCreatePlaylist?: (name: string) => Promise<string | void>;
   onToggleFavorite?: (songId: string) => void;
   onShowDeleteDialog: (songId: string) => void;
+  onRelinkMissing?: () => void;
+  onEditTags?: (song: Song) => void;
 }
 
 // Memoized song row component to prevent unnecessary re-renders
@@ -73,6 +82,8 @@ const SongRow = memo(function SongRow({
   onCreatePlaylist,
   onToggleFavorite,
   onShowDeleteDialog,
+  onRelinkMissing,
+  onEditTags,
 }: SongRowProps) {
   const isCurrentlyPlaying = isCurrentSong && isPlaying;
 
@@ -98,13 +109,31 @@ const SongRow = memo(function SongRow({
           >
             {song.title}
           </p>
-          <p className="text-xs text-red-400/70 truncate">
-            Audio unavailable - re-import or delete
+          <p
+            className="text-xs text-red-400/70 truncate"
+            title={song.filePath}
+          >
+            {song.filePath
+              ? "File not found - relink or delete"
+              : "Audio unavailable - re-import or delete"}
           </p>
         </div>
 
         {/* Action buttons */}
         <div className="flex items-center gap-1">
+          {song.filePath && onRelinkMissing && (
+            <button
+              onClick={(e) => {
+                e.stopPropagation();
+                onRelinkMissing();
+              }}
+              className="flex items-center gap-1.5 px-3 py-1.5 rounded-lg bg-vinyl-border/50 hover:bg-vinyl-border transition-colors text-vinyl-text text-sm font-medium"
+              {...tooltipProps("Search a folder for missing files")}
+            >
+              <FolderSearch className="w-4 h-4" />
+              Relink
+            </button>
+          )}
           <button
             onClick={(e) => {
               e.stopPropagation();
@@ -138,21 +167,20 @@ const SongRow = memo(function SongRow({
       {/* Album art or track number */}
       <div
         className={`${compact ? "w-8 h-8" : "w-10 h-10"} flex items-center justify-center flex-shrink-0 rounded overflow-hidden relative ${
-          !song.coverArt ? "bg-vinyl-border" : ""
+          !song.artworkId ? "bg-vinyl-border" : ""
         }`}
       >
-        {song.coverArt ? (
-          <img
-            src={song.coverArt}
-            alt={song.album}
-            className="w-full h-full object-cover"
-            loading="lazy"
-          />
-        ) : (
-          <Music
-            className={`${compact ? "w-4 h-4" : "w-5 h-5"} text-vinyl-text-muted`}
-          />
-        )}
+        <ArtworkImage
+          artworkId={song.artworkId}
+          alt={song.album}
+          className="w-full h-full object-cover"
+          loading="lazy"
+          fallback={
+            <Music
+              className={`${compact ? "w-4 h-4" : "w-5 h-5"} text-vinyl-text-muted`}
+            />
+          }
+        />
 
         {/* Playing indicator overlay */}
         {isCurrentlyPlaying && (
@@ -288,6 +316,20 @@ const SongRow = memo(function SongRow({
           </div>
         )}
 
+        {/* Edit tags */}
+        {!compact && onEditTags && (
+          <button
+            onClick={(e) => {
+              e.stopPropagation();
+              onEditTags(song);
+            }}
+            className="p-2 rounded-full hover:bg-vinyl-border transition-colors text-vinyl-text-muted hover:text-vinyl-accent"
+            {...tooltipProps("Edit tags")}
+          >
+            <PenLine className="w-4 h-4" />
+          </button>
+        )}
+
         {/* Delete button */}
         {!compact && (
           <button
@@ -327,8 +369,10 @@ export function VirtualizedSongList({
   onCreatePlaylist,
   unavailableSongIds = new Set(),
   onDeleteSong,
+  onRelinkMissing,
   favoriteSongIds = new Set(),
   onToggleFavorite,
+  onEditTags,
   skipDeleteConfirmation = false,
   onSkipDeleteConfirmationChange,
 }: VirtualizedSongListProps) {
@@ -480,6 +524,8 @@ export function VirtualizedSongList({
                   onCreatePlaylist={onCreatePlaylist}
                   onToggleFavorite={onToggleFavorite}
                   onShowDeleteDialog={handleShowDeleteDialog}
+                  onRelinkMissing={onRelinkMissing}
+                  onEditTags={onEditTags}
                 />
               </div>
             );
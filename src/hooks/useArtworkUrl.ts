@@ -0,0 +1,35 @@
+import { useEffect, useState } from "react";
+import {
+  getCachedArtworkUrl,
+  loadArtworkUrl,
+  type ArtworkVariant,
+} from "../lib/artwork";
+
+// Object URL for a song's artwork, once it has been read from the store
+export function useArtworkUrl(
+  artworkId: string | undefined,
+  variant: ArtworkVariant = "thumbnail",
+): string | undefined {
+  const cached = artworkId
+    ? getCachedArtworkUrl(artworkId, variant)
+    : undefined;
+  const [loaded, setLoaded] = useState<{ key: string; url?: string } | null>(
+    null,
+  );
+  const key = `${variant}:${artworkId}`;
+
+  useEffect(() => {
+    if (!artworkId || cached) return;
+
+    let cancelled = false;
+    loadArtworkUrl(artworkId, variant).then((url) => {
+      if (!cancelled) setLoaded({ key: `${variant}:${artworkId}`, url });
+    });
+    return () => {
+      cancelled = true;
+    };
+  }, [artworkId, variant, cached]);
+
+  if (!artworkId) return undefined;
+  return cached ?? (loaded?.key === key ? loaded.url : undefined);
+}
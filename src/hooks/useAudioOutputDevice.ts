@@ -0,0 +1,42 @@
+import { useState, useEffect } from "react";
+
+// Chromium lists the system default output as "Default - <device name>"
+const DEFAULT_PREFIX = /^Default - /;
+
+/**
+ * Name of the system's default audio output (the player doesn't pick one)
+ * Updates when devices are plugged in or switched. Null where the browser
+ * doesn't expose device names.
+ */
+export function useAudioOutputDevice(): string | null {
+  const [device, setDevice] = useState<string | null>(null);
+
+  useEffect(() => {
+    const mediaDevices = navigator.mediaDevices;
+    if (!mediaDevices?.enumerateDevices) return;
+    let cancelled = false;
+
+    const update = async () => {
+      try {
+        const outputs = (await mediaDevices.enumerateDevices()).filter(
+          (d) => d.kind === "audiooutput",
+        );
+        const current =
+          outputs.find((d) => d.deviceId === "default") ?? outputs[0];
+        const name = current?.label.replace(DEFAULT_PREFIX, "").trim();
+        if (!cancelled) setDevice(name || null);
+      } catch (error) {
+        console.error("Failed to list audio outputs:", error);
+      }
+    };
+
+    void update();
+    mediaDevices.addEventListener("devicechange", update);
+    return () => {
+      cancelled = true;
+      mediaDevices.removeEventListener("devicechange", update);
+    };
+  }, []);
+
+  return device;
+}
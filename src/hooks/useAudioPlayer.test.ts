@@ -10,14 +10,16 @@ vi.mock('../lib/db', () => ({
 }));
 
 vi.mock('./useSongs', () => ({
-  getCachedFile: vi.fn(),
+  readNativeMetadataMap: vi.fn().mockResolvedValue(new Map()),
+  resolveSongFile: vi.fn().mockResolvedValue(undefined),
   setCachedFile: vi.fn(),
 }));
 
 vi.mock('../lib/platform', () => ({
   isDesktop: vi.fn().mockReturnValue(false),
-  getAssetUrl: vi.fn(),
+  getPlaybackUrl: vi.fn(),
   fileExists: vi.fn().mockResolvedValue(false),
+  readMediaData: vi.fn().mockResolvedValue(null),
 }));
 
 vi.mock('../lib/audioMetadata', () => {
@@ -32,7 +34,7 @@ vi.mock('../lib/audioMetadata', () => {
       artist: 'Quick Play Artist',
       album: 'Quick Play Album',
       duration: 200,
-      coverArt: undefined,
+      artworkId: undefined,
     }),
     isAudioFile: (file: File) => {
       const ext = file.name.toLowerCase().slice(file.name.lastIndexOf('.'));
@@ -1,5 +1,6 @@
 import { useState, useRef, useEffect, useCallback } from "react";
 import type {
+  AudibleRange,
   Song,
   PlayerState,
   PlaybackState,
@@ -7,9 +8,46 @@ import type {
   AppSettings,
 } from "../types";
 import { savePlayerState, getPlayerState } from "../lib/db";
-import { getCachedFile, setCachedFile } from "./useSongs";
-import { isDesktop, getAssetUrl, fileExists } from "../lib/platform";
+import {
+  readNativeMetadataMap,
+  resolveSongFile,
+  setCachedFile,
+} from "./useSongs";
+import { useSongPitchMemory } from "./useSongPitchMemory";
+import {
+  isDesktop,
+  getPlaybackUrl,
+  fileExists,
+  readMediaData,
+} from "../lib/platform";
 import { extractMetadata, isAudioFile, generateId } from "../lib/audioMetadata";
+import { pickTagFields } from "../lib/songMetadata";
+import {
+  connectAudioElement,
+  getPitchShifter,
+  getSharedAudioContext,
+  peekSharedAudioContext,
+} from "../lib/audioContext";
+import {
+  clampSemitones,
+  getPitchRatio,
+  isPitchShiftSupported,
+} from "../lib/pitchShift";
+import { getNormalizationGain } from "../lib/replayGain";
+import type { PlaybackAdjustments } from "../lib/playbackProfiles";
+import {
+  GaplessEngine,
+  decodeGaplessBuffer,
+  type GaplessTrack,
+} from "../lib/gapless";
+import {
+  MIN_CROSSFADE_DURATION,
+  equalPowerCurve,
+  findAudibleRange,
+  isSameAlbumSequence,
+  planCrossfade,
+  type CrossfadePlan,
+} from "../lib/crossfade";
 
 const defaultPlayerState: PlayerState = {
   currentSongId: null,
@@ -24,7 +62,51 @@ const defaultPlayerState: PlayerState = {
   currentPlaylistId: null,
 };
 
-export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
+// Gapless playback decodes whole tracks, about 23 MB per minute at 48 kHz
+// stereo; longer ones use the <audio> element
+const MAX_GAPLESS_DURATION = 8 * 60;
+
+// How often to report the position of decoded-buffer playback
+const GAPLESS_TIME_UPDATE_MS = 250;
+
+// Songs without a measured audible range longer than this crossfade over
+// their full length instead of being decoded to find their silence
+const MAX_SILENCE_SCAN_DURATION = 30 * 60;
+
+function updateMediaSession(song: Song) {
+  if ("mediaSession" in navigator) {
+    try {
+      navigator.mediaSession.metadata = new MediaMetadata({
+        title: song.title,
+        artist: song.artist,
+        album: song.album,
+      });
+    } catch {
+      // Ignore Media Session errors
+    }
+  }
+}
+
+// Read a song's bytes for decoding (desktop file over the streaming
+// protocol, then memory cache or a remembered web folder)
+async function readGaplessData(song: Song): Promise<ArrayBuffer | null> {
+  if (isDesktop() && song.filePath) {
+    const data = await readMediaData(song.filePath);
+    if (data) return data;
+  }
+  const cachedFile = await resolveSongFile(song);
+  return cachedFile ? await cachedFile.arrayBuffer() : null;
+}
+
+export function useAudioPlayer(
+  songs: Song[],
+  settings?: AppSettings,
+  // Speed and gain offset of the playback profiles matching a song
+  getPlaybackProfile?: (
+    song: Song,
+    playlistId: string | null,
+  ) => PlaybackAdjustments,
+) {
   const audioRef = useRef<HTMLAudioElement | null>(null);
   const nextAudioRef = useRef<HTMLAudioElement | null>(null); // For crossfade
   const [playerState, setPlayerState] =
@@ -38,14 +120,54 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
   const savedPositionRef = useRef<number>(0);
   const isLoadingRef = useRef(false); // Prevent concurrent loads
   const crossfadeInProgressRef = useRef(false); // Track if crossfade is happening
-  const crossfadeIntervalRef = useRef<ReturnType<typeof setInterval> | null>(
+  const crossfadeRequestRef = useRef(0); // Bumped to cancel a crossfade still loading
+  // Fade under way: finish() jumps to the incoming track, cancel() keeps the outgoing one
+  const crossfadeRef = useRef<{ finish: () => void; cancel: () => void } | null>(
     null,
   );
+  // Queue item the crossfade will move to (kept so shuffle doesn't re-pick)
+  const crossfadeNextRef = useRef<{
+    currentId: string;
+    index: number;
+    songId: string;
+  } | null>(null);
+  // Audible part of songs without a stored range, by id; null until measured
+  const audibleRangesRef = useRef(new Map<string, AudibleRange | null>());
+  const gaplessRef = useRef<GaplessEngine | null>(null);
+  // Decoded tracks by song id: the current one and the one queued after it
+  const gaplessTracksRef = useRef(
+    new Map<string, Promise<GaplessTrack | null>>(),
+  );
+  const gaplessNextIndexRef = useRef<number | null>(null); // Queue index of the queued track
+  const gaplessRequestRef = useRef(0); // Bumped to cancel in-flight decodes
+  const gaplessEndedRef = useRef<() => void>(() => {});
 
   // Use refs for values needed in event handlers to avoid stale closures
   const playerStateRef = useRef(playerState);
   const songsRef = useRef(songs);
   const settingsRef = useRef(settings);
+  const getPlaybackProfileRef = useRef(getPlaybackProfile);
+  // Speed picked by hand while a profile's speed applied, for that song only
+  const [speedOverride, setSpeedOverride] = useState<{
+    songId: string;
+    speed: number;
+  } | null>(null);
+  const speedOverrideRef = useRef(speedOverride);
+  // Key and tempo remembered for songs
+  const {
+    memory: pitchMemory,
+    remember: rememberSongPitch,
+    forget: forgetSongPitch,
+  } = useSongPitchMemory();
+  const pitchMemoryRef = useRef(pitchMemory);
+  // Transposition of a song that isn't remembered, for that song only
+  const [pitchOverride, setPitchOverride] = useState<{
+    songId: string;
+    semitones: number;
+  } | null>(null);
+  const [pitchShifter, setPitchShifter] = useState<AudioWorkletNode | null>(
+    null,
+  );
 
   // Keep refs in sync
   useEffect(() => {
@@ -56,10 +178,156 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
     songsRef.current = songs;
   }, [songs]);
 
+  useEffect(() => {
+    getPlaybackProfileRef.current = getPlaybackProfile;
+  }, [getPlaybackProfile]);
+
+  useEffect(() => {
+    speedOverrideRef.current = speedOverride;
+  }, [speedOverride]);
+
+  useEffect(() => {
+    pitchMemoryRef.current = pitchMemory;
+  }, [pitchMemory]);
+
+  // Playback rate for a song: remembered for it, picked by hand for it, else
+  // its profile's, else the player's
+  const getSongSpeed = useCallback((song: Song) => {
+    const remembered = pitchMemoryRef.current[song.id];
+    if (remembered) return remembered.speed;
+    const override = speedOverrideRef.current;
+    if (override?.songId === song.id) return override.speed;
+    const state = playerStateRef.current;
+    return (
+      getPlaybackProfileRef.current?.(song, state.currentPlaylistId).speed ??
+      state.speed
+    );
+  }, []);
+
   useEffect(() => {
     settingsRef.current = settings;
   }, [settings]);
 
+  // Crossfade overlaps tracks, so it takes precedence over gapless
+  const isGaplessEnabled = useCallback(() => {
+    const appSettings = settingsRef.current;
+    return (
+      !!appSettings?.gaplessPlayback && !(appSettings.crossfadeDuration > 0)
+    );
+  }, []);
+
+  // Drop decoded tracks other than the given ones
+  const pruneGaplessTracks = useCallback((...keep: (string | null)[]) => {
+    for (const id of gaplessTracksRef.current.keys()) {
+      if (!keep.includes(id)) gaplessTracksRef.current.delete(id);
+    }
+  }, []);
+
+  const getGaplessEngine = useCallback((): GaplessEngine | null => {
+    if (gaplessRef.current) return gaplessRef.current;
+    if (!audioRef.current) return null;
+
+    // Feed the same analyser/EQ chain as the <audio> element
+    const shared = getSharedAudioContext(audioRef.current);
+    if (!shared) return null;
+
+    const engine = new GaplessEngine(shared);
+    engine.onAdvance = (songId) => {
+      const index = gaplessNextIndexRef.current;
+      gaplessNextIndexRef.current = null;
+      setPlayerState((prev) => ({
+        ...prev,
+        currentSongId: songId,
+        queueIndex: index ?? prev.queue.indexOf(songId),
+      }));
+      setCurrentTime(0);
+      setDuration(engine.duration);
+
+      // Only the new current track needs to stay decoded
+      pruneGaplessTracks(songId);
+
+      const song = songsRef.current.find((s) => s.id === songId);
+      if (song) updateMediaSession(song);
+    };
+    engine.onEnded = () => gaplessEndedRef.current();
+    gaplessRef.current = engine;
+    return engine;
+  }, [pruneGaplessTracks]);
+
+  // Decode a song for the gapless engine (shared between play and preload)
+  const loadGaplessTrack = useCallback(
+    (song: Song, engine: GaplessEngine): Promise<GaplessTrack | null> => {
+      const cache = gaplessTracksRef.current;
+      const pending = cache.get(song.id);
+      if (pending) return pending;
+      if (song.duration > MAX_GAPLESS_DURATION) return Promise.resolve(null);
+
+      const promise = (async () => {
+        const data = await readGaplessData(song);
+        if (!data) return null;
+        const buffer = await decodeGaplessBuffer(engine.audioContext, data);
+        return { id: song.id, buffer };
+      })().catch((error) => {
+        console.warn("[Gapless] Decode failed, using <audio>:", error);
+        cache.delete(song.id);
+        return null;
+      });
+      cache.set(song.id, promise);
+      return promise;
+    },
+    [],
+  );
+
+  // Stop decoded-buffer playback and cancel any decode waiting to start
+  const stopGapless = useCallback(() => {
+    gaplessRequestRef.current++;
+    gaplessNextIndexRef.current = null;
+    gaplessRef.current?.stop();
+  }, []);
+
+  // Start a song on the gapless engine
+  // Returns false if it can't be decoded, so the caller falls back to <audio>
+  const playGapless = useCallback(
+    async (song: Song): Promise<boolean> => {
+      const engine = getGaplessEngine();
+      if (!engine) return false;
+
+      stopGapless();
+      const request = gaplessRequestRef.current;
+      audioRef.current?.pause();
+      pruneGaplessTracks(song.id);
+
+      const track = await loadGaplessTrack(song, engine);
+      // Another song was started while this one decoded
+      if (request !== gaplessRequestRef.current) return true;
+      if (!track) return false;
+
+      engine.setVolume(playerStateRef.current.volume);
+      engine.setPlaybackRate(getSongSpeed(song));
+      engine.play(track);
+      setCurrentTime(0);
+      setDuration(engine.duration);
+      setPlaybackState("playing");
+      updateMediaSession(song);
+      return true;
+    },
+    [
+      getGaplessEngine,
+      loadGaplessTrack,
+      pruneGaplessTracks,
+      stopGapless,
+      getSongSpeed,
+    ],
+  );
+
+  // Drop a crossfade that is loading or under way, keeping the outgoing track
+  const cancelCrossfade = useCallback(() => {
+    crossfadeRequestRef.current++;
+    crossfadeRef.current?.cancel();
+    crossfadeRef.current = null;
+    crossfadeInProgressRef.current = false;
+  }, []);
+
   // Play song at index - defined early so it can be used by handleSongEnd
   const playSongAtIndex = useCallback(async (index: number) => {
     const state = playerStateRef.current;
@@ -72,6 +340,8 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
     const audio = audioRef.current;
 
     // Stop and reset current audio before loading new source
+    stopGapless();
+    cancelCrossfade();
     audio.pause();
     audio.currentTime = 0;
 
@@ -102,24 +372,28 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
     setPlaybackState("buffering");
 
     try {
+      if (isGaplessEnabled() && (await playGapless(song))) {
+        return;
+      }
+
       // Try to get audio source
       let audioUrl: string | null = null;
 
-      // On desktop with file path, read file and create blob URL
+      // On desktop with file path, stream from disk (blob URL fallback)
       if (isDesktop() && song.filePath) {
         const exists = await fileExists(song.filePath);
         if (exists) {
-          audioUrl = await getAssetUrl(song.filePath);
-          // getAssetUrl now returns blob URLs that need cleanup
-          if (audioUrl) {
+          audioUrl = await getPlaybackUrl(song.filePath);
+          // Only blob URLs need cleanup
+          if (audioUrl?.startsWith("blob:")) {
             objectUrlRef.current = audioUrl;
           }
         }
       }
 
-      // Fall back to memory cache (web or desktop without filePath)
+      // Fall back to memory cache or a remembered web folder
       if (!audioUrl) {
-        const cachedFile = getCachedFile(song.id);
+        const cachedFile = await resolveSongFile(song);
         if (cachedFile) {
           audioUrl = URL.createObjectURL(cachedFile);
           objectUrlRef.current = audioUrl;
@@ -137,7 +411,7 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
       await new Promise((resolve) => setTimeout(resolve, 50));
 
       audio.src = audioUrl;
-      audio.playbackRate = state.speed;
+      audio.playbackRate = getSongSpeed(song);
 
       // Setup Media Session (skip artwork to avoid potential issues with large images)
       if ("mediaSession" in navigator) {
@@ -182,7 +456,13 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
       setPlaybackState("idle");
       setPlayerState((prev) => ({ ...prev, isPlaying: false }));
     }
-  }, []);
+  }, [
+    cancelCrossfade,
+    isGaplessEnabled,
+    playGapless,
+    stopGapless,
+    getSongSpeed,
+  ]);
 
   // Get next song index based on shuffle and repeat settings
   const getNextIndex = useCallback((state: PlayerState): number | null => {
@@ -224,19 +504,218 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
     }
   }, []);
 
+  // Queue index the crossfade will move to
+  // Shuffle picks at random, so a pick is kept while it is still in the queue
+  const getCrossfadeNextIndex = useCallback(
+    (state: PlayerState): number | null => {
+      const picked = crossfadeNextRef.current;
+      if (
+        state.shuffle &&
+        picked &&
+        picked.currentId === state.currentSongId &&
+        state.queue[picked.index] === picked.songId
+      ) {
+        return picked.index;
+      }
+
+      const index = getNextIndex(state);
+      crossfadeNextRef.current =
+        index !== null && state.currentSongId
+          ? {
+              currentId: state.currentSongId,
+              index,
+              songId: state.queue[index],
+            }
+          : null;
+      return index;
+    },
+    [getNextIndex],
+  );
+
+  // Decode a song once to find its leading and trailing silence, for songs
+  // the desktop analysis hasn't covered (web imports)
+  const measureAudibleRange = useCallback((song: Song) => {
+    const ranges = audibleRangesRef.current;
+    if (
+      song.audibleRange ||
+      ranges.has(song.id) ||
+      song.duration > MAX_SILENCE_SCAN_DURATION
+    ) {
+      return;
+    }
+    const shared = audioRef.current
+      ? getSharedAudioContext(audioRef.current)
+      : null;
+    if (!shared) return;
+
+    // Unknown until decoded, the crossfade uses the full length meanwhile
+    ranges.set(song.id, null);
+    readGaplessData(song)
+      .then((data) => (data ? shared.audioContext.decodeAudioData(data) : null))
+      .then((buffer) => {
+        if (buffer && ranges.has(song.id)) {
+          ranges.set(song.id, findAudibleRange(buffer));
+        }
+      })
+      .catch((error) => {
+        console.warn("[Crossfade] Silence detection failed:", error);
+      });
+  }, []);
+
+  // Measure the silence around the coming transition ahead of time: the end
+  // of the current song and the start of the one after it
+  useEffect(() => {
+    const state = playerStateRef.current;
+    const crossfadeDuration = settings?.crossfadeDuration ?? 0;
+    if (
+      crossfadeDuration <= 0 ||
+      settings?.autoPlay === false ||
+      state.repeat === "one" ||
+      !state.currentSongId
+    ) {
+      return;
+    }
+
+    const nextIndex = getCrossfadeNextIndex(state);
+    const ids = new Set([state.currentSongId]);
+    if (nextIndex !== null) ids.add(state.queue[nextIndex]);
+
+    const ranges = audibleRangesRef.current;
+    for (const id of ranges.keys()) {
+      if (!ids.has(id)) ranges.delete(id);
+    }
+    for (const id of ids) {
+      const song = songsRef.current.find((s) => s.id === id);
+      if (song) measureAudibleRange(song);
+    }
+  }, [
+    playerState.currentSongId,
+    playerState.queue,
+    playerState.queueIndex,
+    playerState.repeat,
+    playerState.shuffle,
+    settings?.crossfadeDuration,
+    settings?.autoPlay,
+    getCrossfadeNextIndex,
+    measureAudibleRange,
+  ]);
+
+  // Decoded-buffer playback finished with nothing queued after it
+  useEffect(() => {
+    gaplessEndedRef.current = () => {
+      const state = playerStateRef.current;
+      const autoPlay = settingsRef.current?.autoPlay !== false;
+
+      setPlaybackState("ended");
+
+      if (state.repeat === "one") {
+        playSongAtIndex(state.queueIndex);
+        return;
+      }
+
+      const nextIndex = autoPlay ? getNextIndex(state) : null;
+      if (nextIndex !== null) {
+        playSongAtIndex(nextIndex);
+      } else {
+        setPlayerState((prev) => ({ ...prev, isPlaying: false }));
+      }
+    };
+  }, [getNextIndex, playSongAtIndex]);
+
+  // Decode the next queue item ahead of time and schedule it to start the
+  // sample the current one ends
+  useEffect(() => {
+    const engine = gaplessRef.current;
+    const state = playerStateRef.current;
+    if (!engine?.isActive || engine.currentId !== state.currentSongId) return;
+
+    const clearQueued = () => {
+      engine.clearNext();
+      gaplessNextIndexRef.current = null;
+    };
+
+    if (!isGaplessEnabled() || settings?.autoPlay === false) {
+      clearQueued();
+      return;
+    }
+
+    // Shuffle picks at random, so keep a pick that is still in the queue
+    const queuedIndex = gaplessNextIndexRef.current;
+    if (
+      state.shuffle &&
+      state.repeat !== "one" &&
+      queuedIndex !== null &&
+      state.queue[queuedIndex] === engine.queuedId
+    ) {
+      return;
+    }
+
+    const nextIndex =
+      state.repeat === "one" ? state.queueIndex : getNextIndex(state);
+    const nextSong =
+      nextIndex !== null
+        ? songsRef.current.find((s) => s.id === state.queue[nextIndex])
+        : undefined;
+    if (nextIndex === null || !nextSong) {
+      clearQueued();
+      return;
+    }
+    if (queuedIndex === nextIndex && engine.queuedId === nextSong.id) return;
+
+    // Decode at most one track ahead: let go of an earlier pick first
+    const currentId = engine.currentId;
+    clearQueued();
+    pruneGaplessTracks(currentId, nextSong.id);
+
+    let cancelled = false;
+    loadGaplessTrack(nextSong, engine).then((track) => {
+      if (cancelled || !track || engine.currentId !== currentId) return;
+      engine.queueNext(track);
+      gaplessNextIndexRef.current = nextIndex;
+    });
+
+    return () => {
+      cancelled = true;
+    };
+  }, [
+    playerState.currentSongId,
+    playerState.queue,
+    playerState.queueIndex,
+    playerState.repeat,
+    playerState.shuffle,
+    playbackState,
+    settings?.gaplessPlayback,
+    settings?.crossfadeDuration,
+    settings?.autoPlay,
+    getNextIndex,
+    isGaplessEnabled,
+    loadGaplessTrack,
+    pruneGaplessTracks,
+  ]);
+
+  // Decoded-buffer playback has no timeupdate events, so poll the position
+  useEffect(() => {
+    if (!playerState.isPlaying) return;
+    const timer = setInterval(() => {
+      const engine = gaplessRef.current;
+      if (engine?.isActive) setCurrentTime(engine.currentTime);
+    }, GAPLESS_TIME_UPDATE_MS);
+    return () => clearInterval(timer);
+  }, [playerState.isPlaying]);
+
   // Helper to get audio URL for a song
   const getAudioUrl = useCallback(
     async (song: Song): Promise<string | null> => {
-      // On desktop with file path, read file and create blob URL
+      // On desktop with file path, stream from disk (blob URL fallback)
       if (isDesktop() && song.filePath) {
         const exists = await fileExists(song.filePath);
         if (exists) {
-          return await getAssetUrl(song.filePath);
+          return await getPlaybackUrl(song.filePath);
         }
       }
 
-      // Fall back to memory cache (web or desktop without filePath)
-      const cachedFile = getCachedFile(song.id);
+      // Fall back to memory cache or a remembered web folder
+      const cachedFile = await resolveSongFile(song);
       if (cachedFile) {
         return URL.createObjectURL(cachedFile);
       }
@@ -246,32 +725,48 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
     [],
   );
 
-  // Perform crossfade from current audio to next
+  // Crossfade from the current audio to the next over equal-power gain curves
+  // on the shared audio context. The plan says where the current track goes
+  // silent and where the next one becomes audible
   const performCrossfade = useCallback(
-    async (nextIndex: number) => {
+    async (nextIndex: number, plan: CrossfadePlan) => {
       if (crossfadeInProgressRef.current) return;
 
       const state = playerStateRef.current;
       const allSongs = songsRef.current;
-      const crossfadeDuration = settingsRef.current?.crossfadeDuration || 0;
-
-      if (crossfadeDuration <= 0) return; // Crossfade disabled
 
       const nextSongId = state.queue[nextIndex];
       const nextSong = allSongs.find((s) => s.id === nextSongId);
 
       if (!nextSong || !audioRef.current) return;
 
-      crossfadeInProgressRef.current = true;
+      const currentAudio = audioRef.current;
+      const shared = getSharedAudioContext(currentAudio);
+      if (!shared) return;
 
       // Create next audio element if needed
       if (!nextAudioRef.current) {
         nextAudioRef.current = new Audio();
+        // vinyl-media:// is another origin, Web Audio needs CORS to hear it
+        nextAudioRef.current.crossOrigin = "anonymous";
       }
-
-      const currentAudio = audioRef.current;
       const nextAudio = nextAudioRef.current;
 
+      // Both elements play through the shared chain, each with its own gain
+      const outGain = connectAudioElement(shared, currentAudio);
+      const inGain = connectAudioElement(shared, nextAudio);
+      if (!outGain || !inGain) return;
+
+      crossfadeInProgressRef.current = true;
+      const request = ++crossfadeRequestRef.current;
+      const isCancelled = () => request !== crossfadeRequestRef.current;
+      const { audioContext } = shared;
+
+      const setGain = (node: GainNode, value: number) => {
+        node.gain.cancelScheduledValues(audioContext.currentTime);
+        node.gain.setValueAtTime(value, audioContext.currentTime);
+      };
+
       // Clean up previous next object URL
       if (nextObjectUrlRef.current) {
         URL.revokeObjectURL(nextObjectUrlRef.current);
@@ -281,15 +776,21 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
       try {
         // Get audio URL for next song
         const audioUrl = await getAudioUrl(nextSong);
+        if (isCancelled()) {
+          if (audioUrl?.startsWith("blob:")) URL.revokeObjectURL(audioUrl);
+          return;
+        }
         if (!audioUrl) {
           crossfadeInProgressRef.current = false;
           return;
         }
 
-        nextObjectUrlRef.current = audioUrl;
+        nextObjectUrlRef.current = audioUrl.startsWith("blob:") ? audioUrl : null;
+        setGain(inGain, 0); // Start silent
         nextAudio.src = audioUrl;
-        nextAudio.volume = 0; // Start silent
-        nextAudio.playbackRate = state.speed;
+        nextAudio.volume = currentAudio.volume;
+        nextAudio.playbackRate = getSongSpeed(nextSong);
+        nextAudio.preservesPitch = currentAudio.preservesPitch;
 
         // Wait for next audio to be ready
         await new Promise<void>((resolve, reject) => {
@@ -311,188 +812,251 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
             resolve();
           }, 5000);
         });
+        if (isCancelled()) return;
 
-        // Start playing next audio (muted)
+        // Skip the next track's leading silence and start it (still silent)
+        if (plan.nextOffset > 0) {
+          nextAudio.currentTime = plan.nextOffset;
+        }
         await nextAudio.play();
+        if (isCancelled()) {
+          nextAudio.pause();
+          return;
+        }
 
-        // Perform the crossfade
-        const startVolume = currentAudio.volume;
-        const fadeSteps = 20;
-        const fadeInterval = (crossfadeDuration * 1000) / fadeSteps;
-        let step = 0;
-
-        crossfadeIntervalRef.current = setInterval(() => {
-          step++;
-          const progress = step / fadeSteps;
-
-          // Fade out current, fade in next
-          currentAudio.volume = Math.max(0, startVolume * (1 - progress));
-          nextAudio.volume = Math.min(startVolume, startVolume * progress);
-
-          if (step >= fadeSteps) {
-            // Crossfade complete
-            if (crossfadeIntervalRef.current) {
-              clearInterval(crossfadeIntervalRef.current);
-              crossfadeIntervalRef.current = null;
-            }
+        // Fade over whatever is left of the current track's audible end,
+        // which shrinks while the next track loads
+        const rate = currentAudio.playbackRate || 1;
+        const fadeDuration = Math.max(
+          MIN_CROSSFADE_DURATION,
+          (plan.fadeEnd - currentAudio.currentTime) / rate,
+        );
+        const startTime = audioContext.currentTime;
+        outGain.gain.cancelScheduledValues(startTime);
+        outGain.gain.setValueCurveAtTime(
+          equalPowerCurve("out"),
+          startTime,
+          fadeDuration,
+        );
+        inGain.gain.cancelScheduledValues(startTime);
+        inGain.gain.setValueCurveAtTime(
+          equalPowerCurve("in"),
+          startTime,
+          fadeDuration,
+        );
+
+        let timer: ReturnType<typeof setTimeout> | undefined;
+
+        const finish = () => {
+          clearTimeout(timer);
+          crossfadeRef.current = null;
+
+          // Detach listeners from old audio
+          const oldHandlers = audioEventHandlersRef.current;
+          if (oldHandlers && currentAudio) {
+            currentAudio.removeEventListener(
+              "timeupdate",
+              oldHandlers.timeUpdate,
+            );
+            currentAudio.removeEventListener(
+              "durationchange",
+              oldHandlers.durationChange,
+            );
+            currentAudio.removeEventListener("ended", oldHandlers.ended);
+            currentAudio.removeEventListener("waiting", oldHandlers.waiting);
+            currentAudio.removeEventListener("canplay", oldHandlers.canPlay);
+            currentAudio.removeEventListener("error", oldHandlers.error);
+          }
 
-            // Detach listeners from old audio
-            const oldHandlers = audioEventHandlersRef.current;
-            if (oldHandlers && currentAudio) {
-              currentAudio.removeEventListener(
-                "timeupdate",
-                oldHandlers.timeUpdate,
-              );
-              currentAudio.removeEventListener(
-                "durationchange",
-                oldHandlers.durationChange,
-              );
-              currentAudio.removeEventListener("ended", oldHandlers.ended);
-              currentAudio.removeEventListener("waiting", oldHandlers.waiting);
-              currentAudio.removeEventListener("canplay", oldHandlers.canPlay);
-              currentAudio.removeEventListener("error", oldHandlers.error);
-            }
+          // Stop current audio
+          currentAudio.pause();
+          currentAudio.currentTime = 0;
+          currentAudio.src = "";
 
-            // Stop current audio
-            currentAudio.pause();
-            currentAudio.currentTime = 0;
-            currentAudio.src = "";
+          // Finished early (paused mid-fade): the new track plays at full level
+          setGain(inGain, 1);
+          setGain(outGain, 1);
 
-            // Swap audio elements
-            const tempUrl = objectUrlRef.current;
-            objectUrlRef.current = nextObjectUrlRef.current;
-            nextObjectUrlRef.current = tempUrl;
+          // Swap audio elements
+          const tempUrl = objectUrlRef.current;
+          objectUrlRef.current = nextObjectUrlRef.current;
+          nextObjectUrlRef.current = tempUrl;
 
-            // Clean up old URL
-            if (nextObjectUrlRef.current) {
-              URL.revokeObjectURL(nextObjectUrlRef.current);
-              nextObjectUrlRef.current = null;
-            }
+          // Clean up old URL
+          if (nextObjectUrlRef.current) {
+            URL.revokeObjectURL(nextObjectUrlRef.current);
+            nextObjectUrlRef.current = null;
+          }
 
-            // Swap refs
-            const tempAudio = audioRef.current;
-            audioRef.current = nextAudioRef.current;
-            nextAudioRef.current = tempAudio;
-
-            // Attach listeners to new audio
-            if (audioRef.current) {
-              // Create new handlers for this audio element
-              const newAudio = audioRef.current;
-              const handlers = {
-                timeUpdate: () => {
-                  setCurrentTime(newAudio.currentTime);
-                  // Check crossfade
-                  const state = playerStateRef.current;
-                  const crossfadeDur =
-                    settingsRef.current?.crossfadeDuration || 0;
-                  if (
-                    crossfadeDur > 0 &&
-                    !crossfadeInProgressRef.current &&
-                    state.repeat !== "one" &&
-                    state.isPlaying &&
-                    newAudio.duration > 0
-                  ) {
-                    const timeRemaining =
-                      newAudio.duration - newAudio.currentTime;
-                    if (
-                      timeRemaining > 0 &&
-                      timeRemaining <= crossfadeDur &&
-                      newAudio.duration > crossfadeDur * 2
-                    ) {
-                      const nextIdx = getNextIndex(state);
-                      if (nextIdx !== null) {
-                        performCrossfade(nextIdx);
-                      }
-                    }
-                  }
-                },
-                durationChange: () => setDuration(newAudio.duration),
-                ended: () => {
-                  if (crossfadeInProgressRef.current) return;
-                  const state = playerStateRef.current;
-                  const autoPlay = settingsRef.current?.autoPlay !== false;
-                  setPlaybackState("ended");
-                  if (state.repeat === "one") {
-                    newAudio.currentTime = 0;
-                    newAudio.play();
-                    setPlaybackState("playing");
-                    return;
-                  }
-                  if (!autoPlay) {
-                    setPlayerState((prev) => ({ ...prev, isPlaying: false }));
-                    return;
-                  }
-                  const nextIdx = getNextIndex(state);
-                  if (nextIdx !== null) {
-                    playSongAtIndex(nextIdx);
-                  } else {
-                    setPlayerState((prev) => ({ ...prev, isPlaying: false }));
-                  }
-                },
-                waiting: () => setPlaybackState("buffering"),
-                canPlay: () => {
-                  if (playerStateRef.current.isPlaying)
-                    setPlaybackState("playing");
-                },
-                error: (e: Event) => {
-                  const targetAudio = e.target as HTMLAudioElement;
-                  const src = targetAudio.src || "";
-                  if (src && !src.startsWith(window.location.origin)) {
-                    console.error("Audio error:", e);
-                  }
-                  setPlaybackState("idle");
-                },
-              };
-
-              newAudio.addEventListener("timeupdate", handlers.timeUpdate);
-              newAudio.addEventListener(
-                "durationchange",
-                handlers.durationChange,
-              );
-              newAudio.addEventListener("ended", handlers.ended);
-              newAudio.addEventListener("waiting", handlers.waiting);
-              newAudio.addEventListener("canplay", handlers.canPlay);
-              newAudio.addEventListener("error", handlers.error);
-              audioEventHandlersRef.current = handlers;
-
-              // Ensure volume is correct
-              newAudio.volume = startVolume;
-
-              // Update duration immediately
-              setDuration(newAudio.duration || 0);
-            }
+          // Swap refs
+          const tempAudio = audioRef.current;
+          audioRef.current = nextAudioRef.current;
+          nextAudioRef.current = tempAudio;
+
+          // Attach listeners to new audio
+          if (audioRef.current) {
+            // Create new handlers for this audio element
+            const newAudio = audioRef.current;
+            const handlers = {
+              timeUpdate: () => {
+                setCurrentTime(newAudio.currentTime);
+                checkCrossfadeRef.current(newAudio);
+              },
+              // Still-running transcodes report an unknown (infinite) length
+              durationChange: () => {
+                if (Number.isFinite(newAudio.duration)) {
+                  setDuration(newAudio.duration);
+                }
+              },
+              ended: () => {
+                if (crossfadeInProgressRef.current) return;
+                const state = playerStateRef.current;
+                const autoPlay = settingsRef.current?.autoPlay !== false;
+                setPlaybackState("ended");
+                if (state.repeat === "one") {
+                  newAudio.currentTime = 0;
+                  newAudio.play();
+                  setPlaybackState("playing");
+                  return;
+                }
+                if (!autoPlay) {
+                  setPlayerState((prev) => ({ ...prev, isPlaying: false }));
+                  return;
+                }
+                const nextIdx = getNextIndex(state);
+                if (nextIdx !== null) {
+                  playSongAtIndex(nextIdx);
+                } else {
+                  setPlayerState((prev) => ({ ...prev, isPlaying: false }));
+                }
+              },
+              waiting: () => setPlaybackState("buffering"),
+              canPlay: () => {
+                if (playerStateRef.current.isPlaying)
+                  setPlaybackState("playing");
+              },
+              error: (e: Event) => {
+                const targetAudio = e.target as HTMLAudioElement;
+                const src = targetAudio.src || "";
+                if (src && !src.startsWith(window.location.origin)) {
+                  console.error("Audio error:", e);
+                }
+                setPlaybackState("idle");
+              },
+            };
 
-            // Update state
-            setPlayerState((prev) => ({
-              ...prev,
-              currentSongId: nextSong.id,
-              queueIndex: nextIndex,
-            }));
-
-            // Update Media Session
-            if ("mediaSession" in navigator) {
-              try {
-                navigator.mediaSession.metadata = new MediaMetadata({
-                  title: nextSong.title,
-                  artist: nextSong.artist,
-                  album: nextSong.album,
-                });
-              } catch {
-                // Ignore
-              }
-            }
+            newAudio.addEventListener("timeupdate", handlers.timeUpdate);
+            newAudio.addEventListener(
+              "durationchange",
+              handlers.durationChange,
+            );
+            newAudio.addEventListener("ended", handlers.ended);
+            newAudio.addEventListener("waiting", handlers.waiting);
+            newAudio.addEventListener("canplay", handlers.canPlay);
+            newAudio.addEventListener("error", handlers.error);
+            audioEventHandlersRef.current = handlers;
+
+            // Update duration immediately
+            setDuration(
+              Number.isFinite(newAudio.duration) ? newAudio.duration : 0,
+            );
+          }
+
+          // Update state
+          setPlayerState((prev) => ({
+            ...prev,
+            currentSongId: nextSong.id,
+            queueIndex: nextIndex,
+          }));
 
-            crossfadeInProgressRef.current = false;
+          // Update Media Session
+          updateMediaSession(nextSong);
+
+          crossfadeInProgressRef.current = false;
+        };
+
+        const cancel = () => {
+          clearTimeout(timer);
+          nextAudio.pause();
+          nextAudio.removeAttribute("src");
+          nextAudio.load();
+          if (nextObjectUrlRef.current) {
+            URL.revokeObjectURL(nextObjectUrlRef.current);
+            nextObjectUrlRef.current = null;
           }
-        }, fadeInterval);
+          setGain(outGain, 1);
+          setGain(inGain, 1);
+        };
+
+        crossfadeRef.current = { finish, cancel };
+        timer = setTimeout(finish, fadeDuration * 1000);
       } catch (error) {
         console.error("Crossfade failed:", error);
-        crossfadeInProgressRef.current = false;
+        if (!isCancelled()) {
+          nextAudio.pause();
+          setGain(outGain, 1);
+          crossfadeInProgressRef.current = false;
+        }
       }
     },
-    [getAudioUrl, getNextIndex, playSongAtIndex],
+    [getAudioUrl, getNextIndex, playSongAtIndex, getSongSpeed],
   );
 
+  // Start the crossfade once the current <audio> track reaches the last
+  // audible seconds before the next one, unless the two belong together
+  const checkCrossfadeRef = useRef<(audio: HTMLAudioElement) => void>(
+    () => {},
+  );
+  useEffect(() => {
+    checkCrossfadeRef.current = (audio) => {
+      const state = playerStateRef.current;
+      const appSettings = settingsRef.current;
+      const crossfadeDuration = appSettings?.crossfadeDuration || 0;
+
+      if (
+        crossfadeDuration <= 0 ||
+        appSettings?.autoPlay === false ||
+        crossfadeInProgressRef.current ||
+        state.repeat === "one" ||
+        !state.isPlaying ||
+        !Number.isFinite(audio.duration) ||
+        audio.duration <= 0
+      ) {
+        return;
+      }
+
+      const nextIndex = getCrossfadeNextIndex(state);
+      if (nextIndex === null) return;
+      const current = songsRef.current.find(
+        (s) => s.id === state.currentSongId,
+      );
+      const next = songsRef.current.find(
+        (s) => s.id === state.queue[nextIndex],
+      );
+      if (!current || !next || isSameAlbumSequence(current, next)) return;
+
+      // Songs not measured yet crossfade over their full length
+      const ranges = audibleRangesRef.current;
+      const currentRange = current.audibleRange ?? ranges.get(current.id);
+      const nextRange = next.audibleRange ?? ranges.get(next.id);
+      const plan = planCrossfade({
+        duration: crossfadeDuration,
+        rate: audio.playbackRate || 1,
+        current: currentRange ?? { start: 0, end: audio.duration },
+        next: nextRange ?? { start: 0, end: next.duration || Infinity },
+      });
+
+      // Past the audible end (seeked there) the track just ends on its own
+      if (
+        plan &&
+        audio.currentTime >= plan.fadeStart &&
+        audio.currentTime < plan.fadeEnd
+      ) {
+        performCrossfade(nextIndex, plan);
+      }
+    };
+  }, [getCrossfadeNextIndex, performCrossfade]);
+
   // Store event handlers in refs so they can be reattached after crossfade
   const audioEventHandlersRef = useRef<{
     timeUpdate: () => void;
@@ -511,30 +1075,14 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
         timeUpdate: () => {
           setCurrentTime(audio.currentTime);
           // Check crossfade from the handler using the always-current audio
-          const state = playerStateRef.current;
-          const crossfadeDuration = settingsRef.current?.crossfadeDuration || 0;
-
-          if (
-            crossfadeDuration > 0 &&
-            !crossfadeInProgressRef.current &&
-            state.repeat !== "one" &&
-            state.isPlaying &&
-            audio.duration > 0
-          ) {
-            const timeRemaining = audio.duration - audio.currentTime;
-            if (
-              timeRemaining > 0 &&
-              timeRemaining <= crossfadeDuration &&
-              audio.duration > crossfadeDuration * 2
-            ) {
-              const nextIndex = getNextIndex(state);
-              if (nextIndex !== null) {
-                performCrossfade(nextIndex);
-              }
-            }
+          checkCrossfadeRef.current(audio);
+        },
+        // Still-running transcodes report an unknown (infinite) length
+        durationChange: () => {
+          if (Number.isFinite(audio.duration)) {
+            setDuration(audio.duration);
           }
         },
-        durationChange: () => setDuration(audio.duration),
         ended: () => {
           // If crossfade already handled the transition, skip
           if (crossfadeInProgressRef.current) return;
@@ -596,7 +1144,7 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
 
       return handlers;
     },
-    [getNextIndex, performCrossfade, playSongAtIndex],
+    [getNextIndex, playSongAtIndex],
   );
 
   // Function to detach event listeners
@@ -615,6 +1163,8 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
   // Initialize audio element
   useEffect(() => {
     audioRef.current = new Audio();
+    // vinyl-media:// is another origin, Web Audio needs CORS to hear it
+    audioRef.current.crossOrigin = "anonymous";
     audioRef.current.volume = settings?.defaultVolume ?? playerState.volume;
 
     const audio = audioRef.current;
@@ -626,14 +1176,13 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
     return () => {
       detachAudioListeners(audio);
       audio.pause();
+      gaplessRef.current?.stop();
       // Only revoke blob URLs
       if (objectUrlRef.current) {
         URL.revokeObjectURL(objectUrlRef.current);
       }
-      // Clean up crossfade interval
-      if (crossfadeIntervalRef.current) {
-        clearInterval(crossfadeIntervalRef.current);
-      }
+      // Stop any crossfade in progress
+      cancelCrossfade();
       // Clean up next audio
       if (nextAudioRef.current) {
         nextAudioRef.current.pause();
@@ -642,7 +1191,7 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
         URL.revokeObjectURL(nextObjectUrlRef.current);
       }
     };
-  }, [attachAudioListeners, detachAudioListeners]);
+  }, [attachAudioListeners, cancelCrossfade, detachAudioListeners]);
 
   // Load saved player state
   const loadSavedState = async () => {
@@ -731,6 +1280,8 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
       }
 
       // Stop and reset current audio before loading new source
+      stopGapless();
+      cancelCrossfade();
       audio.pause();
       audio.currentTime = 0;
 
@@ -756,22 +1307,22 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
       let audioUrl: string | null = null;
 
       try {
-        // On desktop with file path, read file and create blob URL
+        // On desktop with file path, stream from disk (blob URL fallback)
         if (isDesktop() && song.filePath) {
           const exists = await fileExists(song.filePath);
 
           if (exists) {
-            audioUrl = await getAssetUrl(song.filePath);
-            // getAssetUrl now returns blob URLs that need cleanup
-            if (audioUrl) {
+            audioUrl = await getPlaybackUrl(song.filePath);
+            // Only blob URLs need cleanup
+            if (audioUrl?.startsWith("blob:")) {
               objectUrlRef.current = audioUrl;
             }
           }
         }
 
-        // Fall back to memory cache (web or desktop without filePath)
+        // Fall back to memory cache or a remembered web folder
         if (!audioUrl) {
-          const cachedFile = getCachedFile(song.id);
+          const cachedFile = await resolveSongFile(song);
           if (cachedFile) {
             audioUrl = URL.createObjectURL(cachedFile);
             objectUrlRef.current = audioUrl;
@@ -848,7 +1399,7 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
         return false;
       }
     },
-    [],
+    [cancelCrossfade, stopGapless],
   );
 
   // Restore song when songs become available (after page refresh)
@@ -881,13 +1432,13 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
 
       if (loaded && audioRef.current) {
         // Apply saved playback speed
-        audioRef.current.playbackRate = playerState.speed;
+        audioRef.current.playbackRate = getSongSpeed(song);
         setPlaybackState("paused");
       }
     };
 
     restoreSong();
-  }, [songs, playerState.currentSongId, playerState.speed, loadSong]);
+  }, [songs, playerState.currentSongId, loadSong, getSongSpeed]);
 
   // Play a specific song
   const playSong = async (song: Song, playlistId?: string | null) => {
@@ -915,6 +1466,10 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
       // Mark hasRestoredSong to prevent restore effect from interfering
       hasRestoredSong.current = true;
 
+      if (isGaplessEnabled() && (await playGapless(songToPlay))) {
+        return;
+      }
+
       const loaded = await loadSong(songToPlay, undefined, true); // User-initiated
 
       if (!loaded) {
@@ -925,7 +1480,7 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
 
       if (audioRef.current) {
         // Apply current playback speed
-        audioRef.current.playbackRate = playerState.speed;
+        audioRef.current.playbackRate = getSongSpeed(songToPlay);
 
         await audioRef.current.play();
         setPlaybackState("playing");
@@ -937,41 +1492,53 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
     }
   };
 
-  // Play a playlist
-  const playPlaylist = async (playlist: Playlist, playlistSongs: Song[]) => {
-    if (playlistSongs.length === 0) return;
+  // Play a list of songs (playlist, album, artist) as the queue
+  // Starts at `startSongId` if given (moved to the front when shuffling)
+  const playSongs = async (
+    listSongs: Song[],
+    options: { playlistId?: string | null; startSongId?: string } = {},
+  ) => {
+    if (listSongs.length === 0) return;
 
     const appSettings = settingsRef.current;
     const shouldClearQueue = appSettings?.clearQueueOnNewPlaylist !== false;
+    const { playlistId = null, startSongId } = options;
 
-    // Build the new queue from playlist songs only
-    const playlistQueue = playerState.shuffle
-      ? shuffleArray(playlistSongs.map((s) => s.id))
-      : playlistSongs.map((s) => s.id);
+    // Build the new queue from the list songs only
+    const ids = listSongs.map((s) => s.id);
+    let listQueue = playerState.shuffle ? shuffleArray(ids) : ids;
+    if (playerState.shuffle && startSongId && ids.includes(startSongId)) {
+      listQueue = [startSongId, ...listQueue.filter((id) => id !== startSongId)];
+    }
+    const startIndex = startSongId
+      ? Math.max(listQueue.indexOf(startSongId), 0)
+      : 0;
 
-    const firstSong = playlistSongs.find((s) => s.id === playlistQueue[0]);
+    const firstSong = listSongs.find((s) => s.id === listQueue[startIndex]);
     if (!firstSong) return;
 
     setPlayerState((prev) => ({
       ...prev,
       currentSongId: firstSong.id,
       isPlaying: true,
-      // Use only playlist songs in queue when clearQueueOnNewPlaylist is true
-      queue: shouldClearQueue
-        ? playlistQueue
-        : [...prev.queue, ...playlistQueue],
-      queueIndex: shouldClearQueue ? 0 : prev.queue.length,
-      currentPlaylistId: playlist.id,
+      // Use only list songs in queue when clearQueueOnNewPlaylist is true
+      queue: shouldClearQueue ? listQueue : [...prev.queue, ...listQueue],
+      queueIndex: (shouldClearQueue ? 0 : prev.queue.length) + startIndex,
+      currentPlaylistId: playlistId,
     }));
 
     // Mark hasRestoredSong to prevent restore effect from interfering
     hasRestoredSong.current = true;
 
+    if (isGaplessEnabled() && (await playGapless(firstSong))) {
+      return;
+    }
+
     await loadSong(firstSong, undefined, true); // User-initiated
 
     if (audioRef.current) {
       // Apply current playback speed
-      audioRef.current.playbackRate = playerState.speed;
+      audioRef.current.playbackRate = getSongSpeed(firstSong);
 
       try {
         await audioRef.current.play();
@@ -983,6 +1550,51 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
     }
   };
 
+  // Play a playlist
+  const playPlaylist = async (playlist: Playlist, playlistSongs: Song[]) => {
+    await playSongs(playlistSongs, { playlistId: playlist.id });
+  };
+
+  // Add songs to the queue, either right after the current song or at the end
+  // Songs already queued are moved rather than duplicated
+  const addToQueue = (listSongs: Song[], playNext = false) => {
+    if (listSongs.length === 0) return;
+
+    // Nothing playing yet - just start the list
+    if (!playerState.currentSongId || playerState.queueIndex < 0) {
+      void playSongs(listSongs);
+      return;
+    }
+
+    setPlayerState((prev) => {
+      const currentId = prev.queue[prev.queueIndex];
+      const added = listSongs.map((s) => s.id).filter((id) => id !== currentId);
+      const addedIds = new Set(added);
+
+      const remaining: string[] = [];
+      let newQueueIndex = -1;
+      prev.queue.forEach((id, index) => {
+        if (index === prev.queueIndex) {
+          newQueueIndex = remaining.length;
+          remaining.push(id);
+        } else if (!addedIds.has(id)) {
+          remaining.push(id);
+        }
+      });
+
+      const insertAt = playNext ? newQueueIndex + 1 : remaining.length;
+      return {
+        ...prev,
+        queue: [
+          ...remaining.slice(0, insertAt),
+          ...added,
+          ...remaining.slice(insertAt),
+        ],
+        queueIndex: newQueueIndex,
+      };
+    });
+  };
+
   // Play a song from the current queue (doesn't rebuild queue)
   const playFromQueue = async (song: Song) => {
     const queueIndex = playerState.queue.indexOf(song.id);
@@ -1000,7 +1612,23 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
   const togglePlayPause = async () => {
     if (!audioRef.current) return;
 
+    const engine = gaplessRef.current;
+    if (engine?.isActive) {
+      if (playerState.isPlaying) {
+        engine.pause();
+        setPlaybackState("paused");
+      } else {
+        engine.resume();
+        setPlaybackState("playing");
+      }
+      setPlayerState((prev) => ({ ...prev, isPlaying: !prev.isPlaying }));
+      return;
+    }
+
     if (playerState.isPlaying) {
+      // Mid-crossfade, pause on the incoming track rather than leave it playing
+      crossfadeRef.current?.finish();
+      cancelCrossfade();
       audioRef.current.pause();
       setPlayerState((prev) => ({ ...prev, isPlaying: false }));
       setPlaybackState("paused");
@@ -1021,6 +1649,8 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
 
   // Stop playback completely
   const stop = () => {
+    stopGapless();
+    cancelCrossfade();
     if (audioRef.current) {
       audioRef.current.pause();
       audioRef.current.currentTime = 0;
@@ -1047,13 +1677,24 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
     }
   }, [playSongAtIndex, getNextIndex]);
 
+  // Seek the current song back to the start
+  const restartCurrent = useCallback(() => {
+    cancelCrossfade();
+    if (gaplessRef.current?.isActive) {
+      gaplessRef.current.seek(0);
+      setCurrentTime(0);
+    } else if (audioRef.current) {
+      audioRef.current.currentTime = 0;
+    }
+  }, [cancelCrossfade]);
+
   // Play previous track
   const playPrevious = useCallback(() => {
     const state = playerStateRef.current;
 
     // If more than 3 seconds in, restart current song
     if (currentTime > 3 && audioRef.current) {
-      audioRef.current.currentTime = 0;
+      restartCurrent();
       return;
     }
 
@@ -1073,17 +1714,24 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
         if (state.repeat === "all") {
           prevIndex = state.queue.length - 1;
         } else {
-          if (audioRef.current) audioRef.current.currentTime = 0;
+          restartCurrent();
           return;
         }
       }
 
       playSongAtIndex(prevIndex);
     }
-  }, [currentTime, playSongAtIndex, getNextIndex]);
+  }, [currentTime, playSongAtIndex, getNextIndex, restartCurrent]);
 
   // Seek to position
   const seek = (time: number) => {
+    // The seek bar still shows the outgoing track, so stay on it
+    cancelCrossfade();
+    if (gaplessRef.current?.isActive) {
+      gaplessRef.current.seek(time);
+      setCurrentTime(time);
+      return;
+    }
     if (audioRef.current) {
       // Preserve the playing state during seek
       const wasPlaying = playerState.isPlaying;
@@ -1101,6 +1749,10 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
     if (audioRef.current) {
       audioRef.current.volume = volume;
     }
+    if (nextAudioRef.current) {
+      nextAudioRef.current.volume = volume;
+    }
+    gaplessRef.current?.setVolume(volume);
     setPlayerState((prev) => ({ ...prev, volume }));
   };
 
@@ -1124,13 +1776,58 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
   };
 
   // Set playback speed
+  // A song with its tempo remembered keeps the new one. While a playback
+  // profile sets the speed, the choice only lasts for the current song and
+  // the player's own speed stays as it was
   const setSpeed = (speed: number) => {
     if (audioRef.current) {
       audioRef.current.playbackRate = speed;
     }
+    if (nextAudioRef.current) {
+      nextAudioRef.current.playbackRate = speed;
+    }
+    gaplessRef.current?.setPlaybackRate(speed);
+    if (currentSong && rememberedPitch) {
+      rememberSongPitch(currentSong.id, { ...rememberedPitch, speed });
+      return;
+    }
+    if (currentSong && currentProfile.speed !== undefined) {
+      setSpeedOverride({ songId: currentSong.id, speed });
+      return;
+    }
     setPlayerState((prev) => ({ ...prev, speed }));
   };
 
+  // Transpose the current song, for this play unless it's remembered
+  const setPitch = (semitones: number) => {
+    if (!currentSong) return;
+    const pitch = clampSemitones(semitones);
+    if (rememberedPitch) {
+      rememberSongPitch(currentSong.id, {
+        ...rememberedPitch,
+        semitones: pitch,
+      });
+      return;
+    }
+    setPitchOverride({ songId: currentSong.id, semitones: pitch });
+  };
+
+  // Remember the current song's key and tempo, or forget them so it plays
+  // as usual again
+  const toggleRememberPitch = () => {
+    if (!currentSong) return;
+    if (rememberedPitch) {
+      forgetSongPitch(currentSong.id);
+      return;
+    }
+    rememberSongPitch(currentSong.id, {
+      semitones: effectivePitch,
+      speed: effectiveSpeed,
+    });
+    setPitchOverride(null);
+    setSpeedOverride((prev) => (prev?.songId === currentSong.id ? null : prev));
+  };
+
   // Remove a song from the queue and handle if it's currently playing
   const removeSongFromQueue = useCallback((songId: string) => {
     const state = playerStateRef.current;
@@ -1138,6 +1835,8 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
     // Check if this song is currently playing
     if (state.currentSongId === songId) {
       // Stop playback
+      stopGapless();
+      cancelCrossfade();
       if (audioRef.current) {
         audioRef.current.pause();
         audioRef.current.currentTime = 0;
@@ -1180,7 +1879,7 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
         queueIndex: newQueueIndex,
       }));
     }
-  }, []);
+  }, [cancelCrossfade, stopGapless]);
 
   // Setup Media Session handlers
   useEffect(() => {
@@ -1241,6 +1940,8 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
     const audio = audioRef.current;
 
     // Stop and reset current audio
+    stopGapless();
+    cancelCrossfade();
     audio.pause();
     audio.currentTime = 0;
 
@@ -1262,8 +1963,10 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
         title: metadata.title || file.name.replace(/\.[^/.]+$/, ""),
         artist: metadata.artist || "Unknown Artist",
         album: metadata.album || "Unknown Album",
+        ...pickTagFields(metadata),
         duration: metadata.duration || 0,
-        coverArt: metadata.coverArt,
+        artworkId: metadata.artworkId,
+        replayGain: metadata.replayGain,
         sourceType: "local",
         addedAt: Date.now(),
         fileName: file.name,
@@ -1294,7 +1997,7 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
       await new Promise((resolve) => setTimeout(resolve, 50));
 
       audio.src = audioUrl;
-      audio.playbackRate = playerState.speed;
+      audio.playbackRate = getSongSpeed(tempSong);
 
       // Setup Media Session
       if ("mediaSession" in navigator) {
@@ -1341,7 +2044,7 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
       setPlayerState((prev) => ({ ...prev, isPlaying: false }));
       return null;
     }
-  }, [playerState.speed]);
+  }, [cancelCrossfade, stopGapless, getSongSpeed]);
 
   // Play multiple files directly without adding to library
   // Creates a temporary queue from the files
@@ -1354,6 +2057,8 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
     const audio = audioRef.current;
 
     // Stop and reset current audio
+    stopGapless();
+    cancelCrossfade();
     audio.pause();
     audio.currentTime = 0;
 
@@ -1378,8 +2083,10 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
           title: metadata.title || file.name.replace(/\.[^/.]+$/, ""),
           artist: metadata.artist || "Unknown Artist",
           album: metadata.album || "Unknown Album",
+          ...pickTagFields(metadata),
           duration: metadata.duration || 0,
-          coverArt: metadata.coverArt,
+          artworkId: metadata.artworkId,
+          replayGain: metadata.replayGain,
           sourceType: "local",
           addedAt: Date.now(),
           fileName: file.name,
@@ -1416,7 +2123,7 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
       await new Promise((resolve) => setTimeout(resolve, 50));
 
       audio.src = audioUrl;
-      audio.playbackRate = playerState.speed;
+      audio.playbackRate = getSongSpeed(firstSong);
 
       // Setup Media Session
       if ("mediaSession" in navigator) {
@@ -1463,7 +2170,7 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
       setPlayerState((prev) => ({ ...prev, isPlaying: false }));
       return [];
     }
-  }, [playerState.speed]);
+  }, [cancelCrossfade, stopGapless, getSongSpeed]);
 
   // Play a file from a file path (for desktop "Open With" from Finder/Explorer)
   const playFilePath = useCallback(async (filePath: string): Promise<Song | null> => {
@@ -1477,6 +2184,8 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
     const audio = audioRef.current;
 
     // Stop and reset current audio
+    stopGapless();
+    cancelCrossfade();
     audio.pause();
     audio.currentTime = 0;
 
@@ -1497,26 +2206,26 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
         return null;
       }
 
-      // Get the audio URL (reads file and creates blob URL)
-      const audioUrl = await getAssetUrl(filePath);
+      // Get the audio URL (streams from disk, blob URL fallback)
+      const audioUrl = await getPlaybackUrl(filePath);
       if (!audioUrl) {
         console.error("Failed to load audio file:", filePath);
         setPlaybackState("idle");
         return null;
       }
 
-      objectUrlRef.current = audioUrl;
+      if (audioUrl.startsWith("blob:")) {
+        objectUrlRef.current = audioUrl;
+      }
 
       // Extract metadata to create a Song object
       const fileName = filePath.split('/').pop() || filePath.split('\\').pop() || 'Unknown';
       
-      // Try to extract metadata
+      // Read tags natively rather than downloading the file again
       let songMetadata: Partial<Song> = {};
       try {
-        const response = await fetch(audioUrl);
-        const blob = await response.blob();
-        const file = new File([blob], fileName, { type: blob.type });
-        songMetadata = await extractMetadata(file);
+        const metadataByPath = await readNativeMetadataMap([filePath]);
+        songMetadata = metadataByPath.get(filePath) ?? {};
       } catch (metadataError) {
         console.warn("Could not extract metadata:", metadataError);
       }
@@ -1527,9 +2236,11 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
         title: songMetadata.title || fileName.replace(/\.[^/.]+$/, ""),
         artist: songMetadata.artist || "Unknown Artist",
         album: songMetadata.album || "Unknown Album",
+        ...pickTagFields(songMetadata),
         duration: songMetadata.duration || 0,
         filePath: filePath,
-        coverArt: songMetadata.coverArt,
+        artworkId: songMetadata.artworkId,
+        replayGain: songMetadata.replayGain,
         sourceType: "local",
         addedAt: Date.now(),
         fileName: fileName,
@@ -1540,7 +2251,7 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
 
       // Set up audio source
       audio.src = audioUrl;
-      audio.playbackRate = playerState.speed;
+      audio.playbackRate = getSongSpeed(song);
 
       // Update state
       setPlayerState((prev) => ({
@@ -1581,7 +2292,7 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
       setPlayerState((prev) => ({ ...prev, isPlaying: false }));
       return null;
     }
-  }, [playerState.speed]);
+  }, [cancelCrossfade, stopGapless, getSongSpeed]);
 
   // Get current song - check quick play songs first, then library songs
   const currentSong = playerState.currentSongId
@@ -1589,6 +2300,103 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
        songs.find((s) => s.id === playerState.currentSongId))
     : undefined;
 
+  // Playback profile settings for the current song
+  const currentProfile: PlaybackAdjustments =
+    currentSong && getPlaybackProfile
+      ? getPlaybackProfile(currentSong, playerState.currentPlaylistId)
+      : {};
+  const rememberedPitch = currentSong ? pitchMemory[currentSong.id] : undefined;
+  const effectiveSpeed = rememberedPitch
+    ? rememberedPitch.speed
+    : currentSong && speedOverride?.songId === currentSong.id
+      ? speedOverride.speed
+      : (currentProfile.speed ?? playerState.speed);
+  const effectivePitch = rememberedPitch
+    ? rememberedPitch.semitones
+    : currentSong && pitchOverride?.songId === currentSong.id
+      ? pitchOverride.semitones
+      : 0;
+  const profileGain = currentProfile.gain ?? 0;
+
+  // Follow the speed of the current song's profile across track changes
+  useEffect(() => {
+    if (audioRef.current) {
+      audioRef.current.playbackRate = effectiveSpeed;
+    }
+    gaplessRef.current?.setPlaybackRate(effectiveSpeed);
+  }, [effectiveSpeed]);
+
+  // Tracks play varispeed through the pitch shifter, which transposes them
+  // and, when keeping pitch, undoes what the playback rate does to it
+  const preservePitch = settings?.preservePitch ?? true;
+  const pitchRatio = getPitchRatio(
+    effectivePitch,
+    effectiveSpeed,
+    preservePitch,
+  );
+
+  // Add the pitch shifter once playback needs it; it stays in the chain after
+  useEffect(() => {
+    const audio = audioRef.current;
+    if (!audio || pitchShifter || pitchRatio === 1) return;
+    const shared = getSharedAudioContext(audio);
+    if (!shared) return;
+
+    let cancelled = false;
+    void getPitchShifter(shared).then((node) => {
+      if (!cancelled) setPitchShifter(node);
+    });
+    return () => {
+      cancelled = true;
+    };
+  }, [pitchRatio, pitchShifter]);
+
+  useEffect(() => {
+    if (!pitchShifter) return;
+    const ratio = pitchShifter.parameters.get("ratio");
+    ratio?.setValueAtTime(pitchRatio, pitchShifter.context.currentTime);
+  }, [pitchShifter, pitchRatio]);
+
+  // Elements keep their own pitch until the shifter takes over (or where
+  // there's no AudioWorklet to run it)
+  useEffect(() => {
+    const keepPitch = pitchShifter ? false : preservePitch;
+    for (const audio of [audioRef.current, nextAudioRef.current]) {
+      if (audio) audio.preservesPitch = keepPitch;
+    }
+  }, [pitchShifter, preservePitch]);
+
+  // Volume normalization: apply ReplayGain (and the profile's gain offset)
+  // through the pre-EQ gain node
+  const replayGainMode = settings?.replayGainMode ?? "off";
+  const replayGainPreamp = settings?.replayGainPreamp ?? 0;
+  const replayGainPreventClipping = settings?.replayGainPreventClipping ?? true;
+  useEffect(() => {
+    const audio = audioRef.current;
+    if (!audio) return;
+
+    // Don't create an AudioContext just to keep unity gain
+    const shared =
+      replayGainMode === "off" && profileGain === 0
+        ? peekSharedAudioContext(audio)
+        : getSharedAudioContext(audio);
+    if (!shared) return;
+
+    const gain = getNormalizationGain(
+      currentSong,
+      { replayGainMode, replayGainPreamp, replayGainPreventClipping },
+      profileGain,
+    );
+    const { audioContext, preGainNode } = shared;
+    preGainNode.gain.setTargetAtTime(gain, audioContext.currentTime, 0.01);
+  }, [
+    currentSong,
+    replayGainMode,
+    replayGainPreamp,
+    replayGainPreventClipping,
+    profileGain,
+  ]);
+
   // Get queue songs (actual Song objects from queue IDs)
   // Include quick play songs if they're in the queue
   const queueSongs = playerState.queue
@@ -1609,7 +2417,11 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
     volume: playerState.volume,
     repeat: playerState.repeat,
     shuffle: playerState.shuffle,
-    speed: playerState.speed,
+    speed: effectiveSpeed,
+    pitch: effectivePitch,
+    isPitchRemembered: !!rememberedPitch,
+    canTranspose: isPitchShiftSupported(),
+    playbackProfile: currentProfile,
     queue: playerState.queue,
     queueSongs,
     queueIndex: playerState.queueIndex,
@@ -1618,6 +2430,8 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
     playSong,
     playFromQueue,
     playPlaylist,
+    playSongs,
+    addToQueue,
     playFile,
     playFiles,
     playFilePath,
@@ -1630,6 +2444,9 @@ export function useAudioPlayer(songs: Song[], settings?: AppSettings) {
     toggleRepeat,
     toggleShuffle,
     setSpeed,
+    setPitch,
+    toggleRememberPitch,
+    forgetSongPitch,
     removeSongFromQueue,
     reorderQueue,
   };
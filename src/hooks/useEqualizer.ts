@@ -4,130 +4,206 @@ import {
   resumeAudioContext,
   type SharedAudioData,
 } from "../lib/audioContext";
+import { generateId } from "../lib/audioMetadata";
+import {
+  createEqFilter,
+  normalizeEqFilter,
+  parseParametricEq,
+  toBiquadQ,
+  EQ_LIMITS,
+  type EqFilter,
+  type EqProfile,
+} from "../lib/parametricEq";
 
-export interface EqualizerBand {
-  frequency: number;
-  gain: number;
-  label: string;
-}
-
-export interface EqualizerPreset {
+export interface EqualizerPreset extends EqProfile {
   name: string;
-  gains: number[];
 }
 
-// Default frequency bands for a 10-band equalizer
-const DEFAULT_BANDS: EqualizerBand[] = [
-  { frequency: 32, gain: 0, label: "32" },
-  { frequency: 64, gain: 0, label: "64" },
-  { frequency: 125, gain: 0, label: "125" },
-  { frequency: 250, gain: 0, label: "250" },
-  { frequency: 500, gain: 0, label: "500" },
-  { frequency: 1000, gain: 0, label: "1K" },
-  { frequency: 2000, gain: 0, label: "2K" },
-  { frequency: 4000, gain: 0, label: "4K" },
-  { frequency: 8000, gain: 0, label: "8K" },
-  { frequency: 16000, gain: 0, label: "16K" },
+// Bands of the classic 10-band layout the built-in presets are made of
+const PRESET_FREQUENCIES = [
+  32, 64, 125, 250, 500, 1000, 2000, 4000, 8000, 16000,
 ];
 
+function bandPreset(name: string, gains: number[]): EqualizerPreset {
+  return {
+    name,
+    preamp: 0,
+    filters: PRESET_FREQUENCIES.map((frequency, index): EqFilter => ({
+      id: `band-${frequency}`,
+      type: "peaking",
+      frequency,
+      gain: gains[index] ?? 0,
+      q: 1.4,
+    })),
+  };
+}
+
 export const EQUALIZER_PRESETS: EqualizerPreset[] = [
-  { name: "Flat", gains: [0, 0, 0, 0, 0, 0, 0, 0, 0, 0] },
-  { name: "Bass Boost", gains: [6, 5, 4, 2, 0, 0, 0, 0, 0, 0] },
-  { name: "Treble Boost", gains: [0, 0, 0, 0, 0, 0, 2, 4, 5, 6] },
-  { name: "Rock", gains: [5, 4, 2, 0, -1, 0, 2, 3, 4, 4] },
-  { name: "Pop", gains: [-1, -1, 0, 2, 4, 4, 2, 0, -1, -1] },
-  { name: "Jazz", gains: [3, 2, 1, 2, -2, -2, 0, 2, 3, 4] },
-  { name: "Classical", gains: [4, 3, 2, 1, -1, -1, 0, 2, 3, 4] },
-  { name: "Electronic", gains: [4, 4, 1, 0, -2, 2, 1, 1, 4, 5] },
-  { name: "Hip Hop", gains: [5, 4, 1, 3, -1, -1, 2, 0, 2, 3] },
-  { name: "Vocal", gains: [-2, -3, -2, 1, 4, 4, 3, 1, 0, -1] },
-  { name: "Loudness", gains: [6, 4, 0, 0, -2, 0, -1, -5, 5, 1] },
+  bandPreset("Flat", [0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+  bandPreset("Bass Boost", [6, 5, 4, 2, 0, 0, 0, 0, 0, 0]),
+  bandPreset("Treble Boost", [0, 0, 0, 0, 0, 0, 2, 4, 5, 6]),
+  bandPreset("Rock", [5, 4, 2, 0, -1, 0, 2, 3, 4, 4]),
+  bandPreset("Pop", [-1, -1, 0, 2, 4, 4, 2, 0, -1, -1]),
+  bandPreset("Jazz", [3, 2, 1, 2, -2, -2, 0, 2, 3, 4]),
+  bandPreset("Classical", [4, 3, 2, 1, -1, -1, 0, 2, 3, 4]),
+  bandPreset("Electronic", [4, 4, 1, 0, -2, 2, 1, 1, 4, 5]),
+  bandPreset("Hip Hop", [5, 4, 1, 3, -1, -1, 2, 0, 2, 3]),
+  bandPreset("Vocal", [-2, -3, -2, 1, 4, 4, 3, 1, 0, -1]),
+  bandPreset("Loudness", [6, 4, 0, 0, -2, 0, -1, -5, 5, 1]),
 ];
 
-const STORAGE_KEY = "vinyl-equalizer-settings";
+export const STORAGE_KEY = "vinyl-equalizer-settings";
+
+interface ActiveSettings {
+  filters: EqFilter[];
+  preamp: number;
+  enabled: boolean;
+  currentPreset: string | null;
+}
+
+interface StoredSettings extends ActiveSettings {
+  userPresets: EqualizerPreset[];
+}
 
-interface StoredSettings {
-  bands: EqualizerBand[];
+// Before the parametric equalizer, settings held gains of the fixed bands
+interface LegacySettings {
+  bands: { frequency: number; gain: number }[];
   enabled: boolean;
   currentPreset: string | null;
 }
 
-export function useEqualizer(audioElement: HTMLAudioElement | null) {
-  const [bands, setBands] = useState<EqualizerBand[]>(DEFAULT_BANDS);
-  const [enabled, setEnabled] = useState(false);
+function copyFilters(filters: EqFilter[]): EqFilter[] {
+  return filters.map((filter) => ({ ...filter, id: generateId() }));
+}
+
+// Set a filter node's parameters, gliding to them while audio is playing
+function configureNode(
+  node: BiquadFilterNode,
+  filter: EqFilter,
+  glideFrom?: number,
+) {
+  node.type = filter.type;
+  const params: [AudioParam, number][] = [
+    [node.frequency, filter.frequency],
+    [node.gain, filter.gain],
+    [node.Q, toBiquadQ(filter)],
+  ];
+  for (const [param, value] of params) {
+    if (glideFrom === undefined) {
+      param.value = value;
+    } else {
+      param.setTargetAtTime(value, glideFrom, 0.01);
+    }
+  }
+}
+
+function clampPreamp(preamp: number): number {
+  return Math.min(
+    EQ_LIMITS.preamp.max,
+    Math.max(EQ_LIMITS.preamp.min, preamp || 0),
+  );
+}
+
+function loadSettings(): StoredSettings | null {
+  const saved = localStorage.getItem(STORAGE_KEY);
+  if (!saved) return null;
+
+  const settings = JSON.parse(saved) as Partial<StoredSettings> &
+    Partial<LegacySettings>;
+  const filters = Array.isArray(settings.filters)
+    ? settings.filters.map(normalizeEqFilter)
+    : bandPreset(
+        "",
+        (settings.bands ?? []).map((band) => band.gain),
+      ).filters;
+
+  return {
+    filters,
+    preamp: clampPreamp(settings.preamp ?? 0),
+    enabled: settings.enabled ?? false,
+    currentPreset: settings.currentPreset ?? null,
+    userPresets: (settings.userPresets ?? []).map((preset) => ({
+      name: preset.name,
+      preamp: clampPreamp(preset.preamp),
+      filters: preset.filters.map(normalizeEqFilter),
+    })),
+  };
+}
+
+export function useEqualizer(
+  audioElement: HTMLAudioElement | null,
+  // Preset of the playback profile matching the playing track
+  profilePreset?: string,
+) {
+  const [filters, setFilters] = useState<EqFilter[]>(
+    EQUALIZER_PRESETS[0].filters,
+  );
+  const [preamp, setPreampState] = useState(0);
+  const [enabled, setEnabledState] = useState(false);
   const [currentPreset, setCurrentPreset] = useState<string | null>("Flat");
+  const [userPresets, setUserPresets] = useState<EqualizerPreset[]>([]);
   const [isConnected, setIsConnected] = useState(false);
 
   const sharedDataRef = useRef<SharedAudioData | null>(null);
+  const preampNodeRef = useRef<GainNode | null>(null);
   const filtersRef = useRef<BiquadFilterNode[]>([]);
-  const filtersConnectedRef = useRef(false);
+  const settingsRef = useRef<ActiveSettings>({
+    filters,
+    preamp,
+    enabled,
+    currentPreset,
+  });
+  settingsRef.current = { filters, preamp, enabled, currentPreset };
+  // The user's own settings while a profile preset stands in for them
+  const userSettingsRef = useRef<ActiveSettings | null>(null);
+  const appliedProfilePresetRef = useRef<string | undefined>(undefined);
 
   // Load saved settings from localStorage
   useEffect(() => {
     try {
-      const saved = localStorage.getItem(STORAGE_KEY);
-      if (saved) {
-        const settings: StoredSettings = JSON.parse(saved);
-        setBands(settings.bands);
-        setEnabled(settings.enabled);
+      const settings = loadSettings();
+      if (settings) {
+        setFilters(settings.filters);
+        setPreampState(settings.preamp);
+        setEnabledState(settings.enabled);
         setCurrentPreset(settings.currentPreset);
+        setUserPresets(settings.userPresets);
       }
     } catch (error) {
       console.error("Failed to load equalizer settings:", error);
     }
   }, []);
 
-  // Save settings to localStorage
+  // Save settings to localStorage; a profile preset is never saved over the
+  // user's own settings
   useEffect(() => {
     try {
       const settings: StoredSettings = {
-        bands,
-        enabled,
-        currentPreset,
+        ...(userSettingsRef.current ?? {
+          filters,
+          preamp,
+          enabled,
+          currentPreset,
+        }),
+        userPresets,
       };
       localStorage.setItem(STORAGE_KEY, JSON.stringify(settings));
     } catch (error) {
       console.error("Failed to save equalizer settings:", error);
     }
-  }, [bands, enabled, currentPreset]);
+  }, [filters, preamp, enabled, currentPreset, userPresets]);
 
   // Initialize using shared audio context
   const initializeEqualizer = useCallback(() => {
-    if (!audioElement || filtersConnectedRef.current) return;
+    if (!audioElement || sharedDataRef.current) return;
 
     const sharedData = getSharedAudioContext(audioElement);
     if (!sharedData) return;
 
     sharedDataRef.current = sharedData;
-    const { audioContext, preGainNode, postGainNode } = sharedData;
-
-    console.log("[Equalizer] Creating filters...");
-
-    // Create biquad filters for each frequency band
-    filtersRef.current = bands.map((band) => {
-      const filter = audioContext.createBiquadFilter();
-      filter.type = "peaking";
-      filter.frequency.value = band.frequency;
-      filter.Q.value = 1.4;
-      filter.gain.value = enabled ? band.gain : 0;
-      return filter;
-    });
-
-    // Disconnect default connection and insert filters
-    // Chain: source -> analyser -> preGain -> [FILTERS] -> postGain -> destination
-    preGainNode.disconnect();
-
-    let previousNode: AudioNode = preGainNode;
-    filtersRef.current.forEach((filter) => {
-      previousNode.connect(filter);
-      previousNode = filter;
-    });
-    previousNode.connect(postGainNode);
-
-    filtersConnectedRef.current = true;
     setIsConnected(true);
-    console.log("[Equalizer] Filters connected");
-  }, [audioElement, bands, enabled]);
+    console.log("[Equalizer] Connected");
+  }, [audioElement]);
 
   // Connect when enabled
   useEffect(() => {
@@ -148,44 +224,194 @@ export function useEqualizer(audioElement: HTMLAudioElement | null) {
     };
   }, [audioElement]);
 
-  // Update filter gains when bands change
+  // Rebuild the chain when filters are added or removed, or the equalizer is
+  // switched on or off. Pass and notch filters can't be made neutral through
+  // their gain, so a disabled equalizer is taken out of the chain instead.
+  // Chain: source -> analyser -> preGain -> [PREAMP -> FILTERS] -> postGain -> destination
+  const filterCount = filters.length;
+  useEffect(() => {
+    const sharedData = sharedDataRef.current;
+    if (!isConnected || !sharedData) return;
+    const { audioContext, preGainNode, postGainNode } = sharedData;
+
+    preGainNode.disconnect();
+    preampNodeRef.current?.disconnect();
+    filtersRef.current.forEach((filter) => filter.disconnect());
+    preampNodeRef.current = null;
+    filtersRef.current = [];
+
+    if (!enabled) {
+      preGainNode.connect(postGainNode);
+      return;
+    }
+
+    // New nodes start out at the current settings rather than gliding there
+    // from the Web Audio defaults
+    const profile = settingsRef.current;
+    const preampNode = audioContext.createGain();
+    preampNode.gain.value = 10 ** (profile.preamp / 20);
+    const nodes = profile.filters.map((filter) => {
+      const node = audioContext.createBiquadFilter();
+      configureNode(node, filter);
+      return node;
+    });
+
+    let previousNode: AudioNode = preampNode;
+    preGainNode.connect(preampNode);
+    nodes.forEach((filter) => {
+      previousNode.connect(filter);
+      previousNode = filter;
+    });
+    previousNode.connect(postGainNode);
+
+    preampNodeRef.current = preampNode;
+    filtersRef.current = nodes;
+    console.log(`[Equalizer] ${nodes.length} filters connected`);
+  }, [isConnected, enabled, filterCount]);
+
+  // Update filter parameters when the profile changes
   useEffect(() => {
-    if (!filtersRef.current.length) return;
-
-    filtersRef.current.forEach((filter, index) => {
-      const targetGain = enabled ? bands[index].gain : 0;
-      filter.gain.setTargetAtTime(
-        targetGain,
-        sharedDataRef.current?.audioContext.currentTime || 0,
-        0.01,
-      );
+    const audioContext = sharedDataRef.current?.audioContext;
+    if (!audioContext || !preampNodeRef.current) return;
+    const now = audioContext.currentTime;
+
+    preampNodeRef.current.gain.setTargetAtTime(10 ** (preamp / 20), now, 0.01);
+    filtersRef.current.forEach((node, index) => {
+      if (filters[index]) configureNode(node, filters[index], now);
     });
-  }, [bands, enabled]);
-
-  // Set band gain
-  const setBandGain = useCallback((index: number, gain: number) => {
-    setBands((prev) => {
-      const newBands = [...prev];
-      newBands[index] = {
-        ...newBands[index],
-        gain: Math.max(-12, Math.min(12, gain)),
-      };
-      return newBands;
+  }, [filters, preamp]);
+
+  const loadPreset = useCallback((preset: EqualizerPreset) => {
+    setFilters(copyFilters(preset.filters));
+    setPreampState(clampPreamp(preset.preamp));
+    setCurrentPreset(preset.name);
+  }, []);
+
+  // Apply the preset of the playing track's playback profile, and put the
+  // user's own settings back once a track without one comes on
+  useEffect(() => {
+    if (profilePreset === appliedProfilePresetRef.current) return;
+
+    const preset = profilePreset
+      ? [...userPresets, ...EQUALIZER_PRESETS].find(
+          (p) => p.name === profilePreset,
+        )
+      : undefined;
+    // Wait for user presets to load rather than skip the profile
+    if (profilePreset && !preset) return;
+    appliedProfilePresetRef.current = profilePreset;
+
+    if (preset) {
+      userSettingsRef.current ??= settingsRef.current;
+      loadPreset(preset);
+      setEnabledState(true);
+      return;
+    }
+
+    const saved = userSettingsRef.current;
+    if (!saved) return;
+    userSettingsRef.current = null;
+    setFilters(saved.filters);
+    setPreampState(saved.preamp);
+    setEnabledState(saved.enabled);
+    setCurrentPreset(saved.currentPreset);
+  }, [profilePreset, userPresets, loadPreset]);
+
+  // Change one filter
+  const setFilter = useCallback((index: number, patch: Partial<EqFilter>) => {
+    // Changed by hand: keep these settings when the profile's track ends
+    userSettingsRef.current = null;
+    setFilters((prev) => {
+      if (!prev[index]) return prev;
+      const newFilters = [...prev];
+      newFilters[index] = normalizeEqFilter({ ...prev[index], ...patch });
+      return newFilters;
     });
     setCurrentPreset(null);
   }, []);
 
+  const addFilter = useCallback(() => {
+    userSettingsRef.current = null;
+    setFilters((prev) => [...prev, createEqFilter()]);
+    setCurrentPreset(null);
+  }, []);
+
+  const removeFilter = useCallback((index: number) => {
+    userSettingsRef.current = null;
+    setFilters((prev) => prev.filter((_, i) => i !== index));
+    setCurrentPreset(null);
+  }, []);
+
+  const setPreamp = useCallback((gain: number) => {
+    userSettingsRef.current = null;
+    setPreampState(clampPreamp(gain));
+    setCurrentPreset(null);
+  }, []);
+
   // Apply preset
-  const applyPreset = useCallback((preset: EqualizerPreset) => {
-    setBands((prev) =>
-      prev.map((band, index) => ({
-        ...band,
-        gain: preset.gains[index] ?? 0,
-      })),
-    );
-    setCurrentPreset(preset.name);
+  const applyPreset = useCallback(
+    (preset: EqualizerPreset) => {
+      userSettingsRef.current = null;
+      loadPreset(preset);
+    },
+    [loadPreset],
+  );
+
+  // Save the current filters under a name, replacing a user preset with the
+  // same name. Built-in preset names are taken.
+  const savePreset = useCallback(
+    (name: string): boolean => {
+      const trimmed = name.trim();
+      if (
+        !trimmed ||
+        EQUALIZER_PRESETS.some((preset) => preset.name === trimmed)
+      ) {
+        return false;
+      }
+      const preset: EqualizerPreset = {
+        name: trimmed,
+        preamp,
+        filters: copyFilters(filters),
+      };
+      setUserPresets((prev) => [
+        ...prev.filter((p) => p.name !== trimmed),
+        preset,
+      ]);
+      setCurrentPreset(trimmed);
+      return true;
+    },
+    [filters, preamp],
+  );
+
+  const deletePreset = useCallback((name: string) => {
+    setUserPresets((prev) => prev.filter((preset) => preset.name !== name));
+    setCurrentPreset((prev) => (prev === name ? null : prev));
   }, []);
 
+  /**
+   * Load an EqualizerAPO / AutoEq ParametricEQ.txt profile and keep it as a
+   * user preset. Throws when the text isn't a profile; returns how many
+   * filters had to be left out.
+   */
+  const importProfile = useCallback(
+    (text: string, name: string): number => {
+      const { profile, skipped } = parseParametricEq(text);
+      const trimmed = name.trim() || "Imported";
+      const presetName = EQUALIZER_PRESETS.some((p) => p.name === trimmed)
+        ? `${trimmed} (AutoEq)`
+        : trimmed;
+      const preset: EqualizerPreset = { name: presetName, ...profile };
+
+      setUserPresets((prev) => [
+        ...prev.filter((p) => p.name !== presetName),
+        preset,
+      ]);
+      applyPreset(preset);
+      return skipped;
+    },
+    [applyPreset],
+  );
+
   // Reset to flat
   const reset = useCallback(() => {
     applyPreset(EQUALIZER_PRESETS[0]);
@@ -193,16 +419,30 @@ export function useEqualizer(audioElement: HTMLAudioElement | null) {
 
   // Toggle enabled state
   const toggleEnabled = useCallback(() => {
-    setEnabled((prev) => !prev);
+    userSettingsRef.current = null;
+    setEnabledState((prev) => !prev);
+  }, []);
+
+  const setEnabled = useCallback((value: boolean) => {
+    userSettingsRef.current = null;
+    setEnabledState(value);
   }, []);
 
   return {
-    bands,
+    filters,
+    preamp,
     enabled,
     currentPreset,
+    userPresets,
     isConnected,
-    setBandGain,
+    setFilter,
+    addFilter,
+    removeFilter,
+    setPreamp,
     applyPreset,
+    savePreset,
+    deletePreset,
+    importProfile,
     reset,
     toggleEnabled,
     setEnabled,
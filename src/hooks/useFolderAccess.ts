@@ -0,0 +1,173 @@
+import { useState, useEffect, useCallback, useRef } from "react";
+import type { FolderHandleRecord, Song } from "../types";
+import { getAllFolderHandles, addFolderHandle } from "../lib/db";
+import { generateId } from "../lib/audioMetadata";
+import { isDesktop } from "../lib/platform";
+import {
+  supportsFolderHandles,
+  setGrantedFolderHandle,
+  pickFolderHandle,
+  queryFolderPermission,
+  requestFolderPermission,
+  walkFolderHandle,
+  filesFromEntries,
+  matchSongsToEntries,
+  type FolderEntry,
+  type FolderLink,
+} from "../lib/folderHandles";
+
+export interface RememberedFolder {
+  id: string;
+  name: string;
+  permission: PermissionState;
+}
+
+// Web: folders picked with the File System Access API are remembered in
+// IndexedDB so the library survives a refresh. Access is re-checked on launch;
+// folders still in the "prompt" state need restoreAccess() from a click
+export function useFolderAccess(
+  songs: Song[],
+  linkSongs: (links: Map<string, FolderLink>) => Promise<void>,
+) {
+  const isSupported = !isDesktop() && supportsFolderHandles();
+  const [folders, setFolders] = useState<RememberedFolder[]>([]);
+  const [indexVersion, setIndexVersion] = useState(0);
+  const recordsRef = useRef<FolderHandleRecord[]>([]);
+  // Audio files and cover images found in each granted folder, by record id
+  const entriesRef = useRef(new Map<string, FolderEntry[]>());
+
+  const setPermission = useCallback(
+    (id: string, permission: PermissionState) => {
+      setFolders((prev) =>
+        prev.map((f) => (f.id === id ? { ...f, permission } : f)),
+      );
+    },
+    [],
+  );
+
+  // Songs resolve lazily once granted; the walk only finds moved files
+  const indexFolder = useCallback(
+    async (record: FolderHandleRecord): Promise<FolderEntry[]> => {
+      setGrantedFolderHandle(record.id, record.handle);
+      const entries = await walkFolderHandle(record.id, record.handle);
+      entriesRef.current.set(record.id, entries);
+      setIndexVersion((v) => v + 1);
+      return entries;
+    },
+    [],
+  );
+
+  // Check remembered folders on launch
+  useEffect(() => {
+    if (!isSupported) return;
+
+    let cancelled = false;
+    (async () => {
+      try {
+        const records = await getAllFolderHandles();
+        const permissions = await Promise.all(
+          records.map((r) => queryFolderPermission(r.handle)),
+        );
+        if (cancelled) return;
+
+        recordsRef.current = records;
+        records.forEach((record, i) => {
+          if (permissions[i] === "granted") {
+            setGrantedFolderHandle(record.id, record.handle);
+          }
+        });
+        setFolders(
+          records.map((r, i) => ({
+            id: r.id,
+            name: r.name,
+            permission: permissions[i],
+          })),
+        );
+
+        for (const [i, record] of records.entries()) {
+          if (permissions[i] === "granted") await indexFolder(record);
+        }
+      } catch (error) {
+        console.error("Failed to load remembered folders:", error);
+      }
+    })();
+
+    return () => {
+      cancelled = true;
+    };
+  }, [isSupported, indexFolder]);
+
+  // Link songs to files in granted folders as either side changes. Songs that
+  // are already linked drop out, so this settles after one pass
+  useEffect(() => {
+    if (entriesRef.current.size === 0 || songs.length === 0) return;
+    const entries = [...entriesRef.current.values()].flat();
+    const links = matchSongsToEntries(
+      songs,
+      entries,
+      new Set(entriesRef.current.keys()),
+    );
+    if (links.size > 0) {
+      linkSongs(links).catch((error) =>
+        console.error("Failed to link songs to folders:", error),
+      );
+    }
+  }, [songs, indexVersion, linkSongs]);
+
+  // Pick a folder, remember it, and return its audio files for import or
+  // reconnect. Returns null if the picker was dismissed
+  const addFolder = useCallback(async (): Promise<File[] | null> => {
+    const handle = await pickFolderHandle();
+    if (!handle) return null;
+
+    let record: FolderHandleRecord | undefined;
+    for (const existing of recordsRef.current) {
+      if (await existing.handle.isSameEntry(handle)) {
+        record = existing;
+        break;
+      }
+    }
+    if (!record) {
+      record = {
+        id: generateId(),
+        name: handle.name,
+        handle,
+        addedAt: Date.now(),
+      };
+      await addFolderHandle(record);
+      recordsRef.current = [...recordsRef.current, record];
+      setFolders((prev) => [
+        ...prev,
+        { id: record!.id, name: handle.name, permission: "granted" },
+      ]);
+    } else {
+      setPermission(record.id, "granted");
+    }
+
+    const entries = await indexFolder({ ...record, handle });
+    return filesFromEntries(handle.name, entries);
+  }, [indexFolder, setPermission]);
+
+  // Ask again for folders the browser wants confirmed. Must be called from a
+  // user gesture. Returns how many folders are accessible afterwards
+  const restoreAccess = useCallback(async (): Promise<number> => {
+    const restored: FolderHandleRecord[] = [];
+    for (const record of recordsRef.current) {
+      const permission = await requestFolderPermission(record.handle);
+      setPermission(record.id, permission);
+      if (permission === "granted") restored.push(record);
+    }
+    for (const record of restored) {
+      if (!entriesRef.current.has(record.id)) await indexFolder(record);
+    }
+    return restored.length;
+  }, [indexFolder, setPermission]);
+
+  return {
+    isSupported,
+    folders,
+    pendingCount: folders.filter((f) => f.permission === "prompt").length,
+    addFolder,
+    restoreAccess,
+  };
+}
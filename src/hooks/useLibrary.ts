@@ -11,7 +11,9 @@ import {
   stopLibraryWatcher,
   getLibraryWatcherStatus,
   onLibraryFileChange,
+  onLibraryScanProgress,
   type MusicFileInfo,
+  type LibraryScanProgress,
   type LibraryScanResult,
   type FileChangeEvent,
 } from "../lib/platform";
@@ -43,7 +45,7 @@ export interface UseLibraryReturn {
   addFolder: () => Promise<string | null>;
   removeFolder: (folderPath: string) => Promise<boolean>;
   scanFolder: (folderPath: string) => Promise<MusicFileInfo[]>;
-  scanAllFolders: () => Promise<MusicFileInfo[]>;
+  scanAllFolders: () => Promise<LibraryScanResult>;
   refreshFolders: () => Promise<void>;
   clearError: () => void;
   startWatching: () => Promise<boolean>;
@@ -52,6 +54,28 @@ export interface UseLibraryReturn {
   // Event callback setters
   setOnFileAdded: (callback: ((file: MusicFileInfo) => void) | null) => void;
   setOnFileRemoved: (callback: ((filePath: string) => void) | null) => void;
+  setOnFileMoved: (
+    callback: ((previousPath: string, file: MusicFileInfo) => void) | null,
+  ) => void;
+  setOnFileChanged: (callback: ((file: MusicFileInfo) => void) | null) => void;
+}
+
+// Folder listing has no known total; the bar fills once hashing starts
+function describeScanProgress(
+  progress: LibraryScanProgress,
+): NonNullable<LibraryState["scanProgress"]> {
+  if (progress.phase === "walking") {
+    return {
+      current: 0,
+      total: 0,
+      currentFolder: `Listing folders (${progress.current.toLocaleString()} files found)`,
+    };
+  }
+  return {
+    current: progress.current,
+    total: progress.total,
+    currentFolder: `Reading ${progress.current.toLocaleString()} of ${progress.total.toLocaleString()} new or changed files`,
+  };
 }
 
 export function useLibrary(): UseLibraryReturn {
@@ -73,6 +97,12 @@ export function useLibrary(): UseLibraryReturn {
   const onFileRemovedCallback = useRef<((filePath: string) => void) | null>(
     null,
   );
+  const onFileMovedCallback = useRef<
+    ((previousPath: string, file: MusicFileInfo) => void) | null
+  >(null);
+  const onFileChangedCallback = useRef<((file: MusicFileInfo) => void) | null>(
+    null,
+  );
 
   // Refresh folder list from electron store
   const refreshFolders = useCallback(async () => {
@@ -160,6 +190,14 @@ export function useLibrary(): UseLibraryReturn {
         onFileAddedCallback.current(event.file);
       } else if (event.type === "remove" && onFileRemovedCallback.current) {
         onFileRemovedCallback.current(event.file.path);
+      } else if (
+        event.type === "move" &&
+        event.previousPath &&
+        onFileMovedCallback.current
+      ) {
+        onFileMovedCallback.current(event.previousPath, event.file);
+      } else if (event.type === "change" && onFileChangedCallback.current) {
+        onFileChangedCallback.current(event.file);
       }
     });
 
@@ -168,6 +206,19 @@ export function useLibrary(): UseLibraryReturn {
     };
   }, [isDesktop]);
 
+  // Progress of scanAllFolders, streamed while the scan runs off the main thread
+  useEffect(() => {
+    if (!isDesktop) return;
+
+    return onLibraryScanProgress((progress: LibraryScanProgress) => {
+      setState((prev) =>
+        prev.isScanning
+          ? { ...prev, scanProgress: describeScanProgress(progress) }
+          : prev,
+      );
+    });
+  }, [isDesktop]);
+
   // Cleanup watcher on unmount
   useEffect(() => {
     return () => {
@@ -285,8 +336,8 @@ export function useLibrary(): UseLibraryReturn {
   );
 
   // Scan all folders
-  const scanAllFolders = useCallback(async (): Promise<MusicFileInfo[]> => {
-    if (!isDesktop) return [];
+  const scanAllFolders = useCallback(async (): Promise<LibraryScanResult> => {
+    if (!isDesktop) return { files: [], totalCount: 0 };
 
     try {
       setState((prev) => ({
@@ -310,7 +361,7 @@ export function useLibrary(): UseLibraryReturn {
         error: result.error || null,
       }));
 
-      return result.files;
+      return result;
     } catch (error) {
       console.error("Failed to scan all folders:", error);
       setState((prev) => ({
@@ -319,7 +370,7 @@ export function useLibrary(): UseLibraryReturn {
         scanProgress: null,
         error: "Failed to scan folders",
       }));
-      return [];
+      return { files: [], totalCount: 0, error: String(error) };
     }
   }, [isDesktop]);
 
@@ -343,6 +394,22 @@ export function useLibrary(): UseLibraryReturn {
     [],
   );
 
+  const setOnFileMoved = useCallback(
+    (
+      callback: ((previousPath: string, file: MusicFileInfo) => void) | null,
+    ) => {
+      onFileMovedCallback.current = callback;
+    },
+    [],
+  );
+
+  const setOnFileChanged = useCallback(
+    (callback: ((file: MusicFileInfo) => void) | null) => {
+      onFileChangedCallback.current = callback;
+    },
+    [],
+  );
+
   return {
     folders: state.folders,
     isScanning: state.isScanning,
@@ -361,5 +428,7 @@ export function useLibrary(): UseLibraryReturn {
     stopWatching,
     setOnFileAdded,
     setOnFileRemoved,
+    setOnFileMoved,
+    setOnFileChanged,
   };
 }
@@ -0,0 +1,273 @@
+import { useCallback, useEffect } from "react";
+import type { AppSettings, PlaybackProfile } from "../types";
+import { getLibraryRecords, replaceLibraryRecords } from "../lib/db";
+import { artworkFromBackup, artworkToBackup } from "../lib/artwork";
+import {
+  createBackup,
+  migrateBackup,
+  planRestore,
+  type BackupConflict,
+  type BackupData,
+  type BackupRestoreMode,
+  type BackupRestorePlan,
+  type LibraryBackup,
+} from "../lib/backup";
+import {
+  addLibraryFolder,
+  getLibraryFolders,
+  isDesktop,
+  openBackupFile,
+  openFolderPicker,
+  pickBackupSavePath,
+  removeLibraryFolder,
+  resetLibraryScanIndex,
+  writeBackupFile,
+  writeTextFile,
+} from "../lib/platform";
+import { SETTINGS_KEY } from "./useSettings";
+import { STORAGE_KEY as EQUALIZER_STORAGE_KEY } from "./useEqualizer";
+import { SONG_PITCH_KEY } from "./useSongPitchMemory";
+import { PLAYBACK_PROFILES_KEY } from "./usePlaybackProfiles";
+
+const DAY_MS = 24 * 60 * 60 * 1000;
+
+const AUTO_BACKUP_INTERVAL_MS = {
+  daily: DAY_MS,
+  weekly: 7 * DAY_MS,
+};
+
+// How often a running app checks whether a scheduled backup is due
+const AUTO_BACKUP_CHECK_MS = 60 * 60 * 1000;
+
+function readStoredJson<T>(key: string): T | null {
+  try {
+    const saved = localStorage.getItem(key);
+    return saved ? JSON.parse(saved) : null;
+  } catch {
+    return null;
+  }
+}
+
+export function getBackupFileName(now: number): string {
+  return `vinyl-backup-${new Date(now).toISOString().slice(0, 10)}.json`;
+}
+
+async function collectBackupData(): Promise<BackupData> {
+  const { artwork, ...records } = await getLibraryRecords();
+  return {
+    ...records,
+    artwork: await Promise.all(artwork.map(artworkToBackup)),
+    settings: readStoredJson<Partial<AppSettings>>(SETTINGS_KEY),
+    equalizer: readStoredJson<Record<string, unknown>>(EQUALIZER_STORAGE_KEY),
+    songPitch: readStoredJson<Record<string, unknown>>(SONG_PITCH_KEY),
+    playbackProfiles:
+      readStoredJson<PlaybackProfile[]>(PLAYBACK_PROFILES_KEY) ?? [],
+    libraryFolders: isDesktop() ? await getLibraryFolders() : [],
+  };
+}
+
+async function serializeBackup(now: number): Promise<string> {
+  return JSON.stringify(createBackup(await collectBackupData(), now));
+}
+
+// Ask the user to confirm a restored folder the app may not read yet, or pick
+// where it is on this machine. Resolves to the folder picked, if any
+async function confirmLibraryFolder(folder: string): Promise<string | null> {
+  return openFolderPicker({
+    title: `Confirm the restored library folder ${folder}`,
+    defaultPath: folder,
+  });
+}
+
+// Bring the desktop library folders in line with the restored list. Folders
+// that were never picked on this install are confirmed through the folder
+// dialog; ones that can't be added are reported rather than failing the
+// whole restore
+async function syncLibraryFolders(
+  folders: string[],
+  mode: BackupRestoreMode,
+): Promise<BackupConflict[]> {
+  const conflicts: BackupConflict[] = [];
+  const current = await getLibraryFolders();
+
+  if (mode === "replace") {
+    for (const folder of current) {
+      if (!folders.includes(folder)) await removeLibraryFolder(folder);
+    }
+  }
+  for (const folder of folders) {
+    if (current.includes(folder)) continue;
+    let added = folder;
+    let result = await addLibraryFolder(folder);
+    if (result.code === "PATH_NOT_ALLOWED") {
+      const picked = await confirmLibraryFolder(folder);
+      if (!picked) {
+        conflicts.push({
+          kind: "folder",
+          name: folder,
+          resolution: "Not added: not confirmed",
+        });
+        continue;
+      }
+      added = picked;
+      result = await addLibraryFolder(picked);
+    }
+
+    if (result.error) {
+      conflicts.push({
+        kind: "folder",
+        name: folder,
+        resolution: `Not added: ${result.error}`,
+      });
+    } else if (added !== folder) {
+      conflicts.push({
+        kind: "folder",
+        name: folder,
+        resolution: `Added ${added} in its place`,
+      });
+    }
+  }
+  return conflicts;
+}
+
+// Back up and restore everything Vinyl keeps: the library database, settings,
+// the equalizer, playback profiles, remembered song pitch and desktop library
+// folders
+export function useLibraryBackup() {
+  // Resolves to false when the save dialog is cancelled
+  const exportBackup = useCallback(async (): Promise<boolean> => {
+    const now = Date.now();
+    const fileName = getBackupFileName(now);
+    const content = await serializeBackup(now);
+
+    if (isDesktop()) {
+      const filePath = await pickBackupSavePath(fileName);
+      if (!filePath) return false;
+      if (!(await writeTextFile(filePath, content))) {
+        throw new Error("Couldn't write the backup file");
+      }
+      return true;
+    }
+
+    const url = URL.createObjectURL(
+      new Blob([content], { type: "application/json" }),
+    );
+    const link = document.createElement("a");
+    link.href = url;
+    link.download = fileName;
+    link.click();
+    URL.revokeObjectURL(url);
+    return true;
+  }, []);
+
+  // Desktop picks the file through the native dialog; web passes the File
+  // chosen in an <input>. Throws when the file isn't a usable backup
+  const readBackup = useCallback(
+    async (file?: File): Promise<LibraryBackup | null> => {
+      let text: string;
+      if (file) {
+        text = await file.text();
+      } else {
+        const picked = await openBackupFile();
+        if (!picked) return null;
+        text = new TextDecoder().decode(picked.data);
+      }
+
+      let raw: unknown;
+      try {
+        raw = JSON.parse(text);
+      } catch {
+        throw new Error("This file is not a Vinyl backup");
+      }
+      return migrateBackup(raw);
+    },
+    [],
+  );
+
+  // Writes the restored data; the app has to reload afterwards to pick it up
+  const restoreBackup = useCallback(
+    async (
+      backup: LibraryBackup,
+      mode: BackupRestoreMode,
+    ): Promise<BackupRestorePlan> => {
+      const plan = planRestore(await collectBackupData(), backup.data, mode);
+      const { data } = plan;
+
+      await replaceLibraryRecords({
+        ...data,
+        artwork: data.artwork.flatMap(
+          (entry) => artworkFromBackup(entry) ?? [],
+        ),
+      });
+      // Songs left out of the backup are gone, but the index still counts
+      // their files as imported; rescanning from scratch brings them back
+      if (mode === "replace") await resetLibraryScanIndex();
+      if (data.settings) {
+        localStorage.setItem(SETTINGS_KEY, JSON.stringify(data.settings));
+      }
+      if (data.equalizer) {
+        localStorage.setItem(
+          EQUALIZER_STORAGE_KEY,
+          JSON.stringify(data.equalizer),
+        );
+      }
+      if (data.songPitch) {
+        localStorage.setItem(SONG_PITCH_KEY, JSON.stringify(data.songPitch));
+      }
+      localStorage.setItem(
+        PLAYBACK_PROFILES_KEY,
+        JSON.stringify(data.playbackProfiles),
+      );
+
+      const folderConflicts = isDesktop()
+        ? await syncLibraryFolders(data.libraryFolders, mode)
+        : [];
+      return { ...plan, conflicts: [...plan.conflicts, ...folderConflicts] };
+    },
+    [],
+  );
+
+  return { exportBackup, readBackup, restoreBackup };
+}
+
+// Write a backup into the chosen folder once a day or week (Desktop only)
+export function useScheduledBackup(
+  settings: AppSettings,
+  updateSetting: <K extends keyof AppSettings>(
+    key: K,
+    value: AppSettings[K],
+  ) => void,
+) {
+  const { autoBackup, autoBackupFolder, lastAutoBackupAt } = settings;
+
+  useEffect(() => {
+    if (!isDesktop() || autoBackup === "off" || !autoBackupFolder) return;
+
+    const interval = AUTO_BACKUP_INTERVAL_MS[autoBackup];
+    let isRunning = false;
+
+    const runIfDue = async () => {
+      const now = Date.now();
+      if (isRunning || now - (lastAutoBackupAt ?? 0) < interval) return;
+
+      isRunning = true;
+      try {
+        const fileName = getBackupFileName(now);
+        const content = await serializeBackup(now);
+        if (await writeBackupFile(autoBackupFolder, fileName, content)) {
+          updateSetting("lastAutoBackupAt", now);
+        } else {
+          console.error("Scheduled backup couldn't be written:", fileName);
+        }
+      } catch (error) {
+        console.error("Scheduled backup failed:", error);
+      } finally {
+        isRunning = false;
+      }
+    };
+
+    runIfDue();
+    const timer = setInterval(runIfDue, AUTO_BACKUP_CHECK_MS);
+    return () => clearInterval(timer);
+  }, [autoBackup, autoBackupFolder, lastAutoBackupAt, updateSetting]);
+}
@@ -0,0 +1,67 @@
+import { useState, useEffect, useMemo } from "react";
+import type { Song } from "../types";
+import { searchSongIds } from "../lib/db";
+import { parseSearchQuery } from "../lib/searchQuery";
+
+// Wait for a pause in typing before querying the index
+const SEARCH_DEBOUNCE_MS = 80;
+
+/**
+ * Search the library through the persistent search index
+ * Returns null while the query is empty, otherwise the matching songs in
+ * library order.
+ */
+export function useLibrarySearch(songs: Song[], query: string) {
+  const [matchedIds, setMatchedIds] = useState<string[] | null>(null);
+  const [isSearching, setIsSearching] = useState(false);
+
+  const clauses = useMemo(() => parseSearchQuery(query), [query]);
+
+  // Re-query when songs change too, since imports and edits update the index
+  useEffect(() => {
+    if (clauses.length === 0) {
+      setMatchedIds(null);
+      setIsSearching(false);
+      return;
+    }
+
+    let cancelled = false;
+    setIsSearching(true);
+
+    const timer = setTimeout(() => {
+      searchSongIds(clauses)
+        .then((ids) => {
+          if (!cancelled) setMatchedIds(ids);
+        })
+        .catch((error) => {
+          console.error("Search failed:", error);
+        })
+        .finally(() => {
+          if (!cancelled) setIsSearching(false);
+        });
+    }, SEARCH_DEBOUNCE_MS);
+
+    return () => {
+      cancelled = true;
+      clearTimeout(timer);
+    };
+  }, [clauses, songs]);
+
+  // Position lookup is rebuilt per library change, not per keystroke
+  const songIndex = useMemo(
+    () =>
+      new Map(songs.map((song, position) => [song.id, { song, position }] as const)),
+    [songs],
+  );
+
+  const results = useMemo(() => {
+    if (!matchedIds) return null;
+    return matchedIds
+      .map((id) => songIndex.get(id))
+      .filter((entry) => entry !== undefined)
+      .sort((a, b) => a.position - b.position)
+      .map((entry) => entry.song);
+  }, [matchedIds, songIndex]);
+
+  return { results, isSearching };
+}
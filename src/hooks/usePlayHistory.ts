@@ -0,0 +1,116 @@
+import { useEffect, useRef } from "react";
+import type { PlayRecord, PlaySource, Song } from "../types";
+import { generateId } from "../lib/audioMetadata";
+import { playOutcome } from "../lib/playStats";
+
+export interface PlayContext {
+  source: PlaySource;
+  sourceId?: string;
+}
+
+interface Listen extends PlayContext {
+  song: Song;
+  startedAt: number;
+  listened: number;
+  lastTime: number;
+}
+
+// Progress steps larger than this are seeks, not listening
+const MAX_PROGRESS_STEP = 2;
+// Shorter listens (e.g. restoring the last song paused) aren't logged
+const MIN_LISTEN_SECONDS = 1;
+
+interface UsePlayHistoryOptions {
+  currentSong: Song | null;
+  isPlaying: boolean;
+  currentTime: number;
+  // Where the current song was started from, read when it starts
+  getPlayContext: () => PlayContext;
+  recordPlay: (play: PlayRecord) => Promise<void>;
+}
+
+// Logs a play record each time the current song changes, repeats or the page
+// closes, counting only time actually heard
+export function usePlayHistory({
+  currentSong,
+  isPlaying,
+  currentTime,
+  getPlayContext,
+  recordPlay,
+}: UsePlayHistoryOptions) {
+  const listenRef = useRef<Listen | null>(null);
+  const getPlayContextRef = useRef(getPlayContext);
+  const recordPlayRef = useRef(recordPlay);
+
+  useEffect(() => {
+    getPlayContextRef.current = getPlayContext;
+  }, [getPlayContext]);
+
+  useEffect(() => {
+    recordPlayRef.current = recordPlay;
+  }, [recordPlay]);
+
+  const finishListen = () => {
+    const listen = listenRef.current;
+    listenRef.current = null;
+    if (!listen || listen.listened < MIN_LISTEN_SECONDS) return;
+
+    void recordPlayRef.current({
+      id: generateId(),
+      songId: listen.song.id,
+      playedAt: listen.startedAt,
+      listened: Math.round(listen.listened),
+      outcome: playOutcome(listen.listened, listen.song.duration),
+      source: listen.source,
+      sourceId: listen.sourceId,
+    });
+  };
+
+  const startListen = (song: Song, time: number) => {
+    listenRef.current = {
+      song,
+      startedAt: Date.now(),
+      listened: 0,
+      lastTime: time,
+      ...getPlayContextRef.current(),
+    };
+  };
+
+  // A different song closes the previous listen
+  const currentSongId = currentSong?.id;
+  useEffect(() => {
+    if (listenRef.current?.song.id === currentSongId) return;
+    finishListen();
+    if (currentSong) startListen(currentSong, 0);
+  }, [currentSongId]);
+
+  // Accumulate heard time from playback progress
+  useEffect(() => {
+    const listen = listenRef.current;
+    if (!listen || !currentSong || listen.song.id !== currentSong.id) return;
+
+    const step = currentTime - listen.lastTime;
+    // Back at the start after reaching the end: repeat-one started over
+    const repeated =
+      currentTime < MAX_PROGRESS_STEP &&
+      listen.song.duration > 0 &&
+      listen.lastTime >= listen.song.duration - MAX_PROGRESS_STEP;
+    if (repeated) {
+      finishListen();
+      startListen(currentSong, currentTime);
+      return;
+    }
+
+    if (isPlaying && step > 0 && step <= MAX_PROGRESS_STEP) {
+      listen.listened += step;
+    }
+    listen.lastTime = currentTime;
+  }, [currentTime]);
+
+  // Closing the page ends the current listen
+  useEffect(() => {
+    const handlePageHide = () => finishListen();
+    window.addEventListener("pagehide", handlePageHide);
+    return () => window.removeEventListener("pagehide", handlePageHide);
+  }, []);
+}
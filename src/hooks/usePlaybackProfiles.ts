@@ -0,0 +1,64 @@
+import { useState, useEffect, useCallback } from "react";
+import type { PlaybackProfile, PlaybackProfileTarget } from "../types";
+import { generateId } from "../lib/audioMetadata";
+
+export const PLAYBACK_PROFILES_KEY = "vinyl-playback-profiles";
+
+export type PlaybackProfileInput = Omit<PlaybackProfile, "id" | "createdAt">;
+
+// Profiles attaching an EQ preset, speed and gain offset to songs, albums,
+// artists, playlists and output devices
+export function usePlaybackProfiles() {
+  const [profiles, setProfiles] = useState<PlaybackProfile[]>([]);
+  const [isLoaded, setIsLoaded] = useState(false);
+
+  // Load profiles from localStorage on mount
+  useEffect(() => {
+    try {
+      const saved = localStorage.getItem(PLAYBACK_PROFILES_KEY);
+      if (saved) {
+        const parsed: unknown = JSON.parse(saved);
+        if (Array.isArray(parsed)) setProfiles(parsed as PlaybackProfile[]);
+      }
+    } catch (error) {
+      console.error("Failed to load playback profiles:", error);
+    }
+    setIsLoaded(true);
+  }, []);
+
+  // Save profiles to localStorage
+  useEffect(() => {
+    if (!isLoaded) return;
+    try {
+      localStorage.setItem(PLAYBACK_PROFILES_KEY, JSON.stringify(profiles));
+    } catch (error) {
+      console.error("Failed to save playback profiles:", error);
+    }
+  }, [profiles, isLoaded]);
+
+  // Add a profile, replacing the one already attached to the same target
+  const saveProfile = useCallback((input: PlaybackProfileInput) => {
+    setProfiles((prev) => [
+      ...prev.filter(
+        (p) => p.target !== input.target || p.key !== input.key,
+      ),
+      { ...input, id: generateId(), createdAt: Date.now() },
+    ]);
+  }, []);
+
+  const deleteProfile = useCallback((id: string) => {
+    setProfiles((prev) => prev.filter((p) => p.id !== id));
+  }, []);
+
+  // Drop the profile of something that no longer exists, e.g. a deleted song
+  const deleteProfilesFor = useCallback(
+    (target: PlaybackProfileTarget, key: string) => {
+      setProfiles((prev) =>
+        prev.filter((p) => p.target !== target || p.key !== key),
+      );
+    },
+    [],
+  );
+
+  return { profiles, saveProfile, deleteProfile, deleteProfilesFor };
+}
@@ -0,0 +1,118 @@
+import { useCallback } from "react";
+import type { Song } from "../types";
+import {
+  isDesktop,
+  openPlaylistFile,
+  pickPlaylistSavePath,
+  writeTextFile,
+} from "../lib/platform";
+import {
+  PLAYLIST_FILE_FORMATS,
+  decodePlaylistText,
+  getPlaylistBaseDir,
+  matchPlaylistEntries,
+  parsePlaylistFile,
+  serializePlaylist,
+  type PlaylistFileFormat,
+  type PlaylistPathMode,
+} from "../lib/playlistFiles";
+
+export interface PlaylistImportResult {
+  playlistId: string;
+  name: string;
+  matched: number;
+  // Entries with no song in the library
+  unmatched: number;
+}
+
+// Characters that aren't allowed in file names on some platforms
+const UNSAFE_FILE_NAME_CHARS = /[\\/:*?"<>|]/g;
+
+// Import and export playlists as M3U/M3U8, PLS or XSPF files
+export function usePlaylistFiles(
+  songs: Song[],
+  createPlaylist: (name: string, songIds: string[]) => Promise<string>,
+) {
+  // Desktop picks the file through the native dialog so relative entries can
+  // be resolved; web passes the File chosen in an <input>
+  const importPlaylist = useCallback(
+    async (file?: File): Promise<PlaylistImportResult | null> => {
+      let fileName: string;
+      let data: ArrayBuffer | Uint8Array;
+      let baseDir: string | undefined;
+
+      if (file) {
+        fileName = file.name;
+        data = await file.arrayBuffer();
+      } else {
+        const picked = await openPlaylistFile();
+        if (!picked) return null;
+        fileName = picked.path;
+        data = picked.data;
+        baseDir = getPlaylistBaseDir(picked.path);
+      }
+
+      const parsed = parsePlaylistFile(fileName, decodePlaylistText(data));
+      const { songIds, unmatched } = matchPlaylistEntries(
+        parsed.entries,
+        songs,
+        baseDir,
+      );
+      const playlistId = await createPlaylist(parsed.name, songIds);
+
+      return {
+        playlistId,
+        name: parsed.name,
+        matched: songIds.length,
+        unmatched: unmatched.length,
+      };
+    },
+    [songs, createPlaylist],
+  );
+
+  // Resolves to false when the save dialog is cancelled
+  const exportPlaylist = useCallback(
+    async (
+      name: string,
+      playlistSongs: Song[],
+      format: PlaylistFileFormat,
+      pathMode: PlaylistPathMode,
+    ): Promise<boolean> => {
+      const fileName = `${name.replace(UNSAFE_FILE_NAME_CHARS, "_")}.${format}`;
+
+      if (isDesktop()) {
+        const path = await pickPlaylistSavePath(fileName);
+        if (!path) return false;
+
+        const content = serializePlaylist(name, playlistSongs, {
+          format,
+          pathMode,
+          baseDir: getPlaylistBaseDir(path),
+        });
+        if (!(await writeTextFile(path, content))) {
+          throw new Error("Could not write the playlist file");
+        }
+        return true;
+      }
+
+      // Web: download it; entries are paths within the music folder
+      const content = serializePlaylist(name, playlistSongs, {
+        format,
+        pathMode: "relative",
+      });
+      const mimeType = PLAYLIST_FILE_FORMATS.find(
+        (f) => f.key === format,
+      )?.mimeType;
+      const url = URL.createObjectURL(new Blob([content], { type: mimeType }));
+      const link = document.createElement("a");
+      link.href = url;
+      link.download = fileName;
+      link.click();
+      URL.revokeObjectURL(url);
+      return true;
+    },
+    [],
+  );
+
+  return { importPlaylist, exportPlaylist };
+}
@@ -1,5 +1,5 @@
 import { useState, useEffect, useCallback, useMemo } from "react";
-import type { Playlist } from "../types";
+import type { Playlist, SmartPlaylistRules } from "../types";
 import {
   getAllPlaylists,
   addPlaylist as dbAddPlaylist,
@@ -76,6 +76,25 @@ export function usePlaylists() {
     [],
   );
 
+  // Create a smart playlist; its songs come from evaluating the rules
+  const createSmartPlaylist = useCallback(
+    async (name: string, rules: SmartPlaylistRules): Promise<string> => {
+      const playlist: Playlist = {
+        id: generateId(),
+        name,
+        songIds: [],
+        rules,
+        createdAt: Date.now(),
+        updatedAt: Date.now(),
+      };
+
+      await dbAddPlaylist(playlist);
+      setPlaylists((prev) => [playlist, ...prev]);
+      return playlist.id;
+    },
+    [],
+  );
+
   // Delete playlist
   const deletePlaylist = useCallback(async (playlistId: string) => {
     await dbDeletePlaylist(playlistId);
@@ -101,7 +120,8 @@ export function usePlaylists() {
   const addSongToPlaylist = useCallback(
     async (playlistId: string, songId: string) => {
       const playlist = await getPlaylist(playlistId);
-      if (!playlist) return;
+      // Smart playlists only hold what their rules match
+      if (!playlist || playlist.rules) return;
 
       if (!playlist.songIds.includes(songId)) {
         const updated = {
@@ -141,7 +161,7 @@ export function usePlaylists() {
   const addSongsToPlaylist = useCallback(
     async (playlistId: string, songIds: string[]) => {
       const playlist = await getPlaylist(playlistId);
-      if (!playlist) return;
+      if (!playlist || playlist.rules) return;
 
       const newSongIds = songIds.filter((id) => !playlist.songIds.includes(id));
       if (newSongIds.length === 0) return;
@@ -229,6 +249,7 @@ export function usePlaylists() {
     isLoading,
     favoriteSongIds,
     createPlaylist,
+    createSmartPlaylist,
     deletePlaylist,
     updatePlaylist,
     addSongToPlaylist,
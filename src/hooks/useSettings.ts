@@ -1,7 +1,7 @@
 import { useState, useEffect, useCallback } from "react";
 import type { AppSettings, Theme, RepeatMode, QueueBehavior } from "../types";
 
-const SETTINGS_KEY = "vinyl-app-settings";
+export const SETTINGS_KEY = "vinyl-app-settings";
 
 // Helper to adjust color brightness
 function adjustColorBrightness(hex: string, percent: number): string {
@@ -25,6 +25,10 @@ const defaultSettings: AppSettings = {
   autoPlay: true,
   gaplessPlayback: false,
   crossfadeDuration: 0,
+  replayGainMode: "off",
+  replayGainPreamp: 0,
+  replayGainPreventClipping: true,
+  preservePitch: true,
   defaultVolume: 0.7,
   rememberVolume: true,
   defaultShuffleMode: false,
@@ -34,6 +38,10 @@ const defaultSettings: AppSettings = {
   queueBehavior: "replace",
   clearQueueOnNewPlaylist: true,
 
+  // Library
+  librarySort: "addedAt",
+  librarySortDirection: "desc",
+
   // Equalizer
   eqEnabled: true,
   eqPreset: "Flat",
@@ -47,6 +55,11 @@ const defaultSettings: AppSettings = {
 
   // Confirmations
   skipDeleteConfirmation: false,
+
+  // Scheduled library backups
+  autoBackup: "off",
+  autoBackupFolder: null,
+  lastAutoBackupAt: null,
 };
 
 export function useSettings() {
@@ -0,0 +1,32 @@
+import { useState, useEffect, useMemo } from "react";
+import type { Playlist, Song } from "../types";
+import { evaluateSmartPlaylist, isSmartPlaylist } from "../lib/smartPlaylists";
+
+const HOUR_MS = 60 * 60 * 1000;
+
+// Songs for each smart playlist, by playlist id. Re-evaluated whenever the
+// library or the rules change, and hourly so date rules roll over
+export function useSmartPlaylists(
+  playlists: Playlist[],
+  songs: Song[],
+): Map<string, Song[]> {
+  const [now, setNow] = useState(() => Date.now());
+
+  useEffect(() => {
+    const timer = setInterval(() => setNow(Date.now()), HOUR_MS);
+    return () => clearInterval(timer);
+  }, []);
+
+  return useMemo(() => {
+    const result = new Map<string, Song[]>();
+    for (const playlist of playlists) {
+      if (isSmartPlaylist(playlist)) {
+        result.set(
+          playlist.id,
+          evaluateSmartPlaylist(playlist.rules, songs, now),
+        );
+      }
+    }
+    return result;
+  }, [playlists, songs, now]);
+}
@@ -0,0 +1,55 @@
+import { useState, useEffect, useCallback } from "react";
+
+export const SONG_PITCH_KEY = "vinyl-song-pitch";
+
+// Transposition and tempo remembered for one song
+export interface SongPitch {
+  semitones: number;
+  speed: number;
+}
+
+// Songs whose key and tempo were set to be remembered, by song id
+export function useSongPitchMemory() {
+  const [memory, setMemory] = useState<Record<string, SongPitch>>({});
+  const [isLoaded, setIsLoaded] = useState(false);
+
+  // Load from localStorage on mount
+  useEffect(() => {
+    try {
+      const saved = localStorage.getItem(SONG_PITCH_KEY);
+      if (saved) {
+        const parsed: unknown = JSON.parse(saved);
+        if (parsed && typeof parsed === "object" && !Array.isArray(parsed)) {
+          setMemory(parsed as Record<string, SongPitch>);
+        }
+      }
+    } catch (error) {
+      console.error("Failed to load song pitch settings:", error);
+    }
+    setIsLoaded(true);
+  }, []);
+
+  // Save to localStorage
+  useEffect(() => {
+    if (!isLoaded) return;
+    try {
+      localStorage.setItem(SONG_PITCH_KEY, JSON.stringify(memory));
+    } catch (error) {
+      console.error("Failed to save song pitch settings:", error);
+    }
+  }, [memory, isLoaded]);
+
+  const remember = useCallback((songId: string, pitch: SongPitch) => {
+    setMemory((prev) => ({ ...prev, [songId]: pitch }));
+  }, []);
+
+  const forget = useCallback((songId: string) => {
+    setMemory((prev) => {
+      const next = { ...prev };
+      delete next[songId];
+      return next;
+    });
+  }, []);
+
+  return { memory, remember, forget };
+}
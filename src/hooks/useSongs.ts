@@ -1,12 +1,20 @@
 import { useState, useEffect, useCallback, useRef } from "react";
-import type { Song } from "../types";
+import type { PlayRecord, Song, TagEdit } from "../types";
 import {
   getAllSongs,
   addSong,
+  addSongs,
   deleteSong as dbDeleteSong,
   updateSong as dbUpdateSong,
+  recordPlay as dbRecordPlay,
 } from "../lib/db";
 import { extractMetadata, isAudioFile, generateId } from "../lib/audioMetadata";
+import {
+  SONG_METADATA_VERSION,
+  mergeTagFields,
+  needsMetadataRefresh,
+  pickTagFields,
+} from "../lib/songMetadata";
 import {
   isDesktop,
   fileExists,
@@ -15,36 +23,113 @@ import {
   getStoredFolderPath,
   saveStoredFolderPath,
   readFileData,
+  readNativeMetadata,
+  getFilesStats,
+  getSongFolders,
+  migrateSongFolders,
+  forgetScannedFiles,
+  analyzeLoudness as analyzeNativeLoudness,
+  writeTags,
+  type MusicFileInfo,
 } from "../lib/platform";
+import { groupSongsForAnalysis } from "../lib/replayGain";
+import {
+  isFolderGranted,
+  resolveFolderFile,
+  type FolderLink,
+} from "../lib/folderHandles";
+import {
+  chooseRelinks,
+  findRelinkCandidates,
+  pathsNeedingDuration,
+} from "../lib/relink";
+import { getSongLookupKey } from "../lib/songLookup";
+import { applyTagEdit } from "../lib/tagEdits";
+import {
+  findFolderCovers,
+  getRelativeFolder,
+  hasLegacyArtwork,
+  pruneUnusedArtwork,
+  replaceLegacyArtwork,
+  storeArtworkDataUrl,
+  storeArtworkFile,
+  storeLegacyArtwork,
+} from "../lib/artwork";
 
 // Concurrency limit for batch imports
 const IMPORT_CONCURRENCY = 5;
 
-// Normalize string for duplicate checking
-const normalize = (str: string | undefined): string =>
-  (str || "").toLowerCase().trim();
+// Files per native metadata request during desktop imports
+const NATIVE_METADATA_BATCH_SIZE = 50;
 
-// Generate a lookup key for duplicate detection
-function getSongLookupKey(title: string | undefined, artist: string | undefined, duration: number): string {
-  // Round duration to nearest 2 seconds for tolerance
-  const roundedDuration = Math.round((duration || 0) / 2) * 2;
-  return `${normalize(title)}|${normalize(artist)}|${roundedDuration}`;
-}
+// Albums per native loudness request, so progress moves album by album
+const LOUDNESS_ALBUMS_PER_REQUEST = 4;
 
 // Build a lookup Map for O(1) duplicate checking
 function buildSongLookupMap(songs: Song[]): Map<string, Song> {
   const map = new Map<string, Song>();
   for (const song of songs) {
-    const key = getSongLookupKey(song.title, song.artist, song.duration);
-    map.set(key, song);
-    // Also add by filePath for quick path-based lookups
-    if (song.filePath) {
-      map.set(`path:${song.filePath}`, song);
-    }
+    addToSongLookupMap(map, song);
   }
   return map;
 }
 
+function addToSongLookupMap(map: Map<string, Song>, song: Song): void {
+  const key = getSongLookupKey(song.title, song.artist, song.duration);
+  map.set(key, song);
+  // Also add by filePath for quick path-based lookups
+  if (song.filePath) {
+    map.set(`path:${song.filePath}`, song);
+  }
+}
+
+// Desktop: read tags for a batch of paths with the native reader
+// Files it can't read are left out and go through readMetadataFromFileData instead
+// Cover art comes back inline and is moved into the artwork store
+export async function readNativeMetadataMap(
+  paths: string[],
+): Promise<Map<string, Partial<Song>>> {
+  const metadataByPath = new Map<string, Partial<Song>>();
+  if (paths.length === 0) {
+    return metadataByPath;
+  }
+
+  // Every track of an album usually carries the same cover
+  const artworkIds = new Map<string, Promise<string | undefined>>();
+
+  const results = await readNativeMetadata(paths);
+  for (const result of results ?? []) {
+    if (result.metadata) {
+      const { coverArt, ...metadata } = result.metadata;
+      if (coverArt) {
+        let artworkId = artworkIds.get(coverArt);
+        if (!artworkId) {
+          artworkId = storeArtworkDataUrl(coverArt);
+          artworkIds.set(coverArt, artworkId);
+        }
+        metadata.artworkId = await artworkId;
+      }
+      metadataByPath.set(result.path, metadata);
+    } else if (result.error) {
+      console.warn("Native metadata unavailable for:", result.path, result.error);
+    }
+  }
+  return metadataByPath;
+}
+
+// Desktop fallback: load the file into the renderer and parse it with music-metadata
+async function readMetadataFromFileData(file: {
+  path: string;
+  name: string;
+}): Promise<Partial<Song>> {
+  const result = await readFileData(file.path);
+  const blob = new Blob([new Uint8Array(result.data)]);
+  const fileObj = new File([blob], file.name, { type: "audio/mpeg" });
+
+  const metadata = await extractMetadata(fileObj);
+  return { ...metadata, fileSize: result.data.length };
+}
+
 // In-memory file cache for current session playback
 const fileCache = new Map<string, File>();
 
@@ -63,25 +148,55 @@ export function clearCachedFile(songId: string): void {
   fileCache.delete(songId);
 }
 
+// Web: whether the song lives in a remembered folder we have access to
+function isInGrantedFolder(song: Song): boolean {
+  return !!song.relativePath && isFolderGranted(song.folderHandleId);
+}
+
+// Get a song's file for playback: the session cache first, then the
+// remembered folder it was linked to
+export async function resolveSongFile(song: Song): Promise<File | undefined> {
+  const cached = fileCache.get(song.id);
+  if (cached) return cached;
+
+  if (song.folderHandleId && song.relativePath) {
+    const file = await resolveFolderFile(song.folderHandleId, song.relativePath);
+    if (file) {
+      fileCache.set(song.id, file);
+      return file;
+    }
+  }
+  return undefined;
+}
+
+// Desktop: file paths the last library health check couldn't find
+const missingFilePaths = new Set<string>();
+
+// Whether the last health check found this song's file gone
+export function isSongFileMissing(song: Song): boolean {
+  return !!song.filePath && missingFilePaths.has(song.filePath);
+}
+
 // Check if a song is available for playback
-// On desktop: always available if filePath exists (we'll verify on play)
-// On web: available if in memory cache
+// On desktop: available if it has a filePath the health check didn't flag
+// On web: available if in memory cache or in a remembered folder
 export function isSongAvailable(song: Song): boolean {
   if (isDesktop() && song.filePath) {
-    // On desktop, songs with file paths are considered available
-    // Actual file existence is verified when playing
-    return true;
+    return !missingFilePaths.has(song.filePath);
   }
-  return fileCache.has(song.id);
+  return fileCache.has(song.id) || isInGrantedFolder(song);
 }
 
 // Check availability of multiple songs, returns Set of unavailable song IDs
 export function checkSongsAvailability(songs: Song[]): Set<string> {
-  // On desktop, all songs with filePaths are considered available
   if (isDesktop()) {
     return new Set(
       songs
-        .filter((song) => !song.filePath && !fileCache.has(song.id))
+        .filter((song) =>
+          song.filePath
+            ? missingFilePaths.has(song.filePath)
+            : !fileCache.has(song.id),
+        )
         .map((s) => s.id),
     );
   }
@@ -138,8 +253,27 @@ export function useSongs() {
     skipped?: number;
   } | null>(null);
 
+  const [loudnessProgress, setLoudnessProgress] = useState<{
+    current: number;
+    total: number;
+  } | null>(null);
+
+  // Desktop: result of the last bulk check that every filePath still exists
+  const [libraryHealth, setLibraryHealth] = useState<{
+    checkedAt: number;
+    missing: number;
+  } | null>(null);
+
   // Use ref to track songs during import to avoid stale state
   const songsRef = useRef<Song[]>([]);
+
+  // Loudness analysis: one run at a time, each song tried once per session
+  const isAnalyzingRef = useRef(false);
+  const loudnessAttemptedRef = useRef<Set<string>>(new Set());
+
+  // Metadata refresh for songs imported before newer tag fields existed
+  const isRefreshingMetadataRef = useRef(false);
+  const metadataAttemptedRef = useRef<Set<string>>(new Set());
   
   // Lookup map for O(1) duplicate checking
   const songLookupMapRef = useRef<Map<string, Song>>(new Map());
@@ -162,6 +296,8 @@ export function useSongs() {
       setSongs(sortedSongs);
       songsRef.current = sortedSongs;
       songLookupMapRef.current = buildSongLookupMap(sortedSongs);
+      // Desktop: after an upgrade, keep songs from earlier imports readable
+      if (isDesktop()) migrateSongFolders(getSongFolders(sortedSongs));
     } catch (error) {
       console.error("Failed to load songs:", error);
     } finally {
@@ -185,9 +321,9 @@ export function useSongs() {
   const importFile = useCallback(
     async (
       file: File,
-      options: { skipDuplicateCheck?: boolean } = {},
+      options: { skipDuplicateCheck?: boolean; folderCover?: File } = {},
     ): Promise<{ song: Song | null; skipped: boolean }> => {
-      const { skipDuplicateCheck = false } = options;
+      const { skipDuplicateCheck = false, folderCover } = options;
 
       if (!isAudioFile(file)) {
         console.warn("Not an audio file:", file.name);
@@ -207,13 +343,21 @@ export function useSongs() {
           }
         }
 
+        // Fall back to a cover image picked alongside the file
+        const artworkId =
+          metadata.artworkId ??
+          (folderCover && (await storeArtworkFile(folderCover)));
+
         const song: Song = {
           id: generateId(),
           title: metadata.title || file.name.replace(/\.[^/.]+$/, ""),
           artist: metadata.artist || "Unknown Artist",
           album: metadata.album || "Unknown Album",
+          ...pickTagFields(metadata),
           duration: metadata.duration || 0,
-          coverArt: metadata.coverArt,
+          artworkId,
+          replayGain: metadata.replayGain,
+          metadataVersion: SONG_METADATA_VERSION,
           sourceType: "local",
           addedAt: Date.now(),
           // Store filename and size for reconnecting later
@@ -249,6 +393,7 @@ export function useSongs() {
     ): Promise<{ songs: Song[]; imported: number; skipped: number }> => {
       const fileArray = Array.from(files);
       const audioFiles = fileArray.filter(isAudioFile);
+      const folderCovers = findFolderCovers(fileArray);
 
       if (audioFiles.length === 0) {
         return { songs: [], imported: 0, skipped: 0 };
@@ -265,7 +410,11 @@ export function useSongs() {
         
         // Process batch concurrently
         const results = await Promise.all(
-          batch.map(file => importFile(file))
+          batch.map((file) =>
+            importFile(file, {
+              folderCover: folderCovers.get(getRelativeFolder(file)),
+            }),
+          ),
         );
         
         // Collect results
@@ -299,6 +448,8 @@ export function useSongs() {
   // Delete a song
   const deleteSong = useCallback(async (songId: string) => {
     try {
+      const filePath = songsRef.current.find((s) => s.id === songId)?.filePath;
+
       // Clear from memory cache
       clearCachedFile(songId);
 
@@ -306,6 +457,9 @@ export function useSongs() {
       await dbDeleteSong(songId);
 
       setSongs((prev) => prev.filter((s) => s.id !== songId));
+
+      // Otherwise the next scan counts the file as unchanged and never re-imports it
+      if (filePath) await forgetScannedFiles([filePath]);
     } catch (error) {
       console.error("Failed to delete song:", error);
     }
@@ -325,6 +479,257 @@ export function useSongs() {
     [],
   );
 
+  // Desktop: a watched file moved or was renamed. Keep the song (and its id,
+  // playlists and history) and point it at the new path
+  const moveSongFile = useCallback(
+    async (previousPath: string, file: MusicFileInfo): Promise<boolean> => {
+      const song = songsRef.current.find((s) => s.filePath === previousPath);
+      if (!song) return false;
+
+      const updatedSong = { ...song, filePath: file.path, fileName: file.name };
+      await dbUpdateSong(updatedSong);
+      setSongs((prev) => {
+        const newSongs = prev.map((s) => (s.id === song.id ? updatedSong : s));
+        songsRef.current = newSongs;
+        return newSongs;
+      });
+      return true;
+    },
+    [],
+  );
+
+  // Desktop: a watched file was rewritten in place (e.g. retagged). Re-read it
+  // into the existing song
+  const refreshSongFile = useCallback(
+    async (filePath: string): Promise<boolean> => {
+      const song = songsRef.current.find((s) => s.filePath === filePath);
+      if (!song) return false;
+
+      try {
+        const nativeMetadata = await readNativeMetadataMap([filePath]);
+        const metadata =
+          nativeMetadata.get(filePath) ??
+          (await readMetadataFromFileData({
+            path: filePath,
+            name: song.fileName ?? filePath,
+          }));
+
+        const latest = songsRef.current.find((s) => s.id === song.id) ?? song;
+        const updatedSong: Song = {
+          ...mergeTagFields(latest, metadata),
+          title: metadata.title || latest.title,
+          artist: metadata.artist || latest.artist,
+          album: metadata.album || latest.album,
+          duration: metadata.duration || latest.duration,
+          artworkId: metadata.artworkId ?? latest.artworkId,
+          replayGain: metadata.replayGain ?? latest.replayGain,
+          fileSize: metadata.fileSize ?? latest.fileSize,
+        };
+        await dbUpdateSong(updatedSong);
+        setSongs((prev) => {
+          const newSongs = prev.map((s) => (s.id === song.id ? updatedSong : s));
+          songsRef.current = newSongs;
+          return newSongs;
+        });
+        return true;
+      } catch (error) {
+        console.error("Failed to refresh changed file:", filePath, error);
+        return false;
+      }
+    },
+    [],
+  );
+
+  // Apply tag editor changes. Desktop writes them to the files and re-reads
+  // the result; songs without a file path (web) only change in the library.
+  // Songs keep their ids, so playlists and history stay attached
+  const editSongTags = useCallback(
+    async (
+      songIds: string[],
+      edit: TagEdit,
+    ): Promise<{ updated: number; failed: { song: Song; error: string }[] }> => {
+      const ids = new Set(songIds);
+      const targets = songsRef.current.filter((song) => ids.has(song.id));
+      const failed: { song: Song; error: string }[] = [];
+
+      const artworkId = edit.coverArt
+        ? await storeArtworkDataUrl(edit.coverArt)
+        : undefined;
+
+      const onDisk = isDesktop() ? targets.filter((song) => song.filePath) : [];
+      const written = new Set<string>();
+      if (onDisk.length > 0) {
+        const results = await writeTags(
+          onDisk.map((song) => ({ path: song.filePath!, edit })),
+        );
+        const resultByPath = new Map(
+          (results ?? []).map((result) => [result.path, result]),
+        );
+        for (const song of onDisk) {
+          const result = resultByPath.get(song.filePath!);
+          if (result && !result.error) {
+            written.add(song.filePath!);
+          } else {
+            failed.push({
+              song,
+              error:
+                result?.error ??
+                (results
+                  ? "The file wasn't written"
+                  : "Tag writing isn't available"),
+            });
+          }
+        }
+      }
+      const metadataByPath = await readNativeMetadataMap([...written]);
+
+      // Re-read so edits made while writing aren't overwritten
+      const updated = new Map<string, Song>();
+      for (const song of songsRef.current) {
+        if (!ids.has(song.id)) continue;
+        if (!song.filePath || !isDesktop()) {
+          updated.set(song.id, applyTagEdit(song, edit, artworkId));
+          continue;
+        }
+        if (!written.has(song.filePath)) continue;
+
+        const metadata = metadataByPath.get(song.filePath);
+        const reread: Song = metadata
+          ? {
+              ...mergeTagFields(song, metadata),
+              fileSize: metadata.fileSize ?? song.fileSize,
+            }
+          : song;
+        updated.set(
+          song.id,
+          applyTagEdit(reread, edit, metadata?.artworkId ?? artworkId),
+        );
+      }
+
+      if (updated.size > 0) {
+        await addSongs([...updated.values()]);
+        setSongs((prev) => {
+          const newSongs = prev.map((s) => updated.get(s.id) ?? s);
+          songsRef.current = newSongs;
+          return newSongs;
+        });
+      }
+      return { updated: updated.size, failed };
+    },
+    [],
+  );
+
+  // Re-read tags for songs stored with an older metadata schema. Desktop reads
+  // natively by path; on web from reconnected files or remembered folders
+  const refreshMetadata = useCallback(async () => {
+    if (isRefreshingMetadataRef.current) return;
+    isRefreshingMetadataRef.current = true;
+
+    try {
+      const attempted = metadataAttemptedRef.current;
+      // Loop so files reconnected mid-refresh are picked up too
+      for (;;) {
+        const pending = songsRef.current.filter(
+          (song) =>
+            needsMetadataRefresh(song) &&
+            !attempted.has(song.id) &&
+            ((isDesktop() && song.filePath) || isSongAvailable(song)),
+        );
+        if (pending.length === 0) break;
+
+        for (let i = 0; i < pending.length; i += NATIVE_METADATA_BATCH_SIZE) {
+          const batch = pending.slice(i, i + NATIVE_METADATA_BATCH_SIZE);
+          batch.forEach((song) => attempted.add(song.id));
+
+          const metadataById = new Map<string, Partial<Song>>();
+          const nativePaths = batch
+            .filter((song) => isDesktop() && song.filePath)
+            .map((song) => song.filePath!);
+          const nativeResults = await readNativeMetadata(nativePaths, {
+            includeCoverArt: false,
+          });
+          const nativeByPath = new Map<string, Partial<Song>>();
+          for (const result of nativeResults ?? []) {
+            if (result.metadata) nativeByPath.set(result.path, result.metadata);
+          }
+
+          for (const song of batch) {
+            const native = song.filePath && nativeByPath.get(song.filePath);
+            if (native) {
+              metadataById.set(song.id, native);
+              continue;
+            }
+            const file = await resolveSongFile(song);
+            if (file) {
+              metadataById.set(song.id, await extractMetadata(file));
+            }
+          }
+          if (metadataById.size === 0) continue;
+
+          // Re-read so edits made while reading aren't overwritten
+          const updated = new Map<string, Song>();
+          for (const song of songsRef.current) {
+            const metadata = metadataById.get(song.id);
+            if (metadata) updated.set(song.id, mergeTagFields(song, metadata));
+          }
+          await addSongs([...updated.values()]);
+          setSongs((prev) => {
+            const newSongs = prev.map((s) => updated.get(s.id) ?? s);
+            songsRef.current = newSongs;
+            return newSongs;
+          });
+        }
+      }
+    } catch (error) {
+      console.error("Metadata refresh failed:", error);
+    } finally {
+      isRefreshingMetadataRef.current = false;
+    }
+  }, []);
+
+  useEffect(() => {
+    if (!isLoading) refreshMetadata();
+  }, [isLoading, refreshMetadata]);
+
+  // Move cover art kept inline on older songs into the artwork store, then
+  // drop stored artwork no song uses any more
+  const migrateArtwork = useCallback(async () => {
+    try {
+      const legacy = songsRef.current.filter(hasLegacyArtwork);
+      for (let i = 0; i < legacy.length; i += NATIVE_METADATA_BATCH_SIZE) {
+        const batch = legacy.slice(i, i + NATIVE_METADATA_BATCH_SIZE);
+        const artworkIds = await storeLegacyArtwork(batch);
+
+        // Re-read so edits made while storing aren't overwritten
+        const updated = new Map<string, Song>();
+        for (const song of songsRef.current) {
+          if (artworkIds.has(song.id)) {
+            updated.set(
+              song.id,
+              replaceLegacyArtwork(song, artworkIds.get(song.id)),
+            );
+          }
+        }
+        await addSongs([...updated.values()]);
+        setSongs((prev) => {
+          const newSongs = prev.map((s) => updated.get(s.id) ?? s);
+          songsRef.current = newSongs;
+          return newSongs;
+        });
+      }
+      // An empty list may just mean the library failed to load
+      if (songsRef.current.length > 0) {
+        await pruneUnusedArtwork(songsRef.current);
+      }
+    } catch (error) {
+      console.error("Artwork migration failed:", error);
+    }
+  }, []);
+
+  useEffect(() => {
+    if (!isLoading) migrateArtwork();
+  }, [isLoading, migrateArtwork]);
+
   // Connect a folder of files to existing songs in the library
   const connectFolder = useCallback(
     (files: FileList | File[]): { connected: number; newFiles: File[] } => {
@@ -340,9 +745,12 @@ export function useSongs() {
       const matchedFiles = new Set(songIdToFile.values());
       const newFiles = fileArray.filter((f) => !matchedFiles.has(f));
 
+      // Reconnected files can now fill in tags missing from older imports
+      if (connected > 0) refreshMetadata();
+
       return { connected, newFiles };
     },
-    [],
+    [refreshMetadata],
   );
 
   // Desktop: Import from a folder path (scans folder and imports all audio files)
@@ -374,9 +782,42 @@ export function useSongs() {
       const folderSongs = new Map<string, string[]>();
       const rootFolderName = folderPath.split(/[/\\]/).pop() || "Music";
 
+      let nativeMetadata = new Map<string, Partial<Song>>();
+      // New songs are written a batch at a time; the lookup map is updated as
+      // they're created so duplicates within the import are still caught
+      let pending: Song[] = [];
+      const flushPending = async () => {
+        if (pending.length === 0) return;
+        const batch = pending;
+        pending = [];
+        try {
+          await addSongs(batch);
+        } catch (error) {
+          console.error("Failed to import batch:", error);
+          imported -= batch.length;
+          return;
+        }
+        setSongs((prev) => {
+          const newSongs = [...batch.reverse(), ...prev];
+          songsRef.current = newSongs;
+          return newSongs;
+        });
+      };
+
       for (let i = 0; i < files.length; i++) {
         const file = files[i];
 
+        // Read tags natively a batch at a time, skipping files already in the library
+        if (i % NATIVE_METADATA_BATCH_SIZE === 0) {
+          await flushPending();
+          nativeMetadata = await readNativeMetadataMap(
+            files
+              .slice(i, i + NATIVE_METADATA_BATCH_SIZE)
+              .filter((f) => !checkDuplicateByPath(f.path))
+              .map((f) => f.path),
+          );
+        }
+
         // Check if already exists by path - O(1) lookup
         const existingByPath = checkDuplicateByPath(file.path);
         if (existingByPath) {
@@ -391,12 +832,10 @@ export function useSongs() {
         }
 
         try {
-          // Read the file to extract metadata
-          const result = await readFileData(file.path);
-          const blob = new Blob([new Uint8Array(result.data)]);
-          const fileObj = new File([blob], file.name, { type: "audio/mpeg" });
-
-          const metadata = await extractMetadata(fileObj);
+          // Use native metadata when available, otherwise parse the file here
+          const metadata =
+            nativeMetadata.get(file.path) ??
+            (await readMetadataFromFileData(file));
 
           // Check for duplicates by title/artist/duration - O(1) lookup
           const duplicate = checkDuplicate({
@@ -429,21 +868,20 @@ export function useSongs() {
             title: metadata.title || file.name.replace(/\.[^/.]+$/, ""),
             artist: metadata.artist || "Unknown Artist",
             album: metadata.album || "Unknown Album",
+            ...pickTagFields(metadata),
             duration: metadata.duration || 0,
-            coverArt: metadata.coverArt,
+            artworkId: metadata.artworkId,
+            replayGain: metadata.replayGain,
+            metadataVersion: SONG_METADATA_VERSION,
             sourceType: "local",
             addedAt: Date.now(),
             fileName: file.name,
-            fileSize: result.data.length,
+            fileSize: metadata.fileSize,
             filePath: file.path, // Store full path for desktop
           };
 
-          await addSong(song);
-          setSongs((prev) => {
-            const newSongs = [song, ...prev];
-            songsRef.current = newSongs;
-            return newSongs;
-          });
+          pending.push(song);
+          addToSongLookupMap(songLookupMapRef.current, song);
 
           // Track folder for new song (use root folder name if no subfolder)
           const folderKey = file.folder || rootFolderName;
@@ -459,6 +897,8 @@ export function useSongs() {
         setImportProgress({ current: i + 1, total: files.length, skipped });
       }
 
+      await flushPending();
+
       // Save the folder path for future auto-loading
       await saveStoredFolderPath(folderPath);
       setImportProgress(null);
@@ -548,9 +988,42 @@ export function useSongs() {
       let imported = 0;
       let skipped = 0;
 
+      let nativeMetadata = new Map<string, Partial<Song>>();
+      // New songs are written a batch at a time; the lookup map is updated as
+      // they're created so duplicates within the import are still caught
+      let pending: Song[] = [];
+      const flushPending = async () => {
+        if (pending.length === 0) return;
+        const batch = pending;
+        pending = [];
+        try {
+          await addSongs(batch);
+        } catch (error) {
+          console.error("Failed to import batch:", error);
+          imported -= batch.length;
+          return;
+        }
+        setSongs((prev) => {
+          const newSongs = [...batch.reverse(), ...prev];
+          songsRef.current = newSongs;
+          return newSongs;
+        });
+      };
+
       for (let i = 0; i < files.length; i++) {
         const file = files[i];
 
+        // Read tags natively a batch at a time, skipping files already in the library
+        if (i % NATIVE_METADATA_BATCH_SIZE === 0) {
+          await flushPending();
+          nativeMetadata = await readNativeMetadataMap(
+            files
+              .slice(i, i + NATIVE_METADATA_BATCH_SIZE)
+              .filter((f) => !checkDuplicateByPath(f.path))
+              .map((f) => f.path),
+          );
+        }
+
         // Check if already exists by path - O(1) lookup
         const existingByPath = checkDuplicateByPath(file.path);
         if (existingByPath) {
@@ -560,12 +1033,10 @@ export function useSongs() {
         }
 
         try {
-          // Read the file to extract metadata
-          const result = await readFileData(file.path);
-          const blob = new Blob([new Uint8Array(result.data)]);
-          const fileObj = new File([blob], file.name, { type: "audio/mpeg" });
-
-          const metadata = await extractMetadata(fileObj);
+          // Use native metadata when available, otherwise parse the file here
+          const metadata =
+            nativeMetadata.get(file.path) ??
+            (await readMetadataFromFileData(file));
 
           // Check for duplicates by title/artist/duration - O(1) lookup
           const duplicate = checkDuplicate({
@@ -593,21 +1064,20 @@ export function useSongs() {
             title: metadata.title || file.name.replace(/\.[^/.]+$/, ""),
             artist: metadata.artist || "Unknown Artist",
             album: metadata.album || "Unknown Album",
+            ...pickTagFields(metadata),
             duration: metadata.duration || 0,
-            coverArt: metadata.coverArt,
+            artworkId: metadata.artworkId,
+            replayGain: metadata.replayGain,
+            metadataVersion: SONG_METADATA_VERSION,
             sourceType: "local",
             addedAt: Date.now(),
             fileName: file.name,
-            fileSize: result.data.length,
+            fileSize: metadata.fileSize,
             filePath: file.path,
           };
 
-          await addSong(song);
-          setSongs((prev) => {
-            const newSongs = [song, ...prev];
-            songsRef.current = newSongs;
-            return newSongs;
-          });
+          pending.push(song);
+          addToSongLookupMap(songLookupMapRef.current, song);
 
           imported++;
         } catch (error) {
@@ -617,20 +1087,217 @@ export function useSongs() {
         setImportProgress({ current: i + 1, total: files.length, skipped });
       }
 
+      await flushPending();
       setImportProgress(null);
       return { imported, skipped };
     },
     [],
   );
 
+  // Desktop: measure loudness for songs without ReplayGain tags, and where the
+  // music starts and stops in every song for crossfades
+  // Keeps going until songs added meanwhile (e.g. by an import) are covered too
+  const analyzeLoudness = useCallback(async () => {
+    if (!isDesktop() || isAnalyzingRef.current) return;
+    isAnalyzingRef.current = true;
+
+    try {
+      for (;;) {
+        const attempted = loudnessAttemptedRef.current;
+        const pending = songsRef.current.filter(
+          (song) =>
+            song.filePath &&
+            (!song.replayGain || !song.audibleRange) &&
+            !attempted.has(song.id),
+        );
+        if (pending.length === 0) break;
+
+        const albums = groupSongsForAnalysis(pending);
+        let current = 0;
+        setLoudnessProgress({ current, total: pending.length });
+
+        for (let i = 0; i < albums.length; i += LOUDNESS_ALBUMS_PER_REQUEST) {
+          const batch = albums.slice(i, i + LOUDNESS_ALBUMS_PER_REQUEST);
+          const batchSongs = batch.flat();
+          batchSongs.forEach((song) => attempted.add(song.id));
+
+          const results = await analyzeNativeLoudness(
+            batch.map((album) => album.map((song) => song.filePath!)),
+          );
+          if (!results) return; // Analyzer unavailable
+
+          const resultsByPath = new Map(
+            results.map((result) => [result.path, result]),
+          );
+
+          const updated = new Map<string, Song>();
+          for (const song of batchSongs) {
+            const result = resultsByPath.get(song.filePath!);
+            if (!result?.replayGain && !result?.audibleRange) continue;
+            // Re-read so edits made during analysis aren't overwritten
+            const latest =
+              songsRef.current.find((s) => s.id === song.id) ?? song;
+            const updatedSong = {
+              ...latest,
+              replayGain: latest.replayGain ?? result.replayGain,
+              audibleRange: result.audibleRange ?? latest.audibleRange,
+            };
+            await dbUpdateSong(updatedSong);
+            updated.set(song.id, updatedSong);
+          }
+
+          if (updated.size > 0) {
+            setSongs((prev) => {
+              const newSongs = prev.map((s) => updated.get(s.id) ?? s);
+              songsRef.current = newSongs;
+              return newSongs;
+            });
+          }
+
+          current += batchSongs.length;
+          setLoudnessProgress({ current, total: pending.length });
+        }
+      }
+    } catch (error) {
+      console.error("Loudness analysis failed:", error);
+    } finally {
+      isAnalyzingRef.current = false;
+      setLoudnessProgress(null);
+    }
+  }, []);
+
+  // Desktop: stat every song's file in one call and flag the ones that are gone
+  // (deleted, or on a drive that isn't mounted). Returns the missing count
+  const checkLibraryHealth = useCallback(async (): Promise<number | null> => {
+    const paths = songsRef.current
+      .filter((song) => song.filePath)
+      .map((song) => song.filePath!);
+    const stats = await getFilesStats(paths);
+    if (!stats) return null;
+
+    missingFilePaths.clear();
+    stats.forEach((stat, i) => {
+      if (!stat?.isFile) missingFilePaths.add(paths[i]);
+    });
+    setLibraryHealth({ checkedAt: Date.now(), missing: missingFilePaths.size });
+    return missingFilePaths.size;
+  }, []);
+
+  useEffect(() => {
+    if (!isLoading && isDesktop()) {
+      checkLibraryHealth();
+    }
+  }, [isLoading, checkLibraryHealth]);
+
+  // Desktop: find missing songs' files under folderPath by file name, size and
+  // duration, and point the songs at them. Ids (and so playlists, favorites and
+  // history) are kept
+  const relinkMissingSongs = useCallback(
+    async (
+      folderPath: string,
+    ): Promise<{ relinked: number; unmatched: number }> => {
+      const missing = songsRef.current.filter(isSongFileMissing);
+      if (missing.length === 0) return { relinked: 0, unmatched: 0 };
+
+      // Files that already belong to a song aren't candidates
+      const files = (await scanMusicFolder(folderPath)).filter(
+        (file) => !checkDuplicateByPath(file.path),
+      );
+      const stats = await getFilesStats(files.map((file) => file.path));
+      const candidates = findRelinkCandidates(
+        missing,
+        files.map((file, i) => ({
+          path: file.path,
+          name: file.name,
+          size: stats?.[i]?.size,
+        })),
+      );
+
+      // Durations settle renamed or re-encoded files
+      const durations = new Map<string, number>();
+      const needed = pathsNeedingDuration(candidates);
+      for (let i = 0; i < needed.length; i += NATIVE_METADATA_BATCH_SIZE) {
+        const results = await readNativeMetadata(
+          needed.slice(i, i + NATIVE_METADATA_BATCH_SIZE),
+          { includeCoverArt: false },
+        );
+        for (const result of results ?? []) {
+          if (result.metadata?.duration) {
+            durations.set(result.path, result.metadata.duration);
+          }
+        }
+      }
+
+      const chosen = chooseRelinks(missing, candidates, durations);
+      const updated = new Map<string, Song>();
+      for (const song of missing) {
+        const file = chosen.get(song.id);
+        if (!file) continue;
+        missingFilePaths.delete(song.filePath!);
+        updated.set(song.id, { ...song, filePath: file.path, fileName: file.name });
+      }
+
+      if (updated.size > 0) {
+        await addSongs([...updated.values()]);
+        setSongs((prev) => {
+          const newSongs = prev.map((s) => updated.get(s.id) ?? s);
+          songsRef.current = newSongs;
+          return newSongs;
+        });
+        setLibraryHealth({
+          checkedAt: Date.now(),
+          missing: missingFilePaths.size,
+        });
+      }
+
+      return { relinked: updated.size, unmatched: missing.length - updated.size };
+    },
+    [checkDuplicateByPath],
+  );
+
+  // Log a listen and refresh the song's play and skip counts
+  const recordPlay = useCallback(async (play: PlayRecord) => {
+    try {
+      const updated = await dbRecordPlay(play);
+      if (!updated) return;
+      setSongs((prev) => {
+        const newSongs = prev.map((s) => (s.id === updated.id ? updated : s));
+        songsRef.current = newSongs;
+        return newSongs;
+      });
+    } catch (error) {
+      console.error("Failed to record play:", error);
+    }
+  }, []);
+
+  // Web: store where songs were found in remembered folders
+  const linkSongsToFolders = useCallback(
+    async (links: Map<string, FolderLink>) => {
+      const updated = new Map<string, Song>();
+      for (const song of songsRef.current) {
+        const link = links.get(song.id);
+        if (link) updated.set(song.id, { ...song, ...link });
+      }
+      if (updated.size === 0) return;
+
+      await addSongs([...updated.values()]);
+      setSongs((prev) => {
+        const newSongs = prev.map((s) => updated.get(s.id) ?? s);
+        songsRef.current = newSongs;
+        return newSongs;
+      });
+    },
+    [],
+  );
+
   // Get count of connected (playable) songs
-  // On desktop: songs with filePath are always connected
-  // On web: songs in memory cache are connected
+  // On desktop: songs whose file wasn't found missing are connected
+  // On web: songs in memory cache or a remembered folder are connected
   const connectedCount = songs.filter((s) => {
     if (isDesktop() && s.filePath) {
-      return true;
+      return !missingFilePaths.has(s.filePath);
     }
-    return fileCache.has(s.id);
+    return fileCache.has(s.id) || isInGrantedFolder(s);
   }).length;
 
   return {
@@ -641,6 +1308,9 @@ export function useSongs() {
     importFiles,
     deleteSong,
     updateSong,
+    moveSongFile,
+    refreshSongFile,
+    editSongTags,
     connectFolder,
     connectedCount,
     totalCount: songs.length,
@@ -651,5 +1321,13 @@ export function useSongs() {
     importFromMusicFileInfos,
     pickAndImportFolder,
     autoLoadStoredFolder,
+    analyzeLoudness,
+    loudnessProgress,
+    libraryHealth,
+    checkLibraryHealth,
+    relinkMissingSongs,
+    recordPlay,
+    // Web-specific exports
+    linkSongsToFolders,
   };
 }
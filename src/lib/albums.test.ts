@@ -0,0 +1,101 @@
+import { describe, it, expect } from 'vitest';
+import { compareTracks, findArtist, getAlbumId, groupAlbums, groupArtists } from './albums';
+import { createMockSong } from '../test/test-utils';
+
+describe('albums', () => {
+  describe('getAlbumId', () => {
+    it('is stable and ignores case', () => {
+      const a = createMockSong({ album: 'OK Computer', artist: 'Radiohead' });
+      const b = createMockSong({ album: 'ok computer', artist: 'RADIOHEAD' });
+
+      expect(getAlbumId(a)).toBe(getAlbumId(b));
+    });
+
+    it('keys by album artist rather than track artist', () => {
+      const a = createMockSong({ album: 'Hits', artist: 'A', albumArtist: 'Various Artists' });
+      const b = createMockSong({ album: 'Hits', artist: 'B', albumArtist: 'Various Artists' });
+      const c = createMockSong({ album: 'Hits', artist: 'B' });
+
+      expect(getAlbumId(a)).toBe(getAlbumId(b));
+      expect(getAlbumId(a)).not.toBe(getAlbumId(c));
+    });
+  });
+
+  describe('compareTracks', () => {
+    it('orders by disc, then track, with untagged tracks last', () => {
+      const songs = [
+        createMockSong({ id: 'd2t1', discNumber: 2, trackNumber: 1 }),
+        createMockSong({ id: 'untagged', title: 'Bonus' }),
+        createMockSong({ id: 'd1t2', discNumber: 1, trackNumber: 2 }),
+        createMockSong({ id: 't1', trackNumber: 1 }),
+      ];
+
+      expect(songs.sort(compareTracks).map((song) => song.id)).toEqual([
+        't1',
+        'd1t2',
+        'untagged',
+        'd2t1',
+      ]);
+    });
+  });
+
+  describe('groupAlbums', () => {
+    it('groups songs into sorted albums with cover, year and duration', () => {
+      const songs = [
+        createMockSong({
+          id: '2',
+          album: 'Kid A',
+          artist: 'Radiohead',
+          trackNumber: 2,
+          duration: 100,
+          codec: 'FLAC',
+        }),
+        createMockSong({ id: 'x', album: 'Abbey Road', artist: 'The Beatles', trackNumber: 1 }),
+        createMockSong({
+          id: '1',
+          album: 'Kid A',
+          artist: 'Radiohead',
+          trackNumber: 1,
+          duration: 50,
+          year: 2000,
+          genre: 'Electronic',
+          codec: 'FLAC',
+          artworkId: 'cover',
+        }),
+      ];
+
+      const albums = groupAlbums(songs);
+
+      expect(albums.map((album) => album.title)).toEqual(['Kid A', 'Abbey Road']);
+      expect(albums[0]).toMatchObject({
+        artist: 'Radiohead',
+        year: 2000,
+        genre: 'Electronic',
+        codec: 'FLAC',
+        artworkId: 'cover',
+        duration: 150,
+      });
+      expect(albums[0].songs.map((song) => song.id)).toEqual(['1', '2']);
+      expect(albums[1].codec).toBeUndefined();
+    });
+  });
+
+  describe('groupArtists', () => {
+    it('groups albums by album artist and tracks compilation appearances', () => {
+      const albums = groupAlbums([
+        createMockSong({ album: 'Old', artist: 'Björk', year: 1993 }),
+        createMockSong({ album: 'New', artist: 'Björk', year: 2001 }),
+        createMockSong({ album: 'Mix', artist: 'Björk', albumArtist: 'Various Artists' }),
+        createMockSong({ album: 'Mix', artist: 'Other', albumArtist: 'Various Artists' }),
+      ]);
+
+      const artists = groupArtists(albums);
+
+      expect(artists.map((artist) => artist.name)).toEqual(['Björk', 'Various Artists']);
+      const bjork = findArtist(artists, 'björk');
+      expect(bjork?.albums.map((album) => album.title)).toEqual(['New', 'Old']);
+      expect(bjork?.appearsOn.map((album) => album.title)).toEqual(['Mix']);
+      expect(bjork?.songCount).toBe(2);
+    });
+  });
+});
@@ -0,0 +1,163 @@
+import type { Song } from "../types";
+
+export interface Album {
+  id: string;
+  title: string;
+  // Album artist, falling back to the track artist
+  artist: string;
+  year?: number;
+  genre?: string;
+  // Shared codec, e.g. "FLAC"; undefined when tracks differ or are unknown
+  codec?: string;
+  artworkId?: string;
+  // Ordered by disc, then track number
+  songs: Song[];
+  duration: number;
+}
+
+export interface Artist {
+  name: string;
+  // Albums credited to this album artist, newest first
+  albums: Album[];
+  // Albums by other album artists with tracks by this artist (compilations)
+  appearsOn: Album[];
+  songCount: number;
+  artworkId?: string;
+}
+
+export function getAlbumArtist(song: Song): string {
+  return song.albumArtist?.trim() || song.artist;
+}
+
+function groupKey(text: string): string {
+  return text.trim().toLowerCase();
+}
+
+// Short stable id for album URLs (FNV-1a of album artist + title)
+export function getAlbumId(song: Song): string {
+  const key = `${groupKey(getAlbumArtist(song))}\u0000${groupKey(song.album)}`;
+  let hash = 0x811c9dc5;
+  for (let i = 0; i < key.length; i++) {
+    hash ^= key.charCodeAt(i);
+    hash = Math.imul(hash, 0x01000193);
+  }
+  return (hash >>> 0).toString(36);
+}
+
+// Disc, then track number; untagged tracks go last, by title
+export function compareTracks(a: Song, b: Song): number {
+  return (
+    (a.discNumber ?? 1) - (b.discNumber ?? 1) ||
+    (a.trackNumber ?? Infinity) - (b.trackNumber ?? Infinity) ||
+    a.title.localeCompare(b.title)
+  );
+}
+
+export function compareNames(a: string, b: string): number {
+  return a.localeCompare(b, undefined, { sensitivity: "base", numeric: true });
+}
+
+function getSharedCodec(songs: Song[]): string | undefined {
+  const codec = songs[0].codec;
+  return songs.every((song) => song.codec === codec) ? codec : undefined;
+}
+
+/**
+ * Group songs into albums by album artist and title
+ * Albums are sorted by artist, then title.
+ */
+export function groupAlbums(songs: Song[]): Album[] {
+  const groups = new Map<string, Song[]>();
+  for (const song of songs) {
+    const id = getAlbumId(song);
+    const group = groups.get(id);
+    if (group) {
+      group.push(song);
+    } else {
+      groups.set(id, [song]);
+    }
+  }
+
+  const albums: Album[] = [];
+  for (const [id, albumSongs] of groups) {
+    albumSongs.sort(compareTracks);
+    const first = albumSongs[0];
+    albums.push({
+      id,
+      title: first.album,
+      artist: getAlbumArtist(first),
+      year: albumSongs.find((song) => song.year)?.year,
+      genre: albumSongs.find((song) => song.genre)?.genre,
+      codec: getSharedCodec(albumSongs),
+      artworkId: albumSongs.find((song) => song.artworkId)?.artworkId,
+      songs: albumSongs,
+      duration: albumSongs.reduce((total, song) => total + song.duration, 0),
+    });
+  }
+
+  return albums.sort(
+    (a, b) => compareNames(a.artist, b.artist) || compareNames(a.title, b.title),
+  );
+}
+
+// Newest first, undated albums last
+function compareByYear(a: Album, b: Album): number {
+  return (b.year ?? -Infinity) - (a.year ?? -Infinity) || compareNames(a.title, b.title);
+}
+
+/**
+ * Group albums by album artist, sorted by name
+ */
+export function groupArtists(albums: Album[]): Artist[] {
+  const artists = new Map<string, Artist>();
+  const getArtist = (name: string) => {
+    const key = groupKey(name);
+    let artist = artists.get(key);
+    if (!artist) {
+      artist = { name, albums: [], appearsOn: [], songCount: 0 };
+      artists.set(key, artist);
+    }
+    return artist;
+  };
+
+  for (const album of albums) {
+    const artist = getArtist(album.artist);
+    artist.albums.push(album);
+    artist.songCount += album.songs.length;
+    artist.artworkId ??= album.artworkId;
+  }
+
+  // Compilations: credit track artists that aren't the album artist
+  for (const album of albums) {
+    const albumArtist = groupKey(album.artist);
+    const guests = new Set(
+      album.songs
+        .map((song) => song.artist)
+        .filter((name) => groupKey(name) !== albumArtist),
+    );
+    for (const name of guests) {
+      const artist = artists.get(groupKey(name));
+      if (artist) artist.appearsOn.push(album);
+    }
+  }
+
+  for (const artist of artists.values()) {
+    artist.albums.sort(compareByYear);
+    artist.appearsOn.sort(compareByYear);
+  }
+
+  return [...artists.values()].sort((a, b) => compareNames(a.name, b.name));
+}
+
+export function findArtist(artists: Artist[], name: string): Artist | undefined {
+  const key = groupKey(name);
+  return artists.find((artist) => groupKey(artist.name) === key);
+}
+
+export function getArtistPath(name: string): string {
+  return `/artists/${encodeURIComponent(name)}`;
+}
+
+export function getAlbumPath(album: Album): string {
+  return `/albums/${album.id}`;
+}
@@ -0,0 +1,130 @@
+import { describe, it, expect } from 'vitest';
+import {
+  artworkFromBackup,
+  artworkToBackup,
+  findFolderCovers,
+  getFolderCoverRank,
+  getScaledSize,
+  hasLegacyArtwork,
+  parseDataUrl,
+  replaceLegacyArtwork,
+} from './artwork';
+import { createMockSong } from '../test/test-utils';
+
+function pickedFile(path: string): File {
+  const file = new File(['x'], path.slice(path.lastIndexOf('/') + 1));
+  Object.defineProperty(file, 'webkitRelativePath', { value: path });
+  return file;
+}
+
+describe('artwork', () => {
+  describe('getScaledSize', () => {
+    it('scales the longest side down to the limit', () => {
+      expect(getScaledSize(2000, 1000, 256)).toEqual({ width: 256, height: 128 });
+      expect(getScaledSize(600, 1200, 300)).toEqual({ width: 150, height: 300 });
+    });
+
+    it('never scales up', () => {
+      expect(getScaledSize(100, 80, 256)).toEqual({ width: 100, height: 80 });
+    });
+
+    it('keeps at least one pixel per side', () => {
+      expect(getScaledSize(10000, 1, 256)).toEqual({ width: 256, height: 1 });
+    });
+  });
+
+  describe('parseDataUrl', () => {
+    it('decodes base64 data URLs', () => {
+      const parsed = parseDataUrl('data:image/png;base64,AQID');
+      expect(parsed?.mimeType).toBe('image/png');
+      expect(Array.from(parsed!.data)).toEqual([1, 2, 3]);
+    });
+
+    it('decodes percent-encoded data URLs', () => {
+      const parsed = parseDataUrl('data:image/svg+xml,%3Csvg%3E');
+      expect(parsed?.mimeType).toBe('image/svg+xml');
+      expect(new TextDecoder().decode(parsed!.data)).toBe('<svg>');
+    });
+
+    it('defaults the type to JPEG', () => {
+      expect(parseDataUrl('data:;base64,AQID')?.mimeType).toBe('image/jpeg');
+    });
+
+    it('rejects anything else', () => {
+      expect(parseDataUrl('https://example.com/cover.jpg')).toBeUndefined();
+      expect(parseDataUrl('data:image/png;base64,***')).toBeUndefined();
+    });
+  });
+
+  describe('folder covers', () => {
+    it('ranks cover, folder and front images', () => {
+      expect(getFolderCoverRank('cover.jpg')).toBe(0);
+      expect(getFolderCoverRank('Folder.PNG')).toBe(1);
+      expect(getFolderCoverRank('front.jpeg')).toBe(2);
+      expect(getFolderCoverRank('back.jpg')).toBe(-1);
+      expect(getFolderCoverRank('cover.gif')).toBe(-1);
+      expect(getFolderCoverRank('.jpg')).toBe(-1);
+    });
+
+    it('picks the best image in each folder', () => {
+      const front = pickedFile('Music/Album A/front.jpg');
+      const cover = pickedFile('Music/Album A/cover.png');
+      const folder = pickedFile('Music/Album B/folder.jpg');
+      const covers = findFolderCovers([
+        front,
+        pickedFile('Music/Album A/01.mp3'),
+        cover,
+        folder,
+        pickedFile('Music/Album C/back.jpg'),
+      ]);
+
+      expect(covers.get('Music/Album A')).toBe(cover);
+      expect(covers.get('Music/Album B')).toBe(folder);
+      expect(covers.has('Music/Album C')).toBe(false);
+    });
+  });
+
+  describe('legacy artwork', () => {
+    it('detects songs with inline cover art', () => {
+      const legacy = { ...createMockSong(), coverArt: 'data:image/png;base64,AQID' };
+      expect(hasLegacyArtwork(legacy)).toBe(true);
+      expect(hasLegacyArtwork(createMockSong())).toBe(false);
+    });
+
+    it('replaces inline art with the stored id', () => {
+      const legacy = { ...createMockSong(), coverArt: 'data:image/png;base64,AQID' };
+      const updated = replaceLegacyArtwork(legacy, 'abc');
+      expect(updated.artworkId).toBe('abc');
+      expect('coverArt' in updated).toBe(false);
+      expect(updated.title).toBe(legacy.title);
+    });
+
+    it('drops inline art that could not be stored', () => {
+      const legacy = { ...createMockSong(), coverArt: 'data:,' };
+      const updated = replaceLegacyArtwork(legacy, undefined);
+      expect(hasLegacyArtwork(updated)).toBe(false);
+      expect(updated.artworkId).toBeUndefined();
+    });
+  });
+
+  describe('backups', () => {
+    it('round-trips stored artwork through data URLs', async () => {
+      const record = {
+        id: 'abc',
+        thumbnail: new Blob([new Uint8Array([1, 2])], { type: 'image/jpeg' }),
+        full: new Blob([new Uint8Array([3, 4, 5])], { type: 'image/png' }),
+        addedAt: 10,
+      };
+      const entry = await artworkToBackup(record);
+      expect(entry).toEqual({ id: 'abc', thumbnail: 'data:image/jpeg;base64,AQI=', full: 'data:image/png;base64,AwQF', addedAt: 10 });
+
+      const restored = artworkFromBackup(entry)!;
+      expect(restored.full.type).toBe('image/png');
+      expect(Array.from(new Uint8Array(await restored.full.arrayBuffer()))).toEqual([3, 4, 5]);
+    });
+
+    it('skips artwork whose images cannot be read', () => {
+      expect(artworkFromBackup({ id: 'abc', thumbnail: 'nope', full: 'data:image/png;base64,AQID', addedAt: 0 })).toBeUndefined();
+    });
+  });
+});
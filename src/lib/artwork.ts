@@ -0,0 +1,338 @@
+import type { ArtworkRecord, Song } from "../types";
+import type { BackupArtwork } from "./backup";
+import { getArtwork, hasArtwork, pruneArtwork, putArtwork } from "./db";
+
+export type ArtworkVariant = "thumbnail" | "full";
+
+// Longest side of each variant in pixels. Full-size images at or below the
+// limit are stored as they are
+export const ARTWORK_THUMBNAIL_SIZE = 256;
+export const ARTWORK_FULL_SIZE = 1200;
+const ARTWORK_JPEG_QUALITY = 0.85;
+
+// Images used for a folder's songs when they have no embedded art, best first
+const FOLDER_COVER_NAMES = ["cover", "folder", "front"];
+const FOLDER_COVER_EXTENSIONS = [".jpg", ".jpeg", ".png"];
+
+// Full-size object URLs kept at once; thumbnails stay for the session
+const FULL_URL_LIMIT = 8;
+
+// Artwork added before this was stored in an earlier session
+const SESSION_STARTED_AT = Date.now();
+
+export function getScaledSize(
+  width: number,
+  height: number,
+  maxSize: number,
+): { width: number; height: number } {
+  const scale = Math.min(1, maxSize / Math.max(width, height));
+  return {
+    width: Math.max(1, Math.round(width * scale)),
+    height: Math.max(1, Math.round(height * scale)),
+  };
+}
+
+// Bytes and type of a data URL; undefined for anything else
+export function parseDataUrl(
+  url: string,
+): { data: Uint8Array; mimeType: string } | undefined {
+  const match = /^data:([^;,]*)((?:;[^;,]*)*),/.exec(url);
+  if (!match) return undefined;
+
+  const mimeType = match[1] || "image/jpeg";
+  const payload = url.slice(match[0].length);
+  try {
+    if (!match[2].includes(";base64")) {
+      return {
+        data: new TextEncoder().encode(decodeURIComponent(payload)),
+        mimeType,
+      };
+    }
+    const binary = atob(payload);
+    const data = new Uint8Array(binary.length);
+    for (let i = 0; i < binary.length; i++) {
+      data[i] = binary.charCodeAt(i);
+    }
+    return { data, mimeType };
+  } catch {
+    return undefined;
+  }
+}
+
+async function blobToDataUrl(blob: Blob): Promise<string> {
+  const bytes = new Uint8Array(await blob.arrayBuffer());
+  let binary = "";
+  // Chunked, spreading a whole image overflows the call stack
+  for (let i = 0; i < bytes.length; i += 0x8000) {
+    binary += String.fromCharCode(...bytes.subarray(i, i + 0x8000));
+  }
+  return `data:${blob.type || "image/jpeg"};base64,${btoa(binary)}`;
+}
+
+function dataUrlToBlob(url: string): Blob | undefined {
+  const parsed = parseDataUrl(url);
+  return (
+    parsed &&
+    new Blob([new Uint8Array(parsed.data)], { type: parsed.mimeType })
+  );
+}
+
+// Stored artwork in the form it takes in a backup file
+export async function artworkToBackup(
+  record: ArtworkRecord,
+): Promise<BackupArtwork> {
+  return {
+    id: record.id,
+    thumbnail: await blobToDataUrl(record.thumbnail),
+    full: await blobToDataUrl(record.full),
+    addedAt: record.addedAt,
+  };
+}
+
+// Artwork from a backup file; undefined when its images can't be read
+export function artworkFromBackup(
+  entry: BackupArtwork,
+): ArtworkRecord | undefined {
+  const thumbnail = dataUrlToBlob(entry.thumbnail);
+  const full = dataUrlToBlob(entry.full);
+  if (!thumbnail || !full) return undefined;
+  return { id: entry.id, thumbnail, full, addedAt: entry.addedAt };
+}
+
+// Position of a file name in FOLDER_COVER_NAMES, or -1 when it isn't a cover
+export function getFolderCoverRank(fileName: string): number {
+  const name = fileName.toLowerCase();
+  const dot = name.lastIndexOf(".");
+  if (dot <= 0 || !FOLDER_COVER_EXTENSIONS.includes(name.slice(dot))) {
+    return -1;
+  }
+  return FOLDER_COVER_NAMES.indexOf(name.slice(0, dot));
+}
+
+// Folder part of a file's webkitRelativePath; "" for files picked on their own
+export function getRelativeFolder(file: File): string {
+  const path = file.webkitRelativePath || file.name;
+  return path.slice(0, Math.max(0, path.lastIndexOf("/")));
+}
+
+// The best cover image in each folder of a picked file list
+export function findFolderCovers(files: File[]): Map<string, File> {
+  const best = new Map<string, { file: File; rank: number }>();
+  for (const file of files) {
+    const rank = getFolderCoverRank(file.name);
+    if (rank < 0) continue;
+    const folder = getRelativeFolder(file);
+    const current = best.get(folder);
+    if (!current || rank < current.rank) best.set(folder, { file, rank });
+  }
+  return new Map([...best].map(([folder, { file }]) => [folder, file]));
+}
+
+async function hashBytes(data: Uint8Array<ArrayBuffer>): Promise<string> {
+  const digest = await crypto.subtle.digest("SHA-256", data);
+  return Array.from(new Uint8Array(digest), (byte) =>
+    byte.toString(16).padStart(2, "0"),
+  ).join("");
+}
+
+async function encodeJpeg(image: ImageBitmap, maxSize: number): Promise<Blob> {
+  const { width, height } = getScaledSize(image.width, image.height, maxSize);
+  const scaled = await createImageBitmap(image, {
+    resizeWidth: width,
+    resizeHeight: height,
+    resizeQuality: "high",
+  });
+
+  try {
+    const canvas = document.createElement("canvas");
+    canvas.width = width;
+    canvas.height = height;
+    const context = canvas.getContext("2d");
+    if (!context) throw new Error("Canvas is unavailable");
+    context.drawImage(scaled, 0, 0, width, height);
+
+    return await new Promise<Blob>((resolve, reject) =>
+      canvas.toBlob(
+        (blob) =>
+          blob ? resolve(blob) : reject(new Error("Couldn't encode artwork")),
+        "image/jpeg",
+        ARTWORK_JPEG_QUALITY,
+      ),
+    );
+  } finally {
+    scaled.close();
+  }
+}
+
+// Object URLs by `${variant}:${id}`; ids found missing aren't looked up again
+const artworkUrls = new Map<string, string>();
+const pendingUrls = new Map<string, Promise<string | undefined>>();
+const missingArtwork = new Set<string>();
+
+async function createArtwork(id: string, original: Blob): Promise<string> {
+  if (await hasArtwork(id)) return id;
+
+  const image = await createImageBitmap(original);
+  try {
+    const thumbnail = await encodeJpeg(image, ARTWORK_THUMBNAIL_SIZE);
+    const full =
+      Math.max(image.width, image.height) > ARTWORK_FULL_SIZE
+        ? await encodeJpeg(image, ARTWORK_FULL_SIZE)
+        : original;
+    await putArtwork({ id, thumbnail, full, addedAt: Date.now() });
+    missingArtwork.delete(id);
+    return id;
+  } finally {
+    image.close();
+  }
+}
+
+const pendingStores = new Map<string, Promise<string>>();
+
+// Add an image to the artwork store and return its id. An image that is
+// already stored (the same cover on every track of an album) is only hashed.
+// Undefined when the image can't be decoded
+export async function storeArtwork(
+  data: Uint8Array,
+  mimeType = "image/jpeg",
+): Promise<string | undefined> {
+  try {
+    const bytes = new Uint8Array(data);
+    const id = await hashBytes(bytes);
+
+    let pending = pendingStores.get(id);
+    if (!pending) {
+      pending = createArtwork(id, new Blob([bytes], { type: mimeType }));
+      pendingStores.set(id, pending);
+      pending.finally(() => pendingStores.delete(id)).catch(() => {});
+    }
+    return await pending;
+  } catch (error) {
+    console.warn("Failed to store artwork:", error);
+    return undefined;
+  }
+}
+
+export async function storeArtworkDataUrl(
+  url: string,
+): Promise<string | undefined> {
+  const parsed = parseDataUrl(url);
+  return parsed && storeArtwork(parsed.data, parsed.mimeType);
+}
+
+// Folder covers are shared by every song in the folder, so each file is
+// stored once
+const storedFiles = new WeakMap<File, Promise<string | undefined>>();
+
+export function storeArtworkFile(file: File): Promise<string | undefined> {
+  let stored = storedFiles.get(file);
+  if (!stored) {
+    stored = file
+      .arrayBuffer()
+      .then((data) =>
+        storeArtwork(new Uint8Array(data), file.type || "image/jpeg"),
+      )
+      .catch(() => undefined);
+    storedFiles.set(file, stored);
+  }
+  return stored;
+}
+
+function urlKey(id: string, variant: ArtworkVariant): string {
+  return `${variant}:${id}`;
+}
+
+export function getCachedArtworkUrl(
+  id: string,
+  variant: ArtworkVariant,
+): string | undefined {
+  return artworkUrls.get(urlKey(id, variant));
+}
+
+// Oldest full-size URLs go first; an <img> already showing one keeps its image
+function evictFullUrls() {
+  const fullKeys = [...artworkUrls.keys()].filter((key) =>
+    key.startsWith("full:"),
+  );
+  for (const key of fullKeys.slice(0, -FULL_URL_LIMIT)) {
+    URL.revokeObjectURL(artworkUrls.get(key)!);
+    artworkUrls.delete(key);
+  }
+}
+
+export function loadArtworkUrl(
+  id: string,
+  variant: ArtworkVariant,
+): Promise<string | undefined> {
+  const key = urlKey(id, variant);
+  const cached = artworkUrls.get(key);
+  if (cached) return Promise.resolve(cached);
+  if (missingArtwork.has(id)) return Promise.resolve(undefined);
+
+  let pending = pendingUrls.get(key);
+  if (!pending) {
+    pending = getArtwork(id)
+      .then((record) => {
+        if (!record) {
+          missingArtwork.add(id);
+          return undefined;
+        }
+        const url = URL.createObjectURL(record[variant]);
+        artworkUrls.set(key, url);
+        if (variant === "full") evictFullUrls();
+        return url;
+      })
+      .catch((error) => {
+        console.warn("Failed to load artwork:", id, error);
+        return undefined;
+      })
+      .finally(() => pendingUrls.delete(key));
+    pendingUrls.set(key, pending);
+  }
+  return pending;
+}
+
+// Songs saved before the artwork store kept their cover inline as a data URL
+type LegacySong = Song & { coverArt?: string };
+
+export function hasLegacyArtwork(song: Song): boolean {
+  return "coverArt" in song;
+}
+
+// Store the inline cover art of older songs, returning each song's artwork id.
+// Songs sharing an image are only decoded once
+export async function storeLegacyArtwork(
+  songs: Song[],
+): Promise<Map<string, string | undefined>> {
+  const idsByUrl = new Map<string, string | undefined>();
+  const artworkIds = new Map<string, string | undefined>();
+
+  for (const song of songs as LegacySong[]) {
+    const url = song.coverArt;
+    if (url && !idsByUrl.has(url)) {
+      idsByUrl.set(url, await storeArtworkDataUrl(url));
+    }
+    artworkIds.set(song.id, song.artworkId ?? (url && idsByUrl.get(url)));
+  }
+  return artworkIds;
+}
+
+// The song without its inline cover art, pointing at the stored copy instead
+export function replaceLegacyArtwork(
+  song: Song,
+  artworkId: string | undefined,
+): Song {
+  const updated: LegacySong = { ...song };
+  delete updated.coverArt;
+  return artworkId ? { ...updated, artworkId } : updated;
+}
+
+// Drop artwork left behind by deleted or retagged songs. Anything stored this
+// session is kept, since an import may not have saved its songs yet
+export function pruneUnusedArtwork(songs: Song[]): Promise<number> {
+  const used = new Set<string>();
+  for (const song of songs) {
+    if (song.artworkId) used.add(song.artworkId);
+  }
+  return pruneArtwork(used, SESSION_STARTED_AT);
+}
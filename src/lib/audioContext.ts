@@ -1,14 +1,18 @@
 // Shared audio context for equalizer and visualizer
 // An audio element can only be connected to ONE MediaElementSourceNode EVER
 
+import { PITCH_SHIFT_PROCESSOR } from "./pitchShift";
+
 const AUDIO_DATA = Symbol.for("vinyl-shared-audio-context");
+const ELEMENT_GAIN = Symbol.for("vinyl-element-gain");
+const PITCH_SHIFTER = Symbol.for("vinyl-pitch-shifter");
 
 export interface SharedAudioData {
   audioContext: AudioContext;
   sourceNode: MediaElementAudioSourceNode;
   analyser: AnalyserNode;
   preGainNode: GainNode; // Before EQ
-  postGainNode: GainNode; // After EQ, before destination
+  postGainNode: GainNode; // After EQ, before the pitch shifter or destination
 }
 
 function getAudioData(el: HTMLAudioElement): SharedAudioData | undefined {
@@ -19,6 +23,15 @@ function setAudioData(el: HTMLAudioElement, data: SharedAudioData): void {
   (el as unknown as Record<symbol, SharedAudioData>)[AUDIO_DATA] = data;
 }
 
+// Gain of one element's own branch, ahead of the shared chain
+function getElementGain(el: HTMLAudioElement): GainNode | null {
+  return (el as unknown as Record<symbol, GainNode>)[ELEMENT_GAIN] ?? null;
+}
+
+function setElementGain(el: HTMLAudioElement, gain: GainNode): void {
+  (el as unknown as Record<symbol, GainNode>)[ELEMENT_GAIN] = gain;
+}
+
 export function getSharedAudioContext(
   audioElement: HTMLAudioElement
 ): SharedAudioData | null {
@@ -53,9 +66,14 @@ export function getSharedAudioContext(
     const preGainNode = audioContext.createGain();
     const postGainNode = audioContext.createGain();
 
-    // Default chain: source -> analyser -> preGain -> postGain -> destination
-    // The equalizer will insert filters between preGain and postGain
-    sourceNode.connect(analyser);
+    // Each element gets its own gain, so crossfades can fade one against another
+    const elementGain = audioContext.createGain();
+
+    // Default chain: source -> elementGain -> analyser -> preGain -> postGain -> destination
+    // The equalizer will insert filters between preGain and postGain, and
+    // the pitch shifter goes after postGain once something needs it
+    sourceNode.connect(elementGain);
+    elementGain.connect(analyser);
     analyser.connect(preGainNode);
     preGainNode.connect(postGainNode);
     postGainNode.connect(audioContext.destination);
@@ -70,6 +88,7 @@ export function getSharedAudioContext(
 
     // Store on the element itself so it survives HMR
     setAudioData(audioElement, data);
+    setElementGain(audioElement, elementGain);
 
     // Resume if suspended
     if (audioContext.state === "suspended") {
@@ -91,3 +110,89 @@ export function resumeAudioContext(audioElement: HTMLAudioElement): void {
     data.audioContext.resume();
   }
 }
+
+// Get the shared context only if it was already created
+export function peekSharedAudioContext(
+  audioElement: HTMLAudioElement
+): SharedAudioData | null {
+  return getAudioData(audioElement) ?? null;
+}
+
+/**
+ * Route another audio element into an existing shared chain (crossfades play
+ * two elements at once) and return its element gain
+ * Returns null for an element already tied to a different context.
+ */
+export function connectAudioElement(
+  shared: SharedAudioData,
+  audioElement: HTMLAudioElement
+): GainNode | null {
+  const existing = getAudioData(audioElement);
+  if (existing) {
+    return existing === shared ? getElementGain(audioElement) : null;
+  }
+
+  try {
+    const { audioContext, analyser } = shared;
+    const sourceNode = audioContext.createMediaElementSource(audioElement);
+    const elementGain = audioContext.createGain();
+    sourceNode.connect(elementGain);
+    elementGain.connect(analyser);
+
+    // The equalizer and visualizer find the same chain through either element
+    setAudioData(audioElement, shared);
+    setElementGain(audioElement, elementGain);
+    return elementGain;
+  } catch (error) {
+    console.error("[AudioContext] Failed to connect element:", error);
+    return null;
+  }
+}
+
+/**
+ * Pitch shifter between the shared chain and the destination, added the
+ * first time it's asked for
+ * Resolves null where AudioWorklet isn't available or the module won't load.
+ */
+export function getPitchShifter(
+  shared: SharedAudioData
+): Promise<AudioWorkletNode | null> {
+  const data = shared as unknown as Record<
+    symbol,
+    Promise<AudioWorkletNode | null>
+  >;
+  data[PITCH_SHIFTER] ??= createPitchShifter(shared);
+  return data[PITCH_SHIFTER];
+}
+
+async function createPitchShifter({
+  audioContext,
+  postGainNode,
+}: SharedAudioData): Promise<AudioWorkletNode | null> {
+  if (!audioContext.audioWorklet || typeof AudioWorkletNode === "undefined") {
+    return null;
+  }
+
+  try {
+    const { default: moduleUrl } = await import(
+      "./pitchShift.worklet?worker&url"
+    );
+    await audioContext.audioWorklet.addModule(moduleUrl);
+    const node = new AudioWorkletNode(audioContext, PITCH_SHIFT_PROCESSOR, {
+      channelCount: 2,
+      channelCountMode: "explicit",
+      outputChannelCount: [2],
+    });
+
+    // postGain -> pitch shifter -> destination
+    postGainNode.disconnect();
+    postGainNode.connect(node);
+    node.connect(audioContext.destination);
+
+    console.log("[AudioContext] Pitch shifter added");
+    return node;
+  } catch (error) {
+    console.error("[AudioContext] Failed to add pitch shifter:", error);
+    return null;
+  }
+}
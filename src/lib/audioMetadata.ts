@@ -1,4 +1,6 @@
-import type { Song } from "../types";
+import type { Song, ReplayGain } from "../types";
+import { normalizeCodec } from "./songMetadata";
+import { storeArtwork } from "./artwork";
 
 // Lazy-loaded music-metadata module (saves ~106KB on initial load)
 let musicMetadataModule: typeof import("music-metadata") | null = null;
@@ -15,28 +17,30 @@ export function generateId(): string {
   return `${Date.now()}-${Math.random().toString(36).substr(2, 9)}`;
 }
 
-// Type for picture data (compatible with music-metadata IPicture)
-interface PictureData {
-  data: Uint8Array;
-  format?: string;
+// Gain/peak pair as music-metadata reports ReplayGain tags
+interface RatioTag {
+  dB: number;
+  ratio: number;
 }
 
-// Convert picture data to base64 data URL
-function pictureToDataUrl(picture: PictureData): string {
-  const base64 = arrayBufferToBase64(picture.data);
-  const mimeType = picture.format || "image/jpeg";
-  return `data:${mimeType};base64,${base64}`;
-}
-
-// Convert ArrayBuffer to base64 string
-function arrayBufferToBase64(buffer: Uint8Array): string {
-  let binary = "";
-  const bytes = new Uint8Array(buffer);
-  const len = bytes.byteLength;
-  for (let i = 0; i < len; i++) {
-    binary += String.fromCharCode(bytes[i]);
+// Collect ReplayGain tags, if the file has any
+function readReplayGain(common: {
+  replaygain_track_gain?: RatioTag;
+  replaygain_track_peak?: RatioTag;
+  replaygain_album_gain?: RatioTag;
+  replaygain_album_peak?: RatioTag;
+}): ReplayGain | undefined {
+  const trackGain = common.replaygain_track_gain?.dB;
+  const albumGain = common.replaygain_album_gain?.dB;
+  if (trackGain === undefined && albumGain === undefined) {
+    return undefined;
   }
-  return btoa(binary);
+  return {
+    trackGain,
+    trackPeak: common.replaygain_track_peak?.ratio,
+    albumGain,
+    albumPeak: common.replaygain_album_peak?.ratio,
+  };
 }
 
 // Get duration using HTML5 Audio element (fallback)
@@ -90,11 +94,14 @@ export async function extractMetadata(file: File): Promise<Partial<Song>> {
     }
 
     // Get album art
-    let coverArt: string | undefined;
+    let artworkId: string | undefined;
     if (common.picture && common.picture.length > 0) {
       // Get the first picture (usually front cover)
       const picture = common.picture[0];
-      coverArt = pictureToDataUrl(picture);
+      artworkId = await storeArtwork(
+        picture.data,
+        picture.format || "image/jpeg",
+      );
     }
 
     // Get duration - use format duration or fallback to audio element
@@ -107,9 +114,22 @@ export async function extractMetadata(file: File): Promise<Partial<Song>> {
       title: title || file.name.replace(/\.[^/.]+$/, ""),
       artist: artist || "Unknown Artist",
       album: common.album || "Unknown Album",
+      albumArtist: common.albumartist?.trim() || undefined,
+      trackNumber: common.track.no || undefined,
+      discNumber: common.disk.no || undefined,
+      genre: common.genre?.[0]?.trim() || undefined,
+      year: common.year || undefined,
+      composer: common.composer?.[0]?.trim() || undefined,
+      bpm: common.bpm ? Math.round(common.bpm) : undefined,
       duration: duration || 0,
-      coverArt,
+      bitrate: format.bitrate ? Math.round(format.bitrate / 1000) : undefined,
+      sampleRate: format.sampleRate || undefined,
+      bitDepth: format.bitsPerSample || undefined,
+      channels: format.numberOfChannels || undefined,
+      codec: normalizeCodec(format.codec),
+      artworkId,
       sourceType: "local",
+      replayGain: readReplayGain(common),
     };
   } catch (error) {
     console.warn(
@@ -0,0 +1,186 @@
+import { describe, it, expect } from 'vitest';
+import {
+  BACKUP_VERSION,
+  createBackup,
+  migrateBackup,
+  planRestore,
+  type BackupData,
+} from './backup';
+import type { PlaybackProfile, PlayRecord } from '../types';
+import { createMockPlaylist, createMockSong } from '../test/test-utils';
+
+const emptyData = (overrides: Partial<BackupData> = {}): BackupData => ({
+  songs: [],
+  playlists: [],
+  playerState: null,
+  settings: null,
+  equalizer: null,
+  libraryFolders: [],
+  plays: [],
+  artwork: [],
+  songPitch: null,
+  playbackProfiles: [],
+  ...overrides,
+});
+
+const artwork = (id: string) => ({ id, thumbnail: 'data:image/jpeg;base64,AQ==', full: 'data:image/jpeg;base64,AQID', addedAt: 1 });
+
+const play = (id: string, songId: string, playedAt: number): PlayRecord => ({
+  id,
+  songId,
+  playedAt,
+  listened: 200,
+  outcome: 'completed',
+  source: 'library',
+});
+
+describe('migrateBackup', () => {
+  it('round-trips a backup through JSON', () => {
+    const data = emptyData({ songs: [createMockSong({ id: 'a' })], settings: { theme: 'light' } });
+    const backup = createBackup(data, 1000);
+    expect(migrateBackup(JSON.parse(JSON.stringify(backup)))).toEqual(backup);
+    expect(backup.version).toBe(BACKUP_VERSION);
+  });
+
+  it('rejects files that are not backups or come from a newer version', () => {
+    expect(() => migrateBackup({ songs: [] })).toThrow('not a Vinyl backup');
+    expect(() => migrateBackup({ format: 'vinyl-backup', version: BACKUP_VERSION + 1, data: {} })).toThrow('newer version');
+  });
+
+  it('runs each migration after the backup version and fills missing sections', () => {
+    const migrations = [
+      (data: Record<string, unknown>) => ({ ...data, plays: [] }),
+      (data: Record<string, unknown>) => ({ ...data, libraryFolders: ['/music'] }),
+    ];
+    const backup = migrateBackup(
+      { format: 'vinyl-backup', version: 2, data: { songs: [{ id: 'a', title: 'A' }, { title: 'no id' }] } },
+      migrations,
+    );
+
+    expect(backup.version).toBe(3);
+    expect(backup.data.songs).toEqual([{ id: 'a', title: 'A' }]);
+    expect(backup.data.libraryFolders).toEqual(['/music']);
+    expect(backup.data.playlists).toEqual([]);
+    expect(backup.data.settings).toBeNull();
+  });
+
+  it('keeps the inline cover art of version 1 backups for the artwork store', () => {
+    const song = { id: 'a', title: 'A', coverArt: 'data:image/png;base64,AQID' };
+    const backup = migrateBackup({ format: 'vinyl-backup', version: 1, data: { songs: [song] } });
+
+    expect(backup.version).toBe(BACKUP_VERSION);
+    expect(backup.data.songs).toEqual([song]);
+    expect(backup.data.artwork).toEqual([]);
+    expect(backup.data.playbackProfiles).toEqual([]);
+  });
+});
+
+describe('planRestore', () => {
+  it('replaces everything in replace mode', () => {
+    const backup = emptyData({ songs: [createMockSong({ id: 'b' })] });
+    const plan = planRestore(emptyData({ songs: [createMockSong({ id: 'a' })] }), backup, 'replace');
+    expect(plan.data).toBe(backup);
+    expect(plan.added.songs).toBe(1);
+    expect(plan.conflicts).toEqual([]);
+  });
+
+  it('drops artwork ids the backup has no artwork for', () => {
+    const backup = emptyData({
+      songs: [createMockSong({ id: 'a', artworkId: 'cover' }), createMockSong({ id: 'b', artworkId: 'gone' })],
+      artwork: [artwork('cover')],
+    });
+    const plan = planRestore(emptyData(), backup, 'replace');
+
+    expect(plan.data.songs.map((s) => s.artworkId)).toEqual(['cover', undefined]);
+    expect(plan.data.artwork).toEqual([artwork('cover')]);
+  });
+
+  it('moves remembered pitch and song profiles onto library songs', () => {
+    const profile = (id: string, target: PlaybackProfile['target'], key: string): PlaybackProfile => ({ id, target, key, label: key, speed: 1.1, createdAt: 1 });
+    const current = emptyData({
+      songs: [createMockSong({ id: 'lib', title: 'Same', filePath: '/m/same.mp3' })],
+      songPitch: { lib: { semitones: 2, speed: 1 } },
+      playbackProfiles: [profile('p1', 'album', 'x')],
+    });
+    const backup = emptyData({
+      songs: [createMockSong({ id: 'old', title: 'Same', filePath: '/m/same.mp3' }), createMockSong({ id: 'new', title: 'New' })],
+      songPitch: { old: { semitones: -3, speed: 1 }, new: { semitones: 1, speed: 0.9 } },
+      playbackProfiles: [profile('p2', 'song', 'old'), profile('p3', 'album', 'x'), profile('p4', 'song', 'new')],
+    });
+
+    const plan = planRestore(current, backup, 'merge');
+
+    expect(plan.data.songPitch).toEqual({ lib: { semitones: 2, speed: 1 }, new: { semitones: 1, speed: 0.9 } });
+    expect(plan.data.playbackProfiles.map((p) => [p.id, p.key])).toEqual([['p1', 'x'], ['p2', 'lib'], ['p4', 'new']]);
+  });
+
+  it('merges artwork by id', () => {
+    const current = emptyData({ songs: [createMockSong({ id: 'a', artworkId: 'mine' })], artwork: [artwork('mine')] });
+    const backup = emptyData({
+      songs: [createMockSong({ id: 'b', title: 'B', artworkId: 'mine' }), createMockSong({ id: 'c', title: 'C', artworkId: 'theirs' })],
+      artwork: [{ ...artwork('mine'), addedAt: 5 }, artwork('theirs')],
+    });
+    const plan = planRestore(current, backup, 'merge');
+
+    expect(plan.data.artwork).toEqual([artwork('mine'), artwork('theirs')]);
+    expect(plan.data.songs.map((s) => s.artworkId)).toEqual(['mine', 'mine', 'theirs']);
+  });
+
+  it('merges songs, mapping duplicates onto library songs', () => {
+    const current = emptyData({
+      songs: [createMockSong({ id: 'lib', title: 'Same', filePath: '/m/same.mp3', playCount: 1, lastPlayedAt: 10 })],
+      plays: [play('p1', 'lib', 10)],
+    });
+    const backup = emptyData({
+      songs: [
+        createMockSong({ id: 'old', title: 'Same', filePath: '/m/same.mp3', playCount: 2 }),
+        createMockSong({ id: 'new', title: 'New', playCount: 1 }),
+      ],
+      plays: [play('p1', 'old', 10), play('p2', 'old', 20), play('p3', 'new', 30)],
+      playlists: [createMockPlaylist({ id: 'pl', name: 'Mix', songIds: ['old', 'new'] })],
+    });
+
+    const plan = planRestore(current, backup, 'merge');
+    const songs = Object.fromEntries(plan.data.songs.map((s) => [s.id, s]));
+
+    expect(Object.keys(songs)).toEqual(['lib', 'new']);
+    // The backup's extra play counts towards the library song; the new song
+    // keeps its own counts
+    expect(songs.lib.playCount).toBe(2);
+    expect(songs.lib.lastPlayedAt).toBe(20);
+    expect(songs.new.playCount).toBe(1);
+    expect(plan.data.plays.map((p) => [p.id, p.songId])).toEqual([['p1', 'lib'], ['p2', 'lib'], ['p3', 'new']]);
+    expect(plan.data.playlists[0].songIds).toEqual(['lib', 'new']);
+    expect(plan.added).toEqual({ songs: 1, playlists: 1, plays: 2 });
+    expect(plan.conflicts).toEqual([
+      { kind: 'song', name: 'Test Artist - Same', resolution: "Already in the library; kept the library's copy" },
+    ]);
+  });
+
+  it('adds missing songs to playlists with the same name and keeps current settings', () => {
+    const current = emptyData({
+      songs: [createMockSong({ id: 'a', title: 'A' }), createMockSong({ id: 'b', title: 'B' })],
+      playlists: [createMockPlaylist({ id: 'mine', name: 'Road Trip', songIds: ['a'], updatedAt: 1 })],
+      settings: { theme: 'dark' },
+      libraryFolders: ['/music'],
+    });
+    const backup = emptyData({
+      playlists: [createMockPlaylist({ id: 'theirs', name: 'road trip ', songIds: ['b', 'a'], updatedAt: 5 })],
+      settings: { theme: 'light' },
+      equalizer: { enabled: true },
+      libraryFolders: ['/music', '/more'],
+    });
+
+    const plan = planRestore(current, backup, 'merge');
+
+    expect(plan.data.playlists).toHaveLength(1);
+    expect(plan.data.playlists[0]).toMatchObject({ id: 'mine', songIds: ['a', 'b'], updatedAt: 5 });
+    expect(plan.data.settings).toEqual({ theme: 'dark' });
+    expect(plan.data.equalizer).toEqual({ enabled: true });
+    expect(plan.data.libraryFolders).toEqual(['/music', '/more']);
+    expect(plan.conflicts.map((c) => [c.kind, c.resolution])).toEqual([
+      ['playlist', 'Added 1 song from the backup'],
+      ['settings', 'Kept the current values'],
+    ]);
+  });
+});
@@ -0,0 +1,405 @@
+import type {
+  AppSettings,
+  PlaybackProfile,
+  Playlist,
+  PlayerState,
+  PlayRecord,
+  Song,
+} from "../types";
+import { applyPlayToSong } from "./playStats";
+import { getSongLookupKey } from "./songLookup";
+
+export const BACKUP_FORMAT = "vinyl-backup";
+
+// A stored cover, both sizes as data URLs
+export interface BackupArtwork {
+  id: string;
+  thumbnail: string;
+  full: string;
+  addedAt: number;
+}
+
+export interface BackupData {
+  songs: Song[];
+  playlists: Playlist[];
+  playerState: PlayerState | null;
+  settings: Partial<AppSettings> | null;
+  // Stored equalizer state (`vinyl-equalizer-settings`)
+  equalizer: Record<string, unknown> | null;
+  // Desktop library folders
+  libraryFolders: string[];
+  plays: PlayRecord[];
+  // Cover art the songs' artworkId point at
+  artwork: BackupArtwork[];
+  // Key and tempo remembered per song id (`vinyl-song-pitch`)
+  songPitch: Record<string, unknown> | null;
+  playbackProfiles: PlaybackProfile[];
+}
+
+export interface LibraryBackup {
+  format: typeof BACKUP_FORMAT;
+  version: number;
+  createdAt: number;
+  data: BackupData;
+}
+
+type BackupMigration = (
+  data: Record<string, unknown>,
+) => Record<string, unknown>;
+
+// BACKUP_MIGRATIONS[n] upgrades the data of a version n + 1 backup to
+// version n + 2. Add a step whenever the layout of BackupData changes
+export const BACKUP_MIGRATIONS: BackupMigration[] = [
+  // Cover art moved into its own store. Songs backed up before that keep
+  // their inline coverArt, which is moved into the store on the first start
+  // after restoring; artworkIds without artwork are dropped by planRestore
+  (data) => ({ ...data, artwork: [] }),
+  // Remembered song pitch and playback profiles
+  (data) => ({ ...data, songPitch: null, playbackProfiles: [] }),
+];
+
+export const BACKUP_VERSION = BACKUP_MIGRATIONS.length + 1;
+
+export function createBackup(data: BackupData, now: number): LibraryBackup {
+  return {
+    format: BACKUP_FORMAT,
+    version: BACKUP_VERSION,
+    createdAt: now,
+    data,
+  };
+}
+
+function isRecord(value: unknown): value is Record<string, unknown> {
+  return typeof value === "object" && value !== null && !Array.isArray(value);
+}
+
+function records<T>(
+  value: unknown,
+  isValid: (item: Record<string, unknown>) => boolean,
+): T[] {
+  return Array.isArray(value)
+    ? (value.filter((item) => isRecord(item) && isValid(item)) as T[])
+    : [];
+}
+
+// Check a parsed backup file and bring it up to the current version. Missing
+// sections restore as empty; malformed records are dropped
+export function migrateBackup(
+  raw: unknown,
+  migrations: BackupMigration[] = BACKUP_MIGRATIONS,
+): LibraryBackup {
+  const rawData = isRecord(raw) ? raw.data : undefined;
+  if (!isRecord(raw) || raw.format !== BACKUP_FORMAT || !isRecord(rawData)) {
+    throw new Error("This file is not a Vinyl backup");
+  }
+  const version = raw.version;
+  const currentVersion = migrations.length + 1;
+  if (typeof version !== "number" || !Number.isInteger(version)) {
+    throw new Error("This backup has an unknown version");
+  }
+  if (version > currentVersion) {
+    throw new Error("This backup was made by a newer version of Vinyl");
+  }
+
+  let data: Record<string, unknown> = rawData;
+  for (const migrate of migrations.slice(Math.max(0, version - 1))) {
+    data = migrate(data);
+  }
+
+  return {
+    format: BACKUP_FORMAT,
+    version: currentVersion,
+    createdAt: typeof raw.createdAt === "number" ? raw.createdAt : 0,
+    data: {
+      songs: records<Song>(
+        data.songs,
+        (s) => typeof s.id === "string" && typeof s.title === "string",
+      ),
+      playlists: records<Playlist>(
+        data.playlists,
+        (p) => typeof p.id === "string" && Array.isArray(p.songIds),
+      ),
+      playerState: isRecord(data.playerState)
+        ? (data.playerState as unknown as PlayerState)
+        : null,
+      settings: isRecord(data.settings)
+        ? (data.settings as Partial<AppSettings>)
+        : null,
+      equalizer: isRecord(data.equalizer) ? data.equalizer : null,
+      libraryFolders: Array.isArray(data.libraryFolders)
+        ? data.libraryFolders.filter((f): f is string => typeof f === "string")
+        : [],
+      plays: records<PlayRecord>(
+        data.plays,
+        (p) => typeof p.id === "string" && typeof p.songId === "string",
+      ),
+      artwork: records<BackupArtwork>(
+        data.artwork,
+        (a) =>
+          typeof a.id === "string" &&
+          typeof a.thumbnail === "string" &&
+          typeof a.full === "string" &&
+          typeof a.addedAt === "number",
+      ),
+      songPitch: isRecord(data.songPitch) ? data.songPitch : null,
+      playbackProfiles: records<PlaybackProfile>(
+        data.playbackProfiles,
+        (p) =>
+          typeof p.id === "string" &&
+          typeof p.target === "string" &&
+          typeof p.key === "string",
+      ),
+    },
+  };
+}
+
+export type BackupRestoreMode = "merge" | "replace";
+
+export interface BackupConflict {
+  kind: "song" | "playlist" | "settings" | "equalizer" | "folder";
+  name: string;
+  // What the restore did about it
+  resolution: string;
+}
+
+export interface BackupRestorePlan {
+  // Everything to write, current data included when merging
+  data: BackupData;
+  added: { songs: number; playlists: number; plays: number };
+  conflicts: BackupConflict[];
+}
+
+const same = (a: unknown, b: unknown) =>
+  JSON.stringify(a) === JSON.stringify(b);
+
+function songLabel(song: Song): string {
+  return song.artist ? `${song.artist} - ${song.title}` : song.title;
+}
+
+// Songs pointing at artwork the restore doesn't have would show no cover, so
+// the reference is dropped. The same array when nothing changes
+function dropMissingArtwork(songs: Song[], artworkIds: Set<string>): Song[] {
+  const isMissing = (song: Song) =>
+    song.artworkId !== undefined && !artworkIds.has(song.artworkId);
+  if (!songs.some(isMissing)) return songs;
+
+  return songs.map((song) => {
+    if (!isMissing(song)) return song;
+    const updated = { ...song };
+    delete updated.artworkId;
+    return updated;
+  });
+}
+
+// Work out what a restore writes. Replace takes the backup as it is. Merge
+// keeps everything in the library and adds what the backup has on top:
+// songs already in the library (same id, path, or title/artist/duration) keep
+// the library's copy, playlists with the same id or name gain the backup's
+// extra songs, and settings and the equalizer stay as they are
+export function planRestore(
+  current: BackupData,
+  backup: BackupData,
+  mode: BackupRestoreMode,
+): BackupRestorePlan {
+  if (mode === "replace") {
+    const songs = dropMissingArtwork(
+      backup.songs,
+      new Set(backup.artwork.map((a) => a.id)),
+    );
+    return {
+      data: songs === backup.songs ? backup : { ...backup, songs },
+      added: {
+        songs: backup.songs.length,
+        playlists: backup.playlists.length,
+        plays: backup.plays.length,
+      },
+      conflicts: [],
+    };
+  }
+
+  const conflicts: BackupConflict[] = [];
+  const added = { songs: 0, playlists: 0, plays: 0 };
+
+  // Songs; backup ids are mapped to the library song they turned out to be
+  const songs = new Map(current.songs.map((s) => [s.id, s]));
+  const byPath = new Map<string, Song>();
+  const byLookupKey = new Map<string, Song>();
+  for (const song of current.songs) {
+    if (song.filePath) byPath.set(song.filePath, song);
+    byLookupKey.set(
+      getSongLookupKey(song.title, song.artist, song.duration),
+      song,
+    );
+  }
+
+  const songIdMap = new Map<string, string>();
+  const addedSongIds = new Set<string>();
+  for (const song of backup.songs) {
+    const existing =
+      songs.get(song.id) ??
+      (song.filePath ? byPath.get(song.filePath) : undefined) ??
+      byLookupKey.get(getSongLookupKey(song.title, song.artist, song.duration));
+
+    if (!existing) {
+      songs.set(song.id, song);
+      songIdMap.set(song.id, song.id);
+      addedSongIds.add(song.id);
+      added.songs++;
+      continue;
+    }
+
+    songIdMap.set(song.id, existing.id);
+    if (existing.id !== song.id) {
+      conflicts.push({
+        kind: "song",
+        name: songLabel(song),
+        resolution: "Already in the library; kept the library's copy",
+      });
+    } else if (
+      existing.title !== song.title ||
+      existing.artist !== song.artist ||
+      existing.album !== song.album
+    ) {
+      conflicts.push({
+        kind: "song",
+        name: songLabel(existing),
+        resolution: "Tags differ from the backup; kept the library's tags",
+      });
+    }
+  }
+
+  // Play history; counts on songs that were already here include only their
+  // own plays, so the backup's extra plays are added to them
+  const plays = [...current.plays];
+  const playIds = new Set(current.plays.map((p) => p.id));
+  for (const play of backup.plays) {
+    if (playIds.has(play.id)) continue;
+    const songId = songIdMap.get(play.songId) ?? play.songId;
+    plays.push({ ...play, songId });
+    playIds.add(play.id);
+    added.plays++;
+
+    const song = songs.get(songId);
+    if (song && !addedSongIds.has(songId)) {
+      songs.set(songId, applyPlayToSong(song, play));
+    }
+  }
+
+  // Playlists
+  const playlists = new Map(current.playlists.map((p) => [p.id, p]));
+  const byName = new Map(
+    current.playlists.map((p) => [p.name.trim().toLowerCase(), p]),
+  );
+  for (const playlist of backup.playlists) {
+    const songIds = [
+      ...new Set(playlist.songIds.map((id) => songIdMap.get(id) ?? id)),
+    ];
+    const existing =
+      playlists.get(playlist.id) ??
+      byName.get(playlist.name.trim().toLowerCase());
+
+    if (!existing) {
+      playlists.set(playlist.id, { ...playlist, songIds });
+      added.playlists++;
+      continue;
+    }
+
+    if (existing.rules || playlist.rules) {
+      if (!same(existing.rules, playlist.rules)) {
+        conflicts.push({
+          kind: "playlist",
+          name: existing.name,
+          resolution: "Rules differ from the backup; kept the library's rules",
+        });
+      }
+      continue;
+    }
+
+    const missing = songIds.filter((id) => !existing.songIds.includes(id));
+    if (missing.length === 0) continue;
+    playlists.set(existing.id, {
+      ...existing,
+      songIds: [...existing.songIds, ...missing],
+      updatedAt: Math.max(existing.updatedAt, playlist.updatedAt),
+    });
+    conflicts.push({
+      kind: "playlist",
+      name: existing.name,
+      resolution: `Added ${missing.length} song${missing.length === 1 ? "" : "s"} from the backup`,
+    });
+  }
+
+  // Artwork is keyed by content hash, so the same id is the same image
+  const artwork = new Map(current.artwork.map((a) => [a.id, a]));
+  for (const entry of backup.artwork) {
+    if (!artwork.has(entry.id)) artwork.set(entry.id, entry);
+  }
+
+  // Remembered pitch and song profiles follow the song ids above; the
+  // library's own entries win
+  let songPitch = current.songPitch;
+  if (backup.songPitch) {
+    songPitch = { ...current.songPitch };
+    for (const [songId, pitch] of Object.entries(backup.songPitch)) {
+      const id = songIdMap.get(songId) ?? songId;
+      if (!(id in songPitch)) songPitch[id] = pitch;
+    }
+  }
+
+  const playbackProfiles = [...current.playbackProfiles];
+  const profileTargets = new Set(
+    playbackProfiles.map((p) => `${p.target}:${p.key}`),
+  );
+  for (const profile of backup.playbackProfiles) {
+    const key =
+      profile.target === "song"
+        ? (songIdMap.get(profile.key) ?? profile.key)
+        : profile.key;
+    if (profileTargets.has(`${profile.target}:${key}`)) continue;
+    playbackProfiles.push({ ...profile, key });
+    profileTargets.add(`${profile.target}:${key}`);
+  }
+
+  // Single values stay as they are, noting where the backup differs
+  const keepCurrent = <T>(
+    kind: BackupConflict["kind"],
+    name: string,
+    currentValue: T | null,
+    backupValue: T | null,
+  ): T | null => {
+    if (currentValue === null) return backupValue;
+    if (backupValue !== null && !same(currentValue, backupValue)) {
+      conflicts.push({ kind, name, resolution: "Kept the current values" });
+    }
+    return currentValue;
+  };
+
+  return {
+    data: {
+      songs: dropMissingArtwork([...songs.values()], new Set(artwork.keys())),
+      playlists: [...playlists.values()],
+      playerState: current.playerState ?? backup.playerState,
+      settings: keepCurrent(
+        "settings",
+        "Settings",
+        current.settings,
+        backup.settings,
+      ),
+      equalizer: keepCurrent(
+        "equalizer",
+        "Equalizer",
+        current.equalizer,
+        backup.equalizer,
+      ),
+      libraryFolders: [
+        ...new Set([...current.libraryFolders, ...backup.libraryFolders]),
+      ],
+      plays,
+      artwork: [...artwork.values()],
+      songPitch,
+      playbackProfiles,
+    },
+    added,
+    conflicts,
+  };
+}
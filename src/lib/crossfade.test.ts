@@ -0,0 +1,117 @@
+import { describe, it, expect } from 'vitest';
+import { equalPowerCurve, findAudibleRange, isSameAlbumSequence, planCrossfade } from './crossfade';
+import { createMockSong } from '../test/test-utils';
+
+// Mono or stereo buffer from per-channel sample arrays
+function buffer(sampleRate: number, ...channels: number[][]) {
+  return {
+    sampleRate,
+    length: channels[0].length,
+    numberOfChannels: channels.length,
+    getChannelData: (channel: number) => Float32Array.from(channels[channel]),
+  };
+}
+
+describe('crossfade', () => {
+  describe('equalPowerCurve', () => {
+    it('runs from full to silent and back', () => {
+      const fadeIn = equalPowerCurve('in', 5);
+      const fadeOut = equalPowerCurve('out', 5);
+      expect(fadeIn[0]).toBe(0);
+      expect(fadeIn[4]).toBeCloseTo(1);
+      expect(fadeOut[0]).toBe(1);
+      expect(fadeOut[4]).toBeCloseTo(0);
+    });
+
+    it('keeps the summed power constant', () => {
+      const fadeIn = equalPowerCurve('in');
+      const fadeOut = equalPowerCurve('out');
+      for (let i = 0; i < fadeIn.length; i++) {
+        expect(fadeIn[i] ** 2 + fadeOut[i] ** 2).toBeCloseTo(1);
+      }
+      // Midpoint is about -3 dB on each side, not -6 dB as with a linear fade
+      expect(equalPowerCurve('out', 3)[1]).toBeCloseTo(Math.SQRT1_2);
+    });
+  });
+
+  describe('findAudibleRange', () => {
+    it('skips leading and trailing silence', () => {
+      const samples = [0, 0.0001, 0, 0.5, -0.2, 0.3, 0.001, 0, 0, 0];
+      expect(findAudibleRange(buffer(10, samples))).toEqual({ start: 0.3, end: 0.6 });
+    });
+
+    it('counts a frame audible when any channel is', () => {
+      const left = [0, 0, 0, 0.4, 0, 0];
+      const right = [0, 0.4, 0, 0, 0.4, 0];
+      expect(findAudibleRange(buffer(2, left, right))).toEqual({ start: 0.5, end: 2.5 });
+    });
+
+    it('returns null for a silent track', () => {
+      expect(findAudibleRange(buffer(10, [0, 0.001, -0.001, 0]))).toBeNull();
+    });
+  });
+
+  describe('isSameAlbumSequence', () => {
+    const track = (trackNumber?: number, discNumber?: number, album = 'Kid A') =>
+      createMockSong({ artist: 'Radiohead', album, trackNumber, discNumber });
+
+    it('matches the next track on the same album', () => {
+      expect(isSameAlbumSequence(track(3), track(4))).toBe(true);
+      expect(isSameAlbumSequence(track(12, 1), track(1, 2))).toBe(true);
+    });
+
+    it('does not match out of order tracks or other albums', () => {
+      expect(isSameAlbumSequence(track(3), track(5))).toBe(false);
+      expect(isSameAlbumSequence(track(4), track(3))).toBe(false);
+      expect(isSameAlbumSequence(track(12, 1), track(2, 2))).toBe(false);
+      expect(isSameAlbumSequence(track(3), track(4, 1, 'Amnesiac'))).toBe(false);
+    });
+
+    it('treats untagged tracks of an album as consecutive', () => {
+      expect(isSameAlbumSequence(track(), track())).toBe(true);
+      expect(isSameAlbumSequence(track(undefined, undefined, 'Unknown Album'), track(undefined, undefined, 'Unknown Album'))).toBe(false);
+    });
+  });
+
+  describe('planCrossfade', () => {
+    it('overlaps the audible end of one track with the audible start of the next', () => {
+      expect(
+        planCrossfade({
+          duration: 6,
+          rate: 1,
+          current: { start: 0, end: 200 },
+          next: { start: 4, end: 180 },
+        }),
+      ).toEqual({ fadeStart: 194, fadeEnd: 200, nextOffset: 4 });
+    });
+
+    it('accounts for playback speed', () => {
+      const plan = planCrossfade({
+        duration: 4,
+        rate: 2,
+        current: { start: 0, end: 100 },
+        next: { start: 0, end: 100 },
+      });
+      expect(plan).toEqual({ fadeStart: 92, fadeEnd: 100, nextOffset: 0 });
+    });
+
+    it('shortens the fade for short tracks and skips it when too short', () => {
+      expect(
+        planCrossfade({
+          duration: 10,
+          rate: 1,
+          current: { start: 1, end: 9 },
+          next: { start: 0, end: 60 },
+        }),
+      ).toEqual({ fadeStart: 5, fadeEnd: 9, nextOffset: 0 });
+      expect(
+        planCrossfade({
+          duration: 10,
+          rate: 1,
+          current: { start: 0, end: 60 },
+          next: { start: 0, end: 0.5 },
+        }),
+      ).toBeNull();
+    });
+  });
+});
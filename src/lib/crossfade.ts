@@ -0,0 +1,120 @@
+// Crossfade planning: equal-power gain curves, overlap placement around
+// leading/trailing silence, and when to leave a transition alone
+
+import type { AudibleRange, Song } from "../types";
+import { getAlbumId } from "./albums";
+
+// Anything quieter than this counts as silence (about -50 dBFS, the same
+// threshold the desktop loudness analysis uses)
+const SILENCE_THRESHOLD = 10 ** (-50 / 20);
+
+// Shorter overlaps sound like a glitch rather than a blend
+export const MIN_CROSSFADE_DURATION = 0.5;
+
+// Points in the gain curves handed to setValueCurveAtTime
+const CURVE_STEPS = 128;
+
+export interface CrossfadePlan {
+  fadeStart: number; // Position in the current track to start fading at
+  fadeEnd: number; // Position in the current track where it goes silent
+  nextOffset: number; // Position to start the next track from
+}
+
+/**
+ * Gain curve for one side of an equal-power crossfade
+ * sin²(x) + cos²(x) = 1, so the summed power stays constant and the blend
+ * doesn't dip in loudness halfway through like a linear fade does.
+ */
+export function equalPowerCurve(
+  direction: "in" | "out",
+  steps = CURVE_STEPS,
+): Float32Array {
+  const curve = new Float32Array(steps);
+  for (let i = 0; i < steps; i++) {
+    const angle = (i / (steps - 1)) * (Math.PI / 2);
+    curve[i] = direction === "in" ? Math.sin(angle) : Math.cos(angle);
+  }
+  return curve;
+}
+
+interface SampleSource {
+  sampleRate: number;
+  length: number;
+  numberOfChannels: number;
+  getChannelData(channel: number): Float32Array;
+}
+
+function isAudibleFrame(channels: Float32Array[], frame: number): boolean {
+  return channels.some((data) => Math.abs(data[frame]) > SILENCE_THRESHOLD);
+}
+
+/**
+ * Find where the audible part of a decoded track starts and ends
+ * Desktop songs have this measured natively on import; this covers the ones
+ * that don't (web imports). Returns null for a track that is silent throughout.
+ */
+export function findAudibleRange(buffer: SampleSource): AudibleRange | null {
+  const channels = Array.from({ length: buffer.numberOfChannels }, (_, i) =>
+    buffer.getChannelData(i),
+  );
+
+  let first = 0;
+  while (first < buffer.length && !isAudibleFrame(channels, first)) first++;
+  if (first === buffer.length) return null;
+
+  let last = buffer.length - 1;
+  while (last > first && !isAudibleFrame(channels, last)) last--;
+
+  return {
+    start: first / buffer.sampleRate,
+    end: (last + 1) / buffer.sampleRate,
+  };
+}
+
+/**
+ * Whether `next` is the track after `current` on the same album
+ * Those are meant to flow into each other, so they aren't crossfaded.
+ * Untagged track numbers count as consecutive, the queue already put them
+ * next to each other.
+ */
+export function isSameAlbumSequence(current: Song, next: Song): boolean {
+  if (current.album === "Unknown Album") return false;
+  if (getAlbumId(current) !== getAlbumId(next)) return false;
+  if (current.trackNumber === undefined || next.trackNumber === undefined) {
+    return true;
+  }
+
+  const currentDisc = current.discNumber ?? 1;
+  const nextDisc = next.discNumber ?? 1;
+  if (nextDisc === currentDisc) {
+    return next.trackNumber === current.trackNumber + 1;
+  }
+  return nextDisc === currentDisc + 1 && next.trackNumber === 1;
+}
+
+/**
+ * Place the overlap so the current track fades out over its last audible
+ * seconds while the next one fades in from its first audible sample
+ * The fade is shortened to half of either track's audible length; returns
+ * null when that leaves too little to blend.
+ */
+export function planCrossfade(options: {
+  duration: number;
+  rate: number;
+  current: AudibleRange;
+  next: AudibleRange;
+}): CrossfadePlan | null {
+  const { duration, rate, current, next } = options;
+  const fadeDuration = Math.min(
+    duration,
+    (current.end - current.start) / rate / 2,
+    (next.end - next.start) / rate / 2,
+  );
+  if (!(fadeDuration >= MIN_CROSSFADE_DURATION)) return null;
+
+  return {
+    fadeStart: current.end - fadeDuration * rate,
+    fadeEnd: current.end,
+    nextOffset: next.start,
+  };
+}
@@ -1,6 +1,22 @@
 import { openDB } from "idb";
 import type { DBSchema, IDBPDatabase } from "idb";
-import type { Song, Playlist, PlayerState } from "../types";
+import type {
+  Song,
+  Playlist,
+  PlayerState,
+  FolderHandleRecord,
+  PlayRecord,
+  ArtworkRecord,
+} from "../types";
+import {
+  buildSearchEntry,
+  tokenKey,
+  DEFAULT_FIELDS,
+  type SearchClause,
+  type SearchEntry,
+} from "./searchQuery";
+import { backfillFromFileName } from "./songMetadata";
+import { applyPlayToSong } from "./playStats";
 
 interface VinylDB extends DBSchema {
   songs: {
@@ -25,17 +41,42 @@ interface VinylDB extends DBSchema {
     key: string;
     value: PlayerState;
   };
+  searchIndex: {
+    key: string;
+    value: SearchEntry;
+    indexes: {
+      "by-token": string;
+      "by-year": number;
+      "by-duration": number;
+    };
+  };
+  folderHandles: {
+    key: string;
+    value: FolderHandleRecord;
+  };
+  plays: {
+    key: string;
+    value: PlayRecord;
+    indexes: {
+      "by-song": string;
+      "by-playedAt": number;
+    };
+  };
+  artwork: {
+    key: string;
+    value: ArtworkRecord;
+  };
 }
 
 const DB_NAME = "vinyl-music-player";
-const DB_VERSION = 2;
+const DB_VERSION = 7;
 
 let dbPromise: Promise<IDBPDatabase<VinylDB>> | null = null;
 
 export async function getDB(): Promise<IDBPDatabase<VinylDB>> {
   if (!dbPromise) {
     dbPromise = openDB<VinylDB>(DB_NAME, DB_VERSION, {
-      upgrade(db) {
+      async upgrade(db, oldVersion, _newVersion, transaction) {
         // Songs store
         if (!db.objectStoreNames.contains("songs")) {
           const songStore = db.createObjectStore("songs", { keyPath: "id" });
@@ -64,6 +105,52 @@ export async function getDB(): Promise<IDBPDatabase<VinylDB>> {
         if (db.objectStoreNames.contains("audioBlobs" as never)) {
           db.deleteObjectStore("audioBlobs" as never);
         }
+
+        // Search index store (one entry per song, tokens as "field:token")
+        if (!db.objectStoreNames.contains("searchIndex")) {
+          const searchStore = db.createObjectStore("searchIndex", {
+            keyPath: "id",
+          });
+          searchStore.createIndex("by-token", "tokens", { multiEntry: true });
+          searchStore.createIndex("by-year", "year");
+          searchStore.createIndex("by-duration", "duration");
+
+          // Index songs imported before search existed
+          let cursor = await transaction.objectStore("songs").openCursor();
+          while (cursor) {
+            await searchStore.put(buildSearchEntry(cursor.value));
+            cursor = await cursor.continue();
+          }
+        }
+
+        // v4 added track/disc numbers, composer and stream properties. Fill
+        // what the file name gives away; tags are re-read once files are reachable
+        if (oldVersion > 0 && oldVersion < 4) {
+          let cursor = await transaction.objectStore("songs").openCursor();
+          while (cursor) {
+            await cursor.update(backfillFromFileName(cursor.value));
+            cursor = await cursor.continue();
+          }
+        }
+
+        // Web folder handles (File System Access API), kept across reloads
+        if (!db.objectStoreNames.contains("folderHandles")) {
+          db.createObjectStore("folderHandles", { keyPath: "id" });
+        }
+
+        // Play history, one record per listen
+        if (!db.objectStoreNames.contains("plays")) {
+          const playStore = db.createObjectStore("plays", { keyPath: "id" });
+          playStore.createIndex("by-song", "songId");
+          playStore.createIndex("by-playedAt", "playedAt");
+        }
+
+        // Cover art keyed by content hash. Songs stored with inline data URLs
+        // are moved over after startup (see useSongs), since hashing and
+        // resizing can't run inside an upgrade transaction
+        if (!db.objectStoreNames.contains("artwork")) {
+          db.createObjectStore("artwork", { keyPath: "id" });
+        }
       },
     });
   }
@@ -71,9 +158,9 @@ export async function getDB(): Promise<IDBPDatabase<VinylDB>> {
 }
 
 // Song operations
+// Songs and their search entries are always written together
 export async function addSong(song: Song): Promise<void> {
-  const db = await getDB();
-  await db.put("songs", song);
+  await addSongs([song]);
 }
 
 // Batch add multiple songs in a single transaction (more efficient for imports)
@@ -81,10 +168,13 @@ export async function addSongs(songs: Song[]): Promise<void> {
   if (songs.length === 0) return;
   
   const db = await getDB();
-  const tx = db.transaction("songs", "readwrite");
+  const tx = db.transaction(["songs", "searchIndex"], "readwrite");
+  const songStore = tx.objectStore("songs");
+  const searchStore = tx.objectStore("searchIndex");
   
   await Promise.all([
-    ...songs.map(song => tx.store.put(song)),
+    ...songs.map(song => songStore.put(song)),
+    ...songs.map(song => searchStore.put(buildSearchEntry(song))),
     tx.done
   ]);
 }
@@ -101,13 +191,88 @@ export async function getAllSongs(): Promise<Song[]> {
 
 export async function deleteSong(id: string): Promise<void> {
   const db = await getDB();
-  await db.delete("songs", id);
+  const tx = db.transaction(["songs", "searchIndex"], "readwrite");
+  await Promise.all([
+    tx.objectStore("songs").delete(id),
+    tx.objectStore("searchIndex").delete(id),
+    tx.done,
+  ]);
 }
 
 // Update song metadata
 export async function updateSong(song: Song): Promise<void> {
+  await addSongs([song]);
+}
+
+// Search operations
+
+// Ids of songs with a word starting with `prefix` in the clause's fields
+async function getIdsByTokenPrefix(
+  index: ReturnType<typeof getTokenIndex>,
+  clause: Extract<SearchClause, { kind: "text" }>,
+  prefix: string,
+): Promise<Set<string>> {
+  const fields = clause.field ? [clause.field] : DEFAULT_FIELDS;
+  const keys = await Promise.all(
+    fields.map((field) => {
+      const key = tokenKey(field, prefix);
+      return index.getAllKeys(IDBKeyRange.bound(key, key + "\uffff"));
+    }),
+  );
+  return new Set(keys.flat());
+}
+
+function getTokenIndex(db: IDBPDatabase<VinylDB>) {
+  return db.transaction("searchIndex").store.index("by-token");
+}
+
+async function getClauseIds(
+  db: IDBPDatabase<VinylDB>,
+  clause: SearchClause,
+): Promise<Set<string>> {
+  if (clause.kind === "range") {
+    const { lower, upper, lowerOpen, upperOpen } = clause;
+    const range =
+      lower !== undefined && upper !== undefined
+        ? IDBKeyRange.bound(lower, upper, lowerOpen, upperOpen)
+        : lower !== undefined
+          ? IDBKeyRange.lowerBound(lower, lowerOpen)
+          : IDBKeyRange.upperBound(upper, upperOpen);
+    const indexName = clause.field === "year" ? "by-year" : "by-duration";
+    return new Set(await db.getAllKeysFromIndex("searchIndex", indexName, range));
+  }
+
+  // Every word has to match (as a prefix) somewhere in the clause's fields
+  const index = getTokenIndex(db);
+  const perToken = await Promise.all(
+    clause.tokens.map((token) => getIdsByTokenPrefix(index, clause, token)),
+  );
+  return intersect(perToken);
+}
+
+function intersect(sets: Set<string>[]): Set<string> {
+  const [smallest, ...rest] = [...sets].sort((a, b) => a.size - b.size);
+  if (!smallest) return new Set();
+  return new Set([...smallest].filter((id) => rest.every((set) => set.has(id))));
+}
+
+/**
+ * Ids of songs matching all clauses, resolved entirely from the search index
+ */
+export async function searchSongIds(clauses: SearchClause[]): Promise<string[]> {
   const db = await getDB();
-  await db.put("songs", song);
+
+  const include = clauses.filter((clause) => !clause.negate);
+  const exclude = clauses.filter((clause) => clause.negate);
+
+  const [included, ...excluded] = await Promise.all([
+    include.length > 0
+      ? Promise.all(include.map((clause) => getClauseIds(db, clause))).then(intersect)
+      : db.getAllKeys("searchIndex").then((ids) => new Set(ids)),
+    ...exclude.map((clause) => getClauseIds(db, clause)),
+  ]);
+
+  return [...included].filter((id) => !excluded.some((set) => set.has(id)));
 }
 
 // Playlist operations
@@ -137,6 +302,91 @@ export async function updatePlaylist(playlist: Playlist): Promise<void> {
   await db.put("playlists", playlist);
 }
 
+// Folder handle operations (web)
+
+export async function getAllFolderHandles(): Promise<FolderHandleRecord[]> {
+  const db = await getDB();
+  return db.getAll("folderHandles");
+}
+
+export async function addFolderHandle(record: FolderHandleRecord): Promise<void> {
+  const db = await getDB();
+  await db.put("folderHandles", record);
+}
+
+export async function deleteFolderHandle(id: string): Promise<void> {
+  const db = await getDB();
+  await db.delete("folderHandles", id);
+}
+
+// Artwork operations
+
+export async function getArtwork(id: string): Promise<ArtworkRecord | undefined> {
+  const db = await getDB();
+  return db.get("artwork", id);
+}
+
+export async function hasArtwork(id: string): Promise<boolean> {
+  const db = await getDB();
+  return (await db.getKey("artwork", id)) !== undefined;
+}
+
+export async function putArtwork(record: ArtworkRecord): Promise<void> {
+  const db = await getDB();
+  await db.put("artwork", record);
+}
+
+// Delete artwork added before `before` that isn't in keepIds. Returns the
+// number removed
+export async function pruneArtwork(
+  keepIds: Set<string>,
+  before: number,
+): Promise<number> {
+  const db = await getDB();
+  const tx = db.transaction("artwork", "readwrite");
+  let removed = 0;
+  let cursor = await tx.store.openCursor();
+  while (cursor) {
+    if (!keepIds.has(cursor.key) && cursor.value.addedAt < before) {
+      await cursor.delete();
+      removed++;
+    }
+    cursor = await cursor.continue();
+  }
+  await tx.done;
+  return removed;
+}
+
+// Play history operations
+
+// Log a listen and update the song's counters in one transaction. Returns the
+// updated song, or undefined if it was deleted meanwhile
+export async function recordPlay(play: PlayRecord): Promise<Song | undefined> {
+  const db = await getDB();
+  const tx = db.transaction(["plays", "songs"], "readwrite");
+  await tx.objectStore("plays").put(play);
+
+  const songStore = tx.objectStore("songs");
+  const song = await songStore.get(play.songId);
+  let updated: Song | undefined;
+  if (song) {
+    updated = applyPlayToSong(song, play);
+    await songStore.put(updated);
+  }
+  await tx.done;
+  return updated;
+}
+
+// Listens at or after a time, oldest first
+export async function getPlaysSince(since: number): Promise<PlayRecord[]> {
+  const db = await getDB();
+  return db.getAllFromIndex(
+    "plays",
+    "by-playedAt",
+    IDBKeyRange.lowerBound(since),
+  );
+}
+
 // Player state operations
 const PLAYER_STATE_KEY = "current";
 
@@ -182,14 +432,103 @@ export async function clearAllData(): Promise<void> {
   const db = await getDB();
 
   // Clear all object stores
-  const tx = db.transaction(["songs", "playlists", "playerState"], "readwrite");
+  const tx = db.transaction(
+    [
+      "songs",
+      "playlists",
+      "playerState",
+      "searchIndex",
+      "folderHandles",
+      "plays",
+      "artwork",
+    ],
+    "readwrite",
+  );
 
   await Promise.all([
     tx.objectStore("songs").clear(),
+    tx.objectStore("searchIndex").clear(),
     tx.objectStore("playlists").clear(),
     tx.objectStore("playerState").clear(),
+    tx.objectStore("folderHandles").clear(),
+    tx.objectStore("plays").clear(),
+    tx.objectStore("artwork").clear(),
+  ]);
+
+  await tx.done;
+}
+
+// Library contents covered by a backup
+export interface LibraryRecords {
+  songs: Song[];
+  playlists: Playlist[];
+  playerState: PlayerState | null;
+  plays: PlayRecord[];
+  artwork: ArtworkRecord[];
+}
+
+export async function getLibraryRecords(): Promise<LibraryRecords> {
+  const db = await getDB();
+  const tx = db.transaction(
+    ["songs", "playlists", "playerState", "plays", "artwork"],
+    "readonly",
+  );
+  const [songs, playlists, playerState, plays, artwork] = await Promise.all([
+    tx.objectStore("songs").getAll(),
+    tx.objectStore("playlists").getAll(),
+    tx.objectStore("playerState").get(PLAYER_STATE_KEY),
+    tx.objectStore("plays").getAll(),
+    tx.objectStore("artwork").getAll(),
   ]);
+  await tx.done;
+  return {
+    songs,
+    playlists,
+    playerState: playerState ?? null,
+    plays,
+    artwork,
+  };
+}
 
+// Swap the library for restored records in one transaction, so a failed
+// restore leaves the current library untouched
+export async function replaceLibraryRecords(
+  records: LibraryRecords,
+): Promise<void> {
+  const db = await getDB();
+  const tx = db.transaction(
+    ["songs", "searchIndex", "playlists", "playerState", "plays", "artwork"],
+    "readwrite",
+  );
+  const songStore = tx.objectStore("songs");
+  const searchStore = tx.objectStore("searchIndex");
+  const playlistStore = tx.objectStore("playlists");
+  const playerStateStore = tx.objectStore("playerState");
+  const playStore = tx.objectStore("plays");
+  const artworkStore = tx.objectStore("artwork");
+
+  await Promise.all([
+    songStore.clear(),
+    searchStore.clear(),
+    playlistStore.clear(),
+    playerStateStore.clear(),
+    playStore.clear(),
+    artworkStore.clear(),
+  ]);
+  await Promise.all([
+    ...records.songs.flatMap((song) => [
+      songStore.put(song),
+      searchStore.put(buildSearchEntry(song)),
+    ]),
+    ...records.playlists.map((playlist) => playlistStore.put(playlist)),
+    ...records.plays.map((play) => playStore.put(play)),
+    ...records.artwork.map((artwork) => artworkStore.put(artwork)),
+    records.playerState &&
+      playerStateStore.put({
+        ...records.playerState,
+        id: PLAYER_STATE_KEY,
+      } as PlayerState & { id: string }),
+  ]);
   await tx.done;
 }
 
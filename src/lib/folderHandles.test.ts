@@ -0,0 +1,45 @@
+import { describe, it, expect } from 'vitest';
+import { matchSongsToEntries, type FolderEntry } from './folderHandles';
+import { createMockSong } from '../test/test-utils';
+
+const entry = (folderId: string, relativePath: string): FolderEntry => ({
+  folderId,
+  relativePath,
+  name: relativePath.split('/').pop()!,
+  handle: {} as FileSystemFileHandle,
+});
+
+describe('matchSongsToEntries', () => {
+  it('links songs by file name and skips ambiguous names', () => {
+    const songs = [
+      createMockSong({ id: 'a', fileName: 'Intro.mp3' }),
+      createMockSong({ id: 'b', fileName: 'track.mp3' }),
+      createMockSong({ id: 'desktop', fileName: 'x.mp3', filePath: '/music/x.mp3' }),
+    ];
+    const entries = [
+      entry('f1', 'Album/intro.mp3'),
+      entry('f1', 'One/track.mp3'),
+      entry('f1', 'Two/track.mp3'),
+      entry('f1', 'x.mp3'),
+    ];
+
+    const links = matchSongsToEntries(songs, entries, new Set(['f1']));
+    expect(Object.fromEntries(links)).toEqual({
+      a: { folderHandleId: 'f1', relativePath: 'Album/intro.mp3' },
+    });
+  });
+
+  it('relinks only songs whose file moved within a walked folder', () => {
+    const songs = [
+      createMockSong({ id: 'kept', fileName: 'a.mp3', folderHandleId: 'f1', relativePath: 'a.mp3' }),
+      createMockSong({ id: 'moved', fileName: 'b.mp3', folderHandleId: 'f1', relativePath: 'b.mp3' }),
+      createMockSong({ id: 'unwalked', fileName: 'c.mp3', folderHandleId: 'f2', relativePath: 'c.mp3' }),
+    ];
+    const entries = [entry('f1', 'a.mp3'), entry('f1', 'New/b.mp3'), entry('f1', 'Other/c.mp3')];
+
+    const links = matchSongsToEntries(songs, entries, new Set(['f1']));
+    expect(Object.fromEntries(links)).toEqual({
+      moved: { folderHandleId: 'f1', relativePath: 'New/b.mp3' },
+    });
+  });
+});
@@ -0,0 +1,224 @@
+import type { Song } from "../types";
+import { SUPPORTED_AUDIO_EXTENSIONS } from "./audioMetadata";
+import { getFolderCoverRank } from "./artwork";
+
+// File System Access API pieces missing from TypeScript's DOM lib
+type PermissionMode = { mode: "read" | "readwrite" };
+
+declare global {
+  interface Window {
+    showDirectoryPicker?: (options?: {
+      id?: string;
+      mode?: "read" | "readwrite";
+    }) => Promise<FileSystemDirectoryHandle>;
+  }
+
+  interface FileSystemHandle {
+    queryPermission?: (descriptor?: PermissionMode) => Promise<PermissionState>;
+    requestPermission?: (
+      descriptor?: PermissionMode,
+    ) => Promise<PermissionState>;
+  }
+
+  interface FileSystemDirectoryHandle {
+    values(): AsyncIterableIterator<
+      FileSystemFileHandle | FileSystemDirectoryHandle
+    >;
+  }
+}
+
+const READ: PermissionMode = { mode: "read" };
+
+// Chromium-based browsers; elsewhere folders are picked with <input webkitdirectory>
+export function supportsFolderHandles(): boolean {
+  return typeof window !== "undefined" && "showDirectoryPicker" in window;
+}
+
+// Handles the user has granted access to this session, by record id
+const grantedHandles = new Map<string, FileSystemDirectoryHandle>();
+
+export function setGrantedFolderHandle(
+  id: string,
+  handle: FileSystemDirectoryHandle | null,
+): void {
+  if (handle) {
+    grantedHandles.set(id, handle);
+  } else {
+    grantedHandles.delete(id);
+  }
+}
+
+export function isFolderGranted(id: string | undefined): boolean {
+  return !!id && grantedHandles.has(id);
+}
+
+export async function pickFolderHandle(): Promise<FileSystemDirectoryHandle | null> {
+  try {
+    const handle = await window.showDirectoryPicker?.({
+      id: "vinyl-music",
+      mode: "read",
+    });
+    return handle ?? null;
+  } catch (error) {
+    // AbortError when the picker is dismissed
+    if ((error as DOMException).name !== "AbortError") {
+      console.error("Failed to pick folder:", error);
+    }
+    return null;
+  }
+}
+
+// Current permission without prompting; "prompt" needs requestFolderPermission
+export async function queryFolderPermission(
+  handle: FileSystemDirectoryHandle,
+): Promise<PermissionState> {
+  try {
+    return (await handle.queryPermission?.(READ)) ?? "granted";
+  } catch {
+    return "denied";
+  }
+}
+
+// Must run from a user gesture (a click), or the browser rejects it
+export async function requestFolderPermission(
+  handle: FileSystemDirectoryHandle,
+): Promise<PermissionState> {
+  try {
+    return (await handle.requestPermission?.(READ)) ?? "granted";
+  } catch (error) {
+    console.error("Failed to request folder permission:", error);
+    return "denied";
+  }
+}
+
+export interface FolderEntry {
+  folderId: string;
+  // "/"-separated path below the folder, including the file name
+  relativePath: string;
+  name: string;
+  handle: FileSystemFileHandle;
+}
+
+function isAudioFileName(name: string): boolean {
+  const ext = name.toLowerCase().slice(name.lastIndexOf("."));
+  return SUPPORTED_AUDIO_EXTENSIONS.includes(ext);
+}
+
+// List audio files (and folder cover images, used when a song has no embedded
+// art) below a folder without opening them. Dot folders are skipped like the
+// desktop scanner does
+export async function walkFolderHandle(
+  folderId: string,
+  root: FileSystemDirectoryHandle,
+): Promise<FolderEntry[]> {
+  const entries: FolderEntry[] = [];
+  const pending: [FileSystemDirectoryHandle, string][] = [[root, ""]];
+
+  while (pending.length > 0) {
+    const [directory, prefix] = pending.pop()!;
+    try {
+      for await (const handle of directory.values()) {
+        if (handle.name.startsWith(".")) continue;
+        const relativePath = prefix + handle.name;
+        if (handle.kind === "directory") {
+          pending.push([handle, `${relativePath}/`]);
+        } else if (
+          isAudioFileName(handle.name) ||
+          getFolderCoverRank(handle.name) >= 0
+        ) {
+          entries.push({ folderId, relativePath, name: handle.name, handle });
+        }
+      }
+    } catch (error) {
+      console.error("Failed to read folder:", prefix || root.name, error);
+    }
+  }
+  return entries;
+}
+
+// Open a file by its path below a granted folder
+export async function resolveFolderFile(
+  folderId: string,
+  relativePath: string,
+): Promise<File | null> {
+  const root = grantedHandles.get(folderId);
+  if (!root) return null;
+
+  try {
+    const parts = relativePath.split("/");
+    let directory = root;
+    for (const part of parts.slice(0, -1)) {
+      directory = await directory.getDirectoryHandle(part);
+    }
+    const fileHandle = await directory.getFileHandle(parts[parts.length - 1]);
+    return await fileHandle.getFile();
+  } catch {
+    // Moved or deleted since it was linked
+    return null;
+  }
+}
+
+// Files from a walk, shaped like <input webkitdirectory> results so the
+// existing import and reconnect code (which groups by webkitRelativePath)
+// can use them unchanged
+export async function filesFromEntries(
+  rootName: string,
+  entries: FolderEntry[],
+): Promise<File[]> {
+  const files: File[] = [];
+  for (const entry of entries) {
+    try {
+      const file = await entry.handle.getFile();
+      Object.defineProperty(file, "webkitRelativePath", {
+        value: `${rootName}/${entry.relativePath}`,
+      });
+      files.push(file);
+    } catch (error) {
+      console.error("Failed to open file:", entry.relativePath, error);
+    }
+  }
+  return files;
+}
+
+export interface FolderLink {
+  folderHandleId: string;
+  relativePath: string;
+}
+
+// Point songs at files in walked folders, matching by file name the way
+// connectFilesToSongs does. Songs whose link still resolves are left alone, as
+// are names that appear more than once
+export function matchSongsToEntries(
+  songs: Song[],
+  entries: FolderEntry[],
+  walkedFolderIds: Set<string>,
+): Map<string, FolderLink> {
+  const existing = new Set(
+    entries.map((entry) => `${entry.folderId}:${entry.relativePath}`),
+  );
+  const byName = new Map<string, FolderEntry[]>();
+  for (const entry of entries) {
+    const name = entry.name.toLowerCase();
+    byName.set(name, [...(byName.get(name) ?? []), entry]);
+  }
+
+  const links = new Map<string, FolderLink>();
+  for (const song of songs) {
+    if (song.filePath || !song.fileName) continue;
+    if (song.folderHandleId && song.relativePath) {
+      const stillThere = existing.has(
+        `${song.folderHandleId}:${song.relativePath}`,
+      );
+      if (stillThere || !walkedFolderIds.has(song.folderHandleId)) continue;
+    }
+
+    const matches = byName.get(song.fileName.toLowerCase()) ?? [];
+    if (matches.length === 1) {
+      links.set(song.id, {
+        folderHandleId: matches[0].folderId,
+        relativePath: matches[0].relativePath,
+      });
+    }
+  }
+  return links;
+}
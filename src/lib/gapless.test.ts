@@ -0,0 +1,167 @@
+import { describe, it, expect } from 'vitest';
+import {
+  parseLameGaplessInfo,
+  parseITunSMPB,
+  parseGaplessInfo,
+  resolveTrim,
+  type GaplessInfo,
+} from './gapless';
+
+function ascii(text: string): number[] {
+  return Array.from(text, (char) => char.charCodeAt(0));
+}
+
+function uint32(value: number): number[] {
+  return [(value >>> 24) & 0xff, (value >>> 16) & 0xff, (value >>> 8) & 0xff, value & 0xff];
+}
+
+// First frame of a LAME-encoded MPEG-1 Layer III joint stereo file at 44.1 kHz
+function lameFrame(frames: number, delay: number, padding: number): number[] {
+  return [
+    0xff, 0xfb, 0x90, 0x64,
+    ...new Array(32).fill(0), // Side information
+    ...ascii('Info'),
+    ...uint32(0x0f), // Frames, bytes, TOC and quality present
+    ...uint32(frames),
+    ...uint32(123456),
+    ...new Array(100).fill(0),
+    ...uint32(57),
+    ...ascii('LAME3.100'),
+    ...new Array(12).fill(0),
+    delay >> 4,
+    ((delay & 0x0f) << 4) | (padding >> 8),
+    padding & 0xff,
+    ...new Array(64).fill(0),
+  ];
+}
+
+function id3Tag(body: number[]): number[] {
+  const size = body.length;
+  return [
+    ...ascii('ID3'), 4, 0, 0,
+    (size >> 21) & 0x7f, (size >> 14) & 0x7f, (size >> 7) & 0x7f, size & 0x7f,
+    ...body,
+  ];
+}
+
+const SMPB_VALUE = ' 00000000 00000840 000001CC 00000000000AC440 00000000';
+
+function mp4File(sampleRate: number): Uint8Array {
+  return new Uint8Array([
+    ...uint32(20), ...ascii('ftypM4A '), ...uint32(0), ...ascii('M4A '),
+    ...uint32(16), ...ascii('stsd'), ...uint32(0), ...uint32(1),
+    ...uint32(36), ...ascii('mp4a'),
+    ...new Array(24).fill(0),
+    (sampleRate >> 8) & 0xff, sampleRate & 0xff, 0, 0,
+    ...uint32(24), ...ascii('----'), ...ascii('iTunSMPB'),
+    ...uint32(16 + SMPB_VALUE.length), ...ascii('data'), ...uint32(1), ...uint32(0),
+    ...ascii(SMPB_VALUE),
+  ]);
+}
+
+describe('gapless', () => {
+  describe('parseLameGaplessInfo', () => {
+    it('reads delay and padding from the LAME header', () => {
+      const bytes = new Uint8Array(lameFrame(1000, 576, 1200));
+
+      expect(parseLameGaplessInfo(bytes)).toEqual({
+        delay: 576 + 529,
+        padding: 1200 - 529,
+        totalSamples: 1000 * 1152,
+        sampleRate: 44100,
+      });
+    });
+
+    it('skips a leading ID3v2 tag', () => {
+      const bytes = new Uint8Array([...id3Tag(new Array(300).fill(0)), ...lameFrame(10, 576, 600)]);
+
+      expect(parseLameGaplessInfo(bytes)?.totalSamples).toBe(10 * 1152);
+    });
+
+    it('returns null without a Xing/Info frame', () => {
+      const bytes = new Uint8Array([0xff, 0xfb, 0x90, 0x64, ...new Array(200).fill(0)]);
+
+      expect(parseLameGaplessInfo(bytes)).toBeNull();
+    });
+
+    it('returns null for non-MPEG data', () => {
+      expect(parseLameGaplessInfo(new Uint8Array(ascii('fLaC').concat(new Array(64).fill(0))))).toBeNull();
+    });
+  });
+
+  describe('parseITunSMPB', () => {
+    it('reads delay, padding and length with the mp4a sample rate', () => {
+      expect(parseITunSMPB(mp4File(44100))).toEqual({
+        delay: 0x840,
+        padding: 0x1cc,
+        totalSamples: 0x840 + 0xac440 + 0x1cc,
+        sampleRate: 44100,
+      });
+    });
+
+    it('prefers an explicit sample rate', () => {
+      expect(parseITunSMPB(mp4File(44100), 48000)?.sampleRate).toBe(48000);
+    });
+
+    it('returns null without the tag', () => {
+      expect(parseITunSMPB(new Uint8Array(ascii('ftypM4A ')))).toBeNull();
+    });
+  });
+
+  describe('parseGaplessInfo', () => {
+    it('detects MP4 files', () => {
+      expect(parseGaplessInfo(mp4File(48000))?.sampleRate).toBe(48000);
+    });
+
+    it('reads iTunSMPB from MP3 comments when there is no LAME header', () => {
+      const comment = ascii(`COMM iTunSMPB${SMPB_VALUE}`);
+      const bytes = new Uint8Array([
+        ...id3Tag(comment),
+        0xff, 0xfb, 0x90, 0x64,
+        ...new Array(200).fill(0),
+      ]);
+
+      expect(parseGaplessInfo(bytes)).toMatchObject({ delay: 0x840, sampleRate: 44100 });
+    });
+
+    it('returns null for formats that are already sample-exact', () => {
+      const flac = new Uint8Array([...ascii('fLaC'), ...new Array(100).fill(0)]);
+
+      expect(parseGaplessInfo(flac)).toBeNull();
+    });
+  });
+
+  describe('resolveTrim', () => {
+    const info: GaplessInfo = {
+      delay: 1105,
+      padding: 671,
+      totalSamples: 1152 * 1000,
+      sampleRate: 44100,
+    };
+
+    it('trims both ends when the decoder kept everything', () => {
+      expect(resolveTrim(1152 * 1000, 44100, info)).toEqual({ start: 1105, end: 671 });
+    });
+
+    it('trims only the padding when the decoder dropped the delay', () => {
+      expect(resolveTrim(1152 * 1000 - 1105, 44100, info)).toEqual({ start: 0, end: 671 });
+    });
+
+    it('leaves already-trimmed buffers alone', () => {
+      expect(resolveTrim(1152 * 1000 - 1105 - 671, 44100, info)).toEqual({ start: 0, end: 0 });
+    });
+
+    it('scales to the output sample rate', () => {
+      const decoded = Math.round((1152 * 1000 * 48000) / 44100);
+
+      expect(resolveTrim(decoded, 48000, info)).toEqual({
+        start: Math.round((1105 * 48000) / 44100),
+        end: Math.round((671 * 48000) / 44100),
+      });
+    });
+
+    it('returns null when the length matches nothing', () => {
+      expect(resolveTrim(500000, 44100, info)).toBeNull();
+    });
+  });
+});
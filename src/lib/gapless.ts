@@ -0,0 +1,499 @@
+// Gapless playback: decoded-buffer scheduling through the shared AudioContext
+// Encoders pad the start (priming) and end of a stream to whole frames. The
+// LAME/Xing header (MP3) and iTunSMPB tag (AAC/ALAC) record how much, so the
+// padding can be cut and consecutive tracks joined without a click or gap.
+
+import type { SharedAudioData } from "./audioContext";
+
+export interface GaplessInfo {
+  delay: number; // Priming samples at the start
+  padding: number; // Padding samples at the end
+  totalSamples: number; // Full decoded length, delay and padding included
+  sampleRate: number; // Rate the counts above are expressed in
+}
+
+export interface TrimRange {
+  start: number; // Frames to drop from the start of the decoded buffer
+  end: number; // Frames to drop from the end
+}
+
+// MP3 decoders output 528 + 1 samples of their own delay on top of LAME's
+const MP3_DECODER_DELAY = 529;
+
+const MPEG_SAMPLE_RATES: Record<number, number[]> = {
+  3: [44100, 48000, 32000], // MPEG-1
+  2: [22050, 24000, 16000], // MPEG-2
+  0: [11025, 12000, 8000], // MPEG-2.5
+};
+
+// How far past the ID3 tag to look for the first frame
+const FRAME_SYNC_SEARCH_LIMIT = 64 * 1024;
+
+function readAscii(bytes: Uint8Array, offset: number, length: number): string {
+  let text = "";
+  for (let i = offset; i < offset + length && i < bytes.length; i++) {
+    text += String.fromCharCode(bytes[i]);
+  }
+  return text;
+}
+
+function readUint32(bytes: Uint8Array, offset: number): number {
+  return (
+    ((bytes[offset] << 24) |
+      (bytes[offset + 1] << 16) |
+      (bytes[offset + 2] << 8) |
+      bytes[offset + 3]) >>>
+    0
+  );
+}
+
+function indexOfAscii(bytes: Uint8Array, needle: string, from = 0): number {
+  const first = needle.charCodeAt(0);
+  outer: for (let i = from; i <= bytes.length - needle.length; i++) {
+    if (bytes[i] !== first) continue;
+    for (let j = 1; j < needle.length; j++) {
+      if (bytes[i + j] !== needle.charCodeAt(j)) continue outer;
+    }
+    return i;
+  }
+  return -1;
+}
+
+function isFrameSync(bytes: Uint8Array, offset: number): boolean {
+  return bytes[offset] === 0xff && (bytes[offset + 1] & 0xe0) === 0xe0;
+}
+
+// Size of a leading ID3v2 tag, footer included
+function id3v2Size(bytes: Uint8Array): number {
+  if (bytes.length < 10 || readAscii(bytes, 0, 3) !== "ID3") return 0;
+  const size =
+    ((bytes[6] & 0x7f) << 21) |
+    ((bytes[7] & 0x7f) << 14) |
+    ((bytes[8] & 0x7f) << 7) |
+    (bytes[9] & 0x7f);
+  const hasFooter = (bytes[5] & 0x10) !== 0;
+  return 10 + size + (hasFooter ? 10 : 0);
+}
+
+/**
+ * Read encoder delay/padding from the LAME header in an MP3's first frame
+ * Returns null for MP3s without one (CBR files from other encoders)
+ */
+export function parseLameGaplessInfo(bytes: Uint8Array): GaplessInfo | null {
+  // Find the first frame sync after any ID3v2 tags
+  let offset = id3v2Size(bytes);
+  const limit = Math.min(bytes.length - 4, offset + FRAME_SYNC_SEARCH_LIMIT);
+  while (offset < limit && !isFrameSync(bytes, offset)) {
+    offset++;
+  }
+  if (offset >= limit) return null;
+
+  const version = (bytes[offset + 1] >> 3) & 0x03;
+  const layer = (bytes[offset + 1] >> 1) & 0x03;
+  const rateIndex = (bytes[offset + 2] >> 2) & 0x03;
+  const channelMode = (bytes[offset + 3] >> 6) & 0x03;
+  const sampleRate = MPEG_SAMPLE_RATES[version]?.[rateIndex];
+  // Layer III only; the Xing frame sits after the side information
+  if (layer !== 1 || !sampleRate) return null;
+
+  const isMpeg1 = version === 3;
+  const mono = channelMode === 3;
+  const sideInfo = isMpeg1 ? (mono ? 17 : 32) : mono ? 9 : 17;
+  let pos = offset + 4 + sideInfo;
+
+  const tag = readAscii(bytes, pos, 4);
+  if (tag !== "Xing" && tag !== "Info") return null;
+  const flags = readUint32(bytes, pos + 4);
+  pos += 8;
+
+  // Without a frame count there is nothing to trim against
+  if ((flags & 0x01) === 0) return null;
+  const frames = readUint32(bytes, pos);
+  pos += 4;
+  if (flags & 0x02) pos += 4; // Byte count
+  if (flags & 0x04) pos += 100; // Seek table
+  if (flags & 0x08) pos += 4; // Quality
+
+  // LAME extension: 9-byte encoder string, then delay/padding 21 bytes in
+  const encoder = readAscii(bytes, pos, 4);
+  const isLame = encoder === "LAME" || encoder === "Lavc" || encoder === "Lavf";
+  if (!isLame || pos + 24 > bytes.length) return null;
+  const delayPadding = pos + 21;
+  const encoderDelay =
+    (bytes[delayPadding] << 4) | (bytes[delayPadding + 1] >> 4);
+  const encoderPadding =
+    ((bytes[delayPadding + 1] & 0x0f) << 8) | bytes[delayPadding + 2];
+
+  const samplesPerFrame = isMpeg1 ? 1152 : 576;
+  const delay = encoderDelay + MP3_DECODER_DELAY;
+  const padding = Math.max(0, encoderPadding - MP3_DECODER_DELAY);
+  return {
+    delay,
+    padding,
+    totalSamples: frames * samplesPerFrame,
+    sampleRate,
+  };
+}
+
+// Sample rate from the first mp4a/alac sample entry
+function mp4SampleRate(bytes: Uint8Array): number | null {
+  const stsd = indexOfAscii(bytes, "stsd");
+  if (stsd < 0) return null;
+  for (const format of ["mp4a", "alac"]) {
+    const type = indexOfAscii(bytes, format, stsd);
+    // The rate is a 16.16 fixed-point field 28 bytes after the format code
+    if (type >= 4 && type + 30 <= bytes.length) {
+      const rate = (bytes[type + 28] << 8) | bytes[type + 29];
+      if (rate > 0) return rate;
+    }
+  }
+  return null;
+}
+
+/**
+ * Read encoder delay/padding from an iTunSMPB tag (iTunes AAC, also found in
+ * some MP3 comments). The value is hex: " 00000000 delay padding samples ..."
+ */
+export function parseITunSMPB(
+  bytes: Uint8Array,
+  sampleRate?: number,
+): GaplessInfo | null {
+  const tag = indexOfAscii(bytes, "iTunSMPB");
+  if (tag < 0) return null;
+
+  const text = readAscii(bytes, tag + 8, 256);
+  const match =
+    /\s*[0-9A-Fa-f]{8}\s+([0-9A-Fa-f]{8})\s+([0-9A-Fa-f]{8})\s+([0-9A-Fa-f]{16})/.exec(
+      text,
+    );
+  if (!match) return null;
+
+  const rate = sampleRate ?? mp4SampleRate(bytes);
+  if (!rate) return null;
+
+  const delay = parseInt(match[1], 16);
+  const padding = parseInt(match[2], 16);
+  const samples = parseInt(match[3], 16);
+  if (!samples) return null;
+
+  return {
+    delay,
+    padding,
+    totalSamples: delay + samples + padding,
+    sampleRate: rate,
+  };
+}
+
+/**
+ * Read gapless info from a file's bytes, whatever the container
+ * FLAC, Vorbis, Opus and WAV are already sample-exact and return null
+ */
+export function parseGaplessInfo(bytes: Uint8Array): GaplessInfo | null {
+  if (readAscii(bytes, 4, 4) === "ftyp") {
+    return parseITunSMPB(bytes);
+  }
+
+  const lame = parseLameGaplessInfo(bytes);
+  if (lame) return lame;
+
+  // iTunes-encoded MP3s carry iTunSMPB in an ID3 comment frame
+  const tagSize = id3v2Size(bytes);
+  if (tagSize === 0 || !isFrameSync(bytes, tagSize)) return null;
+  const version = (bytes[tagSize + 1] >> 3) & 0x03;
+  const rateIndex = (bytes[tagSize + 2] >> 2) & 0x03;
+  const rate = MPEG_SAMPLE_RATES[version]?.[rateIndex];
+  return rate ? parseITunSMPB(bytes.subarray(0, tagSize), rate) : null;
+}
+
+/**
+ * Work out how much of a decoded buffer is encoder delay/padding
+ * Browsers differ: some decoders already drop the priming samples, some drop
+ * both ends, some neither. Compare the decoded length against each case and
+ * trim whatever is left. Returns null if the length matches none of them.
+ */
+export function resolveTrim(
+  decodedLength: number,
+  outputRate: number,
+  info: GaplessInfo,
+): TrimRange | null {
+  const ratio = outputRate / info.sampleRate;
+  const delay = Math.round(info.delay * ratio);
+  const padding = Math.round(info.padding * ratio);
+  const total = Math.round(info.totalSamples * ratio);
+
+  const candidates: Array<{ length: number; trim: TrimRange }> = [
+    { length: total, trim: { start: delay, end: padding } },
+    { length: total - delay, trim: { start: 0, end: padding } },
+    { length: total - delay - padding, trim: { start: 0, end: 0 } },
+  ];
+
+  // Allow for a frame's worth of decoder disagreement
+  const tolerance = Math.ceil(1152 * ratio);
+  let best: { distance: number; trim: TrimRange } | null = null;
+  for (const candidate of candidates) {
+    const distance = Math.abs(decodedLength - candidate.length);
+    if (distance <= tolerance && (!best || distance < best.distance)) {
+      best = { distance, trim: candidate.trim };
+    }
+  }
+
+  if (!best || best.trim.start + best.trim.end >= decodedLength) return null;
+  return best.trim;
+}
+
+function trimBuffer(
+  context: BaseAudioContext,
+  buffer: AudioBuffer,
+  trim: TrimRange,
+): AudioBuffer {
+  if (trim.start === 0 && trim.end === 0) return buffer;
+
+  const length = buffer.length - trim.start - trim.end;
+  const trimmed = context.createBuffer(
+    buffer.numberOfChannels,
+    length,
+    buffer.sampleRate,
+  );
+  for (let channel = 0; channel < buffer.numberOfChannels; channel++) {
+    trimmed.copyToChannel(
+      buffer.getChannelData(channel).subarray(trim.start, trim.start + length),
+      channel,
+    );
+  }
+  return trimmed;
+}
+
+/**
+ * Decode a file into an AudioBuffer with encoder delay/padding removed
+ */
+export async function decodeGaplessBuffer(
+  context: BaseAudioContext,
+  data: ArrayBuffer,
+): Promise<AudioBuffer> {
+  const info = parseGaplessInfo(new Uint8Array(data));
+  // decodeAudioData detaches the input, so parse first
+  const buffer = await context.decodeAudioData(data);
+  if (!info) return buffer;
+
+  const trim = resolveTrim(buffer.length, buffer.sampleRate, info);
+  return trim ? trimBuffer(context, buffer, trim) : buffer;
+}
+
+export interface GaplessTrack {
+  id: string;
+  buffer: AudioBuffer;
+}
+
+interface ScheduledTrack {
+  track: GaplessTrack;
+  source: AudioBufferSourceNode;
+  startTime: number; // Context time the source starts
+  offset: number; // Track position at startTime
+}
+
+// Lead time so a source never starts in the past
+const START_LEAD = 0.05;
+
+/**
+ * Plays decoded tracks back to back with sample-accurate scheduling
+ * Sources feed the analyser of the shared chain, so the visualizer, EQ and
+ * gain stages apply exactly as they do to the <audio> element.
+ */
+export class GaplessEngine {
+  private readonly context: AudioContext;
+  private readonly output: GainNode;
+  private current: ScheduledTrack | null = null;
+  private next: ScheduledTrack | null = null;
+  private queued: GaplessTrack | null = null;
+  private paused: { track: GaplessTrack; offset: number } | null = null;
+  private rate = 1;
+
+  // Called when the queued track takes over from the current one
+  onAdvance: ((id: string) => void) | null = null;
+  // Called when the current track finishes with nothing queued after it
+  onEnded: (() => void) | null = null;
+
+  constructor(audio: SharedAudioData) {
+    this.context = audio.audioContext;
+    this.output = this.context.createGain();
+    this.output.connect(audio.analyser);
+  }
+
+  get audioContext(): AudioContext {
+    return this.context;
+  }
+
+  // Whether a track is loaded, playing or paused
+  get isActive(): boolean {
+    return this.current !== null || this.paused !== null;
+  }
+
+  get currentId(): string | null {
+    return this.loadedTrack?.id ?? null;
+  }
+
+  get queuedId(): string | null {
+    return this.queued?.id ?? null;
+  }
+
+  get duration(): number {
+    return this.loadedTrack?.buffer.duration ?? 0;
+  }
+
+  get currentTime(): number {
+    if (this.paused) return this.paused.offset;
+    if (!this.current) return 0;
+    const { startTime, offset, track } = this.current;
+    const elapsed = Math.max(0, this.context.currentTime - startTime);
+    return Math.min(track.buffer.duration, offset + elapsed * this.rate);
+  }
+
+  play(track: GaplessTrack, offset = 0): void {
+    this.stop();
+    this.restart(track, offset);
+  }
+
+  // Schedule a track to start the instant the current one ends
+  queueNext(track: GaplessTrack): void {
+    this.clearNext();
+    this.queued = track;
+    if (this.current) {
+      this.next = this.start(track, this.endTime(this.current), 0);
+    }
+  }
+
+  clearNext(): void {
+    if (this.next) this.release(this.next.source);
+    this.next = null;
+    this.queued = null;
+  }
+
+  pause(): void {
+    if (!this.current) return;
+    this.paused = { track: this.current.track, offset: this.currentTime };
+    this.release(this.current.source);
+    if (this.next) this.release(this.next.source);
+    this.current = null;
+    this.next = null;
+  }
+
+  resume(): void {
+    if (!this.paused) return;
+    this.restart(this.paused.track, this.paused.offset);
+  }
+
+  seek(time: number): void {
+    const track = this.loadedTrack;
+    if (!track) return;
+    const offset = Math.max(0, Math.min(time, track.buffer.duration));
+    if (this.paused) {
+      this.paused = { track, offset };
+    } else {
+      this.restart(track, offset);
+    }
+  }
+
+  stop(): void {
+    if (this.current) this.release(this.current.source);
+    this.clearNext();
+    this.current = null;
+    this.paused = null;
+  }
+
+  setVolume(volume: number): void {
+    this.output.gain.setValueAtTime(volume, this.context.currentTime);
+  }
+
+  // Buffer sources resample rather than time-stretch, so pitch follows speed
+  setPlaybackRate(rate: number): void {
+    if (rate === this.rate) return;
+    const offset = this.currentTime;
+    this.rate = rate;
+    if (this.current) this.restart(this.current.track, offset);
+  }
+
+  dispose(): void {
+    this.stop();
+    this.onAdvance = null;
+    this.onEnded = null;
+    this.output.disconnect();
+  }
+
+  private get loadedTrack(): GaplessTrack | null {
+    return this.current?.track ?? this.paused?.track ?? null;
+  }
+
+  // (Re)start a track at an offset, keeping whatever is queued after it
+  private restart(track: GaplessTrack, offset: number): void {
+    if (this.current) this.release(this.current.source);
+    if (this.next) this.release(this.next.source);
+    this.paused = null;
+    if (this.context.state === "suspended") {
+      this.context.resume();
+    }
+
+    this.current = this.start(
+      track,
+      this.context.currentTime + START_LEAD,
+      offset,
+    );
+    this.next = this.queued
+      ? this.start(this.queued, this.endTime(this.current), 0)
+      : null;
+  }
+
+  private endTime(scheduled: ScheduledTrack): number {
+    const remaining = scheduled.track.buffer.duration - scheduled.offset;
+    return scheduled.startTime + remaining / this.rate;
+  }
+
+  private start(
+    track: GaplessTrack,
+    when: number,
+    offset: number,
+  ): ScheduledTrack {
+    const source = this.context.createBufferSource();
+    source.buffer = track.buffer;
+    source.playbackRate.value = this.rate;
+    source.connect(this.output);
+
+    const scheduled: ScheduledTrack = {
+      track,
+      source,
+      startTime: when,
+      offset,
+    };
+    source.onended = () => this.handleEnded(scheduled);
+    source.start(when, offset);
+    return scheduled;
+  }
+
+  private handleEnded(scheduled: ScheduledTrack): void {
+    if (scheduled !== this.current) return;
+    scheduled.source.disconnect();
+
+    if (this.next) {
+      // The queued source has been playing since the boundary
+      this.current = this.next;
+      this.next = null;
+      this.queued = null;
+      this.onAdvance?.(this.current.track.id);
+      return;
+    }
+
+    // Stay loaded at the start, like an <audio> element that has ended
+    this.current = null;
+    this.paused = { track: scheduled.track, offset: 0 };
+    this.onEnded?.();
+  }
+
+  private release(source: AudioBufferSourceNode): void {
+    source.onended = null;
+    try {
+      source.stop();
+    } catch {
+      // Never started
+    }
+    source.disconnect();
+  }
+}
@@ -0,0 +1,110 @@
+import { describe, it, expect } from 'vitest';
+import {
+  createEqFilter,
+  filterResponse,
+  formatFrequency,
+  logFrequencies,
+  normalizeEqFilter,
+  parseParametricEq,
+  profileResponse,
+  toBiquadQ,
+} from './parametricEq';
+
+const AUTOEQ_PROFILE = `Preamp: -6.2 dB
+Filter 1: ON LSC Fc 105 Hz Gain 5.5 dB Q 0.70
+Filter 2: ON PK Fc 3400 Hz Gain -2.1 dB Q 1.41
+Filter 3: ON HSC Fc 10000 Hz Gain -3.0 dB Q 0.70
+`;
+
+describe('parametricEq', () => {
+  describe('parseParametricEq', () => {
+    it('reads an AutoEq ParametricEQ.txt profile', () => {
+      const { profile, skipped } = parseParametricEq(AUTOEQ_PROFILE);
+      expect(skipped).toBe(0);
+      expect(profile.preamp).toBe(-6.2);
+      expect(profile.filters.map(({ type, frequency, gain, q }) => ({ type, frequency, gain, q }))).toEqual([
+        { type: 'lowshelf', frequency: 105, gain: 5.5, q: 0.7 },
+        { type: 'peaking', frequency: 3400, gain: -2.1, q: 1.41 },
+        { type: 'highshelf', frequency: 10000, gain: -3, q: 0.7 },
+      ]);
+    });
+
+    it('handles EqualizerAPO variations', () => {
+      const { profile, skipped } = parseParametricEq(
+        [
+          '# Headphone correction',
+          'Preamp: -3 dB',
+          'Preamp: -1.5 dB',
+          'Filter: ON PK Fc 200 Hz Gain 1 dB BW Oct 1',
+          'Filter 2: OFF PK Fc 100 Hz Gain 3 dB Q 1',
+          'Filter 3: ON BP Fc 100 Hz Q 1',
+          'Filter 4: on hp fc 20 hz',
+          'Filter 5: ON NO Fc 60 Hz Q 30',
+        ].join('\r\n'),
+      );
+      expect(profile.preamp).toBe(-4.5);
+      expect(skipped).toBe(1);
+      expect(profile.filters.map((filter) => filter.type)).toEqual(['peaking', 'highpass', 'notch']);
+      // One octave is Q √2
+      expect(profile.filters[0].q).toBeCloseTo(Math.SQRT2);
+      // No Q given: Butterworth
+      expect(profile.filters[1].q).toBeCloseTo(Math.SQRT1_2);
+    });
+
+    it('rejects text without filters', () => {
+      expect(() => parseParametricEq('Preamp: -6 dB')).toThrow();
+      expect(() => parseParametricEq('not an equalizer file')).toThrow();
+    });
+  });
+
+  describe('filterResponse', () => {
+    it('boosts a peaking filter by its gain at the center frequency only', () => {
+      const peak = createEqFilter({ type: 'peaking', frequency: 1000, gain: 6, q: 1 });
+      expect(filterResponse(peak, 1000)).toBeCloseTo(6);
+      expect(filterResponse(peak, 20)).toBeCloseTo(0, 1);
+    });
+
+    it('applies shelves below or above their corner', () => {
+      const lowShelf = createEqFilter({ type: 'lowshelf', frequency: 100, gain: 6 });
+      expect(filterResponse(lowShelf, 20)).toBeCloseTo(6, 1);
+      expect(filterResponse(lowShelf, 100)).toBeCloseTo(3);
+      expect(filterResponse(lowShelf, 10000)).toBeCloseTo(0);
+
+      const highShelf = createEqFilter({ type: 'highshelf', frequency: 5000, gain: -4 });
+      expect(filterResponse(highShelf, 19000)).toBeCloseTo(-4, 1);
+      expect(filterResponse(highShelf, 50)).toBeCloseTo(0);
+    });
+
+    it('is 3 dB down at the corner of a Butterworth pass filter', () => {
+      const lowPass = createEqFilter({ type: 'lowpass', frequency: 1000, q: Math.SQRT1_2 });
+      expect(filterResponse(lowPass, 1000)).toBeCloseTo(-3.01, 1);
+      expect(filterResponse(lowPass, 50)).toBeCloseTo(0);
+      expect(filterResponse({ ...lowPass, type: 'highpass' }, 1000)).toBeCloseTo(-3.01, 1);
+    });
+
+    it('adds up filters and the preamp', () => {
+      const { profile } = parseParametricEq(AUTOEQ_PROFILE);
+      const [response] = profileResponse(profile, [3400]);
+      const expected = profile.filters.reduce((total, filter) => total + filterResponse(filter, 3400), -6.2);
+      expect(response).toBeCloseTo(expected);
+    });
+  });
+
+  it('converts pass filter Q to the decibels Web Audio expects', () => {
+    expect(toBiquadQ(createEqFilter({ type: 'lowpass', q: Math.SQRT1_2 }))).toBeCloseTo(-3.01, 1);
+    expect(toBiquadQ(createEqFilter({ type: 'peaking', q: 2 }))).toBe(2);
+  });
+
+  it('clamps filters to the supported range', () => {
+    const filter = normalizeEqFilter({ id: 'a', type: 'bandpass' as never, frequency: 5, gain: 40, q: 0 });
+    expect(filter).toEqual({ id: 'a', type: 'peaking', frequency: 20, gain: 24, q: 1 });
+  });
+
+  it('spaces frequencies on a log scale and labels them', () => {
+    const frequencies = logFrequencies(3);
+    expect(frequencies[0]).toBeCloseTo(20);
+    expect(frequencies[1]).toBeCloseTo(632.46, 1);
+    expect(frequencies[2]).toBeCloseTo(20000);
+    expect([125, 1000, 12500, 16000].map(formatFrequency)).toEqual(['125', '1K', '12.5K', '16K']);
+  });
+});
@@ -0,0 +1,305 @@
+// Parametric equalizer: the filter model, its frequency response, and import
+// of EqualizerAPO / AutoEq `ParametricEQ.txt` headphone profiles
+
+import { generateId } from "./audioMetadata";
+
+// Named after the BiquadFilterNode types they map to
+export type EqFilterType =
+  | "peaking"
+  | "lowshelf"
+  | "highshelf"
+  | "lowpass"
+  | "highpass"
+  | "notch";
+
+export const EQ_FILTER_TYPES: { key: EqFilterType; label: string }[] = [
+  { key: "peaking", label: "Peak" },
+  { key: "lowshelf", label: "Low Shelf" },
+  { key: "highshelf", label: "High Shelf" },
+  { key: "lowpass", label: "Low Pass" },
+  { key: "highpass", label: "High Pass" },
+  { key: "notch", label: "Notch" },
+];
+
+export interface EqFilter {
+  id: string;
+  type: EqFilterType;
+  frequency: number; // Hz
+  gain: number; // dB, only used by peaking and shelf filters
+  q: number; // Linear Q, as in EqualizerAPO
+}
+
+export interface EqProfile {
+  preamp: number; // dB, applied before the filters
+  filters: EqFilter[];
+}
+
+export const EQ_LIMITS = {
+  frequency: { min: 20, max: 20000 },
+  gain: { min: -24, max: 24 },
+  q: { min: 0.1, max: 30 },
+  preamp: { min: -30, max: 12 },
+};
+
+// Web Audio shelves have a fixed slope (S = 1, i.e. Q 0.71), the slope
+// AutoEq profiles use as well
+const SHELF_Q = Math.SQRT1_2;
+
+// Sample rate used to draw the response curve when no context is running
+export const DEFAULT_RESPONSE_SAMPLE_RATE = 48000;
+
+export function usesGain(type: EqFilterType): boolean {
+  return type === "peaking" || type === "lowshelf" || type === "highshelf";
+}
+
+export function usesQ(type: EqFilterType): boolean {
+  return type !== "lowshelf" && type !== "highshelf";
+}
+
+function clamp(value: number, range: { min: number; max: number }): number {
+  return Math.min(range.max, Math.max(range.min, value));
+}
+
+export function createEqFilter(overrides: Partial<EqFilter> = {}): EqFilter {
+  return normalizeEqFilter({
+    id: generateId(),
+    type: "peaking",
+    frequency: 1000,
+    gain: 0,
+    q: 1,
+    ...overrides,
+  });
+}
+
+// Keep values inside what the controls and BiquadFilterNode accept
+export function normalizeEqFilter(filter: EqFilter): EqFilter {
+  const type = EQ_FILTER_TYPES.some((t) => t.key === filter.type)
+    ? filter.type
+    : "peaking";
+  return {
+    id: filter.id || generateId(),
+    type,
+    frequency: clamp(filter.frequency || 1000, EQ_LIMITS.frequency),
+    gain: clamp(filter.gain || 0, EQ_LIMITS.gain),
+    q: clamp(filter.q || 1, EQ_LIMITS.q),
+  };
+}
+
+/**
+ * Q to set on a BiquadFilterNode for this filter
+ * Web Audio reads lowpass/highpass Q as resonance in dB, the others as a
+ * linear value.
+ */
+export function toBiquadQ(filter: EqFilter): number {
+  if (filter.type === "lowpass" || filter.type === "highpass") {
+    return 20 * Math.log10(filter.q);
+  }
+  return filter.q;
+}
+
+// Biquad coefficients as the Web Audio spec computes them (RBJ cookbook)
+function biquadCoefficients(filter: EqFilter, sampleRate: number) {
+  const frequency = Math.min(filter.frequency, sampleRate / 2 - 1);
+  const w0 = (2 * Math.PI * frequency) / sampleRate;
+  const cos = Math.cos(w0);
+  const A = 10 ** (filter.gain / 40);
+  const alpha = Math.sin(w0) / (2 * (usesQ(filter.type) ? filter.q : SHELF_Q));
+  const shelf = 2 * Math.sqrt(A) * alpha;
+
+  switch (filter.type) {
+    case "lowpass":
+      return {
+        b: [(1 - cos) / 2, 1 - cos, (1 - cos) / 2],
+        a: [1 + alpha, -2 * cos, 1 - alpha],
+      };
+    case "highpass":
+      return {
+        b: [(1 + cos) / 2, -(1 + cos), (1 + cos) / 2],
+        a: [1 + alpha, -2 * cos, 1 - alpha],
+      };
+    case "notch":
+      return {
+        b: [1, -2 * cos, 1],
+        a: [1 + alpha, -2 * cos, 1 - alpha],
+      };
+    case "lowshelf":
+      return {
+        b: [
+          A * (A + 1 - (A - 1) * cos + shelf),
+          2 * A * (A - 1 - (A + 1) * cos),
+          A * (A + 1 - (A - 1) * cos - shelf),
+        ],
+        a: [
+          A + 1 + (A - 1) * cos + shelf,
+          -2 * (A - 1 + (A + 1) * cos),
+          A + 1 + (A - 1) * cos - shelf,
+        ],
+      };
+    case "highshelf":
+      return {
+        b: [
+          A * (A + 1 + (A - 1) * cos + shelf),
+          -2 * A * (A - 1 + (A + 1) * cos),
+          A * (A + 1 + (A - 1) * cos - shelf),
+        ],
+        a: [
+          A + 1 - (A - 1) * cos + shelf,
+          2 * (A - 1 - (A + 1) * cos),
+          A + 1 - (A - 1) * cos - shelf,
+        ],
+      };
+    default:
+      return {
+        b: [1 + alpha * A, -2 * cos, 1 - alpha * A],
+        a: [1 + alpha / A, -2 * cos, 1 - alpha / A],
+      };
+  }
+}
+
+// Squared magnitude of b0 + b1·z⁻¹ + b2·z⁻² on the unit circle
+function magnitudeSquared(c: number[], w: number): number {
+  const re = c[0] + c[1] * Math.cos(w) + c[2] * Math.cos(2 * w);
+  const im = c[1] * Math.sin(w) + c[2] * Math.sin(2 * w);
+  return re * re + im * im;
+}
+
+// Gain of one filter at `frequency`, in dB
+export function filterResponse(
+  filter: EqFilter,
+  frequency: number,
+  sampleRate = DEFAULT_RESPONSE_SAMPLE_RATE,
+): number {
+  const { b, a } = biquadCoefficients(filter, sampleRate);
+  const w = (2 * Math.PI * frequency) / sampleRate;
+  return 10 * Math.log10(magnitudeSquared(b, w) / magnitudeSquared(a, w));
+}
+
+// Gain of the whole profile (preamp plus every filter) at each frequency, in dB
+export function profileResponse(
+  profile: EqProfile,
+  frequencies: number[],
+  sampleRate = DEFAULT_RESPONSE_SAMPLE_RATE,
+): number[] {
+  return frequencies.map((frequency) =>
+    profile.filters.reduce(
+      (total, filter) => total + filterResponse(filter, frequency, sampleRate),
+      profile.preamp,
+    ),
+  );
+}
+
+// Frequencies spaced evenly on a log scale across the audible range
+export function logFrequencies(
+  count: number,
+  min = EQ_LIMITS.frequency.min,
+  max = EQ_LIMITS.frequency.max,
+): number[] {
+  const ratio = Math.log(max / min);
+  return Array.from(
+    { length: count },
+    (_, i) => min * Math.exp((ratio * i) / (count - 1)),
+  );
+}
+
+// 1000 -> "1K", 12500 -> "12.5K", 125 -> "125"
+export function formatFrequency(frequency: number): string {
+  if (frequency >= 1000) {
+    return `${Math.round(frequency / 100) / 10}K`;
+  }
+  return `${Math.round(frequency)}`;
+}
+
+// EqualizerAPO filter codes; LS/HS take an optional "6dB"/"12dB" slope,
+// which Web Audio shelves can't follow
+const APO_FILTER_TYPES: Record<string, EqFilterType> = {
+  PK: "peaking",
+  PEQ: "peaking",
+  MODAL: "peaking",
+  LS: "lowshelf",
+  LSC: "lowshelf",
+  HS: "highshelf",
+  HSC: "highshelf",
+  LP: "lowpass",
+  LPQ: "lowpass",
+  HP: "highpass",
+  HPQ: "highpass",
+  NO: "notch",
+};
+
+function matchNumber(line: string, pattern: RegExp): number | undefined {
+  const match = line.match(pattern);
+  if (!match) return undefined;
+  const value = parseFloat(match[1]);
+  return Number.isFinite(value) ? value : undefined;
+}
+
+// Q of a filter given as a bandwidth in octaves
+function bandwidthToQ(octaves: number): number {
+  const ratio = 2 ** octaves;
+  return Math.sqrt(ratio) / (ratio - 1);
+}
+
+export interface ParsedParametricEq {
+  profile: EqProfile;
+  // Filter lines that use a type this equalizer doesn't have (band/all pass)
+  skipped: number;
+}
+
+/**
+ * Parse an EqualizerAPO config such as AutoEq's `ParametricEQ.txt`
+ *
+ *   Preamp: -6.2 dB
+ *   Filter 1: ON LSC Fc 105 Hz Gain 5.5 dB Q 0.70
+ *   Filter 2: ON PK Fc 3400 Hz Gain -2.1 dB Q 1.41
+ *
+ * Preamp lines add up, filters switched OFF are ignored.
+ * Throws when the text has no usable filters.
+ */
+export function parseParametricEq(text: string): ParsedParametricEq {
+  let preamp = 0;
+  let skipped = 0;
+  const filters: EqFilter[] = [];
+
+  for (const rawLine of text.split(/\r?\n/)) {
+    const line = rawLine.replace(/#.*$/, "").trim();
+
+    const preampGain = matchNumber(line, /^Preamp:\s*(-?[\d.]+)\s*dB/i);
+    if (preampGain !== undefined) {
+      preamp += preampGain;
+      continue;
+    }
+
+    const filterMatch = line.match(/^Filter\s*\d*:\s*(ON|OFF)\s+([A-Z]+)/i);
+    if (!filterMatch) continue;
+    if (filterMatch[1].toUpperCase() === "OFF") continue;
+
+    const type = APO_FILTER_TYPES[filterMatch[2].toUpperCase()];
+    const frequency = matchNumber(line, /\bFc\s+([\d.]+)\s*Hz/i);
+    if (!type || frequency === undefined) {
+      skipped++;
+      continue;
+    }
+
+    const bandwidth = matchNumber(line, /\bBW\s+Oct\s+([\d.]+)/i);
+    const q =
+      matchNumber(line, /\bQ\s+([\d.]+)/i) ??
+      (bandwidth !== undefined ? bandwidthToQ(bandwidth) : SHELF_Q);
+    filters.push(
+      createEqFilter({
+        type,
+        frequency,
+        gain: matchNumber(line, /\bGain\s+(-?[\d.]+)\s*dB/i) ?? 0,
+        q,
+      }),
+    );
+  }
+
+  if (filters.length === 0) {
+    throw new Error("No equalizer filters found in this file");
+  }
+
+  return {
+    profile: { preamp: clamp(preamp, EQ_LIMITS.preamp), filters },
+    skipped,
+  };
+}
@@ -0,0 +1,85 @@
+import { describe, it, expect } from 'vitest';
+import { PitchShifter, clampSemitones, formatSemitones, getPitchRatio } from './pitchShift';
+
+const SAMPLE_RATE = 44100;
+
+function tone(frequency: number, length: number): Float32Array {
+  const samples = new Float32Array(length);
+  for (let i = 0; i < length; i++) samples[i] = 0.5 * Math.sin((2 * Math.PI * frequency * i) / SAMPLE_RATE);
+  return samples;
+}
+
+// Run in render-quantum sized blocks like an AudioWorklet does
+function shift(input: Float32Array, ratio: number, shifter = new PitchShifter()): Float32Array {
+  const output = new Float32Array(input.length);
+  for (let i = 0; i < input.length; i += 128) {
+    shifter.process(input.subarray(i, i + 128), output.subarray(i, i + 128), ratio);
+  }
+  return output;
+}
+
+// Amplitude of one frequency over the second half, once the shifter has settled
+function amplitudeAt(samples: Float32Array, frequency: number): number {
+  const settled = samples.subarray(samples.length / 2);
+  let re = 0;
+  let im = 0;
+  for (let i = 0; i < settled.length; i++) {
+    const angle = (2 * Math.PI * frequency * i) / SAMPLE_RATE;
+    re += settled[i] * Math.cos(angle);
+    im += settled[i] * Math.sin(angle);
+  }
+  return (2 * Math.hypot(re, im)) / settled.length;
+}
+
+describe('pitchShift', () => {
+  describe('getPitchRatio', () => {
+    it('transposes by semitones', () => {
+      expect(getPitchRatio(12, 1, true)).toBeCloseTo(2);
+      expect(getPitchRatio(-12, 1, false)).toBeCloseTo(0.5);
+      expect(getPitchRatio(0, 1, true)).toBe(1);
+    });
+
+    it('undoes the pitch change of the playback rate when keeping pitch', () => {
+      expect(getPitchRatio(0, 1.5, true)).toBeCloseTo(1 / 1.5);
+      expect(getPitchRatio(0, 1.5, false)).toBe(1);
+      expect(getPitchRatio(12, 0.5, true)).toBeCloseTo(4);
+    });
+  });
+
+  it('clamps and formats semitones', () => {
+    expect(clampSemitones(15)).toBe(12);
+    expect(clampSemitones(-2.4)).toBe(-2);
+    expect(formatSemitones(3)).toBe('+3 st');
+    expect(formatSemitones(-5)).toBe('-5 st');
+    expect(formatSemitones(0)).toBe('0 st');
+  });
+
+  describe('PitchShifter', () => {
+    it('passes audio through with a fixed delay at a ratio of 1', () => {
+      const shifter = new PitchShifter();
+      const input = tone(440, 8192);
+      const output = shift(input, 1, shifter);
+      for (let i = shifter.latency; i < input.length; i += 97) {
+        expect(output[i]).toBeCloseTo(input[i - shifter.latency], 6);
+      }
+    });
+
+    it.each([
+      [2, 440],
+      [0.5, 440],
+      [2 / 3, 660],
+      [Math.pow(2, 3 / 12), 330],
+    ])('moves a tone by a ratio of %f at its level', (ratio, frequency) => {
+      const output = shift(tone(frequency, SAMPLE_RATE), ratio);
+      expect(amplitudeAt(output, frequency * ratio)).toBeGreaterThan(0.45);
+      expect(amplitudeAt(output, frequency)).toBeLessThan(0.02);
+    });
+
+    it('shifts every partial of a chord', () => {
+      const input = tone(440, SAMPLE_RATE).map((v, i) => v + 0.5 * Math.sin((2 * Math.PI * 554.37 * i) / SAMPLE_RATE));
+      const output = shift(input, Math.pow(2, 7 / 12));
+      expect(amplitudeAt(output, 659.26)).toBeGreaterThan(0.4);
+      expect(amplitudeAt(output, 830.61)).toBeGreaterThan(0.4);
+    });
+  });
+});
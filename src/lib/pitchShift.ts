@@ -0,0 +1,287 @@
+// Pitch shifting for transposition and tempo changes that keep their pitch
+// Tracks play varispeed (resampled, so pitch follows speed) and a phase
+// vocoder in an AudioWorklet moves the pitch back, or anywhere else.
+
+export const PITCH_SHIFT_PROCESSOR = "vinyl-pitch-shift";
+
+export const PITCH_LIMITS = { min: -12, max: 12 };
+
+// 2048-sample frames with 4x overlap, zero-padded 2x for finer bins: clean
+// across two octaves either way for ~35ms of latency
+const FRAME_SIZE = 2048;
+const OVERSAMPLING = 4;
+const PADDING = 2;
+
+export function clampSemitones(semitones: number): number {
+  return Math.max(
+    PITCH_LIMITS.min,
+    Math.min(PITCH_LIMITS.max, Math.round(semitones)),
+  );
+}
+
+export function semitonesToRatio(semitones: number): number {
+  return Math.pow(2, semitones / 12);
+}
+
+/**
+ * Frequency ratio the pitch shifter applies to varispeed playback
+ * Keeping pitch undoes the shift the playback rate causes.
+ */
+export function getPitchRatio(
+  semitones: number,
+  speed: number,
+  preservePitch: boolean,
+): number {
+  const ratio = semitonesToRatio(semitones);
+  return preservePitch && speed > 0 ? ratio / speed : ratio;
+}
+
+export function formatSemitones(semitones: number): string {
+  if (semitones === 0) return "0 st";
+  return `${semitones > 0 ? "+" : ""}${semitones} st`;
+}
+
+export function isPitchShiftSupported(): boolean {
+  return typeof AudioWorkletNode !== "undefined";
+}
+
+/**
+ * Streaming phase vocoder pitch shifter for one channel
+ * Moves each spectral peak with the bins around it and keeps their phases
+ * locked to the peak (Laroche & Dolson), so partials keep their shape at
+ * large ratios. Output is delayed by `latency` samples, including while the
+ * ratio is 1 and the signal passes through untouched, so switching on
+ * doesn't jump.
+ */
+export class PitchShifter {
+  readonly latency: number;
+  private readonly size: number;
+  private readonly fftSize: number;
+  private readonly step: number;
+  private readonly window: Float32Array;
+  private readonly inFifo: Float32Array;
+  private readonly outFifo: Float32Array;
+  private readonly accumulator: Float32Array;
+  private readonly real: Float32Array;
+  private readonly imag: Float32Array;
+  private readonly shiftedReal: Float32Array;
+  private readonly shiftedImag: Float32Array;
+  private readonly magnitude: Float32Array;
+  private readonly lastPhase: Float32Array;
+  private readonly rotation: Float32Array;
+  private readonly lastRotation: Float32Array;
+  private readonly peaks: Int32Array;
+  private readonly bitReverse: Uint32Array;
+  private readonly cosTable: Float32Array;
+  private readonly sinTable: Float32Array;
+  private rover: number;
+  private shifting = false;
+
+  constructor(
+    size = FRAME_SIZE,
+    oversampling = OVERSAMPLING,
+    padding = PADDING,
+  ) {
+    const fftSize = size * padding;
+    this.size = size;
+    this.fftSize = fftSize;
+    this.step = size / oversampling;
+    this.latency = size - this.step;
+    this.rover = this.latency;
+
+    const bins = fftSize / 2 + 1;
+    this.window = new Float32Array(size);
+    for (let k = 0; k < size; k++) {
+      this.window[k] = 0.5 - 0.5 * Math.cos((2 * Math.PI * k) / size);
+    }
+    this.inFifo = new Float32Array(size);
+    this.outFifo = new Float32Array(this.step);
+    this.accumulator = new Float32Array(size);
+    this.real = new Float32Array(fftSize);
+    this.imag = new Float32Array(fftSize);
+    this.shiftedReal = new Float32Array(fftSize);
+    this.shiftedImag = new Float32Array(fftSize);
+    this.magnitude = new Float32Array(bins);
+    this.lastPhase = new Float32Array(bins);
+    this.rotation = new Float32Array(bins);
+    this.lastRotation = new Float32Array(bins);
+    this.peaks = new Int32Array(bins);
+
+    const bits = Math.log2(fftSize);
+    this.bitReverse = new Uint32Array(fftSize);
+    for (let i = 0; i < fftSize; i++) {
+      let reversed = 0;
+      for (let b = 0; b < bits; b++) {
+        reversed |= ((i >> b) & 1) << (bits - 1 - b);
+      }
+      this.bitReverse[i] = reversed;
+    }
+    this.cosTable = new Float32Array(fftSize / 2);
+    this.sinTable = new Float32Array(fftSize / 2);
+    for (let i = 0; i < fftSize / 2; i++) {
+      this.cosTable[i] = Math.cos((2 * Math.PI * i) / fftSize);
+      this.sinTable[i] = Math.sin((2 * Math.PI * i) / fftSize);
+    }
+  }
+
+  // Shift `input` by `ratio` (2 = an octave up) into `output`
+  process(input: Float32Array, output: Float32Array, ratio: number): void {
+    const shift = Math.abs(ratio - 1) > 1e-4;
+    if (shift && !this.shifting) {
+      // Start from a clean slate rather than stale phases
+      this.lastPhase.fill(0);
+      this.lastRotation.fill(0);
+      this.accumulator.fill(0);
+      this.outFifo.fill(0);
+    }
+    this.shifting = shift;
+
+    for (let i = 0; i < input.length; i++) {
+      this.inFifo[this.rover] = input[i];
+      output[i] = shift
+        ? this.outFifo[this.rover - this.latency]
+        : this.inFifo[this.rover - this.latency];
+      this.rover++;
+
+      if (this.rover >= this.size) {
+        this.rover = this.latency;
+        if (shift) this.processFrame(ratio);
+        this.inFifo.copyWithin(0, this.step);
+      }
+    }
+  }
+
+  private processFrame(ratio: number): void {
+    const {
+      size,
+      fftSize,
+      step,
+      real,
+      imag,
+      shiftedReal,
+      shiftedImag,
+      magnitude,
+    } = this;
+    const half = fftSize / 2;
+    // Hops per cycle of a bin's frequency
+    const cycles = fftSize / step;
+    const expected = (2 * Math.PI) / cycles;
+
+    for (let k = 0; k < size; k++) {
+      real[k] = this.inFifo[k] * this.window[k];
+    }
+    real.fill(0, size);
+    imag.fill(0);
+    this.fft(real, imag, false);
+    // Squared is enough for finding peaks
+    for (let k = 0; k <= half; k++) {
+      magnitude[k] = real[k] * real[k] + imag[k] * imag[k];
+    }
+
+    // Peaks: louder than two bins either side
+    let peakCount = 0;
+    for (let k = 2; k <= half - 2; k++) {
+      const m = magnitude[k];
+      if (
+        m > 0 &&
+        m > magnitude[k - 1] &&
+        m >= magnitude[k + 1] &&
+        m > magnitude[k - 2] &&
+        m >= magnitude[k + 2]
+      ) {
+        this.peaks[peakCount++] = k;
+      }
+    }
+
+    shiftedReal.fill(0);
+    shiftedImag.fill(0);
+    for (let i = 0; i < peakCount; i++) {
+      const peak = this.peaks[i];
+      // Each peak owns the bins halfway to its neighbours
+      const start = i === 0 ? 0 : Math.ceil((this.peaks[i - 1] + peak) / 2);
+      const end =
+        i === peakCount - 1
+          ? half
+          : Math.ceil((peak + this.peaks[i + 1]) / 2) - 1;
+
+      // True frequency of the peak (in bins) from its phase advance
+      const phase = Math.atan2(imag[peak], real[peak]);
+      let delta = phase - this.lastPhase[peak] - peak * expected;
+      delta -= 2 * Math.PI * Math.round(delta / (2 * Math.PI));
+      const frequency = peak + (cycles * delta) / (2 * Math.PI);
+
+      // Rotate the region so its phase advances at the shifted frequency,
+      // carrying on from the peak that was nearby a frame ago
+      const rotation =
+        (this.lastRotation[peak] +
+          (2 * Math.PI * (ratio - 1) * frequency) / cycles) %
+        (2 * Math.PI);
+      const cos = Math.cos(rotation);
+      const sin = Math.sin(rotation);
+      const offset = Math.round(frequency * ratio) - peak;
+
+      for (let k = start; k <= end; k++) {
+        this.rotation[k] = rotation;
+        const target = k + offset;
+        if (target < 0 || target > half) continue;
+        shiftedReal[target] += real[k] * cos - imag[k] * sin;
+        shiftedImag[target] += real[k] * sin + imag[k] * cos;
+      }
+    }
+    if (peakCount === 0) this.rotation.fill(0);
+    this.lastRotation.set(this.rotation);
+    for (let k = 0; k <= half; k++) {
+      this.lastPhase[k] = Math.atan2(imag[k], real[k]);
+    }
+
+    // Mirror into the negative frequencies for a real output
+    shiftedImag[0] = 0;
+    shiftedImag[half] = 0;
+    for (let k = 1; k < half; k++) {
+      shiftedReal[fftSize - k] = shiftedReal[k];
+      shiftedImag[fftSize - k] = -shiftedImag[k];
+    }
+    this.fft(shiftedReal, shiftedImag, true);
+
+    // Overlap-add the frame (the padding is dropped); a squared Hann window
+    // sums to 3/8 of the overlap
+    const scale = 1 / (fftSize * (size / step) * 0.375);
+    for (let k = 0; k < size; k++) {
+      this.accumulator[k] += this.window[k] * shiftedReal[k] * scale;
+    }
+    this.outFifo.set(this.accumulator.subarray(0, step));
+    this.accumulator.copyWithin(0, step);
+    this.accumulator.fill(0, size - step);
+  }
+
+  // In-place radix-2 FFT, unscaled in both directions
+  private fft(real: Float32Array, imag: Float32Array, inverse: boolean): void {
+    const { fftSize, bitReverse, cosTable, sinTable } = this;
+    for (let i = 0; i < fftSize; i++) {
+      const j = bitReverse[i];
+      if (j > i) {
+        [real[i], real[j]] = [real[j], real[i]];
+        [imag[i], imag[j]] = [imag[j], imag[i]];
+      }
+    }
+    const sign = inverse ? 1 : -1;
+    for (let length = 2; length <= fftSize; length *= 2) {
+      const halfLength = length / 2;
+      const tableStep = fftSize / length;
+      for (let start = 0; start < fftSize; start += length) {
+        for (let k = 0; k < halfLength; k++) {
+          const wr = cosTable[k * tableStep];
+          const wi = sign * sinTable[k * tableStep];
+          const a = start + k;
+          const b = a + halfLength;
+          const tr = real[b] * wr - imag[b] * wi;
+          const ti = real[b] * wi + imag[b] * wr;
+          real[b] = real[a] - tr;
+          imag[b] = imag[a] - ti;
+          real[a] += tr;
+          imag[a] += ti;
+        }
+      }
+    }
+  }
+}
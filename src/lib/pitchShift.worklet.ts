@@ -0,0 +1,50 @@
+// AudioWorklet processor running the pitch shifter on every channel
+// Bundled on its own and loaded with audioWorklet.addModule().
+
+import { PITCH_SHIFT_PROCESSOR, PitchShifter } from "./pitchShift";
+
+// AudioWorkletGlobalScope isn't part of the DOM typings
+declare class AudioWorkletProcessor {
+  readonly port: MessagePort;
+}
+declare function registerProcessor(
+  name: string,
+  processor: new () => AudioWorkletProcessor,
+): void;
+
+class PitchShiftProcessor extends AudioWorkletProcessor {
+  private shifters: PitchShifter[] = [];
+
+  static get parameterDescriptors() {
+    return [
+      {
+        name: "ratio",
+        defaultValue: 1,
+        minValue: 0.25,
+        maxValue: 4,
+        automationRate: "k-rate",
+      },
+    ];
+  }
+
+  process(
+    inputs: Float32Array[][],
+    outputs: Float32Array[][],
+    parameters: Record<string, Float32Array>,
+  ): boolean {
+    const input = inputs[0];
+    const ratio = parameters.ratio[0];
+    outputs[0].forEach((channel, i) => {
+      const source = input[i] ?? input[0];
+      if (!source) {
+        channel.fill(0);
+        return;
+      }
+      this.shifters[i] ??= new PitchShifter();
+      this.shifters[i].process(source, channel, ratio);
+    });
+    return true;
+  }
+}
+
+registerProcessor(PITCH_SHIFT_PROCESSOR, PitchShiftProcessor);
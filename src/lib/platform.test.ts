@@ -6,7 +6,13 @@ import {
   isFirstLaunch,
   completeSetup,
   resetSetup,
+  getMediaUrl,
+  readMediaData,
+  getTranscodeCacheInfo,
+  clearTranscodeCache,
+  getSongFolders,
 } from './platform';
+import { createMockSong } from '../test/test-utils';
 
 describe('Platform utilities', () => {
   beforeEach(() => {
@@ -97,4 +103,109 @@ describe('Platform utilities', () => {
       expect(localStorage.getItem('vinyl-setup-completed')).toBeNull();
     });
   });
+
+  describe('getMediaUrl', () => {
+    it('returns null when not in Electron', () => {
+      expect(getMediaUrl('/music/song.mp3')).toBeNull();
+    });
+
+    it('returns the streaming URL from the desktop bridge', () => {
+      (window as { electron?: unknown }).electron = {
+        isElectron: true,
+        getMediaUrl: (filePath: string) =>
+          `vinyl-media://local/${encodeURIComponent(filePath)}`,
+      };
+      expect(getMediaUrl('/music/a b.mp3')).toBe(
+        'vinyl-media://local/%2Fmusic%2Fa%20b.mp3',
+      );
+    });
+  });
+
+  describe('readMediaData', () => {
+    const bytes = Uint8Array.from({ length: 10 }, (_, i) => i);
+
+    // Serves `bytes` in 4-byte ranges, like the capped desktop protocol
+    function serveInChunks(total: (end: number) => string) {
+      return vi.spyOn(globalThis, 'fetch').mockImplementation(async (_url, init) => {
+        const range = new Headers(init?.headers).get('Range') ?? '';
+        const start = Number(range.match(/bytes=(\d+)-/)?.[1]);
+        if (start >= bytes.length) return new Response(null, { status: 416 });
+        const end = Math.min(start + 4, bytes.length) - 1;
+        return new Response(bytes.slice(start, end + 1), {
+          status: 206,
+          headers: { 'Content-Range': `bytes ${start}-${end}/${total(end)}` },
+        });
+      });
+    }
+
+    beforeEach(() => {
+      (window as { electron?: unknown }).electron = {
+        isElectron: true,
+        getMediaUrl: (filePath: string) => `vinyl-media://local/${encodeURIComponent(filePath)}`,
+      };
+    });
+
+    it('returns null without the streaming protocol', async () => {
+      delete (window as { electron?: unknown }).electron;
+      expect(await readMediaData('/music/song.flac')).toBeNull();
+    });
+
+    it('joins the ranges of a file of known length', async () => {
+      const fetch = serveInChunks(() => String(bytes.length));
+      const data = await readMediaData('/music/song.flac');
+      expect(new Uint8Array(data!)).toEqual(bytes);
+      expect(fetch).toHaveBeenCalledTimes(3);
+    });
+
+    it('reads a transcode of unknown length until it runs out', async () => {
+      serveInChunks(() => '*');
+      const data = await readMediaData('/music/song.wma');
+      expect(new Uint8Array(data!)).toEqual(bytes);
+    });
+
+    it('returns null when the file cannot be served', async () => {
+      vi.spyOn(globalThis, 'fetch').mockResolvedValue(new Response('Not found', { status: 404 }));
+      expect(await readMediaData('/music/gone.flac')).toBeNull();
+    });
+  });
+
+  describe('transcode cache', () => {
+    it('returns null when not in Electron', async () => {
+      expect(await getTranscodeCacheInfo()).toBeNull();
+      expect(await clearTranscodeCache()).toBeNull();
+    });
+
+    it('returns null when the desktop shell has no cache API', async () => {
+      (window as { electron?: unknown }).electron = { isElectron: true };
+      expect(await getTranscodeCacheInfo()).toBeNull();
+    });
+
+    it('returns the cache info from the desktop bridge', async () => {
+      const info = {
+        directory: '/cache',
+        totalSize: 1024,
+        maxSize: 2048,
+        activeTranscodes: 0,
+        entries: [{ sourcePath: '/music/a.wma', size: 1024, lastAccessed: 1 }],
+      };
+      (window as { electron?: unknown }).electron = {
+        isElectron: true,
+        transcodeCache: { getInfo: vi.fn().mockResolvedValue(info) },
+      };
+      expect(await getTranscodeCacheInfo()).toEqual(info);
+    });
+  });
+
+  describe('getSongFolders', () => {
+    it('lists the top-most folders holding song files', () => {
+      const songs = [
+        createMockSong({ filePath: '/Music/Album/01.mp3' }),
+        createMockSong({ filePath: '/Music/Album/CD2/01.mp3' }),
+        createMockSong({ filePath: '/Music/Album B/01.mp3' }),
+        createMockSong({ filePath: 'C:\\Users\\me\\Music\\song.flac' }),
+        createMockSong({ filePath: undefined }),
+      ];
+      expect(getSongFolders(songs).sort()).toEqual(['/Music/Album', '/Music/Album B', 'C:\\Users\\me\\Music']);
+    });
+  });
 });
@@ -1,11 +1,25 @@
 /**
  * Platform abstraction layer
  * Allows the app to work on web browsers and Electron desktop
+ * (the Tauri shell exposes the same `window.electron` API, see src-tauri/src/bridge.js)
  */
 
+import type { Song, ReplayGain, AudibleRange, TagEdit } from "../types";
+
+// Why the desktop app refused a path: it wasn't absolute, or it's outside the
+// music folders and the files the user opened or picked
+export type PathAccessErrorCode = "INVALID_PATH" | "PATH_NOT_ALLOWED";
+
 // Types for library scan results
 export interface LibraryScanResult {
+  // Files new to the scan index (every file when there's no index)
   files: MusicFileInfo[];
+  // Indexed files rewritten since the last scan, and files found under a new path
+  changed?: MusicFileInfo[];
+  moved?: { from: string; file: MusicFileInfo }[];
+  // Indexed paths that are gone from folders that still exist
+  removed?: string[];
+  unchanged?: number;
   totalCount: number;
   folderPath?: string;
   folderStats?: {
@@ -14,50 +28,144 @@ export interface LibraryScanResult {
     exists: boolean;
   }[];
   error?: string;
+  code?: PathAccessErrorCode;
+}
+
+export interface LibraryScanProgress {
+  // "walking" lists folders (total is 0 until done), "hashing" reads new files
+  phase: "walking" | "hashing";
+  current: number;
+  total: number;
+}
+
+export interface FileStats {
+  size: number;
+  mtime: string;
+  isFile: boolean;
+  isDirectory: boolean;
+}
+
+// Title of the folder dialog and the folder it opens in
+export interface FolderPickerOptions {
+  title?: string;
+  defaultPath?: string;
 }
 
 export interface LibraryFolderResult {
   success?: boolean;
   folders?: string[];
   error?: string;
+  code?: PathAccessErrorCode;
 }
 
 // File change event from watcher
+// "move" is a remove paired with an add of the same file (by inode, or size and name);
+// "change" means the file was rewritten in place, e.g. retagged
 export interface FileChangeEvent {
-  type: "add" | "remove";
+  type: "add" | "remove" | "change" | "move";
   file: MusicFileInfo;
+  // Old path of a moved file
+  previousPath?: string;
   rootFolder: string | null;
 }
 
+// Metadata read by the native desktop reader (src-tauri/crates/vinyl-media)
+export interface NativeMetadataResult {
+  path: string;
+  // Cover art (embedded, or a cover/folder image beside the file) comes back
+  // as a data URL for the renderer to put in the artwork store
+  metadata?: Partial<Song> & { coverArt?: string };
+  error?: string;
+  code?: PathAccessErrorCode;
+}
+
+// One file for the native tag writer
+export interface TagWrite {
+  path: string;
+  edit: TagEdit;
+}
+
+// Outcome of writing tags to one file. The original is kept at backupPath
+export interface TagWriteResult {
+  path: string;
+  backupPath?: string;
+  error?: string;
+  code?: PathAccessErrorCode;
+}
+
+// Loudness measured by the native analyzer for one file
+export interface LoudnessResult {
+  path: string;
+  replayGain?: ReplayGain;
+  audibleRange?: AudibleRange;
+  error?: string;
+  code?: PathAccessErrorCode;
+}
+
 // Watcher status
 export interface WatcherStatus {
   watching: boolean;
   folders: string[];
 }
 
+// Transcode cache contents (desktop only)
+export interface TranscodeCacheEntry {
+  sourcePath: string;
+  size: number;
+  lastAccessed: number;
+}
+
+export interface TranscodeCacheInfo {
+  directory: string;
+  totalSize: number;
+  maxSize: number;
+  activeTranscodes: number;
+  // Most recently played first
+  entries: TranscodeCacheEntry[];
+}
+
+export interface OpenedFileData {
+  path: string;
+  data: Uint8Array;
+}
+
 // Types for Electron API exposed via preload
 interface ElectronReadFileResult {
   data: ArrayBuffer;
   transcoded?: boolean;
   mimeType?: string;
   error?: string;
+  code?: PathAccessErrorCode;
 }
 
 interface ElectronAPI {
   platform: string;
   isElectron: boolean;
-  openFolderPicker: () => Promise<string | null>;
+  openFolderPicker: (
+    options?: FolderPickerOptions,
+  ) => Promise<string | null>;
+  openPlaylistFile?: () => Promise<OpenedFileData | null>;
+  pickPlaylistSavePath?: (defaultName: string) => Promise<string | null>;
+  openBackupFile?: () => Promise<OpenedFileData | null>;
+  pickBackupSavePath?: (defaultName: string) => Promise<string | null>;
   scanMusicFolder: (
     folderPath: string,
-  ) => Promise<{ files?: MusicFileInfo[]; error?: string }>;
+  ) => Promise<{
+    files?: MusicFileInfo[];
+    error?: string;
+    code?: PathAccessErrorCode;
+  }>;
   readFile: (filePath: string) => Promise<ElectronReadFileResult>;
+  getMediaUrl?: (filePath: string) => string;
   fileExists: (filePath: string) => Promise<boolean>;
-  getFileStats: (filePath: string) => Promise<{
-    size: number;
-    mtime: string;
-    isFile: boolean;
-    isDirectory: boolean;
-  } | null>;
+  getFileStats: (filePath: string) => Promise<FileStats | null>;
+  getFilesStats?: (filePaths: string[]) => Promise<(FileStats | null)[]>;
+  writeTextFile?: (filePath: string, content: string) => Promise<boolean>;
+  writeBackupFile?: (
+    folderPath: string,
+    fileName: string,
+    content: string,
+  ) => Promise<boolean>;
   showItemInFolder: (filePath: string) => Promise<boolean>;
   store: {
     get: <T>(key: string) => Promise<T | undefined>;
@@ -75,6 +183,15 @@ interface ElectronAPI {
     removeFolder: (folderPath: string) => Promise<LibraryFolderResult>;
     scanFolder: (folderPath: string) => Promise<LibraryScanResult>;
     scanAllFolders: () => Promise<LibraryScanResult>;
+    resetScanIndex?: () => Promise<boolean>;
+    forgetScannedFiles?: (filePaths: string[]) => Promise<boolean>;
+    migrateSongFolders?: (folders: string[]) => Promise<boolean>;
+    readMetadata: (
+      filePaths: string[],
+      options?: { includeCoverArt?: boolean },
+    ) => Promise<NativeMetadataResult[] | null>;
+    analyzeLoudness?: (albums: string[][]) => Promise<LoudnessResult[] | null>;
+    writeTags?: (writes: TagWrite[]) => Promise<TagWriteResult[] | null>;
     // Watcher APIs
     startWatching: () => Promise<{
       success?: boolean;
@@ -84,6 +201,14 @@ interface ElectronAPI {
     stopWatching: () => Promise<{ success?: boolean; error?: string }>;
     getWatcherStatus: () => Promise<WatcherStatus>;
     onFileChange: (callback: (event: FileChangeEvent) => void) => () => void;
+    onScanProgress?: (
+      callback: (progress: LibraryScanProgress) => void,
+    ) => () => void;
+  };
+  transcodeCache?: {
+    getInfo: () => Promise<TranscodeCacheInfo>;
+    clear: () => Promise<TranscodeCacheInfo>;
+    setMaxSize: (maxSize: number) => Promise<TranscodeCacheInfo>;
   };
 }
 
@@ -145,6 +270,8 @@ export async function clearStoredData(): Promise<void> {
     } catch (error) {
       console.error("Failed to clear stored data:", error);
     }
+    // The library is gone, so the next scan has to import every file again
+    await resetLibraryScanIndex();
   }
 }
 
@@ -180,10 +307,12 @@ export async function saveStoredFolderPath(path: string): Promise<void> {
 /**
  * Open a folder picker dialog (Desktop only)
  */
-export async function openFolderPicker(): Promise<string | null> {
+export async function openFolderPicker(
+  options?: FolderPickerOptions,
+): Promise<string | null> {
   if (isElectron() && window.electron) {
     try {
-      return await window.electron.openFolderPicker();
+      return await window.electron.openFolderPicker(options);
     } catch (error) {
       console.error("Failed to open folder picker:", error);
       return null;
@@ -192,6 +321,112 @@ export async function openFolderPicker(): Promise<string | null> {
   return null;
 }
 
+/**
+ * Pick a playlist file and read its bytes (Desktop only)
+ */
+export async function openPlaylistFile(): Promise<OpenedFileData | null> {
+  if (isElectron() && window.electron?.openPlaylistFile) {
+    try {
+      return await window.electron.openPlaylistFile();
+    } catch (error) {
+      console.error("Failed to open playlist file:", error);
+      return null;
+    }
+  }
+  return null;
+}
+
+/**
+ * Pick where to save an exported playlist (Desktop only)
+ */
+export async function pickPlaylistSavePath(
+  defaultName: string,
+): Promise<string | null> {
+  if (isElectron() && window.electron?.pickPlaylistSavePath) {
+    try {
+      return await window.electron.pickPlaylistSavePath(defaultName);
+    } catch (error) {
+      console.error("Failed to open save dialog:", error);
+      return null;
+    }
+  }
+  return null;
+}
+
+/**
+ * Pick a library backup and read its bytes (Desktop only)
+ */
+export async function openBackupFile(): Promise<OpenedFileData | null> {
+  if (isElectron() && window.electron?.openBackupFile) {
+    try {
+      return await window.electron.openBackupFile();
+    } catch (error) {
+      console.error("Failed to open backup file:", error);
+      return null;
+    }
+  }
+  return null;
+}
+
+/**
+ * Pick where to save a library backup (Desktop only)
+ */
+export async function pickBackupSavePath(
+  defaultName: string,
+): Promise<string | null> {
+  if (isElectron() && window.electron?.pickBackupSavePath) {
+    try {
+      return await window.electron.pickBackupSavePath(defaultName);
+    } catch (error) {
+      console.error("Failed to open save dialog:", error);
+      return null;
+    }
+  }
+  return null;
+}
+
+/**
+ * Write a text file (Desktop only)
+ */
+export async function writeTextFile(
+  filePath: string,
+  content: string,
+): Promise<boolean> {
+  if (isElectron() && window.electron?.writeTextFile) {
+    try {
+      return await window.electron.writeTextFile(filePath, content);
+    } catch (error) {
+      console.error("Failed to write file:", error);
+      return false;
+    }
+  }
+  return false;
+}
+
+/**
+ * Write a scheduled backup into a picked folder (Desktop only)
+ * Unlike writeTextFile, the folder doesn't need a save dialog for each file.
+ */
+export async function writeBackupFile(
+  folderPath: string,
+  fileName: string,
+  content: string,
+): Promise<boolean> {
+  if (isElectron() && window.electron?.writeBackupFile) {
+    try {
+      return await window.electron.writeBackupFile(
+        folderPath,
+        fileName,
+        content,
+      );
+    } catch (error) {
+      console.error("Failed to write backup:", error);
+      return false;
+    }
+  }
+  return false;
+}
+
 /**
  * Scan a folder for music files (Desktop only)
  */
@@ -229,6 +464,24 @@ export async function fileExists(filePath: string): Promise<boolean> {
   return false;
 }
 
+/**
+ * Stat many files in one call (Desktop only)
+ * Missing files come back as null; resolves to null when unavailable
+ */
+export async function getFilesStats(
+  filePaths: string[],
+): Promise<(FileStats | null)[] | null> {
+  if (isElectron() && window.electron?.getFilesStats) {
+    try {
+      return await window.electron.getFilesStats(filePaths);
+    } catch (error) {
+      console.error("getFilesStats failed:", error);
+      return null;
+    }
+  }
+  return null;
+}
+
 /**
  * Read file data from disk (Desktop only)
  * May transcode audio files if needed for playback
@@ -260,6 +513,79 @@ export async function readFileData(filePath: string): Promise<ReadFileResult> {
   throw new Error("Cannot read file by path in web browser");
 }
 
+/**
+ * Get a streaming URL for a file path (Desktop only)
+ * Served by the vinyl-media:// protocol with Range support, so playback
+ * starts without reading the whole file into memory
+ */
+export function getMediaUrl(filePath: string): string | null {
+  if (isDesktop() && window.electron?.getMediaUrl) {
+    return window.electron.getMediaUrl(filePath);
+  }
+  return null;
+}
+
+// Final length from a Content-Range header, null while it isn't known yet
+// (a transcode still being written)
+function contentRangeTotal(header: string | null): number | null {
+  const total = header?.match(/\/(\d+)$/)?.[1];
+  return total === undefined ? null : Number(total);
+}
+
+/**
+ * Read what the player would play for a file through the streaming protocol
+ * (the transcoded copy for formats the webview can't decode), one range at a
+ * time instead of copying the whole file over IPC. Null when the protocol is
+ * unavailable or the file can't be served.
+ */
+export async function readMediaData(
+  filePath: string,
+): Promise<ArrayBuffer | null> {
+  const url = getMediaUrl(filePath);
+  if (!url) return null;
+
+  const chunks: Uint8Array[] = [];
+  let offset = 0;
+  try {
+    for (;;) {
+      const response = await fetch(url, {
+        headers: { Range: `bytes=${offset}-` },
+      });
+      // Asked past the end of a file whose length wasn't known before
+      if (response.status === 416 && offset > 0) break;
+      if (!response.ok) return null;
+
+      const chunk = new Uint8Array(await response.arrayBuffer());
+      chunks.push(chunk);
+      offset += chunk.byteLength;
+
+      // Anything but a partial response is the whole file
+      if (response.status !== 206 || chunk.byteLength === 0) break;
+      const total = contentRangeTotal(response.headers.get("Content-Range"));
+      if (total !== null && offset >= total) break;
+    }
+  } catch (error) {
+    console.error("Failed to read media:", error);
+    return null;
+  }
+
+  const data = new Uint8Array(offset);
+  let position = 0;
+  for (const chunk of chunks) {
+    data.set(chunk, position);
+    position += chunk.byteLength;
+  }
+  return data.buffer;
+}
+
+/**
+ * Get a URL the <audio> element can play for a file path
+ * Prefers the streaming protocol and falls back to a blob URL
+ */
+export async function getPlaybackUrl(filePath: string): Promise<string | null> {
+  return getMediaUrl(filePath) ?? (await getAssetUrl(filePath));
+}
+
 /**
  * Convert a file path to a playable blob URL
  * Reads the file and creates a blob URL
@@ -426,6 +752,146 @@ export async function scanAllLibraryFolders(): Promise<LibraryScanResult> {
   return { files: [], totalCount: 0, error: "Not available on web" };
 }
 
+/**
+ * Forget the desktop scan index, so the next scan reports every file as new
+ */
+export async function resetLibraryScanIndex(): Promise<boolean> {
+  if (isElectron() && window.electron?.library?.resetScanIndex) {
+    try {
+      return await window.electron.library.resetScanIndex();
+    } catch (error) {
+      console.error("Failed to reset scan index:", error);
+      return false;
+    }
+  }
+  return false;
+}
+
+/**
+ * Drop deleted songs from the desktop scan index, so the next scan imports
+ * their files again instead of counting them as unchanged
+ */
+export async function forgetScannedFiles(
+  filePaths: string[],
+): Promise<boolean> {
+  if (
+    filePaths.length > 0 &&
+    isElectron() &&
+    window.electron?.library?.forgetScannedFiles
+  ) {
+    try {
+      return await window.electron.library.forgetScannedFiles(filePaths);
+    } catch (error) {
+      console.error("Failed to update scan index:", error);
+      return false;
+    }
+  }
+  return false;
+}
+
+// Folder part of a desktop path, or null at the root
+function parentFolder(filePath: string): string | null {
+  const separator = Math.max(
+    filePath.lastIndexOf("/"),
+    filePath.lastIndexOf("\\"),
+  );
+  return separator > 0 ? filePath.slice(0, separator) : null;
+}
+
+/**
+ * Folders holding the songs' files, without those inside another listed folder
+ */
+export function getSongFolders(songs: Song[]): string[] {
+  const folders = new Set<string>();
+  for (const song of songs) {
+    const folder = song.filePath ? parentFolder(song.filePath) : null;
+    if (folder) folders.add(folder);
+  }
+
+  const isNested = (folder: string) => {
+    let parent = parentFolder(folder);
+    while (parent) {
+      if (folders.has(parent)) return true;
+      parent = parentFolder(parent);
+    }
+    return false;
+  };
+  return [...folders].filter((folder) => !isNested(folder));
+}
+
+/**
+ * Report the folders of the library's songs once after an upgrade, so songs
+ * imported before file access was limited to picked folders keep playing
+ * (Desktop only; the app ignores it afterwards)
+ */
+export async function migrateSongFolders(folders: string[]): Promise<boolean> {
+  if (isElectron() && window.electron?.library?.migrateSongFolders) {
+    try {
+      return await window.electron.library.migrateSongFolders(folders);
+    } catch (error) {
+      console.error("Failed to migrate song folders:", error);
+      return false;
+    }
+  }
+  return false;
+}
+
+/**
+ * Read tags and durations for many files natively, without loading audio data
+ * Returns null when the native reader isn't available (callers fall back to music-metadata)
+ */
+export async function readNativeMetadata(
+  filePaths: string[],
+  options: { includeCoverArt?: boolean } = {},
+): Promise<NativeMetadataResult[] | null> {
+  if (isElectron() && window.electron?.library?.readMetadata) {
+    try {
+      return await window.electron.library.readMetadata(filePaths, options);
+    } catch (error) {
+      console.error("Failed to read native metadata:", error);
+      return null;
+    }
+  }
+  return null;
+}
+
+/**
+ * Measure track and album loudness natively (Desktop only)
+ * Each inner array is one album's file paths
+ */
+export async function analyzeLoudness(
+  albums: string[][],
+): Promise<LoudnessResult[] | null> {
+  if (isElectron() && window.electron?.library?.analyzeLoudness) {
+    try {
+      return await window.electron.library.analyzeLoudness(albums);
+    } catch (error) {
+      console.error("Failed to analyze loudness:", error);
+      return null;
+    }
+  }
+  return null;
+}
+
+/**
+ * Write edited tags back to files (Desktop only)
+ * Each file is rewritten atomically and its original kept as a backup.
+ * Returns null when the native writer isn't available
+ */
+export async function writeTags(
+  writes: TagWrite[],
+): Promise<TagWriteResult[] | null> {
+  if (isElectron() && window.electron?.library?.writeTags) {
+    try {
+      return await window.electron.library.writeTags(writes);
+    } catch (error) {
+      console.error("Failed to write tags:", error);
+      return null;
+    }
+  }
+  return null;
+}
+
 // ============================================
 // File Watcher (Desktop only)
 // ============================================
@@ -502,6 +968,66 @@ export function onLibraryFileChange(
   return () => {};
 }
 
+/**
+ * Subscribe to progress of scanAllLibraryFolders
+ */
+export function onLibraryScanProgress(
+  callback: (progress: LibraryScanProgress) => void,
+): () => void {
+  if (isElectron() && window.electron?.library?.onScanProgress) {
+    return window.electron.library.onScanProgress(callback);
+  }
+  return () => {};
+}
+
+// ============================================
+// Transcode Cache (Desktop only)
+// ============================================
+
+/**
+ * Get the size, limit and contents of the transcode cache
+ */
+export async function getTranscodeCacheInfo(): Promise<TranscodeCacheInfo | null> {
+  if (isElectron() && window.electron?.transcodeCache) {
+    try {
+      return await window.electron.transcodeCache.getInfo();
+    } catch (error) {
+      console.error("Failed to get transcode cache info:", error);
+    }
+  }
+  return null;
+}
+
+/**
+ * Delete all finished transcodes
+ */
+export async function clearTranscodeCache(): Promise<TranscodeCacheInfo | null> {
+  if (isElectron() && window.electron?.transcodeCache) {
+    try {
+      return await window.electron.transcodeCache.clear();
+    } catch (error) {
+      console.error("Failed to clear transcode cache:", error);
+    }
+  }
+  return null;
+}
+
+/**
+ * Change the transcode cache size limit, evicting old files if needed
+ */
+export async function setTranscodeCacheMaxSize(
+  maxSize: number,
+): Promise<TranscodeCacheInfo | null> {
+  if (isElectron() && window.electron?.transcodeCache) {
+    try {
+      return await window.electron.transcodeCache.setMaxSize(maxSize);
+    } catch (error) {
+      console.error("Failed to set transcode cache size:", error);
+    }
+  }
+  return null;
+}
+
 // ============================================
 // Setup / First Launch (Desktop only)
 // ============================================
@@ -0,0 +1,63 @@
+import { describe, it, expect } from 'vitest';
+import { applyPlayToSong, computeListeningStats, playOutcome } from './playStats';
+import { createMockSong } from '../test/test-utils';
+import type { PlayRecord } from '../types';
+
+let nextId = 0;
+const play = (songId: string, listened: number, outcome: PlayRecord['outcome'] = 'completed'): PlayRecord => ({
+  id: `p${nextId++}`,
+  songId,
+  playedAt: 1000 + nextId,
+  listened,
+  outcome,
+  source: 'library',
+});
+
+describe('playOutcome', () => {
+  it('counts half the song or four minutes as a play', () => {
+    expect(playOutcome(100, 180)).toBe('completed');
+    expect(playOutcome(80, 180)).toBe('skipped');
+    expect(playOutcome(240, 1200)).toBe('completed');
+    expect(playOutcome(239, 1200)).toBe('skipped');
+  });
+});
+
+describe('applyPlayToSong', () => {
+  it('updates play or skip counters', () => {
+    const song = createMockSong({ playCount: 2, lastPlayedAt: 50 });
+    expect(applyPlayToSong(song, { ...play(song.id, 200), playedAt: 900 })).toMatchObject({ playCount: 3, lastPlayedAt: 900 });
+    expect(applyPlayToSong(song, play(song.id, 5, 'skipped'))).toMatchObject({ playCount: 2, skipCount: 1, lastPlayedAt: 50 });
+  });
+});
+
+describe('computeListeningStats', () => {
+  it('ranks artists, albums and tracks by completed plays', () => {
+    const songs = [
+      createMockSong({ id: 'a1', title: 'One', artist: 'Nina', album: 'Blue' }),
+      createMockSong({ id: 'a2', title: 'Two', artist: 'nina ', album: 'Blue' }),
+      createMockSong({ id: 'b1', title: 'Three', artist: 'Otis', album: 'Soul' }),
+    ];
+    const plays = [
+      play('a1', 200),
+      play('a2', 200),
+      play('b1', 200),
+      play('b1', 200),
+      play('b1', 10, 'skipped'),
+      play('gone', 300),
+    ];
+
+    const stats = computeListeningStats(plays, new Map(songs.map((s) => [s.id, s])));
+    expect(stats).toMatchObject({ totalListened: 1110, plays: 5, skips: 1 });
+    // Tied on plays, so more time listened ranks first
+    expect(stats.topArtists.map((e) => [e.label, e.plays])).toEqual([
+      ['Otis', 2],
+      ['Nina', 2],
+    ]);
+    expect(stats.topAlbums.map((e) => e.label)).toEqual(['Soul', 'Blue']);
+    expect(stats.topTracks.map((e) => [e.key, e.plays, e.listened])).toEqual([
+      ['b1', 2, 410],
+      ['a1', 1, 200],
+      ['a2', 1, 200],
+    ]);
+  });
+});
@@ -0,0 +1,146 @@
+import type { PlayRecord, Song } from "../types";
+import { getAlbumArtist, getAlbumId } from "./albums";
+
+// A listen counts as a play after half the song or four minutes, whichever
+// comes first (the usual scrobbling rule); anything shorter is a skip
+export const PLAY_THRESHOLD_SECONDS = 240;
+
+export function playOutcome(
+  listened: number,
+  duration: number,
+): PlayRecord["outcome"] {
+  const threshold =
+    duration > 0
+      ? Math.min(duration / 2, PLAY_THRESHOLD_SECONDS)
+      : PLAY_THRESHOLD_SECONDS;
+  return listened >= threshold ? "completed" : "skipped";
+}
+
+// The song's aggregate counters after one more listen
+export function applyPlayToSong(song: Song, play: PlayRecord): Song {
+  if (play.outcome === "skipped") {
+    return { ...song, skipCount: (song.skipCount ?? 0) + 1 };
+  }
+  return {
+    ...song,
+    playCount: (song.playCount ?? 0) + 1,
+    lastPlayedAt: Math.max(song.lastPlayedAt ?? 0, play.playedAt),
+  };
+}
+
+export type StatsPeriod = "week" | "month" | "year" | "all";
+
+export const STATS_PERIODS: { key: StatsPeriod; label: string }[] = [
+  { key: "week", label: "Week" },
+  { key: "month", label: "Month" },
+  { key: "year", label: "Year" },
+  { key: "all", label: "All Time" },
+];
+
+const PERIOD_DAYS: Record<Exclude<StatsPeriod, "all">, number> = {
+  week: 7,
+  month: 30,
+  year: 365,
+};
+
+// Earliest playedAt included in a period (rolling, not calendar)
+export function getPeriodStart(period: StatsPeriod, now: number): number {
+  if (period === "all") return 0;
+  return now - PERIOD_DAYS[period] * 24 * 60 * 60 * 1000;
+}
+
+export interface RankedEntry {
+  key: string;
+  label: string;
+  // Artist for albums and tracks
+  detail?: string;
+  plays: number;
+  listened: number;
+  song: Song;
+}
+
+export interface ListeningStats {
+  // Seconds heard across all listens, skips included
+  totalListened: number;
+  plays: number;
+  skips: number;
+  topArtists: RankedEntry[];
+  topAlbums: RankedEntry[];
+  topTracks: RankedEntry[];
+}
+
+function rank(
+  entries: Map<string, RankedEntry>,
+  limit: number,
+): RankedEntry[] {
+  return [...entries.values()]
+    .filter((e) => e.plays > 0)
+    .sort((a, b) => b.plays - a.plays || b.listened - a.listened)
+    .slice(0, limit);
+}
+
+// Top artists, albums and tracks by completed plays (ties broken by time
+// listened). Listens of songs no longer in the library still count towards
+// the totals
+export function computeListeningStats(
+  plays: PlayRecord[],
+  songsById: Map<string, Song>,
+  limit = 10,
+): ListeningStats {
+  const artists = new Map<string, RankedEntry>();
+  const albums = new Map<string, RankedEntry>();
+  const tracks = new Map<string, RankedEntry>();
+  const stats: ListeningStats = {
+    totalListened: 0,
+    plays: 0,
+    skips: 0,
+    topArtists: [],
+    topAlbums: [],
+    topTracks: [],
+  };
+
+  const add = (
+    map: Map<string, RankedEntry>,
+    key: string,
+    init: Omit<RankedEntry, "key" | "plays" | "listened">,
+    play: PlayRecord,
+  ) => {
+    const entry = map.get(key) ?? { ...init, key, plays: 0, listened: 0 };
+    if (play.outcome === "completed") entry.plays++;
+    entry.listened += play.listened;
+    map.set(key, entry);
+  };
+
+  for (const play of plays) {
+    stats.totalListened += play.listened;
+    if (play.outcome === "completed") stats.plays++;
+    else stats.skips++;
+
+    const song = songsById.get(play.songId);
+    if (!song) continue;
+
+    add(
+      artists,
+      song.artist.trim().toLowerCase(),
+      { label: song.artist, song },
+      play,
+    );
+    add(
+      albums,
+      getAlbumId(song),
+      { label: song.album, detail: getAlbumArtist(song), song },
+      play,
+    );
+    add(
+      tracks,
+      song.id,
+      { label: song.title, detail: song.artist, song },
+      play,
+    );
+  }
+
+  stats.topArtists = rank(artists, limit);
+  stats.topAlbums = rank(albums, limit);
+  stats.topTracks = rank(tracks, limit);
+  return stats;
+}
@@ -0,0 +1,59 @@
+import { describe, it, expect } from 'vitest';
+import { describeAdjustments, getProfileKey, matchesProfile, resolvePlaybackProfile } from './playbackProfiles';
+import { getAlbumId } from './albums';
+import { createMockSong } from '../test/test-utils';
+import type { PlaybackProfile } from '../types';
+
+function profile(overrides: Partial<PlaybackProfile>): PlaybackProfile {
+  return { id: Math.random().toString(36), target: 'song', key: '', label: '', createdAt: 0, ...overrides };
+}
+
+describe('playbackProfiles', () => {
+  const song = createMockSong({ id: 'song-1', artist: 'Thom Yorke', albumArtist: 'Various Artists', album: 'Tribute' });
+  const context = { song, playlistId: 'playlist-1', outputDevice: 'AirPods Pro' };
+
+  describe('matchesProfile', () => {
+    it('matches song, album, playlist and output device', () => {
+      expect(matchesProfile(profile({ target: 'song', key: 'song-1' }), context)).toBe(true);
+      expect(matchesProfile(profile({ target: 'album', key: getAlbumId(song) }), context)).toBe(true);
+      expect(matchesProfile(profile({ target: 'playlist', key: 'playlist-1' }), context)).toBe(true);
+      expect(matchesProfile(profile({ target: 'output', key: 'airpods pro' }), context)).toBe(true);
+      expect(matchesProfile(profile({ target: 'playlist', key: 'playlist-2' }), context)).toBe(false);
+      expect(matchesProfile(profile({ target: 'output', key: 'AirPods Pro' }), { song })).toBe(false);
+    });
+
+    it('matches an artist by track or album artist', () => {
+      expect(matchesProfile(profile({ target: 'artist', key: 'Thom Yorke' }), context)).toBe(true);
+      expect(matchesProfile(profile({ target: 'artist', key: 'various artists' }), context)).toBe(true);
+      expect(matchesProfile(profile({ target: 'artist', key: 'Radiohead' }), context)).toBe(false);
+    });
+  });
+
+  describe('resolvePlaybackProfile', () => {
+    it('takes each setting from the most specific profile that sets it', () => {
+      const profiles = [
+        profile({ target: 'output', key: 'AirPods Pro', eqPreset: 'AirPods', gain: -2 }),
+        profile({ target: 'artist', key: 'Thom Yorke', speed: 1.25, eqPreset: 'Vocal' }),
+        profile({ target: 'song', key: 'song-1', speed: 0.75 }),
+      ];
+      expect(resolvePlaybackProfile(profiles, context)).toEqual({ eqPreset: 'Vocal', speed: 0.75, gain: -2 });
+    });
+
+    it('is empty when nothing matches', () => {
+      expect(resolvePlaybackProfile([profile({ target: 'song', key: 'other', speed: 2 })], context)).toEqual({});
+    });
+  });
+
+  it('has no key for missing context', () => {
+    const untagged = createMockSong({ album: 'Unknown Album', artist: 'Unknown Artist' });
+    expect(getProfileKey('album', { song: untagged })).toBeNull();
+    expect(getProfileKey('artist', { song: untagged })).toBeNull();
+    expect(getProfileKey('playlist', { song: untagged })).toBeNull();
+    expect(getProfileKey('artist', context)).toBe('Various Artists');
+  });
+
+  it('describes adjustments', () => {
+    expect(describeAdjustments({ eqPreset: 'Vocal', speed: 1.25, gain: 3 })).toBe('EQ Vocal, 1.25x, +3 dB');
+    expect(describeAdjustments({ gain: -1.5 })).toBe('-1.5 dB');
+  });
+});
@@ -0,0 +1,120 @@
+// Playback profiles: which profiles match the playing track and how their
+// EQ preset, speed and gain offset combine
+
+import type { PlaybackProfile, PlaybackProfileTarget, Song } from "../types";
+import { getAlbumArtist, getAlbumId } from "./albums";
+
+export const PLAYBACK_PROFILE_TARGETS: {
+  key: PlaybackProfileTarget;
+  label: string;
+}[] = [
+  { key: "song", label: "Song" },
+  { key: "album", label: "Album" },
+  { key: "artist", label: "Artist" },
+  { key: "playlist", label: "Playlist" },
+  { key: "output", label: "Output Device" },
+];
+
+// Most specific first: each setting comes from the most specific matching
+// profile that sets it, so a song's speed wins over its album's
+const TARGET_PRIORITY: PlaybackProfileTarget[] = [
+  "song",
+  "album",
+  "playlist",
+  "artist",
+  "output",
+];
+
+export const PROFILE_GAIN_LIMITS = { min: -12, max: 12 };
+
+export interface PlaybackContext {
+  song: Song;
+  playlistId?: string | null;
+  outputDevice?: string | null;
+}
+
+export type PlaybackAdjustments = Pick<
+  PlaybackProfile,
+  "eqPreset" | "speed" | "gain"
+>;
+
+function sameName(a: string, b: string): boolean {
+  return a.trim().toLowerCase() === b.trim().toLowerCase();
+}
+
+/**
+ * Key a profile for `target` would need to match this context
+ * Null when there is nothing to attach to, e.g. no playlist is playing or
+ * the song has no album.
+ */
+export function getProfileKey(
+  target: PlaybackProfileTarget,
+  context: PlaybackContext,
+): string | null {
+  const { song } = context;
+  switch (target) {
+    case "song":
+      return song.id;
+    case "album":
+      return song.album === "Unknown Album" ? null : getAlbumId(song);
+    case "artist":
+      return song.artist === "Unknown Artist" ? null : getAlbumArtist(song);
+    case "playlist":
+      return context.playlistId ?? null;
+    case "output":
+      return context.outputDevice ?? null;
+  }
+}
+
+export function matchesProfile(
+  profile: PlaybackProfile,
+  context: PlaybackContext,
+): boolean {
+  switch (profile.target) {
+    // Either credit counts, so an artist's profile covers guest tracks on
+    // compilations too
+    case "artist":
+      return (
+        sameName(profile.key, context.song.artist) ||
+        sameName(profile.key, getAlbumArtist(context.song))
+      );
+    case "output":
+      return (
+        !!context.outputDevice && sameName(profile.key, context.outputDevice)
+      );
+    default:
+      return profile.key === getProfileKey(profile.target, context);
+  }
+}
+
+// Combined settings of every profile matching the context
+export function resolvePlaybackProfile(
+  profiles: PlaybackProfile[],
+  context: PlaybackContext,
+): PlaybackAdjustments {
+  const matching = profiles
+    .filter((profile) => matchesProfile(profile, context))
+    .sort(
+      (a, b) =>
+        TARGET_PRIORITY.indexOf(a.target) - TARGET_PRIORITY.indexOf(b.target),
+    );
+  const pick = <K extends keyof PlaybackAdjustments>(field: K) =>
+    matching.find((profile) => profile[field] !== undefined)?.[field];
+
+  return {
+    eqPreset: pick("eqPreset"),
+    speed: pick("speed"),
+    gain: pick("gain"),
+  };
+}
+
+// "EQ Vocal, 1.25x, -3 dB"
+export function describeAdjustments(adjustments: PlaybackAdjustments): string {
+  const parts: string[] = [];
+  if (adjustments.eqPreset) parts.push(`EQ ${adjustments.eqPreset}`);
+  if (adjustments.speed !== undefined) parts.push(`${adjustments.speed}x`);
+  if (adjustments.gain !== undefined) {
+    parts.push(`${adjustments.gain > 0 ? "+" : ""}${adjustments.gain} dB`);
+  }
+  return parts.join(", ");
+}
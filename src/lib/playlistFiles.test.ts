@@ -0,0 +1,162 @@
+import { describe, it, expect } from 'vitest';
+import {
+  decodePlaylistText,
+  getPlaylistBaseDir,
+  matchPlaylistEntries,
+  parsePlaylistFile,
+  relativePlaylistPath,
+  resolvePlaylistLocation,
+  serializePlaylist,
+} from './playlistFiles';
+import { createMockSong } from '../test/test-utils';
+
+describe('parsePlaylistFile', () => {
+  it('reads M3U entries with #EXTINF details and a #PLAYLIST name', () => {
+    const content = [
+      '\uFEFF#EXTM3U',
+      '#PLAYLIST:Road Trip',
+      '#EXTINF:215,Otis Redding - Try a Little Tenderness',
+      'Soul/01 Tenderness.mp3',
+      '#EXTVLCOPT:start-time=0',
+      '/music/plain.flac',
+      '#EXTINF:-1 tvg-id="x",Untitled Stream',
+      'http://radio.example/stream',
+    ].join('\r\n');
+
+    const parsed = parsePlaylistFile('trip.m3u8', content);
+    expect(parsed.name).toBe('Road Trip');
+    expect(parsed.entries).toEqual([
+      { location: 'Soul/01 Tenderness.mp3', duration: 215, artist: 'Otis Redding', title: 'Try a Little Tenderness' },
+      { location: '/music/plain.flac' },
+      { location: 'http://radio.example/stream', duration: undefined, title: 'Untitled Stream' },
+    ]);
+  });
+
+  it('reads PLS entries in index order and names the playlist after the file', () => {
+    const content = [
+      '[playlist]',
+      'File2=C:\\Music\\b.mp3',
+      'Title2=Second',
+      'File1=C:\\Music\\a.mp3',
+      'Length1=120',
+      'NumberOfEntries=2',
+    ].join('\n');
+
+    const parsed = parsePlaylistFile('/lists/Mix Tape.pls', content);
+    expect(parsed.name).toBe('Mix Tape');
+    expect(parsed.entries).toEqual([
+      { location: 'C:\\Music\\a.mp3', duration: 120 },
+      { location: 'C:\\Music\\b.mp3', title: 'Second' },
+    ]);
+  });
+
+  it('reads XSPF tracks with millisecond durations and encoded locations', () => {
+    const content = `<?xml version="1.0" encoding="UTF-8"?>
+      <playlist version="1" xmlns="http://xspf.org/ns/0/">
+        <title>Late Night</title>
+        <trackList>
+          <track>
+            <location>file:///home/me/Music/Nina%20Simone/Feeling%20Good.mp3</location>
+            <title>Feeling Good</title>
+            <creator>Nina Simone</creator>
+            <duration>178000</duration>
+          </track>
+          <track><location>Jazz/So%20What.flac</location></track>
+        </trackList>
+      </playlist>`;
+
+    const parsed = parsePlaylistFile('late.xspf', content);
+    expect(parsed.name).toBe('Late Night');
+    expect(parsed.entries).toEqual([
+      { location: 'file:///home/me/Music/Nina%20Simone/Feeling%20Good.mp3', title: 'Feeling Good', artist: 'Nina Simone', duration: 178 },
+      { location: 'Jazz/So What.flac', title: undefined, artist: undefined, duration: undefined },
+    ]);
+  });
+
+  it('detects the format from the content when the extension is unknown', () => {
+    expect(parsePlaylistFile('list.txt', '#EXTM3U\n/a.mp3').entries).toHaveLength(1);
+    expect(() => parsePlaylistFile('notes.txt', 'hello')).toThrow('Unsupported playlist format');
+  });
+
+  it('falls back to Windows-1252 for files that are not UTF-8', () => {
+    expect(decodePlaylistText(new Uint8Array([0x43, 0x61, 0x66, 0xe9]))).toBe('Café');
+    expect(decodePlaylistText(new TextEncoder().encode('Café'))).toBe('Café');
+  });
+});
+
+describe('playlist paths', () => {
+  it('resolves relative entries against the playlist folder', () => {
+    expect(resolvePlaylistLocation('../Soul/a.mp3', '/home/me/Music/Lists')).toBe('/home/me/Music/Soul/a.mp3');
+    expect(resolvePlaylistLocation('..\\Soul\\a.mp3', 'C:\\Music\\Lists')).toBe('C:\\Music\\Soul\\a.mp3');
+    expect(resolvePlaylistLocation('file:///C:/My%20Music/a.mp3')).toBe('C:\\My Music\\a.mp3');
+    expect(resolvePlaylistLocation('https://example.com/a.mp3')).toBeUndefined();
+  });
+
+  it('writes paths relative to the playlist folder', () => {
+    expect(relativePlaylistPath('/home/me/Music/Lists', '/home/me/Music/Soul/a.mp3')).toBe('../Soul/a.mp3');
+    expect(relativePlaylistPath('C:\\Music', 'c:\\music\\Soul\\a.mp3')).toBe('Soul\\a.mp3');
+    // Different drives can't be relative
+    expect(relativePlaylistPath('D:\\Lists', 'C:\\Music\\a.mp3')).toBe('C:\\Music\\a.mp3');
+    expect(getPlaylistBaseDir('/home/me/Lists/mix.m3u8')).toBe('/home/me/Lists');
+  });
+});
+
+describe('matchPlaylistEntries', () => {
+  const songs = [
+    createMockSong({ id: 'path', title: 'A', filePath: '/music/Soul/a.mp3' }),
+    createMockSong({ id: 'tags', title: 'Feeling Good', artist: 'Nina Simone', duration: 177, filePath: '/music/Nina/fg.mp3' }),
+    createMockSong({ id: 'live', title: 'Intro', filePath: '/music/Live/01 Intro.mp3' }),
+    createMockSong({ id: 'studio', title: 'Intro', duration: 90, filePath: '/music/Studio/01 Intro.mp3' }),
+    createMockSong({ id: 'web', title: 'Web', relativePath: 'Music/Jazz/so what.flac' }),
+  ];
+
+  it('matches by path, then tags, then file name', () => {
+    const result = matchPlaylistEntries(
+      [
+        { location: 'Soul/a.mp3' },
+        { location: '/elsewhere/feeling.mp3', title: 'feeling good', artist: 'NINA SIMONE', duration: 178 },
+        { location: 'D:\\Old\\Studio\\01 Intro.mp3' },
+        { location: 'Jazz/So What.flac' },
+        { location: 'Soul/a.mp3' },
+        { location: 'missing.mp3', title: 'Missing' },
+      ],
+      songs,
+      '/music',
+    );
+
+    expect(result.songIds).toEqual(['path', 'tags', 'studio', 'web']);
+    expect(result.unmatched).toEqual([{ location: 'missing.mp3', title: 'Missing' }]);
+  });
+
+  it('leaves ambiguous file names unmatched', () => {
+    const result = matchPlaylistEntries([{ location: '01 Intro.mp3' }], songs);
+    expect(result.songIds).toEqual([]);
+  });
+});
+
+describe('serializePlaylist', () => {
+  const songs = [
+    createMockSong({ title: 'Tenderness', artist: 'Otis Redding', album: 'Otis Blue', duration: 215.4, filePath: '/music/Soul/a & b.mp3' }),
+  ];
+
+  it('writes extended M3U with relative paths', () => {
+    expect(serializePlaylist('Trip', songs, { format: 'm3u8', pathMode: 'relative', baseDir: '/music/Lists' })).toBe(
+      '#EXTM3U\n#PLAYLIST:Trip\n#EXTINF:215,Otis Redding - Tenderness\n../Soul/a & b.mp3\n',
+    );
+  });
+
+  it('writes PLS with absolute paths', () => {
+    expect(serializePlaylist('Trip', songs, { format: 'pls', pathMode: 'absolute' })).toBe(
+      '[playlist]\nFile1=/music/Soul/a & b.mp3\nTitle1=Otis Redding - Tenderness\nLength1=215\nNumberOfEntries=1\nVersion=2\n',
+    );
+  });
+
+  it('writes XSPF that reads back to the same songs', () => {
+    const xspf = serializePlaylist('Trip & Back', songs, { format: 'xspf', pathMode: 'absolute' });
+    expect(xspf).toContain('<location>file:///music/Soul/a%20%26%20b.mp3</location>');
+
+    const parsed = parsePlaylistFile('trip.xspf', xspf);
+    expect(parsed.name).toBe('Trip & Back');
+    expect(matchPlaylistEntries(parsed.entries, songs).songIds).toEqual([songs[0].id]);
+  });
+});
@@ -0,0 +1,543 @@
+import type { Song } from "../types";
+import { getSongLookupKey } from "./songLookup";
+
+export type PlaylistFileFormat = "m3u" | "m3u8" | "pls" | "xspf";
+
+export const PLAYLIST_FILE_FORMATS: {
+  key: PlaylistFileFormat;
+  label: string;
+  mimeType: string;
+}[] = [
+  { key: "m3u8", label: "M3U8", mimeType: "audio/x-mpegurl" },
+  { key: "m3u", label: "M3U", mimeType: "audio/x-mpegurl" },
+  { key: "pls", label: "PLS", mimeType: "audio/x-scpls" },
+  { key: "xspf", label: "XSPF", mimeType: "application/xspf+xml" },
+];
+
+export const PLAYLIST_FILE_EXTENSIONS = [".m3u", ".m3u8", ".pls", ".xspf"];
+
+export interface PlaylistFileEntry {
+  // Path or URL as written in the file
+  location?: string;
+  title?: string;
+  artist?: string;
+  // Seconds, when the file gives a positive length
+  duration?: number;
+}
+
+export interface ParsedPlaylistFile {
+  name: string;
+  entries: PlaylistFileEntry[];
+}
+
+// Playlist files come as UTF-8 (M3U8, XSPF) or, for older M3U/PLS, Windows-1252
+export function decodePlaylistText(data: ArrayBuffer | Uint8Array): string {
+  try {
+    return new TextDecoder("utf-8", { fatal: true }).decode(data);
+  } catch {
+    return new TextDecoder("windows-1252").decode(data);
+  }
+}
+
+export function getPlaylistFileFormat(
+  fileName: string,
+  content: string,
+): PlaylistFileFormat | null {
+  const extension = fileName.split(".").pop()?.toLowerCase();
+  if (
+    extension === "m3u" ||
+    extension === "m3u8" ||
+    extension === "pls" ||
+    extension === "xspf"
+  ) {
+    return extension;
+  }
+
+  const head = content.trimStart().slice(0, 256).toLowerCase();
+  if (head.startsWith("#extm3u")) return "m3u8";
+  if (head.startsWith("[playlist]")) return "pls";
+  if (head.includes("<playlist")) return "xspf";
+  return null;
+}
+
+// "Artist - Title", the display text used by #EXTINF and PLS titles
+function splitDisplayTitle(
+  display: string,
+): Pick<PlaylistFileEntry, "title" | "artist"> {
+  const text = display.trim();
+  const separator = text.indexOf(" - ");
+  if (separator > 0) {
+    return {
+      artist: text.slice(0, separator).trim(),
+      title: text.slice(separator + 3).trim(),
+    };
+  }
+  return { title: text || undefined };
+}
+
+function positiveSeconds(value: number): number | undefined {
+  return Number.isFinite(value) && value > 0 ? value : undefined;
+}
+
+// #EXTINF:<seconds>[ key="value" ...],<display title>
+function parseExtInf(info: string): PlaylistFileEntry {
+  const comma = info.indexOf(",");
+  const seconds = parseFloat(comma >= 0 ? info.slice(0, comma) : info);
+  return {
+    duration: positiveSeconds(seconds),
+    ...(comma >= 0 ? splitDisplayTitle(info.slice(comma + 1)) : {}),
+  };
+}
+
+interface PlaylistFileContents {
+  name?: string;
+  entries: PlaylistFileEntry[];
+}
+
+function parseM3U(content: string): PlaylistFileContents {
+  const entries: PlaylistFileEntry[] = [];
+  let name: string | undefined;
+  let info: PlaylistFileEntry = {};
+
+  for (const raw of content.split(/\r?\n/)) {
+    const line = raw.trim();
+    if (!line) continue;
+    if (line.startsWith("#")) {
+      if (/^#EXTINF:/i.test(line)) {
+        info = parseExtInf(line.slice("#EXTINF:".length));
+      } else if (/^#PLAYLIST:/i.test(line)) {
+        name = line.slice("#PLAYLIST:".length).trim() || undefined;
+      }
+      continue;
+    }
+    entries.push({ ...info, location: line });
+    info = {};
+  }
+
+  return { name, entries };
+}
+
+function parsePLS(content: string): PlaylistFileEntry[] {
+  const byIndex = new Map<number, PlaylistFileEntry>();
+
+  for (const raw of content.split(/\r?\n/)) {
+    const match = /^(File|Title|Length)(\d+)\s*=(.*)$/i.exec(raw.trim());
+    if (!match) continue;
+    const [, key, index, value] = match;
+    const entry = byIndex.get(Number(index)) ?? {};
+    switch (key.toLowerCase()) {
+      case "file":
+        entry.location = value.trim();
+        break;
+      case "title":
+        Object.assign(entry, splitDisplayTitle(value));
+        break;
+      case "length":
+        entry.duration = positiveSeconds(Number(value));
+        break;
+    }
+    byIndex.set(Number(index), entry);
+  }
+
+  return [...byIndex.entries()]
+    .sort(([a], [b]) => a - b)
+    .map(([, entry]) => entry)
+    .filter((entry) => entry.location);
+}
+
+function childText(parent: Element, tag: string): string | undefined {
+  const child = Array.from(parent.children).find((c) => c.localName === tag);
+  return child?.textContent?.trim() || undefined;
+}
+
+// XSPF locations are URIs; relative ones are decoded to plain paths here,
+// file: URLs when they are resolved
+function decodeXspfLocation(location: string | undefined): string | undefined {
+  if (!location || /^[a-z][a-z0-9+.-]*:/i.test(location)) return location;
+  try {
+    return decodeURIComponent(location);
+  } catch {
+    return location;
+  }
+}
+
+function parseXSPF(content: string): PlaylistFileContents {
+  const doc = new DOMParser().parseFromString(content, "application/xml");
+  if (doc.getElementsByTagName("parsererror").length > 0) {
+    throw new Error("Invalid XSPF playlist");
+  }
+
+  const playlist = doc.documentElement;
+  const trackList = Array.from(playlist.children).find(
+    (c) => c.localName === "trackList",
+  );
+  const tracks = trackList
+    ? Array.from(trackList.children).filter((c) => c.localName === "track")
+    : [];
+
+  return {
+    name: childText(playlist, "title"),
+    entries: tracks.map((track) => {
+      const duration = childText(track, "duration");
+      return {
+        location: decodeXspfLocation(childText(track, "location")),
+        title: childText(track, "title"),
+        artist: childText(track, "creator"),
+        // XSPF durations are milliseconds
+        duration: duration
+          ? positiveSeconds(Number(duration) / 1000)
+          : undefined,
+      };
+    }),
+  };
+}
+
+// Read an M3U/M3U8, PLS or XSPF file; the name falls back to the file name
+export function parsePlaylistFile(
+  fileName: string,
+  content: string,
+): ParsedPlaylistFile {
+  const text = content.replace(/^\uFEFF/, "");
+  const format = getPlaylistFileFormat(fileName, text);
+  const baseName = fileName.split(/[/\\]/).pop() ?? fileName;
+  const fallbackName = baseName.replace(/\.[^.]+$/, "") || "Imported Playlist";
+
+  switch (format) {
+    case "m3u":
+    case "m3u8": {
+      const parsed = parseM3U(text);
+      return { name: parsed.name ?? fallbackName, entries: parsed.entries };
+    }
+    case "pls":
+      return { name: fallbackName, entries: parsePLS(text) };
+    case "xspf": {
+      const parsed = parseXSPF(text);
+      return { name: parsed.name ?? fallbackName, entries: parsed.entries };
+    }
+    default:
+      throw new Error("Unsupported playlist format");
+  }
+}
+
+// Paths
+// Playlists written on Windows and POSIX systems are both handled, whatever
+// platform reads them
+
+function isWindowsPath(path: string): boolean {
+  return /^[a-zA-Z]:[\\/]/.test(path) || path.startsWith("\\\\");
+}
+
+function isAbsolutePath(path: string): boolean {
+  return path.startsWith("/") || isWindowsPath(path);
+}
+
+// Collapse ".", ".." and repeated separators, keeping the path's own style
+function normalizePath(path: string): string {
+  const windows = isWindowsPath(path);
+  const unc = path.startsWith("\\\\");
+  const root = windows && !unc ? 1 : 0;
+  const segments: string[] = [];
+  for (const part of path.split(/[\\/]/)) {
+    if (!part || part === ".") continue;
+    if (part === "..") {
+      if (segments.length > root) segments.pop();
+    } else {
+      segments.push(part);
+    }
+  }
+  if (unc) return `\\\\${segments.join("\\")}`;
+  if (windows) return segments.join("\\");
+  return `/${segments.join("/")}`;
+}
+
+function dirName(path: string): string {
+  return normalizePath(`${path}/..`);
+}
+
+function fileUrlToPath(url: string): string {
+  try {
+    const parsed = new URL(url);
+    const path = decodeURIComponent(parsed.pathname);
+    if (parsed.host && parsed.host !== "localhost") {
+      return `\\\\${parsed.host}${path.replace(/\//g, "\\")}`;
+    }
+    // file:///C:/Music -> C:/Music
+    return /^\/[a-zA-Z]:\//.test(path) ? path.slice(1) : path;
+  } catch {
+    return url;
+  }
+}
+
+// Where an entry points, as a normalized absolute path when it can be resolved
+// against the playlist's folder. Streams and other URLs give undefined
+export function resolvePlaylistLocation(
+  location: string,
+  baseDir?: string,
+): string | undefined {
+  let path = location.trim();
+  if (/^file:/i.test(path)) {
+    path = fileUrlToPath(path);
+  } else if (/^[a-z][a-z0-9+.-]+:\/\//i.test(path)) {
+    return undefined;
+  }
+
+  if (isAbsolutePath(path)) return normalizePath(path);
+  if (baseDir) return normalizePath(`${baseDir}/${path}`);
+  // Without a folder to resolve against, keep the relative path for
+  // matching by its trailing segments
+  return path.split(/[\\/]/).filter((p) => p && p !== ".").join("/");
+}
+
+// Windows paths compare case-insensitively
+function pathKey(path: string): string {
+  const normalized = normalizePath(path);
+  return isWindowsPath(normalized) ? normalized.toLowerCase() : normalized;
+}
+
+function pathSegments(path: string): string[] {
+  return path
+    .toLowerCase()
+    .split(/[\\/]/)
+    .filter((p) => p && p !== ".");
+}
+
+// Number of trailing path segments two paths share
+function sharedTail(a: string[], b: string[]): number {
+  let count = 0;
+  while (
+    count < a.length &&
+    count < b.length &&
+    a[a.length - 1 - count] === b[b.length - 1 - count]
+  ) {
+    count++;
+  }
+  return count;
+}
+
+export interface PlaylistMatchResult {
+  // Library songs in playlist order, without repeats
+  songIds: string[];
+  // Entries with no song in the library
+  unmatched: PlaylistFileEntry[];
+}
+
+// Find library songs for playlist entries: by exact path first, then by
+// title/artist/duration, then by file name (preferring the song whose folders
+// match the most trailing segments, for playlists from another machine)
+export function matchPlaylistEntries(
+  entries: PlaylistFileEntry[],
+  songs: Song[],
+  baseDir?: string,
+): PlaylistMatchResult {
+  const byPath = new Map<string, Song>();
+  const byLookupKey = new Map<string, Song>();
+  const byFileName = new Map<string, { song: Song; segments: string[] }[]>();
+
+  for (const song of songs) {
+    if (song.filePath) byPath.set(pathKey(song.filePath), song);
+    byLookupKey.set(
+      getSongLookupKey(song.title, song.artist, song.duration),
+      song,
+    );
+    const location = song.filePath ?? song.relativePath ?? song.fileName;
+    if (location) {
+      const segments = pathSegments(location);
+      const name = segments[segments.length - 1];
+      byFileName.set(name, [
+        ...(byFileName.get(name) ?? []),
+        { song, segments },
+      ]);
+    }
+  }
+
+  const matchByFileName = (path: string): Song | undefined => {
+    const segments = pathSegments(path);
+    const candidates = byFileName.get(segments[segments.length - 1]) ?? [];
+    if (candidates.length === 1) return candidates[0].song;
+
+    const scored = candidates
+      .map((c) => ({ song: c.song, score: sharedTail(c.segments, segments) }))
+      .sort((a, b) => b.score - a.score);
+    // Only settle on a folder match that is unambiguous
+    if (
+      scored.length > 1 &&
+      scored[0].score > 1 &&
+      scored[0].score > scored[1].score
+    ) {
+      return scored[0].song;
+    }
+    return undefined;
+  };
+
+  const songIds: string[] = [];
+  const seen = new Set<string>();
+  const unmatched: PlaylistFileEntry[] = [];
+
+  for (const entry of entries) {
+    const path = entry.location
+      ? resolvePlaylistLocation(entry.location, baseDir)
+      : undefined;
+
+    let song =
+      path && isAbsolutePath(path) ? byPath.get(pathKey(path)) : undefined;
+    if (!song && entry.title && entry.duration) {
+      song = byLookupKey.get(
+        getSongLookupKey(entry.title, entry.artist, entry.duration),
+      );
+    }
+    if (!song && path) {
+      song = matchByFileName(path);
+    }
+
+    if (!song) {
+      unmatched.push(entry);
+    } else if (!seen.has(song.id)) {
+      seen.add(song.id);
+      songIds.push(song.id);
+    }
+  }
+
+  return { songIds, unmatched };
+}
+
+export type PlaylistPathMode = "absolute" | "relative";
+
+export interface PlaylistExportOptions {
+  format: PlaylistFileFormat;
+  pathMode: PlaylistPathMode;
+  // Folder the playlist file is saved in, for relative paths
+  baseDir?: string;
+}
+
+// Path of `target` as seen from folder `fromDir`; absolute when they don't
+// share a root (e.g. different drives)
+export function relativePlaylistPath(fromDir: string, target: string): string {
+  const from = normalizePath(fromDir);
+  const to = normalizePath(target);
+  const windows = isWindowsPath(to);
+  if (windows !== isWindowsPath(from)) return to;
+
+  const same = (a: string, b: string) =>
+    windows ? a.toLowerCase() === b.toLowerCase() : a === b;
+  const fromParts = from.split(/[\\/]/).filter(Boolean);
+  const toParts = to.split(/[\\/]/).filter(Boolean);
+  if (!same(fromParts[0] ?? "", toParts[0] ?? "")) return to;
+
+  let common = 0;
+  while (
+    common < fromParts.length &&
+    common < toParts.length - 1 &&
+    same(fromParts[common], toParts[common])
+  ) {
+    common++;
+  }
+  return [
+    ...Array<string>(fromParts.length - common).fill(".."),
+    ...toParts.slice(common),
+  ].join(windows ? "\\" : "/");
+}
+
+function songLocation(song: Song, options: PlaylistExportOptions): string {
+  // Web songs only know where they sit inside their folder
+  if (!song.filePath) return song.relativePath ?? song.fileName ?? song.title;
+  if (options.pathMode === "relative" && options.baseDir) {
+    return relativePlaylistPath(options.baseDir, song.filePath);
+  }
+  return song.filePath;
+}
+
+function pathToFileUrl(path: string): string {
+  const encode = (parts: string[]) => parts.map(encodeURIComponent).join("/");
+  if (path.startsWith("\\\\")) {
+    const [host, ...rest] = path.slice(2).split("\\");
+    return `file://${host}/${encode(rest)}`;
+  }
+  const parts = path.split(/[\\/]/).filter(Boolean);
+  if (isWindowsPath(path)) {
+    const [drive, ...rest] = parts;
+    return `file:///${drive}/${encode(rest)}`;
+  }
+  return `file:///${encode(parts)}`;
+}
+
+function xspfLocation(location: string): string {
+  if (isAbsolutePath(location)) return pathToFileUrl(location);
+  return location.split(/[\\/]/).map(encodeURIComponent).join("/");
+}
+
+function escapeXml(text: string): string {
+  return text
+    .replace(/&/g, "&amp;")
+    .replace(/</g, "&lt;")
+    .replace(/>/g, "&gt;")
+    .replace(/"/g, "&quot;");
+}
+
+function displayTitle(song: Song): string {
+  return song.artist ? `${song.artist} - ${song.title}` : song.title;
+}
+
+// Write songs as a playlist file in the given format
+export function serializePlaylist(
+  name: string,
+  songs: Song[],
+  options: PlaylistExportOptions,
+): string {
+  switch (options.format) {
+    case "m3u":
+    case "m3u8": {
+      const lines = ["#EXTM3U", `#PLAYLIST:${name}`];
+      for (const song of songs) {
+        lines.push(
+          `#EXTINF:${Math.round(song.duration) || -1},${displayTitle(song)}`,
+          songLocation(song, options),
+        );
+      }
+      return `${lines.join("\n")}\n`;
+    }
+    case "pls": {
+      const lines = ["[playlist]"];
+      songs.forEach((song, index) => {
+        const n = index + 1;
+        lines.push(
+          `File${n}=${songLocation(song, options)}`,
+          `Title${n}=${displayTitle(song)}`,
+          `Length${n}=${Math.round(song.duration) || -1}`,
+        );
+      });
+      lines.push(`NumberOfEntries=${songs.length}`, "Version=2");
+      return `${lines.join("\n")}\n`;
+    }
+    case "xspf": {
+      const tracks = songs.map((song) => {
+        const fields = [
+          `<location>${escapeXml(
+            xspfLocation(songLocation(song, options)),
+          )}</location>`,
+          `<title>${escapeXml(song.title)}</title>`,
+          song.artist && `<creator>${escapeXml(song.artist)}</creator>`,
+          song.album && `<album>${escapeXml(song.album)}</album>`,
+          song.duration > 0 &&
+            `<duration>${Math.round(song.duration * 1000)}</duration>`,
+        ].filter(Boolean);
+        const lines = fields.map((field) => `      ${field}`).join("\n");
+        return `    <track>\n${lines}\n    </track>`;
+      });
+      return [
+        '<?xml version="1.0" encoding="UTF-8"?>',
+        '<playlist version="1" xmlns="http://xspf.org/ns/0/">',
+        `  <title>${escapeXml(name)}</title>`,
+        "  <trackList>",
+        ...tracks,
+        "  </trackList>",
+        "</playlist>",
+        "",
+      ].join("\n");
+    }
+  }
+}
+
+// Folder containing a file, for resolving and writing relative entries
+export function getPlaylistBaseDir(filePath: string): string {
+  return dirName(filePath);
+}
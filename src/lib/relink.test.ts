@@ -0,0 +1,66 @@
+import { describe, it, expect } from 'vitest';
+import {
+  chooseRelinks,
+  findRelinkCandidates,
+  pathsNeedingDuration,
+} from './relink';
+import { createMockSong } from '../test/test-utils';
+
+const paths = (chosen: Map<string, { path: string }>) =>
+  Object.fromEntries([...chosen].map(([id, file]) => [id, file.path]));
+
+describe('relink matching', () => {
+  it('relinks an exact name and size match without reading durations', () => {
+    const songs = [
+      createMockSong({ id: 'a', fileName: 'Song.mp3', fileSize: 100, filePath: '/old/Song.mp3' }),
+    ];
+    const files = [
+      { path: '/new/song.MP3', name: 'song.MP3', size: 100 },
+      { path: '/new/other.mp3', name: 'other.mp3', size: 200 },
+    ];
+
+    const candidates = findRelinkCandidates(songs, files);
+    expect(pathsNeedingDuration(candidates)).toEqual([]);
+    expect(paths(chooseRelinks(songs, candidates, new Map()))).toEqual({ a: '/new/song.MP3' });
+  });
+
+  it('confirms renamed or re-encoded files by duration', () => {
+    const songs = [
+      createMockSong({ id: 'renamed', fileName: 'a.mp3', fileSize: 100, duration: 200 }),
+      createMockSong({ id: 'reencoded', fileName: 'b.flac', fileSize: 500, duration: 300 }),
+    ];
+    const files = [
+      { path: '/new/01 A.mp3', name: '01 A.mp3', size: 100 },
+      { path: '/new/b.flac', name: 'b.flac', size: 450 },
+    ];
+
+    const candidates = findRelinkCandidates(songs, files);
+    expect(pathsNeedingDuration(candidates).sort()).toEqual(['/new/01 A.mp3', '/new/b.flac']);
+
+    const durations = new Map([
+      ['/new/01 A.mp3', 201],
+      ['/new/b.flac', 250],
+    ]);
+    expect(paths(chooseRelinks(songs, candidates, durations))).toEqual({ renamed: '/new/01 A.mp3' });
+  });
+
+  it('leaves ties unresolved and never assigns a file twice', () => {
+    const songs = [
+      createMockSong({ id: 'first', fileName: 'track.mp3', fileSize: 100 }),
+      createMockSong({ id: 'second', fileName: 'track.mp3', fileSize: 100 }),
+      createMockSong({ id: 'tied', fileName: 'intro.mp3', duration: 60 }),
+    ];
+    const files = [
+      { path: '/x/track.mp3', name: 'track.mp3', size: 100 },
+      { path: '/x/1/intro.mp3', name: 'intro.mp3', size: 10 },
+      { path: '/x/2/intro.mp3', name: 'intro.mp3', size: 20 },
+    ];
+
+    const candidates = findRelinkCandidates(songs, files);
+    const durations = new Map([
+      ['/x/1/intro.mp3', 60],
+      ['/x/2/intro.mp3', 61],
+    ]);
+    expect(paths(chooseRelinks(songs, candidates, durations))).toEqual({ first: '/x/track.mp3' });
+  });
+});
@@ -0,0 +1,127 @@
+import type { Song } from "../types";
+
+// Durations within this many seconds count as the same recording
+const DURATION_TOLERANCE = 2;
+
+export interface RelinkFile {
+  path: string;
+  name: string;
+  size?: number;
+}
+
+export interface RelinkCandidate {
+  file: RelinkFile;
+  nameMatch: boolean;
+  sizeMatch: boolean;
+}
+
+function songFileName(song: Song): string | undefined {
+  const name = song.fileName ?? song.filePath?.split(/[/\\]/).pop();
+  return name?.toLowerCase();
+}
+
+// Files that could be a missing song: same file name (any case) or same size
+export function findRelinkCandidates(
+  songs: Song[],
+  files: RelinkFile[],
+): Map<string, RelinkCandidate[]> {
+  const byName = new Map<string, RelinkFile[]>();
+  const bySize = new Map<number, RelinkFile[]>();
+  for (const file of files) {
+    const name = file.name.toLowerCase();
+    byName.set(name, [...(byName.get(name) ?? []), file]);
+    if (file.size !== undefined) {
+      bySize.set(file.size, [...(bySize.get(file.size) ?? []), file]);
+    }
+  }
+
+  const candidates = new Map<string, RelinkCandidate[]>();
+  for (const song of songs) {
+    const found = new Map<string, RelinkCandidate>();
+    const name = songFileName(song);
+    for (const file of (name && byName.get(name)) || []) {
+      found.set(file.path, {
+        file,
+        nameMatch: true,
+        sizeMatch: !!song.fileSize && file.size === song.fileSize,
+      });
+    }
+    for (const file of (song.fileSize && bySize.get(song.fileSize)) || []) {
+      if (!found.has(file.path)) {
+        found.set(file.path, { file, nameMatch: false, sizeMatch: true });
+      }
+    }
+    if (found.size > 0) {
+      candidates.set(song.id, [...found.values()]);
+    }
+  }
+  return candidates;
+}
+
+// A single candidate with the same name and size needs no further checks
+function exactMatch(list: RelinkCandidate[]): RelinkCandidate | undefined {
+  const exact = list.filter((c) => c.nameMatch && c.sizeMatch);
+  return exact.length === 1 ? exact[0] : undefined;
+}
+
+// Paths whose duration has to be read to settle the remaining songs
+export function pathsNeedingDuration(
+  candidates: Map<string, RelinkCandidate[]>,
+): string[] {
+  const paths = new Set<string>();
+  for (const list of candidates.values()) {
+    if (exactMatch(list)) continue;
+    for (const candidate of list) {
+      paths.add(candidate.file.path);
+    }
+  }
+  return [...paths];
+}
+
+const rank = (c: RelinkCandidate) =>
+  (c.nameMatch ? 2 : 0) + (c.sizeMatch ? 1 : 0);
+
+// Pick at most one file per song and one song per file. Anything short of an
+// exact name and size match must also agree on duration, and ties are left
+// unresolved rather than guessed
+export function chooseRelinks(
+  songs: Song[],
+  candidates: Map<string, RelinkCandidate[]>,
+  durations: Map<string, number>,
+): Map<string, RelinkFile> {
+  const chosen = new Map<string, RelinkFile>();
+  const claimed = new Set<string>();
+  const claim = (songId: string, file: RelinkFile) => {
+    chosen.set(songId, file);
+    claimed.add(file.path);
+  };
+
+  for (const song of songs) {
+    const exact = exactMatch(candidates.get(song.id) ?? []);
+    if (exact && !claimed.has(exact.file.path)) {
+      claim(song.id, exact.file);
+    }
+  }
+
+  for (const song of songs) {
+    if (chosen.has(song.id) || !song.duration) continue;
+
+    const confirmed = (candidates.get(song.id) ?? []).filter((c) => {
+      const duration = durations.get(c.file.path);
+      return (
+        !claimed.has(c.file.path) &&
+        duration !== undefined &&
+        Math.abs(duration - song.duration) <= DURATION_TOLERANCE
+      );
+    });
+    if (confirmed.length === 0) continue;
+
+    const best = Math.max(...confirmed.map(rank));
+    const top = confirmed.filter((c) => rank(c) === best);
+    if (top.length === 1) {
+      claim(song.id, top[0].file);
+    }
+  }
+
+  return chosen;
+}
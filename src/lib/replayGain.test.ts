@@ -0,0 +1,78 @@
+import { describe, it, expect } from 'vitest';
+import { dbToGain, getNormalizationGain, groupSongsForAnalysis } from './replayGain';
+import { createMockSong } from '../test/test-utils';
+
+const settings = {
+  replayGainMode: 'track' as const,
+  replayGainPreamp: 0,
+  replayGainPreventClipping: false,
+};
+
+describe('replayGain', () => {
+  describe('getNormalizationGain', () => {
+    const song = createMockSong({
+      replayGain: { trackGain: -6, trackPeak: 0.5, albumGain: -3, albumPeak: 0.9 },
+    });
+
+    it('is unity when off or untagged', () => {
+      expect(getNormalizationGain(song, { ...settings, replayGainMode: 'off' })).toBe(1);
+      expect(getNormalizationGain(createMockSong(), settings)).toBe(1);
+      expect(getNormalizationGain(undefined, settings)).toBe(1);
+    });
+
+    it('applies track or album gain', () => {
+      expect(getNormalizationGain(song, settings)).toBeCloseTo(dbToGain(-6));
+      expect(getNormalizationGain(song, { ...settings, replayGainMode: 'album' })).toBeCloseTo(
+        dbToGain(-3),
+      );
+    });
+
+    it('falls back to the other gain when one is missing', () => {
+      const trackOnly = createMockSong({ replayGain: { trackGain: -4 } });
+      expect(getNormalizationGain(trackOnly, { ...settings, replayGainMode: 'album' })).toBeCloseTo(
+        dbToGain(-4),
+      );
+    });
+
+    it('adds the preamp', () => {
+      expect(getNormalizationGain(song, { ...settings, replayGainPreamp: 2 })).toBeCloseTo(
+        dbToGain(-4),
+      );
+    });
+
+    it('limits gain to keep the peak below full scale', () => {
+      const quiet = createMockSong({ replayGain: { trackGain: 10, trackPeak: 0.5 } });
+      const preventClipping = { ...settings, replayGainPreventClipping: true };
+
+      expect(getNormalizationGain(quiet, preventClipping)).toBeCloseTo(2);
+      expect(getNormalizationGain(quiet, settings)).toBeCloseTo(dbToGain(10));
+    });
+
+    it('adds the profile offset and keeps it below full scale too', () => {
+      const preventClipping = { ...settings, replayGainPreventClipping: true };
+
+      expect(getNormalizationGain(song, settings, 3)).toBeCloseTo(dbToGain(-3));
+      expect(getNormalizationGain(song, preventClipping, 13)).toBeCloseTo(2);
+      expect(getNormalizationGain(song, { ...settings, replayGainMode: 'off' }, -6)).toBeCloseTo(dbToGain(-6));
+      expect(getNormalizationGain(song, { ...preventClipping, replayGainMode: 'off' }, 12)).toBeCloseTo(2);
+      expect(getNormalizationGain(createMockSong(), preventClipping, 6)).toBeCloseTo(dbToGain(6));
+    });
+  });
+
+  describe('groupSongsForAnalysis', () => {
+    it('groups by folder and album, measuring untitled albums alone', () => {
+      const a1 = createMockSong({ id: 'a1', album: 'Album', filePath: '/music/a/1.mp3' });
+      const a2 = createMockSong({ id: 'a2', album: 'album', filePath: '/music/a/2.mp3' });
+      const b1 = createMockSong({ id: 'b1', album: 'Album', filePath: '/music/b/1.mp3' });
+      const s1 = createMockSong({ id: 's1', album: 'Unknown Album', filePath: '/music/a/3.mp3' });
+      const s2 = createMockSong({ id: 's2', album: 'Unknown Album', filePath: '/music/a/4.mp3' });
+      const web = createMockSong({ id: 'web', album: 'Album', filePath: undefined });
+
+      const groups = groupSongsForAnalysis([a1, b1, a2, s1, s2, web]).map((group) =>
+        group.map((song) => song.id),
+      );
+
+      expect(groups).toEqual([['a1', 'a2'], ['b1'], ['s1'], ['s2']]);
+    });
+  });
+});
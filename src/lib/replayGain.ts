@@ -0,0 +1,85 @@
+import type { AppSettings, Song } from "../types";
+
+type NormalizationSettings = Pick<
+  AppSettings,
+  "replayGainMode" | "replayGainPreamp" | "replayGainPreventClipping"
+>;
+
+// Keep boosted quiet tracks from getting absurdly loud
+const MAX_GAIN_DB = 15;
+
+export function dbToGain(db: number): number {
+  return Math.pow(10, db / 20);
+}
+
+/**
+ * Linear gain for a song under the current normalization settings
+ * Album mode falls back to track values (and vice versa) when a tag is
+ * missing; songs without any values play at unity gain. `profileGainDb` (a
+ * playback profile's offset) is added on top, and clipping prevention covers
+ * it too.
+ */
+export function getNormalizationGain(
+  song: Song | undefined,
+  settings: NormalizationSettings,
+  profileGainDb = 0,
+): number {
+  const replayGain = song?.replayGain;
+  const preferAlbum = settings.replayGainMode === "album";
+  const gainDb =
+    settings.replayGainMode === "off"
+      ? undefined
+      : preferAlbum
+        ? (replayGain?.albumGain ?? replayGain?.trackGain)
+        : (replayGain?.trackGain ?? replayGain?.albumGain);
+
+  // Nothing is boosted, so there is nothing to clip
+  if (gainDb === undefined && profileGainDb <= 0) {
+    return dbToGain(profileGainDb);
+  }
+
+  const peak = preferAlbum
+    ? (replayGain?.albumPeak ?? replayGain?.trackPeak)
+    : (replayGain?.trackPeak ?? replayGain?.albumPeak);
+
+  const songGainDb =
+    gainDb === undefined
+      ? 0
+      : Math.min(gainDb + settings.replayGainPreamp, MAX_GAIN_DB);
+  let gain = dbToGain(songGainDb + profileGainDb);
+
+  // Scale down so the loudest sample stays below full scale
+  if (settings.replayGainPreventClipping && peak && peak > 0) {
+    gain = Math.min(gain, 1 / peak);
+  }
+  return gain;
+}
+
+/**
+ * Group desktop songs into albums for loudness analysis
+ * Tracks share an album when they share a folder and album name; untitled
+ * albums are measured as singles.
+ */
+export function groupSongsForAnalysis(songs: Song[]): Song[][] {
+  const albums = new Map<string, Song[]>();
+  const singles: Song[][] = [];
+
+  for (const song of songs) {
+    if (!song.filePath) continue;
+    if (!song.album || song.album === "Unknown Album") {
+      singles.push([song]);
+      continue;
+    }
+
+    const folder = song.filePath.replace(/[/\\][^/\\]*$/, "");
+    const key = `${folder}|${song.album.toLowerCase()}`;
+    const album = albums.get(key);
+    if (album) {
+      album.push(song);
+    } else {
+      albums.set(key, [song]);
+    }
+  }
+
+  return [...albums.values(), ...singles];
+}
@@ -0,0 +1,115 @@
+import { describe, it, expect } from 'vitest';
+import { buildSearchEntry, parseDuration, parseSearchQuery, tokenize } from './searchQuery';
+import { createMockSong } from '../test/test-utils';
+
+describe('searchQuery', () => {
+  describe('tokenize', () => {
+    it('lowercases, strips accents and splits on punctuation', () => {
+      expect(tokenize('Beyoncé - Déjà Vu (Remix)')).toEqual(['beyonce', 'deja', 'vu', 'remix']);
+    });
+  });
+
+  describe('parseDuration', () => {
+    it.each([
+      ['90', 90],
+      ['90s', 90],
+      ['3m', 180],
+      ['3m30s', 210],
+      ['1h2m', 3720],
+      ['3:30', 210],
+      ['1:02:03', 3723],
+    ])('parses %s', (value, seconds) => {
+      expect(parseDuration(value)).toBe(seconds);
+    });
+
+    it('rejects non-durations', () => {
+      expect(parseDuration('')).toBeNull();
+      expect(parseDuration('abc')).toBeNull();
+      expect(parseDuration('3x')).toBeNull();
+    });
+  });
+
+  describe('parseSearchQuery', () => {
+    it('treats plain words as one free-text clause each', () => {
+      expect(parseSearchQuery('Radiohead creep')).toEqual([
+        { kind: 'text', field: null, tokens: ['radiohead'], negate: false },
+        { kind: 'text', field: null, tokens: ['creep'], negate: false },
+      ]);
+    });
+
+    it('parses field filters and quoted values', () => {
+      expect(parseSearchQuery('artist:radiohead album:"OK Computer"')).toEqual([
+        { kind: 'text', field: 'artist', tokens: ['radiohead'], negate: false },
+        { kind: 'text', field: 'album', tokens: ['ok', 'computer'], negate: false },
+      ]);
+    });
+
+    it('parses numeric comparisons and ranges', () => {
+      expect(parseSearchQuery('year:>2000 duration:<3m year:1990..1999')).toEqual([
+        { kind: 'range', field: 'year', lower: 2000, lowerOpen: true, upperOpen: false, negate: false },
+        { kind: 'range', field: 'duration', upper: 180, lowerOpen: false, upperOpen: true, negate: false },
+        { kind: 'range', field: 'year', lower: 1990, upper: 1999, lowerOpen: false, upperOpen: false, negate: false },
+      ]);
+    });
+
+    it('swaps reversed range bounds', () => {
+      expect(parseSearchQuery('year:1999..1990')).toEqual([
+        { kind: 'range', field: 'year', lower: 1990, upper: 1999, lowerOpen: false, upperOpen: false, negate: false },
+      ]);
+    });
+
+    it('matches an exact duration to the whole second', () => {
+      expect(parseSearchQuery('duration:3:30')).toEqual([
+        { kind: 'range', field: 'duration', lower: 210, upper: 211, lowerOpen: false, upperOpen: true, negate: false },
+      ]);
+    });
+
+    it('supports negation', () => {
+      expect(parseSearchQuery('-genre:live')).toEqual([
+        { kind: 'text', field: 'genre', tokens: ['live'], negate: true },
+      ]);
+    });
+
+    it('ignores incomplete filters while typing', () => {
+      expect(parseSearchQuery('year:> artist:')).toEqual([]);
+    });
+
+    it('searches unknown fields as plain text', () => {
+      expect(parseSearchQuery('foo:bar')).toEqual([
+        { kind: 'text', field: null, tokens: ['foo', 'bar'], negate: false },
+      ]);
+    });
+  });
+
+  describe('buildSearchEntry', () => {
+    it('indexes words per field with numeric values', () => {
+      const song = createMockSong({
+        id: 'song-1',
+        title: 'Creep',
+        artist: 'Radiohead',
+        album: 'Pablo Honey',
+        genre: 'Alt Rock',
+        year: 1993,
+        duration: 238.6,
+        filePath: '/music/Radiohead/creep.mp3',
+      });
+
+      const entry = buildSearchEntry(song);
+
+      expect(entry).toMatchObject({ id: 'song-1', year: 1993, duration: 239 });
+      expect(entry.tokens).toEqual(
+        expect.arrayContaining([
+          'title:creep',
+          'artist:radiohead',
+          'album:pablo',
+          'album:honey',
+          'genre:alt',
+          'genre:rock',
+          'path:music',
+          'path:radiohead',
+          'path:mp3',
+        ]),
+      );
+    });
+  });
+});
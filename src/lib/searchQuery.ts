@@ -0,0 +1,191 @@
+import type { Song } from "../types";
+
+// Library search query language
+//
+//   radiohead creep          songs matching every word (title/artist/album/genre)
+//   artist:radiohead         field filter, prefix match on each word
+//   album:"ok computer"      quoted values may contain spaces
+//   year:>2000 year:1990..1999 duration:<3m duration:>=4:30
+//   -genre:live              leading "-" excludes matches
+
+export const TEXT_FIELDS = ["title", "artist", "album", "genre", "path"] as const;
+export const NUMERIC_FIELDS = ["year", "duration"] as const;
+
+export type TextField = (typeof TEXT_FIELDS)[number];
+export type NumericField = (typeof NUMERIC_FIELDS)[number];
+
+// Fields matched by words without a field prefix
+export const DEFAULT_FIELDS: TextField[] = ["title", "artist", "album", "genre"];
+
+export type SearchClause =
+  | {
+      kind: "text";
+      // null = any of DEFAULT_FIELDS
+      field: TextField | null;
+      tokens: string[];
+      negate: boolean;
+    }
+  | {
+      kind: "range";
+      field: NumericField;
+      lower?: number;
+      upper?: number;
+      lowerOpen: boolean;
+      upperOpen: boolean;
+      negate: boolean;
+    };
+
+// Persisted per song in the searchIndex store
+export interface SearchEntry {
+  id: string;
+  // "field:token" keys, queried by prefix
+  tokens: string[];
+  year?: number;
+  duration: number;
+}
+
+// Lowercase and strip accents so "Beyoncé" matches "beyonce"
+export function normalizeText(text: string): string {
+  return text.normalize("NFKD").replace(/[\u0300-\u036f]/g, "").toLowerCase();
+}
+
+export function tokenize(text: string): string[] {
+  return normalizeText(text)
+    .split(/[^\p{L}\p{N}]+/u)
+    .filter(Boolean);
+}
+
+export function tokenKey(field: TextField, token: string): string {
+  return `${field}:${token}`;
+}
+
+function getFieldText(song: Song, field: TextField): string | undefined {
+  switch (field) {
+    case "path":
+      return song.filePath ?? song.fileName;
+    default:
+      return song[field];
+  }
+}
+
+export function buildSearchEntry(song: Song): SearchEntry {
+  const tokens = new Set<string>();
+  for (const field of TEXT_FIELDS) {
+    const text = getFieldText(song, field);
+    if (!text) continue;
+    for (const token of tokenize(text)) {
+      tokens.add(tokenKey(field, token));
+    }
+  }
+
+  return {
+    id: song.id,
+    tokens: [...tokens],
+    year: song.year,
+    duration: Math.round(song.duration || 0),
+  };
+}
+
+/**
+ * Parse durations like "90", "90s", "3m", "3m30s", "1h2m", "3:30" or "1:02:03"
+ * Returns seconds, or null if the value isn't a duration
+ */
+export function parseDuration(value: string): number | null {
+  const text = value.trim().toLowerCase();
+
+  if (/^\d+(:\d{1,2}){1,2}$/.test(text)) {
+    return text.split(":").reduce((total, part) => total * 60 + Number(part), 0);
+  }
+
+  const match = /^(?:(\d+(?:\.\d+)?)h)?(?:(\d+(?:\.\d+)?)m)?(?:(\d+(?:\.\d+)?)s?)?$/.exec(text);
+  if (!match || !text) return null;
+  const [, hours, minutes, seconds] = match;
+  return (
+    Number(hours ?? 0) * 3600 + Number(minutes ?? 0) * 60 + Number(seconds ?? 0)
+  );
+}
+
+function parseNumber(field: NumericField, value: string): number | null {
+  if (field === "duration") return parseDuration(value);
+  if (!/^\d+$/.test(value)) return null;
+  return Number(value);
+}
+
+function parseRange(
+  field: NumericField,
+  value: string,
+  negate: boolean,
+): SearchClause | null {
+  const base = { kind: "range" as const, field, negate, lowerOpen: false, upperOpen: false };
+
+  // year:1990..1999 (inclusive)
+  const between = value.split("..");
+  if (between.length === 2) {
+    const lower = between[0] ? parseNumber(field, between[0]) : undefined;
+    const upper = between[1] ? parseNumber(field, between[1]) : undefined;
+    if (lower === null || upper === null) return null;
+    if (lower === undefined && upper === undefined) return null;
+    // year:1999..1990 means the same range; a reversed key range would throw
+    if (lower !== undefined && upper !== undefined && lower > upper) {
+      return { ...base, lower: upper, upper: lower };
+    }
+    return { ...base, lower, upper };
+  }
+
+  const match = /^(>=|<=|>|<|=)?(.+)$/.exec(value);
+  if (!match) return null;
+  const [, operator, operand] = match;
+  const number = parseNumber(field, operand);
+  if (number === null) return null;
+
+  switch (operator) {
+    case ">":
+      return { ...base, lower: number, lowerOpen: true };
+    case ">=":
+      return { ...base, lower: number };
+    case "<":
+      return { ...base, upper: number, upperOpen: true };
+    case "<=":
+      return { ...base, upper: number };
+    default:
+      // Durations are stored in whole seconds
+      return field === "duration"
+        ? { ...base, lower: Math.floor(number), upper: Math.floor(number) + 1, upperOpen: true }
+        : { ...base, lower: number, upper: number };
+  }
+}
+
+// Split on whitespace, keeping quoted values together
+function splitTerms(query: string): string[] {
+  return query.match(/(?:[^\s"]+|"[^"]*"?)+/g) ?? [];
+}
+
+export function parseSearchQuery(query: string): SearchClause[] {
+  const clauses: SearchClause[] = [];
+
+  for (const term of splitTerms(query)) {
+    const negate = term.length > 1 && term.startsWith("-");
+    const body = negate ? term.slice(1) : term;
+
+    const separator = body.indexOf(":");
+    const name = separator > 0 ? body.slice(0, separator).toLowerCase() : "";
+    const value = body.slice(separator + 1).replace(/"/g, "");
+
+    if ((NUMERIC_FIELDS as readonly string[]).includes(name)) {
+      // Incomplete values (e.g. "year:>" while typing) are ignored
+      const clause = parseRange(name as NumericField, value, negate);
+      if (clause) clauses.push(clause);
+      continue;
+    }
+
+    const field = (TEXT_FIELDS as readonly string[]).includes(name)
+      ? (name as TextField)
+      : null;
+    const tokens = tokenize(field ? value : body.replace(/"/g, ""));
+    if (tokens.length > 0) {
+      clauses.push({ kind: "text", field, tokens, negate });
+    }
+  }
+
+  return clauses;
+}
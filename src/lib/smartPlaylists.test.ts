@@ -0,0 +1,81 @@
+import { describe, it, expect } from 'vitest';
+import { evaluateSmartPlaylist } from './smartPlaylists';
+import { createMockSong } from '../test/test-utils';
+import type { SmartPlaylistRules, SmartRule } from '../types';
+
+const DAY = 24 * 60 * 60 * 1000;
+const NOW = 1_800_000_000_000;
+
+const rules = (overrides: Partial<SmartPlaylistRules>): SmartPlaylistRules => ({
+  match: 'all',
+  rules: [],
+  sortBy: 'title',
+  sortDirection: 'asc',
+  ...overrides,
+});
+
+const ids = (songs: { id: string }[]) => songs.map((s) => s.id);
+
+describe('evaluateSmartPlaylist', () => {
+  it('matches songs added in the last 30 days', () => {
+    const songs = [
+      createMockSong({ id: 'new', title: 'A', addedAt: NOW - 2 * DAY }),
+      createMockSong({ id: 'old', title: 'B', addedAt: NOW - 40 * DAY }),
+    ];
+    const result = evaluateSmartPlaylist(
+      rules({ rules: [{ field: 'addedAt', operator: 'inLast', value: 30 }] }),
+      songs,
+      NOW,
+    );
+    expect(ids(result)).toEqual(['new']);
+  });
+
+  it('combines rules with all or any', () => {
+    const songs = [
+      createMockSong({ id: 'jazz', title: 'A', artist: 'Miles', genre: 'Jazz' }),
+      createMockSong({ id: 'soul', title: 'B', artist: 'Aretha', genre: 'soul' }),
+      createMockSong({ id: 'rock', title: 'C', artist: 'Miles', genre: 'Rock' }),
+    ];
+    const genre: SmartRule = { field: 'genre', operator: 'in', value: ['jazz', 'Soul'] };
+    const artist: SmartRule = { field: 'artist', operator: 'is', value: 'miles' };
+
+    expect(ids(evaluateSmartPlaylist(rules({ rules: [genre, artist] }), songs, NOW))).toEqual(['jazz']);
+    expect(
+      ids(evaluateSmartPlaylist(rules({ match: 'any', rules: [genre, artist] }), songs, NOW)),
+    ).toEqual(['jazz', 'soul', 'rock']);
+  });
+
+  it('treats never-played songs as not played recently', () => {
+    const songs = [
+      createMockSong({ id: 'recent', artist: 'X', lastPlayedAt: NOW - 10 * DAY, playCount: 4 }),
+      createMockSong({ id: 'stale', artist: 'X', lastPlayedAt: NOW - 200 * DAY, playCount: 9 }),
+      createMockSong({ id: 'never', artist: 'X' }),
+      createMockSong({ id: 'other', artist: 'Y' }),
+    ];
+    const result = evaluateSmartPlaylist(
+      rules({
+        rules: [
+          { field: 'artist', operator: 'is', value: 'X' },
+          { field: 'lastPlayedAt', operator: 'notInLast', value: 180 },
+        ],
+        sortBy: 'playCount',
+        sortDirection: 'desc',
+      }),
+      songs,
+      NOW,
+    );
+    expect(ids(result)).toEqual(['stale', 'never']);
+  });
+
+  it('limits by total time and keeps random order stable per seed', () => {
+    const songs = Array.from({ length: 20 }, (_, i) =>
+      createMockSong({ id: `s${i}`, duration: 600 }),
+    );
+    const twoHours = rules({ sortBy: 'random', seed: 7, limit: { value: 2, unit: 'hours' } });
+
+    const first = evaluateSmartPlaylist(twoHours, songs, NOW);
+    expect(first).toHaveLength(12);
+    expect(ids(evaluateSmartPlaylist(twoHours, [...songs].reverse(), NOW))).toEqual(ids(first));
+    expect(ids(evaluateSmartPlaylist({ ...twoHours, seed: 8 }, songs, NOW))).not.toEqual(ids(first));
+  });
+});
@@ -0,0 +1,279 @@
+import type {
+  Playlist,
+  SmartPlaylistRules,
+  SmartRule,
+  SmartRuleField,
+  SmartRuleOperator,
+  SmartSortKey,
+  Song,
+} from "../types";
+import { SONG_SORT_OPTIONS, sortSongs } from "./songSort";
+
+const DAY_MS = 24 * 60 * 60 * 1000;
+
+export type SmartFieldKind = "text" | "number" | "date";
+
+export const SMART_RULE_FIELDS: {
+  key: SmartRuleField;
+  label: string;
+  kind: SmartFieldKind;
+}[] = [
+  { key: "title", label: "Title", kind: "text" },
+  { key: "artist", label: "Artist", kind: "text" },
+  { key: "album", label: "Album", kind: "text" },
+  { key: "albumArtist", label: "Album Artist", kind: "text" },
+  { key: "genre", label: "Genre", kind: "text" },
+  { key: "composer", label: "Composer", kind: "text" },
+  { key: "year", label: "Year", kind: "number" },
+  { key: "duration", label: "Duration (seconds)", kind: "number" },
+  { key: "bpm", label: "BPM", kind: "number" },
+  { key: "addedAt", label: "Date Added", kind: "date" },
+  { key: "playCount", label: "Play Count", kind: "number" },
+  { key: "skipCount", label: "Skip Count", kind: "number" },
+  { key: "lastPlayedAt", label: "Last Played", kind: "date" },
+];
+
+// Sort keys beyond the library's, for the editor's sort menu
+export const SMART_SORT_OPTIONS: { key: SmartSortKey; label: string }[] = [
+  ...SONG_SORT_OPTIONS,
+  { key: "playCount", label: "Play Count" },
+  { key: "lastPlayedAt", label: "Last Played" },
+  { key: "random", label: "Random" },
+];
+
+export const SMART_RULE_OPERATORS: Record<
+  SmartFieldKind,
+  { key: SmartRuleOperator; label: string }[]
+> = {
+  text: [
+    { key: "is", label: "is" },
+    { key: "isNot", label: "is not" },
+    { key: "contains", label: "contains" },
+    { key: "notContains", label: "does not contain" },
+    { key: "in", label: "is one of" },
+    { key: "notIn", label: "is none of" },
+  ],
+  number: [
+    { key: "is", label: "is" },
+    { key: "gt", label: "is greater than" },
+    { key: "lt", label: "is less than" },
+    { key: "between", label: "is between" },
+  ],
+  date: [
+    { key: "inLast", label: "in the last (days)" },
+    { key: "notInLast", label: "not in the last (days)" },
+  ],
+};
+
+export function getFieldKind(field: SmartRuleField): SmartFieldKind {
+  return SMART_RULE_FIELDS.find((f) => f.key === field)?.kind ?? "text";
+}
+
+export function isSmartPlaylist(
+  playlist: Playlist,
+): playlist is Playlist & { rules: SmartPlaylistRules } {
+  return !!playlist.rules;
+}
+
+export const MOST_PLAYED_RULES: SmartPlaylistRules = {
+  match: "all",
+  rules: [{ field: "playCount", operator: "gt", value: 0 }],
+  sortBy: "playCount",
+  sortDirection: "desc",
+  limit: { value: 50, unit: "songs" },
+};
+
+export const RECENTLY_PLAYED_RULES: SmartPlaylistRules = {
+  match: "all",
+  rules: [{ field: "lastPlayedAt", operator: "inLast", value: 30 }],
+  sortBy: "lastPlayedAt",
+  sortDirection: "desc",
+  limit: { value: 50, unit: "songs" },
+};
+
+// Starting point for a new smart playlist: songs added in the last 30 days
+export function createDefaultSmartRules(): SmartPlaylistRules {
+  return {
+    match: "all",
+    rules: [{ field: "addedAt", operator: "inLast", value: 30 }],
+    sortBy: "addedAt",
+    sortDirection: "desc",
+  };
+}
+
+function fieldValue(
+  song: Song,
+  field: SmartRuleField,
+): string | number | undefined {
+  switch (field) {
+    case "albumArtist":
+      return song.albumArtist || song.artist;
+    case "playCount":
+    case "skipCount":
+      // Never played (or skipped) counts as zero
+      return song[field] ?? 0;
+    default:
+      return song[field];
+  }
+}
+
+const normalize = (value: unknown) => String(value).trim().toLowerCase();
+
+function matchesText(
+  actual: string,
+  operator: SmartRuleOperator,
+  value: SmartRule["value"],
+): boolean {
+  const list = Array.isArray(value)
+    ? (value as (string | number)[]).map(normalize)
+    : [normalize(value)];
+  switch (operator) {
+    case "is":
+      return actual === list[0];
+    case "isNot":
+      return actual !== list[0];
+    case "contains":
+      return actual.includes(list[0]);
+    case "notContains":
+      return !actual.includes(list[0]);
+    case "in":
+      return list.includes(actual);
+    case "notIn":
+      return !list.includes(actual);
+    default:
+      return false;
+  }
+}
+
+function matchesNumber(
+  actual: number,
+  operator: SmartRuleOperator,
+  value: SmartRule["value"],
+): boolean {
+  if (operator === "between") {
+    if (!Array.isArray(value)) return false;
+    const [min, max] = (value as (string | number)[]).map(Number);
+    return actual >= min && actual <= max;
+  }
+  const target = Number(value);
+  switch (operator) {
+    case "is":
+      return actual === target;
+    case "gt":
+      return actual > target;
+    case "lt":
+      return actual < target;
+    default:
+      return false;
+  }
+}
+
+export function matchesRule(
+  song: Song,
+  rule: SmartRule,
+  now: number,
+): boolean {
+  const actual = fieldValue(song, rule.field);
+  switch (getFieldKind(rule.field)) {
+    case "text":
+      // Missing tags compare as empty, so "is not" still matches them
+      return matchesText(normalize(actual ?? ""), rule.operator, rule.value);
+    case "number":
+      return (
+        typeof actual === "number" &&
+        matchesNumber(actual, rule.operator, rule.value)
+      );
+    case "date": {
+      // A song never played was not played recently either
+      if (typeof actual !== "number") return rule.operator === "notInLast";
+      const recent = now - actual <= Number(rule.value) * DAY_MS;
+      return rule.operator === "inLast" ? recent : !recent;
+    }
+  }
+}
+
+// FNV-1a over seed and id: a shuffle that stays put until the seed changes
+function shuffleKey(songId: string, seed: number): number {
+  let hash = 0x811c9dc5;
+  const input = `${seed}:${songId}`;
+  for (let i = 0; i < input.length; i++) {
+    hash ^= input.charCodeAt(i);
+    hash = Math.imul(hash, 0x01000193);
+  }
+  return hash >>> 0;
+}
+
+function applyLimit(
+  songs: Song[],
+  limit: SmartPlaylistRules["limit"],
+): Song[] {
+  if (!limit || limit.value <= 0) return songs;
+  if (limit.unit === "songs") return songs.slice(0, limit.value);
+
+  // Time limits stop before the first song that would run over
+  const budget = limit.value * (limit.unit === "hours" ? 3600 : 60);
+  let total = 0;
+  const limited: Song[] = [];
+  for (const song of songs) {
+    if (total + song.duration > budget) break;
+    total += song.duration;
+    limited.push(song);
+  }
+  return limited;
+}
+
+function sortByPlays(
+  songs: Song[],
+  key: "playCount" | "lastPlayedAt",
+  direction: number,
+): Song[] {
+  return [...songs].sort(
+    (a, b) => ((a[key] ?? 0) - (b[key] ?? 0)) * direction,
+  );
+}
+
+// Songs a smart playlist currently holds, in playlist order
+export function evaluateSmartPlaylist(
+  rules: SmartPlaylistRules,
+  songs: Song[],
+  now = Date.now(),
+): Song[] {
+  const matched =
+    rules.rules.length === 0
+      ? songs
+      : songs.filter((song) =>
+          rules.match === "all"
+            ? rules.rules.every((rule) => matchesRule(song, rule, now))
+            : rules.rules.some((rule) => matchesRule(song, rule, now)),
+        );
+
+  const seed = rules.seed ?? 0;
+  const direction = rules.sortDirection === "asc" ? 1 : -1;
+  let sorted: Song[];
+  if (rules.sortBy === "random") {
+    sorted = [...matched].sort(
+      (a, b) => shuffleKey(a.id, seed) - shuffleKey(b.id, seed),
+    );
+  } else if (
+    rules.sortBy === "playCount" ||
+    rules.sortBy === "lastPlayedAt"
+  ) {
+    sorted = sortByPlays(matched, rules.sortBy, direction);
+  } else {
+    sorted = sortSongs(matched, rules.sortBy, rules.sortDirection);
+  }
+
+  return applyLimit(sorted, rules.limit);
+}
+
+// Short summary for playlist lists, e.g. "3 rules • up to 2 hours"
+export function describeSmartRules(rules: SmartPlaylistRules): string {
+  const parts = [
+    rules.rules.length === 1 ? "1 rule" : `${rules.rules.length} rules`,
+  ];
+  if (rules.limit && rules.limit.value > 0) {
+    const { value, unit } = rules.limit;
+    parts.push(`up to ${value} ${value === 1 ? unit.slice(0, -1) : unit}`);
+  }
+  return parts.join(" • ");
+}
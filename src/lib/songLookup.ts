@@ -0,0 +1,14 @@
+// Normalize string for duplicate checking
+const normalize = (str: string | undefined): string =>
+  (str || "").toLowerCase().trim();
+
+// Generate a lookup key for duplicate detection
+export function getSongLookupKey(
+  title: string | undefined,
+  artist: string | undefined,
+  duration: number,
+): string {
+  // Round duration to nearest 2 seconds for tolerance
+  const roundedDuration = Math.round((duration || 0) / 2) * 2;
+  return `${normalize(title)}|${normalize(artist)}|${roundedDuration}`;
+}
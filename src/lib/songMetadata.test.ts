@@ -0,0 +1,71 @@
+import { describe, it, expect } from 'vitest';
+import {
+  SONG_METADATA_VERSION,
+  backfillFromFileName,
+  mergeTagFields,
+  needsMetadataRefresh,
+  normalizeCodec,
+  pickTagFields,
+  trackFromFileName,
+} from './songMetadata';
+import { createMockSong } from '../test/test-utils';
+
+describe('songMetadata', () => {
+  describe('pickTagFields', () => {
+    it('keeps only defined tag fields', () => {
+      expect(
+        pickTagFields({ title: 'Ignored', composer: 'Bach', bpm: undefined, sampleRate: 44100 }),
+      ).toEqual({ composer: 'Bach', sampleRate: 44100 });
+    });
+  });
+
+  describe('mergeTagFields', () => {
+    it('updates tag fields without touching user-facing ones', () => {
+      const song = createMockSong({ title: 'Mine', genre: 'Rock', trackNumber: 3 });
+
+      const merged = mergeTagFields(song, { title: 'Tagged', genre: 'Jazz', codec: 'FLAC' });
+
+      expect(merged).toMatchObject({ title: 'Mine', genre: 'Jazz', trackNumber: 3, codec: 'FLAC' });
+      expect(merged.metadataVersion).toBe(SONG_METADATA_VERSION);
+      expect(needsMetadataRefresh(merged)).toBe(false);
+      expect(needsMetadataRefresh(song)).toBe(true);
+    });
+  });
+
+  describe('normalizeCodec', () => {
+    it('maps music-metadata codec names to short names', () => {
+      expect(normalizeCodec('MPEG 1 Layer 3')).toBe('MP3');
+      expect(normalizeCodec('Vorbis I')).toBe('Vorbis');
+      expect(normalizeCodec('MPEG-4/AAC')).toBe('AAC');
+      expect(normalizeCodec('ALAC')).toBe('ALAC');
+      expect(normalizeCodec('PCM')).toBe('PCM');
+      expect(normalizeCodec(undefined)).toBeUndefined();
+    });
+  });
+
+  describe('trackFromFileName', () => {
+    it('reads leading track and disc numbers', () => {
+      expect(trackFromFileName('01 - Intro.mp3')).toEqual({ trackNumber: 1 });
+      expect(trackFromFileName('03. Song.flac')).toEqual({ trackNumber: 3 });
+      expect(trackFromFileName('2-07 Song.flac')).toEqual({ trackNumber: 7, discNumber: 2 });
+    });
+
+    it('ignores years and names starting with digits', () => {
+      expect(trackFromFileName('1999 - Prince.mp3')).toBeUndefined();
+      expect(trackFromFileName('2Pac - Changes.mp3')).toBeUndefined();
+      expect(trackFromFileName(undefined)).toBeUndefined();
+    });
+  });
+
+  describe('backfillFromFileName', () => {
+    it('derives codec and track number without overwriting tags', () => {
+      const song = createMockSong({ fileName: '05 - Song.flac' });
+      expect(backfillFromFileName(song)).toMatchObject({ codec: 'FLAC', trackNumber: 5 });
+
+      const tagged = createMockSong({ fileName: '05 - Song.m4a', trackNumber: 9 });
+      const backfilled = backfillFromFileName(tagged);
+      expect(backfilled.trackNumber).toBe(9);
+      expect('codec' in backfilled).toBe(false);
+    });
+  });
+});
@@ -0,0 +1,119 @@
+import type { Song } from "../types";
+
+// Bump when extractMetadata / the native reader learn new fields, so songs
+// imported earlier get their tags re-read once the file is reachable
+export const SONG_METADATA_VERSION = 2;
+
+// Fields that come straight from tags and stream properties. Re-reading a
+// file only ever touches these, never title/artist/album the user may rely on
+export const TAG_FIELDS = [
+  "albumArtist",
+  "trackNumber",
+  "discNumber",
+  "genre",
+  "year",
+  "composer",
+  "bpm",
+  "bitrate",
+  "sampleRate",
+  "bitDepth",
+  "channels",
+  "codec",
+] as const satisfies readonly (keyof Song)[];
+
+export type TagFields = Pick<Song, (typeof TAG_FIELDS)[number]>;
+
+export function pickTagFields(metadata: Partial<Song>): TagFields {
+  const fields: Partial<Record<keyof TagFields, unknown>> = {};
+  for (const field of TAG_FIELDS) {
+    if (metadata[field] !== undefined) {
+      fields[field] = metadata[field];
+    }
+  }
+  return fields as TagFields;
+}
+
+// Whether a stored song predates the current metadata schema
+export function needsMetadataRefresh(song: Song): boolean {
+  return (song.metadataVersion ?? 1) < SONG_METADATA_VERSION;
+}
+
+// Apply freshly read tags to a stored song, keeping values the file no longer has
+export function mergeTagFields(song: Song, metadata: Partial<Song>): Song {
+  return {
+    ...song,
+    ...pickTagFields(metadata),
+    metadataVersion: SONG_METADATA_VERSION,
+  };
+}
+
+// music-metadata reports codecs as "MPEG 1 Layer 3", "Vorbis I", "PCM", ...
+// Collapse them to the short names the native reader uses
+export function normalizeCodec(codec?: string): string | undefined {
+  if (!codec) return undefined;
+  const value = codec.toLowerCase();
+  if (value.includes("layer 3") || value === "mp3") return "MP3";
+  if (value.includes("alac")) return "ALAC";
+  if (value.includes("aac") || value.includes("mp4a")) return "AAC";
+  if (value.includes("flac")) return "FLAC";
+  if (value.includes("opus")) return "Opus";
+  if (value.includes("vorbis")) return "Vorbis";
+  if (value.includes("pcm")) return "PCM";
+  if (value.includes("wavpack")) return "WavPack";
+  if (value.includes("monkey")) return "APE";
+  if (value.includes("musepack")) return "Musepack";
+  if (value.includes("speex")) return "Speex";
+  return codec.trim() || undefined;
+}
+
+const CODEC_BY_EXTENSION: Record<string, string> = {
+  mp3: "MP3",
+  flac: "FLAC",
+  ogg: "Vorbis",
+  oga: "Vorbis",
+  opus: "Opus",
+  wav: "PCM",
+  aiff: "PCM",
+  aif: "PCM",
+  aac: "AAC",
+  ape: "APE",
+  wv: "WavPack",
+  mpc: "Musepack",
+};
+
+// Best guess from the file name; .m4a may be AAC or ALAC so it's left unknown
+export function codecFromFileName(fileName?: string): string | undefined {
+  const extension = fileName?.split(".").pop()?.toLowerCase();
+  return extension ? CODEC_BY_EXTENSION[extension] : undefined;
+}
+
+// "01 - Title.mp3", "1-03 Title.flac" or "03. Title.ogg"
+const TRACK_PREFIX = /^(?:(\d{1,2})-)?(\d{1,3})(?:\s*[-._]\s*|\s+)\S/;
+
+export function trackFromFileName(
+  fileName?: string,
+): { trackNumber: number; discNumber?: number } | undefined {
+  const match = fileName ? TRACK_PREFIX.exec(fileName) : null;
+  if (!match) return undefined;
+  const trackNumber = parseInt(match[2], 10);
+  if (trackNumber === 0) return undefined;
+  const discNumber = match[1] ? parseInt(match[1], 10) : undefined;
+  return { trackNumber, discNumber: discNumber || undefined };
+}
+
+// Fill what can be derived without reading the file. Used by the DB migration
+// so older records sort and group sensibly before their tags are re-read
+export function backfillFromFileName(song: Song): Song {
+  const backfilled = { ...song };
+  const codec = codecFromFileName(song.fileName);
+  if (!song.codec && codec) backfilled.codec = codec;
+
+  const track = song.trackNumber ? undefined : trackFromFileName(song.fileName);
+  if (track) {
+    backfilled.trackNumber = track.trackNumber;
+    if (!song.discNumber && track.discNumber) {
+      backfilled.discNumber = track.discNumber;
+    }
+  }
+  return backfilled;
+}
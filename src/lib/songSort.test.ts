@@ -0,0 +1,47 @@
+import { describe, it, expect } from 'vitest';
+import { sortSongs } from './songSort';
+import { createMockSong } from '../test/test-utils';
+
+const ids = (songs: { id: string }[]) => songs.map((song) => song.id);
+
+describe('sortSongs', () => {
+  it('sorts albums by disc and track number', () => {
+    const songs = [
+      createMockSong({ id: 'b2', album: 'B', discNumber: 2, trackNumber: 1 }),
+      createMockSong({ id: 'a', album: 'A', trackNumber: 1 }),
+      createMockSong({ id: 'b1', album: 'B', discNumber: 1, trackNumber: 4 }),
+    ];
+
+    expect(ids(sortSongs(songs, 'album', 'asc'))).toEqual(['a', 'b1', 'b2']);
+    expect(ids(sortSongs(songs, 'album', 'desc'))).toEqual(['b1', 'b2', 'a']);
+  });
+
+  it('orders each artist by year, then album', () => {
+    const songs = [
+      createMockSong({ id: 'new', artist: 'X', album: 'New', year: 2010 }),
+      createMockSong({ id: 'other', artist: 'A', album: 'Z' }),
+      createMockSong({ id: 'old', artist: 'X', album: 'Old', year: 1990 }),
+    ];
+
+    expect(ids(sortSongs(songs, 'artist', 'asc'))).toEqual(['other', 'old', 'new']);
+  });
+
+  it('keeps songs without a value last in both directions', () => {
+    const songs = [
+      createMockSong({ id: 'none' }),
+      createMockSong({ id: '2000', year: 2000 }),
+      createMockSong({ id: '1980', year: 1980 }),
+    ];
+
+    expect(ids(sortSongs(songs, 'year', 'asc'))).toEqual(['1980', '2000', 'none']);
+    expect(ids(sortSongs(songs, 'year', 'desc'))).toEqual(['2000', '1980', 'none']);
+  });
+
+  it('does not mutate the input', () => {
+    const songs = [createMockSong({ id: 'b', title: 'B' }), createMockSong({ id: 'a', title: 'A' })];
+
+    sortSongs(songs, 'title', 'asc');
+
+    expect(ids(songs)).toEqual(['b', 'a']);
+  });
+});
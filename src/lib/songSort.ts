@@ -0,0 +1,77 @@
+import type { Song, SongSortKey, SortDirection } from "../types";
+import { compareNames, compareTracks, getAlbumArtist } from "./albums";
+
+export const SONG_SORT_OPTIONS: { key: SongSortKey; label: string }[] = [
+  { key: "addedAt", label: "Date Added" },
+  { key: "title", label: "Title" },
+  { key: "artist", label: "Artist" },
+  { key: "album", label: "Album" },
+  { key: "year", label: "Year" },
+  { key: "genre", label: "Genre" },
+  { key: "duration", label: "Duration" },
+];
+
+// Missing values sort last in either direction
+function compareOptional<T>(
+  a: T | undefined,
+  b: T | undefined,
+  compare: (a: T, b: T) => number,
+  direction: number,
+): number {
+  if (a === undefined) return b === undefined ? 0 : 1;
+  if (b === undefined) return -1;
+  return compare(a, b) * direction;
+}
+
+const byNumber = (a: number, b: number) => a - b;
+
+// Album order within a group: album artist, album, then disc and track
+function compareAlbumTracks(a: Song, b: Song): number {
+  return (
+    compareNames(getAlbumArtist(a), getAlbumArtist(b)) ||
+    compareNames(a.album, b.album) ||
+    compareTracks(a, b)
+  );
+}
+
+function getComparator(
+  key: SongSortKey,
+  direction: number,
+): (a: Song, b: Song) => number {
+  switch (key) {
+    case "title":
+      return (a, b) => compareNames(a.title, b.title) * direction;
+    case "artist":
+      // Discography order within each artist
+      return (a, b) =>
+        compareNames(a.artist, b.artist) * direction ||
+        compareOptional(a.year, b.year, byNumber, 1) ||
+        compareAlbumTracks(a, b);
+    case "album":
+      return (a, b) =>
+        compareNames(a.album, b.album) * direction ||
+        compareAlbumTracks(a, b);
+    case "year":
+      return (a, b) =>
+        compareOptional(a.year, b.year, byNumber, direction) ||
+        compareAlbumTracks(a, b);
+    case "genre":
+      return (a, b) =>
+        compareOptional(a.genre, b.genre, compareNames, direction) ||
+        compareNames(a.artist, b.artist) ||
+        compareAlbumTracks(a, b);
+    case "duration":
+      return (a, b) => (a.duration - b.duration) * direction;
+    case "addedAt":
+      return (a, b) => (a.addedAt - b.addedAt) * direction;
+  }
+}
+
+// Sorted copy of songs; ties keep their library order
+export function sortSongs(
+  songs: Song[],
+  key: SongSortKey,
+  direction: SortDirection,
+): Song[] {
+  return [...songs].sort(getComparator(key, direction === "asc" ? 1 : -1));
+}
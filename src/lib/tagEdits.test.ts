@@ -0,0 +1,98 @@
+import { describe, it, expect } from 'vitest';
+import {
+  applyTagEdit,
+  buildTagEdit,
+  getTagFormValues,
+  hasTagChanges,
+  parseTagNumber,
+} from './tagEdits';
+import { createMockSong } from '../test/test-utils';
+
+describe('tagEdits', () => {
+  describe('getTagFormValues', () => {
+    it('fills the form from a single song', () => {
+      const song = createMockSong({ title: 'Song', artist: 'Artist', album: 'Album', trackNumber: 4, year: 2001 });
+      const { values, mixed } = getTagFormValues([song]);
+
+      expect(values.title).toBe('Song');
+      expect(values.trackNumber).toBe('4');
+      expect(values.year).toBe('2001');
+      expect(values.genre).toBe('');
+      expect(mixed.size).toBe(0);
+    });
+
+    it('leaves fields that differ between songs empty', () => {
+      const songs = [
+        createMockSong({ title: 'One', album: 'Album', year: 2001 }),
+        createMockSong({ title: 'Two', album: 'Album' }),
+      ];
+      const { values, mixed } = getTagFormValues(songs);
+
+      expect(values.album).toBe('Album');
+      expect(values.title).toBe('');
+      expect(mixed.has('title')).toBe(true);
+      expect(mixed.has('year')).toBe(true);
+      expect(mixed.has('album')).toBe(false);
+    });
+  });
+
+  describe('parseTagNumber', () => {
+    it('parses whole numbers and clears on empty', () => {
+      expect(parseTagNumber('trackNumber', ' 7 ')).toBe(7);
+      expect(parseTagNumber('year', '')).toBe(0);
+    });
+
+    it('rejects anything else', () => {
+      expect(parseTagNumber('trackNumber', '3/12')).toBeUndefined();
+      expect(parseTagNumber('trackNumber', '-1')).toBeUndefined();
+      expect(parseTagNumber('trackNumber', '1000')).toBeUndefined();
+      expect(parseTagNumber('year', '10000')).toBeUndefined();
+    });
+  });
+
+  describe('buildTagEdit', () => {
+    it('only includes changed fields', () => {
+      const { values } = getTagFormValues([createMockSong({ title: 'Song', artist: 'Artist' })]);
+      values.artist = '  New Artist ';
+      values.year = '1999';
+      values.discNumber = 'x';
+
+      expect(buildTagEdit(values, ['artist', 'year', 'discNumber'])).toEqual({
+        artist: 'New Artist',
+        year: 1999,
+      });
+      expect(hasTagChanges(buildTagEdit(values, []))).toBe(false);
+    });
+  });
+
+  describe('applyTagEdit', () => {
+    it('updates edited fields and keeps the id', () => {
+      const song = createMockSong({ id: 'keep', genre: 'Rock', trackNumber: 2 });
+      const updated = applyTagEdit(song, { album: 'New Album', genre: '', trackNumber: 0, year: 1999 });
+
+      expect(updated.id).toBe('keep');
+      expect(updated.album).toBe('New Album');
+      expect(updated.genre).toBeUndefined();
+      expect(updated.trackNumber).toBeUndefined();
+      expect(updated.year).toBe(1999);
+      expect(updated.title).toBe(song.title);
+    });
+
+    it('falls back like an import when required fields are cleared', () => {
+      const song = createMockSong({ fileName: 'Some File.mp3' });
+      const updated = applyTagEdit(song, { title: ' ', artist: '', album: '' });
+
+      expect(updated.title).toBe('Some File');
+      expect(updated.artist).toBe('Unknown Artist');
+      expect(updated.album).toBe('Unknown Album');
+    });
+
+    it('sets or removes artwork only when the edit touches it', () => {
+      const song = createMockSong({ artworkId: 'old' });
+
+      expect(applyTagEdit(song, { title: 'x' }, 'new').artworkId).toBe('old');
+      expect(applyTagEdit(song, { coverArt: 'data:image/png;base64,AA==' }, 'new').artworkId).toBe('new');
+      expect(applyTagEdit(song, { coverArt: '' }).artworkId).toBeUndefined();
+    });
+  });
+});
@@ -0,0 +1,132 @@
+import type { Song, TagEdit } from "../types";
+
+export type TagTextField =
+  | "title"
+  | "artist"
+  | "albumArtist"
+  | "album"
+  | "genre";
+export type TagNumberField = "trackNumber" | "discNumber" | "year";
+export type TagEditField = TagTextField | TagNumberField;
+
+export const TAG_EDIT_FIELDS: {
+  field: TagEditField;
+  label: string;
+  // Fields that only make sense per track aren't offered for batch edits
+  singleOnly?: boolean;
+}[] = [
+  { field: "title", label: "Title", singleOnly: true },
+  { field: "artist", label: "Artist" },
+  { field: "albumArtist", label: "Album Artist" },
+  { field: "album", label: "Album" },
+  { field: "trackNumber", label: "Track", singleOnly: true },
+  { field: "discNumber", label: "Disc" },
+  { field: "year", label: "Year" },
+  { field: "genre", label: "Genre" },
+];
+
+export type TagFormValues = Record<TagEditField, string>;
+
+function fieldText(song: Song, field: TagEditField): string {
+  const value = song[field];
+  return value === undefined ? "" : String(value);
+}
+
+// Form values for the songs being edited. Fields that differ between songs
+// are left empty and listed in `mixed`
+export function getTagFormValues(songs: Song[]): {
+  values: TagFormValues;
+  mixed: Set<TagEditField>;
+} {
+  const values = {} as TagFormValues;
+  const mixed = new Set<TagEditField>();
+
+  for (const { field } of TAG_EDIT_FIELDS) {
+    const texts = new Set(songs.map((song) => fieldText(song, field)));
+    if (texts.size > 1) {
+      mixed.add(field);
+      values[field] = "";
+    } else {
+      values[field] = texts.values().next().value ?? "";
+    }
+  }
+  return { values, mixed };
+}
+
+export function isTagNumberField(
+  field: TagEditField,
+): field is TagNumberField {
+  return field === "trackNumber" || field === "discNumber" || field === "year";
+}
+
+// Parse a number field; "" clears it. Undefined for anything that isn't a
+// whole number in range
+export function parseTagNumber(
+  field: TagNumberField,
+  text: string,
+): number | undefined {
+  const trimmed = text.trim();
+  if (trimmed === "") return 0;
+  if (!/^\d+$/.test(trimmed)) return undefined;
+  const value = parseInt(trimmed, 10);
+  const max = field === "year" ? 9999 : 999;
+  return value <= max ? value : undefined;
+}
+
+// The edit for the fields the user changed. Invalid numbers are left out
+export function buildTagEdit(
+  values: TagFormValues,
+  changed: Iterable<TagEditField>,
+): TagEdit {
+  const edit: TagEdit = {};
+  for (const field of changed) {
+    if (isTagNumberField(field)) {
+      const value = parseTagNumber(field, values[field]);
+      if (value !== undefined) edit[field] = value;
+    } else {
+      edit[field] = values[field].trim();
+    }
+  }
+  return edit;
+}
+
+export function hasTagChanges(edit: TagEdit): boolean {
+  return Object.values(edit).some((value) => value !== undefined);
+}
+
+// Apply an edit to the library copy of a song. Cleared titles, artists and
+// albums fall back the same way they do on import. `artworkId` is the stored
+// copy of a new cover, used when the edit changes the artwork
+export function applyTagEdit(
+  song: Song,
+  edit: TagEdit,
+  artworkId?: string,
+): Song {
+  const updated: Song = { ...song };
+
+  if (edit.title !== undefined) {
+    updated.title =
+      edit.title.trim() ||
+      song.fileName?.replace(/\.[^/.]+$/, "") ||
+      song.title;
+  }
+  if (edit.artist !== undefined) {
+    updated.artist = edit.artist.trim() || "Unknown Artist";
+  }
+  if (edit.album !== undefined) {
+    updated.album = edit.album.trim() || "Unknown Album";
+  }
+  if (edit.albumArtist !== undefined) {
+    updated.albumArtist = edit.albumArtist.trim() || undefined;
+  }
+  if (edit.genre !== undefined) {
+    updated.genre = edit.genre.trim() || undefined;
+  }
+  for (const field of ["trackNumber", "discNumber", "year"] as const) {
+    if (edit[field] !== undefined) updated[field] = edit[field] || undefined;
+  }
+  if (edit.coverArt !== undefined) {
+    updated.artworkId = edit.coverArt ? artworkId : undefined;
+  }
+  return updated;
+}
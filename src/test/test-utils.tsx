@@ -80,12 +80,18 @@ export function createMockSettings(
     autoPlay: true,
     gaplessPlayback: false,
     crossfadeDuration: 0,
+    replayGainMode: 'off',
+    replayGainPreamp: 0,
+    replayGainPreventClipping: true,
+    preservePitch: true,
     defaultVolume: 0.7,
     rememberVolume: true,
     defaultShuffleMode: false,
     defaultRepeatMode: 'none',
     queueBehavior: 'replace',
     clearQueueOnNewPlaylist: true,
+    librarySort: 'addedAt',
+    librarySortDirection: 'desc',
     eqEnabled: true,
     eqPreset: 'Flat',
     displayMode: 'vinyl',
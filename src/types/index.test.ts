@@ -37,13 +37,13 @@ describe('Types', () => {
         duration: 180,
         sourceType: 'local',
         addedAt: Date.now(),
-        coverArt: 'data:image/png;base64,...',
+        artworkId: 'a1b2c3',
         fileName: 'test.mp3',
         fileSize: 1024,
         filePath: '/path/to/file.mp3',
       };
 
-      expect(song.coverArt).toBeDefined();
+      expect(song.artworkId).toBeDefined();
       expect(song.fileName).toBe('test.mp3');
     });
   });
@@ -142,18 +142,27 @@ describe('Types', () => {
         autoPlay: true,
         gaplessPlayback: false,
         crossfadeDuration: 0,
+        replayGainMode: 'off',
+        replayGainPreamp: 0,
+        replayGainPreventClipping: true,
+        preservePitch: true,
         defaultVolume: 0.7,
         rememberVolume: true,
         defaultShuffleMode: false,
         defaultRepeatMode: 'none',
         queueBehavior: 'replace',
         clearQueueOnNewPlaylist: true,
+        librarySort: 'addedAt',
+        librarySortDirection: 'desc',
         eqEnabled: true,
         eqPreset: 'Flat',
         displayMode: 'vinyl',
         visualizerEnabled: false,
         visualizerStyle: 'bars',
         skipDeleteConfirmation: false,
+        autoBackup: 'off',
+        autoBackupFolder: null,
+        lastAutoBackupAt: null,
       };
 
       expect(settings.theme).toBe('dark');
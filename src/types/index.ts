@@ -3,27 +3,206 @@ export interface Song {
   title: string;
   artist: string;
   album: string;
+  albumArtist?: string;
+  trackNumber?: number;
+  discNumber?: number;
+  genre?: string;
+  year?: number;
+  composer?: string;
+  bpm?: number;
   duration: number;
-  coverArt?: string;
+  // Stream properties: bitrate in kbps, sample rate in Hz
+  bitrate?: number;
+  sampleRate?: number;
+  bitDepth?: number;
+  channels?: number;
+  codec?: string;
+  // Content hash of the cover art in the artwork store
+  artworkId?: string;
   sourceType: "local";
   addedAt: number;
   // Original filename for matching when reconnecting (web)
   fileName?: string;
   // File size in bytes for matching
   fileSize?: number;
-  // Full file path for desktop apps (Tauri)
+  // Full file path for desktop apps (Electron/Tauri)
   // When present, we can load the file directly without user interaction
   filePath?: string;
+  // Web (File System Access API): the stored folder handle and the path inside
+  // it, so the file can be reopened after a reload without re-selecting it
+  folderHandleId?: string;
+  relativePath?: string;
+  // Loudness normalization, from tags or measured on desktop
+  replayGain?: ReplayGain;
+  // Where the music starts and stops, measured on desktop for crossfades
+  // (web songs are measured by the player when they come up instead)
+  audibleRange?: AudibleRange;
+  // Schema version of the tag fields above, see SONG_METADATA_VERSION
+  metadataVersion?: number;
+  // Aggregated from the plays store as listens are recorded
+  playCount?: number;
+  skipCount?: number;
+  lastPlayedAt?: number;
+}
+
+export type PlaySource =
+  | "library"
+  | "playlist"
+  | "album"
+  | "artist"
+  | "shuffle";
+
+// One listen of a song. Counts as a play once enough of it was heard (see
+// PLAY_THRESHOLD_SECONDS); anything shorter is a skip
+export interface PlayRecord {
+  id: string;
+  songId: string;
+  playedAt: number;
+  // Seconds actually heard, not counting seeks
+  listened: number;
+  outcome: "completed" | "skipped";
+  source: PlaySource;
+  // Playlist id or album key, when the source has one
+  sourceId?: string;
+}
+
+// ReplayGain 2.0 values: gains in dB relative to -18 LUFS,
+// peaks as linear sample values (1.0 = full scale)
+export interface ReplayGain {
+  trackGain?: number;
+  trackPeak?: number;
+  albumGain?: number;
+  albumPeak?: number;
+}
+
+// Seconds of a track between its first and last audible sample
+export interface AudibleRange {
+  start: number;
+  end: number;
+}
+
+// Tag changes from the tag editor, for one song or many. Fields left out are
+// unchanged; an empty string or 0 clears the field
+export interface TagEdit {
+  title?: string;
+  artist?: string;
+  albumArtist?: string;
+  album?: string;
+  genre?: string;
+  trackNumber?: number;
+  discNumber?: number;
+  year?: number;
+  // New front cover as a data URL, or "" to remove the artwork
+  coverArt?: string;
+}
+
+// Cover art, stored once per distinct image and shared by every song that uses it
+export interface ArtworkRecord {
+  // SHA-256 of the original image bytes
+  id: string;
+  // Small JPEG for lists and grids
+  thumbnail: Blob;
+  // Original image, or a downscaled JPEG when it is larger than needed
+  full: Blob;
+  addedAt: number;
+}
+
+// A music folder the web app was granted access to, kept across reloads
+export interface FolderHandleRecord {
+  id: string;
+  name: string;
+  handle: FileSystemDirectoryHandle;
+  addedAt: number;
 }
 
 export interface Playlist {
   id: string;
   name: string;
+  // Manual playlists only; smart playlists are evaluated from their rules
   songIds: string[];
+  // Present on smart playlists
+  rules?: SmartPlaylistRules;
   createdAt: number;
   updatedAt: number;
 }
 
+export type SmartRuleField =
+  | "title"
+  | "artist"
+  | "album"
+  | "albumArtist"
+  | "genre"
+  | "composer"
+  | "year"
+  | "duration"
+  | "bpm"
+  | "addedAt"
+  | "playCount"
+  | "skipCount"
+  | "lastPlayedAt";
+
+export type SmartRuleOperator =
+  // Text fields
+  | "is"
+  | "isNot"
+  | "contains"
+  | "notContains"
+  | "in"
+  | "notIn"
+  // Numbers (duration in seconds)
+  | "gt"
+  | "lt"
+  | "between"
+  // Dates, value in days
+  | "inLast"
+  | "notInLast";
+
+export interface SmartRule {
+  field: SmartRuleField;
+  operator: SmartRuleOperator;
+  // A string, a number, a list for in/notIn, or a [min, max] pair for between
+  value: string | number | string[] | [number, number];
+}
+
+export type SmartSortKey =
+  | SongSortKey
+  | "playCount"
+  | "lastPlayedAt"
+  | "random";
+
+export interface SmartPlaylistRules {
+  match: "all" | "any";
+  rules: SmartRule[];
+  sortBy: SmartSortKey;
+  sortDirection: SortDirection;
+  limit?: { value: number; unit: "songs" | "minutes" | "hours" };
+  // Keeps "random" order stable between re-evaluations; changed to reshuffle
+  seed?: number;
+}
+
+// What a playback profile is attached to
+export type PlaybackProfileTarget =
+  | "song"
+  | "album"
+  | "artist"
+  | "playlist"
+  | "output";
+
+// EQ preset, speed and gain offset applied while a matching track plays
+export interface PlaybackProfile {
+  id: string;
+  target: PlaybackProfileTarget;
+  // Song id, album id, artist name, playlist id or output device name
+  key: string;
+  // Shown in Settings, e.g. the album title
+  label: string;
+  // Unset fields leave the player's own setting alone
+  eqPreset?: string;
+  speed?: number;
+  gain?: number; // dB
+  createdAt: number;
+}
+
 export interface PlayerState {
   currentSongId: string | null;
   position: number;
@@ -50,6 +229,21 @@ export type RepeatMode = "none" | "one" | "all";
 
 export type QueueBehavior = "replace" | "append" | "ask";
 
+export type ReplayGainMode = "off" | "track" | "album";
+
+export type SongSortKey =
+  | "addedAt"
+  | "title"
+  | "artist"
+  | "album"
+  | "year"
+  | "genre"
+  | "duration";
+
+export type SortDirection = "asc" | "desc";
+
+export type AutoBackupInterval = "off" | "daily" | "weekly";
+
 export interface AppSettings {
   // Appearance
   theme: Theme;
@@ -62,6 +256,10 @@ export interface AppSettings {
   autoPlay: boolean;
   gaplessPlayback: boolean;
   crossfadeDuration: number; // 0 = disabled, in seconds
+  replayGainMode: ReplayGainMode;
+  replayGainPreamp: number; // dB added to tagged gains
+  replayGainPreventClipping: boolean;
+  preservePitch: boolean; // Speed changes keep the key
   defaultVolume: number;
   rememberVolume: boolean;
   defaultShuffleMode: boolean;
@@ -71,6 +269,10 @@ export interface AppSettings {
   queueBehavior: QueueBehavior;
   clearQueueOnNewPlaylist: boolean;
 
+  // Library
+  librarySort: SongSortKey;
+  librarySortDirection: SortDirection;
+
   // Equalizer
   eqEnabled: boolean;
   eqPreset: string | null;
@@ -84,4 +286,9 @@ export interface AppSettings {
 
   // Confirmations
   skipDeleteConfirmation: boolean;
+
+  // Scheduled library backups (Desktop only)
+  autoBackup: AutoBackupInterval;
+  autoBackupFolder: string | null;
+  lastAutoBackupAt: number | null;
 }